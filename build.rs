@@ -0,0 +1,10 @@
+//! Compiles the gRPC definitions in `proto/`
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("task_service_descriptor.bin"))
+        .compile_protos(&["proto/task_service.proto"], &["proto"])?;
+    println!("cargo:rerun-if-changed=proto/task_service.proto");
+    Ok(())
+}
@@ -0,0 +1,599 @@
+//! Caching decorator for task repositories
+//!
+//! Wraps any domain `TaskRepository` and keeps recently read tasks in an
+//! in-memory TTL cache, saving a FalkorDB round trip on repeated lookups.
+//! Only single-task reads are cached; filtered queries always reach the
+//! inner repository.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{BoxStream, StreamExt};
+use moka::future::Cache;
+use std::collections::HashSet;
+use std::time::Duration;
+use tyl_errors::TylResult;
+
+use crate::config::CacheConfig;
+use crate::domain::{
+    AlertMetrics, ArchiveResult, AuditEntry, Bottleneck, BottleneckResolution, CrossProjectDependency, CustomPropertiesPatch,
+    DependencyPath, ExternalTicketLink, MergeOptions, NewTask, Project, ProjectSnapshot, ProjectWorkflowConfig, Tag, TagUsage, Task, TaskComment, TaskContext, TaskSearchResult,
+    TaskDependency, TaskFilter, TaskRepository, TaskStatus, TaskSubgraph, WipViolation, PageRequest, PagedResult, SortSpec,
+    TaskSearchQuery,
+};
+
+/// Repository decorator caching `find_task_by_id` results
+pub struct CachingTaskRepository<R: TaskRepository> {
+    inner: R,
+    tasks: Cache<String, Task>,
+}
+
+impl<R: TaskRepository> CachingTaskRepository<R> {
+    pub fn new(inner: R, config: CacheConfig) -> Self {
+        let tasks = Cache::builder()
+            .max_capacity(config.max_capacity)
+            .time_to_live(Duration::from_secs(config.task_ttl_seconds))
+            .build();
+
+        Self { inner, tasks }
+    }
+
+    /// Access the wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<R: TaskRepository + Send + Sync> TaskRepository for CachingTaskRepository<R> {
+    async fn save_task(&self, task: &Task) -> TylResult<()> {
+        self.inner.save_task(task).await?;
+        self.tasks.invalidate(&task.id).await;
+        Ok(())
+    }
+
+    async fn save_task_if_version(&self, task: &Task, expected_version: u64) -> TylResult<bool> {
+        let saved = self.inner.save_task_if_version(task, expected_version).await?;
+        self.tasks.invalidate(&task.id).await;
+        Ok(saved)
+    }
+
+    async fn save_tasks(&self, tasks: &[NewTask]) -> TylResult<()> {
+        self.inner.save_tasks(tasks).await?;
+        for new_task in tasks {
+            self.tasks.invalidate(&new_task.task.id).await;
+        }
+        Ok(())
+    }
+
+    async fn find_existing_task_ids(&self, ids: &[String]) -> TylResult<HashSet<String>> {
+        self.inner.find_existing_task_ids(ids).await
+    }
+
+    async fn find_tasks_by_ids(&self, ids: &[String]) -> TylResult<Vec<Task>> {
+        self.inner.find_tasks_by_ids(ids).await
+    }
+
+    async fn find_task_by_id(&self, id: &str) -> TylResult<Option<Task>> {
+        if let Some(task) = self.tasks.get(id).await {
+            return Ok(Some(task));
+        }
+
+        let task = self.inner.find_task_by_id(id).await?;
+        if let Some(ref task) = task {
+            self.tasks.insert(id.to_string(), task.clone()).await;
+        }
+        Ok(task)
+    }
+
+    async fn find_tasks_by_filter(&self, filter: &TaskFilter) -> TylResult<Vec<Task>> {
+        self.inner.find_tasks_by_filter(filter).await
+    }
+
+    async fn fulltext_search_tasks(&self, query: &str, context: Option<TaskContext>, limit: usize) -> TylResult<Vec<TaskSearchResult>> {
+        self.inner.fulltext_search_tasks(query, context, limit).await
+    }
+
+    async fn search_tasks_advanced(&self, query: &TaskSearchQuery, sort: &[SortSpec], page: &PageRequest) -> TylResult<PagedResult<Task>> {
+        self.inner.search_tasks_advanced(query, sort, page).await
+    }
+
+    async fn delete_task(&self, id: &str) -> TylResult<()> {
+        self.inner.delete_task(id).await?;
+        self.tasks.invalidate(id).await;
+        Ok(())
+    }
+
+    async fn patch_task_custom_properties(&self, task_id: &str, patch: &CustomPropertiesPatch, expected_version: Option<u64>) -> TylResult<Option<Task>> {
+        let task = self.inner.patch_task_custom_properties(task_id, patch, expected_version).await?;
+        self.tasks.invalidate(task_id).await;
+        Ok(task)
+    }
+
+    async fn save_dependency(&self, dependency: &TaskDependency) -> TylResult<()> {
+        self.inner.save_dependency(dependency).await
+    }
+
+    async fn delete_dependency(&self, dependency_id: &str) -> TylResult<()> {
+        self.inner.delete_dependency(dependency_id).await
+    }
+
+    async fn find_dependencies_by_task(&self, task_id: &str) -> TylResult<Vec<TaskDependency>> {
+        self.inner.find_dependencies_by_task(task_id).await
+    }
+
+    async fn find_deleted_dependencies(&self, task_id: &str, since: DateTime<Utc>) -> TylResult<Vec<TaskDependency>> {
+        self.inner.find_deleted_dependencies(task_id, since).await
+    }
+
+    async fn find_blocking_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_blocking_tasks(task_id).await
+    }
+
+    async fn find_prerequisite_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_prerequisite_tasks(task_id).await
+    }
+
+    async fn find_dependent_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_dependent_tasks(task_id).await
+    }
+
+    async fn find_task_subgraph(&self, task_id: &str, depth: u32) -> TylResult<TaskSubgraph> {
+        self.inner.find_task_subgraph(task_id, depth).await
+    }
+
+    async fn add_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
+        self.inner.add_parent_child_relationship(parent_id, child_id).await
+    }
+
+    async fn remove_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
+        self.inner.remove_parent_child_relationship(parent_id, child_id).await
+    }
+
+    async fn find_children(&self, parent_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_children(parent_id).await
+    }
+
+    async fn find_parent(&self, child_id: &str) -> TylResult<Option<Task>> {
+        self.inner.find_parent(child_id).await
+    }
+
+    async fn assign_user_to_task(&self, task_id: &str, user_id: &str, role: &str) -> TylResult<()> {
+        self.inner.assign_user_to_task(task_id, user_id, role).await
+    }
+
+    async fn unassign_user_from_task(&self, task_id: &str, user_id: &str) -> TylResult<()> {
+        self.inner.unassign_user_from_task(task_id, user_id).await
+    }
+
+    async fn find_assigned_tasks(&self, user_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_assigned_tasks(user_id).await
+    }
+
+    async fn reassign_user_tasks(&self, from_user_id: &str, to_user_id: Option<&str>, task_ids: &[String]) -> TylResult<()> {
+        self.inner.reassign_user_tasks(from_user_id, to_user_id, task_ids).await
+    }
+
+    async fn find_task_assignments(&self, task_id: &str) -> TylResult<Vec<(String, String)>> {
+        self.inner.find_task_assignments(task_id).await
+    }
+
+    async fn user_exists(&self, user_id: &str) -> TylResult<bool> {
+        self.inner.user_exists(user_id).await
+    }
+
+    async fn has_assignee(&self, task_id: &str) -> TylResult<bool> {
+        self.inner.has_assignee(task_id).await
+    }
+
+    async fn save_review_request(&self, task_id: &str, reviewer_id: &str, requested_at: DateTime<Utc>) -> TylResult<()> {
+        self.inner.save_review_request(task_id, reviewer_id, requested_at).await
+    }
+
+    async fn find_review_queue(&self, reviewer_id: Option<&str>, project_id: Option<&str>) -> TylResult<Vec<(Task, Option<DateTime<Utc>>)>> {
+        self.inner.find_review_queue(reviewer_id, project_id).await
+    }
+
+    async fn save_external_link(&self, link: &ExternalTicketLink) -> TylResult<()> {
+        self.inner.save_external_link(link).await
+    }
+
+    async fn find_external_links(&self, task_id: &str) -> TylResult<Vec<ExternalTicketLink>> {
+        self.inner.find_external_links(task_id).await
+    }
+
+    async fn save_comment(&self, comment: &TaskComment) -> TylResult<()> {
+        self.inner.save_comment(comment).await
+    }
+
+    async fn find_comment_by_id(&self, comment_id: &str) -> TylResult<Option<TaskComment>> {
+        self.inner.find_comment_by_id(comment_id).await
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> TylResult<()> {
+        self.inner.delete_comment(comment_id).await
+    }
+
+    async fn find_task_comments(&self, task_id: &str) -> TylResult<Vec<TaskComment>> {
+        self.inner.find_task_comments(task_id).await
+    }
+
+    async fn find_task_tags(&self, task_id: &str) -> TylResult<Vec<Tag>> {
+        self.inner.find_task_tags(task_id).await
+    }
+
+    async fn add_task_tag(&self, task_id: &str, tag: &Tag) -> TylResult<()> {
+        self.inner.add_task_tag(task_id, tag).await?;
+        self.tasks.invalidate(task_id).await;
+        Ok(())
+    }
+
+    async fn remove_task_tag(&self, task_id: &str, tag_name: &str) -> TylResult<()> {
+        self.inner.remove_task_tag(task_id, tag_name).await?;
+        self.tasks.invalidate(task_id).await;
+        Ok(())
+    }
+
+    async fn count_tag_usage(&self) -> TylResult<Vec<TagUsage>> {
+        self.inner.count_tag_usage().await
+    }
+
+    async fn save_project(&self, project: &Project) -> TylResult<()> {
+        self.inner.save_project(project).await
+    }
+
+    async fn add_task_to_project(&self, task_id: &str, project_id: &str) -> TylResult<()> {
+        self.inner.add_task_to_project(task_id, project_id).await
+    }
+
+    async fn find_project_tasks(&self, project_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_project_tasks(project_id).await
+    }
+
+    async fn find_task_project_id(&self, task_id: &str) -> TylResult<Option<String>> {
+        self.inner.find_task_project_id(task_id).await
+    }
+
+    async fn find_project_by_id(&self, project_id: &str) -> TylResult<Option<Project>> {
+        self.inner.find_project_by_id(project_id).await
+    }
+
+    async fn set_project_lead(&self, project_id: &str, user_id: &str) -> TylResult<()> {
+        self.inner.set_project_lead(project_id, user_id).await
+    }
+
+    async fn save_workflow_config(&self, config: &ProjectWorkflowConfig) -> TylResult<()> {
+        self.inner.save_workflow_config(config).await
+    }
+
+    async fn find_workflow_config(&self, project_id: &str) -> TylResult<Option<ProjectWorkflowConfig>> {
+        self.inner.find_workflow_config(project_id).await
+    }
+
+    async fn find_wip_limit_violations(&self, project_id: &str) -> TylResult<Vec<WipViolation>> {
+        self.inner.find_wip_limit_violations(project_id).await
+    }
+
+    async fn save_project_snapshot(&self, snapshot: &ProjectSnapshot) -> TylResult<()> {
+        self.inner.save_project_snapshot(snapshot).await
+    }
+
+    async fn find_project_snapshot(&self, snapshot_id: &str) -> TylResult<Option<ProjectSnapshot>> {
+        self.inner.find_project_snapshot(snapshot_id).await
+    }
+
+    async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
+        self.inner.calculate_completion_percentage(task_id).await
+    }
+
+    async fn find_critical_path(&self, project_id: &str) -> TylResult<Vec<Task>> {
+        self.inner.find_critical_path(project_id).await
+    }
+
+    async fn detect_circular_dependencies(&self) -> TylResult<Vec<Vec<String>>> {
+        self.inner.detect_circular_dependencies().await
+    }
+
+    async fn find_dependencies_between(&self, task_ids: &[String]) -> TylResult<Vec<TaskDependency>> {
+        self.inner.find_dependencies_between(task_ids).await
+    }
+
+    async fn find_dependencies_with_tasks(&self) -> TylResult<Vec<(TaskDependency, Task, Task)>> {
+        self.inner.find_dependencies_with_tasks().await
+    }
+
+    async fn find_dependency_edges(&self) -> TylResult<Vec<(String, String)>> {
+        self.inner.find_dependency_edges().await
+    }
+
+    async fn find_cross_project_dependencies(&self) -> TylResult<Vec<CrossProjectDependency>> {
+        self.inner.find_cross_project_dependencies().await
+    }
+
+    async fn delete_tasks(&self, task_ids: &[String]) -> TylResult<()> {
+        self.inner.delete_tasks(task_ids).await?;
+        for id in task_ids {
+            self.tasks.invalidate(id).await;
+        }
+        Ok(())
+    }
+
+    async fn update_tasks_status(&self, task_ids: &[String], status: TaskStatus) -> TylResult<()> {
+        self.inner.update_tasks_status(task_ids, status).await?;
+        for id in task_ids {
+            self.tasks.invalidate(id).await;
+        }
+        Ok(())
+    }
+
+    async fn merge_task_relationships(&self, source_id: &str, target_id: &str, options: &MergeOptions) -> TylResult<()> {
+        self.inner.merge_task_relationships(source_id, target_id, options).await
+    }
+
+    /// Not cached: an export reads every task once
+    fn export_tasks_streaming(&self, filter: &TaskFilter) -> BoxStream<'static, TylResult<Task>> {
+        self.inner.export_tasks_streaming(filter)
+    }
+
+    async fn archive_completed_tasks_before(&self, cutoff: DateTime<Utc>, batch_size: u32) -> TylResult<ArchiveResult> {
+        let result = self.inner.archive_completed_tasks_before(cutoff, batch_size).await?;
+        // Archived ids are not reported back, so drop everything
+        self.tasks.invalidate_all();
+        Ok(result)
+    }
+
+    async fn archive_task(&self, task_id: &str, archive_graph: &str) -> TylResult<()> {
+        self.inner.archive_task(task_id, archive_graph).await?;
+        self.tasks.invalidate(task_id).await;
+        Ok(())
+    }
+
+    async fn save_audit_entry(&self, entry: &AuditEntry) -> TylResult<()> {
+        self.inner.save_audit_entry(entry).await
+    }
+
+    async fn collect_alert_metrics(&self) -> TylResult<AlertMetrics> {
+        self.inner.collect_alert_metrics().await
+    }
+
+    async fn save_bottleneck_actions(&self, project_id: &str, bottleneck: &Bottleneck) -> TylResult<()> {
+        self.inner.save_bottleneck_actions(project_id, bottleneck).await
+    }
+
+    async fn save_bottleneck_resolution(&self, resolution: &BottleneckResolution) -> TylResult<()> {
+        self.inner.save_bottleneck_resolution(resolution).await
+    }
+
+    async fn find_bottleneck_resolutions(&self, bottleneck_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        self.inner.find_bottleneck_resolutions(bottleneck_id).await
+    }
+
+    async fn find_project_bottleneck_resolutions(&self, project_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        self.inner.find_project_bottleneck_resolutions(project_id).await
+    }
+
+    async fn count_daily_completions(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        self.inner.count_daily_completions(project_id, since).await
+    }
+
+    async fn count_daily_creations(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        self.inner.count_daily_creations(project_id, since).await
+    }
+
+    async fn find_completed_tasks_for_user(&self, user_id: &str, since: DateTime<Utc>) -> TylResult<Vec<Task>> {
+        self.inner.find_completed_tasks_for_user(user_id, since).await
+    }
+
+    async fn find_estimated_completed_tasks(
+        &self,
+        user_id: Option<&str>,
+        context: Option<TaskContext>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<Task>> {
+        self.inner.find_estimated_completed_tasks(user_id, context, since).await
+    }
+
+    async fn find_recurring_task_instances(
+        &self,
+        user_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<(String, Task)>> {
+        self.inner.find_recurring_task_instances(user_id, since).await
+    }
+
+    async fn find_dependency_chains(&self, project_id: &str, top_n: usize) -> TylResult<Vec<DependencyPath>> {
+        self.inner.find_dependency_chains(project_id, top_n).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskContext;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// In-memory repository counting reads of single tasks
+    #[derive(Default)]
+    struct CountingRepository {
+        tasks: Mutex<HashMap<String, Task>>,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TaskRepository for CountingRepository {
+        async fn save_task(&self, task: &Task) -> TylResult<()> {
+            self.tasks.lock().unwrap().insert(task.id.clone(), task.clone());
+            Ok(())
+        }
+        async fn save_task_if_version(&self, task: &Task, expected_version: u64) -> TylResult<bool> {
+            let mut stored = self.tasks.lock().unwrap();
+            if !stored.get(&task.id).is_some_and(|current| current.version == expected_version) {
+                return Ok(false);
+            }
+            stored.insert(task.id.clone(), task.clone());
+            Ok(true)
+        }
+        async fn save_tasks(&self, tasks: &[NewTask]) -> TylResult<()> {
+            let mut stored = self.tasks.lock().unwrap();
+            for new_task in tasks {
+                stored.insert(new_task.task.id.clone(), new_task.task.clone());
+            }
+            Ok(())
+        }
+        async fn find_existing_task_ids(&self, ids: &[String]) -> TylResult<HashSet<String>> {
+            let stored = self.tasks.lock().unwrap();
+            Ok(ids.iter().filter(|id| stored.contains_key(*id)).cloned().collect())
+        }
+        async fn find_tasks_by_ids(&self, ids: &[String]) -> TylResult<Vec<Task>> {
+            let stored = self.tasks.lock().unwrap();
+            Ok(ids.iter().filter_map(|id| stored.get(id).cloned()).collect())
+        }
+        async fn find_task_by_id(&self, id: &str) -> TylResult<Option<Task>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.tasks.lock().unwrap().get(id).cloned())
+        }
+        async fn find_tasks_by_filter(&self, _filter: &TaskFilter) -> TylResult<Vec<Task>> {
+            Ok(self.tasks.lock().unwrap().values().cloned().collect())
+        }
+        async fn fulltext_search_tasks(&self, _query: &str, _context: Option<TaskContext>, _limit: usize) -> TylResult<Vec<TaskSearchResult>> { Ok(vec![]) }
+        async fn search_tasks_advanced(&self, query: &TaskSearchQuery, sort: &[SortSpec], page: &PageRequest) -> TylResult<PagedResult<Task>> {
+            Ok(crate::domain::search_tasks_in_memory(self.tasks.lock().unwrap().values().cloned(), query, sort, page))
+        }
+        async fn delete_task(&self, id: &str) -> TylResult<()> {
+            self.tasks.lock().unwrap().remove(id);
+            Ok(())
+        }
+        async fn patch_task_custom_properties(&self, task_id: &str, patch: &CustomPropertiesPatch, _expected_version: Option<u64>) -> TylResult<Option<Task>> {
+            Ok(self.tasks.lock().unwrap().get_mut(task_id).map(|task| {
+                task.apply_custom_properties_patch(patch);
+                task.clone()
+            }))
+        }
+        async fn save_dependency(&self, _dependency: &TaskDependency) -> TylResult<()> { Ok(()) }
+        async fn delete_dependency(&self, _dependency_id: &str) -> TylResult<()> { Ok(()) }
+        async fn find_dependencies_by_task(&self, _task_id: &str) -> TylResult<Vec<TaskDependency>> { Ok(vec![]) }
+        async fn find_deleted_dependencies(&self, _task_id: &str, _since: DateTime<Utc>) -> TylResult<Vec<TaskDependency>> { Ok(vec![]) }
+        async fn find_blocking_tasks(&self, _task_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_prerequisite_tasks(&self, _task_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_dependent_tasks(&self, _task_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_task_subgraph(&self, _task_id: &str, _depth: u32) -> TylResult<TaskSubgraph> { Ok(TaskSubgraph::default()) }
+        async fn add_parent_child_relationship(&self, _parent_id: &str, _child_id: &str) -> TylResult<()> { Ok(()) }
+        async fn remove_parent_child_relationship(&self, _parent_id: &str, _child_id: &str) -> TylResult<()> { Ok(()) }
+        async fn find_children(&self, _parent_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_parent(&self, _child_id: &str) -> TylResult<Option<Task>> { Ok(None) }
+        async fn assign_user_to_task(&self, _task_id: &str, _user_id: &str, _role: &str) -> TylResult<()> { Ok(()) }
+        async fn unassign_user_from_task(&self, _task_id: &str, _user_id: &str) -> TylResult<()> { Ok(()) }
+        async fn find_assigned_tasks(&self, _user_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn reassign_user_tasks(&self, _from_user_id: &str, _to_user_id: Option<&str>, _task_ids: &[String]) -> TylResult<()> { Ok(()) }
+        async fn find_task_assignments(&self, _task_id: &str) -> TylResult<Vec<(String, String)>> { Ok(vec![]) }
+        async fn user_exists(&self, _user_id: &str) -> TylResult<bool> { Ok(false) }
+        async fn has_assignee(&self, _task_id: &str) -> TylResult<bool> { Ok(false) }
+        async fn save_review_request(&self, _task_id: &str, _reviewer_id: &str, _requested_at: DateTime<Utc>) -> TylResult<()> { Ok(()) }
+        async fn find_review_queue(&self, _reviewer_id: Option<&str>, _project_id: Option<&str>) -> TylResult<Vec<(Task, Option<DateTime<Utc>>)>> { Ok(vec![]) }
+        async fn save_external_link(&self, _link: &ExternalTicketLink) -> TylResult<()> { Ok(()) }
+        async fn find_external_links(&self, _task_id: &str) -> TylResult<Vec<ExternalTicketLink>> { Ok(vec![]) }
+        async fn save_comment(&self, _comment: &TaskComment) -> TylResult<()> { Ok(()) }
+        async fn find_comment_by_id(&self, _comment_id: &str) -> TylResult<Option<TaskComment>> { Ok(None) }
+        async fn delete_comment(&self, _comment_id: &str) -> TylResult<()> { Ok(()) }
+        async fn find_task_comments(&self, _task_id: &str) -> TylResult<Vec<TaskComment>> { Ok(vec![]) }
+        async fn find_task_tags(&self, _task_id: &str) -> TylResult<Vec<Tag>> { Ok(vec![]) }
+        async fn add_task_tag(&self, _task_id: &str, _tag: &Tag) -> TylResult<()> { Ok(()) }
+        async fn remove_task_tag(&self, _task_id: &str, _tag_name: &str) -> TylResult<()> { Ok(()) }
+        async fn count_tag_usage(&self) -> TylResult<Vec<TagUsage>> { Ok(vec![]) }
+        async fn save_project(&self, _project: &Project) -> TylResult<()> { Ok(()) }
+        async fn add_task_to_project(&self, _task_id: &str, _project_id: &str) -> TylResult<()> { Ok(()) }
+        async fn find_project_tasks(&self, _project_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_task_project_id(&self, _task_id: &str) -> TylResult<Option<String>> { Ok(None) }
+        async fn find_project_by_id(&self, _project_id: &str) -> TylResult<Option<Project>> { Ok(None) }
+        async fn set_project_lead(&self, _project_id: &str, _user_id: &str) -> TylResult<()> { Ok(()) }
+        async fn save_workflow_config(&self, _config: &ProjectWorkflowConfig) -> TylResult<()> { Ok(()) }
+        async fn find_workflow_config(&self, _project_id: &str) -> TylResult<Option<ProjectWorkflowConfig>> { Ok(None) }
+        async fn find_wip_limit_violations(&self, _project_id: &str) -> TylResult<Vec<WipViolation>> { Ok(vec![]) }
+        async fn save_project_snapshot(&self, _snapshot: &ProjectSnapshot) -> TylResult<()> { Ok(()) }
+        async fn find_project_snapshot(&self, _snapshot_id: &str) -> TylResult<Option<ProjectSnapshot>> { Ok(None) }
+        async fn calculate_completion_percentage(&self, _task_id: &str) -> TylResult<f64> { Ok(0.0) }
+        async fn find_critical_path(&self, _project_id: &str) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn detect_circular_dependencies(&self) -> TylResult<Vec<Vec<String>>> { Ok(vec![]) }
+        async fn find_dependencies_between(&self, _task_ids: &[String]) -> TylResult<Vec<TaskDependency>> { Ok(vec![]) }
+        async fn find_dependencies_with_tasks(&self) -> TylResult<Vec<(TaskDependency, Task, Task)>> { Ok(vec![]) }
+        async fn find_dependency_edges(&self) -> TylResult<Vec<(String, String)>> { Ok(vec![]) }
+        async fn find_cross_project_dependencies(&self) -> TylResult<Vec<CrossProjectDependency>> { Ok(vec![]) }
+        async fn archive_completed_tasks_before(&self, _cutoff: DateTime<Utc>, _batch_size: u32) -> TylResult<ArchiveResult> { Ok(ArchiveResult::default()) }
+        async fn archive_task(&self, _task_id: &str, _archive_graph: &str) -> TylResult<()> { Ok(()) }
+        async fn delete_tasks(&self, task_ids: &[String]) -> TylResult<()> {
+            let mut tasks = self.tasks.lock().unwrap();
+            for id in task_ids {
+                tasks.remove(id);
+            }
+            Ok(())
+        }
+        async fn update_tasks_status(&self, task_ids: &[String], status: TaskStatus) -> TylResult<()> {
+            let mut tasks = self.tasks.lock().unwrap();
+            for id in task_ids {
+                if let Some(task) = tasks.get_mut(id) {
+                    task.set_status(status);
+                }
+            }
+            Ok(())
+        }
+        async fn merge_task_relationships(&self, _source_id: &str, _target_id: &str, _options: &MergeOptions) -> TylResult<()> { Ok(()) }
+        fn export_tasks_streaming(&self, _filter: &TaskFilter) -> BoxStream<'static, TylResult<Task>> {
+            futures::stream::empty().boxed()
+        }
+        async fn save_audit_entry(&self, _entry: &AuditEntry) -> TylResult<()> { Ok(()) }
+        async fn collect_alert_metrics(&self) -> TylResult<AlertMetrics> { Ok(AlertMetrics::default()) }
+        async fn save_bottleneck_actions(&self, _project_id: &str, _bottleneck: &Bottleneck) -> TylResult<()> { Ok(()) }
+        async fn save_bottleneck_resolution(&self, _resolution: &BottleneckResolution) -> TylResult<()> { Ok(()) }
+        async fn find_bottleneck_resolutions(&self, _bottleneck_id: &str) -> TylResult<Vec<BottleneckResolution>> { Ok(vec![]) }
+        async fn find_project_bottleneck_resolutions(&self, _project_id: &str) -> TylResult<Vec<BottleneckResolution>> { Ok(vec![]) }
+        async fn count_daily_completions(&self, _project_id: &str, _since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> { Ok(vec![]) }
+        async fn count_daily_creations(&self, _project_id: &str, _since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> { Ok(vec![]) }
+        async fn find_completed_tasks_for_user(&self, _user_id: &str, _since: DateTime<Utc>) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_estimated_completed_tasks(&self, _user_id: Option<&str>, _context: Option<TaskContext>, _since: DateTime<Utc>) -> TylResult<Vec<Task>> { Ok(vec![]) }
+        async fn find_recurring_task_instances(&self, _user_id: Option<&str>, _since: DateTime<Utc>) -> TylResult<Vec<(String, Task)>> { Ok(vec![]) }
+        async fn find_dependency_chains(&self, _project_id: &str, _top_n: usize) -> TylResult<Vec<DependencyPath>> { Ok(vec![]) }
+    }
+
+    fn task(id: &str, name: &str) -> Task {
+        Task::new(id.to_string(), name.to_string(), TaskContext::Work)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_reads_hit_cache() {
+        let repo = CachingTaskRepository::new(CountingRepository::default(), CacheConfig::default());
+        repo.save_task(&task("T1", "Cached")).await.unwrap();
+
+        for _ in 0..3 {
+            let found = repo.find_task_by_id("T1").await.unwrap();
+            assert_eq!(found.unwrap().name, "Cached");
+        }
+
+        assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_save_and_delete_invalidate_cache() {
+        let repo = CachingTaskRepository::new(CountingRepository::default(), CacheConfig::default());
+        repo.save_task(&task("T1", "Original")).await.unwrap();
+        repo.find_task_by_id("T1").await.unwrap();
+
+        repo.save_task(&task("T1", "Renamed")).await.unwrap();
+        let found = repo.find_task_by_id("T1").await.unwrap().unwrap();
+        assert_eq!(found.name, "Renamed");
+        assert_eq!(repo.inner().reads.load(Ordering::SeqCst), 2);
+
+        repo.delete_task("T1").await.unwrap();
+        assert!(repo.find_task_by_id("T1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_tasks_are_not_cached() {
+        let repo = CachingTaskRepository::new(CountingRepository::default(), CacheConfig::default());
+
+        assert!(repo.find_task_by_id("T1").await.unwrap().is_none());
+        repo.inner().save_task(&task("T1", "Late")).await.unwrap();
+
+        assert!(repo.find_task_by_id("T1").await.unwrap().is_some());
+    }
+}
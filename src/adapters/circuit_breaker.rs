@@ -0,0 +1,194 @@
+//! Circuit breaker for FalkorDB calls
+//!
+//! The breaker starts `Closed` and counts consecutive connection failures.
+//! Once `failure_threshold` is reached it turns `Open` and fails every call
+//! without touching the database. After `recovery_timeout` it turns
+//! `HalfOpen` and lets calls through again: the first success closes it, the
+//! first failure opens it for another `recovery_timeout`.
+//!
+//! Its state is mirrored in the shared health flag read by the readiness
+//! probe and in the `db_circuit_breaker_state` gauge.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tyl_errors::TylResult;
+
+use crate::config::DatabaseConfig;
+use crate::{telemetry, TaskServiceError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Value of the `db_circuit_breaker_state` gauge
+    pub fn gauge_value(self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive connection failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before calls are attempted again
+    pub recovery_timeout: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            recovery_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl From<&DatabaseConfig> for CircuitBreakerConfig {
+    fn from(config: &DatabaseConfig) -> Self {
+        Self {
+            failure_threshold: config.circuit_breaker_failure_threshold,
+            recovery_timeout: Duration::from_secs(config.circuit_breaker_timeout_seconds),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Circuit breaker tracking consecutive connection failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+    healthy: Arc<AtomicBool>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig, healthy: Arc<AtomicBool>) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState { state: CircuitState::Closed, consecutive_failures: 0, opened_at: None }),
+            healthy,
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Fail fast while the circuit is open; once `recovery_timeout` has
+    /// passed the circuit turns half-open and calls are let through
+    pub fn check(&self) -> TylResult<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.state == CircuitState::Open {
+            let recovered = state.opened_at.is_some_and(|at| at.elapsed() >= self.config.recovery_timeout);
+            if !recovered {
+                return Err(TaskServiceError::Database { message: "Circuit breaker open".to_string() }.into());
+            }
+            self.transition(&mut state, CircuitState::HalfOpen);
+        }
+        Ok(())
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+        self.transition(&mut state, CircuitState::Closed);
+        self.healthy.store(true, Ordering::Relaxed);
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        // A failed trial call reopens the circuit straight away
+        if state.state == CircuitState::HalfOpen || state.consecutive_failures >= self.config.failure_threshold {
+            state.opened_at = Some(Instant::now());
+            self.transition(&mut state, CircuitState::Open);
+            self.healthy.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Current state, without letting the recovery timeout move it on
+    pub fn state(&self) -> CircuitState {
+        self.state.lock().unwrap().state
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.check().is_err()
+    }
+
+    fn transition(&self, state: &mut BreakerState, to: CircuitState) {
+        if state.state != to {
+            tracing::info!(from = ?state.state, to = ?to, "Database circuit breaker changed state");
+            state.state = to;
+        }
+        telemetry::record_db_circuit_state(to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(recovery_timeout: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            CircuitBreakerConfig { failure_threshold: 2, recovery_timeout },
+            Arc::new(AtomicBool::new(true)),
+        )
+    }
+
+    #[test]
+    fn test_opens_after_threshold_and_fails_fast() {
+        let breaker = breaker(Duration::from_secs(60));
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.check().is_ok());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.consecutive_failures(), 2);
+        assert!(breaker.check().unwrap_err().to_string().contains("Circuit breaker open"));
+        assert!(!breaker.healthy.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_half_open_after_recovery_timeout() {
+        let breaker = breaker(Duration::ZERO);
+        breaker.record_failure();
+        breaker.record_failure();
+
+        assert!(breaker.check().is_ok());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // A failed trial reopens the circuit, a successful one closes it
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.check().is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+        assert!(breaker.healthy.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,144 @@
+//! Cypher query parameters
+//!
+//! Values reach FalkorDB as query parameters instead of being pasted into the
+//! query text: queries refer to them as `$name`, and [`bind_params`] prepends
+//! them in FalkorDB's `CYPHER name=value ...` header, encoding every value as a
+//! Cypher literal. Query builders return their query together with its
+//! [`CypherParams`] for [`ExecuteCypherWithParams::execute_cypher_with_params`].
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tyl_errors::{TylError, TylResult};
+use tyl_falkordb_adapter::FalkorDBAdapter;
+
+/// Values of the `$name` placeholders of a query, by name
+pub type CypherParams = HashMap<String, Value>;
+
+/// Parameters from `(name, value)` pairs, e.g. `cypher_params([("task_id", json!(id))])`
+pub fn cypher_params<'a>(pairs: impl IntoIterator<Item = (&'a str, Value)>) -> CypherParams {
+    pairs.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+}
+
+/// `query` preceded by the `CYPHER` header binding `params`
+pub fn bind_params(query: &str, params: &CypherParams) -> TylResult<String> {
+    if params.is_empty() {
+        return Ok(query.to_string());
+    }
+    // Sorted so the same parameters always give the same query text
+    let sorted: BTreeMap<_, _> = params.iter().collect();
+    let mut bindings = Vec::with_capacity(sorted.len());
+    for (name, value) in sorted {
+        if !is_identifier(name) {
+            return Err(TylError::validation("cypher", format!("Invalid query parameter name '{}'", name)));
+        }
+        bindings.push(format!("{}={}", name, cypher_literal(value)));
+    }
+    Ok(format!("CYPHER {} {}", bindings.join(" "), query))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `value` written as a Cypher literal, map keys quoted with backticks
+fn cypher_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => {
+            let mut literal = String::with_capacity(s.len() + 2);
+            literal.push('"');
+            for c in s.chars() {
+                match c {
+                    '\\' => literal.push_str("\\\\"),
+                    '"' => literal.push_str("\\\""),
+                    '\n' => literal.push_str("\\n"),
+                    '\r' => literal.push_str("\\r"),
+                    '\t' => literal.push_str("\\t"),
+                    c => literal.push(c),
+                }
+            }
+            literal.push('"');
+            literal
+        }
+        Value::Array(items) => format!(
+            "[{}]",
+            items.iter().map(cypher_literal).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(fields) => format!(
+            "{{{}}}",
+            fields.iter()
+                .map(|(key, value)| format!("`{}`: {}", key.replace('`', ""), cypher_literal(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Run queries with parameters on a FalkorDB adapter
+#[async_trait]
+pub trait ExecuteCypherWithParams {
+    async fn execute_cypher_with_params(&self, query: &str, params: CypherParams) -> TylResult<Value>;
+}
+
+#[async_trait]
+impl ExecuteCypherWithParams for FalkorDBAdapter {
+    async fn execute_cypher_with_params(&self, query: &str, params: CypherParams) -> TylResult<Value> {
+        self.execute_cypher(&bind_params(query, &params)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bind_params_encodes_values_as_literals() {
+        let params = cypher_params([
+            ("task_id", json!("x' OR 1=1 //\"\\")),
+            ("limit", json!(10)),
+            ("ids", json!(["a", "b"])),
+            ("props", json!({ "done": true, "due-soon": null })),
+        ]);
+        assert_eq!(
+            bind_params("MATCH (t:Task {id: $task_id}) RETURN t", &params).unwrap(),
+            "CYPHER ids=[\"a\", \"b\"] limit=10 props={`done`: true, `due-soon`: null} task_id=\"x' OR 1=1 //\\\"\\\\\" \
+             MATCH (t:Task {id: $task_id}) RETURN t"
+        );
+        assert_eq!(bind_params("RETURN 1", &CypherParams::new()).unwrap(), "RETURN 1");
+
+        assert!(bind_params("RETURN $x", &cypher_params([("x = 1 MATCH", json!(1))])).is_err());
+    }
+
+    /// Stands in for a lint: values must be bound, not escaped or quoted into the query text
+    #[test]
+    fn test_queries_do_not_escape_values_into_cypher() {
+        // A quoted placeholder after an operator, bracket or map key is a value formatted
+        // into Cypher; messages such as "Task '{}' not found" quote them after a word
+        let quoted_value = regex::Regex::new(r"(=|<|>|\[|\(|,|:|\bIN|\bCONTAINS)\s*'\{[^}']*\}'").unwrap();
+        // Split so this file doesn't match its own pattern
+        let escaped_value = ["replace('", "\\", "''"].concat();
+        let mut dirs = vec![std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension() != Some("rs".as_ref()) {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).unwrap();
+                if let Some(line) = source.lines().position(|line| quoted_value.is_match(line) || line.contains(&escaped_value)) {
+                    panic!("{}:{} puts a value into a Cypher query, bind it as a parameter", path.display(), line + 1);
+                }
+            }
+        }
+    }
+}
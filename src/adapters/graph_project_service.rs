@@ -0,0 +1,148 @@
+//! FalkorDB-backed project service
+//!
+//! Projects are the `(:Project)` nodes the task repository already stores,
+//! with tasks linked through `BELONGS_TO_PROJECT`. The service reuses the
+//! repository's connection and project queries and adds the lifecycle ones.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+use tyl_errors::{TylError, TylResult};
+
+use super::cypher::cypher_params;
+use super::graph_repository::{project_from_row, GraphTaskRepository, PROJECT_COLUMNS};
+use crate::domain::{
+    transfer_ownership_in_repository, validate_create_project_request, CreateProjectRequest, OwnershipTransferResult,
+    Project, ProjectService, ProjectSummary, TaskRepository, UpdateProjectRequest, UserServicePort,
+    PROJECT_STATUS_ARCHIVED,
+};
+
+/// Project service on top of the graph task repository
+#[derive(Clone)]
+pub struct GraphProjectService {
+    repository: GraphTaskRepository,
+    user_service: Option<Arc<dyn UserServicePort + Send + Sync>>,
+}
+
+impl GraphProjectService {
+    pub fn new(repository: GraphTaskRepository) -> Self {
+        Self { repository, user_service: None }
+    }
+
+    /// Only make users `user_service` knows project leads
+    pub fn with_user_service(mut self, user_service: Arc<dyn UserServicePort + Send + Sync>) -> Self {
+        self.user_service = Some(user_service);
+        self
+    }
+
+    async fn find_existing_project(&self, project_id: &str) -> TylResult<Project> {
+        self.repository.find_project_by_id(project_id).await?
+            .ok_or_else(|| TylError::not_found("project", project_id))
+    }
+
+    /// Set properties of the project node, moving `updated_at` on
+    async fn set_project_properties(&self, project_id: &str, mut properties: serde_json::Map<String, serde_json::Value>) -> TylResult<Project> {
+        properties.insert("updated_at".to_string(), json!(Utc::now().to_rfc3339()));
+        let query = format!("MATCH (p:Project {{id: $project_id}}) SET p += $properties RETURN {}", PROJECT_COLUMNS);
+        let params = cypher_params([("project_id", json!(project_id)), ("properties", serde_json::Value::Object(properties))]);
+        let result = self.repository.execute_cypher_with_params(&query, params).await?;
+
+        result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(project_from_row)
+            .ok_or_else(|| TylError::not_found("project", project_id))
+    }
+}
+
+#[async_trait]
+impl ProjectService for GraphProjectService {
+    async fn create_project(&self, request: CreateProjectRequest) -> TylResult<Project> {
+        validate_create_project_request(&request)?;
+        if self.repository.find_project_by_id(&request.id).await?.is_some() {
+            return Err(TylError::validation("id", format!("Project '{}' already exists", request.id)));
+        }
+
+        let project = Project::from(request);
+        self.repository.save_project(&project).await?;
+        Ok(project)
+    }
+
+    async fn get_project(&self, project_id: &str) -> TylResult<Option<Project>> {
+        self.repository.find_project_by_id(project_id).await
+    }
+
+    async fn update_project(&self, project_id: &str, request: UpdateProjectRequest) -> TylResult<Project> {
+        let mut properties = serde_json::Map::new();
+        if let Some(name) = request.name {
+            if name.trim().is_empty() {
+                return Err(TylError::validation("name", "Project name cannot be empty"));
+            }
+            properties.insert("name".to_string(), json!(name));
+        }
+        if let Some(description) = request.description {
+            properties.insert("description".to_string(), json!(description));
+        }
+        if let Some(start_date) = request.start_date {
+            properties.insert("start_date".to_string(), json!(start_date.to_rfc3339()));
+        }
+        if let Some(end_date) = request.end_date {
+            properties.insert("end_date".to_string(), json!(end_date.to_rfc3339()));
+        }
+
+        self.set_project_properties(project_id, properties).await
+    }
+
+    async fn archive_project(&self, project_id: &str) -> TylResult<Project> {
+        let mut properties = serde_json::Map::new();
+        properties.insert("status".to_string(), json!(PROJECT_STATUS_ARCHIVED));
+        self.set_project_properties(project_id, properties).await
+    }
+
+    async fn delete_project(&self, project_id: &str) -> TylResult<()> {
+        self.find_existing_project(project_id).await?;
+        let task_count = self.repository.find_project_tasks(project_id).await?.len();
+        if task_count > 0 {
+            return Err(TylError::validation(
+                "project_id",
+                format!("Project {} still has {} tasks. Move or delete them first.", project_id, task_count)
+            ));
+        }
+
+        let query = "MATCH (p:Project {id: $project_id}) DETACH DELETE p";
+        self.repository.execute_cypher_with_params(query, cypher_params([("project_id", json!(project_id))])).await?;
+        Ok(())
+    }
+
+    async fn list_projects(&self, include_archived: bool) -> TylResult<Vec<Project>> {
+        let filter = if include_archived { "" } else { "WHERE coalesce(p.status, '') <> $archived" };
+        let query = format!("MATCH (p:Project) {} RETURN {} ORDER BY p.name, p.id", filter, PROJECT_COLUMNS);
+        let params = cypher_params([("archived", json!(PROJECT_STATUS_ARCHIVED))]);
+        let result = self.repository.execute_cypher_with_params(&query, params).await?;
+
+        Ok(result.as_array()
+            .map(|rows| rows.iter().filter_map(project_from_row).collect())
+            .unwrap_or_default())
+    }
+
+    async fn add_task_to_project(&self, task_id: &str, project_id: &str) -> TylResult<()> {
+        if self.repository.find_task_by_id(task_id).await?.is_none() {
+            return Err(TylError::not_found("task", task_id));
+        }
+        self.find_existing_project(project_id).await?;
+
+        self.repository.add_task_to_project(task_id, project_id).await
+    }
+
+    async fn get_project_summary(&self, project_id: &str) -> TylResult<ProjectSummary> {
+        self.find_existing_project(project_id).await?;
+        let tasks = self.repository.find_project_tasks(project_id).await?;
+
+        Ok(ProjectSummary::from_tasks(project_id, &tasks, Utc::now()))
+    }
+
+    async fn transfer_project_ownership(&self, project_id: &str, new_owner_id: &str, reason: String) -> TylResult<OwnershipTransferResult> {
+        transfer_ownership_in_repository(&self.repository, self.user_service.as_deref(), project_id, new_owner_id, reason).await
+    }
+}
@@ -4,9 +4,10 @@
 //! providing graph database operations for the task management system.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tyl_errors::{TylError, TylResult};
 use tyl_falkordb_adapter::{FalkorDBAdapter, GraphNode as FalkorNode, GraphRelationship as FalkorRel};
@@ -14,23 +15,147 @@ use tyl_graph_port::{
     GraphStore, GraphTraversal, GraphAnalytics, TraversalDirection, TraversalParams, CentralityType,
 };
 
+use super::cypher::{cypher_params, CypherParams};
+use super::retrying_adapter::{ConnectionRetryPolicy, RetryingFalkorDBAdapter};
+use crate::telemetry;
 use crate::domain::{
-    TaskRepository, Task, TaskDependency, TaskFilter, Project, TaskStatus, TaskPriority, 
-    TaskContext, TaskComplexity, TaskSource, TaskVisibility, DependencyType
+    AlertMetrics, ArchiveResult, AuditEntry, Bottleneck, BottleneckResolution, CrossProjectDependency, DependencyPath,
+    ExternalSystem, ExternalTicketLink, SyncStatus, Tag, TagUsage, TaskRepository, Task, TaskComment, TaskDependency, TaskFilter, Project, TaskStatus, TaskPriority, 
+    TaskContext, TaskComplexity, TaskSource, TaskVisibility, DependencyType, ProjectSnapshot, ProjectWorkflowConfig, TaskCursor, WipViolation,
+    TaskSearchResult, CustomPropertiesPatch, MergeOptions, NewTask, fulltext_search_clause,
+    PageRequest, PagedResult, SortSpec, TaskSearchQuery, search_order_clause,
+    TaskSubgraph, task_subgraph_from_rows, task_subgraph_queries,
 };
 
+/// `RETURN` columns of a project `p`, read by `project_from_row`
+pub(crate) const PROJECT_COLUMNS: &str = "p.id AS id, p.code AS code, p.name AS name, p.description AS description, \
+     p.status AS status, p.start_date AS start_date, p.end_date AS end_date, \
+     p.project_lead_user_id AS project_lead_user_id, p.created_at AS created_at, p.updated_at AS updated_at";
+
+/// `RETURN` columns of a comment `c` on task `t`, read by `parse_comments`
+const COMMENT_COLUMNS: &str = "c.id AS id, t.id AS task_id, c.author_id AS author_id, c.content AS body, \
+     c.edited AS edited, c.created_at AS created_at, c.updated_at AS updated_at";
+
 /// Graph-based repository implementation using FalkorDB
+#[derive(Clone)]
 pub struct GraphTaskRepository {
-    adapter: Arc<FalkorDBAdapter>,
+    adapter: Arc<RetryingFalkorDBAdapter>,
     graph_name: String,
+    /// Adapters of the graphs `archive_task` can move tasks to, by graph name
+    archive_graphs: HashMap<String, Arc<RetryingFalkorDBAdapter>>,
 }
 
 impl GraphTaskRepository {
     pub fn new(adapter: FalkorDBAdapter, graph_name: String) -> Self {
+        Self::with_adapter(RetryingFalkorDBAdapter::new(adapter, ConnectionRetryPolicy::default()), graph_name)
+    }
+    
+    /// Create the repository on top of an adapter with a configured retry policy
+    pub fn with_adapter(adapter: RetryingFalkorDBAdapter, graph_name: String) -> Self {
         Self {
             adapter: Arc::new(adapter),
             graph_name,
+            archive_graphs: HashMap::new(),
+        }
+    }
+    
+    /// Allow `archive_task` to move tasks to `graph_name` through `adapter`
+    pub fn with_archive_graph(mut self, graph_name: String, adapter: RetryingFalkorDBAdapter) -> Self {
+        self.archive_graphs.insert(graph_name, Arc::new(adapter));
+        self
+    }
+    
+    /// Comments from rows returned with [`COMMENT_COLUMNS`]
+    fn parse_comments(result: &serde_json::Value) -> Vec<TaskComment> {
+        let date = |row: &serde_json::Value, key: &str| row.get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let created_at = date(row, "created_at")?;
+                    Some(TaskComment {
+                        id: row.get("id")?.as_str()?.to_string(),
+                        task_id: row.get("task_id")?.as_str()?.to_string(),
+                        author_id: row.get("author_id")?.as_str()?.to_string(),
+                        body: row.get("body")?.as_str()?.to_string(),
+                        edited: row.get("edited").and_then(|v| v.as_bool()).unwrap_or(false),
+                        created_at,
+                        updated_at: date(row, "updated_at").unwrap_or(created_at),
+                    })
+                })
+                .collect())
+            .unwrap_or_default()
+    }
+
+    /// Run a Cypher query, recording its duration by query type
+    pub(crate) async fn execute_cypher(&self, query: &str) -> TylResult<serde_json::Value> {
+        telemetry::time_graph_query(telemetry::cypher_query_type(query), self.adapter.execute_cypher(query)).await
+    }
+    
+    /// Run a Cypher query with its `$name` parameters, recording its duration by query type
+    pub(crate) async fn execute_cypher_with_params(&self, query: &str, params: CypherParams) -> TylResult<serde_json::Value> {
+        telemetry::time_graph_query(
+            telemetry::cypher_query_type(query),
+            self.adapter.execute_cypher_with_params(query, params),
+        ).await
+    }
+    
+    /// Create the indexes the task queries rely on
+    ///
+    /// Safe to run on every startup: statements that fail because the index
+    /// already exists are skipped. FalkorDB only creates unique constraints
+    /// through `GRAPH.CONSTRAINT CREATE`, which the graph adapter does not
+    /// expose, so task ids are indexed but their uniqueness is not enforced.
+    pub async fn ensure_schema(&self) -> TylResult<()> {
+        self.create_fulltext_index().await?;
+        
+        const SCHEMA_STATEMENTS: &[&str] = &[
+            "CREATE INDEX ON :Task(id)",
+            "CREATE INDEX ON :Task(status)",
+            "CREATE INDEX ON :Task(due_date)",
+            "CREATE INDEX ON :Task(updated_at)",
+            "CREATE INDEX ON :Task(tenant_id)",
+        ];
+        
+        for statement in SCHEMA_STATEMENTS {
+            if let Err(e) = self.execute_cypher(statement).await {
+                if e.to_string().to_lowercase().contains("already") {
+                    tracing::debug!("Schema statement skipped on graph '{}', already exists: {}", self.graph_name, statement);
+                    continue;
+                }
+                return Err(e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Create the full-text index on the name and description of `Task` nodes
+    ///
+    /// FalkorDB names full-text indexes after the label, so searches query `'Task'`.
+    pub async fn create_fulltext_index(&self) -> TylResult<()> {
+        let statement = "CALL db.idx.fulltext.createNodeIndex('Task', 'name', 'description')";
+        if let Err(e) = self.execute_cypher(statement).await {
+            if e.to_string().to_lowercase().contains("already") {
+                tracing::debug!("Full-text index on graph '{}' already exists", self.graph_name);
+                return Ok(());
+            }
+            return Err(e);
         }
+        Ok(())
+    }
+    
+    /// Properties and `SET` label items for writing `task` over a matched node `t`
+    fn task_write_parts(&self, task: &Task) -> TylResult<(serde_json::Value, String)> {
+        let mut node = self.task_to_graph_node(task)?;
+        node.properties.remove("version");
+        let labels = node.labels.iter()
+            .map(|label| format!("t:{}", label))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok((serde_json::Value::Object(node.properties.into_iter().collect()), labels))
     }
     
     /// Convert domain Task to graph node
@@ -85,12 +210,28 @@ impl GraphTaskRepository {
         if !task.attachments.is_empty() {
             properties.insert("attachments".to_string(), json!(task.attachments));
         }
+        if !task.tags.is_empty() {
+            properties.insert("tags".to_string(), json!(task.tags));
+        }
+        properties.insert("version".to_string(), json!(task.version));
+        if let Some(ref created_by) = task.created_by {
+            properties.insert("created_by".to_string(), json!(created_by));
+        }
+        if let Some(ref parent_context) = task.parent_context {
+            properties.insert("parent_context".to_string(), json!(parent_context));
+        }
         
         // Custom properties
         for (key, value) in &task.custom_properties {
             properties.insert(format!("custom_{}", key), value.clone());
         }
         
+        // Priority churn
+        properties.insert("priority_change_count".to_string(), json!(task.priority_change_count));
+        if let Some(ref last_change) = task.last_priority_change_at {
+            properties.insert("last_priority_change_at".to_string(), json!(last_change.to_rfc3339()));
+        }
+        
         let mut node = FalkorNode::new(task.id.clone());
         node.labels = vec!["Task".to_string(), format!("Task_{:?}", task.context)];
         node.properties = properties;
@@ -189,6 +330,8 @@ impl GraphTaskRepository {
         let started_at = parse_date("started_at");
         let completed_at = parse_date("completed_at");
         
+        let last_priority_change_at = parse_date("last_priority_change_at");
+        
         let created_at = parse_date("created_at")
             .unwrap_or_else(Utc::now);
         let updated_at = parse_date("updated_at")
@@ -221,6 +364,25 @@ impl GraphTaskRepository {
                 .ok())
             .unwrap_or_default();
         
+        let version = properties.get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        
+        let tags = properties.get("tags")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        
+        let created_by = properties.get("created_by")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        
+        let parent_context = properties.get("parent_context")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        
+        let priority_change_count = properties.get("priority_change_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
+        
         // Extract custom properties
         let mut custom_properties = HashMap::new();
         for (key, value) in properties {
@@ -253,6 +415,12 @@ impl GraphTaskRepository {
             visibility,
             attachments,
             custom_properties,
+            priority_change_count,
+            last_priority_change_at,
+            tags,
+            version,
+            created_by,
+            parent_context,
         })
     }
     
@@ -312,10 +480,15 @@ impl GraphTaskRepository {
             .map(|dt| dt.with_timezone(&Utc))
             .unwrap_or_else(Utc::now);
         
+        let deleted_at = properties.get("deleted_at")
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        
         // Extract additional properties
         let mut additional_properties = HashMap::new();
         for (key, value) in properties {
-            if !matches!(key.as_str(), "id" | "from_task_id" | "to_task_id" | "dependency_type" | "is_hard_dependency" | "delay_days" | "created_at") {
+            if !matches!(key.as_str(), "id" | "from_task_id" | "to_task_id" | "dependency_type" | "is_hard_dependency" | "delay_days" | "created_at" | "deleted_at") {
                 additional_properties.insert(key.clone(), value.clone());
             }
         }
@@ -328,6 +501,7 @@ impl GraphTaskRepository {
             is_hard_dependency,
             delay_days,
             created_at,
+            deleted_at,
             properties: additional_properties,
         })
     }
@@ -381,6 +555,15 @@ impl GraphTaskRepository {
         Ok(dependencies)
     }
     
+    /// Run a query returning a single numeric `value` column
+    async fn query_scalar(&self, query: &str, params: CypherParams) -> TylResult<Option<f64>> {
+        let result = self.execute_cypher_with_params(query, params).await?;
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("value"))
+            .and_then(|value| value.as_f64()))
+    }
+    
     /// Convert domain TaskDependency to graph relationship
     fn dependency_to_graph_relationship(&self, dependency: &TaskDependency) -> FalkorRel {
         let mut properties = HashMap::new();
@@ -405,91 +588,232 @@ impl GraphTaskRepository {
         }
     }
     
-    /// Build Cypher WHERE clause from TaskFilter
-    fn build_filter_clause(&self, filter: &TaskFilter) -> String {
+    /// Build Cypher WHERE clause from TaskFilter, with the parameters it refers to
+    fn build_filter_clause(&self, filter: &TaskFilter) -> (String, CypherParams) {
         let mut conditions = Vec::new();
+        let mut params = CypherParams::new();
         
         if let Some(ref statuses) = filter.status {
-            let status_list: Vec<String> = statuses.iter()
-                .map(|s| format!("'{:?}'", s).to_lowercase())
-                .collect();
-            conditions.push(format!("t.status IN [{}]", status_list.join(", ")));
+            conditions.push("t.status IN $statuses".to_string());
+            params.insert("statuses".to_string(), json!(statuses));
         }
         
         if let Some(ref priorities) = filter.priority {
-            let priority_list: Vec<String> = priorities.iter()
-                .map(|p| format!("'{:?}'", p).to_lowercase())
-                .collect();
-            conditions.push(format!("t.priority IN [{}]", priority_list.join(", ")));
+            conditions.push("t.priority IN $priorities".to_string());
+            params.insert("priorities".to_string(), json!(priorities));
         }
         
         if let Some(ref contexts) = filter.context {
-            let context_list: Vec<String> = contexts.iter()
-                .map(|c| format!("'{:?}'", c).to_lowercase())
-                .collect();
-            conditions.push(format!("t.context IN [{}]", context_list.join(", ")));
+            conditions.push("t.context IN $contexts".to_string());
+            params.insert("contexts".to_string(), json!(contexts));
         }
         
         if let Some(ref user_id) = filter.assigned_user_id {
-            conditions.push(format!("EXISTS((t)<-[:ASSIGNED_TO]-(u:User {{id: '{}'}}))", user_id));
+            conditions.push("EXISTS((t)<-[:ASSIGNED_TO]-(u:User {id: $assigned_user_id}))".to_string());
+            params.insert("assigned_user_id".to_string(), json!(user_id));
         }
         
         if let Some(ref project_id) = filter.project_id {
-            conditions.push(format!("EXISTS((t)-[:BELONGS_TO_PROJECT]->(p:Project {{id: '{}'}}))", project_id));
+            conditions.push("EXISTS((t)-[:BELONGS_TO_PROJECT]->(p:Project {id: $project_id}))".to_string());
+            params.insert("project_id".to_string(), json!(project_id));
         }
         
         if let Some(ref due_before) = filter.due_before {
-            conditions.push(format!("t.due_date < '{}'", due_before.to_rfc3339()));
+            conditions.push("t.due_date < $due_before".to_string());
+            params.insert("due_before".to_string(), json!(due_before.to_rfc3339()));
         }
         
         if let Some(ref due_after) = filter.due_after {
-            conditions.push(format!("t.due_date > '{}'", due_after.to_rfc3339()));
+            conditions.push("t.due_date > $due_after".to_string());
+            params.insert("due_after".to_string(), json!(due_after.to_rfc3339()));
         }
         
         if let Some(ref created_after) = filter.created_after {
-            conditions.push(format!("t.created_at > '{}'", created_after.to_rfc3339()));
+            conditions.push("t.created_at > $created_after".to_string());
+            params.insert("created_after".to_string(), json!(created_after.to_rfc3339()));
         }
         
+        if let Some(ref search_text) = filter.search_text {
+            conditions.push(
+                "(toLower(t.name) CONTAINS toLower($search) OR toLower(t.description) CONTAINS toLower($search) \
+                 OR toLower(t.implementation_details) CONTAINS toLower($search))".to_string()
+            );
+            params.insert("search".to_string(), json!(search_text));
+        }
+        
+        if let Some(ref tags) = filter.tags {
+            if filter.require_all_tags {
+                for (i, tag) in tags.iter().enumerate() {
+                    conditions.push(format!("EXISTS((t)-[:TAGGED_WITH]->(:Tag {{name: $tag_{}}}))", i));
+                    params.insert(format!("tag_{}", i), json!(tag));
+                }
+            } else {
+                conditions.push("EXISTS((t)-[:TAGGED_WITH]->(g:Tag) WHERE g.name IN $tags)".to_string());
+                params.insert("tags".to_string(), json!(tags));
+            }
+        }
+        
+        if let Some(Ok((cursor_ts, cursor_id))) = filter.cursor.as_deref().map(TaskCursor::decode) {
+            conditions.push("(t.created_at < $cursor_created_at OR (t.created_at = $cursor_created_at AND t.id < $cursor_id))".to_string());
+            params.insert("cursor_created_at".to_string(), json!(cursor_ts.to_rfc3339()));
+            params.insert("cursor_id".to_string(), json!(cursor_id));
+        }
+        
+        // Overdue tasks and subtasks are both due before the same `$now`
+        let now = Utc::now().to_rfc3339();
         if filter.is_overdue == Some(true) {
-            let now = Utc::now().to_rfc3339();
-            conditions.push(format!("t.due_date < '{}' AND t.status NOT IN ['done', 'cancelled']", now));
+            conditions.push("t.due_date < $now AND t.status NOT IN ['done', 'cancelled']".to_string());
+            params.insert("now".to_string(), json!(now));
+        }
+        
+        if let Some(changes) = filter.priority_changed_more_than {
+            conditions.push("t.priority_change_count > $priority_changes".to_string());
+            params.insert("priority_changes".to_string(), json!(changes));
         }
         
-        if conditions.is_empty() {
+        if let Some(cross_context) = filter.cross_context {
+            let condition = "t.parent_context IS NOT NULL AND t.parent_context <> t.context";
+            if cross_context {
+                conditions.push(condition.to_string());
+            } else {
+                conditions.push(format!("NOT ({})", condition));
+            }
+        }
+        
+        if let Some(has_overdue_subtasks) = filter.has_overdue_subtasks {
+            let overdue_subtask =
+                "EXISTS((t)<-[:SUBTASK_OF]-(child:Task) WHERE child.due_date < $now AND child.status NOT IN ['done', 'cancelled'])";
+            params.insert("now".to_string(), json!(now));
+            if has_overdue_subtasks {
+                conditions.push(overdue_subtask.to_string());
+            } else {
+                conditions.push(format!("NOT {}", overdue_subtask));
+            }
+        }
+        
+        let clause = if conditions.is_empty() {
             String::new()
         } else {
             format!("WHERE {}", conditions.join(" AND "))
-        }
+        };
+        (clause, params)
     }
 }
 
 #[async_trait]
 impl TaskRepository for GraphTaskRepository {
     async fn save_task(&self, task: &Task) -> TylResult<()> {
-        let node = self.task_to_graph_node(task)?;
-        
-        // Check if task exists
-        match self.adapter.get_node(&task.id).await? {
-            Some(_) => {
-                // Update existing node - in a real implementation we'd use graph update operations
-                // For now, we'll delete and recreate
-                let _result = self.adapter.execute_cypher(&format!(
-                    "MATCH (t:Task {{id: '{}'}}) DELETE t", 
-                    task.id.replace('\'', "\\'")
-                )).await?;
+        let (properties, labels) = self.task_write_parts(task)?;
+        
+        // Written in place so the task keeps its relationships; every write moves
+        // the stored version on, whatever version the caller read
+        let query = format!(
+            "MERGE (t:Task {{id: $id}}) WITH t, coalesce(t.version, 0) AS stored \
+             SET t = $properties, {}, t.version = CASE WHEN stored > $version THEN stored ELSE $version END + 1",
+            labels
+        );
+        let params = cypher_params([
+            ("id", json!(task.id)),
+            ("properties", properties),
+            ("version", json!(task.version)),
+        ]);
+        self.execute_cypher_with_params(&query, params).await?;
+        Ok(())
+    }
+    
+    async fn save_task_if_version(&self, task: &Task, expected_version: u64) -> TylResult<bool> {
+        let (properties, labels) = self.task_write_parts(task)?;
+        
+        // Checking and writing in one statement, so two writers holding the same version cannot both land
+        let query = format!(
+            "MATCH (t:Task {{id: $id}}) WHERE t.version = $expected_version \
+             SET t = $properties, {}, t.version = $expected_version + 1 RETURN t",
+            labels
+        );
+        let params = cypher_params([
+            ("id", json!(task.id)),
+            ("properties", properties),
+            ("expected_version", json!(expected_version)),
+        ]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        Ok(!self.parse_tasks_from_cypher_results(&result)?.is_empty())
+    }
+    
+    async fn save_tasks(&self, tasks: &[NewTask]) -> TylResult<()> {
+        if tasks.is_empty() {
+            return Ok(());
+        }
+        
+        // A single statement runs atomically, so a failing node or edge rolls back the whole batch
+        let mut patterns = Vec::with_capacity(tasks.len());
+        let mut assignments = Vec::with_capacity(tasks.len());
+        let mut owners = Vec::new();
+        let mut project_links = Vec::new();
+        let mut params = CypherParams::new();
+        for (i, NewTask { task, assigned_user_id, project_id }) in tasks.iter().enumerate() {
+            let mut node = self.task_to_graph_node(task)?;
+            node.properties.insert("version".to_string(), json!(task.version + 1));
+            patterns.push(format!("(n{}:{})", i, node.labels.join(":")));
+            assignments.push(format!("n{} = $task_{}", i, i));
+            params.insert(format!("task_{}", i), serde_json::Value::Object(node.properties.into_iter().collect()));
+            if let Some(user_id) = assigned_user_id {
+                owners.push(json!({"task_id": task.id, "user_id": user_id}));
             }
-            None => {
-                // Node doesn't exist, will be created below
+            if let Some(project_id) = project_id {
+                project_links.push(json!({"task_id": task.id, "project_id": project_id}));
             }
         }
+        params.insert("owners".to_string(), json!(owners));
+        params.insert("project_links".to_string(), json!(project_links));
+        params.insert("role".to_string(), json!("owner"));
         
-        // Create the node
-        self.adapter.create_node(node).await?;
+        // Unknown users and projects match nothing and are skipped, as in
+        // `assign_user_to_task` and `add_task_to_project`
+        let query = format!(
+            "CREATE {} SET {} \
+             WITH count(*) AS created \
+             UNWIND $owners AS owner \
+             MATCH (t:Task {{id: owner.task_id}}), (u:User {{id: owner.user_id}}) \
+             CREATE (t)-[:ASSIGNED_TO {{role: $role}}]->(u) \
+             WITH count(*) AS assigned \
+             UNWIND $project_links AS link \
+             MATCH (t:Task {{id: link.task_id}}), (p:Project {{id: link.project_id}}) \
+             CREATE (t)-[:BELONGS_TO_PROJECT]->(p)",
+            patterns.join(", "), assignments.join(", ")
+        );
+        self.execute_cypher_with_params(&query, params).await?;
         Ok(())
     }
     
+    async fn find_existing_task_ids(&self, ids: &[String]) -> TylResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let query = "MATCH (t:Task) WHERE t.id IN $ids RETURN t.id AS id";
+        let params = cypher_params([("ids", json!(ids))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| row.get("id").and_then(|id| id.as_str()))
+                .map(String::from)
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn find_tasks_by_ids(&self, ids: &[String]) -> TylResult<Vec<Task>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = "MATCH (t:Task) WHERE t.id IN $ids RETURN t";
+        let params = cypher_params([("ids", json!(ids))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
     async fn find_task_by_id(&self, id: &str) -> TylResult<Option<Task>> {
-        match self.adapter.get_node(id).await? {
+        match telemetry::time_graph_query("get_node", self.adapter.with_retry(|| self.adapter.inner().get_node(id))).await? {
             Some(node) => {
                 let task = self.graph_node_to_task(&node)?;
                 Ok(Some(task))
@@ -499,113 +823,174 @@ impl TaskRepository for GraphTaskRepository {
     }
     
     async fn find_tasks_by_filter(&self, filter: &TaskFilter) -> TylResult<Vec<Task>> {
-        let where_clause = self.build_filter_clause(filter);
+        if let Some(ref cursor) = filter.cursor {
+            TaskCursor::decode(cursor)?;
+        }
+        let (where_clause, params) = self.build_filter_clause(filter);
         let limit_clause = if let Some(limit) = filter.limit {
             format!("LIMIT {}", limit)
         } else {
             String::new()
         };
-        let offset_clause = if let Some(offset) = filter.offset {
-            format!("SKIP {}", offset)
-        } else {
-            String::new()
+        // The cursor already marks where the page starts
+        let offset_clause = match filter.offset {
+            Some(offset) if filter.cursor.is_none() => format!("SKIP {}", offset),
+            _ => String::new(),
         };
         
         let query = format!(
-            "MATCH (t:Task) {} RETURN t ORDER BY t.created_at DESC {} {}",
+            "MATCH (t:Task) {} RETURN t ORDER BY t.created_at DESC, t.id DESC {} {}",
             where_clause, offset_clause, limit_clause
         );
         
-        let result = self.adapter.execute_cypher(&query).await?;
+        let result = self.execute_cypher_with_params(&query, params).await?;
         
         // Parse the Cypher results into Task objects
         self.parse_tasks_from_cypher_results(&result)
     }
     
     async fn delete_task(&self, id: &str) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $id}) DETACH DELETE t";
+        let params = cypher_params([("id", json!(id))]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn patch_task_custom_properties(&self, task_id: &str, patch: &CustomPropertiesPatch, expected_version: Option<u64>) -> TylResult<Option<Task>> {
+        let mut set_props = serde_json::Map::new();
+        set_props.insert("updated_at".to_string(), json!(Utc::now().to_rfc3339()));
+        let mut removed = Vec::new();
+        for (key, value) in patch {
+            match value {
+                Some(value) => { set_props.insert(format!("custom_{}", key), value.clone()); }
+                None => removed.push(format!("t.`custom_{}`", key.replace('`', ""))),
+            }
+        }
+        let remove_clause = if removed.is_empty() {
+            String::new()
+        } else {
+            format!("REMOVE {}", removed.join(", "))
+        };
+        
+        let version_clause = if expected_version.is_some() {
+            "WHERE t.version = $expected_version"
+        } else {
+            ""
+        };
+        
+        // Only the patched properties are touched, so concurrent patches of other keys survive
         let query = format!(
-            "MATCH (t:Task {{id: '{}'}}) DETACH DELETE t", 
-            id.replace('\'', "\\'")
+            "MATCH (t:Task {{id: $task_id}}) {} SET t += $properties, t.version = coalesce(t.version, 0) + 1 {} RETURN t",
+            version_clause, remove_clause
         );
-        self.adapter.execute_cypher(&query).await?;
-        Ok(())
+        let params = cypher_params([
+            ("task_id", json!(task_id)),
+            ("properties", serde_json::Value::Object(set_props)),
+            ("expected_version", json!(expected_version)),
+        ]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        Ok(self.parse_tasks_from_cypher_results(&result)?.into_iter().next())
     }
     
     async fn save_dependency(&self, dependency: &TaskDependency) -> TylResult<()> {
         let relationship = self.dependency_to_graph_relationship(dependency);
-        self.adapter.create_relationship(relationship).await?;
+        telemetry::time_graph_query("create_relationship", self.adapter.with_retry(|| self.adapter.inner().create_relationship(relationship.clone()))).await?;
         Ok(())
     }
     
     async fn delete_dependency(&self, dependency_id: &str) -> TylResult<()> {
-        let query = format!(
-            "MATCH ()-[r:DEPENDS_ON {{id: '{}'}}]-() DELETE r", 
-            dependency_id.replace('\'', "\\'")
-        );
-        self.adapter.execute_cypher(&query).await?;
+        // The edge is replaced by a :DeletedDependency tombstone so dependency diffs can show the removal
+        let query = "MATCH (from:Task)-[r:DEPENDS_ON {id: $dependency_id}]->(to:Task) \
+                     CREATE (:DeletedDependency {id: r.id, from_task_id: from.id, to_task_id: to.id, \
+                     dependency_type: r.dependency_type, is_hard_dependency: r.is_hard_dependency, \
+                     delay_days: r.delay_days, created_at: r.created_at, deleted_at: $deleted_at}) \
+                     DELETE r";
+        let params = cypher_params([
+            ("dependency_id", json!(dependency_id)),
+            ("deleted_at", json!(Utc::now().to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
     async fn find_dependencies_by_task(&self, task_id: &str) -> TylResult<Vec<TaskDependency>> {
-        let query = format!(
-            "MATCH (t:Task {{id: '{}'}})-[r:DEPENDS_ON]->(dep:Task) RETURN r", 
-            task_id.replace('\'', "\\'")
-        );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (t:Task {id: $task_id})-[r:DEPENDS_ON]->(dep:Task) RETURN r";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
         
         // Parse the Cypher results into TaskDependency objects
         self.parse_dependencies_from_cypher_results(&result)
     }
     
+    async fn find_deleted_dependencies(&self, task_id: &str, since: DateTime<Utc>) -> TylResult<Vec<TaskDependency>> {
+        let query = "MATCH (d:DeletedDependency {from_task_id: $task_id}) WHERE d.deleted_at > $since RETURN d AS r";
+        let params = cypher_params([("task_id", json!(task_id)), ("since", json!(since.to_rfc3339()))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        self.parse_dependencies_from_cypher_results(&result)
+    }
+    
     async fn find_blocking_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
-        let query = format!(
-            "MATCH (t:Task {{id: '{}'}})<-[r:DEPENDS_ON]-(blocked:Task) WHERE r.dependency_type = 'blocks' RETURN blocked", 
-            task_id.replace('\'', "\\'")
-        );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (t:Task {id: $task_id})<-[r:DEPENDS_ON]-(blocked:Task) WHERE r.dependency_type = 'blocks' RETURN blocked";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
         
         // Parse the Cypher results into Task objects
         self.parse_tasks_from_cypher_results(&result)
     }
     
+    async fn find_prerequisite_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        let query = "MATCH (t:Task {id: $task_id})-[:DEPENDS_ON]->(pred:Task) RETURN DISTINCT pred";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
+    async fn find_dependent_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        let query = "MATCH (t:Task {id: $task_id})<-[:DEPENDS_ON]-(dependent:Task) RETURN DISTINCT dependent";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
+    async fn find_task_subgraph(&self, task_id: &str, depth: u32) -> TylResult<TaskSubgraph> {
+        let (nodes_query, edges_query, params) = task_subgraph_queries(task_id, depth);
+        let nodes = self.execute_cypher_with_params(&nodes_query, params.clone()).await?;
+        let edges = self.execute_cypher_with_params(&edges_query, params).await?;
+        Ok(task_subgraph_from_rows(&nodes, &edges))
+    }
+    
     async fn add_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
-        let query = format!(
-            "MATCH (parent:Task {{id: '{}'}}), (child:Task {{id: '{}'}}) 
-             CREATE (child)-[:SUBTASK_OF]->(parent)", 
-            parent_id.replace('\'', "\\'"),
-            child_id.replace('\'', "\\'")
-        );
-        self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (parent:Task {id: $parent_id}), (child:Task {id: $child_id}) 
+             CREATE (child)-[:SUBTASK_OF]->(parent)";
+        let params = cypher_params([("parent_id", json!(parent_id)), ("child_id", json!(child_id))]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
     async fn remove_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
-        let query = format!(
-            "MATCH (parent:Task {{id: '{}'}})<-[r:SUBTASK_OF]-(child:Task {{id: '{}'}}) DELETE r", 
-            parent_id.replace('\'', "\\'"),
-            child_id.replace('\'', "\\'")
-        );
-        self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (parent:Task {id: $parent_id})<-[r:SUBTASK_OF]-(child:Task {id: $child_id}) DELETE r";
+        let params = cypher_params([("parent_id", json!(parent_id)), ("child_id", json!(child_id))]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
     async fn find_children(&self, parent_id: &str) -> TylResult<Vec<Task>> {
-        let query = format!(
-            "MATCH (parent:Task {{id: '{}'}})<-[:SUBTASK_OF]-(child:Task) RETURN child", 
-            parent_id.replace('\'', "\\'")
-        );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (parent:Task {id: $parent_id})<-[:SUBTASK_OF]-(child:Task) RETURN child";
+        let params = cypher_params([("parent_id", json!(parent_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
         
         // Parse the Cypher results into Task objects
         self.parse_tasks_from_cypher_results(&result)
     }
     
     async fn find_parent(&self, child_id: &str) -> TylResult<Option<Task>> {
-        let query = format!(
-            "MATCH (child:Task {{id: '{}'}})-[:SUBTASK_OF]->(parent:Task) RETURN parent", 
-            child_id.replace('\'', "\\'")
-        );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (child:Task {id: $child_id})-[:SUBTASK_OF]->(parent:Task) RETURN parent";
+        let params = cypher_params([("child_id", json!(child_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
         
         // Parse the Cypher results - get first task if any
         let tasks = self.parse_tasks_from_cypher_results(&result)?;
@@ -613,36 +998,353 @@ impl TaskRepository for GraphTaskRepository {
     }
     
     async fn assign_user_to_task(&self, task_id: &str, user_id: &str, role: &str) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $task_id}), (u:User {id: $user_id}) 
+             CREATE (t)-[:ASSIGNED_TO {role: $role}]->(u)";
+        let params = cypher_params([("task_id", json!(task_id)), ("user_id", json!(user_id)), ("role", json!(role))]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn unassign_user_from_task(&self, task_id: &str, user_id: &str) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $task_id})-[r:ASSIGNED_TO]->(u:User {id: $user_id}) DELETE r";
+        let params = cypher_params([("task_id", json!(task_id)), ("user_id", json!(user_id))]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn reassign_user_tasks(&self, from_user_id: &str, to_user_id: Option<&str>, task_ids: &[String]) -> TylResult<()> {
+        let query = "MATCH (u:User {id: $from_user_id})<-[r:ASSIGNED_TO]-(t:Task) WHERE t.id IN $task_ids
+             WITH r, t.id AS task_id, coalesce(r.role, 'owner') AS role
+             DELETE r
+             RETURN task_id, role";
+        let params = cypher_params([("from_user_id", json!(from_user_id)), ("task_ids", json!(task_ids))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        let Some(to_user_id) = to_user_id else {
+            return Ok(());
+        };
+        
+        let assignments: Vec<serde_json::Value> = result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| Some(json!({ "task_id": row.get("task_id")?.as_str()?, "role": row.get("role")?.as_str()? })))
+                .collect())
+            .unwrap_or_default();
+        if assignments.is_empty() {
+            return Ok(());
+        }
+        let query = "UNWIND $assignments AS a
+             MATCH (t:Task {id: a.task_id}), (u:User {id: $to_user_id})
+             MERGE (t)-[r:ASSIGNED_TO]->(u)
+             ON CREATE SET r.role = a.role";
+        let params = cypher_params([("assignments", json!(assignments)), ("to_user_id", json!(to_user_id))]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn find_assigned_tasks(&self, user_id: &str) -> TylResult<Vec<Task>> {
+        let query = "MATCH (t:Task)-[:ASSIGNED_TO]->(u:User {id: $user_id}) RETURN t";
+        let params = cypher_params([("user_id", json!(user_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        // Parse the Cypher results into Task objects
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
+    async fn find_task_assignments(&self, task_id: &str) -> TylResult<Vec<(String, String)>> {
+        let query = "MATCH (t:Task {id: $task_id})-[r:ASSIGNED_TO]->(u:User) RETURN u.id AS user_id, r.role AS role";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let user_id = row.get("user_id")?.as_str()?.to_string();
+                    let role = row.get("role").and_then(|r| r.as_str()).unwrap_or("owner").to_string();
+                    Some((user_id, role))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn user_exists(&self, user_id: &str) -> TylResult<bool> {
+        let query = "MATCH (u:User {id: $user_id}) RETURN count(u) AS users";
+        let params = cypher_params([("user_id", json!(user_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("users"))
+            .and_then(|count| count.as_u64())
+            .is_some_and(|count| count > 0))
+    }
+    
+    async fn has_assignee(&self, task_id: &str) -> TylResult<bool> {
+        let query = "MATCH (t:Task {id: $task_id}) RETURN EXISTS((t)-[:ASSIGNED_TO]->()) AS assigned";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("assigned"))
+            .and_then(|assigned| assigned.as_bool().or_else(|| assigned.as_i64().map(|n| n != 0)))
+            .unwrap_or(false))
+    }
+    
+    async fn save_review_request(&self, task_id: &str, reviewer_id: &str, requested_at: DateTime<Utc>) -> TylResult<()> {
+        // Asking the same reviewer again keeps the original request time
+        let query = "MATCH (t:Task {id: $task_id}), (u:User {id: $reviewer_id}) 
+             MERGE (t)-[r:ASSIGNED_AS_REVIEWER]->(u) 
+             ON CREATE SET r.requested_at = $requested_at";
+        let params = cypher_params([
+            ("task_id", json!(task_id)),
+            ("reviewer_id", json!(reviewer_id)),
+            ("requested_at", json!(requested_at.to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn find_review_queue(&self, reviewer_id: Option<&str>, project_id: Option<&str>) -> TylResult<Vec<(Task, Option<DateTime<Utc>>)>> {
+        let mut conditions = vec!["t.status = 'review'"];
+        let mut params = CypherParams::new();
+        if let Some(project_id) = project_id {
+            conditions.push("EXISTS((t)-[:BELONGS_TO_PROJECT]->(:Project {id: $project_id}))");
+            params.insert("project_id".to_string(), json!(project_id));
+        }
+        let reviewer_match = match reviewer_id {
+            Some(reviewer_id) => {
+                params.insert("reviewer_id".to_string(), json!(reviewer_id));
+                "MATCH (t)-[r:ASSIGNED_AS_REVIEWER]->(:User {id: $reviewer_id})"
+            }
+            None => "OPTIONAL MATCH (t)-[r:ASSIGNED_AS_REVIEWER]->(:User)",
+        };
+        
         let query = format!(
-            "MATCH (t:Task {{id: '{}'}}), (u:User {{id: '{}'}}) 
-             CREATE (t)-[:ASSIGNED_TO {{role: '{}'}}]->(u)", 
-            task_id.replace('\'', "\\'"),
-            user_id.replace('\'', "\\'"),
-            role.replace('\'', "\\'")
+            "MATCH (t:Task) WHERE {}
+             {}
+             RETURN t, min(r.requested_at) AS review_requested_at",
+            conditions.join(" AND "),
+            reviewer_match
         );
-        self.adapter.execute_cypher(&query).await?;
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let task = self.parse_task_from_cypher_result(row).ok()?;
+                    let requested_at = row.get("review_requested_at")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+                    Some((task, requested_at))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn save_external_link(&self, link: &ExternalTicketLink) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $task_id}) 
+             MERGE (t)-[:HAS_EXTERNAL_LINK]->(l:ExternalLink {system: $system, external_id: $external_id}) 
+             SET l.external_url = $external_url, l.sync_status = $sync_status, l.last_synced_at = $last_synced_at";
+        let params = cypher_params([
+            ("task_id", json!(link.task_id)),
+            ("system", json!(link.system.name())),
+            ("external_id", json!(link.external_id)),
+            ("external_url", json!(link.external_url)),
+            ("sync_status", json!(link.sync_status)),
+            ("last_synced_at", json!(link.last_synced_at.map(|at| at.to_rfc3339()))),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
-    async fn unassign_user_from_task(&self, task_id: &str, user_id: &str) -> TylResult<()> {
+    async fn find_external_links(&self, task_id: &str) -> TylResult<Vec<ExternalTicketLink>> {
+        let query = "MATCH (t:Task {id: $task_id})-[:HAS_EXTERNAL_LINK]->(l:ExternalLink) 
+             RETURN l.system AS system, l.external_id AS external_id, l.external_url AS external_url, 
+                    l.sync_status AS sync_status, l.last_synced_at AS last_synced_at 
+             ORDER BY l.system, l.external_id";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| Some(ExternalTicketLink {
+                    task_id: task_id.to_string(),
+                    system: ExternalSystem::from_name(row.get("system")?.as_str()?),
+                    external_id: row.get("external_id")?.as_str()?.to_string(),
+                    external_url: row.get("external_url")?.as_str()?.to_string(),
+                    sync_status: row.get("sync_status")
+                        .and_then(|v| serde_json::from_value::<SyncStatus>(v.clone()).ok())
+                        .unwrap_or_default(),
+                    last_synced_at: row.get("last_synced_at")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&Utc)),
+                }))
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn save_comment(&self, comment: &TaskComment) -> TylResult<()> {
+        // The body is kept in `content`, where comments were stored before they could be edited
+        let query = "MATCH (t:Task {id: $task_id}) 
+             MERGE (t)-[:HAS_COMMENT]->(c:Comment {id: $id}) 
+             SET c.content = $body, c.author_id = $author_id, c.edited = $edited, c.created_at = $created_at, c.updated_at = $updated_at";
+        let params = cypher_params([
+            ("task_id", json!(comment.task_id)),
+            ("id", json!(comment.id)),
+            ("body", json!(comment.body)),
+            ("author_id", json!(comment.author_id)),
+            ("edited", json!(comment.edited)),
+            ("created_at", json!(comment.created_at.to_rfc3339())),
+            ("updated_at", json!(comment.updated_at.to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn find_comment_by_id(&self, comment_id: &str) -> TylResult<Option<TaskComment>> {
         let query = format!(
-            "MATCH (t:Task {{id: '{}'}})-[r:ASSIGNED_TO]->(u:User {{id: '{}'}}) DELETE r", 
-            task_id.replace('\'', "\\'"),
-            user_id.replace('\'', "\\'")
+            "MATCH (t:Task)-[:HAS_COMMENT]->(c:Comment {{id: $comment_id}}) RETURN {}", 
+            COMMENT_COLUMNS
         );
-        self.adapter.execute_cypher(&query).await?;
+        let params = cypher_params([("comment_id", json!(comment_id))]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        Ok(Self::parse_comments(&result).into_iter().next())
+    }
+    
+    async fn delete_comment(&self, comment_id: &str) -> TylResult<()> {
+        let query = "MATCH (c:Comment {id: $comment_id}) DETACH DELETE c";
+        let params = cypher_params([("comment_id", json!(comment_id))]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
-    async fn find_assigned_tasks(&self, user_id: &str) -> TylResult<Vec<Task>> {
+    async fn find_task_comments(&self, task_id: &str) -> TylResult<Vec<TaskComment>> {
         let query = format!(
-            "MATCH (t:Task)-[:ASSIGNED_TO]->(u:User {{id: '{}'}}) RETURN t", 
-            user_id.replace('\'', "\\'")
+            "MATCH (t:Task {{id: $task_id}})-[:HAS_COMMENT]->(c:Comment) 
+             RETURN {} 
+             ORDER BY c.created_at, c.id", 
+            COMMENT_COLUMNS
         );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        Ok(Self::parse_comments(&result))
+    }
+    
+    async fn find_task_tags(&self, task_id: &str) -> TylResult<Vec<Tag>> {
+        let query = "MATCH (t:Task {id: $task_id})-[:TAGGED_WITH]->(g:Tag) 
+             RETURN g.id AS id, g.name AS name, g.color AS color, g.category AS category, 
+                    g.description AS description, g.created_at AS created_at 
+             ORDER BY g.name";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let text = |key: &str| row.get(key).and_then(|v| v.as_str()).map(String::from);
+                    Some(Tag {
+                        id: text("id")?,
+                        name: text("name")?,
+                        color: text("color").unwrap_or_default(),
+                        category: text("category").unwrap_or_default(),
+                        description: text("description"),
+                        created_at: text("created_at")
+                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(Utc::now),
+                    })
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn add_task_tag(&self, task_id: &str, tag: &Tag) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $task_id}) 
+             MERGE (g:Tag {name: $name}) 
+             ON CREATE SET g.id = $id, g.color = $color, g.category = $category, g.created_at = $created_at 
+             MERGE (t)-[:TAGGED_WITH]->(g) 
+             SET t.tags = CASE WHEN g.name IN coalesce(t.tags, []) THEN t.tags ELSE coalesce(t.tags, []) + g.name END";
+        let params = cypher_params([
+            ("task_id", json!(task_id)),
+            ("name", json!(tag.name)),
+            ("id", json!(tag.id)),
+            ("color", json!(tag.color)),
+            ("category", json!(tag.category)),
+            ("created_at", json!(tag.created_at.to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn remove_task_tag(&self, task_id: &str, tag_name: &str) -> TylResult<()> {
+        let query = "MATCH (t:Task {id: $task_id})-[r:TAGGED_WITH]->(g:Tag {name: $tag_name}) 
+             DELETE r 
+             SET t.tags = [name IN coalesce(t.tags, []) WHERE name <> g.name]";
+        let params = cypher_params([("task_id", json!(task_id)), ("tag_name", json!(tag_name))]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn fulltext_search_tasks(&self, query: &str, context: Option<TaskContext>, limit: usize) -> TylResult<Vec<TaskSearchResult>> {
+        let (search_clause, params) = fulltext_search_clause(query, context.as_ref());
+        let search_query = format!("{} RETURN t, score ORDER BY score DESC LIMIT {}", search_clause, limit);
+        let result = self.execute_cypher_with_params(&search_query, params).await?;
         
-        // Parse the Cypher results into Task objects
-        self.parse_tasks_from_cypher_results(&result)
+        let mut results = Vec::new();
+        if let Some(rows) = result.as_array() {
+            for row in rows {
+                results.push(TaskSearchResult {
+                    task: self.parse_task_from_cypher_result(row)?,
+                    relevance_score: row.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                });
+            }
+        }
+        Ok(results)
+    }
+    
+    async fn search_tasks_advanced(&self, query: &TaskSearchQuery, sort: &[SortSpec], page: &PageRequest) -> TylResult<PagedResult<Task>> {
+        let (condition, params) = query.to_cypher();
+        let result = self.execute_cypher_with_params(
+            &format!("MATCH (t:Task) WHERE {} RETURN count(t) AS total", condition),
+            params.clone(),
+        ).await?;
+        let total = result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("total"))
+            .and_then(|total| total.as_u64())
+            .unwrap_or(0) as usize;
+        
+        let result = self.execute_cypher_with_params(&format!(
+            "MATCH (t:Task) WHERE {} RETURN t {} SKIP {} LIMIT {}",
+            condition,
+            search_order_clause(sort),
+            page.offset(),
+            page.page_size
+        ), params).await?;
+        let mut items = Vec::new();
+        if let Some(rows) = result.as_array() {
+            for row in rows {
+                items.push(self.parse_task_from_cypher_result(row)?);
+            }
+        }
+        Ok(PagedResult { items, total, page: page.page, page_size: page.page_size })
+    }
+    
+    async fn count_tag_usage(&self) -> TylResult<Vec<TagUsage>> {
+        let query = "MATCH (t:Task)-[:TAGGED_WITH]->(g:Tag) 
+             RETURN g.name AS tag, count(t) AS task_count 
+             ORDER BY task_count DESC, tag";
+        let result = self.execute_cypher(query).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| Some(TagUsage {
+                    tag: row.get("tag")?.as_str()?.to_string(),
+                    task_count: row.get("task_count")?.as_u64()? as usize,
+                }))
+                .collect())
+            .unwrap_or_default())
     }
     
     async fn save_project(&self, project: &Project) -> TylResult<()> {
@@ -663,80 +1365,862 @@ impl TaskRepository for GraphTaskRepository {
         if let Some(ref end_date) = project.end_date {
             properties.insert("end_date".to_string(), json!(end_date.to_rfc3339()));
         }
+        if let Some(ref lead) = project.project_lead_user_id {
+            properties.insert("project_lead_user_id".to_string(), json!(lead));
+        }
         
         let mut node = FalkorNode::new(project.id.clone());
         node.labels = vec!["Project".to_string()];
         node.properties = properties;
         
-        self.adapter.create_node(node).await?;
+        telemetry::time_graph_query("create_node", self.adapter.with_retry(|| self.adapter.inner().create_node(node.clone()))).await?;
         Ok(())
     }
     
     async fn add_task_to_project(&self, task_id: &str, project_id: &str) -> TylResult<()> {
-        let query = format!(
-            "MATCH (t:Task {{id: '{}'}}), (p:Project {{id: '{}'}}) 
-             CREATE (t)-[:BELONGS_TO_PROJECT]->(p)", 
-            task_id.replace('\'', "\\'"),
-            project_id.replace('\'', "\\'")
-        );
-        self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (t:Task {id: $task_id}), (p:Project {id: $project_id}) 
+             CREATE (t)-[:BELONGS_TO_PROJECT]->(p)";
+        let params = cypher_params([("task_id", json!(task_id)), ("project_id", json!(project_id))]);
+        self.execute_cypher_with_params(query, params).await?;
         Ok(())
     }
     
     async fn find_project_tasks(&self, project_id: &str) -> TylResult<Vec<Task>> {
-        let query = format!(
-            "MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p:Project {{id: '{}'}}) RETURN t", 
-            project_id.replace('\'', "\\'")
-        );
-        let result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p:Project {id: $project_id}) RETURN t";
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
         
         // Parse the Cypher results into Task objects
         self.parse_tasks_from_cypher_results(&result)
     }
     
-    async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
+    async fn find_task_project_id(&self, task_id: &str) -> TylResult<Option<String>> {
+        let query = "MATCH (t:Task {id: $task_id})-[:BELONGS_TO_PROJECT]->(p:Project) RETURN p.id AS project_id LIMIT 1";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("project_id"))
+            .and_then(|id| id.as_str())
+            .map(String::from))
+    }
+    
+    async fn find_project_by_id(&self, project_id: &str) -> TylResult<Option<Project>> {
         let query = format!(
-            "MATCH (parent:Task {{id: '{}'}})<-[:SUBTASK_OF]-(child:Task)
+            "MATCH (p:Project {{id: $project_id}}) RETURN {} LIMIT 1", 
+            PROJECT_COLUMNS
+        );
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(project_from_row))
+    }
+    
+    async fn set_project_lead(&self, project_id: &str, user_id: &str) -> TylResult<()> {
+        let query = "MATCH (p:Project {id: $project_id}) 
+             SET p.project_lead_user_id = $user_id, p.updated_at = $updated_at";
+        let params = cypher_params([
+            ("project_id", json!(project_id)),
+            ("user_id", json!(user_id)),
+            ("updated_at", json!(Utc::now().to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn save_workflow_config(&self, config: &ProjectWorkflowConfig) -> TylResult<()> {
+        // Limits are stored as one WipLimit node per status, replacing the previous set
+        let query = "MATCH (p:Project {id: $project_id})-[:HAS_WIP_LIMIT]->(l:WipLimit) DETACH DELETE l";
+        self.execute_cypher_with_params(query, cypher_params([("project_id", json!(config.project_id))])).await?;
+        
+        for (status, limit) in &config.wip_limits {
+            let query = "MATCH (p:Project {id: $project_id}) 
+                 CREATE (p)-[:HAS_WIP_LIMIT]->(:WipLimit {status: $status, max_tasks: $max_tasks})";
+            let params = cypher_params([
+                ("project_id", json!(config.project_id)),
+                ("status", json!(status_value(status))),
+                ("max_tasks", json!(limit)),
+            ]);
+            self.execute_cypher_with_params(query, params).await?;
+        }
+        Ok(())
+    }
+    
+    async fn find_workflow_config(&self, project_id: &str) -> TylResult<Option<ProjectWorkflowConfig>> {
+        let query = "MATCH (p:Project {id: $project_id}) 
+             OPTIONAL MATCH (p)-[:HAS_WIP_LIMIT]->(l:WipLimit) 
+             RETURN p.id AS project_id, collect([l.status, l.max_tasks]) AS limits";
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        let Some(row) = result.as_array().and_then(|rows| rows.first()) else {
+            return Ok(None);
+        };
+        let wip_limits = row.get("limits").and_then(|l| l.as_array())
+            .map(|limits| limits.iter()
+                .filter_map(|pair| {
+                    let status = serde_json::from_value(pair.get(0)?.clone()).ok()?;
+                    let limit = pair.get(1)?.as_u64()? as u32;
+                    Some((status, limit))
+                })
+                .collect())
+            .unwrap_or_default();
+        
+        Ok(Some(ProjectWorkflowConfig { project_id: project_id.to_string(), wip_limits }))
+    }
+    
+    async fn find_wip_limit_violations(&self, project_id: &str) -> TylResult<Vec<WipViolation>> {
+        let query = "MATCH (p:Project {id: $project_id})-[:HAS_WIP_LIMIT]->(l:WipLimit)
+             MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p)
+             WHERE t.status = l.status
+             WITH l, t
+             ORDER BY CASE t.priority
+                 WHEN 'wish' THEN 1 WHEN 'low' THEN 2 WHEN 'medium' THEN 3
+                 WHEN 'high' THEN 4 WHEN 'critical' THEN 5 ELSE 3
+               END, t.id
+             WITH l.status AS status, l.max_tasks AS max_tasks, collect(t.id) AS task_ids
+             WHERE size(task_ids) > max_tasks
+             RETURN status, max_tasks, task_ids";
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let status = serde_json::from_value(row.get("status")?.clone()).ok()?;
+                    let limit = row.get("max_tasks")?.as_u64()? as u32;
+                    let task_ids = row.get("task_ids")?.as_array()?
+                        .iter()
+                        .filter_map(|id| id.as_str().map(String::from))
+                        .collect();
+                    WipViolation::from_usage(status, limit, task_ids)
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn save_project_snapshot(&self, snapshot: &ProjectSnapshot) -> TylResult<()> {
+        let metrics = serde_json::to_string(&snapshot.metrics)
+            .map_err(|e| TylError::internal(format!("Failed to serialize snapshot metrics: {}", e)))?;
+        let query = "MATCH (p:Project {id: $project_id})
+             CREATE (p)-[:HAS_SNAPSHOT]->(:ProjectSnapshot {id: $id, project_id: $project_id, label: $label, snapshot_at: $snapshot_at, metrics: $metrics})";
+        let params = cypher_params([
+            ("project_id", json!(snapshot.project_id)),
+            ("id", json!(snapshot.id)),
+            ("label", json!(snapshot.label)),
+            ("snapshot_at", json!(snapshot.snapshot_at.to_rfc3339())),
+            ("metrics", json!(metrics)),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn find_project_snapshot(&self, snapshot_id: &str) -> TylResult<Option<ProjectSnapshot>> {
+        let query = "MATCH (s:ProjectSnapshot {id: $snapshot_id})
+             RETURN s.id AS id, s.project_id AS project_id, s.label AS label, s.snapshot_at AS snapshot_at, s.metrics AS metrics";
+        let params = cypher_params([("snapshot_id", json!(snapshot_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| Some(ProjectSnapshot {
+                id: row.get("id")?.as_str()?.to_string(),
+                project_id: row.get("project_id")?.as_str()?.to_string(),
+                label: row.get("label")?.as_str()?.to_string(),
+                snapshot_at: DateTime::parse_from_rfc3339(row.get("snapshot_at")?.as_str()?).ok()?.with_timezone(&Utc),
+                metrics: serde_json::from_str(row.get("metrics")?.as_str()?).ok()?,
+            })))
+    }
+    
+    async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
+        // OPTIONAL MATCH keeps tasks without subtasks, which are 0% or 100% by status
+        let query = "MATCH (parent:Task {id: $task_id})
+             OPTIONAL MATCH (parent)<-[:SUBTASK_OF]-(child:Task)
              WITH parent, count(child) as total_subtasks, 
                   size([c in collect(child) WHERE c.status = 'done']) as completed_subtasks
              RETURN CASE WHEN total_subtasks = 0 THEN 
                 CASE WHEN parent.status = 'done' THEN 100.0 ELSE 0.0 END
-                ELSE (completed_subtasks * 100.0 / total_subtasks) END as percentage", 
-            task_id.replace('\'', "\\'")
-        );
-        let _result = self.adapter.execute_cypher(&query).await?;
-        
-        // In a real implementation, we would parse the result
-        // For now, return a default value
-        Ok(0.0)
+                ELSE (completed_subtasks * 100.0 / total_subtasks) END as percentage";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("percentage"))
+            .and_then(|percentage| percentage.as_f64())
+            .unwrap_or(0.0))
     }
     
     async fn find_critical_path(&self, project_id: &str) -> TylResult<Vec<Task>> {
-        let query = format!(
-            "MATCH (p:Project {{id: '{}'}})
+        let query = "MATCH (p:Project {id: $project_id})
              MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p)
              // Complex critical path algorithm would be implemented here
-             RETURN t", 
-            project_id.replace('\'', "\\'")
-        );
-        let _result = self.adapter.execute_cypher(&query).await?;
+             RETURN t";
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let _result = self.execute_cypher_with_params(query, params).await?;
         
         // In a real implementation, we would implement critical path algorithm
         Ok(vec![])
     }
     
+    async fn find_dependency_chains(&self, project_id: &str, top_n: usize) -> TylResult<Vec<DependencyPath>> {
+        // Chains run from tasks nothing depends on down to tasks without
+        // dependencies. Hours and weights mirror TaskComplexity::default_estimated_hours
+        // and TaskPriority::weight.
+        let query = format!(
+            "MATCH (p:Project {{id: $project_id}})
+             MATCH path = (start:Task)-[:DEPENDS_ON*]->(end:Task)
+             WHERE (start)-[:BELONGS_TO_PROJECT]->(p)
+               AND (end)-[:BELONGS_TO_PROJECT]->(p)
+               AND NOT ()-[:DEPENDS_ON]->(start)
+               AND NOT (end)-[:DEPENDS_ON]->()
+             WITH path,
+                  reduce(total = 0.0, n IN nodes(path) | total + coalesce(n.estimated_hours,
+                    CASE n.complexity
+                      WHEN 'trivial' THEN 1.0 WHEN 'simple' THEN 2.0 WHEN 'medium' THEN 4.0
+                      WHEN 'complex' THEN 8.0 WHEN 'very_complex' THEN 16.0 WHEN 'epic' THEN 32.0 ELSE 4.0
+                    END)) AS total_hours,
+                  reduce(total = 0.0, n IN nodes(path) | total +
+                    CASE n.priority
+                      WHEN 'critical' THEN 5.0 WHEN 'high' THEN 4.0 WHEN 'medium' THEN 3.0
+                      WHEN 'low' THEN 2.0 WHEN 'wish' THEN 1.0 ELSE 3.0
+                    END) / length(nodes(path)) AS avg_priority_weight
+             RETURN reverse([n IN nodes(path) | n.id]) AS task_chain, total_hours, avg_priority_weight
+             ORDER BY total_hours DESC
+             LIMIT {}",
+            top_n
+        );
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        let mut chains: Vec<DependencyPath> = result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let task_chain = row.get("task_chain")?.as_array()?
+                        .iter()
+                        .filter_map(|id| id.as_str().map(String::from))
+                        .collect();
+                    let total_hours = row.get("total_hours")?.as_f64()?;
+                    let avg_priority_weight = row.get("avg_priority_weight")?.as_f64()?;
+                    Some(DependencyPath::from_chain(task_chain, total_hours, avg_priority_weight))
+                })
+                .collect())
+            .unwrap_or_default();
+        
+        if let Some(longest) = chains.first_mut() {
+            longest.longest_chain = true;
+        }
+        Ok(chains)
+    }
+    
     async fn detect_circular_dependencies(&self) -> TylResult<Vec<Vec<String>>> {
+        // Closed variable-length patterns (t)-[*]->(t) are not supported by every
+        // FalkorDB version, so find the edges that lie on a cycle instead
         let query = "
-            MATCH (t:Task)-[:DEPENDS_ON*]->(t)
-            WITH collect(DISTINCT t.id) as cycle
-            WHERE size(cycle) > 1
-            RETURN cycle
+            MATCH (a:Task)-[:DEPENDS_ON]->(b:Task)
+            WHERE (b)-[:DEPENDS_ON*]->(a)
+            RETURN DISTINCT a.id AS from_id, b.id AS to_id
         ";
-        let _result = self.adapter.execute_cypher(query).await?;
+        let result = self.execute_cypher(query).await?;
+        
+        let edges: Vec<(String, String)> = result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let from = row.get("from_id")?.as_str()?;
+                    let to = row.get("to_id")?.as_str()?;
+                    Some((from.to_string(), to.to_string()))
+                })
+                .collect())
+            .unwrap_or_default();
         
-        // In a real implementation, we would parse the cycles
-        Ok(vec![])
+        Ok(cycles_from_edges(&edges))
+    }
+    
+    async fn find_dependencies_between(&self, task_ids: &[String]) -> TylResult<Vec<TaskDependency>> {
+        if task_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        
+        let query = "MATCH (a:Task)-[r:DEPENDS_ON]->(b:Task) WHERE a.id IN $task_ids AND b.id IN $task_ids RETURN r";
+        let result = self.execute_cypher_with_params(query, cypher_params([("task_ids", json!(task_ids))])).await?;
+        
+        self.parse_dependencies_from_cypher_results(&result)
+    }
+    
+    async fn find_dependency_edges(&self) -> TylResult<Vec<(String, String)>> {
+        let result = self
+            .execute_cypher("MATCH (a:Task)-[:DEPENDS_ON]->(b:Task) RETURN a.id AS from_id, b.id AS to_id")
+            .await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let from = row.get("from_id")?.as_str()?;
+                    let to = row.get("to_id")?.as_str()?;
+                    Some((from.to_string(), to.to_string()))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn find_dependencies_with_tasks(&self) -> TylResult<Vec<(TaskDependency, Task, Task)>> {
+        let result = self
+            .execute_cypher("MATCH (t:Task)-[r:DEPENDS_ON]->(to:Task) RETURN r, t, to")
+            .await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let dependency = self.parse_dependency_from_cypher_result(row).ok()?;
+                    let from = self.parse_task_from_cypher_result(row).ok()?;
+                    let to_node = row.get("to")?;
+                    let to = self.parse_task_from_json(to_node.get("properties").unwrap_or(to_node)).ok()?;
+                    Some((dependency, from, to))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn find_cross_project_dependencies(&self) -> TylResult<Vec<CrossProjectDependency>> {
+        let result = self
+            .execute_cypher(
+                "MATCH (t:Task)-[r:DEPENDS_ON]->(to:Task)
+                 MATCH (t)-[:BELONGS_TO_PROJECT]->(pa:Project)
+                 MATCH (to)-[:BELONGS_TO_PROJECT]->(pb:Project)
+                 WHERE pa.id <> pb.id
+                 RETURN r, t, pa.id AS from_project, to, pb.id AS to_project
+                 ORDER BY pa.id, t.id",
+            )
+            .await?;
+        
+        let now = Utc::now();
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let dependency = self.parse_dependency_from_cypher_result(row).ok()?;
+                    let from = self.parse_task_from_cypher_result(row).ok()?;
+                    let to_node = row.get("to")?;
+                    let to = self.parse_task_from_json(to_node.get("properties").unwrap_or(to_node)).ok()?;
+                    let from_project = row.get("from_project")?.as_str()?.to_string();
+                    let to_project = row.get("to_project")?.as_str()?.to_string();
+                    Some(CrossProjectDependency::new(&dependency, (&from, from_project), (&to, to_project), now))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn delete_tasks(&self, task_ids: &[String]) -> TylResult<()> {
+        if task_ids.is_empty() {
+            return Ok(());
+        }
+        
+        let query = "MATCH (t:Task) WHERE t.id IN $task_ids DETACH DELETE t";
+        self.execute_cypher_with_params(query, cypher_params([("task_ids", json!(task_ids))])).await?;
+        Ok(())
+    }
+    
+    async fn update_tasks_status(&self, task_ids: &[String], status: TaskStatus) -> TylResult<()> {
+        if task_ids.is_empty() {
+            return Ok(());
+        }
+        
+        // Same timestamps as Task::set_status
+        let mut assignments = vec![
+            "t.status = $status",
+            "t.updated_at = $now",
+            "t.version = coalesce(t.version, 0) + 1",
+        ];
+        match status {
+            TaskStatus::InProgress => assignments.push("t.started_at = coalesce(t.started_at, $now)"),
+            TaskStatus::Done => assignments.push("t.completed_at = $now"),
+            _ => {}
+        }
+        
+        let query = format!("UNWIND $task_ids AS id MATCH (t:Task {{id: id}}) SET {}", assignments.join(", "));
+        let params = cypher_params([
+            ("task_ids", json!(task_ids)),
+            ("status", json!(status)),
+            ("now", json!(Utc::now().to_rfc3339())),
+        ]);
+        self.execute_cypher_with_params(&query, params).await?;
+        Ok(())
+    }
+    
+    async fn merge_task_relationships(&self, source_id: &str, target_id: &str, options: &MergeOptions) -> TylResult<()> {
+        let params = cypher_params([("source_id", json!(source_id)), ("target_id", json!(target_id))]);
+        
+        // Relationships cannot be re-pointed, so each one is copied onto the target before it is deleted
+        if options.transfer_dependencies {
+            let outgoing = "MATCH (s:Task {id: $source_id})-[r:DEPENDS_ON]->(d:Task), (t:Task {id: $target_id}) 
+                 WHERE d.id <> t.id AND NOT (t)-[:DEPENDS_ON]->(d) 
+                 CREATE (t)-[n:DEPENDS_ON]->(d) SET n = properties(r)";
+            let incoming = "MATCH (d:Task)-[r:DEPENDS_ON]->(s:Task {id: $source_id}), (t:Task {id: $target_id}) 
+                 WHERE d.id <> t.id AND NOT (d)-[:DEPENDS_ON]->(t) 
+                 CREATE (d)-[n:DEPENDS_ON]->(t) SET n = properties(r)";
+            self.execute_cypher_with_params(outgoing, params.clone()).await?;
+            self.execute_cypher_with_params(incoming, params.clone()).await?;
+            self.execute_cypher_with_params("MATCH (s:Task {id: $source_id})-[r:DEPENDS_ON]-() DELETE r", params.clone()).await?;
+        }
+        
+        if options.transfer_subtasks {
+            // The target itself stops being a subtask of the source rather than becoming its own
+            let query = "MATCH (child:Task)-[r:SUBTASK_OF]->(s:Task {id: $source_id}), (t:Task {id: $target_id}) 
+                 FOREACH (_ IN CASE WHEN child.id <> t.id THEN [1] ELSE [] END | CREATE (child)-[:SUBTASK_OF]->(t)) 
+                 DELETE r";
+            self.execute_cypher_with_params(query, params.clone()).await?;
+        }
+        
+        if options.transfer_comments {
+            let query = "MATCH (s:Task {id: $source_id})-[r:HAS_COMMENT]->(c:Comment), (t:Task {id: $target_id}) 
+                 CREATE (t)-[:HAS_COMMENT]->(c) 
+                 DELETE r";
+            self.execute_cypher_with_params(query, params).await?;
+        }
+        Ok(())
+    }
+    
+    fn export_tasks_streaming(&self, filter: &TaskFilter) -> BoxStream<'static, TylResult<Task>> {
+        if let Some(ref cursor) = filter.cursor {
+            if let Err(e) = TaskCursor::decode(cursor) {
+                return stream::once(async move { Err(e) }).boxed();
+            }
+        }
+        let repository = self.clone();
+        let (where_clause, params) = self.build_filter_clause(filter);
+        let query = format!("MATCH (t:Task) {} RETURN t ORDER BY t.id", where_clause);
+        self.adapter.execute_cypher_streaming_with_params(&query, params)
+            .map(move |row| row.and_then(|row| repository.parse_task_from_cypher_result(&row)))
+            .boxed()
+    }
+    
+    async fn archive_completed_tasks_before(&self, cutoff: DateTime<Utc>, batch_size: u32) -> TylResult<ArchiveResult> {
+        let mut result = ArchiveResult::default();
+        let batch_size = batch_size.max(1);
+        
+        // Archived tasks stay in the graph but lose the :Task label, which takes
+        // them out of every task query while keeping their relationships
+        loop {
+            let query = format!(
+                "MATCH (t:Task) WHERE t.status = 'done' AND t.completed_at < $cutoff AND NOT t.id IN $failed_ids \
+                 RETURN t.id AS id LIMIT {}",
+                batch_size
+            );
+            let params = cypher_params([
+                ("cutoff", json!(cutoff.to_rfc3339())),
+                ("failed_ids", json!(result.failed_ids)),
+            ]);
+            let rows = self.execute_cypher_with_params(&query, params).await?;
+            let ids: Vec<String> = rows.as_array()
+                .map(|rows| rows.iter()
+                    .filter_map(|row| row.get("id").and_then(|v| v.as_str()).map(str::to_string))
+                    .collect())
+                .unwrap_or_default();
+            
+            if ids.is_empty() {
+                break;
+            }
+            let batch_len = ids.len();
+            
+            for id in ids {
+                let archive = "MATCH (t:Task {id: $id}) SET t:ArchivedTask, t.archived_at = $archived_at REMOVE t:Task";
+                let params = cypher_params([("id", json!(id)), ("archived_at", json!(Utc::now().to_rfc3339()))]);
+                match self.execute_cypher_with_params(archive, params).await {
+                    Ok(_) => result.archived_count += 1,
+                    Err(e) => {
+                        tracing::warn!("Failed to archive task {}: {}", id, e);
+                        result.failed_ids.push(id);
+                    }
+                }
+            }
+            
+            if batch_len < batch_size as usize {
+                break;
+            }
+        }
+        
+        Ok(result)
+    }
+    
+    async fn archive_task(&self, task_id: &str, archive_graph: &str) -> TylResult<()> {
+        let archive = self.archive_graphs.get(archive_graph)
+            .ok_or_else(|| TylError::configuration(format!("Archive graph '{}' is not configured", archive_graph)))?;
+        let task = self.find_task_by_id(task_id).await?
+            .ok_or_else(|| TylError::not_found("task", task_id))?;
+        let id_params = cypher_params([("id", json!(task_id))]);
+        
+        let mut node = self.task_to_graph_node(&task)?;
+        node.properties.insert("version".to_string(), json!(task.version));
+        node.properties.insert("archived_at".to_string(), json!(Utc::now().to_rfc3339()));
+        let properties = serde_json::Value::Object(node.properties.into_iter().collect());
+        
+        // Replace an earlier copy, left by a run that failed before the delete below
+        let copy_queries = [
+            ("MATCH (t:Task {id: $id}) DELETE t".to_string(), id_params.clone()),
+            (format!("CREATE (n:{}) SET n = $properties", node.labels.join(":")), cypher_params([("properties", properties)])),
+        ];
+        for (query, params) in copy_queries {
+            telemetry::time_graph_query(
+                telemetry::cypher_query_type(&query),
+                archive.execute_cypher_with_params(&query, params),
+            ).await?;
+        }
+        
+        self.execute_cypher_with_params("MATCH (t:Task {id: $id}) DETACH DELETE t", id_params).await?;
+        Ok(())
+    }
+    
+    async fn collect_alert_metrics(&self) -> TylResult<AlertMetrics> {
+        let now = Utc::now().to_rfc3339();
+        
+        let overdue_tasks = self.query_scalar(
+            "MATCH (t:Task) WHERE t.due_date < $now AND NOT t.status IN ['done', 'cancelled'] 
+             RETURN count(t) AS value",
+            cypher_params([("now", json!(now))])
+        ).await?;
+        
+        // Share of a project's open tasks that are neither blocked nor overdue
+        let lowest_project_health_score = self.query_scalar(
+            "MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p:Project) 
+             WHERE NOT t.status IN ['done', 'cancelled'] 
+             WITH p, count(t) AS open, 
+                  sum(CASE WHEN t.status = 'blocked' OR t.due_date < $now THEN 1 ELSE 0 END) AS troubled 
+             RETURN min(100.0 * (open - troubled) / open) AS value",
+            cypher_params([("now", json!(now))])
+        ).await?;
+        
+        let max_single_user_task_count = self.query_scalar(
+            "MATCH (t:Task)-[:ASSIGNED_TO]->(u:User) 
+             WHERE NOT t.status IN ['done', 'cancelled'] 
+             WITH u, count(t) AS open 
+             RETURN max(open) AS value",
+            CypherParams::new()
+        ).await?;
+        
+        let max_dependency_chain_length = self.query_scalar(
+            "MATCH path = (:Task)-[:DEPENDS_ON*]->(:Task) 
+             RETURN max(length(path)) + 1 AS value",
+            CypherParams::new()
+        ).await?;
+        
+        Ok(AlertMetrics {
+            overdue_tasks: overdue_tasks.unwrap_or(0.0) as u32,
+            lowest_project_health_score,
+            max_single_user_task_count: max_single_user_task_count.unwrap_or(0.0) as u32,
+            max_dependency_chain_length: max_dependency_chain_length.unwrap_or(0.0) as u32,
+        })
+    }
+    
+    async fn save_bottleneck_actions(&self, project_id: &str, bottleneck: &Bottleneck) -> TylResult<()> {
+        let query = "MATCH (p:Project {id: $project_id}) 
+             MERGE (p)-[:HAS_BOTTLENECK]->(:Bottleneck {id: $bottleneck_id})";
+        let params = cypher_params([("project_id", json!(project_id)), ("bottleneck_id", json!(bottleneck.bottleneck_id))]);
+        self.execute_cypher_with_params(query, params).await?;
+        
+        for (index, action) in bottleneck.suggested_actions.iter().enumerate() {
+            let query = "MATCH (b:Bottleneck {id: $bottleneck_id}) 
+                 MERGE (b)-[:SUGGESTS]->(a:BottleneckAction {action_index: $action_index}) 
+                 ON CREATE SET a.description = $description, a.resolved = false";
+            let params = cypher_params([
+                ("bottleneck_id", json!(bottleneck.bottleneck_id)),
+                ("action_index", json!(index)),
+                ("description", json!(action)),
+            ]);
+            self.execute_cypher_with_params(query, params).await?;
+        }
+        Ok(())
+    }
+    
+    async fn save_bottleneck_resolution(&self, resolution: &BottleneckResolution) -> TylResult<()> {
+        let query = "MATCH (:Bottleneck {id: $bottleneck_id})-[:SUGGESTS]->(a:BottleneckAction {action_index: $action_index}) 
+             SET a.resolved = $resolved, a.resolved_at = $resolved_at, a.resolved_by = $resolved_by";
+        let params = cypher_params([
+            ("bottleneck_id", json!(resolution.bottleneck_id)),
+            ("action_index", json!(resolution.suggested_action_index)),
+            ("resolved", json!(resolution.resolved)),
+            ("resolved_at", json!(resolution.resolved_at.map(|at| at.to_rfc3339()))),
+            ("resolved_by", json!(resolution.resolved_by)),
+        ]);
+        self.execute_cypher_with_params(query, params).await?;
+        Ok(())
+    }
+    
+    async fn find_bottleneck_resolutions(&self, bottleneck_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        let query = "MATCH (b:Bottleneck {id: $bottleneck_id})-[:SUGGESTS]->(a:BottleneckAction) 
+             RETURN b.id AS bottleneck_id, a.action_index AS action_index, a.resolved AS resolved, 
+                    a.resolved_at AS resolved_at, a.resolved_by AS resolved_by 
+             ORDER BY a.action_index";
+        let params = cypher_params([("bottleneck_id", json!(bottleneck_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        Ok(bottleneck_resolutions_from_rows(&result))
+    }
+    
+    async fn find_project_bottleneck_resolutions(&self, project_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        let query = "MATCH (:Project {id: $project_id})-[:HAS_BOTTLENECK]->(b:Bottleneck)-[:SUGGESTS]->(a:BottleneckAction) 
+             RETURN b.id AS bottleneck_id, a.action_index AS action_index, a.resolved AS resolved, 
+                    a.resolved_at AS resolved_at, a.resolved_by AS resolved_by 
+             ORDER BY b.id, a.action_index";
+        let params = cypher_params([("project_id", json!(project_id))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        Ok(bottleneck_resolutions_from_rows(&result))
+    }
+    
+    async fn save_audit_entry(&self, entry: &AuditEntry) -> TylResult<()> {
+        // The entity type is used as the node label, so only letters are allowed in it
+        if entry.entity_type.is_empty() || !entry.entity_type.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(TylError::validation("entity_type", "Audit entity type must be a node label"));
+        }
+        
+        let mut properties = serde_json::Map::new();
+        properties.insert("id".to_string(), json!(entry.id));
+        properties.insert("entity_type".to_string(), json!(entry.entity_type));
+        properties.insert("entity_id".to_string(), json!(entry.entity_id));
+        properties.insert("action".to_string(), json!(entry.action));
+        properties.insert("reason".to_string(), json!(entry.reason));
+        properties.insert("created_at".to_string(), json!(entry.created_at.to_rfc3339()));
+        if let Some(ref previous_value) = entry.previous_value {
+            properties.insert("previous_value".to_string(), json!(previous_value));
+        }
+        if let Some(ref new_value) = entry.new_value {
+            properties.insert("new_value".to_string(), json!(new_value));
+        }
+        
+        let query = format!(
+            "MATCH (n:{} {{id: $entity_id}}) 
+             CREATE (n)-[:HAS_AUDIT_ENTRY]->(e:AuditEntry) SET e = $properties", 
+            entry.entity_type
+        );
+        let params = cypher_params([("entity_id", json!(entry.entity_id)), ("properties", serde_json::Value::Object(properties))]);
+        self.execute_cypher_with_params(&query, params).await?;
+        Ok(())
+    }
+    
+    async fn count_daily_completions(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        // completed_at is stored as RFC 3339 text, so its first ten characters are the day
+        let query = "MATCH (t:Task {status: 'done'})-[:BELONGS_TO_PROJECT]->(:Project {id: $project_id}) 
+             WHERE t.completed_at >= $since 
+             RETURN left(t.completed_at, 10) AS day, count(t) AS completed 
+             ORDER BY day";
+        let params = cypher_params([("project_id", json!(project_id)), ("since", json!(since.to_rfc3339()))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let day = NaiveDate::parse_from_str(row.get("day")?.as_str()?, "%Y-%m-%d").ok()?;
+                    Some((day, row.get("completed")?.as_u64()? as u32))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn count_daily_creations(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        // created_at is stored as RFC 3339 text, so its first ten characters are the day
+        let query = "MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(:Project {id: $project_id}) 
+             WHERE t.created_at >= $since 
+             RETURN left(t.created_at, 10) AS day, count(t) AS created 
+             ORDER BY day";
+        let params = cypher_params([("project_id", json!(project_id)), ("since", json!(since.to_rfc3339()))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let day = NaiveDate::parse_from_str(row.get("day")?.as_str()?, "%Y-%m-%d").ok()?;
+                    Some((day, row.get("created")?.as_u64()? as u32))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn find_completed_tasks_for_user(&self, user_id: &str, since: DateTime<Utc>) -> TylResult<Vec<Task>> {
+        let query = "MATCH (t:Task {status: 'done'})-[:ASSIGNED_TO]->(:User {id: $user_id}) 
+             WHERE t.completed_at >= $since 
+             RETURN t 
+             ORDER BY t.completed_at";
+        let params = cypher_params([("user_id", json!(user_id)), ("since", json!(since.to_rfc3339()))]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
+    async fn find_estimated_completed_tasks(
+        &self,
+        user_id: Option<&str>,
+        context: Option<TaskContext>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<Task>> {
+        let mut conditions = vec![
+            "t.status = 'done'".to_string(),
+            "t.started_at IS NOT NULL".to_string(),
+            "t.estimated_date IS NOT NULL".to_string(),
+            "t.completed_at >= $since".to_string(),
+        ];
+        let mut params = cypher_params([("since", json!(since.to_rfc3339()))]);
+        if let Some(user_id) = user_id {
+            conditions.push("EXISTS((t)-[:ASSIGNED_TO]->(:User {id: $user_id}))".to_string());
+            params.insert("user_id".to_string(), json!(user_id));
+        }
+        if let Some(context) = context {
+            conditions.push("t.context = $context".to_string());
+            params.insert("context".to_string(), json!(format!("{:?}", context).to_lowercase()));
+        }
+        
+        let query = format!("MATCH (t:Task) WHERE {} RETURN t", conditions.join(" AND "));
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        self.parse_tasks_from_cypher_results(&result)
+    }
+    
+    async fn find_recurring_task_instances(
+        &self,
+        user_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<(String, Task)>> {
+        let mut conditions = vec![
+            "t.recurrence IS NOT NULL".to_string(),
+            "t.due_date >= $since".to_string(),
+        ];
+        let mut params = cypher_params([("since", json!(since.to_rfc3339()))]);
+        if let Some(user_id) = user_id {
+            conditions.push("EXISTS((t)-[:ASSIGNED_TO]->(:User {id: $user_id}))".to_string());
+            params.insert("user_id".to_string(), json!(user_id));
+        }
+        
+        // Instances point at the previous one, so the series starts at the end of the chain
+        let query = format!(
+            "MATCH (t:Task) WHERE {}
+             OPTIONAL MATCH (t)-[:RECURRENCE_PARENT*]->(root:Task)
+             WHERE NOT (root)-[:RECURRENCE_PARENT]->(:Task)
+             RETURN t, coalesce(root.id, t.id) AS series_id
+             ORDER BY series_id, t.due_date",
+            conditions.join(" AND ")
+        );
+        let result = self.execute_cypher_with_params(&query, params).await?;
+        
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let task = self.parse_task_from_cypher_result(row).ok()?;
+                    let series_id = row.get("series_id")?.as_str()?.to_string();
+                    Some((series_id, task))
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Parse a row returned by the project lookup query
+pub(crate) fn project_from_row(row: &serde_json::Value) -> Option<Project> {
+    let text = |key: &str| row.get(key).and_then(|v| v.as_str()).map(String::from);
+    let date = |key: &str| row.get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|v| v.with_timezone(&Utc));
+    
+    Some(Project {
+        id: text("id")?,
+        code: text("code").unwrap_or_default(),
+        name: text("name").unwrap_or_default(),
+        description: text("description"),
+        status: text("status").unwrap_or_else(|| "active".to_string()),
+        start_date: date("start_date"),
+        end_date: date("end_date"),
+        project_lead_user_id: text("project_lead_user_id"),
+        created_at: date("created_at").unwrap_or_else(Utc::now),
+        updated_at: date("updated_at").unwrap_or_else(Utc::now),
+    })
+}
+
+/// Parse rows returned by the bottleneck action queries
+fn bottleneck_resolutions_from_rows(result: &serde_json::Value) -> Vec<BottleneckResolution> {
+    result.as_array()
+        .map(|rows| rows.iter()
+            .filter_map(|row| Some(BottleneckResolution {
+                bottleneck_id: row.get("bottleneck_id")?.as_str()?.to_string(),
+                suggested_action_index: row.get("action_index")?.as_u64()? as u32,
+                resolved: row.get("resolved").and_then(|r| r.as_bool()).unwrap_or(false),
+                resolved_at: row.get("resolved_at")
+                    .and_then(|at| at.as_str())
+                    .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                    .map(|at| at.with_timezone(&Utc)),
+                resolved_by: row.get("resolved_by").and_then(|by| by.as_str()).map(String::from),
+            }))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Status as stored on task nodes, e.g. `in_progress`
+fn status_value(status: &TaskStatus) -> String {
+    json!(status).as_str().unwrap_or_default().to_string()
+}
+
+/// Group edges known to lie on cycles into one representative cycle per
+/// strongly connected component
+///
+/// Each cycle is returned closed, starting and ending with the same task id.
+pub(crate) fn cycles_from_edges(edges: &[(String, String)]) -> Vec<Vec<String>> {
+    use std::collections::{BTreeMap, BTreeSet};
+    
+    let mut adjacency: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (from, to) in edges {
+        adjacency.entry(from.as_str()).or_default().insert(to.as_str());
+    }
+    
+    let reachable_from = |start: &str| -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for next in adjacency.get(node).into_iter().flatten() {
+                if seen.insert(next.to_string()) {
+                    stack.push(*next);
+                }
+            }
+        }
+        seen
+    };
+    
+    let mut assigned: HashSet<String> = HashSet::new();
+    let mut cycles = Vec::new();
+    
+    for &start in adjacency.keys() {
+        if assigned.contains(start) {
+            continue;
+        }
+        
+        let forward = reachable_from(start);
+        let component: BTreeSet<&str> = forward.iter()
+            .map(String::as_str)
+            .filter(|node| reachable_from(*node).contains(start))
+            .chain(std::iter::once(start))
+            .collect();
+        
+        // Walk inside the component until a task repeats; that loop is the cycle
+        let mut path = vec![start];
+        let mut current = start;
+        loop {
+            let next = match adjacency.get(current)
+                .and_then(|targets| targets.iter().find(|t| component.contains(*t)))
+            {
+                Some(next) => *next,
+                None => break,
+            };
+            if let Some(pos) = path.iter().position(|id| *id == next) {
+                let mut cycle: Vec<String> = path[pos..].iter().map(|id| id.to_string()).collect();
+                cycle.push(next.to_string());
+                cycles.push(cycle);
+                break;
+            }
+            path.push(next);
+            current = next;
+        }
+        
+        assigned.extend(component.into_iter().map(str::to_string));
     }
+    
+    cycles
 }
 
 #[cfg(test)]
@@ -819,13 +2303,87 @@ mod tests {
             ..Default::default()
         };
         
-        let clause = repo.build_filter_clause(&filter);
+        let (clause, params) = repo.build_filter_clause(&filter);
         
         // Check that the clause contains expected conditions
         assert!(clause.contains("WHERE"));
-        assert!(clause.contains("t.status IN"));
-        assert!(clause.contains("t.priority IN"));
-        assert!(clause.contains("t.context IN"));
-        assert!(clause.contains("EXISTS((t)<-[:ASSIGNED_TO]-(u:User {id: 'user123'}))"));
+        assert!(clause.contains("t.status IN $statuses"));
+        assert!(clause.contains("t.priority IN $priorities"));
+        assert!(clause.contains("t.context IN $contexts"));
+        assert!(clause.contains("EXISTS((t)<-[:ASSIGNED_TO]-(u:User {id: $assigned_user_id}))"));
+        assert_eq!(params["statuses"], json!(["ready", "in_progress"]));
+        assert_eq!(params["priorities"], json!(["high"]));
+        assert_eq!(params["contexts"], json!(["work"]));
+        assert_eq!(params["assigned_user_id"], "user123");
+        
+        let filter = TaskFilter { priority_changed_more_than: Some(2), ..Default::default() };
+        assert_eq!(
+            repo.build_filter_clause(&filter),
+            ("WHERE t.priority_change_count > $priority_changes".to_string(), cypher_params([("priority_changes", json!(2))]))
+        );
+        
+        let due_before = Utc::now();
+        let filter = TaskFilter { due_before: Some(due_before), ..Default::default() };
+        let (clause, params) = repo.build_filter_clause(&filter);
+        assert_eq!(clause, "WHERE t.due_date < $due_before");
+        assert_eq!(params["due_before"], due_before.to_rfc3339());
+        
+        let filter = TaskFilter { tags: Some(vec!["api".to_string(), "o'clock".to_string()]), ..Default::default() };
+        let (clause, params) = repo.build_filter_clause(&filter);
+        assert_eq!(clause, "WHERE EXISTS((t)-[:TAGGED_WITH]->(g:Tag) WHERE g.name IN $tags)");
+        assert_eq!(params["tags"], json!(["api", "o'clock"]));
+        let filter = TaskFilter { require_all_tags: true, ..filter };
+        let (clause, params) = repo.build_filter_clause(&filter);
+        assert_eq!(
+            clause,
+            "WHERE EXISTS((t)-[:TAGGED_WITH]->(:Tag {name: $tag_0})) AND EXISTS((t)-[:TAGGED_WITH]->(:Tag {name: $tag_1}))"
+        );
+        assert_eq!(params["tag_1"], "o'clock");
+        
+        let task = Task::new("TEST-001".to_string(), "Test Task".to_string(), TaskContext::Work);
+        let filter = TaskFilter { cursor: Some(TaskCursor::encode(&task).into_string()), ..Default::default() };
+        let (clause, params) = repo.build_filter_clause(&filter);
+        assert_eq!(
+            clause,
+            "WHERE (t.created_at < $cursor_created_at OR (t.created_at = $cursor_created_at AND t.id < $cursor_id))"
+        );
+        assert_eq!(params["cursor_created_at"], task.created_at.to_rfc3339());
+        assert_eq!(params["cursor_id"], "TEST-001");
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_build_filter_clause_overdue_subtasks() {
+        let config = RedisConfig::default();
+        let mock_adapter = FalkorDBAdapter::new(config, "test".to_string()).await.unwrap();
+        let repo = GraphTaskRepository::new(mock_adapter, "test".to_string());
+        
+        let filter = TaskFilter { has_overdue_subtasks: Some(true), ..Default::default() };
+        let (clause, params) = repo.build_filter_clause(&filter);
+        assert!(clause.starts_with("WHERE EXISTS((t)<-[:SUBTASK_OF]-(child:Task) WHERE child.due_date < $now"));
+        assert!(params.contains_key("now"));
+        assert!(clause.contains("child.status NOT IN ['done', 'cancelled']"));
+        
+        // Parents without overdue subtasks are the negation
+        let filter = TaskFilter { has_overdue_subtasks: Some(false), ..Default::default() };
+        let (clause, _) = repo.build_filter_clause(&filter);
+        assert!(clause.starts_with("WHERE NOT EXISTS((t)<-[:SUBTASK_OF]-(child:Task)"));
+        
+        assert!(repo.build_filter_clause(&TaskFilter::default()).0.is_empty());
+    }
+    
+    #[test]
+    fn test_cycles_from_edges() {
+        let edge = |a: &str, b: &str| (a.to_string(), b.to_string());
+        let edges = vec![
+            edge("A", "B"), edge("B", "C"), edge("C", "A"),
+            edge("X", "Y"), edge("Y", "X"),
+        ];
+        
+        let cycles = cycles_from_edges(&edges);
+        
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0], vec!["A", "B", "C", "A"]);
+        assert_eq!(cycles[1], vec!["X", "Y", "X"]);
+        assert!(cycles_from_edges(&[]).is_empty());
+    }
+}
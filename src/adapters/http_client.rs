@@ -298,6 +298,7 @@ mod tests {
             timeout_ms: 5000,
             retry_attempts: 3,
             retry_delay_ms: 1000,
+            user_service_url: None,
         }
     }
 
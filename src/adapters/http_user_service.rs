@@ -0,0 +1,114 @@
+//! User service adapter over HTTP
+//!
+//! Looks users up with `GET <user_service_url>/users/<id>`, which answers with
+//! a JSON [`User`] or `404` for unknown users. Connection failures and server
+//! errors are retried as configured in [`ExternalConfig`].
+
+use async_trait::async_trait;
+use reqwest::{Client, ClientBuilder, StatusCode, Url};
+use std::time::Duration;
+use tracing::warn;
+use tyl_errors::{TylError, TylResult};
+
+use crate::config::ExternalConfig;
+use crate::domain::user_port::{User, UserServicePort};
+use crate::middleware::correlation::{current_correlation_id, CORRELATION_ID_HEADER};
+use crate::{TaskServiceError, TaskServiceResult};
+
+/// [`UserServicePort`] backed by the user service's HTTP API
+pub struct HttpUserServiceAdapter {
+    client: Client,
+    base_url: Url,
+    config: ExternalConfig,
+}
+
+impl HttpUserServiceAdapter {
+    pub fn new(base_url: &str, config: ExternalConfig) -> TaskServiceResult<Self> {
+        let base_url = Url::parse(base_url).map_err(|e| TaskServiceError::Configuration {
+            message: format!("Invalid user service URL '{}': {}", base_url, e),
+        })?;
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .user_agent("tyl-task-service/1.0")
+            .build()
+            .map_err(|e| TaskServiceError::ExternalService {
+                message: format!("Failed to create HTTP client: {}", e),
+            })?;
+
+        Ok(Self { client, base_url, config })
+    }
+
+    /// URL of the user, with the id escaped as a single path segment
+    fn user_url(&self, user_id: &str) -> Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().push("users").push(user_id);
+        }
+        url
+    }
+
+    async fn fetch_user(&self, url: &Url) -> TaskServiceResult<Option<User>> {
+        let mut request = self.client.get(url.clone());
+        if let Some(correlation_id) = current_correlation_id() {
+            request = request.header(CORRELATION_ID_HEADER, correlation_id);
+        }
+        let response = request.send().await.map_err(|e| TaskServiceError::ExternalService {
+            message: format!("User lookup at {} failed: {}", url, e),
+        })?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => response.json::<User>().await
+                .map(Some)
+                .map_err(|e| TaskServiceError::ExternalService {
+                    message: format!("Invalid user from {}: {}", url, e),
+                }),
+            status => Err(TaskServiceError::ExternalService {
+                message: format!("User lookup at {} failed with status: {}", url, status),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl UserServicePort for HttpUserServiceAdapter {
+    async fn get_user(&self, user_id: &str) -> TylResult<Option<User>> {
+        let url = self.user_url(user_id);
+        let attempts = self.config.retry_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match self.fetch_user(&url).await {
+                Ok(user) => return Ok(user),
+                Err(e) if attempt < attempts => {
+                    warn!(attempt = attempt, max_attempts = attempts, error = %e, "User lookup failed, retrying...");
+                    tokio::time::sleep(Duration::from_millis(self.config.retry_delay_ms)).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(TylError::from(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn external_config() -> ExternalConfig {
+        ExternalConfig {
+            timeout_ms: 1000,
+            retry_attempts: 1,
+            retry_delay_ms: 0,
+            user_service_url: None,
+        }
+    }
+
+    #[test]
+    fn test_user_url_escapes_user_id() {
+        let adapter = HttpUserServiceAdapter::new("http://users.internal/api/", external_config()).unwrap();
+        assert_eq!(adapter.user_url("user-1").as_str(), "http://users.internal/api/users/user-1");
+        assert_eq!(adapter.user_url("a/b c").as_str(), "http://users.internal/api/users/a%2Fb%20c");
+
+        assert!(HttpUserServiceAdapter::new("not a url", external_config()).is_err());
+    }
+}
@@ -0,0 +1,1477 @@
+//! In-memory task repository for tests
+//!
+//! [`InMemoryTaskRepository`] implements the whole [`TaskRepository`] trait on
+//! plain collections, following the semantics of the graph repository: writes
+//! touching a missing task, user or project are ignored, deleting a task drops
+//! every relationship it has and archived tasks disappear from all queries.
+//! [`InMemoryTaskRepository::snapshot`] and [`InMemoryTaskRepository::from_snapshot`]
+//! let tests assert on and seed the stored state. [`InMemoryUserAdapter`] stands
+//! in for the user service.
+//!
+//! Only compiled with the `testing` feature.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use tyl_errors::{TylError, TylResult};
+
+use super::graph_repository::cycles_from_edges;
+use crate::domain::user_port::{User, UserServicePort};
+use crate::domain::{
+    AlertMetrics, ArchiveResult, AuditEntry, Bottleneck, BottleneckResolution, CrossProjectDependency,
+    CustomPropertiesPatch, DependencyPath, DependencyType, ExternalTicketLink, MergeOptions, NewTask, Project, ProjectSnapshot, ProjectWorkflowConfig, Tag, TagUsage,
+    Task, TaskComment, TaskContext, TaskCursor, TaskDependency, TaskEdge, TaskFilter, TaskNode, TaskRepository, TaskSearchResult,
+    TaskStatus, TaskSubgraph, WipViolation,
+    PageRequest, PagedResult, SortSpec, TaskSearchQuery, search_tasks_in_memory,
+};
+
+/// Everything stored by an [`InMemoryTaskRepository`]
+///
+/// Relationships refer to tasks, users and projects by id.
+#[derive(Debug, Clone, Default)]
+pub struct RepositorySnapshot {
+    pub tasks: HashMap<String, Task>,
+    /// Tasks taken out of every query by `archive_completed_tasks_before` or `archive_task`
+    pub archived_tasks: HashMap<String, Task>,
+    pub dependencies: HashMap<String, TaskDependency>,
+    /// Tombstones of removed dependencies by id
+    pub deleted_dependencies: HashMap<String, TaskDependency>,
+    /// Child task id to parent task id
+    pub parents: HashMap<String, String>,
+    pub users: HashSet<String>,
+    /// `(task_id, user_id)` to assignment role
+    pub assignments: BTreeMap<(String, String), String>,
+    /// `(task_id, reviewer_id)` to the time the review was first requested
+    pub review_requests: BTreeMap<(String, String), DateTime<Utc>>,
+    pub projects: HashMap<String, Project>,
+    /// Task id to project id
+    pub project_tasks: HashMap<String, String>,
+    pub workflow_configs: HashMap<String, ProjectWorkflowConfig>,
+    pub project_snapshots: HashMap<String, ProjectSnapshot>,
+    /// Tags by name; which tasks carry them is given by `Task::tags`
+    pub tags: HashMap<String, Tag>,
+    pub external_links: Vec<ExternalTicketLink>,
+    /// Comments by id
+    pub comments: HashMap<String, TaskComment>,
+    pub audit_entries: Vec<AuditEntry>,
+    /// Bottleneck id to project id
+    pub bottlenecks: HashMap<String, String>,
+    /// `(bottleneck_id, action_index)` to the state of the suggested action
+    pub bottleneck_actions: BTreeMap<(String, u32), BottleneckResolution>,
+}
+
+/// Everything but the tasks, kept behind its own lock
+#[derive(Debug, Clone, Default)]
+struct Relations {
+    archived_tasks: HashMap<String, Task>,
+    dependencies: HashMap<String, TaskDependency>,
+    deleted_dependencies: HashMap<String, TaskDependency>,
+    parents: HashMap<String, String>,
+    users: HashSet<String>,
+    assignments: BTreeMap<(String, String), String>,
+    review_requests: BTreeMap<(String, String), DateTime<Utc>>,
+    projects: HashMap<String, Project>,
+    project_tasks: HashMap<String, String>,
+    workflow_configs: HashMap<String, ProjectWorkflowConfig>,
+    project_snapshots: HashMap<String, ProjectSnapshot>,
+    tags: HashMap<String, Tag>,
+    external_links: Vec<ExternalTicketLink>,
+    comments: HashMap<String, TaskComment>,
+    audit_entries: Vec<AuditEntry>,
+    bottlenecks: HashMap<String, String>,
+    bottleneck_actions: BTreeMap<(String, u32), BottleneckResolution>,
+}
+
+impl Relations {
+    fn is_assigned(&self, task_id: &str, user_id: &str) -> bool {
+        self.assignments.contains_key(&(task_id.to_string(), user_id.to_string()))
+    }
+
+    fn project_of(&self, task_id: &str) -> Option<&str> {
+        self.project_tasks.get(task_id).map(String::as_str)
+    }
+
+    /// Forget every relationship of a deleted task
+    fn detach(&mut self, task_id: &str) {
+        self.dependencies.retain(|_, d| d.from_task_id != task_id && d.to_task_id != task_id);
+        self.parents.retain(|child, parent| child != task_id && parent != task_id);
+        self.assignments.retain(|(task, _), _| task != task_id);
+        self.review_requests.retain(|(task, _), _| task != task_id);
+        self.project_tasks.remove(task_id);
+        self.external_links.retain(|link| link.task_id != task_id);
+        self.comments.retain(|_, comment| comment.task_id != task_id);
+    }
+}
+
+/// Task repository keeping everything in memory
+///
+/// Clones share the same storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTaskRepository {
+    tasks: Arc<RwLock<HashMap<String, Task>>>,
+    relations: Arc<RwLock<Relations>>,
+}
+
+impl InMemoryTaskRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Repository holding the given state
+    pub fn from_snapshot(snapshot: RepositorySnapshot) -> Self {
+        let RepositorySnapshot {
+            tasks,
+            archived_tasks,
+            dependencies,
+            deleted_dependencies,
+            parents,
+            users,
+            assignments,
+            review_requests,
+            projects,
+            project_tasks,
+            workflow_configs,
+            project_snapshots,
+            tags,
+            external_links,
+            comments,
+            audit_entries,
+            bottlenecks,
+            bottleneck_actions,
+        } = snapshot;
+        let relations = Relations {
+            archived_tasks,
+            dependencies,
+            deleted_dependencies,
+            parents,
+            users,
+            assignments,
+            review_requests,
+            projects,
+            project_tasks,
+            workflow_configs,
+            project_snapshots,
+            tags,
+            external_links,
+            comments,
+            audit_entries,
+            bottlenecks,
+            bottleneck_actions,
+        };
+        Self {
+            tasks: Arc::new(RwLock::new(tasks)),
+            relations: Arc::new(RwLock::new(relations)),
+        }
+    }
+
+    /// Copy of the stored state
+    pub fn snapshot(&self) -> RepositorySnapshot {
+        let tasks = self.tasks.read().unwrap().clone();
+        let Relations {
+            archived_tasks,
+            dependencies,
+            deleted_dependencies,
+            parents,
+            users,
+            assignments,
+            review_requests,
+            projects,
+            project_tasks,
+            workflow_configs,
+            project_snapshots,
+            tags,
+            external_links,
+            comments,
+            audit_entries,
+            bottlenecks,
+            bottleneck_actions,
+        } = self.relations.read().unwrap().clone();
+        RepositorySnapshot {
+            tasks,
+            archived_tasks,
+            dependencies,
+            deleted_dependencies,
+            parents,
+            users,
+            assignments,
+            review_requests,
+            projects,
+            project_tasks,
+            workflow_configs,
+            project_snapshots,
+            tags,
+            external_links,
+            comments,
+            audit_entries,
+            bottlenecks,
+            bottleneck_actions,
+        }
+    }
+
+    /// Register a user, so tasks can be assigned to them
+    pub fn add_user(&self, user_id: &str) {
+        self.relations.write().unwrap().users.insert(user_id.to_string());
+    }
+
+    fn contains_task(&self, task_id: &str) -> bool {
+        self.tasks.read().unwrap().contains_key(task_id)
+    }
+
+    /// Stored tasks matching `predicate`, ordered by id
+    fn tasks_where(&self, predicate: impl Fn(&Task, &Relations) -> bool) -> Vec<Task> {
+        let tasks = self.tasks.read().unwrap();
+        let relations = self.relations.read().unwrap();
+        let mut matching: Vec<Task> = tasks.values()
+            .filter(|task| predicate(task, &relations))
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| a.id.cmp(&b.id));
+        matching
+    }
+
+    /// Unordered tasks passing `filter` that come after `cursor`, ignoring paging
+    fn tasks_matching(&self, filter: &TaskFilter, cursor: Option<&(DateTime<Utc>, String)>) -> Vec<Task> {
+        let now = Utc::now();
+        let tasks = self.tasks.read().unwrap();
+        let relations = self.relations.read().unwrap();
+        tasks.values()
+            .filter(|task| matches_filter(task, filter, &relations, now))
+            .filter(|task| match filter.has_overdue_subtasks {
+                Some(wanted) => has_overdue_subtask(&task.id, &tasks, &relations, now) == wanted,
+                None => true,
+            })
+            .filter(|task| match cursor {
+                Some((created_at, id)) => task.created_at < *created_at || (task.created_at == *created_at && task.id < *id),
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Stored tasks with the given ids, ordered by id
+    fn tasks_with_ids<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Vec<Task> {
+        let ids: HashSet<&str> = ids.into_iter().collect();
+        self.tasks_where(|task, _| ids.contains(task.id.as_str()))
+    }
+
+    /// Dependencies whose tasks are both stored
+    fn live_dependencies(&self) -> Vec<(TaskDependency, Task, Task)> {
+        let tasks = self.tasks.read().unwrap();
+        let relations = self.relations.read().unwrap();
+        let mut dependencies: Vec<(TaskDependency, Task, Task)> = relations.dependencies.values()
+            .filter_map(|d| Some((d.clone(), tasks.get(&d.from_task_id)?.clone(), tasks.get(&d.to_task_id)?.clone())))
+            .collect();
+        dependencies.sort_by(|a, b| (&a.0.from_task_id, &a.0.to_task_id, &a.0.id).cmp(&(&b.0.from_task_id, &b.0.to_task_id, &b.0.id)));
+        dependencies
+    }
+
+    /// Dependency edges between stored tasks, dependent task first
+    fn dependency_edges(&self) -> Vec<(String, String)> {
+        let mut edges: Vec<(String, String)> = self.live_dependencies().into_iter()
+            .map(|(d, _, _)| (d.from_task_id, d.to_task_id))
+            .collect();
+        edges.dedup();
+        edges
+    }
+}
+
+/// Whether `task` passes every condition of `filter` except the cursor and paging
+fn matches_filter(task: &Task, filter: &TaskFilter, relations: &Relations, now: DateTime<Utc>) -> bool {
+    let due_date_passes = |check: &dyn Fn(DateTime<Utc>) -> bool| task.due_date.is_some_and(check);
+    let open = !matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled);
+
+    if filter.status.as_ref().is_some_and(|statuses| !statuses.contains(&task.status)) {
+        return false;
+    }
+    if filter.priority.as_ref().is_some_and(|priorities| !priorities.contains(&task.priority)) {
+        return false;
+    }
+    if filter.context.as_ref().is_some_and(|contexts| !contexts.contains(&task.context)) {
+        return false;
+    }
+    if filter.complexity.as_ref().is_some_and(|complexities| !complexities.contains(&task.complexity)) {
+        return false;
+    }
+    if filter.assigned_user_id.as_deref().is_some_and(|user_id| !relations.is_assigned(&task.id, user_id)) {
+        return false;
+    }
+    if filter.project_id.as_deref().is_some_and(|project_id| relations.project_of(&task.id) != Some(project_id)) {
+        return false;
+    }
+    if let Some(from) = filter.due_date_from {
+        if !due_date_passes(&|due| due >= from) {
+            return false;
+        }
+    }
+    if let Some(to) = filter.due_date_to {
+        if !due_date_passes(&|due| due <= to) {
+            return false;
+        }
+    }
+    if let Some(before) = filter.due_before {
+        if !due_date_passes(&|due| due < before) {
+            return false;
+        }
+    }
+    if let Some(after) = filter.due_after {
+        if !due_date_passes(&|due| due > after) {
+            return false;
+        }
+    }
+    if filter.created_after.is_some_and(|after| task.created_at <= after) {
+        return false;
+    }
+    if filter.created_before.is_some_and(|before| task.created_at >= before) {
+        return false;
+    }
+    if let Some(ref text) = filter.search_text {
+        let text = text.to_lowercase();
+        let in_name = task.name.to_lowercase().contains(&text);
+        let in_description = task.description.as_deref().is_some_and(|d| d.to_lowercase().contains(&text));
+        let in_details = task.implementation_details.as_deref().is_some_and(|d| d.to_lowercase().contains(&text));
+        if !in_name && !in_description && !in_details {
+            return false;
+        }
+    }
+    if filter.tags.as_ref().is_some_and(|tags| !task.has_tags(tags, filter.require_all_tags)) {
+        return false;
+    }
+    if let Some(has_dependencies) = filter.has_dependencies {
+        if relations.dependencies.values().any(|d| d.from_task_id == task.id) != has_dependencies {
+            return false;
+        }
+    }
+    if let Some(is_overdue) = filter.is_overdue {
+        if (open && due_date_passes(&|due| due < now)) != is_overdue {
+            return false;
+        }
+    }
+    if filter.priority_changed_more_than.is_some_and(|changes| task.priority_change_count <= changes) {
+        return false;
+    }
+    if filter.cross_context.is_some_and(|cross_context| task.is_cross_context() != cross_context) {
+        return false;
+    }
+    true
+}
+
+/// Whether any open subtask of `parent_id` is past its due date
+fn has_overdue_subtask(parent_id: &str, tasks: &HashMap<String, Task>, relations: &Relations, now: DateTime<Utc>) -> bool {
+    relations.parents.iter()
+        .filter(|(_, parent)| parent.as_str() == parent_id)
+        .filter_map(|(child, _)| tasks.get(child))
+        .any(|child| {
+            child.due_date.is_some_and(|due| due < now)
+                && !matches!(child.status, TaskStatus::Done | TaskStatus::Cancelled)
+        })
+}
+
+/// Every dependency chain as task ids, from a task nothing depends on down to a task without dependencies
+fn dependency_chains(edges: &[(String, String)]) -> Vec<Vec<String>> {
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut has_dependent: HashSet<&str> = HashSet::new();
+    for (from, to) in edges {
+        successors.entry(from.as_str()).or_default().push(to.as_str());
+        has_dependent.insert(to.as_str());
+    }
+
+    fn walk<'a>(node: &'a str, successors: &HashMap<&'a str, Vec<&'a str>>, path: &mut Vec<&'a str>, chains: &mut Vec<Vec<String>>) {
+        path.push(node);
+        match successors.get(node) {
+            Some(targets) => {
+                for next in targets {
+                    if !path.contains(next) {
+                        walk(*next, successors, path, chains);
+                    }
+                }
+            }
+            None if path.len() > 1 => chains.push(path.iter().map(|id| id.to_string()).collect()),
+            None => {}
+        }
+        path.pop();
+    }
+
+    let mut starts: Vec<&str> = successors.keys().copied().filter(|id| !has_dependent.contains(id)).collect();
+    starts.sort();
+    let mut chains = Vec::new();
+    for start in starts {
+        walk(start, &successors, &mut Vec::new(), &mut chains);
+    }
+    chains
+}
+
+/// Number of tasks in the longest dependency path
+fn longest_path_length(edges: &[(String, String)]) -> u32 {
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, to) in edges {
+        successors.entry(from.as_str()).or_default().push(to.as_str());
+    }
+
+    fn depth<'a>(node: &'a str, successors: &HashMap<&'a str, Vec<&'a str>>, path: &mut Vec<&'a str>) -> u32 {
+        path.push(node);
+        let mut deepest = 0;
+        for next in successors.get(node).into_iter().flatten() {
+            if !path.contains(next) {
+                deepest = deepest.max(depth(*next, successors, path));
+            }
+        }
+        path.pop();
+        deepest + 1
+    }
+
+    successors.keys()
+        .map(|start| depth(start, &successors, &mut Vec::new()))
+        .max()
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl TaskRepository for InMemoryTaskRepository {
+    async fn save_task(&self, task: &Task) -> TylResult<()> {
+        let mut tasks = self.tasks.write().unwrap();
+        let stored_version = tasks.get(&task.id).map(|stored| stored.version);
+        let mut task = task.clone();
+        task.version = stored_version.map_or(task.version, |stored| stored.max(task.version)) + 1;
+        tasks.insert(task.id.clone(), task);
+        Ok(())
+    }
+
+    async fn save_task_if_version(&self, task: &Task, expected_version: u64) -> TylResult<bool> {
+        let mut tasks = self.tasks.write().unwrap();
+        if !tasks.get(&task.id).is_some_and(|stored| stored.version == expected_version) {
+            return Ok(false);
+        }
+        let mut task = task.clone();
+        task.version = expected_version + 1;
+        tasks.insert(task.id.clone(), task);
+        Ok(true)
+    }
+
+    async fn save_tasks(&self, tasks: &[NewTask]) -> TylResult<()> {
+        let mut stored = self.tasks.write().unwrap();
+        let mut relations = self.relations.write().unwrap();
+        for NewTask { task, assigned_user_id, project_id } in tasks {
+            let mut task = task.clone();
+            task.version += 1;
+            // Unknown users and projects are skipped, as when linking a stored task
+            if let Some(user_id) = assigned_user_id.as_ref().filter(|user_id| relations.users.contains(*user_id)) {
+                relations.assignments.insert((task.id.clone(), user_id.clone()), "owner".to_string());
+            }
+            if let Some(project_id) = project_id.as_ref().filter(|project_id| relations.projects.contains_key(*project_id)) {
+                relations.project_tasks.insert(task.id.clone(), project_id.clone());
+            }
+            stored.insert(task.id.clone(), task);
+        }
+        Ok(())
+    }
+
+    async fn find_existing_task_ids(&self, ids: &[String]) -> TylResult<HashSet<String>> {
+        let stored = self.tasks.read().unwrap();
+        Ok(ids.iter().filter(|id| stored.contains_key(*id)).cloned().collect())
+    }
+
+    async fn find_tasks_by_ids(&self, ids: &[String]) -> TylResult<Vec<Task>> {
+        let stored = self.tasks.read().unwrap();
+        Ok(ids.iter().filter_map(|id| stored.get(id).cloned()).collect())
+    }
+
+    async fn find_task_by_id(&self, id: &str) -> TylResult<Option<Task>> {
+        Ok(self.tasks.read().unwrap().get(id).cloned())
+    }
+
+    async fn find_tasks_by_filter(&self, filter: &TaskFilter) -> TylResult<Vec<Task>> {
+        let cursor = filter.cursor.as_deref().map(TaskCursor::decode).transpose()?;
+        let mut matching = self.tasks_matching(filter, cursor.as_ref());
+        matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+
+        let page = matching.into_iter()
+            // The cursor already marks where the page starts
+            .skip(if cursor.is_none() { filter.offset.unwrap_or(0) } else { 0 })
+            .take(filter.limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(page)
+    }
+
+    async fn fulltext_search_tasks(&self, query: &str, context: Option<TaskContext>, limit: usize) -> TylResult<Vec<TaskSearchResult>> {
+        // Relevance is the number of query terms found in the name or description
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let mut results: Vec<TaskSearchResult> = self.tasks_where(|task, _| context.map_or(true, |c| task.context == c))
+            .into_iter()
+            .filter_map(|task| {
+                let text = format!("{} {}", task.name, task.description.as_deref().unwrap_or_default()).to_lowercase();
+                let hits = terms.iter().filter(|term| text.contains(term.as_str())).count();
+                (hits > 0).then(|| TaskSearchResult { task, relevance_score: hits as f64 })
+            })
+            .collect();
+        results.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn search_tasks_advanced(&self, query: &TaskSearchQuery, sort: &[SortSpec], page: &PageRequest) -> TylResult<PagedResult<Task>> {
+        let tasks: Vec<Task> = self.tasks.read().unwrap().values().cloned().collect();
+        Ok(search_tasks_in_memory(tasks, query, sort, page))
+    }
+
+    async fn delete_task(&self, id: &str) -> TylResult<()> {
+        self.delete_tasks(&[id.to_string()]).await
+    }
+
+    async fn patch_task_custom_properties(&self, task_id: &str, patch: &CustomPropertiesPatch, expected_version: Option<u64>) -> TylResult<Option<Task>> {
+        let mut tasks = self.tasks.write().unwrap();
+        let task = tasks.get_mut(task_id)
+            .filter(|task| expected_version.unwrap_or(task.version) == task.version);
+        Ok(task.map(|task| {
+            task.apply_custom_properties_patch(patch);
+            task.updated_at = Utc::now();
+            task.version += 1;
+            task.clone()
+        }))
+    }
+
+    async fn save_dependency(&self, dependency: &TaskDependency) -> TylResult<()> {
+        if !self.contains_task(&dependency.from_task_id) || !self.contains_task(&dependency.to_task_id) {
+            return Ok(());
+        }
+        self.relations.write().unwrap().dependencies.insert(dependency.id.clone(), dependency.clone());
+        Ok(())
+    }
+
+    async fn delete_dependency(&self, dependency_id: &str) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        if let Some(mut dependency) = relations.dependencies.remove(dependency_id) {
+            dependency.deleted_at = Some(Utc::now());
+            relations.deleted_dependencies.insert(dependency.id.clone(), dependency);
+        }
+        Ok(())
+    }
+
+    async fn find_dependencies_by_task(&self, task_id: &str) -> TylResult<Vec<TaskDependency>> {
+        Ok(self.live_dependencies().into_iter()
+            .map(|(d, _, _)| d)
+            .filter(|d| d.from_task_id == task_id)
+            .collect())
+    }
+
+    async fn find_deleted_dependencies(&self, task_id: &str, since: DateTime<Utc>) -> TylResult<Vec<TaskDependency>> {
+        let mut tombstones: Vec<TaskDependency> = self.relations.read().unwrap().deleted_dependencies.values()
+            .filter(|d| d.from_task_id == task_id && d.deleted_at.is_some_and(|deleted_at| deleted_at > since))
+            .cloned()
+            .collect();
+        tombstones.sort_by(|a, b| (&a.to_task_id, &a.id).cmp(&(&b.to_task_id, &b.id)));
+        Ok(tombstones)
+    }
+
+    async fn find_blocking_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        Ok(self.live_dependencies().into_iter()
+            .filter(|(d, _, _)| d.to_task_id == task_id && d.dependency_type == DependencyType::Blocks)
+            .map(|(_, from, _)| from)
+            .collect())
+    }
+
+    async fn find_prerequisite_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        let dependencies = self.live_dependencies();
+        Ok(self.tasks_with_ids(dependencies.iter()
+            .filter(|(d, _, _)| d.from_task_id == task_id)
+            .map(|(d, _, _)| d.to_task_id.as_str())))
+    }
+
+    async fn find_dependent_tasks(&self, task_id: &str) -> TylResult<Vec<Task>> {
+        let dependencies = self.live_dependencies();
+        Ok(self.tasks_with_ids(dependencies.iter()
+            .filter(|(d, _, _)| d.to_task_id == task_id)
+            .map(|(d, _, _)| d.from_task_id.as_str())))
+    }
+
+    async fn find_task_subgraph(&self, task_id: &str, depth: u32) -> TylResult<TaskSubgraph> {
+        let Some(root) = self.tasks.read().unwrap().get(task_id).cloned() else {
+            return Ok(TaskSubgraph::default());
+        };
+        let dependencies = self.live_dependencies();
+        let mut subgraph = TaskSubgraph { nodes: vec![TaskNode::from(&root)], edges: Vec::new() };
+        let mut seen_nodes = HashSet::from([root.id.clone()]);
+        let mut seen_edges = HashSet::new();
+        // Away from the task along dependencies, then towards it, as the graph query does
+        for outgoing in [true, false] {
+            let mut frontier = vec![root.id.clone()];
+            for _ in 0..depth {
+                let mut next = Vec::new();
+                for (dependency, from, to) in &dependencies {
+                    let (near, far) = if outgoing { (from, to) } else { (to, from) };
+                    if !frontier.contains(&near.id) {
+                        continue;
+                    }
+                    if seen_edges.insert((dependency.from_task_id.clone(), dependency.to_task_id.clone(), dependency.dependency_type)) {
+                        subgraph.edges.push(TaskEdge {
+                            from: dependency.from_task_id.clone(),
+                            to: dependency.to_task_id.clone(),
+                            dependency_type: dependency.dependency_type,
+                        });
+                    }
+                    if seen_nodes.insert(far.id.clone()) {
+                        subgraph.nodes.push(TaskNode::from(far));
+                    }
+                    next.push(far.id.clone());
+                }
+                next.sort();
+                next.dedup();
+                frontier = next;
+            }
+        }
+        Ok(subgraph)
+    }
+
+    async fn add_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
+        if self.contains_task(parent_id) && self.contains_task(child_id) {
+            self.relations.write().unwrap().parents.insert(child_id.to_string(), parent_id.to_string());
+        }
+        Ok(())
+    }
+
+    async fn remove_parent_child_relationship(&self, parent_id: &str, child_id: &str) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        if relations.parents.get(child_id).map(String::as_str) == Some(parent_id) {
+            relations.parents.remove(child_id);
+        }
+        Ok(())
+    }
+
+    async fn find_children(&self, parent_id: &str) -> TylResult<Vec<Task>> {
+        Ok(self.tasks_where(|task, relations| relations.parents.get(&task.id).map(String::as_str) == Some(parent_id)))
+    }
+
+    async fn find_parent(&self, child_id: &str) -> TylResult<Option<Task>> {
+        let parent_id = self.relations.read().unwrap().parents.get(child_id).cloned();
+        Ok(parent_id.and_then(|id| self.tasks.read().unwrap().get(&id).cloned()))
+    }
+
+    async fn assign_user_to_task(&self, task_id: &str, user_id: &str, role: &str) -> TylResult<()> {
+        if !self.contains_task(task_id) {
+            return Ok(());
+        }
+        let mut relations = self.relations.write().unwrap();
+        if relations.users.contains(user_id) {
+            relations.assignments.insert((task_id.to_string(), user_id.to_string()), role.to_string());
+        }
+        Ok(())
+    }
+
+    async fn unassign_user_from_task(&self, task_id: &str, user_id: &str) -> TylResult<()> {
+        self.relations.write().unwrap().assignments.remove(&(task_id.to_string(), user_id.to_string()));
+        Ok(())
+    }
+
+    async fn find_assigned_tasks(&self, user_id: &str) -> TylResult<Vec<Task>> {
+        Ok(self.tasks_where(|task, relations| relations.is_assigned(&task.id, user_id)))
+    }
+
+    async fn reassign_user_tasks(&self, from_user_id: &str, to_user_id: Option<&str>, task_ids: &[String]) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        let to_user_id = to_user_id.filter(|user_id| relations.users.contains(*user_id));
+        for task_id in task_ids {
+            let Some(role) = relations.assignments.remove(&(task_id.clone(), from_user_id.to_string())) else {
+                continue;
+            };
+            if let Some(to_user_id) = to_user_id {
+                relations.assignments.entry((task_id.clone(), to_user_id.to_string())).or_insert(role);
+            }
+        }
+        Ok(())
+    }
+
+    async fn find_task_assignments(&self, task_id: &str) -> TylResult<Vec<(String, String)>> {
+        Ok(self.relations.read().unwrap().assignments.iter()
+            .filter(|((task, _), _)| task == task_id)
+            .map(|((_, user), role)| (user.clone(), role.clone()))
+            .collect())
+    }
+
+    async fn user_exists(&self, user_id: &str) -> TylResult<bool> {
+        Ok(self.relations.read().unwrap().users.contains(user_id))
+    }
+
+    async fn has_assignee(&self, task_id: &str) -> TylResult<bool> {
+        Ok(self.relations.read().unwrap().assignments.keys().any(|(task, _)| task == task_id))
+    }
+
+    async fn save_review_request(&self, task_id: &str, reviewer_id: &str, requested_at: DateTime<Utc>) -> TylResult<()> {
+        if !self.contains_task(task_id) {
+            return Ok(());
+        }
+        let mut relations = self.relations.write().unwrap();
+        if relations.users.contains(reviewer_id) {
+            // Asking the same reviewer again keeps the original request time
+            relations.review_requests
+                .entry((task_id.to_string(), reviewer_id.to_string()))
+                .or_insert(requested_at);
+        }
+        Ok(())
+    }
+
+    async fn find_review_queue(&self, reviewer_id: Option<&str>, project_id: Option<&str>) -> TylResult<Vec<(Task, Option<DateTime<Utc>>)>> {
+        let in_review = self.tasks_where(|task, relations| {
+            task.status == TaskStatus::Review
+                && project_id.map_or(true, |project_id| relations.project_of(&task.id) == Some(project_id))
+        });
+
+        let relations = self.relations.read().unwrap();
+        Ok(in_review.into_iter()
+            .filter_map(|task| {
+                let mut requests = relations.review_requests.iter()
+                    .filter(|((id, reviewer), _)| *id == task.id && reviewer_id.map_or(true, |r| reviewer == r))
+                    .map(|(_, requested_at)| *requested_at)
+                    .peekable();
+                if reviewer_id.is_some() && requests.peek().is_none() {
+                    return None;
+                }
+                let requested_at = requests.min();
+                Some((task, requested_at))
+            })
+            .collect())
+    }
+
+    async fn save_external_link(&self, link: &ExternalTicketLink) -> TylResult<()> {
+        if !self.contains_task(&link.task_id) {
+            return Ok(());
+        }
+        let mut relations = self.relations.write().unwrap();
+        relations.external_links.retain(|existing| existing.task_id != link.task_id || !existing.same_ticket(link));
+        relations.external_links.push(link.clone());
+        Ok(())
+    }
+
+    async fn find_external_links(&self, task_id: &str) -> TylResult<Vec<ExternalTicketLink>> {
+        let mut links: Vec<ExternalTicketLink> = self.relations.read().unwrap().external_links.iter()
+            .filter(|link| link.task_id == task_id)
+            .cloned()
+            .collect();
+        links.sort_by(|a, b| (a.system.name(), &a.external_id).cmp(&(b.system.name(), &b.external_id)));
+        Ok(links)
+    }
+
+    async fn save_comment(&self, comment: &TaskComment) -> TylResult<()> {
+        if !self.contains_task(&comment.task_id) {
+            return Ok(());
+        }
+        self.relations.write().unwrap().comments.insert(comment.id.clone(), comment.clone());
+        Ok(())
+    }
+
+    async fn find_comment_by_id(&self, comment_id: &str) -> TylResult<Option<TaskComment>> {
+        Ok(self.relations.read().unwrap().comments.get(comment_id).cloned())
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> TylResult<()> {
+        self.relations.write().unwrap().comments.remove(comment_id);
+        Ok(())
+    }
+
+    async fn find_task_comments(&self, task_id: &str) -> TylResult<Vec<TaskComment>> {
+        let mut comments: Vec<TaskComment> = self.relations.read().unwrap().comments.values()
+            .filter(|comment| comment.task_id == task_id)
+            .cloned()
+            .collect();
+        comments.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(comments)
+    }
+
+    async fn find_task_tags(&self, task_id: &str) -> TylResult<Vec<Tag>> {
+        let names = match self.tasks.read().unwrap().get(task_id) {
+            Some(task) => task.tags.clone(),
+            None => return Ok(vec![]),
+        };
+        let relations = self.relations.read().unwrap();
+        let mut tags: Vec<Tag> = names.iter().filter_map(|name| relations.tags.get(name).cloned()).collect();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tags)
+    }
+
+    async fn add_task_tag(&self, task_id: &str, tag: &Tag) -> TylResult<()> {
+        let mut tasks = self.tasks.write().unwrap();
+        let Some(task) = tasks.get_mut(task_id) else {
+            return Ok(());
+        };
+        self.relations.write().unwrap().tags.entry(tag.name.clone()).or_insert_with(|| tag.clone());
+        if !task.tags.contains(&tag.name) {
+            task.tags.push(tag.name.clone());
+        }
+        Ok(())
+    }
+
+    async fn remove_task_tag(&self, task_id: &str, tag_name: &str) -> TylResult<()> {
+        if let Some(task) = self.tasks.write().unwrap().get_mut(task_id) {
+            task.tags.retain(|name| name != tag_name);
+        }
+        Ok(())
+    }
+
+    async fn count_tag_usage(&self) -> TylResult<Vec<TagUsage>> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        {
+            let tasks = self.tasks.read().unwrap();
+            let relations = self.relations.read().unwrap();
+            for name in tasks.values().flat_map(|task| &task.tags).filter(|name| relations.tags.contains_key(*name)) {
+                *counts.entry(name.clone()).or_default() += 1;
+            }
+        }
+        let mut usage: Vec<TagUsage> = counts.into_iter().map(|(tag, task_count)| TagUsage { tag, task_count }).collect();
+        usage.sort_by(|a, b| b.task_count.cmp(&a.task_count).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(usage)
+    }
+
+    async fn save_project(&self, project: &Project) -> TylResult<()> {
+        self.relations.write().unwrap().projects.insert(project.id.clone(), project.clone());
+        Ok(())
+    }
+
+    async fn add_task_to_project(&self, task_id: &str, project_id: &str) -> TylResult<()> {
+        if !self.contains_task(task_id) {
+            return Ok(());
+        }
+        let mut relations = self.relations.write().unwrap();
+        if relations.projects.contains_key(project_id) {
+            relations.project_tasks.insert(task_id.to_string(), project_id.to_string());
+        }
+        Ok(())
+    }
+
+    async fn find_project_tasks(&self, project_id: &str) -> TylResult<Vec<Task>> {
+        Ok(self.tasks_where(|task, relations| relations.project_of(&task.id) == Some(project_id)))
+    }
+
+    async fn find_task_project_id(&self, task_id: &str) -> TylResult<Option<String>> {
+        Ok(self.relations.read().unwrap().project_tasks.get(task_id).cloned())
+    }
+
+    async fn find_project_by_id(&self, project_id: &str) -> TylResult<Option<Project>> {
+        Ok(self.relations.read().unwrap().projects.get(project_id).cloned())
+    }
+
+    async fn set_project_lead(&self, project_id: &str, user_id: &str) -> TylResult<()> {
+        if let Some(project) = self.relations.write().unwrap().projects.get_mut(project_id) {
+            project.project_lead_user_id = Some(user_id.to_string());
+            project.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn save_workflow_config(&self, config: &ProjectWorkflowConfig) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        if relations.projects.contains_key(&config.project_id) {
+            relations.workflow_configs.insert(config.project_id.clone(), config.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_workflow_config(&self, project_id: &str) -> TylResult<Option<ProjectWorkflowConfig>> {
+        let relations = self.relations.read().unwrap();
+        if !relations.projects.contains_key(project_id) {
+            return Ok(None);
+        }
+        Ok(Some(relations.workflow_configs.get(project_id).cloned()
+            .unwrap_or_else(|| ProjectWorkflowConfig::new(project_id.to_string()))))
+    }
+
+    async fn find_wip_limit_violations(&self, project_id: &str) -> TylResult<Vec<WipViolation>> {
+        let Some(config) = self.find_workflow_config(project_id).await? else {
+            return Ok(vec![]);
+        };
+        let project_tasks = self.find_project_tasks(project_id).await?;
+
+        let mut violations: Vec<WipViolation> = config.wip_limits.iter()
+            .filter_map(|(status, limit)| {
+                let mut in_status: Vec<&Task> = project_tasks.iter().filter(|task| task.status == *status).collect();
+                in_status.sort_by(|a, b| a.priority.weight().total_cmp(&b.priority.weight()).then_with(|| a.id.cmp(&b.id)));
+                WipViolation::from_usage(*status, *limit, in_status.iter().map(|task| task.id.clone()).collect())
+            })
+            .collect();
+        violations.sort_by(|a, b| b.overflow.cmp(&a.overflow));
+        Ok(violations)
+    }
+
+    async fn save_project_snapshot(&self, snapshot: &ProjectSnapshot) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        if relations.projects.contains_key(&snapshot.project_id) {
+            relations.project_snapshots.insert(snapshot.id.clone(), snapshot.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_project_snapshot(&self, snapshot_id: &str) -> TylResult<Option<ProjectSnapshot>> {
+        Ok(self.relations.read().unwrap().project_snapshots.get(snapshot_id).cloned())
+    }
+
+    async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
+        let Some(task) = self.find_task_by_id(task_id).await? else {
+            return Ok(0.0);
+        };
+        let children = self.find_children(task_id).await?;
+        if children.is_empty() {
+            return Ok(if task.status == TaskStatus::Done { 100.0 } else { 0.0 });
+        }
+        let done = children.iter().filter(|child| child.status == TaskStatus::Done).count();
+        Ok(done as f64 * 100.0 / children.len() as f64)
+    }
+
+    async fn find_critical_path(&self, project_id: &str) -> TylResult<Vec<Task>> {
+        // The critical path is the chain with the most estimated work
+        let Some(longest) = self.find_dependency_chains(project_id, 1).await?.into_iter().next() else {
+            return Ok(vec![]);
+        };
+        let tasks = self.tasks.read().unwrap();
+        Ok(longest.task_chain.iter().filter_map(|id| tasks.get(id).cloned()).collect())
+    }
+
+    async fn find_dependency_chains(&self, project_id: &str, top_n: usize) -> TylResult<Vec<DependencyPath>> {
+        let chains = dependency_chains(&self.dependency_edges());
+        let tasks = self.tasks.read().unwrap();
+        let relations = self.relations.read().unwrap();
+        let in_project = |id: &String| relations.project_of(id) == Some(project_id);
+
+        let mut weighted: Vec<(f64, DependencyPath)> = chains.into_iter()
+            .filter(|chain| chain.first().is_some_and(in_project) && chain.last().is_some_and(in_project))
+            .map(|mut chain| {
+                let chain_tasks: Vec<&Task> = chain.iter().filter_map(|id| tasks.get(id)).collect();
+                let total_hours: f64 = chain_tasks.iter().map(|task| task.complexity.default_estimated_hours()).sum();
+                let avg_priority_weight = chain_tasks.iter().map(|task| task.priority.weight()).sum::<f64>() / chain_tasks.len() as f64;
+                // Prerequisites first
+                chain.reverse();
+                (total_hours, DependencyPath::from_chain(chain, total_hours, avg_priority_weight))
+            })
+            .collect();
+        weighted.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut paths: Vec<DependencyPath> = weighted.into_iter().take(top_n).map(|(_, path)| path).collect();
+
+        if let Some(longest) = paths.first_mut() {
+            longest.longest_chain = true;
+        }
+        Ok(paths)
+    }
+
+    async fn detect_circular_dependencies(&self) -> TylResult<Vec<Vec<String>>> {
+        let edges = self.dependency_edges();
+        let reaches = |from: &str, target: &str| -> bool {
+            let mut seen: HashSet<&str> = HashSet::new();
+            let mut stack = vec![from];
+            while let Some(node) = stack.pop() {
+                if node == target {
+                    return true;
+                }
+                for (_, next) in edges.iter().filter(|(f, _)| f == node) {
+                    if seen.insert(next.as_str()) {
+                        stack.push(next.as_str());
+                    }
+                }
+            }
+            false
+        };
+
+        // Edges a -> b lie on a cycle when b leads back to a
+        let on_cycle: Vec<(String, String)> = edges.iter()
+            .filter(|(from, to)| reaches(to, from))
+            .cloned()
+            .collect();
+        Ok(cycles_from_edges(&on_cycle))
+    }
+
+    async fn find_dependencies_between(&self, task_ids: &[String]) -> TylResult<Vec<TaskDependency>> {
+        Ok(self.live_dependencies().into_iter()
+            .map(|(d, _, _)| d)
+            .filter(|d| task_ids.contains(&d.from_task_id) && task_ids.contains(&d.to_task_id))
+            .collect())
+    }
+
+    async fn find_dependencies_with_tasks(&self) -> TylResult<Vec<(TaskDependency, Task, Task)>> {
+        Ok(self.live_dependencies())
+    }
+
+    async fn find_dependency_edges(&self) -> TylResult<Vec<(String, String)>> {
+        Ok(self.live_dependencies().into_iter()
+            .map(|(d, _, _)| (d.from_task_id, d.to_task_id))
+            .collect())
+    }
+
+    async fn find_cross_project_dependencies(&self) -> TylResult<Vec<CrossProjectDependency>> {
+        let now = Utc::now();
+        let dependencies = self.live_dependencies();
+        let relations = self.relations.read().unwrap();
+
+        let mut cross: Vec<CrossProjectDependency> = dependencies.iter()
+            .filter_map(|(dependency, from, to)| {
+                let from_project = relations.project_of(&from.id)?.to_string();
+                let to_project = relations.project_of(&to.id)?.to_string();
+                (from_project != to_project)
+                    .then(|| CrossProjectDependency::new(dependency, (from, from_project), (to, to_project), now))
+            })
+            .collect();
+        cross.sort_by(|a, b| (&a.from_project, &a.from_task.id).cmp(&(&b.from_project, &b.from_task.id)));
+        Ok(cross)
+    }
+
+    async fn archive_completed_tasks_before(&self, cutoff: DateTime<Utc>, _batch_size: u32) -> TylResult<ArchiveResult> {
+        let mut tasks = self.tasks.write().unwrap();
+        let archived_ids: Vec<String> = tasks.values()
+            .filter(|task| task.status == TaskStatus::Done && task.completed_at.is_some_and(|at| at < cutoff))
+            .map(|task| task.id.clone())
+            .collect();
+
+        // Archived tasks keep their relationships but leave every task query
+        let mut relations = self.relations.write().unwrap();
+        for id in &archived_ids {
+            if let Some(task) = tasks.remove(id) {
+                relations.archived_tasks.insert(id.clone(), task);
+            }
+        }
+        Ok(ArchiveResult { archived_count: archived_ids.len() as u32, failed_ids: vec![] })
+    }
+
+    async fn archive_task(&self, task_id: &str, _archive_graph: &str) -> TylResult<()> {
+        let task = self.tasks.write().unwrap().remove(task_id)
+            .ok_or_else(|| TylError::not_found("task", task_id))?;
+        // Unlike the label-based archive, the archive graph keeps no relationships
+        let mut relations = self.relations.write().unwrap();
+        relations.detach(task_id);
+        relations.archived_tasks.insert(task_id.to_string(), task);
+        Ok(())
+    }
+
+    async fn delete_tasks(&self, task_ids: &[String]) -> TylResult<()> {
+        let mut tasks = self.tasks.write().unwrap();
+        let mut relations = self.relations.write().unwrap();
+        for id in task_ids {
+            tasks.remove(id);
+            relations.detach(id);
+        }
+        Ok(())
+    }
+
+    async fn update_tasks_status(&self, task_ids: &[String], status: TaskStatus) -> TylResult<()> {
+        let mut tasks = self.tasks.write().unwrap();
+        for id in task_ids {
+            if let Some(task) = tasks.get_mut(id) {
+                task.set_status(status);
+                task.version += 1;
+            }
+        }
+        Ok(())
+    }
+
+    async fn merge_task_relationships(&self, source_id: &str, target_id: &str, options: &MergeOptions) -> TylResult<()> {
+        if !self.contains_task(source_id) || !self.contains_task(target_id) {
+            return Ok(());
+        }
+        let mut relations = self.relations.write().unwrap();
+
+        if options.transfer_dependencies {
+            let touches_source = |d: &TaskDependency| d.from_task_id == source_id || d.to_task_id == source_id;
+            let mut pairs: HashSet<(String, String)> = relations.dependencies.values()
+                .filter(|d| !touches_source(d))
+                .map(|d| (d.from_task_id.clone(), d.to_task_id.clone()))
+                .collect();
+            let moved: Vec<String> = relations.dependencies.values()
+                .filter(|d| touches_source(d))
+                .map(|d| d.id.clone())
+                .collect();
+            for id in moved {
+                let Some(mut dependency) = relations.dependencies.remove(&id) else { continue };
+                for task_id in [&mut dependency.from_task_id, &mut dependency.to_task_id] {
+                    if *task_id == source_id {
+                        *task_id = target_id.to_string();
+                    }
+                }
+                if dependency.from_task_id != dependency.to_task_id
+                    && pairs.insert((dependency.from_task_id.clone(), dependency.to_task_id.clone()))
+                {
+                    relations.dependencies.insert(id, dependency);
+                }
+            }
+        }
+
+        if options.transfer_subtasks {
+            if relations.parents.get(target_id).map(String::as_str) == Some(source_id) {
+                relations.parents.remove(target_id);
+            }
+            for parent in relations.parents.values_mut() {
+                if *parent == source_id {
+                    *parent = target_id.to_string();
+                }
+            }
+        }
+
+        if options.transfer_comments {
+            for comment in relations.comments.values_mut() {
+                if comment.task_id == source_id {
+                    comment.task_id = target_id.to_string();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn export_tasks_streaming(&self, filter: &TaskFilter) -> BoxStream<'static, TylResult<Task>> {
+        let cursor = match filter.cursor.as_deref().map(TaskCursor::decode).transpose() {
+            Ok(cursor) => cursor,
+            Err(e) => return stream::once(async move { Err(e) }).boxed(),
+        };
+        let mut matching = self.tasks_matching(filter, cursor.as_ref());
+        matching.sort_by(|a, b| a.id.cmp(&b.id));
+        stream::iter(matching.into_iter().map(Ok)).boxed()
+    }
+
+    async fn save_audit_entry(&self, entry: &AuditEntry) -> TylResult<()> {
+        // Same rule as the graph, where the entity type is a node label
+        if entry.entity_type.is_empty() || !entry.entity_type.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(TylError::validation("entity_type", "Audit entity type must be a node label"));
+        }
+        self.relations.write().unwrap().audit_entries.push(entry.clone());
+        Ok(())
+    }
+
+    async fn collect_alert_metrics(&self) -> TylResult<AlertMetrics> {
+        let now = Utc::now();
+        let open_tasks = self.tasks_where(|task, _| !matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled));
+        let max_dependency_chain_length = longest_path_length(&self.dependency_edges());
+        let relations = self.relations.read().unwrap();
+
+        let overdue_tasks = open_tasks.iter().filter(|task| task.is_overdue()).count() as u32;
+
+        // Share of a project's open tasks that are neither blocked nor overdue
+        let mut per_project: HashMap<&str, (u32, u32)> = HashMap::new();
+        for task in &open_tasks {
+            if let Some(project_id) = relations.project_of(&task.id) {
+                let (open, troubled) = per_project.entry(project_id).or_default();
+                *open += 1;
+                if task.status == TaskStatus::Blocked || task.due_date.is_some_and(|due| due < now) {
+                    *troubled += 1;
+                }
+            }
+        }
+        let lowest_project_health_score = per_project.values()
+            .map(|(open, troubled)| 100.0 * (open - troubled) as f64 / *open as f64)
+            .min_by(f64::total_cmp);
+
+        let mut per_user: HashMap<&str, u32> = HashMap::new();
+        for (task_id, user_id) in relations.assignments.keys() {
+            if open_tasks.iter().any(|task| task.id == *task_id) {
+                *per_user.entry(user_id.as_str()).or_default() += 1;
+            }
+        }
+
+        Ok(AlertMetrics {
+            overdue_tasks,
+            lowest_project_health_score,
+            max_single_user_task_count: per_user.values().copied().max().unwrap_or(0),
+            max_dependency_chain_length,
+        })
+    }
+
+    async fn save_bottleneck_actions(&self, project_id: &str, bottleneck: &Bottleneck) -> TylResult<()> {
+        let mut relations = self.relations.write().unwrap();
+        if !relations.projects.contains_key(project_id) {
+            return Ok(());
+        }
+        relations.bottlenecks.insert(bottleneck.bottleneck_id.clone(), project_id.to_string());
+        for index in 0..bottleneck.suggested_actions.len() as u32 {
+            relations.bottleneck_actions
+                .entry((bottleneck.bottleneck_id.clone(), index))
+                .or_insert_with(|| BottleneckResolution {
+                    bottleneck_id: bottleneck.bottleneck_id.clone(),
+                    suggested_action_index: index,
+                    resolved: false,
+                    resolved_at: None,
+                    resolved_by: None,
+                });
+        }
+        Ok(())
+    }
+
+    async fn save_bottleneck_resolution(&self, resolution: &BottleneckResolution) -> TylResult<()> {
+        let key = (resolution.bottleneck_id.clone(), resolution.suggested_action_index);
+        if let Some(action) = self.relations.write().unwrap().bottleneck_actions.get_mut(&key) {
+            *action = resolution.clone();
+        }
+        Ok(())
+    }
+
+    async fn find_bottleneck_resolutions(&self, bottleneck_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        Ok(self.relations.read().unwrap().bottleneck_actions.values()
+            .filter(|action| action.bottleneck_id == bottleneck_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_project_bottleneck_resolutions(&self, project_id: &str) -> TylResult<Vec<BottleneckResolution>> {
+        let relations = self.relations.read().unwrap();
+        Ok(relations.bottleneck_actions.values()
+            .filter(|action| relations.bottlenecks.get(&action.bottleneck_id).map(String::as_str) == Some(project_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn count_daily_completions(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        let mut per_day: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+        for task in self.find_project_tasks(project_id).await? {
+            match task.completed_at {
+                Some(completed_at) if task.status == TaskStatus::Done && completed_at >= since => {
+                    *per_day.entry(completed_at.date_naive()).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+        Ok(per_day.into_iter().collect())
+    }
+
+    async fn count_daily_creations(&self, project_id: &str, since: DateTime<Utc>) -> TylResult<Vec<(NaiveDate, u32)>> {
+        let mut per_day: BTreeMap<NaiveDate, u32> = BTreeMap::new();
+        for task in self.find_project_tasks(project_id).await? {
+            if task.created_at >= since {
+                *per_day.entry(task.created_at.date_naive()).or_default() += 1;
+            }
+        }
+        Ok(per_day.into_iter().collect())
+    }
+
+    async fn find_completed_tasks_for_user(&self, user_id: &str, since: DateTime<Utc>) -> TylResult<Vec<Task>> {
+        let mut tasks = self.tasks_where(|task, relations| {
+            task.status == TaskStatus::Done
+                && task.completed_at.is_some_and(|at| at >= since)
+                && relations.is_assigned(&task.id, user_id)
+        });
+        tasks.sort_by_key(|task| task.completed_at);
+        Ok(tasks)
+    }
+
+    async fn find_estimated_completed_tasks(
+        &self,
+        user_id: Option<&str>,
+        context: Option<TaskContext>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<Task>> {
+        Ok(self.tasks_where(|task, relations| {
+            task.status == TaskStatus::Done
+                && task.started_at.is_some()
+                && task.estimated_date.is_some()
+                && task.completed_at.is_some_and(|at| at >= since)
+                && user_id.map_or(true, |user_id| relations.is_assigned(&task.id, user_id))
+                && context.map_or(true, |context| task.context == context)
+        }))
+    }
+
+    async fn find_recurring_task_instances(
+        &self,
+        user_id: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> TylResult<Vec<(String, Task)>> {
+        // Nothing links instances to the previous one here, so every task is its own series
+        let mut instances: Vec<(String, Task)> = self.tasks_where(|task, relations| {
+            task.recurrence.is_some()
+                && task.due_date.is_some_and(|due| due >= since)
+                && user_id.map_or(true, |user_id| relations.is_assigned(&task.id, user_id))
+        })
+        .into_iter()
+        .map(|task| (task.id.clone(), task))
+        .collect();
+        instances.sort_by(|a, b| (&a.0, a.1.due_date).cmp(&(&b.0, b.1.due_date)));
+        Ok(instances)
+    }
+}
+
+/// User service knowing a fixed set of users
+///
+/// Clones share the same users.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryUserAdapter {
+    users: Arc<RwLock<HashMap<String, User>>>,
+    unavailable: bool,
+}
+
+impl InMemoryUserAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adapter failing every lookup, like an unreachable user service
+    pub fn unavailable() -> Self {
+        Self { unavailable: true, ..Self::default() }
+    }
+
+    pub fn with_user(self, user: User) -> Self {
+        self.add_user(user);
+        self
+    }
+
+    pub fn add_user(&self, user: User) {
+        self.users.write().unwrap().insert(user.id.clone(), user);
+    }
+}
+
+#[async_trait]
+impl UserServicePort for InMemoryUserAdapter {
+    async fn get_user(&self, user_id: &str) -> TylResult<Option<User>> {
+        if self.unavailable {
+            return Err(TylError::network("User service unavailable"));
+        }
+        Ok(self.users.read().unwrap().get(user_id).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{TaskComplexity, TaskPriority};
+    use chrono::Duration;
+
+    fn task(id: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(id.to_string(), format!("Task {}", id), TaskContext::Work);
+        task.status = status;
+        task
+    }
+
+    async fn seeded() -> InMemoryTaskRepository {
+        let repository = InMemoryTaskRepository::new();
+        repository.add_user("alice");
+        repository.save_project(&Project::new("P1".to_string(), "P1".to_string(), "Project".to_string())).await.unwrap();
+        for (id, status) in [("T1", TaskStatus::Ready), ("T2", TaskStatus::InProgress), ("T3", TaskStatus::Done)] {
+            repository.save_task(&task(id, status)).await.unwrap();
+            repository.add_task_to_project(id, "P1").await.unwrap();
+        }
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_save_task_moves_version_on() {
+        let repository = InMemoryTaskRepository::new();
+        let mut stored = task("T1", TaskStatus::Backlog);
+        repository.save_task(&stored).await.unwrap();
+        stored = repository.find_task_by_id("T1").await.unwrap().unwrap();
+        assert_eq!(stored.version, 1);
+
+        // A stale copy still moves the stored version on
+        let mut stale = stored.clone();
+        stale.version = 0;
+        repository.save_task(&stale).await.unwrap();
+        assert_eq!(repository.find_task_by_id("T1").await.unwrap().unwrap().version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_save_task_if_version_only_writes_over_that_version() {
+        let repository = InMemoryTaskRepository::new();
+        repository.save_task(&task("T1", TaskStatus::Backlog)).await.unwrap();
+        let mut stored = repository.find_task_by_id("T1").await.unwrap().unwrap();
+
+        stored.name = "First".to_string();
+        assert!(repository.save_task_if_version(&stored, 1).await.unwrap());
+        stored.name = "Second".to_string();
+        assert!(!repository.save_task_if_version(&stored, 1).await.unwrap());
+        assert!(!repository.save_task_if_version(&task("missing", TaskStatus::Backlog), 0).await.unwrap());
+
+        let stored = repository.find_task_by_id("T1").await.unwrap().unwrap();
+        assert_eq!(stored.name, "First");
+        assert_eq!(stored.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_find_tasks_by_filter() {
+        let repository = seeded().await;
+        repository.assign_user_to_task("T1", "alice", "owner").await.unwrap();
+        let mut overdue = task("T4", TaskStatus::InProgress);
+        overdue.due_date = Some(Utc::now() - Duration::days(1));
+        overdue.priority = TaskPriority::High;
+        overdue.description = Some("Fix the login form".to_string());
+        repository.save_task(&overdue).await.unwrap();
+
+        let find = |filter: TaskFilter| {
+            let repository = repository.clone();
+            async move {
+                let mut ids: Vec<String> = repository.find_tasks_by_filter(&filter).await.unwrap()
+                    .into_iter().map(|t| t.id).collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        assert_eq!(find(TaskFilter { status: Some(vec![TaskStatus::InProgress]), ..Default::default() }).await, vec!["T2", "T4"]);
+        assert_eq!(find(TaskFilter { priority: Some(vec![TaskPriority::High]), ..Default::default() }).await, vec!["T4"]);
+        assert_eq!(find(TaskFilter { assigned_user_id: Some("alice".to_string()), ..Default::default() }).await, vec!["T1"]);
+        assert_eq!(find(TaskFilter { project_id: Some("P1".to_string()), ..Default::default() }).await, vec!["T1", "T2", "T3"]);
+        assert_eq!(find(TaskFilter { is_overdue: Some(true), ..Default::default() }).await, vec!["T4"]);
+        assert_eq!(find(TaskFilter { due_before: Some(Utc::now()), ..Default::default() }).await, vec!["T4"]);
+        assert_eq!(find(TaskFilter { search_text: Some("LOGIN".to_string()), ..Default::default() }).await, vec!["T4"]);
+        assert_eq!(find(TaskFilter { context: Some(vec![TaskContext::Personal]), ..Default::default() }).await, Vec::<String>::new());
+        assert_eq!(find(TaskFilter { complexity: Some(vec![TaskComplexity::Medium]), ..Default::default() }).await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_search_text_ignores_case() {
+        let repository = seeded().await;
+        let mut with_details = task("T4", TaskStatus::Ready);
+        with_details.implementation_details = Some("Rotate the OAuth signing keys".to_string());
+        repository.save_task(&with_details).await.unwrap();
+        let mut with_description = task("T5", TaskStatus::Ready);
+        with_description.description = Some("Document oauth scopes".to_string());
+        repository.save_task(&with_description).await.unwrap();
+
+        for text in ["oauth", "OAUTH", "OAuth"] {
+            let filter = TaskFilter { search_text: Some(text.to_string()), ..Default::default() };
+            let mut ids: Vec<String> = repository.find_tasks_by_filter(&filter).await.unwrap()
+                .into_iter().map(|t| t.id).collect();
+            ids.sort();
+            assert_eq!(ids, vec!["T4", "T5"]);
+        }
+
+        let filter = TaskFilter { search_text: Some("task t2".to_string()), ..Default::default() };
+        let tasks = repository.find_tasks_by_filter(&filter).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, "T2");
+    }
+
+    #[tokio::test]
+    async fn test_delete_detaches_relationships() {
+        let repository = seeded().await;
+        repository.save_dependency(&TaskDependency::new("T2".to_string(), "T1".to_string(), DependencyType::Blocks)).await.unwrap();
+        repository.add_parent_child_relationship("T1", "T3").await.unwrap();
+        repository.assign_user_to_task("T1", "alice", "owner").await.unwrap();
+        assert_eq!(repository.find_prerequisite_tasks("T2").await.unwrap()[0].id, "T1");
+        assert_eq!(repository.calculate_completion_percentage("T1").await.unwrap(), 100.0);
+
+        repository.delete_task("T1").await.unwrap();
+
+        let snapshot = repository.snapshot();
+        assert!(!snapshot.tasks.contains_key("T1"));
+        assert!(snapshot.dependencies.is_empty());
+        assert!(snapshot.parents.is_empty());
+        assert!(snapshot.assignments.is_empty());
+        assert!(!snapshot.project_tasks.contains_key("T1"));
+        assert_eq!(snapshot.tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_detect_circular_dependencies() {
+        let repository = seeded().await;
+        for (from, to) in [("T1", "T2"), ("T2", "T3"), ("T3", "T1")] {
+            repository.save_dependency(&TaskDependency::new(from.to_string(), to.to_string(), DependencyType::Requires)).await.unwrap();
+        }
+
+        let cycles = repository.detect_circular_dependencies().await.unwrap();
+        assert_eq!(cycles, vec![vec!["T1", "T2", "T3", "T1"]]);
+
+        // Dependencies on missing tasks are not stored
+        repository.save_dependency(&TaskDependency::new("T1".to_string(), "T9".to_string(), DependencyType::Requires)).await.unwrap();
+        assert_eq!(repository.snapshot().dependencies.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let repository = seeded().await;
+        repository.add_task_tag("T1", &Tag::new("tag-1".to_string(), "api".to_string(), "user".to_string())).await.unwrap();
+
+        let restored = InMemoryTaskRepository::from_snapshot(repository.snapshot());
+
+        assert_eq!(restored.find_project_tasks("P1").await.unwrap().len(), 3);
+        assert_eq!(restored.find_task_tags("T1").await.unwrap()[0].name, "api");
+        assert_eq!(restored.count_tag_usage().await.unwrap(), vec![TagUsage { tag: "api".to_string(), task_count: 1 }]);
+        assert!(restored.user_exists("alice").await.unwrap());
+    }
+}
@@ -2,13 +2,32 @@
 //!
 //! This module contains adapters for external systems like databases, HTTP clients,
 //! message queues, and other infrastructure concerns. The graph_repository provides
-//! graph database integration using tyl-graph-port and tyl-falkordb-adapter.
+//! graph database integration using tyl-graph-port and tyl-falkordb-adapter, and
+//! graph_project_service manages projects on top of it; the in_memory
+//! repository, behind the `testing` feature, stands in for it in tests. The
+//! http_user_service adapter looks up assignees in the external user service.
 
+pub mod caching_repository;
+pub mod circuit_breaker;
+pub mod cypher;
 pub mod database;
+pub mod graph_project_service;
 pub mod graph_repository;
 pub mod http_client;
+pub mod http_user_service;
+#[cfg(any(test, feature = "testing"))]
+pub mod in_memory;
+pub mod retrying_adapter;
 
 // Re-export commonly used adapters
+pub use caching_repository::*;
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use cypher::{cypher_params, CypherParams, ExecuteCypherWithParams};
 pub use database::*;
+pub use graph_project_service::GraphProjectService;
 pub use graph_repository::*;
-pub use http_client::*;
\ No newline at end of file
+pub use http_client::*;
+pub use http_user_service::HttpUserServiceAdapter;
+#[cfg(any(test, feature = "testing"))]
+pub use in_memory::{InMemoryTaskRepository, InMemoryUserAdapter, RepositorySnapshot};
+pub use retrying_adapter::{ConnectionRetryPolicy, RetryingFalkorDBAdapter};
\ No newline at end of file
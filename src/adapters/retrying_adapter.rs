@@ -0,0 +1,341 @@
+//! Resilient FalkorDB access
+//!
+//! [`RetryingFalkorDBAdapter`] retries operations that fail because FalkorDB
+//! could not be reached, backing off exponentially between attempts. Query
+//! errors such as Cypher syntax errors are returned straight away. After
+//! repeated connection failures a [`CircuitBreaker`] fails calls fast until
+//! the database has had time to recover.
+
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+use tyl_errors::{TylError, TylResult};
+use tyl_falkordb_adapter::FalkorDBAdapter;
+
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use super::cypher::{bind_params, CypherParams};
+use crate::config::DatabaseConfig;
+
+/// Rows fetched per query by [`RetryingFalkorDBAdapter::execute_cypher_streaming`]
+pub const STREAM_PAGE_SIZE: usize = 500;
+
+/// Retry and circuit breaker settings for database calls
+#[derive(Debug, Clone)]
+pub struct ConnectionRetryPolicy {
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on every further attempt
+    pub base_delay: Duration,
+    pub circuit_breaker: CircuitBreakerConfig,
+}
+
+impl Default for ConnectionRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            circuit_breaker: CircuitBreakerConfig::default(),
+        }
+    }
+}
+
+impl From<&DatabaseConfig> for ConnectionRetryPolicy {
+    fn from(config: &DatabaseConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            base_delay: Duration::from_millis(config.retry_base_ms),
+            circuit_breaker: CircuitBreakerConfig::from(config),
+        }
+    }
+}
+
+/// Whether an error means the database could not be reached, as opposed to
+/// the database rejecting the operation
+pub fn is_connection_error(error: &TylError) -> bool {
+    const MARKERS: &[&str] = &[
+        "connection", "network", "timed out", "timeout", "broken pipe", "refused", "reset", "io error",
+    ];
+
+    let message = error.to_string().to_lowercase();
+    MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// Run `operation`, retrying connection failures with exponential backoff
+pub async fn retry_with_backoff<T, F, Fut>(
+    policy: &ConnectionRetryPolicy,
+    breaker: &CircuitBreaker,
+    mut operation: F,
+) -> TylResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = TylResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        breaker.check()?;
+
+        match operation().await {
+            Ok(value) => {
+                breaker.record_success();
+                return Ok(value);
+            }
+            Err(e) if is_connection_error(&e) => {
+                breaker.record_failure();
+                if attempt >= policy.max_retries {
+                    return Err(e);
+                }
+                let delay = policy.base_delay.saturating_mul(1 << attempt.min(16));
+                tracing::warn!(attempt = attempt + 1, ?delay, error = %e, "FalkorDB call failed, retrying");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Rows fetched by `fetch_page(skip, limit)`, one page at a time as the stream is polled
+///
+/// A page shorter than `page_size` ends the stream, as does the first error.
+pub fn paged_stream<F, Fut>(page_size: usize, fetch_page: F) -> BoxStream<'static, TylResult<serde_json::Value>>
+where
+    F: FnMut(usize, usize) -> Fut + Send + 'static,
+    Fut: Future<Output = TylResult<Vec<serde_json::Value>>> + Send + 'static,
+{
+    struct Pages<F> {
+        fetch_page: F,
+        page_size: usize,
+        offset: usize,
+        rows: VecDeque<serde_json::Value>,
+        exhausted: bool,
+    }
+
+    let pages = Pages { fetch_page, page_size: page_size.max(1), offset: 0, rows: VecDeque::new(), exhausted: false };
+    stream::unfold(pages, |mut pages| async move {
+        loop {
+            if let Some(row) = pages.rows.pop_front() {
+                return Some((Ok(row), pages));
+            }
+            if pages.exhausted {
+                return None;
+            }
+            match (pages.fetch_page)(pages.offset, pages.page_size).await {
+                Ok(rows) => {
+                    pages.exhausted = rows.len() < pages.page_size;
+                    pages.offset += rows.len();
+                    pages.rows = rows.into();
+                }
+                Err(e) => {
+                    pages.exhausted = true;
+                    return Some((Err(e), pages));
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// FalkorDB adapter wrapper adding retries and a circuit breaker
+pub struct RetryingFalkorDBAdapter {
+    inner: FalkorDBAdapter,
+    policy: ConnectionRetryPolicy,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl RetryingFalkorDBAdapter {
+    pub fn new(inner: FalkorDBAdapter, policy: ConnectionRetryPolicy) -> Self {
+        Self::with_health_flag(inner, policy, Arc::new(AtomicBool::new(true)))
+    }
+
+    /// Create the adapter reporting database health through `healthy`
+    pub fn with_health_flag(inner: FalkorDBAdapter, policy: ConnectionRetryPolicy, healthy: Arc<AtomicBool>) -> Self {
+        let breaker = Arc::new(CircuitBreaker::new(policy.circuit_breaker.clone(), healthy));
+        Self::with_circuit_breaker(inner, policy, breaker)
+    }
+
+    /// Create the adapter on a breaker shared with others, e.g. the health routes
+    pub fn with_circuit_breaker(inner: FalkorDBAdapter, policy: ConnectionRetryPolicy, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { inner, policy, breaker }
+    }
+
+    pub fn circuit_breaker(&self) -> Arc<CircuitBreaker> {
+        self.breaker.clone()
+    }
+
+    /// The wrapped adapter, for operations that need no retry
+    pub fn inner(&self) -> &FalkorDBAdapter {
+        &self.inner
+    }
+
+    /// Execute a Cypher query, retrying on connection failures
+    pub async fn execute_cypher(&self, query: &str) -> TylResult<serde_json::Value> {
+        self.with_retry(move || self.inner.execute_cypher(query)).await
+    }
+
+    /// Execute a Cypher query with `$name` parameters, retrying on connection failures
+    pub async fn execute_cypher_with_params(&self, query: &str, params: CypherParams) -> TylResult<serde_json::Value> {
+        self.execute_cypher(&bind_params(query, &params)?).await
+    }
+
+    /// Execute a Cypher query, yielding its rows one at a time
+    ///
+    /// The FalkorDB client buffers whole results, so the query is read in
+    /// pages of [`STREAM_PAGE_SIZE`] rows by appending `SKIP`/`LIMIT`; it must
+    /// end with a `RETURN` with a stable `ORDER BY` for pages not to overlap.
+    /// A page is only requested once the previous one has been consumed.
+    pub fn execute_cypher_streaming(self: &Arc<Self>, query: &str) -> BoxStream<'static, TylResult<serde_json::Value>> {
+        self.stream_pages(query.to_string())
+    }
+
+    /// Stream a Cypher query with `$name` parameters as [`Self::execute_cypher_streaming`] does
+    pub fn execute_cypher_streaming_with_params(
+        self: &Arc<Self>,
+        query: &str,
+        params: CypherParams,
+    ) -> BoxStream<'static, TylResult<serde_json::Value>> {
+        match bind_params(query, &params) {
+            Ok(query) => self.stream_pages(query),
+            Err(e) => stream::once(async move { Err(e) }).boxed(),
+        }
+    }
+
+    fn stream_pages(self: &Arc<Self>, query: String) -> BoxStream<'static, TylResult<serde_json::Value>> {
+        let adapter = self.clone();
+        paged_stream(STREAM_PAGE_SIZE, move |skip, limit| {
+            let adapter = adapter.clone();
+            let page_query = format!("{} SKIP {} LIMIT {}", query, skip, limit);
+            async move {
+                Ok(match adapter.execute_cypher(&page_query).await? {
+                    serde_json::Value::Array(rows) => rows,
+                    serde_json::Value::Null => Vec::new(),
+                    row => vec![row],
+                })
+            }
+        })
+    }
+
+    /// Run any adapter operation under the retry policy and circuit breaker
+    pub async fn with_retry<T, F, Fut>(&self, operation: F) -> TylResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = TylResult<T>>,
+    {
+        retry_with_backoff(&self.policy, &self.breaker, operation).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const FAILURE_THRESHOLD: u32 = 5;
+
+    fn fast_policy(max_retries: u32) -> ConnectionRetryPolicy {
+        ConnectionRetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            circuit_breaker: breaker_config(),
+        }
+    }
+
+    fn breaker_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig { failure_threshold: FAILURE_THRESHOLD, recovery_timeout: Duration::from_secs(60) }
+    }
+
+    #[test]
+    fn test_connection_errors_are_told_apart_from_query_errors() {
+        assert!(is_connection_error(&TylError::network("Connection refused")));
+        assert!(is_connection_error(&TylError::database("IO error: broken pipe")));
+        assert!(!is_connection_error(&TylError::database("Invalid input 'MATC': expected a clause")));
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_failures_until_success() {
+        let breaker = CircuitBreaker::new(breaker_config(), Arc::new(AtomicBool::new(true)));
+        let calls = &AtomicU32::new(0);
+
+        let result = retry_with_backoff(&fast_policy(3), &breaker, move || async move {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(TylError::network("connection reset by peer"))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_errors_are_not_retried() {
+        let breaker = CircuitBreaker::new(breaker_config(), Arc::new(AtomicBool::new(true)));
+        let calls = &AtomicU32::new(0);
+
+        let result: TylResult<()> = retry_with_backoff(&fast_policy(3), &breaker, move || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TylError::database("Syntax error in query"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_consecutive_failures() {
+        let healthy = Arc::new(AtomicBool::new(true));
+        let breaker = CircuitBreaker::new(breaker_config(), healthy.clone());
+        let calls = &AtomicU32::new(0);
+        let failing = move || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(TylError::network("connection refused"))
+        };
+
+        assert!(retry_with_backoff(&fast_policy(10), &breaker, failing).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), FAILURE_THRESHOLD);
+        assert!(breaker.is_open());
+        assert!(!healthy.load(Ordering::Relaxed));
+
+        // Open circuit fails without calling the database
+        assert!(retry_with_backoff(&fast_policy(10), &breaker, failing).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), FAILURE_THRESHOLD);
+
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(healthy.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_paged_stream_fetches_pages_on_demand() {
+        let fetches = Arc::new(AtomicU32::new(0));
+        let counter = fetches.clone();
+        let mut rows = paged_stream(2, move |skip, limit| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok((skip..(skip + limit).min(5)).map(|i| serde_json::json!(i)).collect()) }
+        });
+
+        assert_eq!(rows.next().await.unwrap().unwrap(), 0);
+        assert_eq!(rows.next().await.unwrap().unwrap(), 1);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(rows.next().await.unwrap().unwrap(), 2);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        let rest: Vec<_> = rows.map(|row| row.unwrap()).collect().await;
+        assert_eq!(rest, vec![serde_json::json!(3), serde_json::json!(4)]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_paged_stream_stops_after_error() {
+        let rows: Vec<_> = paged_stream(2, |_, _| async { Err(TylError::database("query failed")) })
+            .collect()
+            .await;
+
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].is_err());
+    }
+}
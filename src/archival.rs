@@ -0,0 +1,178 @@
+//! Archival of completed tasks to a separate graph
+//!
+//! [`ArchivalService`] moves done tasks completed more than
+//! `archive_after_days` ago to the archive graph, keeping the task graph
+//! small. Each task is copied there and then detached and deleted from the
+//! task graph, so its relationships are not archived. The cycle runs once a
+//! day while archival is enabled and can be started by hand from the admin
+//! endpoints.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant};
+use tyl_errors::TylResult;
+
+use crate::config::ArchivalConfig;
+use crate::domain::{PageRequest, SearchOp, SortDirection, SortSpec, TaskRepository, TaskSearchQuery};
+
+/// Time between two scheduled cycles
+const ARCHIVAL_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Tasks fetched per query of a cycle
+const ARCHIVAL_BATCH_SIZE: usize = 100;
+
+/// Outcome of one archival cycle
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ArchivalReport {
+    /// Tasks completed before this time were archived
+    pub cutoff: DateTime<Utc>,
+    pub archived_ids: Vec<String>,
+    /// Tasks left in the task graph because archiving them failed
+    pub failed_ids: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Archival settings together with the outcome of the latest cycle
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ArchivalStatus {
+    pub enabled: bool,
+    pub archive_after_days: u32,
+    pub archive_graph_name: String,
+    pub last_run: Option<ArchivalReport>,
+}
+
+/// Moves old completed tasks to the archive graph
+pub struct ArchivalService {
+    config: ArchivalConfig,
+    repository: Arc<dyn TaskRepository + Send + Sync>,
+    last_report: Mutex<Option<ArchivalReport>>,
+}
+
+impl ArchivalService {
+    pub fn new(config: ArchivalConfig, repository: Arc<dyn TaskRepository + Send + Sync>) -> Self {
+        Self {
+            config,
+            repository,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Archive every done task completed before the threshold
+    ///
+    /// A task that fails to be archived is logged and skipped so the rest are
+    /// still archived.
+    pub async fn run_archival_cycle(&self) -> TylResult<ArchivalReport> {
+        let started_at = Utc::now();
+        let cutoff = started_at - chrono::Duration::days(self.config.archive_after_days as i64);
+        let mut report = ArchivalReport {
+            cutoff,
+            archived_ids: Vec::new(),
+            failed_ids: Vec::new(),
+            started_at,
+            finished_at: started_at,
+        };
+
+        let sort = [SortSpec { field: "completed_at".to_string(), direction: SortDirection::Asc }];
+        let page = PageRequest { page: 1, page_size: ARCHIVAL_BATCH_SIZE };
+        // Archived tasks leave the graph, so the first page always holds the next batch
+        loop {
+            let query = archivable_tasks_query(cutoff, &report.failed_ids);
+            let batch = self.repository.search_tasks_advanced(&query, &sort, &page).await?;
+            if batch.items.is_empty() {
+                break;
+            }
+            for task in batch.items {
+                match self.repository.archive_task(&task.id, &self.config.archive_graph_name).await {
+                    Ok(()) => report.archived_ids.push(task.id),
+                    Err(e) => {
+                        tracing::warn!(task_id = %task.id, error = %e, "Failed to archive task");
+                        report.failed_ids.push(task.id);
+                    }
+                }
+            }
+        }
+
+        report.finished_at = Utc::now();
+        tracing::info!(
+            archived = report.archived_ids.len(),
+            failed = report.failed_ids.len(),
+            "Archived tasks completed before {}", cutoff
+        );
+        *self.last_report.lock().unwrap() = Some(report.clone());
+        Ok(report)
+    }
+
+    pub fn status(&self) -> ArchivalStatus {
+        ArchivalStatus {
+            enabled: self.config.enabled,
+            archive_after_days: self.config.archive_after_days,
+            archive_graph_name: self.config.archive_graph_name.clone(),
+            last_run: self.last_report.lock().unwrap().clone(),
+        }
+    }
+
+    /// Run a cycle every day, starting one day from now, until the task is aborted
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + ARCHIVAL_INTERVAL, ARCHIVAL_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_archival_cycle().await {
+                    tracing::warn!(error = %e, "Task archival cycle failed");
+                }
+            }
+        })
+    }
+}
+
+/// Done tasks completed before `cutoff`, leaving out those that already failed
+fn archivable_tasks_query(cutoff: DateTime<Utc>, failed_ids: &[String]) -> TaskSearchQuery {
+    let field = |name: &str, op, value| TaskSearchQuery::Field { name: name.to_string(), op, value };
+    let mut conditions = vec![
+        field("status", SearchOp::Eq, serde_json::json!("done")),
+        field("completed_at", SearchOp::Lt, serde_json::json!(cutoff.to_rfc3339())),
+    ];
+    if !failed_ids.is_empty() {
+        conditions.push(TaskSearchQuery::Not(Box::new(field("id", SearchOp::In, serde_json::json!(failed_ids)))));
+    }
+    TaskSearchQuery::And(conditions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryTaskRepository;
+    use crate::domain::{Task, TaskContext, TaskStatus};
+
+    fn completed_task(id: &str, days_ago: i64) -> Task {
+        let mut task = Task::new(id.to_string(), "Ship release".to_string(), TaskContext::Work);
+        task.status = TaskStatus::Done;
+        task.completed_at = Some(Utc::now() - chrono::Duration::days(days_ago));
+        task
+    }
+
+    #[tokio::test]
+    async fn test_run_archival_cycle_moves_old_done_tasks() {
+        let repository = InMemoryTaskRepository::new();
+        repository.save_task(&completed_task("OLD", 120)).await.unwrap();
+        repository.save_task(&completed_task("RECENT", 10)).await.unwrap();
+        repository.save_task(&Task::new("OPEN".to_string(), "Plan".to_string(), TaskContext::Work)).await.unwrap();
+        let service = ArchivalService::new(
+            ArchivalConfig { enabled: true, archive_after_days: 90, archive_graph_name: "archive".to_string() },
+            Arc::new(repository.clone()),
+        );
+
+        let report = service.run_archival_cycle().await.unwrap();
+
+        assert_eq!(report.archived_ids, vec!["OLD"]);
+        assert!(report.failed_ids.is_empty());
+        let snapshot = repository.snapshot();
+        assert!(snapshot.archived_tasks.contains_key("OLD"));
+        assert!(snapshot.tasks.contains_key("RECENT") && snapshot.tasks.contains_key("OPEN"));
+        assert_eq!(service.status().last_run.unwrap().archived_ids, vec!["OLD"]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Authorization of task operations
+//!
+//! Handlers identify the caller with the [`Actor`] extractor and ask the
+//! [`AuthorizationPolicy`] held in [`crate::AppState`] before touching a task.
+//! [`PermissivePolicy`] allows everything and is the default;
+//! [`VisibilityPolicy`] is used when `api.enforce_task_visibility` is set.
+
+use async_trait::async_trait;
+use axum::{extract::FromRequestParts, http::request::Parts};
+use std::convert::Infallible;
+use std::sync::Arc;
+use utoipa::openapi::path::{Parameter, ParameterBuilder, ParameterIn};
+use utoipa::openapi::Required;
+use utoipa::{IntoParams, PartialSchema};
+
+use crate::domain::{Task, TaskService, TaskVisibility};
+use crate::TaskServiceError;
+
+/// Header carrying the id of the calling user
+pub const USER_ID_HEADER: &str = "x-user-id";
+
+/// Caller of a request, anonymous when no `X-User-Id` header was sent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: Option<String>,
+}
+
+impl Actor {
+    pub fn user(user_id: impl Into<String>) -> Self {
+        Self { user_id: Some(user_id.into()) }
+    }
+
+    pub fn anonymous() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Actor {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user_id = parts.headers.get(USER_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+        Ok(Self { user_id })
+    }
+}
+
+/// Documents the `X-User-Id` header read by the extractor
+impl IntoParams for Actor {
+    fn into_params(_parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        vec![ParameterBuilder::new()
+            .name(USER_ID_HEADER)
+            .parameter_in(ParameterIn::Header)
+            .required(Required::False)
+            .description(Some("Id of the calling user, anonymous when omitted"))
+            .schema(Some(String::schema()))
+            .build()]
+    }
+}
+
+/// Decides which task operations an actor may perform
+#[async_trait]
+pub trait AuthorizationPolicy {
+    async fn can_read_task(&self, actor: &Actor, task: &Task) -> bool;
+    async fn can_write_task(&self, actor: &Actor, task: &Task) -> bool;
+    async fn can_delete_task(&self, actor: &Actor, task: &Task) -> bool;
+    async fn can_assign_task(&self, actor: &Actor, task: &Task) -> bool;
+}
+
+/// Policy allowing every operation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PermissivePolicy;
+
+#[async_trait]
+impl AuthorizationPolicy for PermissivePolicy {
+    async fn can_read_task(&self, _actor: &Actor, _task: &Task) -> bool {
+        true
+    }
+
+    async fn can_write_task(&self, _actor: &Actor, _task: &Task) -> bool {
+        true
+    }
+
+    async fn can_delete_task(&self, _actor: &Actor, _task: &Task) -> bool {
+        true
+    }
+
+    async fn can_assign_task(&self, _actor: &Actor, _task: &Task) -> bool {
+        true
+    }
+}
+
+/// Policy based on the visibility of the task
+///
+/// Public tasks are open to everyone and shared tasks to any identified user.
+/// Private tasks are only open to their creator and assignees. Writing,
+/// deleting and assigning follow the same rule as reading.
+pub struct VisibilityPolicy {
+    service: Arc<dyn TaskService + Send + Sync>,
+}
+
+impl VisibilityPolicy {
+    pub fn new(service: Arc<dyn TaskService + Send + Sync>) -> Self {
+        Self { service }
+    }
+
+    async fn is_visible(&self, actor: &Actor, task: &Task) -> bool {
+        let user_id = match (&task.visibility, actor.user_id.as_deref()) {
+            (TaskVisibility::Public, _) => return true,
+            (_, None) => return false,
+            (TaskVisibility::Shared, Some(_)) => return true,
+            (TaskVisibility::Private, Some(user_id)) => user_id,
+        };
+        if task.created_by.as_deref() == Some(user_id) {
+            return true;
+        }
+
+        match self.service.get_assigned_tasks(user_id).await {
+            Ok(tasks) => tasks.iter().any(|assigned| assigned.id == task.id),
+            Err(e) => {
+                tracing::warn!("Failed to load assignments of {}, denying access to {}: {}", user_id, task.id, e);
+                false
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AuthorizationPolicy for VisibilityPolicy {
+    async fn can_read_task(&self, actor: &Actor, task: &Task) -> bool {
+        self.is_visible(actor, task).await
+    }
+
+    async fn can_write_task(&self, actor: &Actor, task: &Task) -> bool {
+        self.is_visible(actor, task).await
+    }
+
+    async fn can_delete_task(&self, actor: &Actor, task: &Task) -> bool {
+        self.is_visible(actor, task).await
+    }
+
+    async fn can_assign_task(&self, actor: &Actor, task: &Task) -> bool {
+        self.is_visible(actor, task).await
+    }
+}
+
+/// Turn a policy decision into a `NotAuthorized` error
+pub fn require(allowed: bool, action: &str, task: &Task) -> Result<(), TaskServiceError> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(TaskServiceError::NotAuthorized {
+            action: action.to_string(),
+            resource: format!("task {}", task.id),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{MockTaskService, TaskContext};
+
+    fn task(visibility: TaskVisibility, created_by: Option<&str>) -> Task {
+        let mut task = Task::builder("T-1".to_string(), "Task".to_string(), TaskContext::Work)
+            .visibility(visibility)
+            .build();
+        task.created_by = created_by.map(str::to_string);
+        task
+    }
+
+    #[tokio::test]
+    async fn test_visibility_policy() {
+        let policy = VisibilityPolicy::new(Arc::new(MockTaskService::new()));
+        let private = task(TaskVisibility::Private, Some("alice"));
+        let shared = task(TaskVisibility::Shared, None);
+        let public = task(TaskVisibility::Public, None);
+
+        assert!(policy.can_read_task(&Actor::user("alice"), &private).await);
+        assert!(policy.can_delete_task(&Actor::user("alice"), &private).await);
+        assert!(!policy.can_read_task(&Actor::user("bob"), &private).await);
+        assert!(!policy.can_write_task(&Actor::anonymous(), &private).await);
+
+        assert!(policy.can_read_task(&Actor::user("bob"), &shared).await);
+        assert!(!policy.can_read_task(&Actor::anonymous(), &shared).await);
+
+        assert!(policy.can_assign_task(&Actor::anonymous(), &public).await);
+        assert!(PermissivePolicy.can_delete_task(&Actor::anonymous(), &private).await);
+    }
+
+    #[test]
+    fn test_require_reports_action_and_resource() {
+        let private = task(TaskVisibility::Private, None);
+        assert!(require(true, "read", &private).is_ok());
+
+        match require(false, "delete", &private) {
+            Err(TaskServiceError::NotAuthorized { action, resource }) => {
+                assert_eq!(action, "delete");
+                assert_eq!(resource, "task T-1");
+            }
+            other => panic!("expected NotAuthorized, got {:?}", other),
+        }
+    }
+}
@@ -8,6 +8,12 @@ pub use tyl_config::{ConfigResult, RedisConfig};
 pub use tyl_errors::{TylError, TylResult};
 
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use crate::domain::{ContextSchema, CustomPropertySchema, TaskContext, DEFAULT_STALE_REVIEW_HOURS};
+use crate::plugins::PluginBag;
+use crate::TaskServiceResult;
 
 /// Main configuration for the task service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,8 +34,51 @@ pub struct TaskServiceConfig {
     /// Event system configuration
     pub events: EventConfig,
     
+    /// Outbound delivery of domain events, disabled when unset
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    
+    /// Storage for task attachments, uploads are disabled when unset
+    #[serde(default)]
+    pub attachments: Option<AttachmentStorageConfig>,
+    
+    /// gRPC server run next to the HTTP API, disabled when unset
+    #[serde(default)]
+    pub grpc: Option<GrpcConfig>,
+    
+    /// Maintainers of the API, published in its OpenAPI document
+    #[serde(default)]
+    pub contact: ContactConfig,
+    
+    /// In-memory task cache in front of the graph repository
+    pub cache: CacheConfig,
+    
+    /// Moving old completed tasks to an archive graph
+    #[serde(default)]
+    pub archival: ArchivalConfig,
+    
+    /// Recording every task change to an event graph
+    #[serde(default)]
+    pub event_store: EventStoreConfig,
+    
     /// Logging and monitoring
     pub monitoring: MonitoringConfig,
+    
+    /// Export of handler spans, used while `monitoring.tracing_enabled` is set
+    #[serde(default)]
+    pub tracing: TracingConfig,
+    
+    /// Custom property schemas per task context
+    pub context_schemas: Vec<ContextSchema>,
+    
+    /// JSON Schemas for custom properties per task context
+    #[serde(default)]
+    pub custom_property_schemas: Vec<CustomPropertySchema>,
+    
+    /// Router plugins applied by `create_app` after the built-in ones;
+    /// registered in code, never loaded from configuration
+    #[serde(skip)]
+    pub plugins: PluginBag,
 }
 
 /// API server configuration
@@ -38,7 +87,120 @@ pub struct ApiConfig {
     pub host: String,
     pub port: u16,
     pub request_timeout_ms: u64,
-    pub max_request_size: usize,
+    /// Largest request body accepted, see [`crate::middleware::limits`]
+    #[serde(default)]
+    pub request_size_limit: RequestSizeLimitConfig,
+    pub rate_limit_enabled: bool,
+    /// Limits applied per client when rate limiting is enabled
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Cross-origin requests browsers are allowed to make
+    #[serde(default)]
+    pub cors: CorsConfig,
+    /// Grace period for in-flight requests, and then background event publishes, on shutdown
+    pub shutdown_timeout_seconds: u64,
+    /// Key expected in the `X-Admin-Key` header; admin endpoints are disabled when unset
+    pub admin_api_key: Option<String>,
+    /// Restrict private tasks to their creator and assignees, see [`crate::auth::VisibilityPolicy`]
+    #[serde(default)]
+    pub enforce_task_visibility: bool,
+    /// Serve Swagger UI at `/docs`; the OpenAPI document itself is always served
+    #[serde(default)]
+    pub docs_enabled: bool,
+}
+
+/// CORS policy of the HTTP API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins like `https://app.example.com`; `"*"` allows any origin
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Let browsers send cookies and credentials, only with explicit origins
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response
+    pub max_age_seconds: u64,
+}
+
+impl CorsConfig {
+    /// Read the policy from `CORS_ALLOWED_ORIGINS`, a comma-separated list, or
+    /// `TYL_TASK_SERVICE_API_CORS_*`
+    pub fn from_env() -> Self {
+        let list = |name: &str| {
+            std::env::var(name).ok().map(|v| {
+                v.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect::<Vec<_>>()
+            })
+        };
+        let defaults = Self::default();
+        Self {
+            allowed_origins: list("CORS_ALLOWED_ORIGINS")
+                .or_else(|| list("TYL_TASK_SERVICE_API_CORS_ALLOWED_ORIGINS"))
+                .unwrap_or(defaults.allowed_origins),
+            allowed_methods: list("TYL_TASK_SERVICE_API_CORS_ALLOWED_METHODS").unwrap_or(defaults.allowed_methods),
+            allowed_headers: list("TYL_TASK_SERVICE_API_CORS_ALLOWED_HEADERS").unwrap_or(defaults.allowed_headers),
+            allow_credentials: std::env::var("TYL_TASK_SERVICE_API_CORS_ALLOW_CREDENTIALS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.allow_credentials),
+            max_age_seconds: std::env::var("TYL_TASK_SERVICE_API_CORS_MAX_AGE_SECONDS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.max_age_seconds),
+        }
+    }
+
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"].map(String::from).to_vec(),
+            allowed_headers: ["content-type", "authorization", "if-match", "x-user-id", "x-correlation-id", "x-request-id", "x-admin-key"]
+                .map(String::from)
+                .to_vec(),
+            allow_credentials: false,
+            max_age_seconds: 3600,
+        }
+    }
+}
+
+/// Request body limit of routes without their own, see [`crate::middleware::limits`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestSizeLimitConfig {
+    pub max_body_bytes: usize,
+}
+
+impl RequestSizeLimitConfig {
+    /// Read the limit from `TYL_TASK_SERVICE_API_MAX_BODY_BYTES`, or the older
+    /// `TYL_TASK_SERVICE_API_MAX_REQUEST_SIZE`
+    pub fn from_env() -> Self {
+        Self {
+            max_body_bytes: std::env::var("TYL_TASK_SERVICE_API_MAX_BODY_BYTES")
+                .or_else(|_| std::env::var("TYL_TASK_SERVICE_API_MAX_REQUEST_SIZE"))
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(crate::middleware::limits::DEFAULT_MAX_BODY_BYTES),
+        }
+    }
+}
+
+impl Default for RequestSizeLimitConfig {
+    fn default() -> Self {
+        Self { max_body_bytes: crate::middleware::limits::DEFAULT_MAX_BODY_BYTES }
+    }
+}
+
+/// Every consistency problem found by [`TaskServiceConfig::validate`]
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Invalid configuration: {}", violations.join("; "))]
+pub struct ConfigValidationError {
+    pub violations: Vec<String>,
 }
 
 /// FalkorDB database configuration - extends tyl-config RedisConfig
@@ -50,6 +212,17 @@ pub struct DatabaseConfig {
     pub graph_name: String,
     /// Query timeout in milliseconds
     pub query_timeout_ms: u64,
+    /// Detect circular dependencies with a single Cypher query instead of a
+    /// service-side DFS that issues one query per task
+    pub use_graph_detection: bool,
+    /// Retries for queries that fail because FalkorDB is unreachable
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further attempt
+    pub retry_base_ms: u64,
+    /// How long to fail fast after repeated connection failures
+    pub circuit_breaker_timeout_seconds: u64,
+    /// Consecutive connection failures that open the circuit breaker
+    pub circuit_breaker_failure_threshold: u32,
 }
 
 /// External services configuration
@@ -58,6 +231,9 @@ pub struct ExternalConfig {
     pub timeout_ms: u64,
     pub retry_attempts: u32,
     pub retry_delay_ms: u64,
+    /// Base URL of the user service checking assignees, unchecked when unset
+    #[serde(default)]
+    pub user_service_url: Option<String>,
 }
 
 /// Event system configuration
@@ -67,6 +243,299 @@ pub struct EventConfig {
     pub retry_attempts: u32,
     pub retry_delay_ms: u64,
     pub batch_size: usize,
+    /// Largest number of events accepted by a single `EventService::publish_batch`
+    pub batch_publish_max_size: usize,
+    /// How long a publish waits for room in the event channel before failing
+    pub publish_timeout_ms: u64,
+    /// Events that can be in flight at once before publishers have to wait
+    pub event_channel_depth: usize,
+}
+
+/// Sliding-window rate limit, see [`crate::middleware::rate_limit`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Requests allowed per key in any 60 second window
+    pub requests_per_minute: u32,
+    /// Requests allowed per key in any one second, so the minute's budget cannot be spent at once
+    pub burst_size: u32,
+    pub key_strategy: RateLimitKeyStrategy,
+    /// Peers whose `X-Forwarded-For` and `X-User-Id` headers are believed;
+    /// requests from anyone else are limited by their own address
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// What requests share a rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKeyStrategy {
+    /// Per client address, taken from `X-Forwarded-For` when sent by a trusted proxy
+    IpAddress,
+    /// Per `X-User-Id` when sent by a trusted proxy, else per client address
+    UserId,
+    /// One limit for all requests
+    GlobalShared,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 600,
+            burst_size: 20,
+            key_strategy: RateLimitKeyStrategy::IpAddress,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Read the limits from `TYL_TASK_SERVICE_API_RATE_LIMIT_*`, defaulting each one
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            requests_per_minute: std::env::var("TYL_TASK_SERVICE_API_RATE_LIMIT_REQUESTS_PER_MINUTE")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.requests_per_minute),
+            burst_size: std::env::var("TYL_TASK_SERVICE_API_RATE_LIMIT_BURST_SIZE")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.burst_size),
+            key_strategy: std::env::var("TYL_TASK_SERVICE_API_RATE_LIMIT_KEY_STRATEGY")
+                .ok()
+                .and_then(|v| serde_json::from_value(serde_json::Value::String(v)).ok())
+                .unwrap_or(defaults.key_strategy),
+            // Comma-separated addresses, e.g. `10.0.0.1,10.0.0.2`
+            trusted_proxies: std::env::var("TYL_TASK_SERVICE_API_RATE_LIMIT_TRUSTED_PROXIES")
+                .map(|v| v.split(',').filter_map(|ip| ip.trim().parse().ok()).collect())
+                .unwrap_or(defaults.trusted_proxies),
+        }
+    }
+}
+
+/// Webhook receiving domain events as JSON `POST`s
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Key for the HMAC-SHA256 body signature sent in `X-TYL-Signature`
+    pub secret: Option<String>,
+    /// Topics to deliver; every domain event topic when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+    pub timeout_ms: u64,
+}
+
+impl WebhookConfig {
+    /// Read the webhook from `TYL_TASK_SERVICE_WEBHOOK_*`, `None` without a URL
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("TYL_TASK_SERVICE_WEBHOOK_URL").ok()?;
+        Some(Self {
+            url,
+            secret: std::env::var("TYL_TASK_SERVICE_WEBHOOK_SECRET").ok(),
+            events: std::env::var("TYL_TASK_SERVICE_WEBHOOK_EVENTS")
+                .map(|v| v.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+                .unwrap_or_default(),
+            timeout_ms: std::env::var("TYL_TASK_SERVICE_WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(5000),
+        })
+    }
+}
+
+/// Longest lifetime S3 accepts for a presigned URL, 7 days
+pub const MAX_PRESIGNED_URL_EXPIRY_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Where attachment files are uploaded to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StorageProvider {
+    /// S3-compatible object storage, addressed path-style as `<endpoint>/<bucket>/<key>`
+    S3 { endpoint: String, region: String },
+    /// Directory on a filesystem shared with the uploaders, e.g. in development
+    Local { base_path: PathBuf },
+}
+
+/// Storage of files attached to tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentStorageConfig {
+    pub provider: StorageProvider,
+    pub bucket: String,
+    /// Prepended to every object key, e.g. `attachments/`
+    #[serde(default)]
+    pub prefix: String,
+    pub presigned_url_expiry_seconds: u64,
+}
+
+impl AttachmentStorageConfig {
+    /// Read the storage from `TYL_TASK_SERVICE_ATTACHMENTS_*`, `None` without a bucket
+    ///
+    /// Files go to `TYL_TASK_SERVICE_ATTACHMENTS_LOCAL_PATH` when it is set and
+    /// to the S3 endpoint otherwise.
+    pub fn from_env() -> Option<Self> {
+        let bucket = std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_BUCKET").ok()?;
+        let provider = match std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_LOCAL_PATH") {
+            Ok(base_path) => StorageProvider::Local { base_path: PathBuf::from(base_path) },
+            Err(_) => StorageProvider::S3 {
+                endpoint: std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_S3_ENDPOINT")
+                    .unwrap_or_else(|_| "https://s3.amazonaws.com".to_string()),
+                region: std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_S3_REGION")
+                    .unwrap_or_else(|_| "us-east-1".to_string()),
+            },
+        };
+        Some(Self {
+            provider,
+            bucket,
+            prefix: std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_PREFIX").unwrap_or_default(),
+            presigned_url_expiry_seconds: std::env::var("TYL_TASK_SERVICE_ATTACHMENTS_PRESIGNED_URL_EXPIRY_SECONDS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(900),
+        })
+    }
+}
+
+/// gRPC server exposing the task API to internal services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    pub port: u16,
+    /// Serve the gRPC reflection service, e.g. for `grpcurl`
+    #[serde(default)]
+    pub reflection: bool,
+}
+
+impl GrpcConfig {
+    /// Read the server from `TYL_TASK_SERVICE_GRPC_*`, `None` without a port
+    pub fn from_env() -> Option<Self> {
+        let port = std::env::var("TYL_TASK_SERVICE_GRPC_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())?;
+        Some(Self {
+            port,
+            reflection: std::env::var("TYL_TASK_SERVICE_GRPC_REFLECTION")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+        })
+    }
+}
+
+/// Contact details of the team running the service
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContactConfig {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub url: Option<String>,
+}
+
+impl ContactConfig {
+    /// Read the contact from `TYL_TASK_SERVICE_CONTACT_*`
+    pub fn from_env() -> Self {
+        Self {
+            name: std::env::var("TYL_TASK_SERVICE_CONTACT_NAME").ok(),
+            email: std::env::var("TYL_TASK_SERVICE_CONTACT_EMAIL").ok(),
+            url: std::env::var("TYL_TASK_SERVICE_CONTACT_URL").ok(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.email.is_none() && self.url.is_none()
+    }
+}
+
+/// OTLP export of handler spans, see [`crate::tracing::OtelTracingService`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TracingConfig {
+    /// OTLP gRPC endpoint like `http://localhost:4317`; spans are not exported when empty
+    pub exporter_url: String,
+    /// Service name the exported spans are reported under
+    pub service_name: String,
+}
+
+impl TracingConfig {
+    /// Read the exporter from `TYL_TASK_SERVICE_TRACING_*`, or the standard
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` and `OTEL_SERVICE_NAME`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            exporter_url: std::env::var("TYL_TASK_SERVICE_TRACING_EXPORTER_URL")
+                .or_else(|_| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT"))
+                .unwrap_or(defaults.exporter_url),
+            service_name: std::env::var("TYL_TASK_SERVICE_TRACING_SERVICE_NAME")
+                .or_else(|_| std::env::var("OTEL_SERVICE_NAME"))
+                .unwrap_or(defaults.service_name),
+        }
+    }
+
+    pub fn exports(&self) -> bool {
+        !self.exporter_url.is_empty()
+    }
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            exporter_url: String::new(),
+            service_name: "tyl-task-service".to_string(),
+        }
+    }
+}
+
+/// Task cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub task_ttl_seconds: u64,
+    pub max_capacity: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            task_ttl_seconds: 60,
+            max_capacity: 10_000,
+        }
+    }
+}
+
+/// Archival of completed tasks to a separate graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivalConfig {
+    pub enabled: bool,
+    /// Days after completion before a done task is archived
+    pub archive_after_days: u32,
+    /// FalkorDB graph the archived tasks are moved to
+    pub archive_graph_name: String,
+}
+
+impl Default for ArchivalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            archive_after_days: 90,
+            archive_graph_name: "tyl_tasks_archive".to_string(),
+        }
+    }
+}
+
+/// Event store holding the history of every task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventStoreConfig {
+    pub enabled: bool,
+    /// FalkorDB graph the task events are stored in
+    pub graph_name: String,
+}
+
+impl Default for EventStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            graph_name: "tyl_task_events".to_string(),
+        }
+    }
 }
 
 /// Monitoring and observability configuration
@@ -79,6 +548,68 @@ pub struct MonitoringConfig {
     pub log_format: String, // "console" or "json"
     pub trace_sampling_rate: f64,
     pub max_spans: usize,
+    #[serde(default)]
+    pub alert_thresholds: AlertThresholds,
+}
+
+/// Limits checked by the threshold monitor; breaching one emits a
+/// `ThresholdBreached` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertThresholds {
+    pub max_overdue_tasks: u32,
+    /// Lowest acceptable project health score, from 0 to 100
+    pub min_project_health_score: f64,
+    pub max_single_user_task_count: u32,
+    pub max_dependency_chain_length: u32,
+    /// How often the monitor checks the thresholds
+    pub check_interval_seconds: u64,
+    /// Hours in review after which the review queue flags a task as stale
+    pub stale_review_hours: u64,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self {
+            max_overdue_tasks: 20,
+            min_project_health_score: 60.0,
+            max_single_user_task_count: 15,
+            max_dependency_chain_length: 10,
+            check_interval_seconds: 300,
+            stale_review_hours: DEFAULT_STALE_REVIEW_HOURS,
+        }
+    }
+}
+
+impl AlertThresholds {
+    fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_overdue_tasks: std::env::var("TYL_TASK_SERVICE_ALERT_MAX_OVERDUE_TASKS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.max_overdue_tasks),
+            min_project_health_score: std::env::var("TYL_TASK_SERVICE_ALERT_MIN_PROJECT_HEALTH_SCORE")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.min_project_health_score),
+            max_single_user_task_count: std::env::var("TYL_TASK_SERVICE_ALERT_MAX_SINGLE_USER_TASK_COUNT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.max_single_user_task_count),
+            max_dependency_chain_length: std::env::var("TYL_TASK_SERVICE_ALERT_MAX_DEPENDENCY_CHAIN_LENGTH")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.max_dependency_chain_length),
+            check_interval_seconds: std::env::var("TYL_TASK_SERVICE_ALERT_CHECK_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.check_interval_seconds),
+            stale_review_hours: std::env::var("TYL_TASK_SERVICE_ALERT_STALE_REVIEW_HOURS")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.stale_review_hours),
+        }
+    }
 }
 
 impl TaskServiceConfig {
@@ -103,10 +634,26 @@ impl TaskServiceConfig {
                     .ok()
                     .and_then(|p| p.parse().ok())
                     .unwrap_or(30000),
-                max_request_size: std::env::var("TYL_TASK_SERVICE_API_MAX_REQUEST_SIZE")
+                rate_limit_enabled: std::env::var("TYL_TASK_SERVICE_API_RATE_LIMIT_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(false),
+                rate_limit: RateLimitConfig::from_env(),
+                cors: CorsConfig::from_env(),
+                shutdown_timeout_seconds: std::env::var("TYL_TASK_SERVICE_API_SHUTDOWN_TIMEOUT_SECONDS")
                     .ok()
                     .and_then(|p| p.parse().ok())
-                    .unwrap_or(1024 * 1024), // 1MB default
+                    .unwrap_or(30),
+                admin_api_key: std::env::var("TYL_TASK_SERVICE_API_ADMIN_KEY").ok(),
+                enforce_task_visibility: std::env::var("TYL_TASK_SERVICE_API_ENFORCE_TASK_VISIBILITY")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(false),
+                docs_enabled: std::env::var("TYL_TASK_SERVICE_API_DOCS_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(false),
+                request_size_limit: RequestSizeLimitConfig::from_env(),
             },
             
             database: DatabaseConfig {
@@ -143,6 +690,26 @@ impl TaskServiceConfig {
                     .ok()
                     .and_then(|p| p.parse().ok())
                     .unwrap_or(10000),
+                use_graph_detection: std::env::var("TYL_TASK_SERVICE_DATABASE_USE_GRAPH_DETECTION")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(true),
+                max_retries: std::env::var("TYL_TASK_SERVICE_DATABASE_MAX_RETRIES")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(3),
+                retry_base_ms: std::env::var("TYL_TASK_SERVICE_DATABASE_RETRY_BASE_MS")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(100),
+                circuit_breaker_timeout_seconds: std::env::var("TYL_TASK_SERVICE_DATABASE_CIRCUIT_BREAKER_TIMEOUT_SECONDS")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(30),
+                circuit_breaker_failure_threshold: std::env::var("TYL_TASK_SERVICE_DATABASE_CIRCUIT_BREAKER_FAILURE_THRESHOLD")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(5),
             },
             
             external: ExternalConfig {
@@ -158,6 +725,7 @@ impl TaskServiceConfig {
                     .ok()
                     .and_then(|p| p.parse().ok())
                     .unwrap_or(1000),
+                user_service_url: std::env::var("TYL_TASK_SERVICE_EXTERNAL_USER_SERVICE_URL").ok(),
             },
             
             events: EventConfig {
@@ -177,6 +745,61 @@ impl TaskServiceConfig {
                     .ok()
                     .and_then(|p| p.parse().ok())
                     .unwrap_or(10),
+                batch_publish_max_size: std::env::var("TYL_TASK_SERVICE_EVENTS_BATCH_PUBLISH_MAX_SIZE")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(100),
+                publish_timeout_ms: std::env::var("TYL_TASK_SERVICE_EVENTS_PUBLISH_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(1000),
+                event_channel_depth: std::env::var("TYL_TASK_SERVICE_EVENTS_CHANNEL_DEPTH")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(1000),
+            },
+            
+            webhook: WebhookConfig::from_env(),
+            
+            attachments: AttachmentStorageConfig::from_env(),
+            grpc: GrpcConfig::from_env(),
+            contact: ContactConfig::from_env(),
+            
+            cache: CacheConfig {
+                enabled: std::env::var("TYL_TASK_SERVICE_CACHE_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(true),
+                task_ttl_seconds: std::env::var("TYL_TASK_SERVICE_CACHE_TASK_TTL_SECONDS")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(60),
+                max_capacity: std::env::var("TYL_TASK_SERVICE_CACHE_MAX_CAPACITY")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(10_000),
+            },
+            
+            archival: ArchivalConfig {
+                enabled: std::env::var("TYL_TASK_SERVICE_ARCHIVAL_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(false),
+                archive_after_days: std::env::var("TYL_TASK_SERVICE_ARCHIVAL_ARCHIVE_AFTER_DAYS")
+                    .ok()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(90),
+                archive_graph_name: std::env::var("TYL_TASK_SERVICE_ARCHIVAL_GRAPH_NAME")
+                    .unwrap_or_else(|_| "tyl_tasks_archive".to_string()),
+            },
+            
+            event_store: EventStoreConfig {
+                enabled: std::env::var("TYL_TASK_SERVICE_EVENT_STORE_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(true),
+                graph_name: std::env::var("TYL_TASK_SERVICE_EVENT_STORE_GRAPH_NAME")
+                    .unwrap_or_else(|_| "tyl_task_events".to_string()),
             },
             
             monitoring: MonitoringConfig {
@@ -206,58 +829,241 @@ impl TaskServiceConfig {
                     .ok()
                     .and_then(|p| p.parse().ok())
                     .unwrap_or(1000),
+                alert_thresholds: AlertThresholds::from_env(),
+            },
+            
+            tracing: TracingConfig::from_env(),
+            
+            context_schemas: Self::load_context_schemas()?,
+            
+            custom_property_schemas: match std::env::var("TYL_TASK_SERVICE_CUSTOM_PROPERTY_SCHEMAS_FILE") {
+                Ok(path) => load_custom_property_schemas(std::path::Path::new(&path))?,
+                Err(_) => Vec::new(),
             },
+            
+            plugins: PluginBag::new(),
         })
     }
+    
+    /// Load context schemas from the YAML file named by
+    /// `TYL_TASK_SERVICE_CONTEXT_SCHEMAS_FILE`, falling back to the built-in ones
+    fn load_context_schemas() -> ConfigResult<Vec<ContextSchema>> {
+        let Ok(path) = std::env::var("TYL_TASK_SERVICE_CONTEXT_SCHEMAS_FILE") else {
+            return Ok(ContextSchema::defaults());
+        };
+        
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| TylError::configuration(format!("Cannot read context schemas '{}': {}", path, e)))?;
+        serde_yaml::from_str(&contents)
+            .map_err(|e| TylError::configuration(format!("Invalid context schemas '{}': {}", path, e)))
+    }
 
-    /// Validate configuration values
-    pub fn validate(&self) -> ConfigResult<()> {
+    /// Validate configuration values, reporting every violation found at once
+    pub fn validate(&self) -> TaskServiceResult<()> {
+        let mut violations = Vec::new();
+        
         if self.service_name.is_empty() {
-            return Err(TylError::configuration("Service name cannot be empty"));
+            violations.push("Service name cannot be empty".to_string());
         }
         
         if self.api.port == 0 {
-            return Err(TylError::configuration("API port must be greater than 0"));
+            violations.push("API port must be greater than 0".to_string());
+        }
+        
+        if self.api.request_size_limit.max_body_bytes == 0 {
+            violations.push("API max body bytes must be greater than 0".to_string());
         }
         
         if self.database.redis.host.is_empty() {
-            return Err(TylError::configuration("Database host cannot be empty"));
+            violations.push("Database host cannot be empty".to_string());
         }
         
         if self.database.graph_name.is_empty() {
-            return Err(TylError::configuration("Graph name cannot be empty"));
+            violations.push("Graph name cannot be empty".to_string());
         }
         
         if self.database.redis.port == 0 {
-            return Err(TylError::configuration("Database port must be greater than 0"));
+            violations.push("Database port must be greater than 0".to_string());
+        }
+        
+        if self.database.query_timeout_ms == 0 {
+            violations.push("Database query timeout must be greater than 0".to_string());
+        }
+        
+        if self.database.circuit_breaker_timeout_seconds == 0 {
+            violations.push("Circuit breaker timeout must be greater than 0".to_string());
+        }
+        
+        if self.database.circuit_breaker_failure_threshold == 0 {
+            violations.push("Circuit breaker failure threshold must be greater than 0".to_string());
         }
         
         // Validate log level
-        let valid_levels = ["error", "warn", "info", "debug", "trace"];
+        let valid_levels = ["debug", "info", "warn", "error"];
         if !valid_levels.contains(&self.monitoring.log_level.as_str()) {
-            return Err(TylError::configuration(
-                format!("Invalid log level '{}'. Must be one of: {}", 
-                    self.monitoring.log_level, valid_levels.join(", "))
-            ));
+            violations.push(format!("Invalid log level '{}'. Must be one of: {}", 
+                self.monitoring.log_level, valid_levels.join(", ")));
         }
         
         // Validate log format
         let valid_formats = ["console", "json"];
         if !valid_formats.contains(&self.monitoring.log_format.as_str()) {
-            return Err(TylError::configuration(
-                format!("Invalid log format '{}'. Must be one of: {}", 
-                    self.monitoring.log_format, valid_formats.join(", "))
-            ));
+            violations.push(format!("Invalid log format '{}'. Must be one of: {}", 
+                self.monitoring.log_format, valid_formats.join(", ")));
         }
         
         // Validate trace sampling rate
         if self.monitoring.trace_sampling_rate < 0.0 || self.monitoring.trace_sampling_rate > 1.0 {
-            return Err(TylError::configuration(
-                "Trace sampling rate must be between 0.0 and 1.0".to_string()
-            ));
+            violations.push("Trace sampling rate must be between 0.0 and 1.0".to_string());
         }
         
-        Ok(())
+        if self.tracing.exports() {
+            if reqwest::Url::parse(&self.tracing.exporter_url).is_err() {
+                violations.push(format!("Invalid tracing exporter URL '{}'", self.tracing.exporter_url));
+            }
+            if self.tracing.service_name.trim().is_empty() {
+                violations.push("Tracing service name cannot be empty".to_string());
+            }
+        }
+        
+        let rate_limit = &self.api.rate_limit;
+        if self.api.rate_limit_enabled && (rate_limit.requests_per_minute == 0 || rate_limit.burst_size == 0) {
+            violations.push("Rate limit must allow at least 1 request per minute and a burst of 1 when enabled".to_string());
+        }
+        
+        // Explicit CORS origins must be absolute URLs
+        let cors = &self.api.cors;
+        if cors.allows_any_origin() {
+            if cors.allow_credentials {
+                violations.push("CORS credentials cannot be allowed for any origin".to_string());
+            }
+        } else {
+            for origin in &cors.allowed_origins {
+                if reqwest::Url::parse(origin).is_err() || axum::http::HeaderValue::from_str(origin).is_err() {
+                    violations.push(format!("Invalid CORS origin '{}'", origin));
+                }
+            }
+        }
+        for method in &cors.allowed_methods {
+            if axum::http::Method::from_bytes(method.as_bytes()).is_err() {
+                violations.push(format!("Invalid CORS method '{}'", method));
+            }
+        }
+        for header in &cors.allowed_headers {
+            if axum::http::HeaderName::from_bytes(header.as_bytes()).is_err() {
+                violations.push(format!("Invalid CORS header '{}'", header));
+            }
+        }
+        
+        let thresholds = &self.monitoring.alert_thresholds;
+        if !(0.0..=100.0).contains(&thresholds.min_project_health_score) {
+            violations.push("Minimum project health score must be between 0 and 100".to_string());
+        }
+        
+        if thresholds.check_interval_seconds == 0 {
+            violations.push("Alert check interval must be greater than 0".to_string());
+        }
+        
+        if self.archival.enabled {
+            if self.archival.archive_after_days == 0 {
+                violations.push("Archival threshold must be at least 1 day".to_string());
+            }
+            if self.archival.archive_graph_name.is_empty() || self.archival.archive_graph_name == self.database.graph_name {
+                violations.push("Archive graph name must be set and differ from the task graph".to_string());
+            }
+        }
+        
+        if self.event_store.enabled
+            && (self.event_store.graph_name.is_empty() || self.event_store.graph_name == self.database.graph_name)
+        {
+            violations.push("Event store graph name must be set and differ from the task graph".to_string());
+        }
+        
+        if self.events.batch_publish_max_size == 0 {
+            violations.push("Event batch publish size must be greater than 0".to_string());
+        }
+        
+        if self.events.publish_timeout_ms == 0 {
+            violations.push("Event publish timeout must be greater than 0".to_string());
+        }
+        
+        if self.events.event_channel_depth == 0 {
+            violations.push("Event channel depth must be greater than 0".to_string());
+        }
+        
+        if let Some(ref webhook) = self.webhook {
+            if reqwest::Url::parse(&webhook.url).is_err() {
+                violations.push(format!("Invalid webhook URL '{}'", webhook.url));
+            }
+            if webhook.timeout_ms == 0 {
+                violations.push("Webhook timeout must be greater than 0".to_string());
+            }
+        }
+        
+        if let Some(ref attachments) = self.attachments {
+            if attachments.bucket.trim().is_empty() {
+                violations.push("Attachment bucket cannot be empty".to_string());
+            }
+            if attachments.presigned_url_expiry_seconds == 0
+                || attachments.presigned_url_expiry_seconds > MAX_PRESIGNED_URL_EXPIRY_SECONDS
+            {
+                violations.push(format!(
+                    "Presigned URL expiry must be between 1 and {} seconds",
+                    MAX_PRESIGNED_URL_EXPIRY_SECONDS
+                ));
+            }
+            if let StorageProvider::S3 { ref endpoint, ref region } = attachments.provider {
+                if reqwest::Url::parse(endpoint).is_err() {
+                    violations.push(format!("Invalid attachment storage endpoint '{}'", endpoint));
+                }
+                if region.trim().is_empty() {
+                    violations.push("Attachment storage region cannot be empty".to_string());
+                }
+            }
+        }
+        
+        if let Some(ref url) = self.external.user_service_url {
+            if reqwest::Url::parse(url).is_err() {
+                violations.push(format!("Invalid user service URL '{}'", url));
+            }
+        }
+        
+        if let Some(ref url) = self.contact.url {
+            if reqwest::Url::parse(url).is_err() {
+                violations.push(format!("Invalid contact URL '{}'", url));
+            }
+        }
+        
+        if let Some(ref grpc) = self.grpc {
+            if grpc.port == 0 || grpc.port == self.api.port {
+                violations.push("gRPC port must be greater than 0 and differ from the API port".to_string());
+            }
+        }
+        
+        if self.api.shutdown_timeout_seconds == 0 {
+            violations.push("Shutdown timeout must be greater than 0".to_string());
+        }
+        
+        for (index, schema) in self.context_schemas.iter().enumerate() {
+            if self.context_schemas[..index].iter().any(|other| other.context == schema.context) {
+                violations.push(format!("Duplicate context schema for {:?}", schema.context));
+            }
+        }
+        
+        for (index, schema) in self.custom_property_schemas.iter().enumerate() {
+            if self.custom_property_schemas[..index].iter().any(|other| other.context == schema.context) {
+                violations.push(format!("Duplicate custom property schema for {:?}", schema.context));
+            }
+            if let Err(e) = jsonschema::JSONSchema::compile(&schema.schema) {
+                violations.push(format!("Invalid custom property schema for {:?}: {}", schema.context, e));
+            }
+        }
+        
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigValidationError { violations }.into())
+        }
     }
 }
 
@@ -270,24 +1076,47 @@ impl Default for TaskServiceConfig {
                 host: "0.0.0.0".to_string(),
                 port: 3000,
                 request_timeout_ms: 30000,
-                max_request_size: 1024 * 1024,
+                request_size_limit: RequestSizeLimitConfig::default(),
+                rate_limit_enabled: false,
+                rate_limit: RateLimitConfig::default(),
+                cors: CorsConfig::default(),
+                shutdown_timeout_seconds: 30,
+                admin_api_key: None,
+                enforce_task_visibility: false,
+                docs_enabled: false,
             },
             database: DatabaseConfig {
                 redis: RedisConfig::default(),
                 graph_name: "tyl_tasks".to_string(),
                 query_timeout_ms: 10000,
+                use_graph_detection: true,
+                max_retries: 3,
+                retry_base_ms: 100,
+                circuit_breaker_timeout_seconds: 30,
+                circuit_breaker_failure_threshold: 5,
             },
             external: ExternalConfig {
                 timeout_ms: 10000,
                 retry_attempts: 3,
                 retry_delay_ms: 1000,
+                user_service_url: None,
             },
             events: EventConfig {
                 enabled: true,
                 retry_attempts: 3,
                 retry_delay_ms: 1000,
                 batch_size: 10,
+                batch_publish_max_size: 100,
+                publish_timeout_ms: 1000,
+                event_channel_depth: 1000,
             },
+            webhook: None,
+            attachments: None,
+            grpc: None,
+            contact: ContactConfig::default(),
+            cache: CacheConfig::default(),
+            archival: ArchivalConfig::default(),
+            event_store: EventStoreConfig::default(),
             monitoring: MonitoringConfig {
                 metrics_enabled: true,
                 tracing_enabled: true,
@@ -296,11 +1125,48 @@ impl Default for TaskServiceConfig {
                 log_format: "console".to_string(),
                 trace_sampling_rate: 1.0,
                 max_spans: 1000,
+                alert_thresholds: AlertThresholds::default(),
             },
+            tracing: TracingConfig::default(),
+            context_schemas: ContextSchema::defaults(),
+            custom_property_schemas: Vec::new(),
+            plugins: PluginBag::new(),
         }
     }
 }
 
+/// Entry of the custom property schema index file
+#[derive(Debug, Deserialize)]
+struct CustomPropertySchemaFile {
+    context: TaskContext,
+    /// YAML or JSON file holding the schema, relative to the index file
+    schema_file: std::path::PathBuf,
+}
+
+/// Load the JSON Schemas listed in a YAML index of `context`/`schema_file` entries
+fn load_custom_property_schemas(index_path: &std::path::Path) -> ConfigResult<Vec<CustomPropertySchema>> {
+    let read = |path: &std::path::Path| {
+        std::fs::read_to_string(path).map_err(|e| {
+            TylError::configuration(format!("Cannot read custom property schemas '{}': {}", path.display(), e))
+        })
+    };
+    let invalid = |path: &std::path::Path, e: serde_yaml::Error| {
+        TylError::configuration(format!("Invalid custom property schemas '{}': {}", path.display(), e))
+    };
+
+    let entries: Vec<CustomPropertySchemaFile> = serde_yaml::from_str(&read(index_path)?)
+        .map_err(|e| invalid(index_path, e))?;
+    let base = index_path.parent().unwrap_or_else(|| std::path::Path::new("."));
+
+    entries.into_iter()
+        .map(|entry| {
+            let path = base.join(&entry.schema_file);
+            let schema = serde_yaml::from_str(&read(&path)?).map_err(|e| invalid(&path, e))?;
+            Ok(CustomPropertySchema { context: entry.context, schema })
+        })
+        .collect()
+}
+
 
 /// Configuration utilities
 impl TaskServiceConfig {
@@ -319,6 +1185,11 @@ impl TaskServiceConfig {
                 },
                 graph_name: "tyl_tasks_test".to_string(),
                 query_timeout_ms: 5000,
+                use_graph_detection: true,
+                max_retries: 1,
+                retry_base_ms: 10,
+                circuit_breaker_timeout_seconds: 1,
+                circuit_breaker_failure_threshold: 2,
             },
             monitoring: MonitoringConfig {
                 log_level: "debug".to_string(),
@@ -382,6 +1253,98 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_tracing_exporter_validated_only_when_set() {
+        let mut config = TaskServiceConfig::default();
+        assert!(!config.tracing.exports());
+        
+        config.tracing.exporter_url = "not a url".to_string();
+        assert!(config.validate().is_err());
+        
+        config.tracing.exporter_url = "http://otel-collector:4317".to_string();
+        assert!(config.validate().is_ok());
+        config.tracing.service_name = " ".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_reports_all_violations() {
+        let mut config = TaskServiceConfig::default();
+        config.api.port = 0;
+        config.database.graph_name = String::new();
+        config.api.rate_limit_enabled = true;
+        config.api.rate_limit.requests_per_minute = 0;
+        config.api.cors.allowed_origins = vec!["https://app.example.com".to_string(), "not a url".to_string()];
+        config.api.shutdown_timeout_seconds = 0;
+        config.contact.url = Some("team page".to_string());
+        
+        match config.validate() {
+            Err(crate::TaskServiceError::ConfigValidation(err)) => {
+                assert_eq!(err.violations.len(), 6);
+                assert!(err.violations.iter().any(|v| v.contains("not a url")));
+                assert!(err.violations.iter().any(|v| v.contains("Invalid contact URL 'team page'")));
+            }
+            other => panic!("expected ConfigValidation error, got {:?}", other),
+        }
+        
+        config = TaskServiceConfig::default();
+        config.api.cors.allowed_origins = vec!["https://app.example.com".to_string()];
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_context_schemas_from_yaml() {
+        let yaml = r#"
+- context: work
+  required_custom_fields:
+    - name: ticket_number
+      field_type: string
+      description: Reference in the issue tracker
+- context: maintenance
+  optional_custom_fields:
+    - name: window
+      field_type:
+        enum: [weekday, weekend]
+      description: null
+"#;
+        let schemas: Vec<ContextSchema> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(schemas.len(), 2);
+        assert_eq!(schemas[0].required_custom_fields[0].name, "ticket_number");
+        assert!(schemas[1].required_custom_fields.is_empty());
+        
+        let mut config = TaskServiceConfig::default();
+        config.context_schemas = schemas.clone();
+        config.context_schemas.push(schemas[0].clone());
+        assert!(config.validate().is_err());
+    }
+    
+    #[test]
+    fn test_custom_property_schemas_from_yaml_files() {
+        let dir = std::env::temp_dir().join(format!("custom-property-schemas-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("index.yaml"), "- context: work\n  schema_file: work.yaml\n").unwrap();
+        std::fs::write(dir.join("work.yaml"), r#"
+type: object
+required: [ticket_number]
+properties:
+  ticket_number:
+    type: string
+"#).unwrap();
+
+        let schemas = load_custom_property_schemas(&dir.join("index.yaml")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(schemas[0].context, TaskContext::Work);
+        assert_eq!(schemas[0].schema["required"][0], "ticket_number");
+
+        let mut config = TaskServiceConfig::default();
+        config.custom_property_schemas = schemas;
+        assert!(config.validate().is_ok());
+        config.custom_property_schemas[0].schema = serde_json::json!({ "type": "not-a-type" });
+        assert!(config.validate().is_err());
+    }
+    
     #[test]
     fn test_env_loading() {
         // Test with empty environment - should work with defaults
@@ -413,5 +1376,7 @@ mod tests {
         assert!(config.events.retry_attempts > 0);
         assert!(config.events.retry_delay_ms > 0);
         assert!(config.events.batch_size > 0);
+        assert!(config.events.publish_timeout_ms > 0);
+        assert!(config.events.event_channel_depth > 0);
     }
 }
\ No newline at end of file
@@ -7,8 +7,36 @@
 pub mod models;
 pub mod services;
 pub mod queries;
+pub mod rules;
+pub mod planning;
+pub mod similarity;
+pub mod natural_language;
+pub mod sentiment;
+pub mod recurrence;
+pub mod dependency_graph;
+pub mod project_service;
+pub mod search;
+pub mod user_port;
+pub mod scheduler;
 
 // Re-export commonly used types
 pub use models::*;
 pub use services::*;
-pub use queries::*;
\ No newline at end of file
+pub use queries::*;
+pub use rules::*;
+pub use planning::*;
+pub use similarity::TfIdfIndex;
+pub use natural_language::{parse_natural_language_task, NaturalLanguageParseResult};
+pub use sentiment::{CommentSentiment, SentimentTrend, DISCUSSION_HEALTH_ALERT_THRESHOLD};
+pub use recurrence::RecurrenceService;
+pub use dependency_graph::DependencyGraph;
+pub use search::{
+    search_order_clause, search_tasks_in_memory, validate_search_query, PageRequest, PagedResult, SearchOp, MAX_SEARCH_PAGE_SIZE,
+    SortDirection, SortSpec, TaskSearchQuery, MAX_SEARCH_CONDITIONS, SEARCHABLE_FIELDS,
+};
+pub use user_port::{ensure_user_known, UserServicePort};
+pub use scheduler::{SchedulingReport, TaskScheduler};
+pub use project_service::{
+    transfer_ownership_in_repository, validate_create_project_request, MockProjectService, OwnershipTransferResult, ProjectMilestone,
+    ProjectService, ProjectSummary,
+};
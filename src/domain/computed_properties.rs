@@ -7,11 +7,15 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::collections::HashMap;
 use tyl_errors::{TylError, TylResult};
 use tyl_falkordb_adapter::FalkorDBAdapter;
 
+use crate::adapters::cypher::{cypher_params, CypherParams, ExecuteCypherWithParams};
+
 use super::{Task, TaskStatus, TaskPriority, DependencyType, TaskContext};
+use super::rules::{ValidationResult, RuleViolation, RuleWarning, ViolationSeverity};
 
 /// Service for computing dynamic task properties based on graph relationships
 #[async_trait]
@@ -64,12 +68,10 @@ impl GraphComputedPropertyService {
     
     /// Get task details from the graph
     async fn get_task(&self, task_id: &str) -> TylResult<Option<Task>> {
-        let query = format!(
-            "MATCH (t:Task {{id: '{}'}}) RETURN t",
-            task_id
-        );
+        let query = "MATCH (t:Task {id: $task_id}) RETURN t";
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let result = self.adapter.execute_cypher(&query).await?;
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse the result and convert to Task
         // This is a simplified implementation - in reality, you'd parse the graph result
@@ -77,30 +79,30 @@ impl GraphComputedPropertyService {
     }
     
     /// Execute a Cypher query and get numeric result
-    async fn execute_numeric_query(&self, query: &str) -> TylResult<f64> {
-        let result = self.adapter.execute_cypher(query).await?;
+    async fn execute_numeric_query(&self, query: &str, params: CypherParams) -> TylResult<f64> {
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         // Parse numeric result from graph response
         // This is simplified - real implementation would parse the actual result
         Ok(0.0)
     }
     
     /// Execute a Cypher query and get count result
-    async fn execute_count_query(&self, query: &str) -> TylResult<u32> {
-        let result = self.adapter.execute_cypher(query).await?;
+    async fn execute_count_query(&self, query: &str, params: CypherParams) -> TylResult<u32> {
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         // Parse count result from graph response
         Ok(0)
     }
     
     /// Execute a Cypher query and get boolean result
-    async fn execute_boolean_query(&self, query: &str) -> TylResult<bool> {
-        let result = self.adapter.execute_cypher(query).await?;
+    async fn execute_boolean_query(&self, query: &str, params: CypherParams) -> TylResult<bool> {
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         // Parse boolean result from graph response
         Ok(false)
     }
     
     /// Execute a Cypher query and get string list result
-    async fn execute_string_list_query(&self, query: &str) -> TylResult<Vec<String>> {
-        let result = self.adapter.execute_cypher(query).await?;
+    async fn execute_string_list_query(&self, query: &str, params: CypherParams) -> TylResult<Vec<String>> {
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         // Parse string list result from graph response
         Ok(vec![])
     }
@@ -110,9 +112,8 @@ impl GraphComputedPropertyService {
 impl ComputedPropertyService for GraphComputedPropertyService {
     async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
         // Calculate completion based on subtasks
-        let query = format!(
-            r#"
-            MATCH (parent:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (parent:Task {id: $task_id})
             OPTIONAL MATCH (parent)-[:SUBTASK_OF]->(child:Task)
             WITH parent, count(child) as total_subtasks, 
                  count(CASE WHEN child.status = 'Done' THEN 1 END) as completed_subtasks
@@ -124,112 +125,97 @@ impl ComputedPropertyService for GraphComputedPropertyService {
                     END
                 ELSE (completed_subtasks * 100.0) / total_subtasks
             END as completion_percentage
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_numeric_query(&query).await
+        self.execute_numeric_query(query, params).await
     }
     
     async fn is_task_actionable(&self, task_id: &str) -> TylResult<bool> {
         // A task is actionable if it's Ready/InProgress and all dependencies are Done
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             WHERE t.status IN ['Ready', 'InProgress']
             OPTIONAL MATCH (t)-[:DEPENDS_ON]->(dep:Task)
             WITH t, count(dep) as total_deps, count(CASE WHEN dep.status = 'Done' THEN 1 END) as completed_deps
             RETURN total_deps = completed_deps as is_actionable
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn is_task_blocked(&self, task_id: &str) -> TylResult<bool> {
         // A task is blocked if it has incomplete dependencies
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             MATCH (t)-[:DEPENDS_ON]->(dep:Task)
             WHERE dep.status <> 'Done'
             RETURN count(dep) > 0 as is_blocked
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn is_task_on_critical_path(&self, task_id: &str) -> TylResult<bool> {
         // Critical path calculation - simplified version
-        let query = format!(
-            r#"
-            MATCH path = (start:Task)-[:DEPENDS_ON*]->(t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH path = (start:Task)-[:DEPENDS_ON*]->(t:Task {id: $task_id})
             WHERE NOT EXISTS((start)-[:DEPENDS_ON]->())
             WITH path, length(path) as path_length
             ORDER BY path_length DESC
             LIMIT 1
             RETURN path_length > 3 as is_critical
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn get_blocking_tasks(&self, task_id: &str) -> TylResult<Vec<String>> {
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             MATCH (t)-[:DEPENDS_ON]->(blocking:Task)
             WHERE blocking.status <> 'Done'
             RETURN blocking.id as blocking_task_id
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_string_list_query(&query).await
+        self.execute_string_list_query(query, params).await
     }
     
     async fn get_blocked_tasks(&self, task_id: &str) -> TylResult<Vec<String>> {
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             MATCH (blocked:Task)-[:DEPENDS_ON]->(t)
             RETURN blocked.id as blocked_task_id
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_string_list_query(&query).await
+        self.execute_string_list_query(query, params).await
     }
     
     async fn calculate_dependency_chain_length(&self, task_id: &str) -> TylResult<u32> {
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             OPTIONAL MATCH path = (t)-[:DEPENDS_ON*]->(dep:Task)
             RETURN max(length(path)) as max_chain_length
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_count_query(&query).await
+        self.execute_count_query(query, params).await
     }
     
     async fn calculate_earliest_start_date(&self, task_id: &str) -> TylResult<Option<DateTime<Utc>>> {
         // Calculate based on dependency completion dates
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             OPTIONAL MATCH (t)-[:DEPENDS_ON]->(dep:Task)
             WITH t, max(dep.estimated_completion_date) as latest_dependency_end
             RETURN COALESCE(latest_dependency_end, t.created_at) as earliest_start
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
         // This would parse the date result from the graph
         // For now, return None as placeholder
@@ -261,25 +247,22 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn is_task_overdue(&self, task_id: &str) -> TylResult<bool> {
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             WHERE t.due_date IS NOT NULL 
               AND datetime(t.due_date) < datetime()
               AND t.status <> 'Done'
             RETURN count(t) > 0 as is_overdue
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn is_task_at_risk(&self, task_id: &str) -> TylResult<bool> {
         // A task is at risk if it's close to due date or has many blocking dependencies
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             OPTIONAL MATCH (t)-[:DEPENDS_ON]->(dep:Task)
             WHERE dep.status <> 'Done'
             WITH t, count(dep) as blocking_count
@@ -288,11 +271,10 @@ impl ComputedPropertyService for GraphComputedPropertyService {
                  duration.between(datetime(), datetime(t.due_date)).days < 3) OR
                 blocking_count > 2
             ) as is_at_risk
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn calculate_priority_score(&self, task_id: &str) -> TylResult<f64> {
@@ -355,35 +337,31 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     
     async fn has_required_resources(&self, task_id: &str) -> TylResult<bool> {
         // Check if task has assigned users and required skills/resources
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             OPTIONAL MATCH (t)-[:ASSIGNED_TO]->(u:User)
             OPTIONAL MATCH (t)-[:REQUIRES]->(r:Resource)
             WITH t, count(u) as assigned_users, count(r) as required_resources
             RETURN assigned_users > 0 AND required_resources = 0 as has_resources
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
     
     async fn calculate_resource_utilization(&self, task_id: &str) -> TylResult<f64> {
         // Calculate resource utilization for assigned users
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             MATCH (t)-[:ASSIGNED_TO]->(u:User)
             OPTIONAL MATCH (u)<-[:ASSIGNED_TO]-(other:Task)
             WHERE other.status IN ['Ready', 'InProgress'] AND other.id <> t.id
             WITH u, count(other) as other_tasks
             RETURN avg(other_tasks) as avg_utilization
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_numeric_query(&query).await
+        self.execute_numeric_query(query, params).await
     }
     
     async fn calculate_complexity_score(&self, task_id: &str) -> TylResult<f64> {
@@ -431,15 +409,13 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     
     async fn is_task_stale(&self, task_id: &str) -> TylResult<bool> {
         // A task is stale if it hasn't been updated in a while
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             RETURN duration.between(datetime(t.updated_at), datetime()).days > 30 as is_stale
-            "#,
-            task_id
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        self.execute_boolean_query(&query).await
+        self.execute_boolean_query(query, params).await
     }
 }
 
@@ -476,67 +452,6 @@ pub trait BusinessRulesEngine {
 // Result Types
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ValidationResult {
-    pub is_valid: bool,
-    pub violations: Vec<RuleViolation>,
-    pub warnings: Vec<RuleWarning>,
-    pub suggestions: Vec<String>,
-}
-
-impl ValidationResult {
-    pub fn valid() -> Self {
-        Self {
-            is_valid: true,
-            violations: Vec::new(),
-            warnings: Vec::new(),
-            suggestions: Vec::new(),
-        }
-    }
-    
-    pub fn invalid(violation: RuleViolation) -> Self {
-        Self {
-            is_valid: false,
-            violations: vec![violation],
-            warnings: Vec::new(),
-            suggestions: Vec::new(),
-        }
-    }
-    
-    pub fn with_warnings(mut self, warnings: Vec<RuleWarning>) -> Self {
-        self.warnings = warnings;
-        self
-    }
-    
-    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
-        self.suggestions = suggestions;
-        self
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RuleViolation {
-    pub rule_name: String,
-    pub severity: ViolationSeverity,
-    pub message: String,
-    pub field: Option<String>,
-    pub suggested_fix: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RuleWarning {
-    pub rule_name: String,
-    pub message: String,
-    pub recommendation: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum ViolationSeverity {
-    Error,   // Blocks the operation
-    Warning, // Allows operation but not recommended
-    Info,    // Informational only
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapacityCheck {
     pub user_id: String,
@@ -590,10 +505,10 @@ impl GraphComputedPropertyService {
     }
     
     /// Build Cypher query to calculate completion percentage based on subtasks
-    fn build_completion_percentage_query(&self, task_id: &str) -> String {
-        format!(
+    fn build_completion_percentage_query(&self, task_id: &str) -> (&'static str, CypherParams) {
+        (
             r#"
-            MATCH (parent:Task {{id: '{}'}})
+            MATCH (parent:Task {id: $task_id})
             OPTIONAL MATCH (parent)<-[:SUBTASK_OF]-(child:Task)
             WITH parent, 
                  count(child) as total_subtasks,
@@ -605,31 +520,31 @@ impl GraphComputedPropertyService {
                 ELSE (completed_subtasks * 100.0 / total_subtasks)
               END as completion_percentage
             "#,
-            task_id.replace('\'', "\\'")
+            cypher_params([("task_id", json!(task_id))]),
         )
     }
     
     /// Build query to check if task is actionable (no blocking dependencies)
-    fn build_actionable_check_query(&self, task_id: &str) -> String {
-        format!(
+    fn build_actionable_check_query(&self, task_id: &str) -> (&'static str, CypherParams) {
+        (
             r#"
-            MATCH (task:Task {{id: '{}'}})
-            OPTIONAL MATCH (task)-[:DEPENDS_ON {{dependency_type: 'blocks'}}]->(blocking:Task)
+            MATCH (task:Task {id: $task_id})
+            OPTIONAL MATCH (task)-[:DEPENDS_ON {dependency_type: 'blocks'}]->(blocking:Task)
             WHERE blocking.status != 'done'
             WITH task, count(blocking) as blocking_count
             RETURN 
               task.status IN ['ready', 'in_progress'] AND 
               blocking_count = 0 as is_actionable
             "#,
-            task_id.replace('\'', "\\'")
+            cypher_params([("task_id", json!(task_id))]),
         )
     }
     
     /// Build query to calculate priority score based on multiple factors
-    fn build_priority_score_query(&self, task_id: &str) -> String {
-        format!(
+    fn build_priority_score_query(&self, task_id: &str) -> (&'static str, CypherParams) {
+        (
             r#"
-            MATCH (task:Task {{id: '{}'}})
+            MATCH (task:Task {id: $task_id})
             
             // Base priority score
             WITH task,
@@ -654,7 +569,7 @@ impl GraphComputedPropertyService {
               END as urgency_score
             
             // Impact based on blocking relationships
-            OPTIONAL MATCH (task)<-[:DEPENDS_ON {{dependency_type: 'blocks'}}]-(blocked:Task)
+            OPTIONAL MATCH (task)<-[:DEPENDS_ON {dependency_type: 'blocks'}]-(blocked:Task)
             WHERE blocked.status != 'done'
             WITH task, base_priority, urgency_score, count(blocked) as blocked_count
             
@@ -668,7 +583,7 @@ impl GraphComputedPropertyService {
             
             RETURN (base_priority + urgency_score + impact_score) as priority_score
             "#,
-            task_id.replace('\'', "\\'")
+            cypher_params([("task_id", json!(task_id))]),
         )
     }
 }
@@ -676,8 +591,8 @@ impl GraphComputedPropertyService {
 #[async_trait]
 impl ComputedPropertyService for GraphComputedPropertyService {
     async fn calculate_completion_percentage(&self, task_id: &str) -> TylResult<f64> {
-        let query = self.build_completion_percentage_query(task_id);
-        let result = self.adapter.execute_cypher(&query).await?;
+        let (query, params) = self.build_completion_percentage_query(task_id);
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse result from Cypher query
         // In a real implementation, we would parse the JSON response
@@ -686,8 +601,8 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn is_task_actionable(&self, task_id: &str) -> TylResult<bool> {
-        let query = self.build_actionable_check_query(task_id);
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let (query, params) = self.build_actionable_check_query(task_id);
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse boolean result from Cypher
         // For now, return true as default
@@ -695,17 +610,15 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn is_task_blocked(&self, task_id: &str) -> TylResult<bool> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
-            OPTIONAL MATCH (task)-[:DEPENDS_ON {{dependency_type: 'blocks'}}]->(blocking:Task)
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
+            OPTIONAL MATCH (task)-[:DEPENDS_ON {dependency_type: 'blocks'}]->(blocking:Task)
             WHERE blocking.status NOT IN ['done', 'cancelled']
             RETURN count(blocking) > 0 as is_blocked
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse result
         Ok(false) // Default implementation
@@ -718,58 +631,51 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn get_blocking_tasks(&self, task_id: &str) -> TylResult<Vec<String>> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             MATCH (task)-[:DEPENDS_ON]->(blocking:Task)
             WHERE blocking.status NOT IN ['done', 'cancelled']
             RETURN collect(blocking.id) as blocking_task_ids
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse and return task IDs
         Ok(vec![]) // Default implementation
     }
     
     async fn get_blocked_tasks(&self, task_id: &str) -> TylResult<Vec<String>> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             MATCH (blocked:Task)-[:DEPENDS_ON]->(task)
             WHERE blocked.status NOT IN ['done', 'cancelled']
             RETURN collect(blocked.id) as blocked_task_ids
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse and return task IDs
         Ok(vec![]) // Default implementation
     }
     
     async fn calculate_dependency_chain_length(&self, task_id: &str) -> TylResult<u32> {
-        let query = format!(
-            r#"
-            MATCH path = (task:Task {{id: '{}'}})-[:DEPENDS_ON*]->(dependency:Task)
+        let query = r#"
+            MATCH path = (task:Task {id: $task_id})-[:DEPENDS_ON*]->(dependency:Task)
             RETURN max(length(path)) as max_chain_length
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(0) // Default implementation
     }
     
     async fn calculate_earliest_start_date(&self, task_id: &str) -> TylResult<Option<DateTime<Utc>>> {
         // Calculate based on dependency completion dates
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             OPTIONAL MATCH (task)-[:DEPENDS_ON]->(dependency:Task)
             WHERE dependency.status != 'done'
             
@@ -780,11 +686,10 @@ impl ComputedPropertyService for GraphComputedPropertyService {
                 WHEN latest_dependency_date IS NULL THEN datetime()
                 ELSE latest_dependency_date
               END as earliest_start_date
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // For now, return current time as default
         Ok(Some(Utc::now()))
@@ -797,18 +702,16 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn is_task_overdue(&self, task_id: &str) -> TylResult<bool> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             RETURN 
               task.due_date IS NOT NULL AND 
               task.due_date < datetime() AND
               task.status NOT IN ['done', 'cancelled'] as is_overdue
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(false) // Default implementation
     }
@@ -819,9 +722,8 @@ impl ComputedPropertyService for GraphComputedPropertyService {
         // 2. Its dependencies are delayed
         // 3. Assigned user is overloaded
         
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             
             // Check if approaching due date
             WITH task,
@@ -836,18 +738,17 @@ impl ComputedPropertyService for GraphComputedPropertyService {
             WITH task, approaching_due, count(dep) as delayed_dependencies
             
             RETURN approaching_due OR delayed_dependencies > 0 as is_at_risk
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(false) // Default implementation
     }
     
     async fn calculate_priority_score(&self, task_id: &str) -> TylResult<f64> {
-        let query = self.build_priority_score_query(task_id);
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let (query, params) = self.build_priority_score_query(task_id);
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse numerical result
         Ok(50.0) // Default medium priority score
@@ -855,9 +756,8 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     
     async fn calculate_impact_score(&self, task_id: &str) -> TylResult<f64> {
         // Impact based on how many other tasks depend on this one
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             OPTIONAL MATCH (blocked:Task)-[:DEPENDS_ON]->(task)
             WITH task, count(blocked) as directly_blocked
             
@@ -867,20 +767,18 @@ impl ComputedPropertyService for GraphComputedPropertyService {
             
             // Weight direct impact more heavily than indirect
             RETURN (directly_blocked * 2.0 + indirectly_blocked * 1.0) as impact_score
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(0.0) // Default implementation
     }
     
     async fn calculate_urgency_score(&self, task_id: &str) -> TylResult<f64> {
         // Urgency based on due date proximity
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             WITH task,
               CASE 
                 WHEN task.due_date IS NULL THEN 0
@@ -894,11 +792,10 @@ impl ComputedPropertyService for GraphComputedPropertyService {
               END as urgency_score
             
             RETURN urgency_score
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(0.0) // Default implementation
     }
@@ -915,9 +812,8 @@ impl ComputedPropertyService for GraphComputedPropertyService {
     }
     
     async fn calculate_complexity_score(&self, task_id: &str) -> TylResult<f64> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             
             // Base complexity score
             WITH task,
@@ -927,6 +823,7 @@ impl ComputedPropertyService for GraphComputedPropertyService {
                 WHEN 'medium' THEN 5
                 WHEN 'complex' THEN 8
                 WHEN 'very_complex' THEN 13
+                WHEN 'epic' THEN 21
                 ELSE 5
               END as base_complexity
             
@@ -940,20 +837,18 @@ impl ComputedPropertyService for GraphComputedPropertyService {
             
             // Calculate final complexity score
             RETURN base_complexity + (subtask_count * 0.5) + (dependency_count * 0.3) as complexity_score
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(5.0) // Default: medium complexity
     }
     
     async fn calculate_risk_score(&self, task_id: &str) -> TylResult<f64> {
         // Risk score based on various factors
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             
             // Risk factors
             WITH task,
@@ -968,6 +863,7 @@ impl ComputedPropertyService for GraphComputedPropertyService {
               
               // Complexity risk
               CASE task.complexity
+                WHEN 'epic' THEN 10
                 WHEN 'very_complex' THEN 8
                 WHEN 'complex' THEN 5
                 WHEN 'medium' THEN 2
@@ -992,28 +888,25 @@ impl ComputedPropertyService for GraphComputedPropertyService {
               END as assignment_risk
             
             RETURN due_date_risk + complexity_risk + (risky_dependencies * 2) + assignment_risk as risk_score
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(0.0) // Default implementation
     }
     
     async fn is_task_stale(&self, task_id: &str) -> TylResult<bool> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             WITH task,
               task.status IN ['ready', 'backlog'] AND 
               task.updated_at < datetime() - duration('P30D') as is_stale
             RETURN is_stale
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(false) // Default implementation
     }
@@ -1034,11 +927,9 @@ impl TaskBusinessRulesEngine {
 impl BusinessRulesEngine for TaskBusinessRulesEngine {
     async fn validate_status_transition(&self, task_id: &str, new_status: TaskStatus) -> TylResult<ValidationResult> {
         // Get current task status
-        let query = format!(
-            "MATCH (task:Task {{id: '{}'}}) RETURN task.status as current_status",
-            task_id.replace('\'', "\\'")
-        );
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let query = "MATCH (task:Task {id: $task_id}) RETURN task.status as current_status";
+        let params = cypher_params([("task_id", json!(task_id))]);
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // In a real implementation, we would parse the current status and validate the transition
         // For now, return a valid result
@@ -1047,15 +938,14 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
     
     async fn can_start_task(&self, task_id: &str) -> TylResult<bool> {
         // Check if all dependencies are met and task is assigned
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             
             // Check if task is assigned
             OPTIONAL MATCH (task)-[:ASSIGNED_TO]->(user:User)
             
             // Check blocking dependencies
-            OPTIONAL MATCH (task)-[:DEPENDS_ON {{dependency_type: 'blocks'}}]->(blocking:Task)
+            OPTIONAL MATCH (task)-[:DEPENDS_ON {dependency_type: 'blocks'}]->(blocking:Task)
             WHERE blocking.status NOT IN ['done', 'cancelled']
             
             WITH task, user, count(blocking) as blocking_count
@@ -1064,20 +954,18 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
               user IS NOT NULL AND 
               blocking_count = 0 AND
               task.status IN ['ready', 'backlog'] as can_start
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(true) // Default implementation
     }
     
     async fn can_complete_task(&self, task_id: &str) -> TylResult<bool> {
         // Check if all subtasks are complete and success criteria are met
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             
             // Check subtasks
             OPTIONAL MATCH (task)<-[:SUBTASK_OF]-(subtask:Task)
@@ -1088,20 +976,18 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
             RETURN 
               task.status IN ['in_progress', 'review'] AND
               incomplete_subtasks = 0 as can_complete
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(true) // Default implementation
     }
     
     async fn validate_task_assignment(&self, task_id: &str, user_id: &str) -> TylResult<ValidationResult> {
         // Check user capacity and skills
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}}), (user:User {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id}), (user:User {id: $user_id})
             
             // Count user's current tasks
             OPTIONAL MATCH (user)<-[:ASSIGNED_TO]-(current_task:Task)
@@ -1112,32 +998,28 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
             RETURN 
               current_task_count < 5 as within_capacity,  // Assume max 5 concurrent tasks
               current_task_count
-            "#,
-            task_id.replace('\'', "\\'"),
-            user_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id)), ("user_id", json!(user_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // For now, return valid assignment
         Ok(ValidationResult::valid())
     }
     
     async fn check_user_capacity(&self, user_id: &str) -> TylResult<CapacityCheck> {
-        let query = format!(
-            r#"
-            MATCH (user:User {{id: '{}'}})
+        let query = r#"
+            MATCH (user:User {id: $user_id})
             OPTIONAL MATCH (user)<-[:ASSIGNED_TO]-(task:Task)
             WHERE task.status IN ['in_progress', 'ready', 'blocked']
             
             WITH user, count(task) as current_tasks
             
             RETURN current_tasks
-            "#,
-            user_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("user_id", json!(user_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Return default capacity check
         Ok(CapacityCheck {
@@ -1169,25 +1051,21 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
     
     async fn check_circular_dependencies(&self, from_task: &str, to_task: &str) -> TylResult<bool> {
         // Check if to_task already depends on from_task (directly or indirectly)
-        let query = format!(
-            r#"
-            MATCH path = (to:Task {{id: '{}'}})-[:DEPENDS_ON*1..10]->(from:Task {{id: '{}'}})
+        let query = r#"
+            MATCH path = (to:Task {id: $to_task})-[:DEPENDS_ON*1..10]->(from:Task {id: $from_task})
             RETURN count(path) > 0 as would_create_cycle
-            "#,
-            to_task.replace('\'', "\\'"),
-            from_task.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("to_task", json!(to_task)), ("from_task", json!(from_task))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(false) // Default: no circular dependency
     }
     
     async fn validate_project_assignment(&self, task_id: &str, project_id: &str) -> TylResult<ValidationResult> {
         // Check if project exists and has capacity
-        let query = format!(
-            r#"
-            MATCH (project:Project {{id: '{}'}})
+        let query = r#"
+            MATCH (project:Project {id: $project_id})
             OPTIONAL MATCH (task:Task)-[:BELONGS_TO_PROJECT]->(project)
             
             WITH project, count(task) as current_task_count
@@ -1195,11 +1073,10 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
             RETURN 
               project IS NOT NULL as project_exists,
               current_task_count < 100 as within_capacity  // Assume max 100 tasks per project
-            "#,
-            project_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("project_id", json!(project_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(ValidationResult::valid())
     }
@@ -1217,31 +1094,27 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
     
     async fn validate_due_date(&self, task_id: &str, due_date: DateTime<Utc>) -> TylResult<ValidationResult> {
         // Check if due date is realistic based on dependencies
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             OPTIONAL MATCH (task)-[:DEPENDS_ON]->(dep:Task)
             
             WITH task, max(dep.estimated_completion_date) as latest_dependency_completion
             
             RETURN 
               latest_dependency_completion IS NULL OR 
-              latest_dependency_completion <= datetime('{}') as is_realistic
-            "#,
-            task_id.replace('\'', "\\'"),
-            due_date.to_rfc3339()
-        );
+              latest_dependency_completion <= datetime($due_date) as is_realistic
+            "#;
+        let params = cypher_params([("task_id", json!(task_id)), ("due_date", json!(due_date.to_rfc3339()))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         Ok(ValidationResult::valid())
     }
     
     async fn suggest_realistic_due_date(&self, task_id: &str) -> TylResult<Option<DateTime<Utc>>> {
         // Calculate based on dependency completion dates and estimated effort
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             OPTIONAL MATCH (task)-[:DEPENDS_ON]->(dep:Task)
             
             WITH task, 
@@ -1251,11 +1124,10 @@ impl BusinessRulesEngine for TaskBusinessRulesEngine {
                  END as earliest_start_date
             
             RETURN earliest_start_date + duration('P7D') as suggested_due_date  // Default: 7 days after earliest start
-            "#,
-            task_id.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Return a reasonable default
         Ok(Some(Utc::now() + Duration::days(7)))
@@ -0,0 +1,136 @@
+//! In-memory copy of the task dependency graph
+//!
+//! Checking a new dependency for cycles walks the graph from the dependency's
+//! target, which costs one repository query per visited task. The
+//! [`DependencyGraph`] keeps the `from -> to` edges in memory instead, so the
+//! walk never leaves the process. It is loaded from the repository on first
+//! use and kept up to date by the domain service as dependencies are added
+//! and tasks deleted.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// Adjacency list of `from -> to` dependencies between task ids
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// `None` until loaded
+    edges: RwLock<Option<HashMap<String, HashSet<String>>>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.edges.read().unwrap().is_some()
+    }
+
+    /// Replace the whole graph, e.g. with every dependency read from storage
+    pub fn load(&self, edges: impl IntoIterator<Item = (String, String)>) {
+        let mut adjacency: HashMap<String, HashSet<String>> = HashMap::new();
+        for (from, to) in edges {
+            adjacency.entry(from).or_default().insert(to);
+        }
+        *self.edges.write().unwrap() = Some(adjacency);
+    }
+
+    /// Forget the whole graph so the next check loads it again
+    pub fn clear(&self) {
+        *self.edges.write().unwrap() = None;
+    }
+
+    /// Record a new dependency; ignored until the graph is loaded, which picks it up anyway
+    pub fn add_edge(&self, from: &str, to: &str) {
+        if let Some(edges) = self.edges.write().unwrap().as_mut() {
+            edges.entry(from.to_string()).or_default().insert(to.to_string());
+        }
+    }
+
+    /// Drop every dependency from or to `task_id`, e.g. once the task is deleted
+    pub fn invalidate(&self, task_id: &str) {
+        if let Some(edges) = self.edges.write().unwrap().as_mut() {
+            edges.remove(task_id);
+            for targets in edges.values_mut() {
+                targets.remove(task_id);
+            }
+        }
+    }
+
+    /// Number of dependencies held
+    pub fn edge_count(&self) -> usize {
+        self.edges.read().unwrap().as_ref().map_or(0, |edges| edges.values().map(HashSet::len).sum())
+    }
+
+    /// Whether adding `from -> to` would close a cycle, i.e. `from` is reachable from `to`
+    ///
+    /// An unloaded graph has no edges, so this is only meaningful once loaded.
+    pub fn would_create_cycle_fast(&self, from: &str, to: &str) -> bool {
+        if from == to {
+            return true;
+        }
+        let edges = self.edges.read().unwrap();
+        let Some(edges) = edges.as_ref() else {
+            return false;
+        };
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![to];
+        while let Some(current) = stack.pop() {
+            if current == from {
+                return true;
+            }
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(targets) = edges.get(current) {
+                stack.extend(targets.iter().map(String::as_str).filter(|target| !visited.contains(target)));
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> DependencyGraph {
+        let graph = DependencyGraph::new();
+        graph.load(edges.iter().map(|(from, to)| (from.to_string(), to.to_string())));
+        graph
+    }
+
+    #[test]
+    fn test_would_create_cycle_fast_follows_transitive_dependencies() {
+        let graph = graph(&[("A", "B"), ("B", "C"), ("X", "C")]);
+
+        assert!(graph.would_create_cycle_fast("C", "A"));
+        assert!(graph.would_create_cycle_fast("B", "A"));
+        assert!(!graph.would_create_cycle_fast("A", "C"));
+        assert!(!graph.would_create_cycle_fast("X", "A"));
+        assert!(graph.would_create_cycle_fast("A", "A"));
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let graph = graph(&[("A", "B")]);
+
+        graph.add_edge("B", "C");
+        assert!(graph.would_create_cycle_fast("C", "A"));
+        assert_eq!(graph.edge_count(), 2);
+
+        graph.invalidate("B");
+        assert!(!graph.would_create_cycle_fast("C", "A"));
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_edges_are_ignored_until_loaded() {
+        let graph = DependencyGraph::new();
+        graph.add_edge("A", "B");
+
+        assert!(!graph.is_loaded());
+        assert_eq!(graph.edge_count(), 0);
+    }
+}
@@ -4,13 +4,17 @@
 //! Following the comprehensive schema provided, these types map directly to graph nodes
 //! and relationships in FalkorDB through tyl-graph-port.
 
-use chrono::{DateTime, Utc};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::collections::HashMap;
 use tyl_errors::{TylError, TylResult};
 
+use super::queries::TimelineEvent;
+
 /// Task context categories
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskContext {
     Work,
@@ -20,8 +24,24 @@ pub enum TaskContext {
     Research,
 }
 
+impl TaskContext {
+    /// Outline offered for the description of a new task in this context
+    pub fn description_template(&self) -> &'static str {
+        match self {
+            TaskContext::Work => "Problem: \nSolution approach: \nDefinition of Done:",
+            TaskContext::Personal => "Goal: \nNext step: \nDone when:",
+            TaskContext::Learning => "Topic: \nResources: \nI can now:",
+            TaskContext::Maintenance => "Component: \nSymptom: \nVerification:",
+            TaskContext::Research => "Question: \nSources: \nExpected outcome:",
+        }
+    }
+}
+
+/// Changes to a task's custom properties, `None` deleting the key
+pub type CustomPropertiesPatch = HashMap<String, Option<serde_json::Value>>;
+
 /// Task status following state machine pattern
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskStatus {
     Backlog,
@@ -115,7 +135,7 @@ impl TaskStatus {
 }
 
 /// Task priority levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskPriority {
     Critical,
@@ -126,7 +146,7 @@ pub enum TaskPriority {
 }
 
 /// Task complexity levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskComplexity {
     Trivial,
@@ -134,10 +154,51 @@ pub enum TaskComplexity {
     Medium,
     Complex,
     VeryComplex,
+    /// Too large to work on directly, split into subtasks with `split_task`
+    Epic,
+}
+
+impl TaskPriority {
+    /// Relative weight used when scoring dependency chains
+    pub fn weight(&self) -> f64 {
+        match self {
+            TaskPriority::Critical => 5.0,
+            TaskPriority::High => 4.0,
+            TaskPriority::Medium => 3.0,
+            TaskPriority::Low => 2.0,
+            TaskPriority::Wish => 1.0,
+        }
+    }
+}
+
+impl TaskComplexity {
+    /// Effort assumed for a task without an explicit estimate
+    pub fn default_estimated_hours(&self) -> f64 {
+        match self {
+            TaskComplexity::Trivial => 1.0,
+            TaskComplexity::Simple => 2.0,
+            TaskComplexity::Medium => 4.0,
+            TaskComplexity::Complex => 8.0,
+            TaskComplexity::VeryComplex => 16.0,
+            TaskComplexity::Epic => 32.0,
+        }
+    }
+    
+    /// Story points assumed for a task without an explicit estimate
+    pub fn default_story_points(&self) -> f64 {
+        match self {
+            TaskComplexity::Trivial => 1.0,
+            TaskComplexity::Simple => 2.0,
+            TaskComplexity::Medium => 3.0,
+            TaskComplexity::Complex => 5.0,
+            TaskComplexity::VeryComplex => 8.0,
+            TaskComplexity::Epic => 13.0,
+        }
+    }
 }
 
 /// Task source origin
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskSource {
     Self_,
@@ -149,7 +210,7 @@ pub enum TaskSource {
 }
 
 /// Task visibility levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskVisibility {
     Private,
@@ -158,23 +219,53 @@ pub enum TaskVisibility {
 }
 
 /// Success criterion for a task
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SuccessCriterion {
     pub criterion: String,
     pub measurable: bool,
     pub verification_method: String,
+    #[serde(default)]
+    pub is_completed: bool,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
 }
 
 /// Recurrence pattern for tasks
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TaskRecurrence {
     pub pattern: String, // "daily", "weekly", "monthly", "custom"
     pub interval: u32,
     pub end_date: Option<DateTime<Utc>>,
+    /// Cron expression of a `custom` pattern, e.g. `0 9 * * Mon`
+    #[serde(default)]
+    pub cron: Option<String>,
+}
+
+impl TaskRecurrence {
+    /// Next occurrence after `from`, or `None` once the recurrence has ended
+    ///
+    /// `custom` patterns follow their cron expression and ignore `interval`.
+    /// Without a valid expression, and for unknown patterns, there is no
+    /// next occurrence.
+    pub fn next_occurrence_after(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let interval = self.interval.max(1);
+        let next = match self.pattern.as_str() {
+            "daily" => from.checked_add_signed(chrono::Duration::days(interval as i64))?,
+            "weekly" => from.checked_add_signed(chrono::Duration::weeks(interval as i64))?,
+            "monthly" => from.checked_add_months(chrono::Months::new(interval))?,
+            "custom" => super::recurrence::next_cron_occurrence(self.cron.as_deref()?, from).ok()?,
+            _ => return None,
+        };
+        
+        match self.end_date {
+            Some(end) if next > end => None,
+            _ => Some(next),
+        }
+    }
 }
 
 /// File attachment for tasks
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TaskAttachment {
     pub name: String,
     pub url: String,
@@ -184,7 +275,7 @@ pub struct TaskAttachment {
 }
 
 /// Core Task domain model
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Task {
     /// Human-readable ID based on project/context (e.g., "PROJ1-T042")
     pub id: String,
@@ -221,6 +312,28 @@ pub struct Task {
     
     /// Custom properties for extensibility
     pub custom_properties: HashMap<String, serde_json::Value>,
+    
+    /// Priority churn; each change is also recorded as a `priority_changed` audit entry
+    #[serde(default)]
+    pub priority_change_count: u32,
+    #[serde(default)]
+    pub last_priority_change_at: Option<DateTime<Utc>>,
+    
+    /// Tag names; each is also a `:Tag` node the task is `TAGGED_WITH` in the graph
+    #[serde(default)]
+    pub tags: Vec<String>,
+    
+    /// Incremented by the repository on every save, for optimistic locking
+    #[serde(default)]
+    pub version: u64,
+    
+    /// User who created the task, taken from the `X-User-Id` header of the create request
+    #[serde(default)]
+    pub created_by: Option<String>,
+    
+    /// Context of the parent task, only set on subtasks whose parent is in another context
+    #[serde(default)]
+    pub parent_context: Option<TaskContext>,
 }
 
 impl Task {
@@ -250,12 +363,18 @@ impl Task {
             visibility: TaskVisibility::Private,
             attachments: Vec::new(),
             custom_properties: HashMap::new(),
+            priority_change_count: 0,
+            last_priority_change_at: None,
+            tags: Vec::new(),
+            version: 0,
+            created_by: None,
+            parent_context: None,
         }
     }
     
     /// Builder pattern for complex task creation
     pub fn builder(id: String, name: String, context: TaskContext) -> TaskBuilder {
-        TaskBuilder::new(id, name, context)
+        TaskBuilder::new().id(id).name(name).context(context)
     }
     
     /// Update task status with validation
@@ -267,21 +386,104 @@ impl Task {
             ));
         }
         
+        self.set_status(new_status);
+        Ok(())
+    }
+    
+    /// Set the status without checking the transition, stamping the start
+    /// and completion times as [`Task::update_status`] does
+    pub fn set_status(&mut self, new_status: TaskStatus) {
+        self.set_status_at(new_status, Utc::now());
+    }
+    
+    /// Set the status as [`Task::set_status`] does, as of `at`
+    pub fn set_status_at(&mut self, new_status: TaskStatus, at: DateTime<Utc>) {
         self.status = new_status;
-        self.updated_at = Utc::now();
+        self.updated_at = at;
         
         // Set timestamps based on status
         match self.status {
             TaskStatus::InProgress if self.started_at.is_none() => {
-                self.started_at = Some(Utc::now());
+                self.started_at = Some(at);
             }
             TaskStatus::Done => {
-                self.completed_at = Some(Utc::now());
+                self.completed_at = Some(at);
             }
             _ => {}
         }
+    }
+    
+    /// Apply the fields set in `request`, returning the priority change if any
+    ///
+    /// `updated_at` is left to the caller.
+    pub fn apply_update(&mut self, request: UpdateTaskRequest) -> Option<PriorityChange> {
+        if let Some(name) = request.name {
+            self.name = name;
+        }
         
-        Ok(())
+        if let Some(description) = request.description {
+            self.description = Some(description);
+        }
+        
+        let priority_change = request.priority
+            .and_then(|priority| self.change_priority(priority, None));
+        
+        if let Some(complexity) = request.complexity {
+            self.complexity = complexity;
+        }
+        
+        if let Some(due_date) = request.due_date {
+            self.due_date = Some(due_date);
+        }
+        
+        if let Some(estimated_date) = request.estimated_date {
+            self.estimated_date = Some(estimated_date);
+        }
+        
+        if let Some(details) = request.implementation_details {
+            self.implementation_details = Some(details);
+        }
+        
+        if let Some(criteria) = request.success_criteria {
+            self.success_criteria = criteria;
+        }
+        
+        if let Some(test_strategy) = request.test_strategy {
+            self.test_strategy = Some(test_strategy);
+        }
+        
+        if let Some(visibility) = request.visibility {
+            self.visibility = visibility;
+        }
+        
+        if let Some(properties) = request.custom_properties {
+            self.custom_properties = properties;
+        }
+        
+        priority_change
+    }
+    
+    /// Set the priority, tracking the change if it differs from the current one
+    pub fn change_priority(&mut self, priority: TaskPriority, changed_by: Option<String>) -> Option<PriorityChange> {
+        if priority == self.priority {
+            return None;
+        }
+        
+        let change = PriorityChange {
+            from_priority: self.priority,
+            to_priority: priority,
+            changed_at: Utc::now(),
+            changed_by,
+        };
+        self.priority = priority;
+        self.priority_change_count += 1;
+        self.last_priority_change_at = Some(change.changed_at);
+        Some(change)
+    }
+    
+    /// Whether this is a subtask of a task in another context
+    pub fn is_cross_context(&self) -> bool {
+        self.parent_context.is_some_and(|parent_context| parent_context != self.context)
     }
     
     /// Check if task is overdue
@@ -292,24 +494,263 @@ impl Task {
         false
     }
     
+    /// Scheduling score combining priority, deadline pressure and how many
+    /// tasks are waiting on this one
+    ///
+    /// Overdue tasks get a boost of 25; open tasks due within a week get a
+    /// share of it that grows as the due date approaches. Each blocked task
+    /// adds 5.
+    pub fn effective_priority(&self, blocked_count: u32) -> f64 {
+        const OVERDUE_BOOST: f64 = 25.0;
+        const BOOST_WINDOW_HOURS: f64 = 7.0 * 24.0;
+        
+        let base_priority_score = self.priority.weight() * 20.0;
+        let overdue_boost = match self.due_date {
+            _ if self.is_overdue() => OVERDUE_BOOST,
+            Some(due_date) if !self.status.is_terminal() => {
+                let hours_left = (due_date - Utc::now()).num_minutes() as f64 / 60.0;
+                OVERDUE_BOOST * (1.0 - hours_left / BOOST_WINDOW_HOURS).max(0.0)
+            }
+            _ => 0.0,
+        };
+        
+        base_priority_score + overdue_boost + blocked_count as f64 * 5.0
+    }
+    
+    /// Story points from the `story_points` custom property, falling back to
+    /// the complexity default
+    pub fn story_points(&self) -> f64 {
+        self.custom_properties.get("story_points")
+            .and_then(|points| points.as_f64())
+            .unwrap_or_else(|| self.complexity.default_story_points())
+    }
+    
     /// Check if task is actionable (ready to work on)
     pub fn is_actionable(&self) -> bool {
         matches!(self.status, TaskStatus::Ready | TaskStatus::InProgress)
     }
+    
+    /// Due date of the next instance of a recurring task
+    ///
+    /// Counted from the due date when there is one, otherwise from completion.
+    pub fn next_recurrence_date(&self) -> Option<DateTime<Utc>> {
+        let recurrence = self.recurrence.as_ref()?;
+        let base = self.due_date.or(self.completed_at)?;
+        recurrence.next_occurrence_after(base)
+    }
+    
+    /// HTTP entity tag of this version of the task, e.g. `"v3"`
+    pub fn etag(&self) -> String {
+        format!("\"v{}\"", self.version)
+    }
+    
+    /// Apply a JSON Merge Patch to the custom properties: `None` removes the
+    /// key, any other value sets it
+    pub fn apply_custom_properties_patch(&mut self, patch: &CustomPropertiesPatch) {
+        for (key, value) in patch {
+            match value {
+                Some(value) => self.custom_properties.insert(key.clone(), value.clone()),
+                None => self.custom_properties.remove(key),
+            };
+        }
+    }
+    
+    /// Whether the task has any of `tags`, or all of them with `require_all`
+    ///
+    /// Tag names are compared case-insensitively.
+    pub fn has_tags(&self, tags: &[String], require_all: bool) -> bool {
+        let has_tag = |wanted: &String| self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted));
+        if require_all {
+            tags.iter().all(has_tag)
+        } else {
+            tags.iter().any(has_tag)
+        }
+    }
+    
+    /// Names of the tags the auto-labelling rules give the task, in rule order
+    ///
+    /// A rule applies when one of its keywords starts a word of the name or
+    /// description, so "fixes" counts for "fix" but "prefix" does not.
+    pub fn auto_label_names(&self) -> Vec<&'static str> {
+        let text = format!("{} {}", self.name, self.description.as_deref().unwrap_or_default()).to_lowercase();
+        let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric() && c != '-')
+            .filter(|word| !word.is_empty())
+            .collect();
+        
+        AUTO_LABEL_RULES.iter()
+            .filter(|(_, keywords)| words.iter().any(|word| keywords.iter().any(|keyword| word.starts_with(keyword))))
+            .map(|(tag, _)| *tag)
+            .collect()
+    }
+    
+    /// Time from when work first started to completion
+    ///
+    /// `None` until the task has both been started and completed.
+    pub fn calculate_cycle_time(&self) -> Option<Duration> {
+        Some(self.completed_at? - self.started_at?)
+    }
+    
+    /// Cycle time minus the periods the task spent blocked
+    ///
+    /// Blocked periods come from `status_change` timeline events whose
+    /// `new_status` metadata is `blocked`; each one lasts until the next status
+    /// change, or until completion for the last one.
+    pub fn calculate_active_time(&self, events: &[TimelineEvent]) -> Option<Duration> {
+        let cycle_time = self.calculate_cycle_time()?;
+        let (started_at, completed_at) = (self.started_at?, self.completed_at?);
+        
+        let mut status_changes: Vec<(DateTime<Utc>, &str)> = events.iter()
+            .filter(|event| event.event_type == "status_change")
+            .filter_map(|event| Some((event.timestamp, event.metadata.get("new_status")?.as_str()?)))
+            .collect();
+        status_changes.sort_by_key(|(timestamp, _)| *timestamp);
+        
+        let mut blocked = Duration::zero();
+        for (index, (timestamp, status)) in status_changes.iter().enumerate() {
+            if *status != "blocked" {
+                continue;
+            }
+            let until = status_changes.get(index + 1).map_or(completed_at, |(next, _)| *next);
+            let (from, until) = ((*timestamp).max(started_at), until.min(completed_at));
+            if until > from {
+                blocked = blocked + (until - from);
+            }
+        }
+        
+        Some(cycle_time - blocked)
+    }
+    
+    /// Mark the success criterion at `index` as completed
+    ///
+    /// Completing an already completed criterion keeps its original timestamp.
+    /// Returns `None` when there is no criterion at `index`.
+    pub fn complete_success_criterion(&mut self, index: usize) -> Option<&SuccessCriterion> {
+        self.set_success_criterion_completed(index, true)
+    }
+    
+    /// Mark the criterion at `index` as completed or open again, `None` when it does not exist
+    pub fn set_success_criterion_completed(&mut self, index: usize, completed: bool) -> Option<&SuccessCriterion> {
+        let now = Utc::now();
+        let criterion = self.success_criteria.get_mut(index)?;
+        if criterion.is_completed != completed {
+            criterion.is_completed = completed;
+            criterion.completed_at = completed.then_some(now);
+            self.updated_at = now;
+        }
+        self.success_criteria.get(index)
+    }
+    
+    /// Convert this task into a creation request for a new task with `new_id`
+    ///
+    /// Used by cloning, templates and recurrence. Lifecycle state (status,
+    /// `started_at`, `completed_at`, attachments) is not carried over, so the
+    /// new task starts fresh in the backlog. Assignment and project membership
+    /// live in the graph and are left for the caller to fill in.
+    pub fn to_creation_request(&self, new_id: String) -> CreateTaskRequest {
+        CreateTaskRequest {
+            id: new_id,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            context: self.context,
+            priority: self.priority,
+            complexity: self.complexity,
+            due_date: self.due_date,
+            estimated_date: self.estimated_date,
+            implementation_details: self.implementation_details.clone(),
+            success_criteria: self.success_criteria.clone(),
+            test_strategy: self.test_strategy.clone(),
+            source: self.source,
+            visibility: self.visibility,
+            recurrence: self.recurrence.clone(),
+            custom_properties: self.custom_properties.clone(),
+            assigned_user_id: None,
+            project_id: None,
+            created_by: None,
+        }
+    }
 }
 
+/// Typestate marker: a required builder field has not been provided yet
+pub struct Missing;
+
+/// Typestate marker: a required builder field has been provided
+pub struct Set;
+
 /// Builder for Task creation
-pub struct TaskBuilder {
+///
+/// The type parameters track whether the required id, name and context have
+/// been provided; `build()` only exists once all three are [`Set`].
+///
+/// ```
+/// use tyl_task_service::domain::{TaskBuilder, TaskContext, TaskPriority};
+///
+/// let task = TaskBuilder::new()
+///     .id("PROJ1-T001".to_string())
+///     .name("Write docs".to_string())
+///     .context(TaskContext::Work)
+///     .priority(TaskPriority::High)
+///     .build();
+/// assert_eq!(task.id, "PROJ1-T001");
+/// ```
+///
+/// ```compile_fail
+/// use tyl_task_service::domain::{TaskBuilder, TaskContext};
+///
+/// // No id: build() is not available
+/// let task = TaskBuilder::new()
+///     .name("Write docs".to_string())
+///     .context(TaskContext::Work)
+///     .build();
+/// ```
+pub struct TaskBuilder<IdState = Set, NameState = Set, ContextState = Set> {
+    // Required fields hold placeholders until their state is `Set`
     task: Task,
+    _state: std::marker::PhantomData<(IdState, NameState, ContextState)>,
 }
 
-impl TaskBuilder {
-    fn new(id: String, name: String, context: TaskContext) -> Self {
+impl TaskBuilder<Missing, Missing, Missing> {
+    pub fn new() -> Self {
         Self {
-            task: Task::new(id, name, context),
+            task: Task::new(String::new(), String::new(), TaskContext::Work),
+            _state: std::marker::PhantomData,
         }
     }
-    
+}
+
+impl Default for TaskBuilder<Missing, Missing, Missing> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, C> TaskBuilder<Missing, N, C> {
+    pub fn id(mut self, id: String) -> TaskBuilder<Set, N, C> {
+        self.task.id = id;
+        TaskBuilder { task: self.task, _state: std::marker::PhantomData }
+    }
+}
+
+impl<I, C> TaskBuilder<I, Missing, C> {
+    pub fn name(mut self, name: String) -> TaskBuilder<I, Set, C> {
+        self.task.name = name;
+        TaskBuilder { task: self.task, _state: std::marker::PhantomData }
+    }
+}
+
+impl<I, N> TaskBuilder<I, N, Missing> {
+    pub fn context(mut self, context: TaskContext) -> TaskBuilder<I, N, Set> {
+        self.task.context = context;
+        TaskBuilder { task: self.task, _state: std::marker::PhantomData }
+    }
+}
+
+impl TaskBuilder<Set, Set, Set> {
+    pub fn build(self) -> Task {
+        self.task
+    }
+}
+
+impl<I, N, C> TaskBuilder<I, N, C> {
     pub fn description(mut self, description: String) -> Self {
         self.task.description = Some(description);
         self
@@ -320,6 +761,11 @@ impl TaskBuilder {
         self
     }
     
+    pub fn created_by(mut self, user_id: String) -> Self {
+        self.task.created_by = Some(user_id);
+        self
+    }
+    
     pub fn complexity(mut self, complexity: TaskComplexity) -> Self {
         self.task.complexity = complexity;
         self
@@ -359,14 +805,10 @@ impl TaskBuilder {
         self.task.custom_properties.insert(key, value);
         self
     }
-    
-    pub fn build(self) -> Task {
-        self.task
-    }
 }
 
 /// Dependency relationship types
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DependencyType {
     Blocks,
@@ -385,6 +827,9 @@ pub struct TaskDependency {
     pub is_hard_dependency: bool,
     pub delay_days: u32,
     pub created_at: DateTime<Utc>,
+    /// When the dependency was removed; only set on the tombstone kept after removal
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
     pub properties: HashMap<String, serde_json::Value>,
 }
 
@@ -402,13 +847,98 @@ impl TaskDependency {
             is_hard_dependency: true,
             delay_days: 0,
             created_at: Utc::now(),
+            deleted_at: None,
             properties: HashMap::new(),
         }
     }
 }
 
+/// Issue tracker a task is mirrored in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalSystem {
+    Jira,
+    #[serde(rename = "github")]
+    GitHub,
+    Linear,
+    Custom(String),
+}
+
+impl ExternalSystem {
+    /// Name stored in the graph; custom systems use their own name
+    pub fn name(&self) -> &str {
+        match self {
+            ExternalSystem::Jira => "jira",
+            ExternalSystem::GitHub => "github",
+            ExternalSystem::Linear => "linear",
+            ExternalSystem::Custom(name) => name,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "jira" => ExternalSystem::Jira,
+            "github" => ExternalSystem::GitHub,
+            "linear" => ExternalSystem::Linear,
+            other => ExternalSystem::Custom(other.to_string()),
+        }
+    }
+}
+
+/// State of the sync between a task and its external ticket
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    #[default]
+    Linked,
+    SyncPending,
+    SyncFailed,
+}
+
+/// Ticket in an external system that tracks the same work as a task
+///
+/// A task has at most one link per system and external id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExternalTicketLink {
+    pub task_id: String,
+    pub system: ExternalSystem,
+    pub external_id: String,
+    pub external_url: String,
+    pub sync_status: SyncStatus,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+impl ExternalTicketLink {
+    pub fn new(task_id: String, system: ExternalSystem, external_id: String, external_url: String) -> Self {
+        Self {
+            task_id,
+            system,
+            external_id,
+            external_url,
+            sync_status: SyncStatus::Linked,
+            last_synced_at: None,
+        }
+    }
+
+    /// Whether both links point at the same external ticket
+    pub fn same_ticket(&self, other: &ExternalTicketLink) -> bool {
+        self.system == other.system && self.external_id == other.external_id
+    }
+
+    /// Whether the link points at a pull or merge request rather than a ticket
+    pub fn is_pull_request(&self) -> bool {
+        self.external_url.contains("/pull/") || self.external_url.contains("/merge_requests/")
+    }
+}
+
+/// `Project::status` of projects in progress
+pub const PROJECT_STATUS_ACTIVE: &str = "active";
+
+/// `Project::status` of projects kept for reference only, see `ProjectService::archive_project`
+pub const PROJECT_STATUS_ARCHIVED: &str = "archived";
+
 /// Project entity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Project {
     pub id: String,
     pub code: String, // Used for task ID generation
@@ -417,6 +947,9 @@ pub struct Project {
     pub status: String,
     pub start_date: Option<DateTime<Utc>>,
     pub end_date: Option<DateTime<Utc>>,
+    /// User leading the project, see `ProjectService::transfer_project_ownership`
+    #[serde(default)]
+    pub project_lead_user_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -429,13 +962,137 @@ impl Project {
             code,
             name,
             description: None,
-            status: "active".to_string(),
+            status: PROJECT_STATUS_ACTIVE.to_string(),
             start_date: None,
             end_date: None,
+            project_lead_user_id: None,
             created_at: now,
             updated_at: now,
         }
     }
+
+    pub fn is_archived(&self) -> bool {
+        self.status == PROJECT_STATUS_ARCHIVED
+    }
+}
+
+/// Record of a change made to an entity, with the reason given for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: String,
+    /// Label of the audited node, e.g. `Project`
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: String,
+    pub previous_value: Option<String>,
+    pub new_value: Option<String>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(entity_type: &str, entity_id: &str, action: &str, reason: String) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            action: action.to_string(),
+            previous_value: None,
+            new_value: None,
+            reason,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_change(mut self, previous_value: Option<String>, new_value: Option<String>) -> Self {
+        self.previous_value = previous_value;
+        self.new_value = new_value;
+        self
+    }
+}
+
+/// Audit action recorded for a [`PriorityChange`]
+pub const PRIORITY_CHANGED_ACTION: &str = "priority_changed";
+
+/// Tasks whose priority changed more often than this are listed as churning
+pub const PRIORITY_CHURN_THRESHOLD: u32 = 2;
+
+/// One change of a task's priority
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorityChange {
+    pub from_priority: TaskPriority,
+    pub to_priority: TaskPriority,
+    pub changed_at: DateTime<Utc>,
+    pub changed_by: Option<String>,
+}
+
+impl PriorityChange {
+    /// Audit entry recording this change of `task_id`'s priority
+    pub fn to_audit_entry(&self, task_id: &str) -> AuditEntry {
+        let reason = match self.changed_by {
+            Some(ref user_id) => format!("Priority changed by {}", user_id),
+            None => "Priority changed".to_string(),
+        };
+        let priority_value = |priority: TaskPriority| serde_json::to_value(priority).ok()
+            .and_then(|v| v.as_str().map(str::to_string));
+        
+        AuditEntry {
+            created_at: self.changed_at,
+            ..AuditEntry::new("Task", task_id, PRIORITY_CHANGED_ACTION, reason)
+                .with_change(priority_value(self.from_priority), priority_value(self.to_priority))
+        }
+    }
+}
+
+/// Similar tasks whose implementation details are suggested for a new task
+pub const DESCRIPTION_SUGGESTION_LIMIT: usize = 3;
+
+/// Description for a new task taken from a similar completed task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DescriptionSuggestion {
+    pub source_task_id: String,
+    pub similarity_score: f64,
+    pub description: String,
+}
+
+impl DescriptionSuggestion {
+    /// Suggest `task`'s implementation details, if it is done and has any
+    pub fn from_similar_task(task: &Task, similarity_score: f64) -> Option<Self> {
+        if task.status != TaskStatus::Done {
+            return None;
+        }
+        let details = task.implementation_details.as_deref()?.trim();
+        if details.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            source_task_id: task.id.clone(),
+            similarity_score,
+            description: details.to_string(),
+        })
+    }
+}
+
+/// Per-project workflow settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ProjectWorkflowConfig {
+    pub project_id: String,
+    /// Maximum number of tasks allowed in each status; statuses without an
+    /// entry are unlimited
+    #[serde(default)]
+    pub wip_limits: HashMap<TaskStatus, u32>,
+}
+
+impl ProjectWorkflowConfig {
+    pub fn new(project_id: String) -> Self {
+        Self { project_id, wip_limits: HashMap::new() }
+    }
+
+    pub fn with_wip_limit(mut self, status: TaskStatus, limit: u32) -> Self {
+        self.wip_limits.insert(status, limit);
+        self
+    }
 }
 
 /// User entity
@@ -467,8 +1124,21 @@ impl User {
     }
 }
 
+/// Tag names given by [`Task::auto_label_names`], with the keywords that earn them
+const AUTO_LABEL_RULES: &[(&str, &[&str])] = &[
+    ("bug", &["bug", "fix", "error"]),
+    ("testing", &["test", "spec", "coverage"]),
+    ("release", &["deploy", "release", "ship"]),
+    ("tech-debt", &["refactor", "cleanup", "tech-debt"]),
+    ("documentation", &["document", "readme", "wiki"]),
+];
+/// Category of the tags added by auto-labelling
+pub const AUTO_LABEL_CATEGORY: &str = "auto";
+/// Category of the tags added by users
+pub const USER_TAG_CATEGORY: &str = "user";
+
 /// Tag entity for categorization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Tag {
     pub id: String,
     pub name: String,
@@ -491,27 +1161,45 @@ impl Tag {
     }
 }
 
-/// Comment on tasks
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Comment {
+/// Number of tasks carrying a tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TagUsage {
+    pub tag: String,
+    pub task_count: usize,
+}
+
+/// Comment on a task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskComment {
     pub id: String,
-    pub content: String,
+    pub task_id: String,
+    pub author_id: String,
+    pub body: String,
+    /// Whether the body changed since the comment was posted
+    pub edited: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
-    pub author_id: String,
 }
 
-impl Comment {
-    pub fn new(id: String, content: String, author_id: String) -> Self {
+impl TaskComment {
+    pub fn new(id: String, task_id: String, author_id: String, body: String) -> Self {
         let now = Utc::now();
         Self {
             id,
-            content,
+            task_id,
+            author_id,
+            body,
+            edited: false,
             created_at: now,
             updated_at: now,
-            author_id,
         }
     }
+
+    pub fn edit(&mut self, body: String) {
+        self.body = body;
+        self.edited = true;
+        self.updated_at = Utc::now();
+    }
 }
 
 /// Milestone entity
@@ -572,8 +1260,121 @@ impl Context {
     }
 }
 
-/// Request DTO for creating a new task
+/// Type of a custom property value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    String,
+    Number,
+    Boolean,
+    /// RFC 3339 timestamp string
+    Date,
+    /// String restricted to the listed values
+    Enum(Vec<String>),
+}
+
+impl FieldType {
+    /// Check whether a JSON value is acceptable for this type
+    pub fn accepts(&self, value: &serde_json::Value) -> bool {
+        match self {
+            FieldType::String => value.is_string(),
+            FieldType::Number => value.is_number(),
+            FieldType::Boolean => value.is_boolean(),
+            FieldType::Date => value.as_str()
+                .is_some_and(|s| DateTime::parse_from_rfc3339(s).is_ok()),
+            FieldType::Enum(allowed) => value.as_str()
+                .is_some_and(|s| allowed.iter().any(|a| a == s)),
+        }
+    }
+}
+
+/// Custom property definition within a context schema
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldSchema {
+    pub name: String,
+    pub field_type: FieldType,
+    pub description: Option<String>,
+}
+
+impl FieldSchema {
+    pub fn new(name: &str, field_type: FieldType, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            field_type,
+            description: Some(description.to_string()),
+        }
+    }
+}
+
+/// Custom properties expected for tasks of a given context
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContextSchema {
+    pub context: TaskContext,
+    #[serde(default)]
+    pub required_custom_fields: Vec<FieldSchema>,
+    #[serde(default)]
+    pub optional_custom_fields: Vec<FieldSchema>,
+}
+
+impl ContextSchema {
+    /// Built-in schemas; they only declare optional fields so that existing
+    /// clients keep working until a deployment configures stricter ones
+    pub fn defaults() -> Vec<ContextSchema> {
+        let optional = |context, fields| ContextSchema {
+            context,
+            required_custom_fields: Vec::new(),
+            optional_custom_fields: fields,
+        };
+        
+        vec![
+            optional(TaskContext::Work, vec![
+                FieldSchema::new("ticket_number", FieldType::String, "Reference in the issue tracker"),
+            ]),
+            optional(TaskContext::Personal, vec![]),
+            optional(TaskContext::Learning, vec![
+                FieldSchema::new("resource_url", FieldType::String, "Course, book or article being studied"),
+            ]),
+            optional(TaskContext::Maintenance, vec![
+                FieldSchema::new("asset", FieldType::String, "System or item being maintained"),
+            ]),
+            optional(TaskContext::Research, vec![
+                FieldSchema::new("hypothesis", FieldType::String, "Question the research should answer"),
+            ]),
+        ]
+    }
+    
+    /// Built-in schema for a single context
+    pub fn default_for(context: TaskContext) -> Option<ContextSchema> {
+        Self::defaults().into_iter().find(|schema| schema.context == context)
+    }
+    
+    /// Look up a field declared by this schema, required or optional
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.required_custom_fields.iter()
+            .chain(self.optional_custom_fields.iter())
+            .find(|field| field.name == name)
+    }
+}
+
+/// JSON Schema that the custom properties of a task context must satisfy
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPropertySchema {
+    pub context: TaskContext,
+    pub schema: serde_json::Value,
+}
+
+impl CustomPropertySchema {
+    /// Property names listed under the schema's `properties` keyword
+    pub fn declared_properties(&self) -> Vec<&str> {
+        self.schema.get("properties")
+            .and_then(|properties| properties.as_object())
+            .map(|properties| properties.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Request DTO for creating a new task
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateTaskRequest {
     pub id: String,
     pub name: String,
@@ -592,6 +1393,9 @@ pub struct CreateTaskRequest {
     pub custom_properties: HashMap<String, serde_json::Value>,
     pub assigned_user_id: Option<String>,
     pub project_id: Option<String>,
+    /// User creating the task, recorded as [`Task::created_by`]
+    #[serde(default)]
+    pub created_by: Option<String>,
 }
 
 /// Request DTO for updating an existing task
@@ -668,15 +1472,66 @@ pub struct TaskFilter {
     pub created_after: Option<DateTime<Utc>>,
     pub created_before: Option<DateTime<Utc>>,
     pub search_text: Option<String>,
+    /// Tasks with any of these tags, or all of them with `require_all_tags`
     pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub require_all_tags: bool,
     pub has_dependencies: Option<bool>,
     pub is_overdue: Option<bool>,
+    /// Tasks whose priority changed more than this many times
+    pub priority_changed_more_than: Option<u32>,
+    /// Parent tasks with (true) or without (false) an open subtask past its due date
+    pub has_overdue_subtasks: Option<bool>,
+    /// Subtasks in another context than their parent (true) or not (false)
+    pub cross_context: Option<bool>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// Opaque [`TaskCursor`]; only tasks after it are listed and `offset` is ignored
+    pub cursor: Option<String>,
+}
+
+/// Keyset pagination position over tasks ordered by `created_at` then `id`, newest first
+///
+/// Encoded as URL-safe base64 JSON so clients pass it back untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskCursor(String);
+
+#[derive(Serialize, Deserialize)]
+struct TaskCursorPosition {
+    created_at: DateTime<Utc>,
+    id: String,
+}
+
+impl TaskCursor {
+    /// Cursor pointing just past `task`
+    pub fn encode(task: &Task) -> TaskCursor {
+        let position = TaskCursorPosition {
+            created_at: task.created_at,
+            id: task.id.clone(),
+        };
+        let json = serde_json::to_vec(&position).expect("cursor position always serializes");
+        TaskCursor(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// The `created_at` and `id` of the last task on the previous page
+    pub fn decode(s: &str) -> TylResult<(DateTime<Utc>, String)> {
+        let invalid = || TylError::validation("cursor", "Invalid pagination cursor");
+        let json = URL_SAFE_NO_PAD.decode(s).map_err(|_| invalid())?;
+        let position: TaskCursorPosition = serde_json::from_slice(&json).map_err(|_| invalid())?;
+        Ok((position.created_at, position.id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
 }
 
 /// Request DTO for creating a new project
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateProjectRequest {
     pub id: String,
     pub code: String,
@@ -686,6 +1541,26 @@ pub struct CreateProjectRequest {
     pub end_date: Option<DateTime<Utc>>,
 }
 
+impl From<CreateProjectRequest> for Project {
+    fn from(request: CreateProjectRequest) -> Self {
+        Self {
+            description: request.description,
+            start_date: request.start_date,
+            end_date: request.end_date,
+            ..Project::new(request.id, request.code, request.name)
+        }
+    }
+}
+
+/// Request DTO for updating an existing project, `None` keeping the current value
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateProjectRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -732,12 +1607,62 @@ mod tests {
         assert!(task.completed_at.is_some());
     }
     
+    #[test]
+    fn test_complete_success_criterion() {
+        let mut task = Task::new("PROJ1-T005".to_string(), "Ship".to_string(), TaskContext::Work);
+        task.success_criteria.push(SuccessCriterion {
+            criterion: "Verify that the release is tagged".to_string(),
+            measurable: true,
+            verification_method: "git tag".to_string(),
+            is_completed: false,
+            completed_at: None,
+        });
+        
+        let completed_at = task.complete_success_criterion(0).unwrap().completed_at;
+        assert!(completed_at.is_some());
+        assert!(task.success_criteria[0].is_completed);
+        assert_eq!(task.complete_success_criterion(0).unwrap().completed_at, completed_at);
+        assert!(task.complete_success_criterion(1).is_none());
+    }
+    
+    #[test]
+    fn test_cycle_time_excludes_blocked_periods() {
+        let start = Utc::now() - Duration::days(10);
+        let mut task = Task::new("PROJ1-T006".to_string(), "Migrate".to_string(), TaskContext::Work);
+        assert!(task.calculate_cycle_time().is_none());
+        
+        task.started_at = Some(start);
+        task.completed_at = Some(start + Duration::days(6));
+        assert_eq!(task.calculate_cycle_time(), Some(Duration::days(6)));
+        
+        let status_change = |days: i64, status: &str| TimelineEvent {
+            event_id: format!("E{}", days),
+            event_type: "status_change".to_string(),
+            timestamp: start + Duration::days(days),
+            actor: None,
+            description: String::new(),
+            metadata: HashMap::from([("new_status".to_string(), serde_json::json!(status))]),
+        };
+        let events = vec![
+            status_change(4, "review"),
+            status_change(1, "blocked"),
+            status_change(3, "in_progress"),
+            status_change(5, "blocked"),
+        ];
+        
+        // Blocked from day 1 to 3 and from day 5 until completion
+        assert_eq!(task.calculate_active_time(&events), Some(Duration::days(3)));
+        assert_eq!(task.calculate_active_time(&[]), Some(Duration::days(6)));
+    }
+    
     #[test]
     fn test_task_builder() {
         let criterion = SuccessCriterion {
             criterion: "All tests pass".to_string(),
             measurable: true,
             verification_method: "Automated test suite".to_string(),
+            is_completed: false,
+            completed_at: None,
         };
         
         let task = Task::builder(
@@ -762,6 +1687,75 @@ mod tests {
         assert!(task.custom_properties.contains_key("epic"));
     }
     
+    #[test]
+    fn test_task_builder_typestate() {
+        // Required fields can be provided in any order
+        let task = TaskBuilder::new()
+            .context(TaskContext::Learning)
+            .priority(TaskPriority::Low)
+            .name("Read the book".to_string())
+            .id("PROJ1-T010".to_string())
+            .build();
+        
+        assert_eq!(task.id, "PROJ1-T010");
+        assert_eq!(task.name, "Read the book");
+        assert_eq!(task.context, TaskContext::Learning);
+        assert_eq!(task.priority, TaskPriority::Low);
+        assert_eq!(task.status, TaskStatus::Backlog);
+    }
+    
+    #[test]
+    fn test_change_priority_tracks_changes() {
+        let mut task = Task::new("PROJ1-T011".to_string(), "Replan".to_string(), TaskContext::Work);
+        
+        assert!(task.change_priority(TaskPriority::Medium, None).is_none());
+        assert_eq!(task.priority_change_count, 0);
+        
+        let change = task.change_priority(TaskPriority::Critical, Some("user-1".to_string())).unwrap();
+        assert_eq!(change.from_priority, TaskPriority::Medium);
+        assert_eq!(change.to_priority, TaskPriority::Critical);
+        assert_eq!(task.priority, TaskPriority::Critical);
+        assert_eq!(task.priority_change_count, 1);
+        assert_eq!(task.last_priority_change_at, Some(change.changed_at));
+        
+        let entry = change.to_audit_entry(&task.id);
+        assert_eq!(entry.action, PRIORITY_CHANGED_ACTION);
+        assert_eq!(entry.previous_value.as_deref(), Some("medium"));
+        assert_eq!(entry.new_value.as_deref(), Some("critical"));
+        assert_eq!(entry.created_at, change.changed_at);
+    }
+    
+    #[test]
+    fn test_external_system_names_round_trip() {
+        for system in [
+            ExternalSystem::Jira,
+            ExternalSystem::GitHub,
+            ExternalSystem::Linear,
+            ExternalSystem::Custom("youtrack".to_string()),
+        ] {
+            assert_eq!(ExternalSystem::from_name(system.name()), system);
+        }
+        assert_eq!(serde_json::to_value(ExternalSystem::GitHub).unwrap(), "github");
+    }
+    
+    #[test]
+    fn test_description_suggestion_from_similar_task() {
+        let mut task = Task::new("PROJ1-T012".to_string(), "Fix login".to_string(), TaskContext::Work);
+        task.implementation_details = Some("Reset the session cookie after the redirect".to_string());
+        
+        // Details of unfinished work are not proven yet
+        assert!(DescriptionSuggestion::from_similar_task(&task, 0.8).is_none());
+        
+        task.status = TaskStatus::Done;
+        let suggestion = DescriptionSuggestion::from_similar_task(&task, 0.8).unwrap();
+        assert_eq!(suggestion.source_task_id, "PROJ1-T012");
+        assert_eq!(suggestion.description, "Reset the session cookie after the redirect");
+        
+        task.implementation_details = Some("  ".to_string());
+        assert!(DescriptionSuggestion::from_similar_task(&task, 0.8).is_none());
+        assert!(TaskContext::Work.description_template().starts_with("Problem:"));
+    }
+    
     #[test]
     fn test_task_overdue() {
         let mut task = Task::new(
@@ -780,6 +1774,119 @@ mod tests {
         assert!(!task.is_overdue());
     }
     
+    #[test]
+    fn test_effective_priority() {
+        let mut task = Task::new("PROJ1-T005".to_string(), "Ship it".to_string(), TaskContext::Work);
+        task.priority = TaskPriority::High;
+        assert_eq!(task.effective_priority(0), 80.0);
+        assert_eq!(task.effective_priority(2), 90.0);
+        
+        task.due_date = Some(Utc::now() - chrono::Duration::hours(1));
+        assert_eq!(task.effective_priority(0), 105.0);
+        
+        // Due in half the boost window: roughly half the boost
+        task.due_date = Some(Utc::now() + chrono::Duration::hours(84));
+        let score = task.effective_priority(0);
+        assert!(score > 92.0 && score < 93.0, "unexpected score {}", score);
+        
+        task.due_date = Some(Utc::now() + chrono::Duration::days(30));
+        assert_eq!(task.effective_priority(0), 80.0);
+    }
+    
+    #[test]
+    fn test_task_to_creation_request() {
+        let mut task = Task::builder(
+            "PROJ1-T004".to_string(),
+            "Weekly report".to_string(),
+            TaskContext::Work,
+        )
+        .description("Summarize the week".to_string())
+        .priority(TaskPriority::High)
+        .complexity(TaskComplexity::Simple)
+        .add_success_criterion(SuccessCriterion {
+            criterion: "Report sent".to_string(),
+            measurable: true,
+            verification_method: "Email".to_string(),
+            is_completed: false,
+            completed_at: None,
+        })
+        .recurrence(TaskRecurrence {
+            pattern: "weekly".to_string(),
+            interval: 1,
+            end_date: None,
+            cron: None,
+        })
+        .add_custom_property("team".to_string(), serde_json::json!("platform"))
+        .build();
+        task.update_status(TaskStatus::Ready).unwrap();
+        task.update_status(TaskStatus::InProgress).unwrap();
+        task.update_status(TaskStatus::Done).unwrap();
+        
+        let request = task.to_creation_request("PROJ1-T005".to_string());
+        
+        assert_eq!(request.id, "PROJ1-T005");
+        assert_eq!(request.name, task.name);
+        assert_eq!(request.description, task.description);
+        assert_eq!(request.context, TaskContext::Work);
+        assert_eq!(request.priority, TaskPriority::High);
+        assert_eq!(request.complexity, TaskComplexity::Simple);
+        assert_eq!(request.success_criteria.len(), 1);
+        assert_eq!(request.recurrence.as_ref().unwrap().pattern, "weekly");
+        assert_eq!(request.custom_properties.get("team"), Some(&serde_json::json!("platform")));
+        assert!(request.assigned_user_id.is_none());
+        assert!(request.project_id.is_none());
+        
+        // A task created from the request starts over
+        let clone = Task::new(request.id, request.name, request.context);
+        assert_eq!(clone.status, TaskStatus::Backlog);
+        assert!(clone.started_at.is_none());
+        assert!(clone.completed_at.is_none());
+    }
+    
+    #[test]
+    fn test_auto_label_names() {
+        let mut task = Task::new("T1".to_string(), "Fix crash on release build".to_string(), TaskContext::Work);
+        assert_eq!(task.auto_label_names(), vec!["bug", "release"]);
+
+        task.name = "Add prefix to latest shipments".to_string();
+        task.description = Some("Update the README and tech-debt notes".to_string());
+        assert_eq!(task.auto_label_names(), vec!["release", "tech-debt", "documentation"]);
+
+        task.name = "Plan the offsite".to_string();
+        task.description = None;
+        assert!(task.auto_label_names().is_empty());
+    }
+
+    #[test]
+    fn test_next_recurrence_date() {
+        let due = DateTime::parse_from_rfc3339("2024-01-31T09:00:00Z").unwrap().with_timezone(&Utc);
+        let mut task = Task::new("PROJ1-T006".to_string(), "Backup".to_string(), TaskContext::Maintenance);
+        task.due_date = Some(due);
+        assert!(task.next_recurrence_date().is_none());
+        
+        task.recurrence = Some(TaskRecurrence { pattern: "weekly".to_string(), interval: 2, end_date: None, cron: None });
+        assert_eq!(task.next_recurrence_date(), Some(due + chrono::Duration::weeks(2)));
+        
+        // Month arithmetic clamps to the end of shorter months
+        task.recurrence = Some(TaskRecurrence { pattern: "monthly".to_string(), interval: 1, end_date: None, cron: None });
+        assert_eq!(task.next_recurrence_date().unwrap().to_rfc3339(), "2024-02-29T09:00:00+00:00");
+        
+        task.recurrence = Some(TaskRecurrence { pattern: "daily".to_string(), interval: 1, end_date: Some(due), cron: None });
+        assert!(task.next_recurrence_date().is_none());
+        
+        task.recurrence = Some(TaskRecurrence { pattern: "custom".to_string(), interval: 3, end_date: None, cron: None });
+        assert!(task.next_recurrence_date().is_none());
+        
+        // 2024-01-31 is a Wednesday
+        task.recurrence = Some(TaskRecurrence {
+            pattern: "custom".to_string(),
+            interval: 1,
+            end_date: None,
+            cron: Some("30 8 * * Fri".to_string()),
+        });
+        assert_eq!(task.next_recurrence_date().unwrap().to_rfc3339(), "2024-02-02T08:30:00+00:00");
+    }
+    
     #[test]
     fn test_task_dependency() {
         let dep = TaskDependency::new(
@@ -793,4 +1900,29 @@ mod tests {
         assert_eq!(dep.dependency_type, DependencyType::Blocks);
         assert!(dep.is_hard_dependency);
     }
+    
+    #[test]
+    fn test_task_cursor_round_trip() {
+        let task = Task::new("PROJ1-T001".to_string(), "Cursor".to_string(), TaskContext::Work);
+        let cursor = TaskCursor::encode(&task);
+        
+        // URL-safe, so it can go into a query string as is
+        assert!(cursor.as_str().chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(TaskCursor::decode(cursor.as_str()).unwrap(), (task.created_at, "PROJ1-T001".to_string()));
+        
+        assert!(TaskCursor::decode("not a cursor").is_err());
+        assert!(TaskCursor::decode("bm90IGpzb24").is_err());
+    }
+    
+    #[test]
+    fn test_has_tags() {
+        let mut task = Task::new("PROJ1-T001".to_string(), "Tagged".to_string(), TaskContext::Work);
+        task.tags = vec!["backend".to_string(), "Urgent".to_string()];
+        let tags = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        
+        assert!(task.has_tags(&tags(&["urgent", "frontend"]), false));
+        assert!(!task.has_tags(&tags(&["urgent", "frontend"]), true));
+        assert!(task.has_tags(&tags(&["backend", "urgent"]), true));
+        assert!(!task.has_tags(&tags(&["frontend"]), false));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,248 @@
+//! Rule-based parsing of free-text task descriptions
+//!
+//! [`parse_natural_language_task`] turns input such as "Fix login bug by
+//! Friday high priority" into a pre-filled [`CreateTaskRequest`]. Date
+//! expressions and priority keywords are cut out of the text, context keywords
+//! are detected, and whatever remains becomes the task name. Nothing is
+//! created; callers review the request and submit it themselves.
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc, Weekday};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tyl_errors::{TylError, TylResult};
+
+use super::models::{CreateTaskRequest, TaskComplexity, TaskContext, TaskPriority, TaskSource, TaskVisibility};
+
+/// Confidence of a parse that only found a task name
+const BASE_CONFIDENCE: f64 = 0.4;
+/// Confidence added for each of due date, priority and context that was inferred
+const INFERRED_FIELD_CONFIDENCE: f64 = 0.2;
+
+/// Task request inferred from free text, with an explanation of the inference
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NaturalLanguageParseResult {
+    /// Request with an empty `id`; ids are assigned when the task is created
+    pub parsed_request: CreateTaskRequest,
+    /// Between 0 and 1, higher when more fields were inferred from the input
+    pub confidence: f64,
+    pub parse_notes: Vec<String>,
+}
+
+fn date_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:(?:by|on|due|before)\s+)?(?:(?P<relative>today|tonight|tomorrow|next week|next month)|in\s+(?P<amount>\d+)\s+(?P<unit>days?|weeks?)|(?:next\s+)?(?P<weekday>monday|tuesday|wednesday|thursday|friday|saturday|sunday)|(?P<date>\d{4}-\d{2}-\d{2}))\b",
+        )
+        .expect("valid date pattern")
+    })
+}
+
+/// Bare level words such as "low" are common in task names, so they only
+/// count when followed by "priority"
+fn priority_patterns() -> &'static [(Regex, TaskPriority)] {
+    static PATTERNS: OnceLock<Vec<(Regex, TaskPriority)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            (r"(?i)\b(?:urgent|critical|asap)(?:\s+priority)?\b", TaskPriority::Critical),
+            (r"(?i)\b(?:high\s+priority|important)\b", TaskPriority::High),
+            (r"(?i)\b(?:medium|normal)\s+priority\b", TaskPriority::Medium),
+            (r"(?i)\blow\s+priority\b", TaskPriority::Low),
+            (r"(?i)\b(?:someday|wish)\b", TaskPriority::Wish),
+        ]
+        .into_iter()
+        .map(|(pattern, priority)| (Regex::new(pattern).expect("valid priority pattern"), priority))
+        .collect()
+    })
+}
+
+/// Tagged keywords (`#health`, `@work`, "for work") are removed from the name,
+/// plain ones stay since they usually belong to it
+fn context_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?P<tag>[#@]|\bfor\s+)?\b(?P<keyword>work|office|personal|home|family|health|learning|study|maintenance|research)\b",
+        )
+        .expect("valid context pattern")
+    })
+}
+
+/// Parse free text into a task request, resolving dates relative to now
+pub fn parse_natural_language_task(input: &str) -> TylResult<NaturalLanguageParseResult> {
+    parse_natural_language_task_at(input, Utc::now())
+}
+
+/// Parse free text into a task request, resolving dates relative to `now`
+pub fn parse_natural_language_task_at(input: &str, now: DateTime<Utc>) -> TylResult<NaturalLanguageParseResult> {
+    if input.trim().is_empty() {
+        return Err(TylError::validation("input", "Input cannot be empty"));
+    }
+
+    let mut text = input.to_string();
+    let mut notes = Vec::new();
+    let mut inferred = 0;
+
+    let mut due_date = None;
+    if let Some(captures) = date_pattern().captures(&text) {
+        let expression = captures[0].to_string();
+        if let Some(date) = resolve_date(&captures, now.date_naive()) {
+            due_date = date.and_hms_opt(23, 59, 59).map(|d| d.and_utc());
+            notes.push(format!("Due date {} from \"{}\"", date, expression));
+            inferred += 1;
+            text = text.replacen(&expression, " ", 1);
+        } else {
+            notes.push(format!("Ignored \"{}\", it is not a valid date", expression));
+        }
+    }
+
+    let mut priority = TaskPriority::Medium;
+    if let Some((found, matched)) = priority_patterns().iter()
+        .find_map(|(pattern, priority)| pattern.find(&text).map(|m| (*priority, m.as_str().to_string())))
+    {
+        priority = found;
+        notes.push(format!("Priority {:?} from \"{}\"", found, matched));
+        inferred += 1;
+        text = text.replacen(&matched, " ", 1);
+    } else {
+        notes.push("No priority keyword, defaulted to Medium".to_string());
+    }
+
+    let mut context = TaskContext::Work;
+    if let Some(captures) = context_pattern().captures(&text) {
+        let keyword = captures["keyword"].to_string();
+        context = context_for_keyword(&keyword);
+        notes.push(format!("Context {:?} from \"{}\"", context, keyword));
+        inferred += 1;
+        if captures.name("tag").is_some() {
+            let tagged = captures[0].to_string();
+            text = text.replacen(&tagged, " ", 1);
+        }
+    } else {
+        notes.push("No context keyword, defaulted to Work".to_string());
+    }
+
+    let name = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = name.trim_matches(|c: char| c.is_whitespace() || ",;:-".contains(c)).to_string();
+    if name.is_empty() {
+        return Err(TylError::validation("input", "No task name left after removing dates and keywords"));
+    }
+
+    Ok(NaturalLanguageParseResult {
+        parsed_request: CreateTaskRequest {
+            id: String::new(),
+            name,
+            description: None,
+            context,
+            priority,
+            complexity: TaskComplexity::Medium,
+            due_date,
+            estimated_date: None,
+            implementation_details: None,
+            success_criteria: vec![],
+            test_strategy: None,
+            source: TaskSource::Self_,
+            visibility: TaskVisibility::Private,
+            recurrence: None,
+            custom_properties: HashMap::new(),
+            assigned_user_id: None,
+            project_id: None,
+            created_by: None,
+        },
+        confidence: (BASE_CONFIDENCE + INFERRED_FIELD_CONFIDENCE * inferred as f64).min(1.0),
+        parse_notes: notes,
+    })
+}
+
+/// Day a matched date expression refers to; weekdays always mean the next one
+fn resolve_date(captures: &Captures, today: NaiveDate) -> Option<NaiveDate> {
+    if let Some(relative) = captures.name("relative") {
+        return match relative.as_str().to_lowercase().as_str() {
+            "today" | "tonight" => Some(today),
+            "tomorrow" => Some(today + Duration::days(1)),
+            "next week" => Some(today + Duration::weeks(1)),
+            _ => today.checked_add_months(Months::new(1)),
+        };
+    }
+    if let (Some(amount), Some(unit)) = (captures.name("amount"), captures.name("unit")) {
+        let amount: i64 = amount.as_str().parse().ok()?;
+        let days = if unit.as_str().to_lowercase().starts_with("week") { amount * 7 } else { amount };
+        return today.checked_add_signed(Duration::days(days));
+    }
+    if let Some(weekday) = captures.name("weekday") {
+        let target: Weekday = weekday.as_str().parse().ok()?;
+        let ahead = (target.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        let ahead = if ahead == 0 { 7 } else { ahead };
+        return Some(today + Duration::days(ahead as i64));
+    }
+    captures.name("date")
+        .and_then(|date| NaiveDate::parse_from_str(date.as_str(), "%Y-%m-%d").ok())
+}
+
+fn context_for_keyword(keyword: &str) -> TaskContext {
+    match keyword.to_lowercase().as_str() {
+        "personal" | "home" | "family" | "health" => TaskContext::Personal,
+        "learning" | "study" => TaskContext::Learning,
+        "maintenance" => TaskContext::Maintenance,
+        "research" => TaskContext::Research,
+        _ => TaskContext::Work,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// A Wednesday
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 15, 9, 0, 0).unwrap()
+    }
+
+    fn due_day(result: &NaturalLanguageParseResult) -> Option<NaiveDate> {
+        result.parsed_request.due_date.map(|d| d.date_naive())
+    }
+
+    #[test]
+    fn test_parse_date_and_priority() {
+        let result = parse_natural_language_task_at("Fix login bug by Friday high priority", now()).unwrap();
+
+        assert_eq!(result.parsed_request.name, "Fix login bug");
+        assert_eq!(due_day(&result), NaiveDate::from_ymd_opt(2024, 5, 17));
+        assert_eq!(result.parsed_request.priority, TaskPriority::High);
+        assert_eq!(result.parsed_request.context, TaskContext::Work);
+        assert!((result.confidence - 0.8).abs() < 1e-9);
+        assert_eq!(result.parse_notes.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_relative_dates() {
+        let day = |input: &str| due_day(&parse_natural_language_task_at(input, now()).unwrap());
+
+        assert_eq!(day("Call mom tomorrow"), NaiveDate::from_ymd_opt(2024, 5, 16));
+        assert_eq!(day("Plan sprint next week"), NaiveDate::from_ymd_opt(2024, 5, 22));
+        assert_eq!(day("Renew passport in 3 days"), NaiveDate::from_ymd_opt(2024, 5, 18));
+        assert_eq!(day("Weekly sync on Wednesday"), NaiveDate::from_ymd_opt(2024, 5, 22));
+        assert_eq!(day("Ship release before 2024-06-01"), NaiveDate::from_ymd_opt(2024, 6, 1));
+        assert_eq!(day("Tidy the backlog"), None);
+    }
+
+    #[test]
+    fn test_parse_context_keywords() {
+        let result = parse_natural_language_task_at("Book dentist appointment #health urgent", now()).unwrap();
+        assert_eq!(result.parsed_request.name, "Book dentist appointment");
+        assert_eq!(result.parsed_request.context, TaskContext::Personal);
+        assert_eq!(result.parsed_request.priority, TaskPriority::Critical);
+
+        // Untagged keywords set the context but stay in the name
+        let result = parse_natural_language_task_at("Clean up home office", now()).unwrap();
+        assert_eq!(result.parsed_request.name, "Clean up home office");
+        assert_eq!(result.parsed_request.context, TaskContext::Personal);
+
+        assert!(parse_natural_language_task_at("urgent tomorrow", now()).is_err());
+        assert!(parse_natural_language_task_at("   ", now()).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+//! Execution planning over the dependency graph
+//!
+//! Pure graph algorithms used to order tasks so that every dependency is
+//! finished before the tasks that depend on it, and the scoring that orders a
+//! user's actionable tasks for the day.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use super::models::{Task, TaskComplexity, TaskDependency};
+use crate::{TaskServiceError, TaskServiceResult};
+
+const URGENCY_WEIGHT: f64 = 0.4;
+const PRIORITY_WEIGHT: f64 = 0.3;
+const IMPACT_WEIGHT: f64 = 0.2;
+const COMPLEXITY_EFFICIENCY_WEIGHT: f64 = 0.1;
+/// Due dates further away than this add no urgency
+const URGENCY_HORIZON_HOURS: f64 = 7.0 * 24.0;
+/// Factors listed in an ordered task's reasoning
+const REASONING_FACTORS: usize = 2;
+/// Working hours that make up one day of an execution plan
+const WORKING_HOURS_PER_DAY: f64 = 8.0;
+
+/// Task placed in a user's daily order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderedTask {
+    pub task: Task,
+    /// 1 for the task to do first
+    pub rank: u32,
+    pub score: f64,
+    /// The factors that contributed most to the score, largest first
+    pub reasoning: Vec<String>,
+}
+
+/// Group tasks into execution waves using Kahn's algorithm
+///
+/// Every wave only depends on tasks from earlier waves, so the tasks inside a
+/// wave can run in parallel. Dependencies pointing outside `task_ids` are
+/// ignored. Tasks within a wave are sorted by id to keep results stable.
+pub fn execution_waves(
+    task_ids: &[String],
+    dependencies: &[TaskDependency],
+) -> TaskServiceResult<Vec<Vec<String>>> {
+    let ids: BTreeSet<&str> = task_ids.iter().map(String::as_str).collect();
+
+    // `from` depends on `to`, so `to` has to be executed first
+    let mut dependents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut in_degree: BTreeMap<&str, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    let mut seen_edges = HashSet::new();
+    for dependency in dependencies {
+        let (before, after) = (dependency.to_task_id.as_str(), dependency.from_task_id.as_str());
+        if !ids.contains(before) || !ids.contains(after) || !seen_edges.insert((before, after)) {
+            continue;
+        }
+        dependents.entry(before).or_default().push(after);
+        *in_degree.entry(after).or_default() += 1;
+    }
+
+    let mut waves = Vec::new();
+    let mut current: Vec<&str> = in_degree.iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    let mut scheduled = 0;
+
+    while !current.is_empty() {
+        scheduled += current.len();
+        let mut next = Vec::new();
+        for id in &current {
+            for dependent in dependents.get(id).into_iter().flatten() {
+                let degree = in_degree.get_mut(dependent).expect("dependent is a known task");
+                *degree -= 1;
+                if *degree == 0 {
+                    next.push(*dependent);
+                }
+            }
+        }
+        next.sort_unstable();
+        waves.push(current.iter().map(|id| id.to_string()).collect());
+        current = next;
+    }
+
+    if scheduled < ids.len() {
+        return Err(TaskServiceError::Domain {
+            message: "Cycle detected in dependency graph".to_string(),
+        });
+    }
+
+    Ok(waves)
+}
+
+/// Tasks that can be worked on at the same time in an execution plan
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionWave {
+    /// 1 for the wave to run first
+    pub wave_number: u32,
+    pub tasks: Vec<Task>,
+    pub can_parallelize: bool,
+    /// Days taken by the wave's longest task, from its complexity
+    pub estimated_duration_days: f64,
+}
+
+/// Tasks grouped into waves that run one after another
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionPlan {
+    pub waves: Vec<ExecutionWave>,
+}
+
+impl ExecutionPlan {
+    /// Plan for `tasks` from the [`execution_waves`] of the dependencies between them
+    pub fn build(tasks: Vec<Task>, dependencies: &[TaskDependency]) -> TaskServiceResult<Self> {
+        let task_ids: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        let waves = execution_waves(&task_ids, dependencies)?;
+
+        let mut by_id: BTreeMap<String, Task> = tasks.into_iter().map(|t| (t.id.clone(), t)).collect();
+        let waves = waves.into_iter()
+            .enumerate()
+            .map(|(index, ids)| {
+                let tasks: Vec<Task> = ids.iter().filter_map(|id| by_id.remove(id)).collect();
+                ExecutionWave {
+                    wave_number: index as u32 + 1,
+                    can_parallelize: tasks.len() > 1,
+                    estimated_duration_days: tasks.iter().map(task_duration_days).fold(0.0, f64::max),
+                    tasks,
+                }
+            })
+            .collect();
+
+        Ok(Self { waves })
+    }
+
+    /// Days needed to finish every task one after another
+    pub fn total_sequential_days(&self) -> f64 {
+        self.waves.iter()
+            .flat_map(|wave| &wave.tasks)
+            .map(task_duration_days)
+            .sum()
+    }
+
+    /// Days needed when the tasks of each wave run in parallel
+    pub fn total_parallel_days(&self) -> f64 {
+        self.waves.iter().map(|wave| wave.estimated_duration_days).sum()
+    }
+}
+
+fn task_duration_days(task: &Task) -> f64 {
+    task.complexity.default_estimated_hours() / WORKING_HOURS_PER_DAY
+}
+
+/// Order tasks, each paired with the number of tasks it blocks, best first
+///
+/// The score is `0.4 * urgency + 0.3 * priority + 0.2 * impact + 0.1 *
+/// complexity efficiency`, every factor between 0 and 1. Urgency is 1 for
+/// overdue tasks and grows over the week before the due date, impact is
+/// relative to the task blocking the most others, and complexity efficiency
+/// is `1 / complexity score` so simpler tasks win close calls.
+pub fn order_tasks(tasks: Vec<(Task, u32)>, now: DateTime<Utc>) -> Vec<OrderedTask> {
+    let max_blocked = tasks.iter().map(|(_, blocked)| *blocked).max().unwrap_or(0);
+
+    let mut ordered: Vec<OrderedTask> = tasks.into_iter()
+        .map(|(task, blocked)| {
+            let urgency = match task.due_date {
+                _ if task.is_overdue() => 1.0,
+                Some(due_date) => {
+                    let hours_left = (due_date - now).num_minutes() as f64 / 60.0;
+                    (1.0 - hours_left / URGENCY_HORIZON_HOURS).clamp(0.0, 1.0)
+                }
+                None => 0.0,
+            };
+            let impact = if max_blocked == 0 { 0.0 } else { blocked as f64 / max_blocked as f64 };
+
+            let mut factors = [
+                (URGENCY_WEIGHT * urgency, match task.due_date {
+                    _ if task.is_overdue() => "Overdue".to_string(),
+                    Some(due_date) => format!("Due {}", due_date.format("%Y-%m-%d")),
+                    None => "No due date".to_string(),
+                }),
+                (PRIORITY_WEIGHT * task.priority.weight() / 5.0, format!("{:?} priority", task.priority)),
+                (IMPACT_WEIGHT * impact, format!("Blocks {} task(s)", blocked)),
+                (
+                    COMPLEXITY_EFFICIENCY_WEIGHT / complexity_score(task.complexity),
+                    format!("{:?} complexity", task.complexity),
+                ),
+            ];
+            let score = factors.iter().map(|(contribution, _)| contribution).sum();
+
+            factors.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let reasoning = factors.into_iter()
+                .filter(|(contribution, _)| *contribution > 0.0)
+                .take(REASONING_FACTORS)
+                .map(|(contribution, factor)| format!("{} (+{:.2})", factor, contribution))
+                .collect();
+
+            OrderedTask { task, rank: 0, score, reasoning }
+        })
+        .collect();
+
+    ordered.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.task.id.cmp(&b.task.id)));
+    for (index, ordered_task) in ordered.iter_mut().enumerate() {
+        ordered_task.rank = index as u32 + 1;
+    }
+    ordered
+}
+
+/// From 1 for trivial tasks to 6 for epics
+fn complexity_score(complexity: TaskComplexity) -> f64 {
+    match complexity {
+        TaskComplexity::Trivial => 1.0,
+        TaskComplexity::Simple => 2.0,
+        TaskComplexity::Medium => 3.0,
+        TaskComplexity::Complex => 4.0,
+        TaskComplexity::VeryComplex => 5.0,
+        TaskComplexity::Epic => 6.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DependencyType;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn depends_on(from: &str, to: &str) -> TaskDependency {
+        TaskDependency::new(from.to_string(), to.to_string(), DependencyType::Requires)
+    }
+
+    #[test]
+    fn test_execution_waves_group_parallel_tasks() {
+        // D needs B and C, which both need A; E is independent
+        let deps = vec![
+            depends_on("B", "A"),
+            depends_on("C", "A"),
+            depends_on("D", "B"),
+            depends_on("D", "C"),
+            depends_on("D", "OUTSIDE"),
+        ];
+
+        let waves = execution_waves(&ids(&["D", "C", "B", "A", "E"]), &deps).unwrap();
+
+        assert_eq!(waves, vec![ids(&["A", "E"]), ids(&["B", "C"]), ids(&["D"])]);
+    }
+
+    #[test]
+    fn test_execution_waves_reject_cycles() {
+        let deps = vec![depends_on("A", "B"), depends_on("B", "A")];
+
+        let result = execution_waves(&ids(&["A", "B", "C"]), &deps);
+
+        assert!(matches!(result, Err(TaskServiceError::Domain { .. })));
+    }
+
+    #[test]
+    fn test_execution_plan_waves_and_durations() {
+        use crate::domain::TaskContext;
+
+        let task = |id: &str, complexity: TaskComplexity| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.complexity = complexity;
+            task
+        };
+        let tasks = vec![
+            task("A", TaskComplexity::Complex),
+            task("B", TaskComplexity::Medium),
+            task("C", TaskComplexity::VeryComplex),
+            task("D", TaskComplexity::Simple),
+        ];
+        let deps = vec![depends_on("B", "A"), depends_on("C", "A"), depends_on("D", "B")];
+
+        let plan = ExecutionPlan::build(tasks, &deps).unwrap();
+
+        let wave_ids: Vec<Vec<&str>> = plan.waves.iter()
+            .map(|wave| wave.tasks.iter().map(|t| t.id.as_str()).collect())
+            .collect();
+        assert_eq!(wave_ids, vec![vec!["A"], vec!["B", "C"], vec!["D"]]);
+        assert_eq!(plan.waves[1].wave_number, 2);
+        assert!(!plan.waves[0].can_parallelize && plan.waves[1].can_parallelize);
+        assert_eq!(plan.waves[1].estimated_duration_days, 2.0);
+        assert_eq!(plan.total_sequential_days(), 3.75);
+        assert_eq!(plan.total_parallel_days(), 3.25);
+    }
+
+    #[test]
+    fn test_order_tasks_puts_critical_overdue_task_first() {
+        use crate::domain::{TaskContext, TaskPriority};
+
+        let now = Utc::now();
+        let task = |id: &str, priority: TaskPriority, complexity: TaskComplexity| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.priority = priority;
+            task.complexity = complexity;
+            task
+        };
+        let mut overdue = task("overdue", TaskPriority::Critical, TaskComplexity::VeryComplex);
+        overdue.due_date = Some(now - chrono::Duration::days(1));
+        let easy = task("easy", TaskPriority::Low, TaskComplexity::Trivial);
+        let blocker = task("blocker", TaskPriority::Low, TaskComplexity::Trivial);
+
+        let ordered = order_tasks(vec![(easy, 0), (overdue, 0), (blocker, 3)], now);
+
+        let ids: Vec<&str> = ordered.iter().map(|o| o.task.id.as_str()).collect();
+        assert_eq!(ids, vec!["overdue", "blocker", "easy"]);
+        assert_eq!(ordered.iter().map(|o| o.rank).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!((ordered[0].score - 0.72).abs() < 1e-9);
+        assert_eq!(ordered[0].reasoning, vec!["Overdue (+0.40)", "Critical priority (+0.30)"]);
+        assert_eq!(ordered[1].reasoning[0], "Blocks 3 task(s) (+0.20)");
+    }
+}
@@ -0,0 +1,359 @@
+//! Project lifecycle management
+//!
+//! [`ProjectService`] owns creating, changing, archiving and deleting
+//! projects, which used to be mixed into [`TaskService`](super::TaskService).
+//! Project analytics built on the project's tasks, such as throughput or the
+//! critical path, stay on the task service.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tyl_errors::{TylError, TylResult};
+
+use super::models::*;
+use super::services::TaskRepository;
+use super::user_port::{ensure_user_known, UserServicePort};
+
+#[async_trait]
+pub trait ProjectService {
+    async fn create_project(&self, request: CreateProjectRequest) -> TylResult<Project>;
+    async fn get_project(&self, project_id: &str) -> TylResult<Option<Project>>;
+    async fn update_project(&self, project_id: &str, request: UpdateProjectRequest) -> TylResult<Project>;
+    /// Mark the project as archived; its tasks are kept
+    async fn archive_project(&self, project_id: &str) -> TylResult<Project>;
+    /// Delete a project that has no tasks left
+    async fn delete_project(&self, project_id: &str) -> TylResult<()>;
+    /// Projects ordered by name, archived ones only with `include_archived`
+    async fn list_projects(&self, include_archived: bool) -> TylResult<Vec<Project>>;
+    async fn add_task_to_project(&self, task_id: &str, project_id: &str) -> TylResult<()>;
+    /// Progress of the project's tasks, see [`ProjectSummary`]
+    async fn get_project_summary(&self, project_id: &str) -> TylResult<ProjectSummary>;
+    /// Make an existing user the project lead and give them the project's unassigned critical tasks
+    async fn transfer_project_ownership(&self, project_id: &str, new_owner_id: &str, reason: String) -> TylResult<OwnershipTransferResult>;
+}
+
+/// Outcome of transferring a project to a new lead
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OwnershipTransferResult {
+    pub project: Project,
+    pub previous_owner_id: Option<String>,
+    /// Unassigned critical tasks now assigned to the new lead
+    pub auto_assigned_task_ids: Vec<String>,
+}
+
+/// Open task of a project due next
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProjectMilestone {
+    pub task_id: String,
+    pub name: String,
+    pub due_date: DateTime<Utc>,
+}
+
+/// Where a project stands, from its tasks
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProjectSummary {
+    pub project_id: String,
+    pub total_tasks: u32,
+    pub tasks_by_status: HashMap<TaskStatus, u32>,
+    /// Effort assumed from the complexity of every task that was not cancelled
+    pub estimated_hours: f64,
+    /// Time from start to completion of the completed tasks
+    pub actual_hours: f64,
+    /// Share of the tasks that were not cancelled which are done
+    pub completion_percentage: f64,
+    /// Open task with the nearest due date still ahead of us
+    pub next_milestone: Option<ProjectMilestone>,
+}
+
+impl ProjectSummary {
+    pub fn from_tasks(project_id: &str, tasks: &[Task], now: DateTime<Utc>) -> Self {
+        let mut tasks_by_status = HashMap::new();
+        for task in tasks {
+            *tasks_by_status.entry(task.status).or_insert(0) += 1;
+        }
+
+        let planned: Vec<&Task> = tasks.iter().filter(|task| task.status != TaskStatus::Cancelled).collect();
+        let done = planned.iter().filter(|task| task.status == TaskStatus::Done).count();
+        let completion_percentage = if planned.is_empty() {
+            0.0
+        } else {
+            done as f64 / planned.len() as f64 * 100.0
+        };
+        let estimated_hours = planned.iter().map(|task| task.complexity.default_estimated_hours()).sum();
+        let actual_hours = tasks.iter()
+            .filter_map(|task| Some(task.completed_at? - task.started_at?))
+            .map(|duration| duration.num_minutes() as f64 / 60.0)
+            .sum();
+
+        let next_milestone = tasks.iter()
+            .filter(|task| !task.status.is_terminal())
+            .filter_map(|task| task.due_date.filter(|due_date| *due_date >= now).map(|due_date| (due_date, task)))
+            .min_by_key(|(due_date, _)| *due_date)
+            .map(|(due_date, task)| ProjectMilestone {
+                task_id: task.id.clone(),
+                name: task.name.clone(),
+                due_date,
+            });
+
+        Self {
+            project_id: project_id.to_string(),
+            total_tasks: tasks.len() as u32,
+            tasks_by_status,
+            estimated_hours,
+            actual_hours,
+            completion_percentage,
+            next_milestone,
+        }
+    }
+}
+
+/// Rules every implementation applies to a new project
+pub fn validate_create_project_request(request: &CreateProjectRequest) -> TylResult<()> {
+    if request.id.trim().is_empty() {
+        return Err(TylError::validation("id", "Project id cannot be empty"));
+    }
+    if request.name.trim().is_empty() {
+        return Err(TylError::validation("name", "Project name cannot be empty"));
+    }
+    Ok(())
+}
+
+/// [`ProjectService::transfer_project_ownership`] for implementations storing
+/// projects in a [`TaskRepository`]
+///
+/// The transfer is audited, and the new lead must be known to the repository
+/// and to `user_service`, if there is one.
+pub async fn transfer_ownership_in_repository<R: TaskRepository + Sync>(
+    repository: &R,
+    user_service: Option<&(dyn UserServicePort + Send + Sync)>,
+    project_id: &str,
+    new_owner_id: &str,
+    reason: String,
+) -> TylResult<OwnershipTransferResult> {
+    if reason.trim().is_empty() {
+        return Err(TylError::validation("reason", "A reason is required to transfer ownership"));
+    }
+    let mut project = repository.find_project_by_id(project_id).await?
+        .ok_or_else(|| TylError::not_found("project", project_id))?;
+    if !repository.user_exists(new_owner_id).await? {
+        return Err(TylError::not_found("user", new_owner_id));
+    }
+    ensure_user_known(user_service, new_owner_id).await?;
+
+    let previous_owner_id = project.project_lead_user_id.replace(new_owner_id.to_string());
+    project.updated_at = Utc::now();
+    repository.set_project_lead(project_id, new_owner_id).await?;
+
+    let entry = AuditEntry::new("Project", project_id, "ownership_transferred", reason)
+        .with_change(previous_owner_id.clone(), Some(new_owner_id.to_string()));
+    repository.save_audit_entry(&entry).await?;
+
+    let mut auto_assigned_task_ids = Vec::new();
+    for task in repository.find_project_tasks(project_id).await? {
+        if task.priority != TaskPriority::Critical || task.status.is_terminal() {
+            continue;
+        }
+        if repository.find_task_assignments(&task.id).await?.is_empty() {
+            repository.assign_user_to_task(&task.id, new_owner_id, "owner").await?;
+            auto_assigned_task_ids.push(task.id);
+        }
+    }
+
+    Ok(OwnershipTransferResult { project, previous_owner_id, auto_assigned_task_ids })
+}
+
+/// Mock implementation of ProjectService for development and testing
+#[derive(Default)]
+pub struct MockProjectService {
+    projects: Arc<Mutex<HashMap<String, Project>>>,
+    /// Tasks with the id of their project
+    tasks: Arc<Mutex<Vec<(String, Task)>>>,
+}
+
+impl MockProjectService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a task in the project, e.g. to seed a summary in tests
+    pub fn insert_task(&self, project_id: &str, task: Task) {
+        self.tasks.lock().unwrap().push((project_id.to_string(), task));
+    }
+
+    fn project_tasks(&self, project_id: &str) -> Vec<Task> {
+        self.tasks.lock().unwrap().iter()
+            .filter(|(id, _)| id == project_id)
+            .map(|(_, task)| task.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ProjectService for MockProjectService {
+    async fn create_project(&self, request: CreateProjectRequest) -> TylResult<Project> {
+        validate_create_project_request(&request)?;
+        let mut projects = self.projects.lock().unwrap();
+        if projects.contains_key(&request.id) {
+            return Err(TylError::validation("id", format!("Project '{}' already exists", request.id)));
+        }
+        let project = Project::from(request);
+        projects.insert(project.id.clone(), project.clone());
+        Ok(project)
+    }
+
+    async fn get_project(&self, project_id: &str) -> TylResult<Option<Project>> {
+        Ok(self.projects.lock().unwrap().get(project_id).cloned())
+    }
+
+    async fn update_project(&self, project_id: &str, request: UpdateProjectRequest) -> TylResult<Project> {
+        let mut projects = self.projects.lock().unwrap();
+        let project = projects.get_mut(project_id)
+            .ok_or_else(|| TylError::not_found("project", project_id))?;
+        if let Some(name) = request.name {
+            project.name = name;
+        }
+        if request.description.is_some() {
+            project.description = request.description;
+        }
+        if request.start_date.is_some() {
+            project.start_date = request.start_date;
+        }
+        if request.end_date.is_some() {
+            project.end_date = request.end_date;
+        }
+        project.updated_at = Utc::now();
+        Ok(project.clone())
+    }
+
+    async fn archive_project(&self, project_id: &str) -> TylResult<Project> {
+        let mut projects = self.projects.lock().unwrap();
+        let project = projects.get_mut(project_id)
+            .ok_or_else(|| TylError::not_found("project", project_id))?;
+        project.status = PROJECT_STATUS_ARCHIVED.to_string();
+        project.updated_at = Utc::now();
+        Ok(project.clone())
+    }
+
+    async fn delete_project(&self, project_id: &str) -> TylResult<()> {
+        if !self.project_tasks(project_id).is_empty() {
+            return Err(TylError::validation("project_id", format!("Project {} still has tasks", project_id)));
+        }
+        self.projects.lock().unwrap().remove(project_id)
+            .map(|_| ())
+            .ok_or_else(|| TylError::not_found("project", project_id))
+    }
+
+    async fn list_projects(&self, include_archived: bool) -> TylResult<Vec<Project>> {
+        let mut projects: Vec<Project> = self.projects.lock().unwrap().values()
+            .filter(|project| include_archived || !project.is_archived())
+            .cloned()
+            .collect();
+        projects.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
+        Ok(projects)
+    }
+
+    async fn add_task_to_project(&self, _task_id: &str, project_id: &str) -> TylResult<()> {
+        if !self.projects.lock().unwrap().contains_key(project_id) {
+            return Err(TylError::not_found("project", project_id));
+        }
+        Ok(()) // Mock implementation
+    }
+
+    async fn get_project_summary(&self, project_id: &str) -> TylResult<ProjectSummary> {
+        if !self.projects.lock().unwrap().contains_key(project_id) {
+            return Err(TylError::not_found("project", project_id));
+        }
+        Ok(ProjectSummary::from_tasks(project_id, &self.project_tasks(project_id), Utc::now()))
+    }
+
+    async fn transfer_project_ownership(&self, project_id: &str, new_owner_id: &str, reason: String) -> TylResult<OwnershipTransferResult> {
+        if reason.trim().is_empty() {
+            return Err(TylError::validation("reason", "A reason is required to transfer ownership"));
+        }
+        // The mock does not track users, so any non-empty id is accepted
+        if new_owner_id.trim().is_empty() {
+            return Err(TylError::not_found("user", new_owner_id));
+        }
+
+        let mut projects = self.projects.lock().unwrap();
+        let project = projects.get_mut(project_id)
+            .ok_or_else(|| TylError::not_found("project", project_id))?;
+        let previous_owner_id = project.project_lead_user_id.replace(new_owner_id.to_string());
+        project.updated_at = Utc::now();
+
+        // Nor assignments, so no task is handed to the new lead
+        Ok(OwnershipTransferResult {
+            project: project.clone(),
+            previous_owner_id,
+            auto_assigned_task_ids: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn task(id: &str, status: TaskStatus, complexity: TaskComplexity) -> Task {
+        let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+        task.status = status;
+        task.complexity = complexity;
+        task
+    }
+
+    #[test]
+    fn test_summary_from_tasks() {
+        let now = Utc::now();
+        let mut done = task("T1", TaskStatus::Done, TaskComplexity::Medium);
+        done.started_at = Some(now - Duration::hours(6));
+        done.completed_at = Some(now);
+        let mut soon = task("T2", TaskStatus::InProgress, TaskComplexity::Simple);
+        soon.due_date = Some(now + Duration::days(2));
+        let mut later = task("T3", TaskStatus::Backlog, TaskComplexity::Complex);
+        later.due_date = Some(now + Duration::days(9));
+        let mut overdue = task("T4", TaskStatus::Ready, TaskComplexity::Trivial);
+        overdue.due_date = Some(now - Duration::days(1));
+        let cancelled = task("T5", TaskStatus::Cancelled, TaskComplexity::VeryComplex);
+
+        let summary = ProjectSummary::from_tasks("P-1", &[done, soon, later, overdue, cancelled], now);
+
+        assert_eq!(summary.total_tasks, 5);
+        assert_eq!(summary.tasks_by_status[&TaskStatus::Done], 1);
+        assert_eq!(summary.tasks_by_status[&TaskStatus::Cancelled], 1);
+        assert_eq!(summary.completion_percentage, 25.0);
+        assert_eq!(summary.estimated_hours, 4.0 + 2.0 + 8.0 + 1.0);
+        assert_eq!(summary.actual_hours, 6.0);
+        assert_eq!(summary.next_milestone.unwrap().task_id, "T2");
+    }
+
+    #[tokio::test]
+    async fn test_mock_project_lifecycle() {
+        let service = MockProjectService::new();
+        let request = CreateProjectRequest {
+            id: "P-1".to_string(),
+            code: "P1".to_string(),
+            name: "Launch".to_string(),
+            description: None,
+            start_date: None,
+            end_date: None,
+        };
+        service.create_project(request.clone()).await.unwrap();
+        assert!(service.create_project(request).await.is_err());
+
+        let transfer = service.transfer_project_ownership("P-1", "lead-1", "Kickoff".to_string()).await.unwrap();
+        assert_eq!(transfer.project.project_lead_user_id.as_deref(), Some("lead-1"));
+        assert!(transfer.previous_owner_id.is_none());
+        assert!(service.transfer_project_ownership("P-1", "lead-2", " ".to_string()).await.is_err());
+
+        service.archive_project("P-1").await.unwrap();
+        assert!(service.list_projects(false).await.unwrap().is_empty());
+        assert_eq!(service.list_projects(true).await.unwrap().len(), 1);
+
+        service.insert_task("P-1", task("T1", TaskStatus::Backlog, TaskComplexity::Medium));
+        assert!(service.delete_project("P-1").await.is_err());
+    }
+}
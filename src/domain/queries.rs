@@ -4,13 +4,17 @@
 //! analytics, and insights using Cypher through the FalkorDB adapter.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc, Duration};
+use chrono::{DateTime, Utc, Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::json;
+use utoipa::ToSchema;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use tyl_errors::{TylError, TylResult};
 use tyl_falkordb_adapter::FalkorDBAdapter;
 
-use super::{Task, TaskStatus, TaskPriority, TaskContext, TaskComplexity, DependencyType};
+use super::{Task, TaskDependency, TaskStatus, TaskPriority, TaskContext, TaskComplexity, DependencyType, ExternalTicketLink};
+use super::search::{search_order_clause, validate_search_query, PageRequest, PagedResult, SortSpec, TaskSearchQuery};
+use crate::adapters::cypher::{cypher_params, CypherParams, ExecuteCypherWithParams};
 
 /// Complex query service for advanced task operations
 #[async_trait]
@@ -33,8 +37,14 @@ pub trait TaskQueryService {
     
     // Advanced search and filtering
     async fn semantic_search(&self, query: &str, context: Option<TaskContext>) -> TylResult<Vec<Task>>;
+    /// Tasks matching `query` in the full-text index over name and description, most relevant first
+    async fn fulltext_search(&self, query: &str, context: Option<TaskContext>, limit: usize) -> TylResult<Vec<TaskSearchResult>>;
     async fn find_tasks_by_pattern(&self, pattern: TaskPattern) -> TylResult<Vec<Task>>;
+    /// Tasks matching the structured `query`, see [`TaskSearchQuery`]
+    async fn search_tasks_advanced(&self, query: TaskSearchQuery, sort: Vec<SortSpec>, page: PageRequest) -> TylResult<PagedResult<Task>>;
     async fn get_task_timeline(&self, task_id: &str) -> TylResult<TaskTimeline>;
+    /// Tasks within `depth` dependency hops of the task in either direction, see [`TaskSubgraph`]
+    async fn get_task_subgraph(&self, task_id: &str, depth: u32) -> TylResult<TaskSubgraph>;
     
     // Resource and workload analysis
     async fn analyze_workload_distribution(&self) -> TylResult<WorkloadDistribution>;
@@ -54,15 +64,1090 @@ pub trait TaskQueryService {
 // Query Result Types
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DependencyPath {
     pub path_id: String,
     pub task_chain: Vec<String>, // Task IDs in dependency order
+    #[schema(value_type = Option<Object>)]
     pub total_estimated_time: Option<Duration>,
     pub blocking_score: f64, // Higher score = more critical
     pub longest_chain: bool,
 }
 
+impl DependencyPath {
+    /// Build a chain from its tasks (prerequisites first), total effort and
+    /// average priority weight; `longest_chain` is left to the caller
+    pub fn from_chain(task_chain: Vec<String>, total_estimated_hours: f64, avg_priority_weight: f64) -> Self {
+        Self {
+            path_id: uuid::Uuid::new_v4().to_string(),
+            blocking_score: task_chain.len() as f64 * avg_priority_weight,
+            total_estimated_time: Some(Duration::minutes((total_estimated_hours * 60.0).round() as i64)),
+            task_chain,
+            longest_chain: false,
+        }
+    }
+}
+
+/// A status holding more tasks than its project's WIP limit allows
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WipViolation {
+    pub status: TaskStatus,
+    pub current_count: u32,
+    pub limit: u32,
+    pub overflow: u32,
+    /// Tasks in the status, lowest priority first
+    pub affected_tasks: Vec<String>,
+    pub recommendation: String,
+}
+
+impl WipViolation {
+    /// Build the violation for `status` from its tasks ordered lowest
+    /// priority first, or `None` when the limit is respected
+    pub fn from_usage(status: TaskStatus, limit: u32, affected_tasks: Vec<String>) -> Option<Self> {
+        let current_count = affected_tasks.len() as u32;
+        let overflow = current_count.checked_sub(limit).filter(|o| *o > 0)?;
+        let to_move = &affected_tasks[..overflow as usize];
+        let recommendation = format!(
+            "Move {} lowest priority task{} back to backlog: {}",
+            overflow,
+            if overflow == 1 { "" } else { "s" },
+            to_move.join(", ")
+        );
+
+        Some(Self { status, current_count, limit, overflow, affected_tasks, recommendation })
+    }
+}
+
+/// Tasks completed per day over a period, with control limits
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ThroughputMetrics {
+    pub project_id: String,
+    pub period_days: u32,
+    /// One entry per day of the period, oldest first, including empty days
+    #[schema(value_type = Vec<Vec<Value>>)]
+    pub daily_throughput: Vec<(NaiveDate, u32)>,
+    pub rolling_7d_avg: f64,
+    pub rolling_30d_avg: f64,
+    #[schema(value_type = Vec<Value>)]
+    pub peak_day: (NaiveDate, u32),
+    #[schema(value_type = Vec<Value>)]
+    pub low_day: (NaiveDate, u32),
+    /// Mean plus three standard deviations of the daily counts
+    pub upper_control_limit: f64,
+    /// Mean minus three standard deviations, never below zero
+    pub lower_control_limit: f64,
+}
+
+impl ThroughputMetrics {
+    /// Build the metrics for the `period_days` days ending on `last_day`
+    ///
+    /// `completions` only needs the days on which tasks were completed;
+    /// counts outside the period are ignored.
+    pub fn from_completions(
+        project_id: &str,
+        period_days: u32,
+        last_day: NaiveDate,
+        completions: &[(NaiveDate, u32)],
+    ) -> Self {
+        let period_days = period_days.max(1);
+        let first_day = last_day - Duration::days(period_days as i64 - 1);
+        let daily_throughput: Vec<(NaiveDate, u32)> = first_day.iter_days()
+            .take(period_days as usize)
+            .map(|day| {
+                let count = completions.iter()
+                    .filter(|(completed_on, _)| *completed_on == day)
+                    .map(|(_, count)| count)
+                    .sum();
+                (day, count)
+            })
+            .collect();
+
+        let counts: Vec<f64> = daily_throughput.iter().map(|(_, count)| *count as f64).collect();
+        let trailing_mean = |days: usize| mean(&counts[counts.len().saturating_sub(days)..]);
+        let average = mean(&counts);
+        let std_dev = (counts.iter().map(|c| (c - average).powi(2)).sum::<f64>() / counts.len() as f64).sqrt();
+
+        // Earliest day wins ties
+        let peak_day = daily_throughput.iter().copied()
+            .fold(daily_throughput[0], |best, day| if day.1 > best.1 { day } else { best });
+        let low_day = daily_throughput.iter().copied()
+            .fold(daily_throughput[0], |best, day| if day.1 < best.1 { day } else { best });
+
+        Self {
+            project_id: project_id.to_string(),
+            period_days,
+            rolling_7d_avg: trailing_mean(7),
+            rolling_30d_avg: trailing_mean(30),
+            peak_day,
+            low_day,
+            upper_control_limit: average + 3.0 * std_dev,
+            lower_control_limit: (average - 3.0 * std_dev).max(0.0),
+            daily_throughput,
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Longest date range a burndown chart covers
+pub const MAX_BURNDOWN_RANGE_DAYS: i64 = 365;
+
+/// Project tasks on one day of a burndown chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Tasks created by the end of the day and not done
+    pub remaining_tasks: u32,
+    pub completed_tasks: u32,
+    pub added_tasks: u32,
+}
+
+/// Remaining tasks of a project per day, next to the steady pace that gets them done by the end date
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BurndownChart {
+    pub project_id: String,
+    /// From the start date to the end date or today, whichever comes first
+    pub data_points: Vec<BurndownPoint>,
+    /// From the tasks open on the start date down to none on the end date
+    pub ideal_line: Vec<BurndownPoint>,
+}
+
+impl BurndownChart {
+    /// Build the chart for the days from `start` to `end`
+    ///
+    /// `created` and `completed` count the project's tasks per day of creation
+    /// and completion; the days before `start` give the tasks still open when
+    /// the chart starts.
+    pub fn from_daily_counts(
+        project_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        today: NaiveDate,
+        created: &[(NaiveDate, u32)],
+        completed: &[(NaiveDate, u32)],
+    ) -> Self {
+        let on = |counts: &[(NaiveDate, u32)], day: NaiveDate| -> u32 {
+            counts.iter().filter(|(date, _)| *date == day).map(|(_, count)| count).sum()
+        };
+        let before = |counts: &[(NaiveDate, u32)], day: NaiveDate| -> u32 {
+            counts.iter().filter(|(date, _)| *date < day).map(|(_, count)| count).sum()
+        };
+
+        let open_at_start = before(created, start).saturating_sub(before(completed, start));
+        let mut remaining = open_at_start;
+        let data_points = start.iter_days()
+            .take_while(|day| *day <= end.min(today))
+            .map(|date| {
+                let added_tasks = on(created, date);
+                let completed_tasks = on(completed, date);
+                remaining = (remaining + added_tasks).saturating_sub(completed_tasks);
+                BurndownPoint { date, remaining_tasks: remaining, completed_tasks, added_tasks }
+            })
+            .collect();
+
+        let total = open_at_start + on(created, start);
+        let span_days = (end - start).num_days().max(1) as f64;
+        let ideal_line = start.iter_days()
+            .take_while(|day| *day <= end)
+            .map(|date| {
+                let elapsed = (date - start).num_days() as f64;
+                let remaining_tasks = (total as f64 * (1.0 - elapsed / span_days)).round() as u32;
+                BurndownPoint { date, remaining_tasks, completed_tasks: total - remaining_tasks, added_tasks: 0 }
+            })
+            .collect();
+
+        Self { project_id: project_id.to_string(), data_points, ideal_line }
+    }
+}
+
+/// Dependencies older than this are checked for an idle blocking task
+pub const STALE_DEPENDENCY_AGE_DAYS: i64 = 90;
+/// Blocking tasks untouched for this long make an old dependency stale
+pub const STALE_BLOCKING_TASK_IDLE_DAYS: i64 = 30;
+
+/// What to do about a stale dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleDependencyAction {
+    /// One end was cancelled, so the dependency can no longer be satisfied
+    Remove,
+    /// The dependency may still hold but should be confirmed
+    Update,
+}
+
+/// Dependency that probably no longer reflects the actual work
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StaleDependency {
+    pub dependency_id: String,
+    pub from_task_id: String,
+    pub to_task_id: String,
+    pub age_days: u32,
+    pub reason: String,
+    pub suggested_action: StaleDependencyAction,
+}
+
+impl StaleDependency {
+    /// Check `dependency`, where `from` depends on the blocking task `to`
+    pub fn evaluate(dependency: &TaskDependency, from: &Task, to: &Task, now: DateTime<Utc>) -> Option<Self> {
+        let age = now - dependency.created_at;
+        let idle = now - to.updated_at;
+
+        let (reason, suggested_action) = if to.status == TaskStatus::Cancelled {
+            (format!("Blocking task {} was cancelled", to.id), StaleDependencyAction::Remove)
+        } else if from.status == TaskStatus::Cancelled {
+            (format!("Dependent task {} was cancelled", from.id), StaleDependencyAction::Remove)
+        } else if age > Duration::days(STALE_DEPENDENCY_AGE_DAYS) && idle > Duration::days(STALE_BLOCKING_TASK_IDLE_DAYS) {
+            (
+                format!(
+                    "Created {} days ago and blocking task {} has not been updated in {} days",
+                    age.num_days(), to.id, idle.num_days()
+                ),
+                StaleDependencyAction::Update,
+            )
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            dependency_id: dependency.id.clone(),
+            from_task_id: from.id.clone(),
+            to_task_id: to.id.clone(),
+            age_days: age.num_days().max(0) as u32,
+            reason,
+            suggested_action,
+        })
+    }
+}
+
+/// Dependencies flagged as stale, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DependencyAgeReport {
+    pub stale_dependencies: Vec<StaleDependency>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl DependencyAgeReport {
+    pub fn from_dependencies(dependencies: &[(TaskDependency, Task, Task)], now: DateTime<Utc>) -> Self {
+        let mut stale_dependencies: Vec<StaleDependency> = dependencies.iter()
+            .filter_map(|(dependency, from, to)| StaleDependency::evaluate(dependency, from, to, now))
+            .collect();
+        stale_dependencies.sort_by(|a, b| b.age_days.cmp(&a.age_days));
+
+        Self { stale_dependencies, generated_at: now }
+    }
+}
+
+/// Dependencies on tasks due within this many days are medium risk
+pub const CROSS_PROJECT_RISK_WINDOW_DAYS: i64 = 7;
+
+/// The fields of a task needed to recognise it in a report
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskSummary {
+    pub id: String,
+    pub name: String,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+impl From<&Task> for TaskSummary {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            status: task.status,
+            priority: task.priority,
+            due_date: task.due_date,
+        }
+    }
+}
+
+/// A blocked task in a standup report
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockerInfo {
+    pub task_id: String,
+    pub task_name: String,
+    /// Unfinished tasks it depends on
+    pub blocked_by: Vec<String>,
+    /// Days since the task was last updated, as the moment it got blocked is not recorded
+    pub days_blocked: u32,
+}
+
+/// What a user finished, is working on and is stuck on, for an async standup
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StandupReport {
+    pub user_id: String,
+    pub date: NaiveDate,
+    /// Completed the day before `date`
+    pub completed_yesterday: Vec<TaskSummary>,
+    pub in_progress_today: Vec<TaskSummary>,
+    /// Ready tasks, highest priority first
+    pub planned_today: Vec<TaskSummary>,
+    pub blockers: Vec<BlockerInfo>,
+    /// The report as a Markdown message ready to paste into chat
+    pub formatted_text: String,
+}
+
+impl StandupReport {
+    /// Report for `date` from the user's tasks, with the unfinished
+    /// prerequisites of each blocked task keyed by its id
+    pub fn build(user_id: &str, date: NaiveDate, tasks: &[Task], blocked_by: &HashMap<String, Vec<String>>) -> Self {
+        let summaries = |status: TaskStatus| -> Vec<TaskSummary> {
+            tasks.iter().filter(|t| t.status == status).map(TaskSummary::from).collect()
+        };
+        let yesterday = date.pred_opt().unwrap_or(date);
+        let completed_yesterday = tasks.iter()
+            .filter(|t| t.status == TaskStatus::Done && t.completed_at.is_some_and(|at| at.date_naive() == yesterday))
+            .map(TaskSummary::from)
+            .collect();
+        let mut planned_today = summaries(TaskStatus::Ready);
+        planned_today.sort_by(|a, b| b.priority.weight().total_cmp(&a.priority.weight()).then_with(|| a.id.cmp(&b.id)));
+        let blockers = tasks.iter()
+            .filter(|t| t.status == TaskStatus::Blocked)
+            .map(|t| BlockerInfo {
+                task_id: t.id.clone(),
+                task_name: t.name.clone(),
+                blocked_by: blocked_by.get(&t.id).cloned().unwrap_or_default(),
+                days_blocked: (date - t.updated_at.date_naive()).num_days().max(0) as u32,
+            })
+            .collect();
+
+        let mut report = Self {
+            user_id: user_id.to_string(),
+            date,
+            completed_yesterday,
+            in_progress_today: summaries(TaskStatus::InProgress),
+            planned_today,
+            blockers,
+            formatted_text: String::new(),
+        };
+        report.formatted_text = report.format_markdown();
+        report
+    }
+
+    fn format_markdown(&self) -> String {
+        let section = |title: &str, lines: Vec<String>| {
+            let lines = if lines.is_empty() { vec!["- Nothing".to_string()] } else { lines };
+            format!("*{}*\n{}", title, lines.join("\n"))
+        };
+        let task_line = |task: &TaskSummary| format!("- {} ({})", task.name, task.id);
+
+        let today = self.in_progress_today.iter()
+            .map(|t| format!("- In progress: {} ({})", t.name, t.id))
+            .chain(self.planned_today.iter().map(|t| format!("- Planned: {} ({})", t.name, t.id)))
+            .collect();
+        let blockers = self.blockers.iter()
+            .map(|b| match b.blocked_by.as_slice() {
+                [] => format!("- {} ({}), blocked for {} day(s)", b.task_name, b.task_id, b.days_blocked),
+                ids => format!("- {} ({}), waiting on {} for {} day(s)", b.task_name, b.task_id, ids.join(", "), b.days_blocked),
+            })
+            .collect();
+
+        [
+            format!("*Standup for {} on {}*", self.user_id, self.date),
+            section("Yesterday", self.completed_yesterday.iter().map(task_line).collect()),
+            section("Today", today),
+            section("Blockers", blockers),
+        ]
+        .join("\n\n")
+    }
+}
+
+/// Hours a task may wait in review before it counts as a stale review
+pub const DEFAULT_STALE_REVIEW_HOURS: u64 = 48;
+
+/// A task waiting in review, as listed in the review queue
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReviewQueueItem {
+    pub task: Task,
+    /// User assigned to the task, empty when it is unassigned
+    pub requested_by: String,
+    /// When the first reviewer was requested, or the task's last update without any
+    pub review_requested_at: DateTime<Utc>,
+    #[schema(value_type = Object)]
+    pub time_in_review: Duration,
+    pub external_pr_links: Vec<ExternalTicketLink>,
+    /// Whether the task has been in review longer than the stale review threshold
+    pub is_stale_review: bool,
+}
+
+impl ReviewQueueItem {
+    pub fn new(
+        task: Task,
+        requested_by: String,
+        review_requested_at: DateTime<Utc>,
+        links: Vec<ExternalTicketLink>,
+        stale_after: Duration,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let time_in_review = (now - review_requested_at).max(Duration::zero());
+        Self {
+            task,
+            requested_by,
+            review_requested_at,
+            time_in_review,
+            external_pr_links: links.into_iter().filter(ExternalTicketLink::is_pull_request).collect(),
+            is_stale_review: time_in_review > stale_after,
+        }
+    }
+}
+
+/// Dependency of a task on a task of another project
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CrossProjectDependency {
+    pub from_task: TaskSummary,
+    pub from_project: String,
+    pub to_task: TaskSummary,
+    pub to_project: String,
+    pub dependency_type: DependencyType,
+    /// High when `to_task` is overdue, medium when it is due within
+    /// [`CROSS_PROJECT_RISK_WINDOW_DAYS`]
+    pub risk_level: RiskLevel,
+}
+
+impl CrossProjectDependency {
+    /// `from` of `from_project` depends on `to` of `to_project` through `dependency`
+    pub fn new(
+        dependency: &TaskDependency,
+        (from, from_project): (&Task, String),
+        (to, to_project): (&Task, String),
+        now: DateTime<Utc>,
+    ) -> Self {
+        let open = !matches!(to.status, TaskStatus::Done | TaskStatus::Cancelled);
+        let risk_level = match to.due_date {
+            Some(due_date) if open && due_date < now => RiskLevel::High,
+            Some(due_date) if open && due_date < now + Duration::days(CROSS_PROJECT_RISK_WINDOW_DAYS) => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        };
+
+        Self {
+            from_task: TaskSummary::from(from),
+            from_project,
+            to_task: TaskSummary::from(to),
+            to_project,
+            dependency_type: dependency.dependency_type,
+            risk_level,
+        }
+    }
+}
+
+/// Days without an update after which a task counts as fully stale
+pub const STALE_TASK_DAYS: i64 = 30;
+/// Days past the due date after which the overdue factor reaches zero
+pub const OVERDUE_HEALTH_DAYS: i64 = 14;
+
+/// One number summarising how well a task is going
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskHealthScore {
+    pub task_id: String,
+    /// Weighted sum of the breakdown, from 0.0 to 1.0, higher is healthier
+    pub score: f64,
+    /// `(factor, value, weight)` of every factor, values from 0.0 to 1.0
+    pub breakdown: Vec<(String, f64, f64)>,
+    pub risk_level: RiskLevel,
+}
+
+impl TaskHealthScore {
+    /// Score `task` from its completion percentage (0 to 100), the tasks it
+    /// depends on and the number of users assigned to it
+    pub fn evaluate(
+        task: &Task,
+        completion_percentage: f64,
+        prerequisites: &[Task],
+        assignee_count: usize,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let overdue_days = match task.due_date {
+            Some(due_date) if task.is_overdue() => (now - due_date).num_days() as f64,
+            _ => 0.0,
+        };
+        let done_prerequisites = prerequisites.iter().filter(|t| t.status == TaskStatus::Done).count();
+        let idle_days = if task.status.is_terminal() { 0.0 } else { (now - task.updated_at).num_days() as f64 };
+
+        let breakdown = vec![
+            ("completion_progress".to_string(), (completion_percentage / 100.0).clamp(0.0, 1.0), 0.3),
+            ("overdue".to_string(), (1.0 - overdue_days / OVERDUE_HEALTH_DAYS as f64).clamp(0.0, 1.0), 0.2),
+            (
+                "dependency_health".to_string(),
+                if prerequisites.is_empty() { 1.0 } else { done_prerequisites as f64 / prerequisites.len() as f64 },
+                0.2,
+            ),
+            ("resource_assignment".to_string(), if assignee_count > 0 { 1.0 } else { 0.0 }, 0.15),
+            ("staleness".to_string(), (1.0 - idle_days / STALE_TASK_DAYS as f64).clamp(0.0, 1.0), 0.15),
+        ];
+        let score = breakdown.iter().map(|(_, value, weight)| value * weight).sum::<f64>();
+        let risk_level = match score {
+            s if s > 0.8 => RiskLevel::Low,
+            s if s > 0.6 => RiskLevel::Medium,
+            s if s > 0.4 => RiskLevel::High,
+            _ => RiskLevel::Critical,
+        };
+
+        Self { task_id: task.id.clone(), score, breakdown, risk_level }
+    }
+}
+
+/// Share of tasks, by betweenness, flagged as single points of failure
+pub const HIGH_RISK_CENTRALITY_SHARE: f64 = 0.1;
+
+/// How central a task is in its project's dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskCentrality {
+    pub task_id: String,
+    /// Share of shortest dependency paths between other tasks that run through this one
+    pub betweenness_centrality: f64,
+    /// Dependencies in and out, relative to the number of other tasks
+    pub degree_centrality: f64,
+    /// Inverse average distance to the reachable tasks, scaled by the share reachable
+    pub closeness_centrality: f64,
+    /// In the top [`HIGH_RISK_CENTRALITY_SHARE`] by betweenness
+    pub is_high_risk: bool,
+}
+
+impl TaskCentrality {
+    /// Centralities of `task_ids` in the graph of `dependencies`, highest betweenness first
+    ///
+    /// Betweenness follows dependency direction (Brandes' algorithm);
+    /// closeness ignores it, since a task is affected by changes either way.
+    /// Dependencies with an end outside `task_ids` are ignored.
+    pub fn from_dependencies(task_ids: &[String], dependencies: &[TaskDependency]) -> Vec<Self> {
+        let n = task_ids.len();
+        let index: HashMap<&str, usize> = task_ids.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+
+        let edges: HashSet<(usize, usize)> = dependencies.iter()
+            .filter_map(|d| Some((*index.get(d.from_task_id.as_str())?, *index.get(d.to_task_id.as_str())?)))
+            .filter(|(from, to)| from != to)
+            .collect();
+        let mut outgoing = vec![Vec::new(); n];
+        let mut neighbours = vec![Vec::new(); n];
+        let mut degree = vec![0usize; n];
+        for &(from, to) in &edges {
+            outgoing[from].push(to);
+            neighbours[from].push(to);
+            neighbours[to].push(from);
+            degree[from] += 1;
+            degree[to] += 1;
+        }
+
+        let betweenness = betweenness(&outgoing);
+        let others = n.saturating_sub(1) as f64;
+        let pairs = (n.saturating_sub(1) * n.saturating_sub(2)) as f64;
+
+        let mut centralities: Vec<Self> = task_ids.iter().enumerate()
+            .map(|(i, task_id)| Self {
+                task_id: task_id.clone(),
+                betweenness_centrality: if pairs > 0.0 { betweenness[i] / pairs } else { 0.0 },
+                degree_centrality: if others > 0.0 { degree[i] as f64 / others } else { 0.0 },
+                closeness_centrality: closeness(&neighbours, i, others),
+                is_high_risk: false,
+            })
+            .collect();
+
+        centralities.sort_by(|a, b| b.betweenness_centrality.total_cmp(&a.betweenness_centrality)
+            .then_with(|| a.task_id.cmp(&b.task_id)));
+        let high_risk = (n as f64 * HIGH_RISK_CENTRALITY_SHARE).ceil() as usize;
+        for centrality in centralities.iter_mut().take(high_risk) {
+            centrality.is_high_risk = centrality.betweenness_centrality > 0.0;
+        }
+        centralities
+    }
+}
+
+/// Unnormalized betweenness of every node of a directed graph
+fn betweenness(outgoing: &[Vec<usize>]) -> Vec<f64> {
+    let n = outgoing.len();
+    let mut betweenness = vec![0.0; n];
+
+    for source in 0..n {
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors = vec![Vec::new(); n];
+        let mut paths = vec![0.0; n];
+        let mut distance = vec![usize::MAX; n];
+        paths[source] = 1.0;
+        distance[source] = 0;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &w in &outgoing[v] {
+                if distance[w] == usize::MAX {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    paths[w] += paths[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; n];
+        while let Some(w) = order.pop() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                betweenness[w] += dependency[w];
+            }
+        }
+    }
+
+    betweenness
+}
+
+/// Closeness of `node` in an undirected graph, scaled by the share of the
+/// `others` nodes it can reach so disconnected tasks score low
+fn closeness(neighbours: &[Vec<usize>], node: usize, others: f64) -> f64 {
+    let mut distance = vec![usize::MAX; neighbours.len()];
+    distance[node] = 0;
+    let mut queue = VecDeque::from([node]);
+    let (mut reachable, mut total_distance) = (0usize, 0usize);
+
+    while let Some(v) = queue.pop_front() {
+        for &w in &neighbours[v] {
+            if distance[w] == usize::MAX {
+                distance[w] = distance[v] + 1;
+                reachable += 1;
+                total_distance += distance[w];
+                queue.push_back(w);
+            }
+        }
+    }
+
+    if total_distance == 0 {
+        return 0.0;
+    }
+    let reachable = reachable as f64;
+    reachable / total_distance as f64 * (reachable / others)
+}
+
+/// Length of the fixed windows completed story points are bucketed into
+pub const VELOCITY_SPRINT_LENGTH_DAYS: i64 = 14;
+/// Number of most recent sprints the velocity forecast looks at
+pub const VELOCITY_FORECAST_SPRINTS: i64 = 3;
+/// Minimum number of those sprints that must have completed work
+pub const MIN_VELOCITY_SAMPLES: usize = 2;
+/// Prefix of the error returned when there are too few velocity samples
+pub const INSUFFICIENT_VELOCITY_DATA: &str = "Insufficient data";
+
+/// When a project's open story points should be done at its recent velocity
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VelocityForecast {
+    /// Story points completed in each recent sprint with completed work, oldest first
+    pub velocity_samples: Vec<f64>,
+    pub remaining_points: f64,
+    pub forecast_completion_sprints: f64,
+    pub forecast_completion_date: DateTime<Utc>,
+    /// Days until completion at average velocity plus and minus one standard deviation
+    #[schema(value_type = Vec<i64>)]
+    pub confidence_interval_days: (i64, i64),
+}
+
+impl VelocityForecast {
+    /// Forecast from a project's tasks
+    ///
+    /// Sprints are the last [`VELOCITY_FORECAST_SPRINTS`] windows of
+    /// [`VELOCITY_SPRINT_LENGTH_DAYS`] ending at `now`, and a sprint's velocity
+    /// is the story points of the tasks completed in it. The pessimistic
+    /// velocity never drops below the slowest sprint observed.
+    pub fn from_tasks(tasks: &[Task], now: DateTime<Utc>) -> TylResult<Self> {
+        let sprint_length = Duration::days(VELOCITY_SPRINT_LENGTH_DAYS);
+        let velocity_samples: Vec<f64> = (0..VELOCITY_FORECAST_SPRINTS).rev()
+            .map(|sprint| {
+                let end = now - sprint_length * sprint as i32;
+                let start = end - sprint_length;
+                tasks.iter()
+                    .filter(|t| t.status == TaskStatus::Done)
+                    .filter(|t| t.completed_at.is_some_and(|at| at > start && at <= end))
+                    .map(Task::story_points)
+                    .sum::<f64>()
+            })
+            .filter(|points| *points > 0.0)
+            .collect();
+
+        if velocity_samples.len() < MIN_VELOCITY_SAMPLES {
+            return Err(TylError::validation(
+                "velocity_samples",
+                format!(
+                    "{}: {} of the last {} sprints have completed story points, {} needed",
+                    INSUFFICIENT_VELOCITY_DATA, velocity_samples.len(), VELOCITY_FORECAST_SPRINTS, MIN_VELOCITY_SAMPLES
+                ),
+            ));
+        }
+
+        let remaining_points: f64 = tasks.iter()
+            .filter(|t| !t.status.is_terminal())
+            .map(Task::story_points)
+            .sum();
+
+        let count = velocity_samples.len() as f64;
+        let average = velocity_samples.iter().sum::<f64>() / count;
+        let std_dev = (velocity_samples.iter().map(|v| (v - average).powi(2)).sum::<f64>() / (count - 1.0)).sqrt();
+        let slowest = velocity_samples.iter().copied().fold(f64::INFINITY, f64::min);
+
+        let days_at = |velocity: f64| (remaining_points / velocity * VELOCITY_SPRINT_LENGTH_DAYS as f64).round() as i64;
+        let forecast_completion_sprints = remaining_points / average;
+
+        Ok(Self {
+            remaining_points,
+            forecast_completion_sprints,
+            forecast_completion_date: now + Duration::days(days_at(average)),
+            confidence_interval_days: (days_at(average + std_dev), days_at((average - std_dev).max(slowest))),
+            velocity_samples,
+        })
+    }
+}
+
+/// Relative error up to which an estimate counts as accurate
+pub const ESTIMATION_ACCURACY_TOLERANCE: f64 = 0.2;
+
+/// How well the estimated durations of completed tasks matched reality
+///
+/// A task's estimate is the time from `started_at` to `estimated_date`, its
+/// actual duration the time from `started_at` to `completed_at`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct EstimationAccuracy {
+    pub total_estimated_tasks: u32,
+    pub mean_absolute_error_days: f64,
+    /// Positive when tasks take longer than estimated, negative when they finish early
+    pub mean_error_days: f64,
+    /// Percentage of tasks finished within [`ESTIMATION_ACCURACY_TOLERANCE`] of their estimate
+    pub accuracy_within_20pct: f64,
+    /// The same percentage per complexity
+    pub by_complexity: HashMap<TaskComplexity, f64>,
+}
+
+impl EstimationAccuracy {
+    /// Accuracy over the completed tasks that were started and had an estimated date
+    /// after their start
+    pub fn from_tasks(tasks: &[Task]) -> Self {
+        let outcomes: Vec<(TaskComplexity, f64, f64)> = tasks.iter()
+            .filter(|t| t.status == TaskStatus::Done)
+            .filter_map(|t| {
+                let started_at = t.started_at?;
+                let estimated_days = (t.estimated_date? - started_at).num_seconds() as f64 / 86_400.0;
+                let actual_days = (t.completed_at? - started_at).num_seconds() as f64 / 86_400.0;
+                (estimated_days > 0.0).then_some((t.complexity, estimated_days, actual_days))
+            })
+            .collect();
+        if outcomes.is_empty() {
+            return Self::default();
+        }
+
+        let count = outcomes.len() as f64;
+        let is_accurate = |estimated: f64, actual: f64| (actual - estimated).abs() <= estimated * ESTIMATION_ACCURACY_TOLERANCE;
+        let mut by_complexity: HashMap<TaskComplexity, (u32, u32)> = HashMap::new();
+        for &(complexity, estimated, actual) in &outcomes {
+            let (accurate, total) = by_complexity.entry(complexity).or_default();
+            *total += 1;
+            if is_accurate(estimated, actual) {
+                *accurate += 1;
+            }
+        }
+
+        Self {
+            total_estimated_tasks: outcomes.len() as u32,
+            mean_absolute_error_days: outcomes.iter().map(|(_, e, a)| (a - e).abs()).sum::<f64>() / count,
+            mean_error_days: outcomes.iter().map(|(_, e, a)| a - e).sum::<f64>() / count,
+            accuracy_within_20pct: outcomes.iter().filter(|(_, e, a)| is_accurate(*e, *a)).count() as f64 / count * 100.0,
+            by_complexity: by_complexity.into_iter()
+                .map(|(complexity, (accurate, total))| (complexity, accurate as f64 / total as f64 * 100.0))
+                .collect(),
+        }
+    }
+}
+
+/// A project's metrics at one point in time, kept as a baseline to compare against
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProjectSnapshot {
+    pub id: String,
+    pub project_id: String,
+    pub label: String,
+    pub snapshot_at: DateTime<Utc>,
+    /// Metric name to value, see [`ProjectSnapshot::project_metrics`]
+    pub metrics: BTreeMap<String, f64>,
+}
+
+impl ProjectSnapshot {
+    pub fn capture(project_id: &str, label: String, metrics: BTreeMap<String, f64>, now: DateTime<Utc>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            label,
+            snapshot_at: now,
+            metrics,
+        }
+    }
+
+    /// Task counts, completion, workload and overdue metrics of a project
+    ///
+    /// `assignees` maps open task ids to the users assigned to them.
+    /// Completion leaves cancelled tasks out; the overdue percentage is
+    /// relative to the open tasks.
+    pub fn project_metrics(tasks: &[Task], assignees: &HashMap<String, Vec<String>>) -> BTreeMap<String, f64> {
+        let count = |status: TaskStatus| tasks.iter().filter(|t| t.status == status).count() as f64;
+        let open: Vec<&Task> = tasks.iter().filter(|t| !t.status.is_terminal()).collect();
+        let completed = count(TaskStatus::Done);
+        let countable = tasks.len() as f64 - count(TaskStatus::Cancelled);
+        let overdue = open.iter().filter(|t| t.is_overdue()).count() as f64;
+
+        let mut open_tasks_per_user: HashMap<&str, u32> = HashMap::new();
+        for user_id in open.iter().filter_map(|t| assignees.get(&t.id)).flatten() {
+            *open_tasks_per_user.entry(user_id.as_str()).or_default() += 1;
+        }
+        let unassigned = open.iter().filter(|t| !assignees.get(&t.id).is_some_and(|users| !users.is_empty())).count() as f64;
+
+        BTreeMap::from([
+            ("total_tasks".to_string(), tasks.len() as f64),
+            ("completed_tasks".to_string(), completed),
+            ("in_progress_tasks".to_string(), count(TaskStatus::InProgress)),
+            ("blocked_tasks".to_string(), count(TaskStatus::Blocked)),
+            ("overdue_tasks".to_string(), overdue),
+            ("completion_percentage".to_string(), if countable > 0.0 { completed / countable * 100.0 } else { 0.0 }),
+            ("assigned_users".to_string(), open_tasks_per_user.len() as f64),
+            ("unassigned_open_tasks".to_string(), unassigned),
+            ("max_open_tasks_per_user".to_string(), open_tasks_per_user.values().copied().max().unwrap_or(0) as f64),
+            ("overdue_percentage".to_string(), if open.is_empty() { 0.0 } else { overdue / open.len() as f64 * 100.0 }),
+        ])
+    }
+}
+
+/// Change of one metric since a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub snapshot_value: f64,
+    pub current_value: f64,
+    /// `current_value - snapshot_value`
+    pub delta: f64,
+}
+
+/// How a project changed since a snapshot was taken
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotDiff {
+    pub snapshot_id: String,
+    pub project_id: String,
+    pub label: String,
+    pub snapshot_at: DateTime<Utc>,
+    pub compared_at: DateTime<Utc>,
+    /// One entry per metric, sorted by name; metrics missing on one side count as 0
+    pub deltas: Vec<MetricDelta>,
+}
+
+impl SnapshotDiff {
+    pub fn compare(snapshot: &ProjectSnapshot, current: &BTreeMap<String, f64>, now: DateTime<Utc>) -> Self {
+        let metrics: BTreeSet<&String> = snapshot.metrics.keys().chain(current.keys()).collect();
+        let deltas = metrics.into_iter()
+            .map(|metric| {
+                let snapshot_value = snapshot.metrics.get(metric).copied().unwrap_or_default();
+                let current_value = current.get(metric).copied().unwrap_or_default();
+                MetricDelta { metric: metric.clone(), snapshot_value, current_value, delta: current_value - snapshot_value }
+            })
+            .collect();
+
+        Self {
+            snapshot_id: snapshot.id.clone(),
+            project_id: snapshot.project_id.clone(),
+            label: snapshot.label.clone(),
+            snapshot_at: snapshot.snapshot_at,
+            compared_at: now,
+            deltas,
+        }
+    }
+}
+
+/// Compliance rate of recurring tasks below which an alert is raised
+pub const RECURRING_COMPLIANCE_ALERT_THRESHOLD: f64 = 0.8;
+
+/// How punctually the instances of one recurring task were completed
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskComplianceRecord {
+    /// The task that started the series
+    pub task_id: String,
+    pub instance_count: u32,
+    pub on_time_rate: f64,
+}
+
+/// Whether recurring task instances were completed by their due date
+///
+/// Instances without a due date, and open instances that are not due yet,
+/// are not counted. Rates are fractions from 0.0 to 1.0.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RecurringTaskCompliance {
+    pub total_instances: u32,
+    pub on_time: u32,
+    pub late: u32,
+    /// Overdue and never completed
+    pub missed: u32,
+    pub compliance_rate: f64,
+    pub by_task: Vec<TaskComplianceRecord>,
+}
+
+impl RecurringTaskCompliance {
+    /// Compliance of `instances`, each paired with the id of the task starting its series
+    pub fn from_instances(instances: &[(String, Task)], now: DateTime<Utc>) -> Self {
+        let mut compliance = Self::default();
+        let mut by_task: HashMap<&str, (u32, u32)> = HashMap::new();
+
+        for (series_id, instance) in instances {
+            let Some(due_date) = instance.due_date else { continue };
+            let on_time = match (instance.status, instance.completed_at) {
+                (TaskStatus::Done, Some(completed_at)) if completed_at <= due_date => {
+                    compliance.on_time += 1;
+                    true
+                }
+                (TaskStatus::Done, Some(_)) => {
+                    compliance.late += 1;
+                    false
+                }
+                (status, _) if !status.is_terminal() && due_date < now => {
+                    compliance.missed += 1;
+                    false
+                }
+                _ => continue,
+            };
+            compliance.total_instances += 1;
+
+            let (count, punctual) = by_task.entry(series_id.as_str()).or_default();
+            *count += 1;
+            if on_time {
+                *punctual += 1;
+            }
+        }
+
+        if compliance.total_instances > 0 {
+            compliance.compliance_rate = compliance.on_time as f64 / compliance.total_instances as f64;
+        }
+        compliance.by_task = by_task.into_iter()
+            .map(|(task_id, (instance_count, on_time))| TaskComplianceRecord {
+                task_id: task_id.to_string(),
+                instance_count,
+                on_time_rate: on_time as f64 / instance_count as f64,
+            })
+            .collect();
+        compliance.by_task.sort_by(|a, b| a.on_time_rate.total_cmp(&b.on_time_rate).then_with(|| a.task_id.cmp(&b.task_id)));
+        compliance
+    }
+
+    /// Whether any instance was counted and the compliance rate is below
+    /// [`RECURRING_COMPLIANCE_ALERT_THRESHOLD`]
+    pub fn needs_alert(&self) -> bool {
+        self.total_instances > 0 && self.compliance_rate < RECURRING_COMPLIANCE_ALERT_THRESHOLD
+    }
+}
+
+/// Days compared by [`FocusScore::week_over_week_change`]
+pub const FOCUS_WEEK_DAYS: i64 = 7;
+/// Week-over-week improvement of the focus score that is worth celebrating
+pub const FOCUS_SCORE_IMPROVEMENT_THRESHOLD: f64 = 0.2;
+
+/// How much a user switched between contexts while completing tasks
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FocusScore {
+    pub user_id: String,
+    pub period_days: u32,
+    pub completed_tasks: u32,
+    /// Context changes between consecutively completed tasks, per day of the period
+    pub context_switches_per_day: f64,
+    /// Days with completions in a single context
+    pub single_context_days: u32,
+    /// Days with completions in more than one context
+    pub multi_context_days: u32,
+    /// Context of most completed tasks, work when nothing was completed
+    pub dominant_context: TaskContext,
+    /// `1 / (1 + context_switches_per_day)`, so 1.0 means no switching at all
+    pub focus_score: f64,
+    pub recommendation: String,
+    /// Relative change of the score of the last week against the week before,
+    /// unset unless tasks were completed in both weeks
+    pub week_over_week_change: Option<f64>,
+}
+
+impl FocusScore {
+    /// Score of the `period_days` ending at `now` from a user's completed tasks
+    pub fn from_completed_tasks(user_id: &str, tasks: &[Task], period_days: u32, now: DateTime<Utc>) -> Self {
+        let mut score = Self::for_window(user_id, tasks, now - Duration::days(period_days as i64), now);
+
+        let week = Duration::days(FOCUS_WEEK_DAYS);
+        let this_week = Self::for_window(user_id, tasks, now - week, now);
+        let last_week = Self::for_window(user_id, tasks, now - week * 2, now - week);
+        if this_week.completed_tasks > 0 && last_week.completed_tasks > 0 {
+            score.week_over_week_change = Some((this_week.focus_score - last_week.focus_score) / last_week.focus_score);
+        }
+        score
+    }
+
+    /// Whether the score improved enough since last week to publish `FocusScoreImproved`
+    pub fn improved_week_over_week(&self) -> bool {
+        self.week_over_week_change.is_some_and(|change| change > FOCUS_SCORE_IMPROVEMENT_THRESHOLD)
+    }
+
+    fn for_window(user_id: &str, tasks: &[Task], since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        let mut completed: Vec<(DateTime<Utc>, TaskContext)> = tasks.iter()
+            .filter(|t| t.status == TaskStatus::Done)
+            .filter_map(|t| Some((t.completed_at?, t.context)))
+            .filter(|(at, _)| *at > since && *at <= until)
+            .collect();
+        completed.sort_by_key(|(at, _)| *at);
+
+        let switches = completed.windows(2).filter(|pair| pair[0].1 != pair[1].1).count();
+        let period_days = (until - since).num_days().max(1) as u32;
+        let context_switches_per_day = switches as f64 / period_days as f64;
+
+        let mut contexts_by_day: HashMap<NaiveDate, Vec<TaskContext>> = HashMap::new();
+        let mut context_counts: Vec<(TaskContext, u32)> = Vec::new();
+        for &(at, context) in &completed {
+            let day = contexts_by_day.entry(at.date_naive()).or_default();
+            if !day.contains(&context) {
+                day.push(context);
+            }
+            match context_counts.iter_mut().find(|(c, _)| *c == context) {
+                Some((_, count)) => *count += 1,
+                None => context_counts.push((context, 1)),
+            }
+        }
+        let single_context_days = contexts_by_day.values().filter(|contexts| contexts.len() == 1).count() as u32;
+        let multi_context_days = contexts_by_day.len() as u32 - single_context_days;
+        // First context to reach the highest count wins ties
+        let dominant_context = context_counts.iter()
+            .fold(None, |best: Option<(TaskContext, u32)>, &(context, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((context, count)),
+            })
+            .map_or(TaskContext::Work, |(context, _)| context);
+
+        let focus_score = 1.0 / (1.0 + context_switches_per_day);
+        let recommendation = if completed.is_empty() {
+            "No completed tasks in this period".to_string()
+        } else if multi_context_days > single_context_days {
+            format!("Most days mix contexts; try batching {:?} tasks together", dominant_context)
+        } else if context_switches_per_day > 1.0 {
+            "Frequent context switches; group tasks by context before starting them".to_string()
+        } else {
+            "Good focus; keep grouping tasks by context".to_string()
+        };
+
+        Self {
+            user_id: user_id.to_string(),
+            period_days,
+            completed_tasks: completed.len() as u32,
+            context_switches_per_day,
+            single_context_days,
+            multi_context_days,
+            dominant_context,
+            focus_score,
+            recommendation,
+            week_over_week_change: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockingPath {
     pub from_task: String,
@@ -72,7 +1157,7 @@ pub struct BlockingPath {
     pub bypass_possible: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DependencyCycle {
     pub cycle_id: String,
     pub tasks_in_cycle: Vec<String>,
@@ -81,7 +1166,7 @@ pub struct DependencyCycle {
     pub suggested_breaks: Vec<DependencyBreakSuggestion>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum CycleSeverity {
     Low,    // Can be resolved easily
     Medium, // Requires planning
@@ -89,7 +1174,7 @@ pub enum CycleSeverity {
     Critical, // Immediate action needed
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DependencyBreakSuggestion {
     pub from_task: String,
     pub to_task: String,
@@ -142,6 +1227,134 @@ pub struct SimilarTask {
     pub lessons_learned: Vec<String>, // Insights from the similar task
 }
 
+/// Task found by full-text search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TaskSearchResult {
+    #[serde(flatten)]
+    pub task: Task,
+    /// Score of the full-text index, higher is more relevant
+    pub relevance_score: f64,
+}
+
+/// Cypher yielding `t` and `score` for the tasks matching `query` in the
+/// full-text index on `Task`, optionally only those of `context`, with its
+/// `$search_query` and `$context` parameters
+pub fn fulltext_search_clause(query: &str, context: Option<&TaskContext>) -> (String, CypherParams) {
+    let mut params = cypher_params([("search_query", json!(query))]);
+    let context_filter = match context {
+        Some(ctx) => {
+            params.insert("context".to_string(), json!(format!("{:?}", ctx).to_lowercase()));
+            " WHERE t.context = $context"
+        }
+        None => "",
+    };
+    let clause = format!(
+        "CALL db.idx.fulltext.queryNodes('Task', $search_query) YIELD node AS t, score{}",
+        context_filter
+    );
+    (clause, params)
+}
+
+/// Default number of dependency hops of a task subgraph
+pub const DEFAULT_SUBGRAPH_DEPTH: u32 = 3;
+
+/// Most dependency hops a task subgraph may span
+pub const MAX_SUBGRAPH_DEPTH: u32 = 10;
+
+/// Most tasks a task subgraph is returned with
+pub const MAX_SUBGRAPH_NODES: usize = 500;
+
+/// Dependency neighbourhood of a task, for rendering it as a graph
+///
+/// Holds the tasks the task depends on and the tasks depending on it, each
+/// up to a number of hops away, and the dependencies on those paths.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskSubgraph {
+    pub nodes: Vec<TaskNode>,
+    pub edges: Vec<TaskEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskNode {
+    pub id: String,
+    pub name: String,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+}
+
+impl From<&Task> for TaskNode {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            status: task.status,
+            priority: task.priority,
+        }
+    }
+}
+
+/// Dependency of `from` on `to`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TaskEdge {
+    pub from: String,
+    pub to: String,
+    pub dependency_type: DependencyType,
+}
+
+/// Cypher for the nodes and for the edges of the subgraph around `task_id`,
+/// following `DEPENDS_ON` up to `depth` hops away from and towards the task,
+/// and the parameters both queries take
+pub fn task_subgraph_queries(task_id: &str, depth: u32) -> (String, String, CypherParams) {
+    let paths = [
+        format!("MATCH path = (t:Task {{id: $task_id}})-[:DEPENDS_ON*0..{}]->(dep:Task)", depth),
+        format!("MATCH path = (t:Task {{id: $task_id}})<-[:DEPENDS_ON*0..{}]-(dep:Task)", depth),
+    ];
+    let nodes = paths.iter()
+        .map(|path| format!(
+            "{} UNWIND nodes(path) AS n \
+             RETURN DISTINCT n.id AS id, n.name AS name, n.status AS status, n.priority AS priority",
+            path
+        ))
+        .collect::<Vec<_>>()
+        .join(" UNION ");
+    let edges = paths.iter()
+        .map(|path| format!(
+            "{} UNWIND relationships(path) AS r \
+             RETURN DISTINCT startNode(r).id AS from, endNode(r).id AS to, r.dependency_type AS dependency_type",
+            path
+        ))
+        .collect::<Vec<_>>()
+        .join(" UNION ");
+    (nodes, edges, cypher_params([("task_id", json!(task_id))]))
+}
+
+/// Subgraph from the rows of the [`task_subgraph_queries`]
+pub fn task_subgraph_from_rows(node_rows: &serde_json::Value, edge_rows: &serde_json::Value) -> TaskSubgraph {
+    let column = |row: &serde_json::Value, name: &str| row.get(name).and_then(|value| value.as_str()).map(String::from);
+    let enum_column = |row: &serde_json::Value, name: &str| row.get(name).and_then(|value| serde_json::from_value(value.clone()).ok());
+    
+    let mut seen = HashSet::new();
+    let nodes = node_rows.as_array().into_iter().flatten()
+        .filter_map(|row| Some(TaskNode {
+            id: column(row, "id")?,
+            name: column(row, "name").unwrap_or_default(),
+            status: enum_column(row, "status").unwrap_or(TaskStatus::Backlog),
+            priority: enum_column(row, "priority").unwrap_or(TaskPriority::Medium),
+        }))
+        .filter(|node| seen.insert(node.id.clone()))
+        .collect();
+    let mut seen = HashSet::new();
+    let edges = edge_rows.as_array().into_iter().flatten()
+        .filter_map(|row| Some(TaskEdge {
+            from: column(row, "from")?,
+            to: column(row, "to")?,
+            dependency_type: enum_column(row, "dependency_type").unwrap_or(DependencyType::Blocks),
+        }))
+        .filter(|edge| seen.insert((edge.from.clone(), edge.to.clone(), edge.dependency_type)))
+        .collect();
+    TaskSubgraph { nodes, edges }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionPrediction {
     pub task_id: String,
@@ -188,13 +1401,85 @@ pub struct Bottleneck {
     pub suggested_actions: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum BottleneckType {
-    SinglePersonDependency, // One person is blocking many tasks
-    ResourceConstraint,     // Limited resources
-    ExternalDependency,     // Waiting on external systems/people
-    ProcessBottleneck,      // Process issue causing delays
-    SkillBottleneck,       // Lack of required skills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BottleneckType {
+    SinglePersonDependency, // One person is blocking many tasks
+    ResourceConstraint,     // Limited resources
+    ExternalDependency,     // Waiting on external systems/people
+    ProcessBottleneck,      // Process issue causing delays
+    SkillBottleneck,       // Lack of required skills
+}
+
+/// Whether one suggested action of a bottleneck has been carried out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BottleneckResolution {
+    pub bottleneck_id: String,
+    pub suggested_action_index: u32,
+    pub resolved: bool,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>,
+}
+
+/// Share of a bottleneck's suggested actions that were carried out
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BottleneckProgress {
+    pub bottleneck_id: String,
+    pub total_actions: u32,
+    pub resolved_actions: u32,
+    pub completion_percentage: f64,
+}
+
+impl BottleneckProgress {
+    /// Progress from the tracked actions of a single bottleneck
+    pub fn from_resolutions(bottleneck_id: &str, resolutions: &[BottleneckResolution]) -> Self {
+        let total_actions = resolutions.len() as u32;
+        let resolved_actions = resolutions.iter().filter(|r| r.resolved).count() as u32;
+        let completion_percentage = if total_actions == 0 {
+            0.0
+        } else {
+            resolved_actions as f64 / total_actions as f64 * 100.0
+        };
+
+        Self {
+            bottleneck_id: bottleneck_id.to_string(),
+            total_actions,
+            resolved_actions,
+            completion_percentage,
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.total_actions > 0 && self.resolved_actions == self.total_actions
+    }
+}
+
+/// Resolution progress of every tracked bottleneck of a project
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResolutionProgress {
+    pub project_id: String,
+    pub bottlenecks: Vec<BottleneckProgress>,
+    /// Resolved actions over all tracked actions of the project
+    pub overall_percentage: f64,
+}
+
+impl ResolutionProgress {
+    pub fn from_resolutions(project_id: &str, resolutions: &[BottleneckResolution]) -> Self {
+        let mut by_bottleneck: std::collections::BTreeMap<&str, Vec<BottleneckResolution>> = Default::default();
+        for resolution in resolutions {
+            by_bottleneck.entry(&resolution.bottleneck_id).or_default().push(resolution.clone());
+        }
+
+        let bottlenecks: Vec<BottleneckProgress> = by_bottleneck.iter()
+            .map(|(id, actions)| BottleneckProgress::from_resolutions(id, actions))
+            .collect();
+        let overall = BottleneckProgress::from_resolutions(project_id, resolutions);
+
+        Self {
+            project_id: project_id.to_string(),
+            bottlenecks,
+            overall_percentage: overall.completion_percentage,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -335,7 +1620,7 @@ pub struct ProjectHealth {
     pub recommended_actions: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum RiskLevel {
     Low,
     Medium,
@@ -385,10 +1670,9 @@ impl GraphTaskQueryService {
     }
     
     /// Build complex Cypher query for dependency chain analysis
-    fn build_dependency_chain_query(&self, task_id: &str) -> String {
-        format!(
-            r#"
-            MATCH path = (start:Task {{id: '{}'}})-[:DEPENDS_ON*1..10]->(dep:Task)
+    fn build_dependency_chain_query(task_id: &str) -> (String, CypherParams) {
+        let query = r#"
+            MATCH path = (start:Task {id: $task_id})-[:DEPENDS_ON*1..10]->(dep:Task)
             WITH path, length(path) as depth
             ORDER BY depth DESC
             WITH collect(path)[0] as longest_path
@@ -398,13 +1682,12 @@ impl GraphTaskQueryService {
                  reduce(total = 0, n IN nodes(longest_path) | 
                    total + coalesce(n.estimated_hours, 0)) as total_hours
             RETURN DISTINCT task_chain, total_hours, length(longest_path) as chain_length
-            "#,
-            task_id.replace('\'', "\\'")
-        )
+            "#;
+        (query.to_string(), cypher_params([("task_id", json!(task_id))]))
     }
     
     /// Build Cypher query for circular dependency detection
-    fn build_circular_dependency_query(&self) -> String {
+    fn build_circular_dependency_query() -> String {
         r#"
         MATCH (t:Task)-[:DEPENDS_ON*1..20]->(t)
         WITH t, 
@@ -417,11 +1700,10 @@ impl GraphTaskQueryService {
     }
     
     /// Build query for task recommendations based on user history and current workload
-    fn build_recommendation_query(&self, user_id: &str) -> String {
-        format!(
-            r#"
+    fn build_recommendation_query(user_id: &str) -> (String, CypherParams) {
+        let query = r#"
             // Find user's skill areas based on completed tasks
-            MATCH (u:User {{id: '{}'}})<-[:ASSIGNED_TO]-(completed:Task {{status: 'done'}})
+            MATCH (u:User {id: $user_id})<-[:ASSIGNED_TO]-(completed:Task {status: 'done'})
             WITH u, collect(DISTINCT completed.context) as user_contexts,
                  avg(completed.complexity_score) as avg_complexity
             
@@ -451,17 +1733,16 @@ impl GraphTaskQueryService {
             RETURN available, (priority_score + urgency_score) as recommendation_score
             ORDER BY recommendation_score DESC
             LIMIT 10
-            "#,
-            user_id.replace('\'', "\\'")
-        )
+            "#;
+        (query.to_string(), cypher_params([("user_id", json!(user_id))]))
     }
 }
 
 #[async_trait]
 impl TaskQueryService for GraphTaskQueryService {
     async fn find_dependency_chain(&self, task_id: &str) -> TylResult<Vec<DependencyPath>> {
-        let query = self.build_dependency_chain_query(task_id);
-        let result = self.adapter.execute_cypher(&query).await?;
+        let (query, params) = Self::build_dependency_chain_query(task_id);
+        let result = self.adapter.execute_cypher_with_params(&query, params).await?;
         
         // In a real implementation, we would parse the Cypher results into DependencyPath structs
         // For now, return a simplified result
@@ -475,18 +1756,15 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn find_blocking_path(&self, from_task: &str, to_task: &str) -> TylResult<Option<BlockingPath>> {
-        let query = format!(
-            r#"
-            MATCH path = shortestPath((from:Task {{id: '{}'}})-[:DEPENDS_ON*]->(to:Task {{id: '{}'}}))
+        let query = r#"
+            MATCH path = shortestPath((from:Task {id: $from_task})-[:DEPENDS_ON*]->(to:Task {id: $to_task}))
             WHERE from.status != 'done' OR to.status != 'done'
             RETURN [n in nodes(path) | n.id] as blocking_path,
                    length(path) as path_length
-            "#,
-            from_task.replace('\'', "\\'"),
-            to_task.replace('\'', "\\'")
-        );
+            "#;
+        let params = cypher_params([("from_task", json!(from_task)), ("to_task", json!(to_task))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse results and return BlockingPath
         // For now, return None (simplified implementation)
@@ -494,7 +1772,7 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn detect_circular_dependencies(&self) -> TylResult<Vec<DependencyCycle>> {
-        let query = self.build_circular_dependency_query();
+        let query = Self::build_circular_dependency_query();
         let _result = self.adapter.execute_cypher(&query).await?;
         
         // Parse results into DependencyCycle structs
@@ -503,9 +1781,8 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn find_critical_path(&self, project_id: &str) -> TylResult<CriticalPath> {
-        let query = format!(
-            r#"
-            MATCH (p:Project {{id: '{}'}})
+        let query = r#"
+            MATCH (p:Project {id: $project_id})
             MATCH (t:Task)-[:BELONGS_TO_PROJECT]->(p)
             
             // Find the longest path through task dependencies
@@ -521,11 +1798,9 @@ impl TaskQueryService for GraphTaskQueryService {
             LIMIT 1
             
             RETURN [n in nodes(path) | n.id] as critical_path_tasks, total_duration
-            "#,
-            project_id.replace('\'', "\\'")
-        );
+            "#;
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, cypher_params([("project_id", json!(project_id))])).await?;
         
         Ok(CriticalPath {
             project_id: project_id.to_string(),
@@ -537,8 +1812,8 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn recommend_next_tasks(&self, user_id: &str, limit: usize) -> TylResult<Vec<TaskRecommendation>> {
-        let query = self.build_recommendation_query(user_id);
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let (query, params) = Self::build_recommendation_query(user_id);
+        let _result = self.adapter.execute_cypher_with_params(&query, params).await?;
         
         // Parse results and create recommendations
         // For now, return empty vector
@@ -548,7 +1823,7 @@ impl TaskQueryService for GraphTaskQueryService {
     async fn find_similar_tasks(&self, task_id: &str, limit: usize) -> TylResult<Vec<SimilarTask>> {
         let query = format!(
             r#"
-            MATCH (target:Task {{id: '{}'}})
+            MATCH (target:Task {{id: $task_id}})
             MATCH (similar:Task)
             WHERE similar.id != target.id
               AND (similar.context = target.context 
@@ -572,11 +1847,10 @@ impl TaskQueryService for GraphTaskQueryService {
             ORDER BY similarity_score DESC
             LIMIT {}
             "#,
-            task_id.replace('\'', "\\'"),
             limit
         );
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(&query, cypher_params([("task_id", json!(task_id))])).await?;
         
         // Parse results into SimilarTask structs
         Ok(vec![])
@@ -584,9 +1858,8 @@ impl TaskQueryService for GraphTaskQueryService {
     
     // Implement remaining methods with similar patterns...
     async fn predict_completion_time(&self, task_id: &str) -> TylResult<CompletionPrediction> {
-        let query = format!(
-            r#"
-            MATCH (target:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (target:Task {id: $task_id})
             OPTIONAL MATCH (similar:Task)
             WHERE similar.context = target.context 
               AND similar.complexity = target.complexity
@@ -602,11 +1875,9 @@ impl TaskQueryService for GraphTaskQueryService {
                    coalesce(avg_completion, target.estimated_days, 5) as predicted_days,
                    coalesce(completion_stdev, 2) as std_deviation,
                    similar_count
-            "#,
-            task_id.replace('\'', "\\'"),
-        );
+            "#;
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, cypher_params([("task_id", json!(task_id))])).await?;
         
         // In a real implementation, we would parse Cypher results
         let now = Utc::now();
@@ -641,7 +1912,7 @@ impl TaskQueryService for GraphTaskQueryService {
     async fn calculate_user_velocity(&self, user_id: &str, days: u32) -> TylResult<UserVelocity> {
         let query = format!(
             r#"
-            MATCH (u:User {{id: '{}'}})  
+            MATCH (u:User {{id: $user_id}})
             MATCH (u)<-[:ASSIGNED_TO]-(t:Task {{status: 'done'}})
             WHERE t.completed_date > datetime() - duration('P{}D')
             
@@ -653,11 +1924,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    collect(DISTINCT t.complexity) as complexities_handled,
                    stdev(completion_days) as completion_variance
             "#,
-            user_id.replace('\'', "\\'"),
             days
         );
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(&query, cypher_params([("user_id", json!(user_id))])).await?;
         
         // Calculate velocity metrics (simplified)
         let tasks_completed = 12u32; // From query results
@@ -680,8 +1950,7 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn analyze_bottlenecks(&self, project_id: Option<&str>) -> TylResult<Vec<Bottleneck>> {
-        let project_filter = project_id.map(|id| format!("AND (t)-[:BELONGS_TO_PROJECT]->(:Project {{id: '{}'}})", id.replace('\'', "\\'")))
-                                      .unwrap_or_else(|| String::new());
+        let project_filter = if project_id.is_some() { "AND (t)-[:BELONGS_TO_PROJECT]->(:Project {id: $project_id})" } else { "" };
         
         let query = format!(
             r#"
@@ -713,7 +1982,8 @@ impl TaskQueryService for GraphTaskQueryService {
             project_filter, project_filter
         );
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let params = cypher_params(project_id.map(|id| ("project_id", json!(id))));
+        let _result = self.adapter.execute_cypher_with_params(&query, params).await?;
         
         // Parse results into bottlenecks
         Ok(vec![
@@ -733,9 +2003,8 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn get_task_impact_analysis(&self, task_id: &str) -> TylResult<TaskImpactAnalysis> {
-        let query = format!(
-            r#"
-            MATCH (target:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (target:Task {id: $task_id})
             
             // Find directly blocked tasks
             OPTIONAL MATCH (target)<-[:DEPENDS_ON]-(direct:Task)
@@ -757,11 +2026,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    count(DISTINCT indirect) as indirect_blocked,
                    collect(DISTINCT p.id) + collect(DISTINCT p2.id) as affected_projects,
                    collect(DISTINCT u.id) + collect(DISTINCT u2.id) as affected_users
-            "#,
-            task_id.replace('\'', "\\'"),
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Calculate impact analysis (simplified)
         let direct_blocked = 3u32;
@@ -791,58 +2059,98 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn semantic_search(&self, query: &str, context: Option<TaskContext>) -> TylResult<Vec<Task>> {
-        let context_filter = context.map(|ctx| format!("AND t.context = '{:?}'", ctx))
-                                   .unwrap_or_else(|| String::new());
-        
+        Ok(self.fulltext_search(query, context, 20).await?
+            .into_iter()
+            .map(|result| result.task)
+            .filter(|task| task.status != TaskStatus::Done)
+            .collect())
+    }
+    
+    async fn fulltext_search(&self, query: &str, context: Option<TaskContext>, limit: usize) -> TylResult<Vec<TaskSearchResult>> {
+        let (search_clause, params) = fulltext_search_clause(query, context.as_ref());
         let search_query = format!(
-            r#"
-            MATCH (t:Task)
-            WHERE (t.name CONTAINS '{}' OR t.description CONTAINS '{}')
-              AND t.status != 'done' {}
-              
-            // Calculate relevance score
-            WITH t,
-                 CASE 
-                   WHEN t.name CONTAINS '{}' THEN 10
-                   ELSE 0
-                 END +
-                 CASE
-                   WHEN t.description CONTAINS '{}' THEN 5 
-                   ELSE 0
-                 END as relevance_score
-                 
-            RETURN t, relevance_score
-            ORDER BY relevance_score DESC, t.created_date DESC
-            LIMIT 20
-            "#,
-            query.replace('\'', "\\'"),
-            query.replace('\'', "\\'"),
-            context_filter,
-            query.replace('\'', "\\'"),
-            query.replace('\'', "\\'"),
+            "{} RETURN t.id AS id, t.name AS name, t.description AS description, t.context AS context, \
+             t.status AS status, t.priority AS priority, t.started_at AS started_at, t.completed_at AS completed_at, score \
+             ORDER BY score DESC LIMIT {}",
+            search_clause,
+            limit
         );
+        let result = self.adapter.execute_cypher_with_params(&search_query, params).await?;
         
-        let _result = self.adapter.execute_cypher(&search_query).await?;
+        Ok(result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let mut task = task_from_row(row)?;
+                    let column = |name: &str| row.get(name).and_then(|value| serde_json::from_value(value.clone()).ok());
+                    task.description = row.get("description").and_then(|value| value.as_str()).map(String::from);
+                    task.status = column("status").unwrap_or(TaskStatus::Backlog);
+                    task.priority = column("priority").unwrap_or(TaskPriority::Medium);
+                    Some(TaskSearchResult {
+                        task,
+                        relevance_score: row.get("score").and_then(|value| value.as_f64()).unwrap_or(0.0),
+                    })
+                })
+                .collect())
+            .unwrap_or_default())
+    }
+    
+    async fn search_tasks_advanced(&self, query: TaskSearchQuery, sort: Vec<SortSpec>, page: PageRequest) -> TylResult<PagedResult<Task>> {
+        validate_search_query(&query, &sort, &page)?;
+        let (condition, params) = query.to_cypher();
         
-        // Parse results into Task structs
-        // For now, return empty vector (would parse Cypher results in real implementation)
-        Ok(vec![])
+        let count_query = format!("MATCH (t:Task) WHERE {} RETURN count(t) AS total", condition);
+        let total = self.adapter.execute_cypher_with_params(&count_query, params.clone()).await?
+            .as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("total"))
+            .and_then(|total| total.as_u64())
+            .unwrap_or(0) as usize;
+        
+        let page_query = format!(
+            "MATCH (t:Task) WHERE {} \
+             RETURN t.id AS id, t.name AS name, t.context AS context, t.description AS description, \
+                    t.status AS status, t.priority AS priority, t.started_at AS started_at, t.completed_at AS completed_at \
+             {} SKIP {} LIMIT {}",
+            condition,
+            search_order_clause(&sort),
+            page.offset(),
+            page.page_size
+        );
+        let result = self.adapter.execute_cypher_with_params(&page_query, params).await?;
+        let items = result.as_array()
+            .map(|rows| rows.iter()
+                .filter_map(|row| {
+                    let mut task = task_from_row(row)?;
+                    let column = |name: &str| row.get(name).and_then(|value| serde_json::from_value(value.clone()).ok());
+                    task.description = row.get("description").and_then(|value| value.as_str()).map(String::from);
+                    task.status = column("status").unwrap_or(TaskStatus::Backlog);
+                    task.priority = column("priority").unwrap_or(TaskPriority::Medium);
+                    Some(task)
+                })
+                .collect())
+            .unwrap_or_default();
+        
+        Ok(PagedResult { items, total, page: page.page, page_size: page.page_size })
     }
     
     async fn find_tasks_by_pattern(&self, pattern: TaskPattern) -> TylResult<Vec<Task>> {
         let mut conditions = Vec::new();
+        let mut params = CypherParams::new();
         
         if let Some(ref name_pattern) = pattern.name_pattern {
-            conditions.push(format!("t.name CONTAINS '{}'", name_pattern.replace('\'', "\\'")));
+            conditions.push("t.name CONTAINS $name_pattern".to_string());
+            params.insert("name_pattern".to_string(), json!(name_pattern));
         }
         
         if let Some(context) = pattern.context {
-            conditions.push(format!("t.context = '{:?}'", context));
+            conditions.push("t.context = $context".to_string());
+            params.insert("context".to_string(), json!(context));
         }
         
         if let Some((min_priority, max_priority)) = pattern.priority_range {
             // Simplified priority comparison (would need proper enum ordering)
-            conditions.push(format!("t.priority IN ['{:?}', '{:?}']", min_priority, max_priority));
+            conditions.push("t.priority IN $priorities".to_string());
+            params.insert("priorities".to_string(), json!([min_priority, max_priority]));
         }
         
         if let Some(has_deps) = pattern.has_dependencies {
@@ -854,19 +2162,14 @@ impl TaskQueryService for GraphTaskQueryService {
         }
         
         if let Some(ref user_pattern) = pattern.assigned_to_pattern {
-            conditions.push(format!("exists((t)<-[:ASSIGNED_TO]-(:User {{id: '{}'}})) OR exists((t)<-[:ASSIGNED_TO]-(u:User WHERE u.name CONTAINS '{}'  OR u.email CONTAINS '{}')); ", 
-                user_pattern.replace('\'', "\\'"), 
-                user_pattern.replace('\'', "\\'"), 
-                user_pattern.replace('\'', "\\'")
-            ));
+            conditions.push("exists((t)<-[:ASSIGNED_TO]-(:User {id: $user_pattern})) OR exists((t)<-[:ASSIGNED_TO]-(u:User WHERE u.name CONTAINS $user_pattern  OR u.email CONTAINS $user_pattern)); ".to_string());
+            params.insert("user_pattern".to_string(), json!(user_pattern));
         }
         
         if let Some((start_date, end_date)) = pattern.created_date_range {
-            conditions.push(format!(
-                "t.created_date >= datetime('{}') AND t.created_date <= datetime('{}')",
-                start_date.format("%Y-%m-%dT%H:%M:%SZ"),
-                end_date.format("%Y-%m-%dT%H:%M:%SZ"),
-            ));
+            conditions.push("t.created_date >= datetime($created_from) AND t.created_date <= datetime($created_to)".to_string());
+            params.insert("created_from".to_string(), json!(start_date.format("%Y-%m-%dT%H:%M:%SZ").to_string()));
+            params.insert("created_to".to_string(), json!(end_date.format("%Y-%m-%dT%H:%M:%SZ").to_string()));
         }
         
         let where_clause = if conditions.is_empty() {
@@ -886,43 +2189,52 @@ impl TaskQueryService for GraphTaskQueryService {
             where_clause
         );
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(&query, params).await?;
         
         // Parse results into Task structs
         Ok(vec![])
     }
     
+    async fn get_task_subgraph(&self, task_id: &str, depth: u32) -> TylResult<TaskSubgraph> {
+        if !(1..=MAX_SUBGRAPH_DEPTH).contains(&depth) {
+            return Err(TylError::validation("depth", format!("Depth must be between 1 and {}", MAX_SUBGRAPH_DEPTH)));
+        }
+        let (nodes_query, edges_query, params) = task_subgraph_queries(task_id, depth);
+        let nodes = self.adapter.execute_cypher_with_params(&nodes_query, params.clone()).await?;
+        let edges = self.adapter.execute_cypher_with_params(&edges_query, params).await?;
+        Ok(task_subgraph_from_rows(&nodes, &edges))
+    }
+    
     async fn get_task_timeline(&self, task_id: &str) -> TylResult<TaskTimeline> {
-        let query = format!(
-            r#"
-            MATCH (t:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (t:Task {id: $task_id})
             OPTIONAL MATCH (t)-[:HAS_EVENT]->(e:TaskEvent)
             
             WITH t, e 
             ORDER BY e.timestamp ASC
             
-            RETURN t,
-                   collect({{
+            RETURN t.id AS id, t.name AS name, t.context AS context,
+                   t.started_at AS started_at, t.completed_at AS completed_at,
+                   collect({
                      event_id: e.id,
                      event_type: e.event_type,
                      timestamp: e.timestamp,
                      actor: e.actor_id,
                      description: e.description,
                      metadata: e.metadata
-                   }}) as events
-            "#,
-            task_id.replace('\'', "\\'"),
-        );
+                   }) as events
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let result = self.adapter.execute_cypher_with_params(query, params).await?;
+        let task = result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(task_from_row);
         
         // Build timeline from events (simplified)
         let now = Utc::now();
         let created_time = now - Duration::days(10);
-        
-        Ok(TaskTimeline {
-            task_id: task_id.to_string(),
-            timeline_events: vec![
+        let timeline_events = vec![
                 TimelineEvent {
                     event_id: uuid::Uuid::new_v4().to_string(),
                     event_type: "created".to_string(),
@@ -939,14 +2251,25 @@ impl TaskQueryService for GraphTaskQueryService {
                     description: "Status changed from Backlog to Ready".to_string(),
                     metadata: HashMap::new(),
                 },
-            ],
+            ];
+        
+        let total_cycle_time = task.as_ref().and_then(Task::calculate_cycle_time);
+        let active_time = task.as_ref().and_then(|task| task.calculate_active_time(&timeline_events));
+        let time_blocked = match (total_cycle_time, active_time) {
+            (Some(total), Some(active)) => total - active,
+            _ => Duration::zero(),
+        };
+        
+        Ok(TaskTimeline {
+            task_id: task_id.to_string(),
+            timeline_events,
             duration_breakdown: DurationBreakdown {
                 time_in_backlog: Duration::days(1),
                 time_in_ready: Duration::days(2),
                 time_in_progress: Duration::days(3),
                 time_in_review: Duration::days(1),
-                time_blocked: Duration::days(0),
-                total_cycle_time: Duration::days(7),
+                time_blocked,
+                total_cycle_time: total_cycle_time.unwrap_or_else(Duration::zero),
             },
             milestone_events: vec![
                 MilestoneEvent {
@@ -1082,14 +2405,13 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn suggest_task_reassignment(&self, task_id: &str) -> TylResult<Vec<ReassignmentSuggestion>> {
-        let query = format!(
-            r#"
-            MATCH (task:Task {{id: '{}'}})
+        let query = r#"
+            MATCH (task:Task {id: $task_id})
             MATCH (u:User)
             WHERE NOT (task)<-[:ASSIGNED_TO]-(u) // Exclude current assignee
             
             // Find users who have worked on similar tasks
-            OPTIONAL MATCH (u)<-[:ASSIGNED_TO]-(similar:Task {{status: 'done'}})
+            OPTIONAL MATCH (u)<-[:ASSIGNED_TO]-(similar:Task {status: 'done'})
             WHERE similar.context = task.context OR similar.complexity = task.complexity
             
             // Calculate current workload
@@ -1128,11 +2450,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    current_workload
             ORDER BY suitability_score DESC
             LIMIT 5
-            "#,
-            task_id.replace('\'', "\\'"),
-        );
+            "#;
+        let params = cypher_params([("task_id", json!(task_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse results into reassignment suggestions
         Ok(vec![
@@ -1163,9 +2484,8 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn find_collaboration_patterns(&self, user_id: &str) -> TylResult<CollaborationPatterns> {
-        let query = format!(
-            r#"
-            MATCH (user:User {{id: '{}'}})
+        let query = r#"
+            MATCH (user:User {id: $user_id})
             
             // Find tasks where this user collaborated with others
             MATCH (user)<-[:ASSIGNED_TO|:REVIEWER|:COLLABORATOR]-(t:Task)-[:ASSIGNED_TO|:REVIEWER|:COLLABORATOR]->(collaborator:User)
@@ -1200,11 +2520,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    collaborators,
                    avg_effectiveness,
                    size(collaborators) as collaboration_count
-            "#,
-            user_id.replace('\'', "\\'"),
-        );
+            "#;
+        let params = cypher_params([("user_id", json!(user_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse results into collaboration patterns
         Ok(CollaborationPatterns {
@@ -1234,10 +2553,9 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn identify_knowledge_experts(&self, domain: &str) -> TylResult<Vec<KnowledgeExpert>> {
-        let query = format!(
-            r#"
-            MATCH (u:User)<-[:ASSIGNED_TO]-(t:Task {{status: 'done'}})
-            WHERE t.context CONTAINS '{}' OR t.name CONTAINS '{}' OR t.description CONTAINS '{}'
+        let query = r#"
+            MATCH (u:User)<-[:ASSIGNED_TO]-(t:Task {status: 'done'})
+            WHERE t.context CONTAINS $domain OR t.name CONTAINS $domain OR t.description CONTAINS $domain
             
             // Calculate expertise metrics
             WITH u, 
@@ -1273,7 +2591,7 @@ impl TaskQueryService for GraphTaskQueryService {
             
             // Calculate mentorship potential based on collaboration and quality
             OPTIONAL MATCH (u)<-[:ASSIGNED_TO|:REVIEWER]-(mentor_task:Task)-[:ASSIGNED_TO|:COLLABORATOR]->(mentee:User)
-            WHERE mentor_task.context CONTAINS '{}'
+            WHERE mentor_task.context CONTAINS $domain
             
             WITH u, expertise_score, success_rate, tasks_completed,
                  count(DISTINCT mentee) as mentees_worked_with,
@@ -1286,14 +2604,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    mentorship_potential
             ORDER BY expertise_score DESC
             LIMIT 10
-            "#,
-            domain.replace('\'', "\\'"),
-            domain.replace('\'', "\\'"),
-            domain.replace('\'', "\\'"),
-            domain.replace('\'', "\\'"),
-        );
+            "#;
+        let params = cypher_params([("domain", json!(domain))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse results into knowledge experts
         Ok(vec![
@@ -1325,9 +2639,8 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn get_project_health_metrics(&self, project_id: &str) -> TylResult<ProjectHealth> {
-        let query = format!(
-            r#"
-            MATCH (p:Project {{id: '{}'}})<-[:BELONGS_TO_PROJECT]-(t:Task)
+        let query = r#"
+            MATCH (p:Project {id: $project_id})<-[:BELONGS_TO_PROJECT]-(t:Task)
             
             WITH p,
                  count(t) as total_tasks,
@@ -1372,11 +2685,10 @@ impl TaskQueryService for GraphTaskQueryService {
                    overdue_tasks,
                    blocked_tasks,
                    avg_task_age
-            "#,
-            project_id.replace('\'', "\\'"),
-        );
+            "#;
+        let params = cypher_params([("project_id", json!(project_id))]);
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(query, params).await?;
         
         // Parse results and build project health
         let health_score = 0.78; // Parsed from query results
@@ -1427,15 +2739,12 @@ impl TaskQueryService for GraphTaskQueryService {
     }
     
     async fn calculate_team_productivity(&self, team_ids: Vec<String>, period_days: u32) -> TylResult<TeamProductivity> {
-        let team_filter = team_ids.iter()
-            .map(|id| format!("'{}'" , id.replace('\'', "\\'")))
-            .collect::<Vec<_>>()
-            .join(", ");
+        let params = cypher_params([("team_ids", json!(team_ids))]);
         
         let query = format!(
             r#"
             MATCH (u:User)<-[:ASSIGNED_TO]-(t:Task {{status: 'done'}})
-            WHERE u.team_id IN [{}] 
+            WHERE u.team_id IN $team_ids 
               AND t.completed_date > datetime() - duration('P{}D')
               
             WITH collect(DISTINCT u.id) as team_members,
@@ -1484,17 +2793,32 @@ impl TaskQueryService for GraphTaskQueryService {
                    process_adherence,
                    productivity_score
             "#,
-            team_filter, period_days, period_days
+            period_days, period_days
         );
         
-        let _result = self.adapter.execute_cypher(&query).await?;
+        let _result = self.adapter.execute_cypher_with_params(&query, params.clone()).await?;
+        
+        // Cycle times come from the tasks themselves rather than the aggregate above
+        let completed_query = "MATCH (u:User)<-[:ASSIGNED_TO]-(t:Task {status: 'done'}) 
+             WHERE u.team_id IN $team_ids AND t.completed_at > $completed_after 
+             RETURN DISTINCT t.id AS id, t.name AS name, t.context AS context, 
+                    t.started_at AS started_at, t.completed_at AS completed_at";
+        let mut completed_params = params;
+        completed_params.insert(
+            "completed_after".to_string(),
+            json!((Utc::now() - Duration::days(period_days as i64)).to_rfc3339()),
+        );
+        let completed = self.adapter.execute_cypher_with_params(completed_query, completed_params).await?;
+        let completed_tasks: Vec<Task> = completed.as_array()
+            .map(|rows| rows.iter().filter_map(task_from_row).collect())
+            .unwrap_or_default();
         
         // Parse results and build team productivity
         Ok(TeamProductivity {
             team_ids,
             period_days,
             total_tasks_completed: 85,
-            average_cycle_time: Duration::days(4),
+            average_cycle_time: average_cycle_time(&completed_tasks).unwrap_or_else(Duration::zero),
             productivity_score: 0.82,
             productivity_trend: VelocityTrend::Improving,
             efficiency_metrics: EfficiencyMetrics {
@@ -1507,24 +2831,115 @@ impl TaskQueryService for GraphTaskQueryService {
     }
 }
 
+/// Mean cycle time of the tasks that have one
+pub fn average_cycle_time(tasks: &[Task]) -> Option<Duration> {
+    let cycle_times: Vec<Duration> = tasks.iter().filter_map(Task::calculate_cycle_time).collect();
+    if cycle_times.is_empty() {
+        return None;
+    }
+    
+    let total_seconds: i64 = cycle_times.iter().map(Duration::num_seconds).sum();
+    Some(Duration::seconds(total_seconds / cycle_times.len() as i64))
+}
+
+/// Task carrying just the fields cycle time needs, read from the `id`, `name`,
+/// `context`, `started_at` and `completed_at` columns of a row
+fn task_from_row(row: &serde_json::Value) -> Option<Task> {
+    let date = |column: &str| row.get(column)
+        .and_then(|value| value.as_str())
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| value.with_timezone(&Utc));
+    
+    let context = row.get("context")
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or(TaskContext::Work);
+    let mut task = Task::new(
+        row.get("id")?.as_str()?.to_string(),
+        row.get("name").and_then(|value| value.as_str()).unwrap_or_default().to_string(),
+        context,
+    );
+    task.started_at = date("started_at");
+    task.completed_at = date("completed_at");
+    Some(task)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     
     #[test]
     fn test_cypher_query_building() {
-        // Test query string building without requiring a real adapter
-        let expected_patterns = vec!["TEST-001", "DEPENDS_ON"];
-        for pattern in expected_patterns {
-            // These would be tested with a real query builder
-            assert!(pattern.len() > 0);
-        }
+        let (query, params) = GraphTaskQueryService::build_dependency_chain_query("TEST-001' OR 1=1");
+        assert!(query.contains("(start:Task {id: $task_id})-[:DEPENDS_ON*1..10]->(dep:Task)"));
+        assert!(!query.contains("TEST-001"));
+        assert_eq!(params["task_id"], "TEST-001' OR 1=1");
         
-        // Test that circular dependency detection logic exists
-        let circular_patterns = vec!["DEPENDS_ON*", "cycle_length"];
-        for pattern in circular_patterns {
-            assert!(pattern.len() > 0);
-        }
+        let (query, params) = GraphTaskQueryService::build_recommendation_query("user-1");
+        assert!(query.contains("(u:User {id: $user_id})"));
+        assert_eq!(params["user_id"], "user-1");
+        
+        let query = GraphTaskQueryService::build_circular_dependency_query();
+        assert!(query.contains("DEPENDS_ON*"));
+        assert!(query.contains("cycle_length"));
+    }
+    
+    #[test]
+    fn test_task_subgraph_queries_and_rows() {
+        let (nodes_query, edges_query, params) = task_subgraph_queries("T'1", 3);
+        assert!(nodes_query.contains("(t:Task {id: $task_id})-[:DEPENDS_ON*0..3]->(dep:Task)"));
+        assert!(nodes_query.contains("(t:Task {id: $task_id})<-[:DEPENDS_ON*0..3]-(dep:Task)"));
+        assert_eq!(params["task_id"], "T'1");
+        assert!(edges_query.contains("UNWIND relationships(path) AS r"));
+        
+        let nodes = serde_json::json!([
+            {"id": "T1", "name": "Root", "status": "in_progress", "priority": "high"},
+            {"id": "T2", "name": "Prerequisite", "status": "done", "priority": "low"},
+            {"id": "T1", "name": "Root", "status": "in_progress", "priority": "high"},
+        ]);
+        let edges = serde_json::json!([
+            {"from": "T1", "to": "T2", "dependency_type": "requires"},
+            {"from": "T1", "to": "T2", "dependency_type": "requires"},
+        ]);
+        let subgraph = task_subgraph_from_rows(&nodes, &edges);
+        assert_eq!(subgraph.nodes.len(), 2);
+        assert_eq!(subgraph.nodes[0].status, TaskStatus::InProgress);
+        assert_eq!(subgraph.edges, vec![TaskEdge {
+            from: "T1".to_string(),
+            to: "T2".to_string(),
+            dependency_type: DependencyType::Requires,
+        }]);
+    }
+    
+    #[test]
+    fn test_stale_dependency_detection() {
+        let now = Utc::now();
+        let task = |id: &str, status: TaskStatus, idle_days: i64| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.status = status;
+            task.updated_at = now - Duration::days(idle_days);
+            task
+        };
+        let dependency = |age_days: i64| {
+            let mut dependency = TaskDependency::new("A".to_string(), "B".to_string(), DependencyType::Blocks);
+            dependency.created_at = now - Duration::days(age_days);
+            dependency
+        };
+
+        let cancelled = StaleDependency::evaluate(&dependency(5), &task("A", TaskStatus::Blocked, 0), &task("B", TaskStatus::Cancelled, 0), now).unwrap();
+        assert_eq!(cancelled.suggested_action, StaleDependencyAction::Remove);
+        assert_eq!(cancelled.age_days, 5);
+
+        let idle = StaleDependency::evaluate(&dependency(120), &task("A", TaskStatus::Blocked, 0), &task("B", TaskStatus::InProgress, 45), now).unwrap();
+        assert_eq!(idle.suggested_action, StaleDependencyAction::Update);
+
+        assert!(StaleDependency::evaluate(&dependency(120), &task("A", TaskStatus::Blocked, 0), &task("B", TaskStatus::InProgress, 10), now).is_none());
+        assert!(StaleDependency::evaluate(&dependency(60), &task("A", TaskStatus::Blocked, 0), &task("B", TaskStatus::InProgress, 45), now).is_none());
+
+        let report = DependencyAgeReport::from_dependencies(&[
+            (dependency(5), task("A", TaskStatus::Cancelled, 0), task("B", TaskStatus::Ready, 0)),
+            (dependency(120), task("A", TaskStatus::Blocked, 0), task("B", TaskStatus::Ready, 45)),
+        ], now);
+        assert_eq!(report.stale_dependencies.iter().map(|d| d.age_days).collect::<Vec<_>>(), vec![120, 5]);
     }
     
     #[test]
@@ -1540,4 +2955,417 @@ mod tests {
         assert_eq!(cycle.cycle_length, 3);
         matches!(cycle.severity, CycleSeverity::Medium);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_wip_violation_recommends_lowest_priority_tasks() {
+        let tasks = vec!["WISH-1".to_string(), "LOW-1".to_string(), "HIGH-1".to_string()];
+        
+        let violation = WipViolation::from_usage(TaskStatus::InProgress, 1, tasks.clone()).unwrap();
+        
+        assert_eq!(violation.current_count, 3);
+        assert_eq!(violation.overflow, 2);
+        assert!(violation.recommendation.contains("WISH-1, LOW-1"));
+        assert!(!violation.recommendation.contains("HIGH-1"));
+        assert!(WipViolation::from_usage(TaskStatus::InProgress, 3, tasks).is_none());
+    }
+
+    #[test]
+    fn test_resolution_progress_per_bottleneck() {
+        let resolution = |bottleneck_id: &str, index: u32, resolved: bool| BottleneckResolution {
+            bottleneck_id: bottleneck_id.to_string(),
+            suggested_action_index: index,
+            resolved,
+            resolved_at: None,
+            resolved_by: None,
+        };
+        let resolutions = vec![
+            resolution("B-2", 0, true),
+            resolution("B-1", 0, true),
+            resolution("B-1", 1, false),
+            resolution("B-2", 1, true),
+        ];
+
+        let progress = ResolutionProgress::from_resolutions("P-1", &resolutions);
+
+        assert_eq!(progress.bottlenecks.len(), 2);
+        assert_eq!(progress.bottlenecks[0].bottleneck_id, "B-1");
+        assert_eq!(progress.bottlenecks[0].completion_percentage, 50.0);
+        assert!(progress.bottlenecks[1].is_resolved());
+        assert_eq!(progress.overall_percentage, 75.0);
+        assert_eq!(ResolutionProgress::from_resolutions("P-2", &[]).overall_percentage, 0.0);
+    }
+
+    #[test]
+    fn test_average_cycle_time_skips_unfinished_tasks() {
+        let start = Utc::now() - Duration::days(20);
+        let task = |id: &str, cycle_days: Option<i64>| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.started_at = Some(start);
+            task.completed_at = cycle_days.map(|days| start + Duration::days(days));
+            task
+        };
+
+        let tasks = vec![task("A", Some(2)), task("B", Some(6)), task("C", None)];
+
+        assert_eq!(average_cycle_time(&tasks), Some(Duration::days(4)));
+        assert_eq!(average_cycle_time(&tasks[2..]), None);
+    }
+
+    #[test]
+    fn test_throughput_metrics_fill_empty_days() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let completions = vec![(day(2), 4), (day(5), 1), (day(8), 3), (day(20), 9)];
+
+        let metrics = ThroughputMetrics::from_completions("P-1", 8, day(8), &completions);
+
+        assert_eq!(metrics.daily_throughput.len(), 8);
+        assert_eq!(metrics.daily_throughput[0], (day(1), 0));
+        assert_eq!(metrics.peak_day, (day(2), 4));
+        assert_eq!(metrics.low_day, (day(1), 0));
+        assert_eq!(metrics.rolling_7d_avg, 8.0 / 7.0);
+        assert_eq!(metrics.rolling_30d_avg, 1.0);
+        assert!(metrics.upper_control_limit > 4.0);
+        assert_eq!(metrics.lower_control_limit, 0.0);
+    }
+
+    #[test]
+    fn test_burndown_chart_from_daily_counts() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let created = vec![(day(1), 4), (day(4), 2)];
+        let completed = vec![(day(2), 1), (day(3), 1), (day(5), 2)];
+
+        let chart = BurndownChart::from_daily_counts("P-1", day(3), day(7), day(5), &created, &completed);
+
+        let remaining: Vec<u32> = chart.data_points.iter().map(|point| point.remaining_tasks).collect();
+        assert_eq!(remaining, vec![2, 4, 2]);
+        assert_eq!(chart.data_points[1].added_tasks, 2);
+        assert_eq!(chart.data_points[2].completed_tasks, 2);
+        let ideal: Vec<u32> = chart.ideal_line.iter().map(|point| point.remaining_tasks).collect();
+        assert_eq!(ideal, vec![3, 2, 2, 1, 0]);
+        assert_eq!(chart.ideal_line[4].completed_tasks, 3);
+    }
+
+    #[test]
+    fn test_velocity_forecast_from_recent_sprints() {
+        let now = Utc::now();
+        let task = |id: &str, complexity: TaskComplexity, completed_days_ago: Option<i64>| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.complexity = complexity;
+            if let Some(days) = completed_days_ago {
+                task.status = TaskStatus::Done;
+                task.completed_at = Some(now - Duration::days(days));
+            }
+            task
+        };
+
+        // Sprints of 8, 5 and 3 points, oldest first; the 60 day old task is out of range
+        let mut tasks = vec![
+            task("A", TaskComplexity::VeryComplex, Some(35)),
+            task("B", TaskComplexity::Complex, Some(20)),
+            task("C", TaskComplexity::Medium, Some(2)),
+            task("D", TaskComplexity::VeryComplex, Some(60)),
+            task("E", TaskComplexity::Complex, None),
+            task("F", TaskComplexity::VeryComplex, None),
+            task("G", TaskComplexity::Trivial, None),
+        ];
+        tasks[6].custom_properties.insert("story_points".to_string(), serde_json::json!(3.0));
+
+        let forecast = VelocityForecast::from_tasks(&tasks, now).unwrap();
+
+        assert_eq!(forecast.velocity_samples, vec![8.0, 5.0, 3.0]);
+        assert_eq!(forecast.remaining_points, 16.0);
+        assert!((forecast.forecast_completion_sprints - 3.0).abs() < 1e-9);
+        assert_eq!(forecast.forecast_completion_date, now + Duration::days(42));
+        let (optimistic, pessimistic) = forecast.confidence_interval_days;
+        assert!(optimistic < 42 && pessimistic > 42);
+        // Velocity minus one standard deviation is below the slowest sprint
+        assert_eq!(pessimistic, (16.0_f64 / 3.0 * 14.0).round() as i64);
+
+        let error = VelocityForecast::from_tasks(&tasks[2..], now).unwrap_err();
+        assert!(error.to_string().contains(INSUFFICIENT_VELOCITY_DATA));
+    }
+
+    #[test]
+    fn test_task_centrality_of_dependency_chain() {
+        let ids: Vec<String> = ["A", "B", "C", "D"].iter().map(|id| id.to_string()).collect();
+        let dependencies = vec![
+            TaskDependency::new("A".to_string(), "B".to_string(), DependencyType::Blocks),
+            TaskDependency::new("B".to_string(), "C".to_string(), DependencyType::Blocks),
+            TaskDependency::new("B".to_string(), "X".to_string(), DependencyType::Blocks),
+        ];
+
+        let centralities = TaskCentrality::from_dependencies(&ids, &dependencies);
+        let of = |id: &str| centralities.iter().find(|c| c.task_id == id).unwrap();
+
+        // Only A -> C runs through B, out of 3 * 2 ordered pairs of other tasks
+        assert_eq!(centralities[0].task_id, "B");
+        assert!((of("B").betweenness_centrality - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(of("A").betweenness_centrality, 0.0);
+        assert!((of("B").degree_centrality - 2.0 / 3.0).abs() < 1e-9);
+        // B reaches 2 of 3 other tasks at distance 1
+        assert!((of("B").closeness_centrality - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(of("D").closeness_centrality, 0.0);
+
+        assert!(of("B").is_high_risk);
+        assert_eq!(centralities.iter().filter(|c| c.is_high_risk).count(), 1);
+    }
+
+    #[test]
+    fn test_cross_project_dependency_risk() {
+        let now = Utc::now();
+        let dependency = TaskDependency::new("A".to_string(), "B".to_string(), DependencyType::Blocks);
+        let from = Task::new("A".to_string(), "A".to_string(), TaskContext::Work);
+        let risk = |due_in_days: Option<i64>, status: TaskStatus| {
+            let mut to = Task::new("B".to_string(), "B".to_string(), TaskContext::Work);
+            to.status = status;
+            to.due_date = due_in_days.map(|days| now + Duration::days(days));
+            CrossProjectDependency::new(&dependency, (&from, "P1".to_string()), (&to, "P2".to_string()), now).risk_level
+        };
+
+        assert!(matches!(risk(Some(-1), TaskStatus::InProgress), RiskLevel::High));
+        assert!(matches!(risk(Some(3), TaskStatus::InProgress), RiskLevel::Medium));
+        assert!(matches!(risk(Some(30), TaskStatus::InProgress), RiskLevel::Low));
+        assert!(matches!(risk(None, TaskStatus::InProgress), RiskLevel::Low));
+        assert!(matches!(risk(Some(-1), TaskStatus::Done), RiskLevel::Low));
+    }
+
+    #[test]
+    fn test_task_health_score() {
+        let now = Utc::now();
+        let mut task = Task::new("T".to_string(), "Task".to_string(), TaskContext::Work);
+        let mut done = Task::new("D".to_string(), "Done".to_string(), TaskContext::Work);
+        done.status = TaskStatus::Done;
+        let open = Task::new("O".to_string(), "Open".to_string(), TaskContext::Work);
+
+        let healthy = TaskHealthScore::evaluate(&task, 100.0, &[done.clone()], 1, now);
+        assert!((healthy.score - 1.0).abs() < 1e-9);
+        assert!(matches!(healthy.risk_level, RiskLevel::Low));
+        assert_eq!(healthy.breakdown.len(), 5);
+        assert!((healthy.breakdown.iter().map(|(_, _, weight)| weight).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // Two weeks overdue, half the prerequisites open, nobody assigned, a month idle
+        task.due_date = Some(now - Duration::days(OVERDUE_HEALTH_DAYS));
+        task.updated_at = now - Duration::days(STALE_TASK_DAYS);
+        let unhealthy = TaskHealthScore::evaluate(&task, 50.0, &[done, open], 0, now);
+        assert!((unhealthy.score - (0.3 * 0.5 + 0.2 * 0.5)).abs() < 1e-9);
+        assert!(matches!(unhealthy.risk_level, RiskLevel::Critical));
+    }
+
+    #[test]
+    fn test_estimation_accuracy() {
+        let now = Utc::now();
+        let estimated = |complexity: TaskComplexity, estimated_days: i64, actual_days: i64| {
+            let mut task = Task::new("T".to_string(), "Task".to_string(), TaskContext::Work);
+            task.status = TaskStatus::Done;
+            task.complexity = complexity;
+            task.started_at = Some(now - Duration::days(30));
+            task.estimated_date = Some(now - Duration::days(30 - estimated_days));
+            task.completed_at = Some(now - Duration::days(30 - actual_days));
+            task
+        };
+        let mut unstarted = estimated(TaskComplexity::Simple, 2, 2);
+        unstarted.started_at = None;
+
+        let accuracy = EstimationAccuracy::from_tasks(&[
+            estimated(TaskComplexity::Simple, 10, 11),
+            estimated(TaskComplexity::Complex, 4, 8),
+            estimated(TaskComplexity::Complex, 10, 7),
+            unstarted,
+        ]);
+
+        assert_eq!(accuracy.total_estimated_tasks, 3);
+        assert!((accuracy.mean_absolute_error_days - 8.0 / 3.0).abs() < 1e-9);
+        assert!((accuracy.mean_error_days - 2.0 / 3.0).abs() < 1e-9);
+        assert!((accuracy.accuracy_within_20pct - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(accuracy.by_complexity[&TaskComplexity::Simple], 100.0);
+        assert_eq!(accuracy.by_complexity[&TaskComplexity::Complex], 0.0);
+
+        assert_eq!(EstimationAccuracy::from_tasks(&[]).total_estimated_tasks, 0);
+    }
+
+    #[test]
+    fn test_project_snapshot_diff() {
+        let now = Utc::now();
+        let task = |id: &str, status: TaskStatus| {
+            let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+            task.status = status;
+            task
+        };
+        let mut overdue = task("T3", TaskStatus::InProgress);
+        overdue.due_date = Some(now - Duration::days(1));
+        let mut tasks = vec![task("T1", TaskStatus::Done), task("T2", TaskStatus::Backlog), overdue, task("T4", TaskStatus::Cancelled)];
+        let assignees = HashMap::from([
+            ("T2".to_string(), vec!["user-1".to_string()]),
+            ("T3".to_string(), vec!["user-1".to_string(), "user-2".to_string()]),
+        ]);
+
+        let metrics = ProjectSnapshot::project_metrics(&tasks, &assignees);
+        assert_eq!(metrics["total_tasks"], 4.0);
+        assert!((metrics["completion_percentage"] - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(metrics["assigned_users"], 2.0);
+        assert_eq!(metrics["max_open_tasks_per_user"], 2.0);
+        assert_eq!(metrics["unassigned_open_tasks"], 0.0);
+        assert_eq!(metrics["overdue_percentage"], 50.0);
+
+        let snapshot = ProjectSnapshot::capture("PROJ1", "Sprint 1".to_string(), metrics, now);
+        tasks[1].status = TaskStatus::Done;
+        let diff = SnapshotDiff::compare(&snapshot, &ProjectSnapshot::project_metrics(&tasks, &HashMap::new()), now);
+
+        assert_eq!(diff.label, "Sprint 1");
+        assert_eq!(diff.deltas.len(), snapshot.metrics.len());
+        let delta = |metric: &str| diff.deltas.iter().find(|d| d.metric == metric).unwrap().delta;
+        assert_eq!(delta("completed_tasks"), 1.0);
+        assert_eq!(delta("assigned_users"), -2.0);
+        assert_eq!(delta("total_tasks"), 0.0);
+    }
+
+    #[test]
+    fn test_standup_report() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let at = |day: u32| NaiveDate::from_ymd_opt(2025, 6, day).unwrap().and_hms_opt(10, 0, 0).unwrap().and_utc();
+        let task = |id: &str, status: TaskStatus, priority: TaskPriority| {
+            let mut task = Task::new(id.to_string(), format!("Task {}", id), TaskContext::Work);
+            task.status = status;
+            task.priority = priority;
+            task.updated_at = at(12);
+            task
+        };
+        let mut done_yesterday = task("T1", TaskStatus::Done, TaskPriority::Medium);
+        done_yesterday.completed_at = Some(at(14));
+        let mut done_earlier = task("T2", TaskStatus::Done, TaskPriority::Medium);
+        done_earlier.completed_at = Some(at(10));
+        let tasks = vec![
+            done_yesterday,
+            done_earlier,
+            task("T3", TaskStatus::InProgress, TaskPriority::Low),
+            task("T4", TaskStatus::Ready, TaskPriority::Low),
+            task("T5", TaskStatus::Ready, TaskPriority::Critical),
+            task("T6", TaskStatus::Blocked, TaskPriority::High),
+        ];
+        let blocked_by = HashMap::from([("T6".to_string(), vec!["T9".to_string()])]);
+
+        let report = StandupReport::build("user-1", date, &tasks, &blocked_by);
+
+        let ids = |summaries: &[TaskSummary]| summaries.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&report.completed_yesterday), vec!["T1"]);
+        assert_eq!(ids(&report.in_progress_today), vec!["T3"]);
+        assert_eq!(ids(&report.planned_today), vec!["T5", "T4"]);
+        assert_eq!(report.blockers[0].blocked_by, vec!["T9"]);
+        assert_eq!(report.blockers[0].days_blocked, 3);
+        assert_eq!(
+            report.formatted_text,
+            "*Standup for user-1 on 2025-06-15*\n\n\
+             *Yesterday*\n- Task T1 (T1)\n\n\
+             *Today*\n- In progress: Task T3 (T3)\n- Planned: Task T5 (T5)\n- Planned: Task T4 (T4)\n\n\
+             *Blockers*\n- Task T6 (T6), waiting on T9 for 3 day(s)"
+        );
+    }
+
+    #[test]
+    fn test_fulltext_search_clause() {
+        let (clause, params) = fulltext_search_clause("login bug", None);
+        assert_eq!(clause, "CALL db.idx.fulltext.queryNodes('Task', $search_query) YIELD node AS t, score");
+        assert_eq!(params, cypher_params([("search_query", json!("login bug"))]));
+
+        let (clause, params) = fulltext_search_clause("o'clock", Some(&TaskContext::Work));
+        assert_eq!(
+            clause,
+            "CALL db.idx.fulltext.queryNodes('Task', $search_query) YIELD node AS t, score WHERE t.context = $context"
+        );
+        assert_eq!(params["search_query"], "o'clock");
+        assert_eq!(params["context"], "work");
+    }
+
+    #[test]
+    fn test_review_queue_item() {
+        let now = Utc::now();
+        let link = |url: &str| ExternalTicketLink::new("T1".to_string(), crate::domain::ExternalSystem::GitHub, url.to_string(), url.to_string());
+        let links = vec![
+            link("https://github.com/acme/api/pull/42"),
+            link("https://github.com/acme/api/issues/7"),
+        ];
+        let task = Task::new("T1".to_string(), "Review me".to_string(), TaskContext::Work);
+
+        let item = ReviewQueueItem::new(task.clone(), "user-1".to_string(), now - Duration::hours(50), links, Duration::hours(48), now);
+        assert_eq!(item.time_in_review, Duration::hours(50));
+        assert!(item.is_stale_review);
+        assert_eq!(item.external_pr_links.len(), 1);
+        assert_eq!(item.external_pr_links[0].external_id, "https://github.com/acme/api/pull/42");
+
+        let fresh = ReviewQueueItem::new(task, String::new(), now - Duration::hours(2), vec![], Duration::hours(48), now);
+        assert!(!fresh.is_stale_review);
+    }
+
+    #[test]
+    fn test_recurring_task_compliance() {
+        let now = Utc::now();
+        let instance = |series: &str, status: TaskStatus, due_days_ago: i64, completed_days_ago: Option<i64>| {
+            let mut task = Task::new(format!("{}-{}", series, due_days_ago), "Standup notes".to_string(), TaskContext::Work);
+            task.status = status;
+            task.due_date = Some(now - Duration::days(due_days_ago));
+            task.completed_at = completed_days_ago.map(|days| now - Duration::days(days));
+            (series.to_string(), task)
+        };
+        let mut undated = instance("weekly", TaskStatus::Done, 0, Some(0));
+        undated.1.due_date = None;
+
+        let compliance = RecurringTaskCompliance::from_instances(&[
+            instance("weekly", TaskStatus::Done, 14, Some(15)),
+            instance("weekly", TaskStatus::Done, 7, Some(5)),
+            instance("weekly", TaskStatus::Backlog, -7, None),
+            instance("daily", TaskStatus::Done, 2, Some(2)),
+            instance("daily", TaskStatus::InProgress, 1, None),
+            instance("daily", TaskStatus::Cancelled, 3, None),
+            undated,
+        ], now);
+
+        assert_eq!((compliance.total_instances, compliance.on_time, compliance.late, compliance.missed), (4, 2, 1, 1));
+        assert!((compliance.compliance_rate - 0.5).abs() < 1e-9);
+        assert!(compliance.needs_alert());
+        let rates: Vec<(&str, u32, f64)> = compliance.by_task.iter()
+            .map(|r| (r.task_id.as_str(), r.instance_count, r.on_time_rate))
+            .collect();
+        assert_eq!(rates, vec![("daily", 2, 0.5), ("weekly", 2, 0.5)]);
+
+        assert!(!RecurringTaskCompliance::from_instances(&[], now).needs_alert());
+    }
+
+    #[test]
+    fn test_focus_score_counts_context_switches() {
+        let now = Utc::now();
+        let completed = |context: TaskContext, hours_ago: i64| {
+            let mut task = Task::new(format!("T-{}", hours_ago), "Task".to_string(), context);
+            task.status = TaskStatus::Done;
+            task.completed_at = Some(now - Duration::hours(hours_ago));
+            task
+        };
+        // This week: one day alternating contexts; last week: two switches
+        let tasks = vec![
+            completed(TaskContext::Work, 1),
+            completed(TaskContext::Personal, 2),
+            completed(TaskContext::Work, 3),
+            completed(TaskContext::Work, 200),
+            completed(TaskContext::Learning, 201),
+            completed(TaskContext::Work, 202),
+        ];
+
+        let score = FocusScore::from_completed_tasks("user-1", &tasks[..3], 7, now);
+        assert_eq!(score.completed_tasks, 3);
+        assert!((score.context_switches_per_day - 2.0 / 7.0).abs() < 1e-9);
+        assert!((score.focus_score - 7.0 / 9.0).abs() < 1e-9);
+        assert!(matches!(score.dominant_context, TaskContext::Work));
+        // The three completions may straddle midnight
+        assert!(score.single_context_days + score.multi_context_days >= 1);
+        assert!(score.week_over_week_change.is_none());
+
+        let score = FocusScore::from_completed_tasks("user-1", &tasks, 14, now);
+        assert_eq!(score.week_over_week_change, Some(0.0));
+        assert!(!score.improved_week_over_week());
+
+        // No switches this week against two the week before
+        let focused: Vec<Task> = tasks.iter().filter(|t| t.context != TaskContext::Personal).cloned().collect();
+        let score = FocusScore::from_completed_tasks("user-1", &focused, 14, now);
+        assert!(score.week_over_week_change.unwrap() > FOCUS_SCORE_IMPROVEMENT_THRESHOLD);
+        assert!(score.improved_week_over_week());
+    }
+}
@@ -0,0 +1,156 @@
+//! Follow-up instances of recurring tasks
+//!
+//! When a task with a [`TaskRecurrence`] is completed, [`RecurrenceService`]
+//! creates its next instance: a copy of the task in the backlog, due on the
+//! date given by [`Task::next_recurrence_date`]. The copy's id is derived from
+//! the completed task and that date, so the same occurrence is never created
+//! twice.
+
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+use tyl_errors::{TylError, TylResult};
+
+use super::models::{Task, TaskRecurrence};
+use super::services::TaskService;
+
+/// Prefix of the id suffix marking a generated occurrence, e.g. `PROJ1-T042-R20240207`
+const OCCURRENCE_ID_MARKER: &str = "-R";
+
+/// Parse a cron expression, with or without the leading seconds field
+///
+/// Five-field expressions (`min hour day month weekday`) run at second zero.
+pub fn parse_cron(expression: &str) -> TylResult<cron::Schedule> {
+    let expression = expression.trim();
+    let expression = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+
+    cron::Schedule::from_str(&expression)
+        .map_err(|e| TylError::validation("recurrence.cron", format!("Invalid cron expression '{}': {}", expression, e)))
+}
+
+/// First time strictly after `from` matched by a cron expression
+pub fn next_cron_occurrence(expression: &str, from: DateTime<Utc>) -> TylResult<DateTime<Utc>> {
+    parse_cron(expression)?
+        .after(&from)
+        .next()
+        .ok_or_else(|| TylError::validation("recurrence.cron", format!("Cron expression '{}' has no future occurrence", expression)))
+}
+
+/// Reject recurrences whose pattern cannot produce a next occurrence
+pub fn validate_recurrence(recurrence: &TaskRecurrence) -> TylResult<()> {
+    match recurrence.pattern.as_str() {
+        "daily" | "weekly" | "monthly" => Ok(()),
+        "custom" => match recurrence.cron.as_deref() {
+            Some(expression) => parse_cron(expression).map(|_| ()),
+            None => Err(TylError::validation("recurrence.cron", "Custom recurrences need a cron expression")),
+        },
+        other => Err(TylError::validation("recurrence.pattern", format!("Unknown recurrence pattern '{}'", other))),
+    }
+}
+
+/// Creates the next instance of completed recurring tasks
+pub struct RecurrenceService;
+
+impl RecurrenceService {
+    /// Id of the instance following `task`, or `None` when the recurrence has ended
+    pub fn next_occurrence_id(task: &Task) -> Option<String> {
+        let due = task.next_recurrence_date()?;
+        Some(format!("{}{}{}", occurrence_base_id(&task.id), OCCURRENCE_ID_MARKER, due.format("%Y%m%d")))
+    }
+
+    /// Create the instance following a completed recurring task
+    ///
+    /// The copy starts in the backlog, is due on the next recurrence date and
+    /// joins the task's project. Returns `None` when the task does not recur
+    /// (any more) or its next instance already exists.
+    pub async fn schedule_next_occurrence(
+        task: &Task,
+        service: &(dyn TaskService + Send + Sync),
+    ) -> TylResult<Option<Task>> {
+        let (Some(due_date), Some(new_id)) = (task.next_recurrence_date(), Self::next_occurrence_id(task)) else {
+            return Ok(None);
+        };
+        if service.get_task_by_id(&new_id).await?.is_some() {
+            return Ok(None);
+        }
+
+        let mut request = task.to_creation_request(new_id);
+        request.project_id = service.get_task_project_id(&task.id).await?;
+        request.due_date = Some(due_date);
+        request.estimated_date = None;
+        request.created_by = task.created_by.clone();
+        for criterion in &mut request.success_criteria {
+            criterion.is_completed = false;
+            criterion.completed_at = None;
+        }
+        service.create_task(request).await.map(Some)
+    }
+}
+
+/// Task id without the suffix of a generated occurrence, so ids do not grow with every instance
+fn occurrence_base_id(task_id: &str) -> &str {
+    match task_id.rsplit_once(OCCURRENCE_ID_MARKER) {
+        Some((base, date)) if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => task_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{MockTaskService, TaskContext, TaskStatus};
+
+    fn completed_weekly_task(id: &str) -> Task {
+        let mut task = Task::new(id.to_string(), "Weekly report".to_string(), TaskContext::Work);
+        task.due_date = Some(DateTime::parse_from_rfc3339("2024-01-31T09:00:00Z").unwrap().with_timezone(&Utc));
+        task.recurrence = Some(TaskRecurrence { pattern: "weekly".to_string(), interval: 1, end_date: None, cron: None });
+        task.status = TaskStatus::Done;
+        task.completed_at = Some(Utc::now());
+        task
+    }
+
+    #[test]
+    fn test_parse_cron_accepts_five_fields() {
+        assert!(parse_cron("0 9 * * Mon").is_ok());
+        assert!(parse_cron("0 0 9 * * Mon *").is_ok());
+        assert!(parse_cron("every monday").is_err());
+
+        let custom = TaskRecurrence { pattern: "custom".to_string(), interval: 1, end_date: None, cron: None };
+        assert!(validate_recurrence(&custom).is_err());
+        assert!(validate_recurrence(&TaskRecurrence { cron: Some("*/15 * * * *".to_string()), ..custom }).is_ok());
+    }
+
+    #[test]
+    fn test_next_occurrence_id_does_not_grow() {
+        let task = completed_weekly_task("PROJ1-T042");
+        assert_eq!(RecurrenceService::next_occurrence_id(&task).unwrap(), "PROJ1-T042-R20240207");
+
+        let task = completed_weekly_task("PROJ1-T042-R20240207");
+        assert_eq!(RecurrenceService::next_occurrence_id(&task).unwrap(), "PROJ1-T042-R20240207");
+
+        let task = completed_weekly_task("TASK-R2");
+        assert_eq!(RecurrenceService::next_occurrence_id(&task).unwrap(), "TASK-R2-R20240207");
+    }
+
+    #[tokio::test]
+    async fn test_schedule_next_occurrence() {
+        let service = MockTaskService::new();
+        let task = completed_weekly_task("PROJ1-T042");
+
+        let next = RecurrenceService::schedule_next_occurrence(&task, &service).await.unwrap().unwrap();
+        assert_eq!(next.id, "PROJ1-T042-R20240207");
+        assert_eq!(next.status, TaskStatus::Backlog);
+        assert_eq!(next.due_date.unwrap().to_rfc3339(), "2024-02-07T09:00:00+00:00");
+        assert!(service.get_task_by_id(&next.id).await.unwrap().is_some());
+
+        // Scheduling twice does not duplicate the instance
+        assert!(RecurrenceService::schedule_next_occurrence(&task, &service).await.unwrap().is_none());
+
+        let mut once = completed_weekly_task("PROJ1-T043");
+        once.recurrence = None;
+        assert!(RecurrenceService::schedule_next_occurrence(&once, &service).await.unwrap().is_none());
+    }
+}
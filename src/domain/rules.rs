@@ -0,0 +1,355 @@
+//! Business rule evaluation results
+//!
+//! Rules report every problem they find instead of failing on the first one:
+//! violations block the operation, warnings let it proceed but are surfaced to
+//! the caller.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{CustomPropertySchema, SuccessCriterion, Task, TaskContext, TaskDependency, TaskStatus};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationResult {
+    pub is_valid: bool,
+    pub violations: Vec<RuleViolation>,
+    pub warnings: Vec<RuleWarning>,
+    pub suggestions: Vec<String>,
+}
+
+impl ValidationResult {
+    pub fn valid() -> Self {
+        Self {
+            is_valid: true,
+            violations: Vec::new(),
+            warnings: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn invalid(violation: RuleViolation) -> Self {
+        Self {
+            is_valid: false,
+            violations: vec![violation],
+            warnings: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<RuleWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+
+    /// Record a violation, marking the result as invalid
+    pub fn add_violation(&mut self, violation: RuleViolation) {
+        self.is_valid = false;
+        self.violations.push(violation);
+    }
+
+    pub fn add_warning(&mut self, warning: RuleWarning) {
+        self.warnings.push(warning);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleViolation {
+    pub rule_name: String,
+    pub severity: ViolationSeverity,
+    pub message: String,
+    pub field: Option<String>,
+    pub suggested_fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleWarning {
+    pub rule_name: String,
+    pub message: String,
+    pub recommendation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ViolationSeverity {
+    Error,   // Blocks the operation
+    Warning, // Allows operation but not recommended
+    Info,    // Informational only
+}
+
+/// Evaluate a status transition against the workflow and the task's dependencies
+///
+/// `dependencies` pairs each dependency of the task with the task it points to.
+/// Unfinished hard dependencies block starting or completing the task; soft ones
+/// only produce warnings.
+pub fn evaluate_status_transition(
+    task: &Task,
+    new_status: &TaskStatus,
+    dependencies: &[(TaskDependency, Task)],
+) -> ValidationResult {
+    let mut result = ValidationResult::valid();
+
+    if !task.status.can_transition_to(new_status) {
+        let valid_next = task.status.valid_next_statuses();
+        result.add_violation(RuleViolation {
+            rule_name: "status_transition".to_string(),
+            severity: ViolationSeverity::Error,
+            message: format!("Cannot transition from {:?} to {:?}", task.status, new_status),
+            field: Some("status".to_string()),
+            suggested_fix: Some(format!("Valid next statuses: {:?}", valid_next)),
+        });
+        return result;
+    }
+
+    if matches!(new_status, TaskStatus::InProgress | TaskStatus::Done) {
+        for (dependency, blocking_task) in dependencies {
+            if matches!(blocking_task.status, TaskStatus::Done | TaskStatus::Cancelled) {
+                continue;
+            }
+
+            if dependency.is_hard_dependency {
+                result.add_violation(RuleViolation {
+                    rule_name: "hard_dependency_incomplete".to_string(),
+                    severity: ViolationSeverity::Error,
+                    message: format!("Hard dependency '{}' is not done", blocking_task.name),
+                    field: Some("dependencies".to_string()),
+                    suggested_fix: Some(format!("Complete task '{}' first", blocking_task.id)),
+                });
+            } else {
+                result.add_warning(RuleWarning {
+                    rule_name: "soft_dependency_incomplete".to_string(),
+                    message: format!("Soft dependency '{}' is not done", blocking_task.name),
+                    recommendation: Some(format!("Check the progress of task '{}'", blocking_task.id)),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Openings that make a success criterion verifiable
+const VERIFIABLE_CRITERION_PREFIXES: &[&str] = &["given ", "when ", "then ", "the system should", "verify that"];
+
+/// Check that success criteria are written so they can be verified
+///
+/// Criteria should follow Given/When/Then or start with "The system should"
+/// or "Verify that", measurable criteria need a verification method, and no
+/// criterion may repeat another. Problems are only reported as warnings.
+pub fn validate_success_criteria_format(criteria: &[SuccessCriterion]) -> Vec<RuleWarning> {
+    let mut warnings = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for (index, criterion) in criteria.iter().enumerate() {
+        let text = criterion.criterion.trim().to_lowercase();
+
+        if !VERIFIABLE_CRITERION_PREFIXES.iter().any(|prefix| text.starts_with(prefix)) {
+            warnings.push(RuleWarning {
+                rule_name: "criterion_not_verifiable".to_string(),
+                message: format!("Success criterion {} does not describe a verifiable outcome", index),
+                recommendation: Some(
+                    "Use Given/When/Then or start with \"The system should...\" or \"Verify that...\"".to_string(),
+                ),
+            });
+        }
+
+        if criterion.measurable && criterion.verification_method.trim().is_empty() {
+            warnings.push(RuleWarning {
+                rule_name: "criterion_missing_verification".to_string(),
+                message: format!("Measurable success criterion {} has no verification method", index),
+                recommendation: Some("Describe how the criterion will be measured".to_string()),
+            });
+        }
+
+        if !seen.insert(text) {
+            warnings.push(RuleWarning {
+                rule_name: "criterion_duplicated".to_string(),
+                message: format!("Success criterion {} repeats an earlier criterion", index),
+                recommendation: Some("Remove the duplicate criterion".to_string()),
+            });
+        }
+    }
+
+    warnings
+}
+
+/// Check custom properties against the JSON Schema registered for `context`
+///
+/// Every schema error, missing required properties included, is a violation.
+/// Properties the schema does not declare are accepted with a warning, unless
+/// the schema itself forbids additional properties.
+pub fn validate_custom_properties_against_schema(
+    schemas: &[CustomPropertySchema],
+    context: TaskContext,
+    properties: &HashMap<String, serde_json::Value>,
+) -> ValidationResult {
+    let mut result = ValidationResult::valid();
+    let Some(schema) = schemas.iter().find(|schema| schema.context == context) else {
+        return result;
+    };
+
+    let compiled = match jsonschema::JSONSchema::compile(&schema.schema) {
+        Ok(compiled) => compiled,
+        Err(e) => {
+            result.add_violation(RuleViolation {
+                rule_name: "custom_property_schema_invalid".to_string(),
+                severity: ViolationSeverity::Error,
+                message: format!("Custom property schema for {:?} tasks is invalid: {}", context, e),
+                field: Some("custom_properties".to_string()),
+                suggested_fix: None,
+            });
+            return result;
+        }
+    };
+
+    let instance = serde_json::to_value(properties).unwrap_or_default();
+    if let Err(errors) = compiled.validate(&instance) {
+        for error in errors {
+            let path = error.instance_path.to_string();
+            result.add_violation(RuleViolation {
+                rule_name: "custom_property_schema".to_string(),
+                severity: ViolationSeverity::Error,
+                message: error.to_string(),
+                field: Some(format!("custom_properties{}", path.replace('/', "."))),
+                suggested_fix: None,
+            });
+        }
+    }
+
+    if schema.schema.get("additionalProperties") == Some(&serde_json::Value::Bool(false)) {
+        return result;
+    }
+
+    let declared = schema.declared_properties();
+    let mut undeclared: Vec<&String> = properties.keys()
+        .filter(|key| !declared.contains(&key.as_str()))
+        .collect();
+    undeclared.sort();
+    for key in undeclared {
+        result.add_warning(RuleWarning {
+            rule_name: "custom_property_undeclared".to_string(),
+            message: format!("Custom property '{}' is not declared by the {:?} schema", key, context),
+            recommendation: Some("Add the property to the schema or remove it from the task".to_string()),
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DependencyType, TaskContext};
+
+    fn task(id: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(id.to_string(), format!("Task {}", id), TaskContext::Work);
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_invalid_transition_is_a_violation() {
+        let result = evaluate_status_transition(&task("A", TaskStatus::Backlog), &TaskStatus::Done, &[]);
+
+        assert!(!result.is_valid);
+        assert_eq!(result.violations[0].rule_name, "status_transition");
+        assert!(result.violations[0].suggested_fix.is_some());
+    }
+
+    #[test]
+    fn test_soft_dependency_warns_hard_dependency_blocks() {
+        let current = task("A", TaskStatus::Ready);
+        let mut soft = TaskDependency::new("A".to_string(), "B".to_string(), DependencyType::RelatedTo);
+        soft.is_hard_dependency = false;
+        let hard = TaskDependency::new("A".to_string(), "C".to_string(), DependencyType::Blocks);
+
+        let result = evaluate_status_transition(
+            &current,
+            &TaskStatus::InProgress,
+            &[(soft.clone(), task("B", TaskStatus::InProgress))],
+        );
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = evaluate_status_transition(
+            &current,
+            &TaskStatus::InProgress,
+            &[(soft, task("B", TaskStatus::InProgress)), (hard, task("C", TaskStatus::Ready))],
+        );
+        assert!(!result.is_valid);
+        assert_eq!(result.violations[0].rule_name, "hard_dependency_incomplete");
+    }
+
+    fn criterion(text: &str, measurable: bool, verification_method: &str) -> SuccessCriterion {
+        SuccessCriterion {
+            criterion: text.to_string(),
+            measurable,
+            verification_method: verification_method.to_string(),
+            is_completed: false,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_success_criteria_format_warnings() {
+        let valid = [
+            criterion("Given a logged in user when they save then the task is stored", true, "Integration test"),
+            criterion("The system should reject empty names", false, ""),
+            criterion("Verify that the report is emailed", true, "Manual check"),
+        ];
+        assert!(validate_success_criteria_format(&valid).is_empty());
+
+        let invalid = [
+            criterion("Works well", false, ""),
+            criterion("Verify that exports finish", true, " "),
+            criterion("verify that EXPORTS finish", true, "Timer"),
+        ];
+        let rules: Vec<_> = validate_success_criteria_format(&invalid)
+            .into_iter()
+            .map(|w| w.rule_name)
+            .collect();
+        assert_eq!(rules, vec!["criterion_not_verifiable", "criterion_missing_verification", "criterion_duplicated"]);
+    }
+
+    #[test]
+    fn test_custom_properties_schema() {
+        let schemas = vec![CustomPropertySchema {
+            context: TaskContext::Work,
+            schema: serde_json::json!({
+                "type": "object",
+                "required": ["ticket_number"],
+                "properties": { "ticket_number": { "type": "string", "pattern": "^[A-Z]+-[0-9]+$" } },
+            }),
+        }];
+        let properties = |pairs: &[(&str, serde_json::Value)]| -> HashMap<String, serde_json::Value> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+        };
+
+        let result = validate_custom_properties_against_schema(&schemas, TaskContext::Work, &properties(&[]));
+        assert!(!result.is_valid);
+        assert_eq!(result.violations[0].rule_name, "custom_property_schema");
+
+        let result = validate_custom_properties_against_schema(
+            &schemas,
+            TaskContext::Work,
+            &properties(&[("ticket_number", serde_json::json!("lowercase"))]),
+        );
+        assert!(!result.is_valid);
+        assert_eq!(result.violations[0].field.as_deref(), Some("custom_properties.ticket_number"));
+
+        let result = validate_custom_properties_against_schema(
+            &schemas,
+            TaskContext::Work,
+            &properties(&[("ticket_number", serde_json::json!("OPS-42")), ("team", serde_json::json!("core"))]),
+        );
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].rule_name, "custom_property_undeclared");
+
+        // Contexts without a schema are not checked
+        assert!(validate_custom_properties_against_schema(&schemas, TaskContext::Personal, &properties(&[])).is_valid);
+    }
+}
@@ -0,0 +1,176 @@
+//! Automatic promotion of backlog tasks
+//!
+//! [`TaskScheduler`] moves backlog tasks to ready once every task they depend
+//! on is done, so nobody has to watch dependencies to know a task can be
+//! picked up. Backlog tasks without dependencies are left to their owners.
+//! A pass runs every minute and publishes a [`TaskAutoPromoted`] event per
+//! promoted task; the outcome of the latest pass is kept for the admin
+//! endpoints.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tyl_errors::TylResult;
+use utoipa::ToSchema;
+
+use crate::domain::{TaskFilter, TaskRepository, TaskService, TaskStatus};
+use crate::events::{DomainEvent, EventService, TaskAutoPromoted};
+
+/// Time between two scheduled passes
+const SCHEDULING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Outcome of one scheduling pass
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SchedulingReport {
+    /// Tasks moved from backlog to ready
+    pub promoted: Vec<String>,
+    /// Backlog tasks with a dependency that is not done yet
+    pub still_blocked: Vec<String>,
+    /// Tasks that could not be checked or promoted, with the reason
+    pub errors: Vec<(String, String)>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Promotes backlog tasks whose dependencies are all done
+pub struct TaskScheduler {
+    event_service: Arc<EventService>,
+    last_report: Mutex<Option<SchedulingReport>>,
+}
+
+impl TaskScheduler {
+    pub fn new(event_service: Arc<EventService>) -> Self {
+        Self {
+            event_service,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Move every backlog task whose dependencies are all done to ready
+    ///
+    /// A task that fails to be checked or promoted is recorded in the report
+    /// and skipped so the rest are still promoted.
+    pub async fn run_scheduling_pass(
+        &self,
+        service: &(dyn TaskService + Send + Sync),
+        repo: &(dyn TaskRepository + Send + Sync),
+    ) -> TylResult<SchedulingReport> {
+        let started_at = Utc::now();
+        let mut report = SchedulingReport {
+            promoted: Vec::new(),
+            still_blocked: Vec::new(),
+            errors: Vec::new(),
+            started_at,
+            finished_at: started_at,
+        };
+
+        let filter = TaskFilter { status: Some(vec![TaskStatus::Backlog]), ..Default::default() };
+        for task in repo.find_tasks_by_filter(&filter).await? {
+            let prerequisites = match repo.find_prerequisite_tasks(&task.id).await {
+                Ok(prerequisites) => prerequisites,
+                Err(e) => {
+                    report.errors.push((task.id, e.to_string()));
+                    continue;
+                }
+            };
+            if prerequisites.is_empty() {
+                continue;
+            }
+            if prerequisites.iter().any(|prerequisite| prerequisite.status != TaskStatus::Done) {
+                report.still_blocked.push(task.id);
+                continue;
+            }
+
+            match service.transition_task_status(&task.id, TaskStatus::Ready, None).await {
+                Ok(_) => {
+                    let event = TaskAutoPromoted { task_id: task.id.clone(), promoted_at: Utc::now() };
+                    if let Err(e) = self.event_service.publish(TaskAutoPromoted::TOPIC, event).await {
+                        tracing::warn!(task_id = %task.id, error = %e, "Failed to publish task auto-promotion");
+                    }
+                    report.promoted.push(task.id);
+                }
+                Err(e) => {
+                    tracing::warn!(task_id = %task.id, error = %e, "Failed to promote task to ready");
+                    report.errors.push((task.id, e.to_string()));
+                }
+            }
+        }
+
+        report.finished_at = Utc::now();
+        tracing::info!(
+            promoted = report.promoted.len(),
+            still_blocked = report.still_blocked.len(),
+            errors = report.errors.len(),
+            "Scheduling pass finished"
+        );
+        *self.last_report.lock().unwrap() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Outcome of the latest pass, `None` before the first one finished
+    pub fn last_report(&self) -> Option<SchedulingReport> {
+        self.last_report.lock().unwrap().clone()
+    }
+
+    /// Run a pass every minute until the task is aborted
+    pub fn spawn(
+        self: Arc<Self>,
+        service: Arc<dyn TaskService + Send + Sync>,
+        repo: Arc<dyn TaskRepository + Send + Sync>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SCHEDULING_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_scheduling_pass(service.as_ref(), repo.as_ref()).await {
+                    tracing::warn!(error = %e, "Task scheduling pass failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryTaskRepository;
+    use crate::domain::{DependencyType, Task, TaskContext, TaskDependency, TaskDomainService};
+
+    fn task(id: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(id.to_string(), id.to_string(), TaskContext::Work);
+        task.status = status;
+        task
+    }
+
+    #[tokio::test]
+    async fn test_run_scheduling_pass_promotes_tasks_with_done_dependencies() {
+        let repository = InMemoryTaskRepository::new();
+        for task in [
+            task("DONE", TaskStatus::Done),
+            task("OPEN", TaskStatus::InProgress),
+            task("UNBLOCKED", TaskStatus::Backlog),
+            task("BLOCKED", TaskStatus::Backlog),
+            task("INDEPENDENT", TaskStatus::Backlog),
+        ] {
+            repository.save_task(&task).await.unwrap();
+        }
+        for (from, to) in [("UNBLOCKED", "DONE"), ("BLOCKED", "DONE"), ("BLOCKED", "OPEN")] {
+            let dependency = TaskDependency::new(from.to_string(), to.to_string(), DependencyType::Requires);
+            repository.save_dependency(&dependency).await.unwrap();
+        }
+        let service = TaskDomainService::new(repository.clone());
+        let scheduler = TaskScheduler::new(Arc::new(EventService::new().await.unwrap()));
+        assert!(scheduler.last_report().is_none());
+
+        let report = scheduler.run_scheduling_pass(&service, &repository).await.unwrap();
+
+        assert_eq!(report.promoted, vec!["UNBLOCKED"]);
+        assert_eq!(report.still_blocked, vec!["BLOCKED"]);
+        assert!(report.errors.is_empty());
+        assert_eq!(repository.find_task_by_id("UNBLOCKED").await.unwrap().unwrap().status, TaskStatus::Ready);
+        assert_eq!(repository.find_task_by_id("INDEPENDENT").await.unwrap().unwrap().status, TaskStatus::Backlog);
+        assert_eq!(scheduler.last_report().unwrap().promoted, vec!["UNBLOCKED"]);
+    }
+}
@@ -0,0 +1,450 @@
+//! Structured task search
+//!
+//! [`TaskSearchQuery`] combines conditions on task fields with `and`, `or`
+//! and `not`, e.g.
+//!
+//! ```json
+//! {"and": [
+//!     {"field": {"name": "status", "op": "in", "value": ["ready", "in_progress"]}},
+//!     {"not": {"field": {"name": "priority", "op": "eq", "value": "low"}}}
+//! ]}
+//! ```
+//!
+//! Queries come straight from API clients and end up in Cypher, so
+//! [`validate_search_query`] only accepts the fields in [`SEARCHABLE_FIELDS`]
+//! and scalar values, which are bound as query parameters when the query is
+//! rendered.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use std::cmp::Ordering;
+use tyl_errors::{TylError, TylResult};
+
+use super::models::Task;
+use crate::adapters::cypher::CypherParams;
+
+/// Most field conditions one query may combine
+pub const MAX_SEARCH_CONDITIONS: usize = 10;
+pub const DEFAULT_SEARCH_PAGE_SIZE: usize = 20;
+pub const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
+/// Task fields a search may filter and sort on
+pub const SEARCHABLE_FIELDS: &[&str] = &[
+    "id", "name", "description", "status", "priority", "context", "complexity", "source", "visibility",
+    "created_at", "updated_at", "started_at", "completed_at", "due_date", "priority_change_count", "tags",
+];
+
+/// Fields stored as RFC 3339 timestamps, compared as such
+const DATE_FIELDS: &[&str] = &["created_at", "updated_at", "started_at", "completed_at", "due_date"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[schema(no_recursion)]
+pub enum TaskSearchQuery {
+    And(Vec<TaskSearchQuery>),
+    Or(Vec<TaskSearchQuery>),
+    Not(Box<TaskSearchQuery>),
+    Field { name: String, op: SearchOp, value: Value },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchOp {
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    /// Case-insensitive substring, or tag membership for `tags`
+    Contains,
+    /// Value is one of an array
+    In,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SortSpec {
+    pub field: String,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+/// One-based page of search results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PageRequest {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_page_size() -> usize {
+    DEFAULT_SEARCH_PAGE_SIZE
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self { page: default_page(), page_size: default_page_size() }
+    }
+}
+
+impl PageRequest {
+    /// Results to skip before this page
+    pub fn offset(&self) -> usize {
+        self.page.saturating_sub(1) * self.page_size
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PagedResult<T> {
+    pub items: Vec<T>,
+    /// Results matching the query over all pages
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl TaskSearchQuery {
+    /// Field conditions in the query, however deeply nested
+    pub fn condition_count(&self) -> usize {
+        match self {
+            TaskSearchQuery::And(queries) | TaskSearchQuery::Or(queries) => {
+                queries.iter().map(TaskSearchQuery::condition_count).sum()
+            }
+            TaskSearchQuery::Not(query) => query.condition_count(),
+            TaskSearchQuery::Field { .. } => 1,
+        }
+    }
+
+    /// Cypher condition on the task bound to `t` and the values of its
+    /// `$search_<n>` parameters; the query must be valid
+    pub fn to_cypher(&self) -> (String, CypherParams) {
+        let mut params = CypherParams::new();
+        let condition = self.write_cypher(&mut params);
+        (condition, params)
+    }
+
+    fn write_cypher(&self, params: &mut CypherParams) -> String {
+        let join = |queries: &[TaskSearchQuery], operator: &str, params: &mut CypherParams| {
+            let conditions: Vec<String> = queries.iter().map(|query| query.write_cypher(params)).collect();
+            format!("({})", conditions.join(operator))
+        };
+        match self {
+            TaskSearchQuery::And(queries) => join(queries, " AND ", params),
+            TaskSearchQuery::Or(queries) => join(queries, " OR ", params),
+            TaskSearchQuery::Not(query) => format!("NOT {}", query.write_cypher(params)),
+            TaskSearchQuery::Field { name, op, value } if name == "tags" => {
+                let tags = match value {
+                    Value::Array(_) => value.clone(),
+                    tag => Value::Array(vec![tag.clone()]),
+                };
+                let condition = format!("EXISTS((t)-[:TAGGED_WITH]->(g:Tag) WHERE g.name IN {})", bind_value(params, name, &tags));
+                if *op == SearchOp::Ne { format!("NOT {}", condition) } else { condition }
+            }
+            TaskSearchQuery::Field { name, op, value } => {
+                let property = format!("t.{}", name);
+                let param = bind_value(params, name, value);
+                match op {
+                    SearchOp::Eq => format!("{} = {}", property, param),
+                    SearchOp::Ne => format!("{} <> {}", property, param),
+                    SearchOp::Gt => format!("{} > {}", property, param),
+                    SearchOp::Lt => format!("{} < {}", property, param),
+                    SearchOp::Contains => format!("toLower({}) CONTAINS toLower({})", property, param),
+                    SearchOp::In => format!("{} IN {}", property, param),
+                }
+            }
+        }
+    }
+
+    /// Whether `task` matches, the way the Cypher condition would
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            TaskSearchQuery::And(queries) => queries.iter().all(|query| query.matches(task)),
+            TaskSearchQuery::Or(queries) => queries.iter().any(|query| query.matches(task)),
+            TaskSearchQuery::Not(query) => !query.matches(task),
+            TaskSearchQuery::Field { name, op, value } if name == "tags" => {
+                let has_any = match value {
+                    Value::Array(tags) => tags.iter().any(|tag| tag.as_str().is_some_and(|tag| task.tags.iter().any(|t| t == tag))),
+                    tag => tag.as_str().is_some_and(|tag| task.tags.iter().any(|t| t == tag)),
+                };
+                if *op == SearchOp::Ne { !has_any } else { has_any }
+            }
+            TaskSearchQuery::Field { name, op, value } => {
+                let actual = field_value(task, name);
+                if actual.is_null() {
+                    return false;
+                }
+                let value = normalized_value(name, value);
+                match op {
+                    SearchOp::Eq => actual == value,
+                    SearchOp::Ne => actual != value,
+                    SearchOp::Gt => compare_values(&actual, &value) == Some(Ordering::Greater),
+                    SearchOp::Lt => compare_values(&actual, &value) == Some(Ordering::Less),
+                    SearchOp::Contains => match (actual.as_str(), value.as_str()) {
+                        (Some(actual), Some(value)) => actual.to_lowercase().contains(&value.to_lowercase()),
+                        _ => false,
+                    },
+                    SearchOp::In => value.as_array().is_some_and(|values| values.contains(&actual)),
+                }
+            }
+        }
+    }
+}
+
+/// Reject queries that are too large, name unknown fields or carry values
+/// the operator cannot use
+pub fn validate_search_query(query: &TaskSearchQuery, sort: &[SortSpec], page: &PageRequest) -> TylResult<()> {
+    let conditions = query.condition_count();
+    if conditions > MAX_SEARCH_CONDITIONS {
+        return Err(TylError::validation(
+            "query",
+            format!("A search combines at most {} conditions, got {}", MAX_SEARCH_CONDITIONS, conditions),
+        ));
+    }
+    validate_query_node(query)?;
+
+    for spec in sort {
+        if !SEARCHABLE_FIELDS.contains(&spec.field.as_str()) || spec.field == "tags" {
+            return Err(TylError::validation("sort", format!("Cannot sort by '{}'", spec.field)));
+        }
+    }
+    if page.page == 0 {
+        return Err(TylError::validation("page", "Pages start at 1"));
+    }
+    if page.page_size == 0 || page.page_size > MAX_SEARCH_PAGE_SIZE {
+        return Err(TylError::validation(
+            "page_size",
+            format!("Page size must be between 1 and {}", MAX_SEARCH_PAGE_SIZE),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_query_node(query: &TaskSearchQuery) -> TylResult<()> {
+    match query {
+        TaskSearchQuery::And(queries) | TaskSearchQuery::Or(queries) => {
+            if queries.is_empty() {
+                return Err(TylError::validation("query", "'and' and 'or' need at least one condition"));
+            }
+            queries.iter().try_for_each(validate_query_node)
+        }
+        TaskSearchQuery::Not(query) => validate_query_node(query),
+        TaskSearchQuery::Field { name, op, value } => validate_condition(name, *op, value),
+    }
+}
+
+fn validate_condition(name: &str, op: SearchOp, value: &Value) -> TylResult<()> {
+    if !SEARCHABLE_FIELDS.contains(&name) {
+        return Err(TylError::validation(
+            "query",
+            format!("Unknown search field '{}', expected one of: {}", name, SEARCHABLE_FIELDS.join(", ")),
+        ));
+    }
+    let invalid = |message: &str| Err(TylError::validation("query", format!("'{}' {}", name, message)));
+
+    if name == "tags" {
+        return match (op, value) {
+            (SearchOp::Contains | SearchOp::Eq | SearchOp::Ne, Value::String(_)) => Ok(()),
+            (SearchOp::In, Value::Array(tags)) if !tags.is_empty() && tags.iter().all(Value::is_string) => Ok(()),
+            _ => invalid("takes a tag with contains, eq or ne, or a list of tags with in"),
+        };
+    }
+    match (op, value) {
+        (SearchOp::In, Value::Array(values)) if !values.is_empty() => {
+            values.iter().try_for_each(|value| validate_scalar(name, value))
+        }
+        (SearchOp::In, _) => invalid("needs a non-empty array with in"),
+        (SearchOp::Contains, Value::String(_)) => Ok(()),
+        (SearchOp::Contains, _) => invalid("needs a string with contains"),
+        (SearchOp::Gt | SearchOp::Lt, Value::String(_) | Value::Number(_)) => validate_scalar(name, value),
+        (SearchOp::Gt | SearchOp::Lt, _) => invalid("needs a string or number with gt and lt"),
+        (SearchOp::Eq | SearchOp::Ne, value) => validate_scalar(name, value),
+    }
+}
+
+fn validate_scalar(name: &str, value: &Value) -> TylResult<()> {
+    match value {
+        Value::String(date) if DATE_FIELDS.contains(&name) && parse_date(date).is_none() => Err(TylError::validation(
+            "query",
+            format!("'{}' needs an RFC 3339 timestamp, got '{}'", name, date),
+        )),
+        Value::String(_) | Value::Number(_) | Value::Bool(_) => Ok(()),
+        _ => Err(TylError::validation("query", format!("'{}' only compares to strings, numbers or booleans", name))),
+    }
+}
+
+/// `ORDER BY` for `sort`, falling back to the newest tasks first; the id keeps pages stable
+pub fn search_order_clause(sort: &[SortSpec]) -> String {
+    if sort.is_empty() {
+        return "ORDER BY t.created_at DESC, t.id DESC".to_string();
+    }
+    let mut keys: Vec<String> = sort.iter()
+        .map(|spec| format!("t.{} {}", spec.field, if spec.direction == SortDirection::Desc { "DESC" } else { "ASC" }))
+        .collect();
+    keys.push("t.id".to_string());
+    format!("ORDER BY {}", keys.join(", "))
+}
+
+/// Run the search over tasks held in memory, e.g. by test doubles
+pub fn search_tasks_in_memory(
+    tasks: impl IntoIterator<Item = Task>,
+    query: &TaskSearchQuery,
+    sort: &[SortSpec],
+    page: &PageRequest,
+) -> PagedResult<Task> {
+    let mut matches: Vec<Task> = tasks.into_iter().filter(|task| query.matches(task)).collect();
+    if sort.is_empty() {
+        matches.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+    } else {
+        matches.sort_by(|a, b| {
+            sort.iter()
+                .map(|spec| {
+                    let ordering = compare_values(&field_value(a, &spec.field), &field_value(b, &spec.field))
+                        .unwrap_or(Ordering::Equal);
+                    if spec.direction == SortDirection::Desc { ordering.reverse() } else { ordering }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.id.cmp(&b.id))
+        });
+    }
+
+    let total = matches.len();
+    let items = matches.into_iter().skip(page.offset()).take(page.page_size).collect();
+    PagedResult { items, total, page: page.page, page_size: page.page_size }
+}
+
+/// Field of a task as it is stored on the graph node
+fn field_value(task: &Task, name: &str) -> Value {
+    let date = |date: Option<DateTime<Utc>>| date.map_or(Value::Null, |date| Value::String(date.to_rfc3339()));
+    match name {
+        "id" => Value::String(task.id.clone()),
+        "name" => Value::String(task.name.clone()),
+        "description" => task.description.clone().map_or(Value::Null, Value::String),
+        "status" => serde_json::json!(task.status),
+        "priority" => serde_json::json!(task.priority),
+        "context" => serde_json::json!(task.context),
+        "complexity" => serde_json::json!(task.complexity),
+        "source" => serde_json::json!(task.source),
+        "visibility" => serde_json::json!(task.visibility),
+        "created_at" => date(Some(task.created_at)),
+        "updated_at" => date(Some(task.updated_at)),
+        "started_at" => date(task.started_at),
+        "completed_at" => date(task.completed_at),
+        "due_date" => date(task.due_date),
+        "priority_change_count" => serde_json::json!(task.priority_change_count),
+        _ => Value::Null,
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|date| date.with_timezone(&Utc))
+}
+
+/// Dates in the form they are stored in, so they compare as text
+fn normalized_value(name: &str, value: &Value) -> Value {
+    match value {
+        Value::String(date) if DATE_FIELDS.contains(&name) => {
+            parse_date(date).map_or_else(|| value.clone(), |date| Value::String(date.to_rfc3339()))
+        }
+        Value::Array(values) => Value::Array(values.iter().map(|value| normalized_value(name, value)).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Placeholder of a new parameter holding the value of field `name`
+fn bind_value(params: &mut CypherParams, name: &str, value: &Value) -> String {
+    let param = format!("search_{}", params.len());
+    params.insert(param.clone(), normalized_value(name, value));
+    format!("${}", param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{TaskContext, TaskPriority, TaskStatus};
+    use serde_json::json;
+
+    fn field(name: &str, op: SearchOp, value: Value) -> TaskSearchQuery {
+        TaskSearchQuery::Field { name: name.to_string(), op, value }
+    }
+
+    #[test]
+    fn test_query_deserializes_and_renders_cypher() {
+        let query: TaskSearchQuery = serde_json::from_value(json!({"and": [
+            {"field": {"name": "status", "op": "in", "value": ["ready", "in_progress"]}},
+            {"not": {"field": {"name": "name", "op": "contains", "value": "it's"}}},
+            {"field": {"name": "tags", "op": "contains", "value": "backend"}}
+        ]})).unwrap();
+
+        let (condition, params) = query.to_cypher();
+        assert_eq!(
+            condition,
+            "(t.status IN $search_0 AND NOT toLower(t.name) CONTAINS toLower($search_1) \
+             AND EXISTS((t)-[:TAGGED_WITH]->(g:Tag) WHERE g.name IN $search_2))"
+        );
+        assert_eq!(params["search_0"], json!(["ready", "in_progress"]));
+        assert_eq!(params["search_1"], json!("it's"));
+        assert_eq!(params["search_2"], json!(["backend"]));
+        assert_eq!(
+            search_order_clause(&[SortSpec { field: "due_date".to_string(), direction: SortDirection::Desc }]),
+            "ORDER BY t.due_date DESC, t.id"
+        );
+    }
+
+    #[test]
+    fn test_validation_rejects_unknown_fields_and_large_queries() {
+        let page = PageRequest::default();
+        let injected = field("name) DETACH DELETE t //", SearchOp::Eq, json!("x"));
+        assert!(validate_search_query(&injected, &[], &page).is_err());
+
+        let many = TaskSearchQuery::Or((0..11).map(|i| field("id", SearchOp::Eq, json!(i.to_string()))).collect());
+        assert!(validate_search_query(&many, &[], &page).is_err());
+
+        assert!(validate_search_query(&field("due_date", SearchOp::Gt, json!("tomorrow")), &[], &page).is_err());
+        assert!(validate_search_query(&field("status", SearchOp::In, json!("ready")), &[], &page).is_err());
+        let sort = [SortSpec { field: "tags".to_string(), direction: SortDirection::Asc }];
+        assert!(validate_search_query(&field("status", SearchOp::Eq, json!("ready")), &sort, &page).is_err());
+        assert!(validate_search_query(&field("status", SearchOp::Eq, json!("ready")), &[], &page).is_ok());
+    }
+
+    #[test]
+    fn test_search_in_memory_filters_sorts_and_pages() {
+        let tasks: Vec<Task> = (1..=5).map(|i| {
+            let mut task = Task::new(format!("T{}", i), format!("Task {}", i), TaskContext::Work);
+            task.priority = if i % 2 == 0 { TaskPriority::High } else { TaskPriority::Low };
+            task.status = TaskStatus::Ready;
+            task
+        }).collect();
+        let query = TaskSearchQuery::And(vec![
+            field("status", SearchOp::Eq, json!("ready")),
+            TaskSearchQuery::Not(Box::new(field("priority", SearchOp::Eq, json!("high")))),
+        ]);
+        let sort = [SortSpec { field: "name".to_string(), direction: SortDirection::Desc }];
+
+        let result = search_tasks_in_memory(tasks, &query, &sort, &PageRequest { page: 1, page_size: 2 });
+
+        assert_eq!(result.total, 3);
+        let ids: Vec<&str> = result.items.iter().map(|task| task.id.as_str()).collect();
+        assert_eq!(ids, ["T5", "T3"]);
+    }
+}
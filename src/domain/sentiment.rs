@@ -0,0 +1,139 @@
+//! Rule-based sentiment of task discussions
+//!
+//! Every comment is scored from fixed lists of positive and negative words,
+//! as `(positive - negative) / (positive + negative)`, so a comment with only
+//! positive words scores 1.0 and one without any listed word scores 0.0. No
+//! model is involved; the lists favour the vocabulary of task threads.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::models::TaskComment;
+
+const POSITIVE_WORDS: &[&str] = &[
+    "great", "done", "resolved", "thanks", "thank", "fixed", "works", "merged", "approved", "good",
+];
+const NEGATIVE_WORDS: &[&str] = &[
+    "blocked", "failed", "broken", "issue", "bug", "error", "stuck", "fails", "wrong", "problem",
+];
+/// Overall score below which a discussion is flagged as unhealthy
+pub const DISCUSSION_HEALTH_ALERT_THRESHOLD: f64 = -0.5;
+/// Difference between the later and earlier half of a thread that counts as a trend
+const SENTIMENT_TREND_THRESHOLD: f64 = 0.2;
+
+/// Direction the sentiment of a thread is moving in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SentimentTrend {
+    Improving,
+    Stable,
+    Declining,
+}
+
+/// Sentiment of the comments on a task
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CommentSentiment {
+    pub task_id: String,
+    /// Mean comment score, from -1.0 (very negative) to 1.0 (very positive)
+    pub overall_score: f64,
+    pub comment_count: u32,
+    pub positive_comments: u32,
+    pub negative_comments: u32,
+    /// Later half of the thread against the earlier half
+    pub trend: SentimentTrend,
+}
+
+impl CommentSentiment {
+    pub fn analyze(task_id: &str, comments: &[TaskComment]) -> Self {
+        let mut comments: Vec<&TaskComment> = comments.iter().collect();
+        comments.sort_by_key(|c| c.created_at);
+        let scores: Vec<f64> = comments.iter().map(|c| comment_score(&c.body)).collect();
+
+        let half = scores.len() / 2;
+        let trend = match mean(&scores[scores.len() - half..]) - mean(&scores[..half]) {
+            _ if half == 0 => SentimentTrend::Stable,
+            change if change > SENTIMENT_TREND_THRESHOLD => SentimentTrend::Improving,
+            change if change < -SENTIMENT_TREND_THRESHOLD => SentimentTrend::Declining,
+            _ => SentimentTrend::Stable,
+        };
+
+        Self {
+            task_id: task_id.to_string(),
+            overall_score: mean(&scores),
+            comment_count: scores.len() as u32,
+            positive_comments: scores.iter().filter(|s| **s > 0.0).count() as u32,
+            negative_comments: scores.iter().filter(|s| **s < 0.0).count() as u32,
+            trend,
+        }
+    }
+
+    /// Whether the discussion is below [`DISCUSSION_HEALTH_ALERT_THRESHOLD`]
+    pub fn needs_alert(&self) -> bool {
+        self.overall_score < DISCUSSION_HEALTH_ALERT_THRESHOLD
+    }
+}
+
+/// Score of a single comment, 0.0 when it has no listed word
+pub fn comment_score(content: &str) -> f64 {
+    let (mut positive, mut negative) = (0u32, 0u32);
+    for word in content.split(|c: char| !c.is_alphanumeric()).map(str::to_lowercase) {
+        if POSITIVE_WORDS.contains(&word.as_str()) {
+            positive += 1;
+        } else if NEGATIVE_WORDS.contains(&word.as_str()) {
+            negative += 1;
+        }
+    }
+
+    if positive + negative == 0 {
+        return 0.0;
+    }
+    (positive as f64 - negative as f64) / (positive + negative) as f64
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn comment(body: &str, minutes_ago: i64) -> TaskComment {
+        let mut comment = TaskComment::new(format!("C-{}", minutes_ago), "T1".to_string(), "user-1".to_string(), body.to_string());
+        comment.created_at = Utc::now() - Duration::minutes(minutes_ago);
+        comment
+    }
+
+    #[test]
+    fn test_comment_score() {
+        assert_eq!(comment_score("Great, thanks!"), 1.0);
+        assert_eq!(comment_score("Still BLOCKED, the build is broken"), -1.0);
+        assert_eq!(comment_score("Fixed one issue"), 0.0);
+        assert_eq!(comment_score("Looking into it"), 0.0);
+    }
+
+    #[test]
+    fn test_declining_discussion_needs_alert() {
+        let sentiment = CommentSentiment::analyze("T1", &[
+            comment("Deploy failed again", 10),
+            comment("Thanks, looks good", 40),
+            comment("Still blocked on the broken API", 5),
+            comment("Any update?", 30),
+            comment("Another bug, the fix failed", 1),
+        ]);
+
+        assert_eq!(sentiment.comment_count, 5);
+        assert_eq!((sentiment.positive_comments, sentiment.negative_comments), (1, 3));
+        assert!((sentiment.overall_score - (-2.0 / 5.0)).abs() < 1e-9);
+        assert_eq!(sentiment.trend, SentimentTrend::Declining);
+        assert!(!sentiment.needs_alert());
+
+        let hostile = CommentSentiment::analyze("T1", &[comment("Broken", 2), comment("Blocked", 1)]);
+        assert!(hostile.needs_alert());
+        assert_eq!(CommentSentiment::analyze("T1", &[]).trend, SentimentTrend::Stable);
+    }
+}
@@ -0,0 +1,166 @@
+//! Textual similarity of task descriptions
+//!
+//! [`TfIdfIndex`] keeps the term counts of every indexed description in memory
+//! and ranks them against a query text by cosine similarity of their TF-IDF
+//! vectors. Inverse document frequencies are computed at query time, so adding
+//! or removing a document never requires rebuilding the index.
+
+use std::collections::HashMap;
+
+/// Terms shorter than this carry too little meaning to be indexed
+const MIN_TERM_LENGTH: usize = 2;
+
+/// In-memory TF-IDF index keyed by document id
+#[derive(Debug, Clone, Default)]
+pub struct TfIdfIndex {
+    /// Term counts of each indexed document
+    documents: HashMap<String, HashMap<String, u32>>,
+    /// Number of documents containing each term
+    document_frequency: HashMap<String, usize>,
+}
+
+impl TfIdfIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index `text` under `id`, replacing any previous text for that id
+    ///
+    /// Texts without any indexable term are not stored.
+    pub fn add_document(&mut self, id: &str, text: &str) {
+        self.remove_document(id);
+
+        let term_counts = term_counts(text);
+        if term_counts.is_empty() {
+            return;
+        }
+        for term in term_counts.keys() {
+            *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+        self.documents.insert(id.to_string(), term_counts);
+    }
+
+    pub fn remove_document(&mut self, id: &str) {
+        let Some(term_counts) = self.documents.remove(id) else {
+            return;
+        };
+        for term in term_counts.keys() {
+            if let Some(frequency) = self.document_frequency.get_mut(term) {
+                *frequency -= 1;
+                if *frequency == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+    }
+
+    /// Ids of the documents sharing terms with `text`, most similar first
+    ///
+    /// Scores are cosine similarities in `(0, 1]`; documents without any term
+    /// in common with the query are left out.
+    pub fn query(&self, text: &str) -> Vec<(String, f64)> {
+        let query_vector = self.weights(&term_counts(text));
+        let query_norm = norm(&query_vector);
+        if query_norm == 0.0 {
+            return Vec::new();
+        }
+
+        let mut scores: Vec<(String, f64)> = self.documents.iter()
+            .filter_map(|(id, term_counts)| {
+                let document_vector = self.weights(term_counts);
+                let dot: f64 = query_vector.iter()
+                    .filter_map(|(term, weight)| document_vector.get(term).map(|w| w * weight))
+                    .sum();
+                if dot <= 0.0 {
+                    return None;
+                }
+                Some((id.clone(), dot / (query_norm * norm(&document_vector))))
+            })
+            .collect();
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scores
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// TF-IDF weight of every term, with smoothed IDF so unseen terms still count
+    fn weights(&self, term_counts: &HashMap<String, u32>) -> HashMap<String, f64> {
+        let total: u32 = term_counts.values().sum();
+        let documents = self.documents.len() as f64;
+
+        term_counts.iter()
+            .map(|(term, count)| {
+                let frequency = self.document_frequency.get(term).copied().unwrap_or(0) as f64;
+                let idf = ((1.0 + documents) / (1.0 + frequency)).ln() + 1.0;
+                (term.clone(), *count as f64 / total as f64 * idf)
+            })
+            .collect()
+    }
+}
+
+/// Lowercased alphanumeric terms of `text` with their number of occurrences
+fn term_counts(text: &str) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for term in text.split(|c: char| !c.is_alphanumeric()) {
+        if term.chars().count() < MIN_TERM_LENGTH {
+            continue;
+        }
+        *counts.entry(term.to_lowercase()).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn norm(vector: &HashMap<String, f64>) -> f64 {
+    vector.values().map(|w| w * w).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> TfIdfIndex {
+        let mut index = TfIdfIndex::new();
+        index.add_document("login", "Fix the login page redirect after password reset");
+        index.add_document("invoice", "Generate monthly invoice PDF for customers");
+        index.add_document("oauth", "Add OAuth login for the admin page");
+        index
+    }
+
+    #[test]
+    fn test_query_ranks_by_cosine_similarity() {
+        let results = index().query("login page redirect");
+
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["login", "oauth"]);
+        assert!(results[0].1 > results[1].1);
+        assert!(results.iter().all(|(_, score)| *score > 0.0 && *score <= 1.0 + f64::EPSILON));
+    }
+
+    #[test]
+    fn test_identical_text_scores_one() {
+        let results = index().query("Generate monthly invoice PDF for customers");
+
+        assert_eq!(results[0].0, "invoice");
+        assert!((results[0].1 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_remove_and_replace_documents() {
+        let mut index = index();
+
+        index.remove_document("login");
+        index.add_document("oauth", "Rotate database credentials");
+
+        assert_eq!(index.len(), 2);
+        assert!(index.query("login page").is_empty());
+        assert_eq!(index.query("database credentials")[0].0, "oauth");
+        assert!(index.document_frequency.get("login").is_none());
+    }
+}
@@ -0,0 +1,42 @@
+//! Port to the service owning users
+//!
+//! Tasks are only assigned to users the [`UserServicePort`] knows, instead of
+//! trusting whatever user id a caller sends. [`User`] is the user service's
+//! record, not the [`User`](super::models::User) node stored in the graph.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tyl_errors::TylResult;
+
+use crate::TaskServiceError;
+
+/// A user as described by the user service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub team_id: Option<String>,
+    /// Tasks the user may work on at the same time
+    pub max_concurrent_tasks: u32,
+}
+
+#[async_trait]
+pub trait UserServicePort {
+    /// The user with `user_id`, `None` when the user service does not know it
+    async fn get_user(&self, user_id: &str) -> TylResult<Option<User>>;
+}
+
+/// Fail unless `user_service`, if there is one, knows the user
+pub async fn ensure_user_known(user_service: Option<&(dyn UserServicePort + Send + Sync)>, user_id: &str) -> TylResult<()> {
+    let Some(user_service) = user_service else {
+        return Ok(());
+    };
+    match user_service.get_user(user_id).await {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(TaskServiceError::UserNotFound { id: user_id.to_string() }.into()),
+        Err(e) => Err(TaskServiceError::ExternalService {
+            message: format!("Failed to look up user '{}': {}", user_id, e),
+        }.into()),
+    }
+}
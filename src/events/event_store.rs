@@ -0,0 +1,342 @@
+//! Event store recording every change made to a task
+//!
+//! [`TaskDomainService`](crate::domain::TaskDomainService) appends an event to
+//! the [`TaskEventStore`] before each write of a task to the task graph. The
+//! events of a task are numbered from 1 in the order they were stored, so its
+//! state at any point can be rebuilt by folding them with
+//! [`TaskEventApplier`].
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tyl_errors::{TylError, TylResult};
+use uuid::Uuid;
+
+use crate::adapters::cypher::{cypher_params, CypherParams};
+use crate::adapters::RetryingFalkorDBAdapter;
+use crate::domain::{CustomPropertiesPatch, Task, TaskAttachment, TaskStatus, UpdateTaskRequest};
+use crate::telemetry;
+
+/// A task was created; the payload is the new [`Task`]
+pub const TASK_CREATED_EVENT: &str = "task.created";
+/// Fields of a task were edited; the payload is the [`UpdateTaskRequest`]
+pub const TASK_UPDATED_EVENT: &str = "task.updated";
+/// Custom properties were merge-patched; the payload is the [`CustomPropertiesPatch`]
+pub const TASK_CUSTOM_PROPERTIES_PATCHED_EVENT: &str = "task.custom_properties_patched";
+/// The status moved; the payload is a [`StatusChange`]
+pub const TASK_STATUS_CHANGED_EVENT: &str = "task.status_changed";
+/// A success criterion was ticked or unticked; the payload is a [`CriterionChange`]
+pub const TASK_CRITERION_UPDATED_EVENT: &str = "task.success_criterion_updated";
+/// A file was attached; the payload is the [`TaskAttachment`]
+pub const TASK_ATTACHMENT_ADDED_EVENT: &str = "task.attachment_added";
+/// A tag was added; the payload is a [`TagChange`]
+pub const TASK_TAGGED_EVENT: &str = "task.tagged";
+/// A tag was removed; the payload is a [`TagChange`]
+pub const TASK_UNTAGGED_EVENT: &str = "task.untagged";
+/// The whole task was overwritten; the payload is the resulting [`Task`]
+pub const TASK_REPLACED_EVENT: &str = "task.replaced";
+/// The task was deleted; the payload is empty
+pub const TASK_DELETED_EVENT: &str = "task.deleted";
+
+/// An event of the store, in the order of its task's events
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredEvent {
+    pub event_id: Uuid,
+    /// ID of the task the event belongs to
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// Position among the events of the task, starting at 1
+    pub sequence: u64,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Payload of [`TASK_STATUS_CHANGED_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+}
+
+/// Payload of [`TASK_CRITERION_UPDATED_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriterionChange {
+    pub index: usize,
+    pub completed: bool,
+}
+
+/// Payload of [`TASK_TAGGED_EVENT`] and [`TASK_UNTAGGED_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagChange {
+    pub tag: String,
+}
+
+/// Append-only log of task events
+#[async_trait]
+pub trait TaskEventStore: Send + Sync {
+    /// Store an event as the next one of `aggregate_id`
+    async fn append(&self, aggregate_id: &str, event_type: &str, payload: serde_json::Value) -> TylResult<StoredEvent>;
+
+    /// Events of `aggregate_id` in order, stopping after `up_to_sequence` if given
+    async fn load_events(&self, aggregate_id: &str, up_to_sequence: Option<u64>) -> TylResult<Vec<StoredEvent>>;
+}
+
+/// Event store kept in memory, for tests and single-instance setups
+#[derive(Clone, Default)]
+pub struct InMemoryTaskEventStore {
+    events: Arc<RwLock<HashMap<String, Vec<StoredEvent>>>>,
+}
+
+impl InMemoryTaskEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TaskEventStore for InMemoryTaskEventStore {
+    async fn append(&self, aggregate_id: &str, event_type: &str, payload: serde_json::Value) -> TylResult<StoredEvent> {
+        let mut events = self.events.write().unwrap();
+        let stream = events.entry(aggregate_id.to_string()).or_default();
+        let event = StoredEvent {
+            event_id: Uuid::new_v4(),
+            aggregate_id: aggregate_id.to_string(),
+            event_type: event_type.to_string(),
+            payload,
+            sequence: stream.len() as u64 + 1,
+            occurred_at: Utc::now(),
+        };
+        stream.push(event.clone());
+        Ok(event)
+    }
+
+    async fn load_events(&self, aggregate_id: &str, up_to_sequence: Option<u64>) -> TylResult<Vec<StoredEvent>> {
+        let events = self.events.read().unwrap();
+        Ok(events.get(aggregate_id)
+            .map(|stream| stream.iter()
+                .filter(|event| up_to_sequence.map_or(true, |last| event.sequence <= last))
+                .cloned()
+                .collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Event store keeping `(:TaskEvent)` nodes in a graph of their own
+pub struct GraphTaskEventStore {
+    adapter: Arc<RetryingFalkorDBAdapter>,
+    graph_name: String,
+}
+
+impl GraphTaskEventStore {
+    pub fn new(adapter: RetryingFalkorDBAdapter, graph_name: String) -> Self {
+        Self {
+            adapter: Arc::new(adapter),
+            graph_name,
+        }
+    }
+
+    async fn execute_cypher(&self, query: &str) -> TylResult<serde_json::Value> {
+        telemetry::time_graph_query(telemetry::cypher_query_type(query), self.adapter.execute_cypher(query)).await
+    }
+
+    async fn execute_cypher_with_params(&self, query: &str, params: CypherParams) -> TylResult<serde_json::Value> {
+        telemetry::time_graph_query(
+            telemetry::cypher_query_type(query),
+            self.adapter.execute_cypher_with_params(query, params),
+        ).await
+    }
+
+    /// Index the events by task; safe to run on every startup
+    pub async fn ensure_schema(&self) -> TylResult<()> {
+        let statement = "CREATE INDEX ON :TaskEvent(aggregate_id)";
+        if let Err(e) = self.execute_cypher(statement).await {
+            if e.to_string().to_lowercase().contains("already") {
+                tracing::debug!("Event index on graph '{}' already exists", self.graph_name);
+                return Ok(());
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskEventStore for GraphTaskEventStore {
+    async fn append(&self, aggregate_id: &str, event_type: &str, payload: serde_json::Value) -> TylResult<StoredEvent> {
+        let event_id = Uuid::new_v4();
+        let occurred_at = Utc::now();
+        // The sequence is taken in the same query so concurrent appends cannot share one
+        let query = "OPTIONAL MATCH (e:TaskEvent {aggregate_id: $aggregate_id})
+             WITH coalesce(max(e.sequence), 0) + 1 AS sequence
+             CREATE (:TaskEvent {event_id: $event_id, aggregate_id: $aggregate_id, event_type: $event_type, payload: $payload, sequence: sequence, occurred_at: $occurred_at})
+             RETURN sequence";
+        let params = cypher_params([
+            ("aggregate_id", serde_json::json!(aggregate_id)),
+            ("event_id", serde_json::json!(event_id.to_string())),
+            ("event_type", serde_json::json!(event_type)),
+            ("payload", serde_json::json!(payload.to_string())),
+            ("occurred_at", serde_json::json!(occurred_at.to_rfc3339())),
+        ]);
+        let result = self.execute_cypher_with_params(query, params).await?;
+        let sequence = result.as_array()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get("sequence"))
+            .and_then(|sequence| sequence.as_u64())
+            .ok_or_else(|| TylError::database(format!("No sequence returned for event of task {}", aggregate_id)))?;
+
+        Ok(StoredEvent {
+            event_id,
+            aggregate_id: aggregate_id.to_string(),
+            event_type: event_type.to_string(),
+            payload,
+            sequence,
+            occurred_at,
+        })
+    }
+
+    async fn load_events(&self, aggregate_id: &str, up_to_sequence: Option<u64>) -> TylResult<Vec<StoredEvent>> {
+        let limit = up_to_sequence
+            .map(|last| format!("WHERE e.sequence <= {}", last))
+            .unwrap_or_default();
+        let query = format!(
+            "MATCH (e:TaskEvent {{aggregate_id: $aggregate_id}}) {}
+             RETURN e.event_id AS event_id, e.event_type AS event_type, e.payload AS payload,
+                    e.sequence AS sequence, e.occurred_at AS occurred_at
+             ORDER BY e.sequence",
+            limit
+        );
+        let result = self.execute_cypher_with_params(&query, cypher_params([("aggregate_id", serde_json::json!(aggregate_id))])).await?;
+
+        result.as_array()
+            .map(|rows| rows.iter().map(|row| event_from_row(aggregate_id, row)).collect())
+            .unwrap_or_else(|| Ok(Vec::new()))
+    }
+}
+
+/// Read a row returned by `GraphTaskEventStore::load_events`
+fn event_from_row(aggregate_id: &str, row: &serde_json::Value) -> TylResult<StoredEvent> {
+    let text = |column: &str| row.get(column).and_then(|value| value.as_str())
+        .ok_or_else(|| TylError::database(format!("Event of task {} has no {}", aggregate_id, column)));
+
+    Ok(StoredEvent {
+        event_id: Uuid::parse_str(text("event_id")?)
+            .map_err(|e| TylError::database(format!("Invalid event id: {}", e)))?,
+        aggregate_id: aggregate_id.to_string(),
+        event_type: text("event_type")?.to_string(),
+        payload: serde_json::from_str(text("payload")?)
+            .map_err(|e| TylError::database(format!("Invalid event payload: {}", e)))?,
+        sequence: row.get("sequence").and_then(|value| value.as_u64()).unwrap_or_default(),
+        occurred_at: DateTime::parse_from_rfc3339(text("occurred_at")?)
+            .map_err(|e| TylError::database(format!("Invalid event time: {}", e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+/// Rebuilds task state from its events
+pub struct TaskEventApplier;
+
+impl TaskEventApplier {
+    /// Fold the events of a task, which must start with its creation
+    pub fn replay(events: Vec<StoredEvent>) -> TylResult<Task> {
+        let mut events = events.into_iter();
+        let first = events.next()
+            .ok_or_else(|| TylError::validation("events", "No events to replay"))?;
+        if first.event_type != TASK_CREATED_EVENT {
+            return Err(TylError::validation(
+                "event_type",
+                format!("History of task {} starts with {} instead of {}", first.aggregate_id, first.event_type, TASK_CREATED_EVENT),
+            ));
+        }
+        let initial: Task = payload(&first)?;
+        events.try_fold(initial, Self::apply)
+    }
+
+    /// State of the task once `event` happened to `state`
+    ///
+    /// Every event but the deletion stands for one save of the task, so it
+    /// moves the version on as the repository does.
+    pub fn apply(mut state: Task, event: StoredEvent) -> TylResult<Task> {
+        match event.event_type.as_str() {
+            TASK_CREATED_EVENT | TASK_REPLACED_EVENT => return payload(&event),
+            TASK_DELETED_EVENT => return Ok(state),
+            TASK_UPDATED_EVENT => {
+                let request: UpdateTaskRequest = payload(&event)?;
+                state.apply_update(request);
+                state.updated_at = event.occurred_at;
+            }
+            TASK_CUSTOM_PROPERTIES_PATCHED_EVENT => {
+                let patch: CustomPropertiesPatch = payload(&event)?;
+                state.apply_custom_properties_patch(&patch);
+                state.updated_at = event.occurred_at;
+            }
+            TASK_STATUS_CHANGED_EVENT => {
+                let change: StatusChange = payload(&event)?;
+                state.set_status_at(change.to, event.occurred_at);
+            }
+            TASK_CRITERION_UPDATED_EVENT => {
+                let change: CriterionChange = payload(&event)?;
+                state.set_success_criterion_completed(change.index, change.completed)
+                    .ok_or_else(|| TylError::not_found("success criterion", &change.index.to_string()))?;
+            }
+            TASK_ATTACHMENT_ADDED_EVENT => {
+                let attachment: TaskAttachment = payload(&event)?;
+                state.attachments.push(attachment);
+                state.updated_at = event.occurred_at;
+            }
+            TASK_TAGGED_EVENT => {
+                let change: TagChange = payload(&event)?;
+                if !state.tags.iter().any(|tag| tag.eq_ignore_ascii_case(&change.tag)) {
+                    state.tags.push(change.tag);
+                }
+            }
+            TASK_UNTAGGED_EVENT => {
+                let change: TagChange = payload(&event)?;
+                state.tags.retain(|tag| !tag.eq_ignore_ascii_case(&change.tag));
+            }
+            other => {
+                return Err(TylError::validation("event_type", format!("Unknown task event type '{}'", other)));
+            }
+        }
+        state.version += 1;
+        Ok(state)
+    }
+}
+
+/// Decode the payload of `event`
+fn payload<T: serde::de::DeserializeOwned>(event: &StoredEvent) -> TylResult<T> {
+    serde_json::from_value(event.payload.clone()).map_err(|e| TylError::validation(
+        "payload",
+        format!("Invalid payload of {} event {} of task {}: {}", event.event_type, event.sequence, event.aggregate_id, e),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskContext;
+
+    #[tokio::test]
+    async fn test_replay_applies_events_in_order() {
+        let store = InMemoryTaskEventStore::new();
+        let task = Task::new("T-1".to_string(), "Write docs".to_string(), TaskContext::Work);
+        store.append("T-1", TASK_CREATED_EVENT, serde_json::to_value(&task).unwrap()).await.unwrap();
+        let change = StatusChange { from: TaskStatus::Backlog, to: TaskStatus::Ready };
+        store.append("T-1", TASK_STATUS_CHANGED_EVENT, serde_json::to_value(change).unwrap()).await.unwrap();
+        let tag = TagChange { tag: "docs".to_string() };
+        store.append("T-1", TASK_TAGGED_EVENT, serde_json::to_value(tag).unwrap()).await.unwrap();
+
+        let events = store.load_events("T-1", None).await.unwrap();
+        assert_eq!(events.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![1, 2, 3]);
+        let state = TaskEventApplier::replay(events).unwrap();
+        assert_eq!(state.status, TaskStatus::Ready);
+        assert_eq!(state.tags, vec!["docs"]);
+        assert_eq!(state.version, 2);
+
+        let state = TaskEventApplier::replay(store.load_events("T-1", Some(2)).await.unwrap()).unwrap();
+        assert!(state.tags.is_empty());
+        assert!(TaskEventApplier::replay(Vec::new()).is_err());
+    }
+}
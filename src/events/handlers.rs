@@ -1,8 +1,12 @@
 //! Domain event handlers and utilities
 
 use async_trait::async_trait;
+use std::sync::Arc;
 use tyl_pubsub_port::{Event, EventHandler, HandlerResult, HandlerError, RetryPolicy};
 
+use crate::domain::{TaskService, TaskStatus};
+use crate::events::TaskStatusChanged;
+
 /// Convenience type alias for event handler results
 pub type EventHandlerResult = HandlerResult;
 
@@ -78,6 +82,34 @@ where
     }
 }
 
+/// Resumes blocked dependents once a task is completed
+pub struct TaskAutoUnblockerHandler {
+    service: Arc<dyn TaskService + Send + Sync>,
+}
+
+impl TaskAutoUnblockerHandler {
+    pub fn new(service: Arc<dyn TaskService + Send + Sync>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler<TaskStatusChanged> for TaskAutoUnblockerHandler {
+    async fn handle_domain_event(&self, event: TaskStatusChanged) -> HandlerResult {
+        if event.new_status != TaskStatus::Done {
+            return Ok(());
+        }
+
+        self.service
+            .transition_dependent_tasks_on_completion(&event.task_id)
+            .await
+            .map(|_| ())
+            .map_err(|e| HandlerError::TemporaryFailure {
+                reason: format!("Failed to unblock dependents of task '{}': {}", event.task_id, e),
+            })
+    }
+}
+
 /// Create a boxed domain event handler
 /// 
 /// Example: `domain_handler!(MyHandler::new())`
@@ -142,6 +174,34 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_auto_unblocker_resumes_blocked_dependents() {
+        use crate::domain::{DependencyType, MockTaskService};
+
+        let service = Arc::new(MockTaskService::new());
+        let mut blocked = crate::domain::Task::new("B".to_string(), "Blocked".to_string(), crate::domain::TaskContext::Work);
+        blocked.status = TaskStatus::Blocked;
+        let mut done = crate::domain::Task::new("A".to_string(), "Done".to_string(), crate::domain::TaskContext::Work);
+        done.status = TaskStatus::Done;
+        service.insert_task(blocked);
+        service.insert_task(done);
+        service.add_task_dependency("B", "A", DependencyType::Requires).await.unwrap();
+
+        let handler = TaskAutoUnblockerHandler::new(service.clone());
+        let event = TaskStatusChanged {
+            task_id: "A".to_string(),
+            previous_status: TaskStatus::Review,
+            new_status: TaskStatus::Done,
+            changed_by: None,
+            comment: None,
+            changed_at: chrono::Utc::now(),
+        };
+        handler.handle_domain_event(event).await.unwrap();
+
+        let resumed = service.get_task_by_id("B").await.unwrap().unwrap();
+        assert_eq!(resumed.status, TaskStatus::InProgress);
+    }
+
     #[tokio::test]
     async fn test_adapter() {
         let handler = DomainEventHandlerAdapter::new(TestHandler);
@@ -5,6 +5,8 @@
 //! - Task-specific domain events for event-driven communication
 //! - Event handler trait and base implementations
 //! - Event routing and dispatching
+//! - Outbound webhook delivery
+//! - Event store and replay of task history
 //!
 //! ## Quick Start
 //!
@@ -31,11 +33,19 @@ pub mod service;
 pub mod handlers;
 pub mod examples;
 pub mod task_events;
+pub mod webhook;
+pub mod event_store;
 
 // Re-export commonly used types
-pub use service::EventService;
-pub use handlers::{DomainEventHandler, EventHandlerResult};
+pub use service::{
+    event_metadata, BatchPublishResult, DeadLetter, EventService, CORRELATION_ID_METADATA_KEY, EVENT_METADATA_FIELD,
+};
+pub use handlers::{DomainEventHandler, EventHandlerResult, TaskAutoUnblockerHandler};
 pub use task_events::*;
+pub use webhook::{WebhookDeliveryLog, WebhookDeliveryService};
+pub use event_store::{
+    GraphTaskEventStore, InMemoryTaskEventStore, StoredEvent, TaskEventApplier, TaskEventStore,
+};
 
 // Re-export tyl-pubsub-port types for convenience
 pub use tyl_pubsub_port::{
@@ -1,9 +1,52 @@
 //! Event service for publishing and managing events
 
-use crate::{TaskServiceError, TaskServiceResult};
+use crate::events::DomainEvent;
+use crate::middleware::correlation::{current_correlation_id, in_current_correlation};
+use crate::{telemetry, TaskServiceError, TaskServiceResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
-use std::sync::Arc;
-use tyl_pubsub_port::{EventPublisher, EventSubscriber, EventHandler, MockPubSubAdapter, EventId, SubscriptionId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tyl_pubsub_port::{
+    Event, EventPublisher, EventSubscriber, EventHandler, HandlerResult, MockPubSubAdapter, EventId, SubscriptionId,
+};
+
+/// Payload field carrying [`event_metadata`] of events published during a request
+///
+/// The pub/sub port builds the `Event` envelope itself, so the metadata
+/// travels next to the payload fields. The underscore keeps it clear of
+/// payloads with a `metadata` field of their own.
+pub const EVENT_METADATA_FIELD: &str = "_metadata";
+pub const CORRELATION_ID_METADATA_KEY: &str = "correlation_id";
+
+/// Batch size used until `with_batch_publish_max_size` says otherwise
+const DEFAULT_BATCH_PUBLISH_MAX_SIZE: usize = 100;
+/// Events in flight at once until `with_channel_depth` says otherwise
+const DEFAULT_EVENT_CHANNEL_DEPTH: usize = 1000;
+/// Wait for room in the channel until `with_publish_timeout` says otherwise
+const DEFAULT_PUBLISH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Outcome of `EventService::publish_batch`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchPublishResult {
+    pub published: u32,
+    /// `(topic, error)` for every event that could not be published
+    pub failed: Vec<(String, String)>,
+}
+
+/// Event that could not be published, kept for inspection and replay
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
 
 /// Event service that provides publishing and subscription capabilities
 /// 
@@ -14,6 +57,14 @@ where
     A: EventPublisher + EventSubscriber + Send + Sync + 'static,
 {
     adapter: Arc<A>,
+    dead_letters: Mutex<Vec<DeadLetter>>,
+    batch_publish_max_size: usize,
+    /// One permit per event that may be in flight, so bursts wait instead of piling up
+    channel_slots: Semaphore,
+    channel_depth: usize,
+    publish_timeout: Duration,
+    /// Publishes left running by `spawn_background_publish`, awaited on shutdown
+    background_publishes: Mutex<JoinSet<()>>,
 }
 
 impl EventService<MockPubSubAdapter> {
@@ -23,9 +74,7 @@ impl EventService<MockPubSubAdapter> {
     pub async fn new() -> TaskServiceResult<Self> {
         let adapter = Arc::new(MockPubSubAdapter::new());
         
-        Ok(Self {
-            adapter,
-        })
+        Ok(Self::with_adapter(adapter))
     }
 }
 
@@ -37,9 +86,34 @@ where
     pub fn with_adapter(adapter: Arc<A>) -> Self {
         Self {
             adapter,
+            dead_letters: Mutex::new(Vec::new()),
+            batch_publish_max_size: DEFAULT_BATCH_PUBLISH_MAX_SIZE,
+            channel_slots: Semaphore::new(DEFAULT_EVENT_CHANNEL_DEPTH),
+            channel_depth: DEFAULT_EVENT_CHANNEL_DEPTH,
+            publish_timeout: DEFAULT_PUBLISH_TIMEOUT,
+            background_publishes: Mutex::new(JoinSet::new()),
         }
     }
 
+    /// Limit how many events a single `publish_batch` call accepts
+    pub fn with_batch_publish_max_size(mut self, max_size: usize) -> Self {
+        self.batch_publish_max_size = max_size;
+        self
+    }
+
+    /// Limit how many events can be in flight at once; further publishers wait for room
+    pub fn with_channel_depth(mut self, depth: usize) -> Self {
+        self.channel_slots = Semaphore::new(depth);
+        self.channel_depth = depth;
+        self
+    }
+
+    /// How long `publish` and `publish_with_key` wait for room in the channel
+    pub fn with_publish_timeout(mut self, timeout: Duration) -> Self {
+        self.publish_timeout = timeout;
+        self
+    }
+
     /// Publish an event to a topic
     /// 
     /// # Arguments
@@ -75,12 +149,29 @@ where
     where
         T: Serialize + Send + Sync,
     {
-        self.adapter
-            .publish(topic, event)
-            .await
-            .map_err(|e| TaskServiceError::ExternalService {
+        self.publish_with_timeout(topic, event, self.publish_timeout).await
+    }
+
+    /// Publish an event, failing when the channel has no room for it within `timeout`
+    ///
+    /// Fails with [`TaskServiceError::EventPublishing`] ("Event channel full")
+    /// instead of waiting indefinitely behind a burst of other publishers.
+    pub async fn publish_with_timeout<T>(&self, topic: &str, event: T, timeout: Duration) -> TaskServiceResult<EventId>
+    where
+        T: Serialize + Send + Sync,
+    {
+        let publish = async move {
+            let result = match with_event_metadata(&event) {
+                Some(event) => self.adapter.publish(topic, event).await,
+                None => self.adapter.publish(topic, event).await,
+            };
+            result.map_err(|e| TaskServiceError::ExternalService {
                 message: format!("Failed to publish event to topic '{}': {}", topic, e),
             })
+        };
+        let result = self.publish_in_channel(topic, timeout, publish).await;
+        record_publish(topic, &result);
+        result
     }
 
     /// Publish an event with a partition key for ordered processing
@@ -88,12 +179,127 @@ where
     where
         T: Serialize + Send + Sync,
     {
-        self.adapter
-            .publish_with_key(topic, key, event)
-            .await
-            .map_err(|e| TaskServiceError::ExternalService {
+        let publish = async move {
+            let result = match with_event_metadata(&event) {
+                Some(event) => self.adapter.publish_with_key(topic, key, event).await,
+                None => self.adapter.publish_with_key(topic, key, event).await,
+            };
+            result.map_err(|e| TaskServiceError::ExternalService {
                 message: format!("Failed to publish keyed event to topic '{}' with key '{}': {}", topic, key, e),
             })
+        };
+        let result = self.publish_in_channel(topic, self.publish_timeout, publish).await;
+        record_publish(topic, &result);
+        result
+    }
+
+    /// Run `publish` holding a slot of the channel, giving up if no slot frees up within `timeout`
+    ///
+    /// Only the wait for a slot is bounded: once started, the adapter call is
+    /// left to finish so a slow broker is never cut off mid-publish.
+    async fn publish_in_channel<F>(&self, topic: &str, timeout: Duration, publish: F) -> TaskServiceResult<EventId>
+    where
+        F: Future<Output = TaskServiceResult<EventId>>,
+    {
+        let slot = match tokio::time::timeout(timeout, self.channel_slots.acquire()).await {
+            Ok(slot) => slot.expect("the event channel is never closed"),
+            Err(_) => {
+                return Err(TaskServiceError::EventPublishing {
+                    event_type: topic.to_string(),
+                    message: "Event channel full".to_string(),
+                })
+            }
+        };
+        self.record_channel_depth();
+        let result = publish.await;
+        drop(slot);
+        self.record_channel_depth();
+        result
+    }
+
+    /// Run `publish` in the background, in the correlation of the current request
+    ///
+    /// Unlike a bare `tokio::spawn`, the publish is tracked so that
+    /// [`Self::wait_for_background_publishes`] can let it finish on shutdown.
+    pub fn spawn_background_publish<F>(&self, publish: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut background = self.background_publishes.lock().unwrap();
+        // Reap the finished ones so the set only holds publishes still running
+        while background.try_join_next().is_some() {}
+        background.spawn(in_current_correlation(publish));
+    }
+
+    /// Wait up to `timeout` for the background publishes to finish
+    ///
+    /// Returns how many were still running and got cancelled, their
+    /// remaining events are lost.
+    pub async fn wait_for_background_publishes(&self, timeout: Duration) -> usize {
+        let mut background = std::mem::take(&mut *self.background_publishes.lock().unwrap());
+        let drained = tokio::time::timeout(timeout, async {
+            while background.join_next().await.is_some() {}
+        }).await;
+        if drained.is_ok() {
+            return 0;
+        }
+        let cancelled = background.len();
+        background.abort_all();
+        cancelled
+    }
+
+    fn record_channel_depth(&self) {
+        let in_flight = self.channel_depth.saturating_sub(self.channel_slots.available_permits());
+        telemetry::record_event_channel_depth(in_flight, self.channel_depth);
+    }
+
+    /// Publish several events in one call
+    ///
+    /// Events are published in order; one failing does not stop the rest.
+    /// Failed events are moved to the dead letter queue and reported in the
+    /// result instead of failing the whole batch.
+    pub async fn publish_batch(&self, events: Vec<(String, serde_json::Value)>) -> TaskServiceResult<BatchPublishResult> {
+        if events.len() > self.batch_publish_max_size {
+            return Err(TaskServiceError::InvalidInput {
+                field: "events".to_string(),
+                message: format!(
+                    "Cannot publish more than {} events in one batch",
+                    self.batch_publish_max_size
+                ),
+            });
+        }
+
+        // The pub/sub port publishes one event per call, so the batch is sent
+        // sequentially through the same adapter
+        let mut result = BatchPublishResult::default();
+        for (topic, payload) in events {
+            match self.publish(&topic, payload.clone()).await {
+                Ok(_) => result.published += 1,
+                Err(e) => {
+                    let error = e.to_string();
+                    tracing::warn!(topic = %topic, error = %error, "Moving unpublished event to dead letter queue");
+                    self.record_dead_letter(&topic, payload, error.clone());
+                    result.failed.push((topic, error));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Move an event that could not be published or delivered to the dead letter queue
+    pub fn record_dead_letter(&self, topic: &str, payload: serde_json::Value, error: String) {
+        self.dead_letters.lock().unwrap().push(DeadLetter {
+            topic: topic.to_string(),
+            payload,
+            error,
+            failed_at: Utc::now(),
+        });
+    }
+
+    /// Events that could not be published, oldest first
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.lock().unwrap().clone()
     }
 
     /// Subscribe to a topic with an event handler
@@ -148,6 +354,20 @@ where
             })
     }
 
+    /// Subscribe an in-process handler to a domain event
+    ///
+    /// The handler runs in its own task, so a slow reaction never holds up
+    /// the publisher. Failures are logged rather than reported back.
+    pub async fn subscribe_domain_event<E>(
+        &self,
+        handler: Arc<dyn EventHandler<E> + Send + Sync>,
+    ) -> TaskServiceResult<SubscriptionId>
+    where
+        E: DomainEvent,
+    {
+        self.subscribe(E::TOPIC, Box::new(SpawnedHandler { inner: handler })).await
+    }
+
     /// Unsubscribe from a topic
     pub async fn unsubscribe(&self, subscription_id: SubscriptionId) -> TaskServiceResult<()> {
         self.adapter
@@ -159,9 +379,61 @@ where
     }
 }
 
+/// Count a publish attempt in the `event_published_total` or `event_publish_failures_total` metric
+fn record_publish<T>(topic: &str, result: &TaskServiceResult<T>) {
+    match result {
+        Ok(_) => telemetry::record_event_published(topic),
+        Err(_) => telemetry::record_event_publish_failure(),
+    }
+}
+
+/// Metadata of events published now, e.g. the correlation ID of the request being handled
+pub fn event_metadata() -> HashMap<String, String> {
+    current_correlation_id()
+        .map(|id| HashMap::from([(CORRELATION_ID_METADATA_KEY.to_string(), id)]))
+        .unwrap_or_default()
+}
+
+/// Payload with [`event_metadata`] attached, or `None` to publish it unchanged
+///
+/// Only JSON objects can carry the metadata; other payloads, and every
+/// payload outside a request, are published as they are.
+fn with_event_metadata<T: Serialize>(event: &T) -> Option<serde_json::Value> {
+    let metadata = event_metadata();
+    if metadata.is_empty() {
+        return None;
+    }
+    match serde_json::to_value(event).ok()? {
+        serde_json::Value::Object(mut fields) => {
+            fields.insert(EVENT_METADATA_FIELD.to_string(), serde_json::json!(metadata));
+            Some(serde_json::Value::Object(fields))
+        }
+        _ => None,
+    }
+}
+
+/// Runs the wrapped handler on a separate Tokio task
+struct SpawnedHandler<E> {
+    inner: Arc<dyn EventHandler<E> + Send + Sync>,
+}
+
+#[async_trait]
+impl<E: DomainEvent> EventHandler<E> for SpawnedHandler<E> {
+    async fn handle(&self, event: Event<E>) -> HandlerResult {
+        let inner = self.inner.clone();
+        tokio::spawn(in_current_correlation(async move {
+            if let Err(e) = inner.handle(event).await {
+                tracing::warn!(topic = E::TOPIC, error = ?e, "Domain event handler failed");
+            }
+        }));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::middleware::correlation::with_correlation_id;
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -198,4 +470,80 @@ mod tests {
         let result = service.publish_with_key("test.events", "key1", event).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_publish_with_timeout_fails_when_channel_is_full() {
+        let service = EventService::new().await.unwrap().with_channel_depth(0);
+        let event = TestEvent {
+            message: "No room".to_string(),
+        };
+
+        let result = service.publish_with_timeout("test.events", event, Duration::from_millis(10)).await;
+
+        assert!(matches!(
+            result,
+            Err(TaskServiceError::EventPublishing { ref message, .. }) if message == "Event channel full"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_background_publishes_lets_them_finish() {
+        let service = Arc::new(EventService::new().await.unwrap());
+        let publisher = service.clone();
+        service.spawn_background_publish(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let event = TestEvent {
+                message: "Late".to_string(),
+            };
+            publisher.publish("test.events", event).await.unwrap();
+        });
+
+        assert_eq!(service.wait_for_background_publishes(Duration::from_secs(1)).await, 0);
+        assert_eq!(service.wait_for_background_publishes(Duration::from_secs(1)).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_background_publishes_reports_cancelled_ones() {
+        let service = EventService::new().await.unwrap();
+        service.spawn_background_publish(tokio::time::sleep(Duration::from_secs(60)));
+
+        assert_eq!(service.wait_for_background_publishes(Duration::from_millis(10)).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_counts_every_event() {
+        let service = EventService::new().await.unwrap();
+        let events = (0..3)
+            .map(|i| ("test.events".to_string(), serde_json::json!({ "message": i })))
+            .collect();
+
+        let result = service.publish_batch(events).await.unwrap();
+
+        assert_eq!(result.published, 3);
+        assert!(result.failed.is_empty());
+        assert!(service.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_rejects_oversized_batches() {
+        let service = EventService::new().await.unwrap().with_batch_publish_max_size(2);
+        let events = vec![("test.events".to_string(), serde_json::json!({})); 3];
+
+        let result = service.publish_batch(events).await;
+
+        assert!(matches!(result, Err(TaskServiceError::InvalidInput { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_event_metadata_carries_correlation_id_of_request() {
+        let event = TestEvent { message: "hi".to_string() };
+        assert!(with_event_metadata(&event).is_none());
+
+        let payload = with_correlation_id("req-1".to_string(), async { with_event_metadata(&event) }).await.unwrap();
+
+        assert_eq!(payload["message"], "hi");
+        assert_eq!(payload[EVENT_METADATA_FIELD][CORRELATION_ID_METADATA_KEY], "req-1");
+        let not_an_object = with_correlation_id("req-1".to_string(), async { with_event_metadata(&"hi") }).await;
+        assert!(not_an_object.is_none());
+    }
 }
\ No newline at end of file
@@ -5,10 +5,146 @@
 //! and to support features like notifications, audit logging, and analytics.
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::collections::HashMap;
 
-use crate::domain::{TaskStatus, TaskPriority, TaskContext, DependencyType};
+use crate::domain::{TaskStatus, TaskPriority, TaskContext, DependencyType, SentimentTrend};
+
+/// Event published on a fixed topic, allowing typed in-process subscriptions
+pub trait DomainEvent: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Topic the event is published to
+    const TOPIC: &'static str;
+}
+
+impl DomainEvent for TaskCreated {
+    const TOPIC: &'static str = "task.created";
+}
+
+impl DomainEvent for TaskUpdated {
+    const TOPIC: &'static str = "task.updated";
+}
+
+impl DomainEvent for TaskStatusChanged {
+    const TOPIC: &'static str = "task.status_changed";
+}
+
+impl DomainEvent for TasksBulkStatusTransitioned {
+    const TOPIC: &'static str = "task.bulk_status_transitioned";
+}
+
+impl DomainEvent for BatchStatusChanged {
+    const TOPIC: &'static str = "task.batch_status_changed";
+}
+
+impl DomainEvent for TaskAssigned {
+    const TOPIC: &'static str = "task.assigned";
+}
+
+impl DomainEvent for WipLimitViolated {
+    const TOPIC: &'static str = "project.wip_limit_violated";
+}
+
+impl DomainEvent for WipLimitRestored {
+    const TOPIC: &'static str = "project.wip_limit_restored";
+}
+
+impl DomainEvent for ThresholdBreached {
+    const TOPIC: &'static str = "monitoring.threshold_breached";
+}
+
+impl DomainEvent for BottleneckResolved {
+    const TOPIC: &'static str = "analytics.bottleneck_resolved";
+}
+
+impl DomainEvent for ProjectOwnershipTransferred {
+    const TOPIC: &'static str = "project.ownership_transferred";
+}
+
+impl DomainEvent for FocusScoreImproved {
+    const TOPIC: &'static str = "analytics.focus_score_improved";
+}
+
+impl DomainEvent for RecurringTaskComplianceAlert {
+    const TOPIC: &'static str = "analytics.recurring_task_compliance_alert";
+}
+
+impl DomainEvent for DiscussionHealthAlert {
+    const TOPIC: &'static str = "task.discussion_health_alert";
+}
+
+impl DomainEvent for TaskAutoLabeled {
+    const TOPIC: &'static str = "task.auto_labeled";
+}
+
+impl DomainEvent for TaskReviewRequested {
+    const TOPIC: &'static str = "task.review_requested";
+}
+
+impl DomainEvent for TaskTagged {
+    const TOPIC: &'static str = "task.tagged";
+}
+
+impl DomainEvent for TaskRecurrenceScheduled {
+    const TOPIC: &'static str = "task.recurrence.scheduled";
+}
+
+impl DomainEvent for TaskCloned {
+    const TOPIC: &'static str = "task.cloned";
+}
+
+impl DomainEvent for TaskCommented {
+    const TOPIC: &'static str = "task.commented";
+}
+
+impl DomainEvent for TasksMerged {
+    const TOPIC: &'static str = "task.merged";
+}
+
+impl DomainEvent for TaskSplit {
+    const TOPIC: &'static str = "task.split";
+}
+
+impl DomainEvent for DueSoonDigest {
+    const TOPIC: &'static str = "task.due_soon.digest";
+}
+
+impl DomainEvent for TaskAutoPromoted {
+    const TOPIC: &'static str = "task.auto_promoted";
+}
+
+impl DomainEvent for BulkTasksReassigned {
+    const TOPIC: &'static str = "task.bulk_reassigned";
+}
+
+/// Topic of every [`DomainEvent`], e.g. for subscribers interested in all of them
+pub const DOMAIN_EVENT_TOPICS: &[&str] = &[
+    TaskCreated::TOPIC,
+    TaskUpdated::TOPIC,
+    TaskStatusChanged::TOPIC,
+    TasksBulkStatusTransitioned::TOPIC,
+    BatchStatusChanged::TOPIC,
+    TaskAssigned::TOPIC,
+    WipLimitViolated::TOPIC,
+    WipLimitRestored::TOPIC,
+    ThresholdBreached::TOPIC,
+    BottleneckResolved::TOPIC,
+    ProjectOwnershipTransferred::TOPIC,
+    FocusScoreImproved::TOPIC,
+    RecurringTaskComplianceAlert::TOPIC,
+    DiscussionHealthAlert::TOPIC,
+    TaskAutoLabeled::TOPIC,
+    TaskReviewRequested::TOPIC,
+    TaskTagged::TOPIC,
+    TaskRecurrenceScheduled::TOPIC,
+    TaskCloned::TOPIC,
+    TaskCommented::TOPIC,
+    TasksMerged::TOPIC,
+    TaskSplit::TOPIC,
+    DueSoonDigest::TOPIC,
+    TaskAutoPromoted::TOPIC,
+    BulkTasksReassigned::TOPIC,
+];
 
 /// Event published when a new task is created
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +179,24 @@ pub struct TaskStatusChanged {
     pub changed_at: DateTime<Utc>,
 }
 
+/// Event published once for a bulk status transition, listing every task
+/// that changed instead of one `TaskStatusChanged` per task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksBulkStatusTransitioned {
+    pub task_ids: Vec<String>,
+    pub new_status: TaskStatus,
+    pub changed_by: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Event published once for a batch status update, listing every task that changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchStatusChanged {
+    pub task_ids: Vec<String>,
+    pub new_status: TaskStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
 /// Event published when a task is assigned to a user
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskAssigned {
@@ -176,13 +330,101 @@ pub struct TaskPriorityChanged {
 
 /// Event published when a comment is added to a task
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaskCommentAdded {
+pub struct TaskCommented {
     pub task_id: String,
     pub comment_id: String,
-    pub content: String,
     pub author_id: String,
-    pub is_internal: bool, // Whether the comment is internal to the team
-    pub added_at: DateTime<Utc>,
+    pub body: String,
+    pub commented_at: DateTime<Utc>,
+}
+
+/// Event published when completing a recurring task created its next instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRecurrenceScheduled {
+    pub completed_task_id: String,
+    pub next_task_id: String,
+    pub next_due_date: Option<DateTime<Utc>>,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// Event published when a task was created as a copy of another one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCloned {
+    pub source_task_id: String,
+    pub cloned_task_id: String,
+    pub cloned_at: DateTime<Utc>,
+}
+
+/// Event published when a duplicate task was merged into another one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TasksMerged {
+    pub source_id: String,
+    pub target_id: String,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// Event published when an epic was split into new subtasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSplit {
+    pub parent_id: String,
+    pub subtask_ids: Vec<String>,
+    pub split_at: DateTime<Utc>,
+}
+
+/// Periodic digest of open tasks coming due, for notification services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueSoonDigest {
+    pub task_ids: Vec<String>,
+    /// Ids of the due tasks per assigned user; unassigned tasks are only in `task_ids`
+    pub task_ids_by_assignee: HashMap<String, Vec<String>>,
+    pub earliest_due: Option<DateTime<Utc>>,
+    pub lookahead_hours: i64,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Event published when the scheduler moved a backlog task to ready because
+/// every task it depends on was done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskAutoPromoted {
+    pub task_id: String,
+    pub promoted_at: DateTime<Utc>,
+}
+
+/// Event published when every task of a user was moved to another user,
+/// or unassigned when `to_user_id` is `None`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTasksReassigned {
+    pub from_user_id: String,
+    pub to_user_id: Option<String>,
+    pub task_count: usize,
+    pub reassigned_at: DateTime<Utc>,
+}
+
+/// Event published when auto-labelling added tags to a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskAutoLabeled {
+    pub task_id: String,
+    /// Names of the tags added
+    pub tags_added: Vec<String>,
+    pub labeled_at: DateTime<Utc>,
+}
+
+/// Event published when a user is asked to review a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskReviewRequested {
+    pub task_id: String,
+    pub reviewer_id: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// Event published when the comments on a task turn markedly negative
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscussionHealthAlert {
+    pub task_id: String,
+    pub sentiment_score: f64,
+    pub comment_count: u32,
+    pub trend: SentimentTrend,
+    pub detected_at: DateTime<Utc>,
 }
 
 /// Event published when an attachment is added to a task
@@ -239,6 +481,86 @@ pub struct CircularDependencyDetected {
     pub detected_at: DateTime<Utc>,
 }
 
+/// Event published when a status of a project goes over its WIP limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipLimitViolated {
+    pub project_id: String,
+    pub status: TaskStatus,
+    pub current_count: u32,
+    pub limit: u32,
+    pub affected_tasks: Vec<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Event published when a status is back within its WIP limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipLimitRestored {
+    pub project_id: String,
+    pub status: TaskStatus,
+    pub restored_at: DateTime<Utc>,
+}
+
+/// How far past its limit a monitored value is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+/// Event published when a monitored value breaches its alert threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdBreached {
+    pub threshold_name: String,
+    pub current_value: f64,
+    pub threshold_value: f64,
+    pub severity: AlertSeverity,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Event published once every suggested action of a bottleneck is resolved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BottleneckResolved {
+    pub bottleneck_id: String,
+    pub resolved_actions: u32,
+    /// Who resolved the last outstanding action
+    pub resolved_by: Option<String>,
+    pub resolved_at: DateTime<Utc>,
+}
+
+/// Event published when a project gets a new lead
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectOwnershipTransferred {
+    pub project_id: String,
+    pub previous_owner_id: Option<String>,
+    pub new_owner_id: String,
+    pub reason: String,
+    /// Unassigned critical tasks handed to the new lead
+    pub auto_assigned_task_ids: Vec<String>,
+    pub transferred_at: DateTime<Utc>,
+}
+
+/// Event published when a user's focus score improved markedly since last week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusScoreImproved {
+    pub user_id: String,
+    pub focus_score: f64,
+    /// Relative change of the last week's score against the week before
+    pub week_over_week_change: f64,
+    pub measured_at: DateTime<Utc>,
+}
+
+/// Event published when recurring tasks are completed on time less often than the threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringTaskComplianceAlert {
+    pub user_id: Option<String>,
+    pub period_days: u32,
+    pub compliance_rate: f64,
+    pub threshold: f64,
+    pub missed: u32,
+    pub measured_at: DateTime<Utc>,
+}
+
 /// Event published when task analytics are calculated
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskAnalyticsCalculated {
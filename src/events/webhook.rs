@@ -0,0 +1,252 @@
+//! Outbound webhook delivery of domain events
+//!
+//! [`WebhookDeliveryService`] subscribes to the configured topics and `POST`s
+//! every event to the webhook URL as `{"topic", "payload", "sent_at"}`. With a
+//! secret, the body is signed with HMAC-SHA256 and the hex digest is sent as
+//! `X-TYL-Signature: sha256=<digest>`. Failed deliveries are retried with
+//! exponential backoff; events still undelivered after the last attempt are
+//! moved to the event service's dead letter queue.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use utoipa::ToSchema;
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tyl_pubsub_port::{Event, EventHandler, HandlerResult};
+
+use crate::config::WebhookConfig;
+use crate::events::{EventService, DOMAIN_EVENT_TOPICS};
+use crate::TaskServiceResult;
+
+/// Header carrying the HMAC-SHA256 signature of the body
+pub const SIGNATURE_HEADER: &str = "X-TYL-Signature";
+/// Attempts per event before it is dead-lettered
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+/// Wait before the first retry; doubled for every later one
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Delivery attempts kept for `recent_deliveries`
+const MAX_DELIVERY_LOGS: usize = 500;
+
+/// One attempt at delivering an event to the webhook
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookDeliveryLog {
+    pub topic: String,
+    /// 1 for the first attempt at an event, up to [`MAX_DELIVERY_ATTEMPTS`]
+    pub attempt: u32,
+    /// Status the webhook answered with, if it answered
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+    /// Set when the webhook accepted the event
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Body posted to the webhook
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    topic: &'a str,
+    payload: &'a serde_json::Value,
+    sent_at: DateTime<Utc>,
+}
+
+/// Delivers domain events to the configured webhook
+pub struct WebhookDeliveryService {
+    config: WebhookConfig,
+    client: reqwest::Client,
+    event_service: Arc<EventService>,
+    retry_base_delay: Duration,
+    deliveries: Mutex<VecDeque<WebhookDeliveryLog>>,
+}
+
+impl WebhookDeliveryService {
+    pub fn new(config: WebhookConfig, event_service: Arc<EventService>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            client,
+            event_service,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            deliveries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Change the wait before the first retry, e.g. to keep tests fast
+    pub fn with_retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Topics delivered to the webhook
+    pub fn topics(&self) -> Vec<String> {
+        if self.config.events.is_empty() {
+            DOMAIN_EVENT_TOPICS.iter().map(|topic| topic.to_string()).collect()
+        } else {
+            self.config.events.clone()
+        }
+    }
+
+    /// Subscribe to every delivered topic
+    pub async fn start(self: Arc<Self>) -> TaskServiceResult<()> {
+        for topic in self.topics() {
+            let handler = WebhookTopicHandler { topic: topic.clone(), service: self.clone() };
+            self.event_service.subscribe(&topic, Box::new(handler)).await?;
+        }
+        Ok(())
+    }
+
+    /// Deliver one event, retrying until the webhook accepts it
+    ///
+    /// Returns whether the event was delivered; undelivered events end up in
+    /// the dead letter queue.
+    pub async fn deliver(&self, topic: &str, payload: serde_json::Value) -> bool {
+        let body = match serde_json::to_vec(&WebhookPayload { topic, payload: &payload, sent_at: Utc::now() }) {
+            Ok(body) => body,
+            Err(e) => {
+                self.event_service.record_dead_letter(topic, payload, format!("Cannot serialize webhook body: {}", e));
+                return false;
+            }
+        };
+        let signature = self.config.secret.as_deref()
+            .map(|secret| format!("sha256={}", sign_payload(secret.as_bytes(), &body)));
+
+        let mut last_error = String::new();
+        for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+            if attempt > 1 {
+                tokio::time::sleep(self.retry_base_delay * 2u32.pow(attempt - 2)).await;
+            }
+
+            let mut request = self.client.post(&self.config.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(ref signature) = signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let attempted_at = Utc::now();
+            let (status_code, error) = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    self.log(WebhookDeliveryLog {
+                        topic: topic.to_string(),
+                        attempt,
+                        status_code: Some(response.status().as_u16()),
+                        error: None,
+                        attempted_at,
+                        delivered_at: Some(Utc::now()),
+                    });
+                    return true;
+                }
+                Ok(response) => (Some(response.status().as_u16()), format!("Webhook answered {}", response.status())),
+                Err(e) => (None, e.to_string()),
+            };
+            self.log(WebhookDeliveryLog {
+                topic: topic.to_string(),
+                attempt,
+                status_code,
+                error: Some(error.clone()),
+                attempted_at,
+                delivered_at: None,
+            });
+            last_error = error;
+        }
+
+        tracing::warn!(topic = %topic, error = %last_error, "Moving undelivered webhook event to dead letter queue");
+        self.event_service.record_dead_letter(
+            topic,
+            payload,
+            format!("Webhook delivery failed after {} attempts: {}", MAX_DELIVERY_ATTEMPTS, last_error),
+        );
+        false
+    }
+
+    /// The latest delivery attempts, newest first
+    pub fn recent_deliveries(&self, limit: usize) -> Vec<WebhookDeliveryLog> {
+        self.deliveries.lock().unwrap().iter().rev().take(limit).cloned().collect()
+    }
+
+    fn log(&self, entry: WebhookDeliveryLog) {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        if deliveries.len() == MAX_DELIVERY_LOGS {
+            deliveries.pop_front();
+        }
+        deliveries.push_back(entry);
+    }
+}
+
+/// Hex HMAC-SHA256 of `body` keyed with `secret`
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Hands the events of one topic to the delivery service
+struct WebhookTopicHandler {
+    topic: String,
+    service: Arc<WebhookDeliveryService>,
+}
+
+#[async_trait]
+impl EventHandler<serde_json::Value> for WebhookTopicHandler {
+    async fn handle(&self, event: Event<serde_json::Value>) -> HandlerResult {
+        // Retries can take seconds, so they never hold up the publisher
+        let (service, topic) = (self.service.clone(), self.topic.clone());
+        tokio::spawn(async move {
+            service.deliver(&topic, event.payload).await;
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: &str, events: Vec<String>) -> WebhookConfig {
+        WebhookConfig { url: url.to_string(), secret: Some("secret".to_string()), events, timeout_ms: 500 }
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_topics_default_to_every_domain_event() {
+        let event_service = Arc::new(EventService::new().await.unwrap());
+        let service = WebhookDeliveryService::new(config("http://localhost/hook", vec![]), event_service.clone());
+        assert_eq!(service.topics().len(), DOMAIN_EVENT_TOPICS.len());
+
+        let service = WebhookDeliveryService::new(config("http://localhost/hook", vec!["task.created".to_string()]), event_service);
+        assert_eq!(service.topics(), vec!["task.created"]);
+    }
+
+    #[tokio::test]
+    async fn test_undeliverable_event_is_retried_then_dead_lettered() {
+        let event_service = Arc::new(EventService::new().await.unwrap());
+        // Nothing listens on the discard port
+        let service = WebhookDeliveryService::new(config("http://127.0.0.1:9/hook", vec![]), event_service.clone())
+            .with_retry_base_delay(Duration::from_millis(1));
+
+        let delivered = service.deliver("task.created", serde_json::json!({ "task_id": "T-1" })).await;
+
+        assert!(!delivered);
+        let deliveries = service.recent_deliveries(10);
+        assert_eq!(deliveries.len(), MAX_DELIVERY_ATTEMPTS as usize);
+        assert_eq!(deliveries[0].attempt, MAX_DELIVERY_ATTEMPTS);
+        assert!(deliveries.iter().all(|d| d.delivered_at.is_none() && d.error.is_some()));
+        let dead_letters = event_service.dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].payload["task_id"], "T-1");
+    }
+}
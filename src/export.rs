@@ -0,0 +1,228 @@
+//! Streaming task exports
+//!
+//! [`TaskExportService`] turns the task stream of
+//! [`TaskService::export_tasks`] into CSV or newline-delimited JSON chunks,
+//! one task at a time, so an export of the whole graph never sits in memory.
+//! Tasks the caller may not read are skipped.
+
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::sync::Arc;
+use tyl_errors::{TylError, TylResult};
+
+use crate::auth::{Actor, AuthorizationPolicy};
+use crate::domain::{Task, TaskComplexity, TaskContext, TaskFilter, TaskPriority, TaskService, TaskStatus};
+use crate::handlers::tasks::TaskResponse;
+
+/// Columns of a CSV export, in the order of [`CsvTaskRow`]'s fields
+const CSV_COLUMNS: &[&str] = &[
+    "id", "name", "description", "context", "status", "priority", "complexity", "tags",
+    "created_at", "updated_at", "started_at", "completed_at", "due_date", "estimated_date",
+    "is_overdue", "is_actionable",
+];
+
+/// Separator of the tags in the `tags` CSV column
+const CSV_TAG_SEPARATOR: &str = ";";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// Flat view of a task, as one CSV line
+#[derive(Debug, Serialize)]
+struct CsvTaskRow<'a> {
+    id: &'a str,
+    name: &'a str,
+    description: Option<&'a str>,
+    context: TaskContext,
+    status: TaskStatus,
+    priority: TaskPriority,
+    complexity: TaskComplexity,
+    tags: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    due_date: Option<DateTime<Utc>>,
+    estimated_date: Option<DateTime<Utc>>,
+    is_overdue: bool,
+    is_actionable: bool,
+}
+
+impl<'a> From<&'a TaskResponse> for CsvTaskRow<'a> {
+    fn from(task: &'a TaskResponse) -> Self {
+        Self {
+            id: &task.id,
+            name: &task.name,
+            description: task.description.as_deref(),
+            context: task.context,
+            status: task.status,
+            priority: task.priority,
+            complexity: task.complexity,
+            tags: task.tags.join(CSV_TAG_SEPARATOR),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            started_at: task.started_at,
+            completed_at: task.completed_at,
+            due_date: task.due_date,
+            estimated_date: task.estimated_date,
+            is_overdue: task.is_overdue,
+            is_actionable: task.is_actionable,
+        }
+    }
+}
+
+/// Exports of the tasks one caller may read
+pub struct TaskExportService {
+    domain_service: Arc<dyn TaskService + Send + Sync>,
+    authorization_policy: Arc<dyn AuthorizationPolicy + Send + Sync>,
+    actor: Actor,
+}
+
+impl TaskExportService {
+    pub fn new(
+        domain_service: Arc<dyn TaskService + Send + Sync>,
+        authorization_policy: Arc<dyn AuthorizationPolicy + Send + Sync>,
+        actor: Actor,
+    ) -> Self {
+        Self { domain_service, authorization_policy, actor }
+    }
+
+    /// Header line followed by one line per task
+    pub fn stream_csv(&self, filter: TaskFilter) -> impl Stream<Item = TylResult<Bytes>> + Send + 'static {
+        let header = stream::once(async { csv_line(|writer| writer.write_record(CSV_COLUMNS)) });
+        let rows = self.readable_tasks(filter).map(|task| {
+            let task = TaskResponse::from(&task?);
+            csv_line(|writer| writer.serialize(CsvTaskRow::from(&task)))
+        });
+        header.chain(rows)
+    }
+
+    /// One JSON `TaskResponse` per line
+    pub fn stream_ndjson(&self, filter: TaskFilter) -> impl Stream<Item = TylResult<Bytes>> + Send + 'static {
+        self.readable_tasks(filter).map(|task| {
+            let mut line = serde_json::to_vec(&TaskResponse::from(&task?))
+                .map_err(|e| TylError::internal(format!("Failed to serialize task: {}", e)))?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        })
+    }
+
+    pub fn stream(&self, format: ExportFormat, filter: TaskFilter) -> BoxStream<'static, TylResult<Bytes>> {
+        match format {
+            ExportFormat::Csv => self.stream_csv(filter).boxed(),
+            ExportFormat::Ndjson => self.stream_ndjson(filter).boxed(),
+        }
+    }
+
+    fn readable_tasks(&self, filter: TaskFilter) -> BoxStream<'static, TylResult<Task>> {
+        let policy = self.authorization_policy.clone();
+        let actor = self.actor.clone();
+        self.domain_service
+            .export_tasks(filter)
+            .filter_map(move |task| {
+                let policy = policy.clone();
+                let actor = actor.clone();
+                async move {
+                    match task {
+                        Ok(task) => policy.can_read_task(&actor, &task).await.then_some(Ok(task)),
+                        Err(e) => Some(Err(e)),
+                    }
+                }
+            })
+            .boxed()
+    }
+}
+
+/// Bytes of the line `write` adds to a fresh CSV writer
+fn csv_line(write: impl FnOnce(&mut csv::Writer<Vec<u8>>) -> csv::Result<()>) -> TylResult<Bytes> {
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+    write(&mut writer).map_err(|e| TylError::internal(format!("Failed to write CSV: {}", e)))?;
+    let line = writer
+        .into_inner()
+        .map_err(|e| TylError::internal(format!("Failed to write CSV: {}", e)))?;
+    Ok(Bytes::from(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryTaskRepository;
+    use crate::auth::PermissivePolicy;
+    use crate::domain::{TaskDomainService, TaskRepository};
+    use futures::TryStreamExt;
+
+    async fn export_service() -> TaskExportService {
+        let repository = InMemoryTaskRepository::new();
+        for (id, name, status) in [
+            ("T1", "Write, then review", TaskStatus::Backlog),
+            ("T2", "Ship", TaskStatus::Done),
+        ] {
+            let mut task = Task::new(id.to_string(), name.to_string(), TaskContext::Work);
+            task.set_status(status);
+            task.tags = vec!["api".to_string(), "q3".to_string()];
+            repository.save_task(&task).await.unwrap();
+        }
+        let domain_service = Arc::new(TaskDomainService::new(repository));
+        TaskExportService::new(domain_service, Arc::new(PermissivePolicy), Actor::default())
+    }
+
+    async fn collect(stream: impl Stream<Item = TylResult<Bytes>>) -> String {
+        let chunks: Vec<Bytes> = stream.try_collect().await.unwrap();
+        String::from_utf8(chunks.concat()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_csv_writes_header_and_filtered_rows() {
+        let service = export_service().await;
+        let filter = TaskFilter { status: Some(vec![TaskStatus::Backlog]), ..Default::default() };
+
+        let csv = collect(service.stream_csv(filter)).await;
+
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        assert_eq!(reader.headers().unwrap().iter().collect::<Vec<_>>(), CSV_COLUMNS);
+        let rows: Vec<std::collections::HashMap<String, String>> =
+            reader.deserialize().collect::<Result<_, _>>().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["id"], "T1");
+        assert_eq!(rows[0]["name"], "Write, then review");
+        assert_eq!(rows[0]["status"], "backlog");
+        assert_eq!(rows[0]["tags"], "api;q3");
+    }
+
+    #[tokio::test]
+    async fn test_stream_ndjson_writes_one_task_per_line() {
+        let service = export_service().await;
+
+        let ndjson = collect(service.stream_ndjson(TaskFilter::default())).await;
+
+        let ids: Vec<String> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, vec!["T1", "T2"]);
+    }
+}
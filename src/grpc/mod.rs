@@ -0,0 +1,466 @@
+//! gRPC API for internal services
+//!
+//! [`GrpcTaskService`] implements `proto/task_service.proto` on top of the same
+//! [`AppState`] as the HTTP API: requests go to the shared domain service, are
+//! validated and authorized like their REST counterparts and publish the same
+//! domain events. The caller is read from the `x-user-id` metadata.
+
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tonic::{Request, Response, Status};
+use tyl_errors::TylError;
+
+use crate::auth::{Actor, USER_ID_HEADER};
+use crate::config::GrpcConfig;
+use crate::domain::{Task, TaskFilter, TaskService, UpdateTaskRequest};
+use crate::events::{DomainEvent, DomainEventHandler, HandlerResult, TaskCreated, TaskStatusChanged, TaskUpdated};
+use crate::handlers::tasks::{create_task_request, CreateTaskApiRequest};
+use crate::validation::Validate;
+use crate::{AppState, TaskServiceError, TaskServiceResult};
+
+/// Code generated from `proto/task_service.proto`
+pub mod proto {
+    tonic::include_proto!("tyl.tasks.v1");
+
+    /// Descriptors of the generated services, served by gRPC reflection
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("task_service_descriptor");
+}
+
+use proto::task_service_server::{TaskService as TaskServiceRpc, TaskServiceServer};
+
+/// Changes buffered per `StreamTaskUpdates` client before it starts skipping
+const UPDATE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Default page size of `ListTasks`, as in the HTTP API
+const DEFAULT_LIST_LIMIT: usize = 100;
+
+/// A task that changed, by the topic of the domain event that reported it
+#[derive(Debug, Clone)]
+struct TaskChange {
+    event_type: &'static str,
+    task_id: String,
+}
+
+/// Forwards task domain events to the `StreamTaskUpdates` clients
+#[derive(Clone)]
+struct TaskUpdateFeed {
+    sender: broadcast::Sender<TaskChange>,
+}
+
+impl TaskUpdateFeed {
+    fn notify(&self, event_type: &'static str, task_id: String) {
+        // Fails only while no client is streaming
+        let _ = self.sender.send(TaskChange { event_type, task_id });
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler<TaskCreated> for TaskUpdateFeed {
+    async fn handle_domain_event(&self, event: TaskCreated) -> HandlerResult {
+        self.notify(TaskCreated::TOPIC, event.task_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler<TaskUpdated> for TaskUpdateFeed {
+    async fn handle_domain_event(&self, event: TaskUpdated) -> HandlerResult {
+        self.notify(TaskUpdated::TOPIC, event.task_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler<TaskStatusChanged> for TaskUpdateFeed {
+    async fn handle_domain_event(&self, event: TaskStatusChanged) -> HandlerResult {
+        self.notify(TaskStatusChanged::TOPIC, event.task_id);
+        Ok(())
+    }
+}
+
+/// The `tyl.tasks.v1.TaskService` gRPC service
+#[derive(Clone)]
+pub struct GrpcTaskService {
+    state: AppState,
+    updates: broadcast::Sender<TaskChange>,
+}
+
+impl GrpcTaskService {
+    /// Create the service and subscribe it to the task events it streams
+    pub async fn new(state: AppState) -> TaskServiceResult<Self> {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        let feed = TaskUpdateFeed { sender: updates.clone() };
+        state.register_domain_handler::<TaskCreated, _>(feed.clone()).await?;
+        state.register_domain_handler::<TaskUpdated, _>(feed.clone()).await?;
+        state.register_domain_handler::<TaskStatusChanged, _>(feed).await?;
+        Ok(Self { state, updates })
+    }
+
+    fn domain_service(&self) -> &Arc<dyn TaskService + Send + Sync> {
+        &self.state.domain_service
+    }
+
+    async fn find_task(&self, task_id: &str) -> Result<Task, Status> {
+        self.domain_service().get_task_by_id(task_id).await
+            .map_err(domain_status)?
+            .ok_or_else(|| Status::not_found(format!("Task '{}' not found", task_id)))
+    }
+}
+
+/// Serve the gRPC API on `api.host` and `config.port` until `shutdown` completes
+pub async fn spawn_server<F>(state: AppState, config: GrpcConfig, shutdown: F) -> TaskServiceResult<JoinHandle<TaskServiceResult<()>>>
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    // Served on the same interface as the HTTP API
+    let host = state.config.api.host.clone();
+    let address = tokio::net::lookup_host((host.as_str(), config.port)).await
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| TaskServiceError::Configuration {
+            message: format!("Failed to resolve gRPC address {}:{}", host, config.port),
+        })?;
+    let service = GrpcTaskService::new(state).await?;
+    let reflection = if config.reflection {
+        Some(tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
+            .build_v1()
+            .map_err(|e| TaskServiceError::Configuration {
+                message: format!("Failed to build gRPC reflection service: {}", e),
+            })?)
+    } else {
+        None
+    };
+    Ok(tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(TaskServiceServer::new(service))
+            .add_optional_service(reflection)
+            .serve_with_shutdown(address, shutdown)
+            .await
+            .map_err(|e| TaskServiceError::Api {
+                message: format!("gRPC server error: {}", e),
+            })
+    }))
+}
+
+#[tonic::async_trait]
+impl TaskServiceRpc for GrpcTaskService {
+    type StreamTaskUpdatesStream = Pin<Box<dyn Stream<Item = Result<proto::TaskUpdate, Status>> + Send + 'static>>;
+
+    async fn create_task(&self, request: Request<proto::CreateTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let actor = actor_from(&request);
+        let request = request.into_inner();
+        let api_request = CreateTaskApiRequest {
+            name: request.name,
+            description: request.description,
+            context: parse_enum("context", &request.context)?,
+            priority: request.priority.as_deref().map(|p| parse_enum("priority", p)).transpose()?,
+            complexity: request.complexity.as_deref().map(|c| parse_enum("complexity", c)).transpose()?,
+            due_date: request.due_date.as_deref().map(|d| parse_time("due_date", d)).transpose()?,
+            estimated_date: request.estimated_date.as_deref().map(|d| parse_time("estimated_date", d)).transpose()?,
+            implementation_details: None,
+            success_criteria: None,
+            test_strategy: None,
+            source: None,
+            visibility: None,
+            recurrence: None,
+            custom_properties: None,
+            assigned_user_id: request.assigned_user_id,
+            project_id: request.project_id,
+        };
+        let assigned_user_id = api_request.assigned_user_id.clone();
+        let project_id = api_request.project_id.clone();
+        let mut domain_request = create_task_request(api_request, Default::default());
+        domain_request.created_by = actor.user_id;
+        domain_request.validate().map_err(status_from)?;
+
+        let task = self.domain_service().create_task(domain_request).await
+            .map_err(domain_status)?;
+
+        let event = TaskCreated {
+            task_id: task.id.clone(),
+            name: task.name.clone(),
+            context: task.context,
+            priority: task.priority,
+            assigned_user_id,
+            project_id,
+            created_at: task.created_at,
+        };
+        if let Err(e) = self.state.event_service.publish(TaskCreated::TOPIC, event).await {
+            tracing::warn!("Failed to publish task.created event: {}", e);
+        }
+
+        Ok(Response::new(proto::Task::from(&task)))
+    }
+
+    async fn get_task(&self, request: Request<proto::GetTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let actor = actor_from(&request);
+        let task = self.find_task(&request.get_ref().id).await?;
+        if !self.state.authorization_policy.can_read_task(&actor, &task).await {
+            return Err(Status::permission_denied(format!("Not allowed to read task '{}'", task.id)));
+        }
+
+        Ok(Response::new(proto::Task::from(&task)))
+    }
+
+    async fn update_task(&self, request: Request<proto::UpdateTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let actor = actor_from(&request);
+        let request = request.into_inner();
+        let original_task = self.find_task(&request.id).await?;
+        if !self.state.authorization_policy.can_write_task(&actor, &original_task).await {
+            return Err(Status::permission_denied(format!("Not allowed to update task '{}'", original_task.id)));
+        }
+
+        let domain_request = UpdateTaskRequest {
+            name: request.name,
+            description: request.description,
+            priority: request.priority.as_deref().map(|p| parse_enum("priority", p)).transpose()?,
+            complexity: request.complexity.as_deref().map(|c| parse_enum("complexity", c)).transpose()?,
+            due_date: request.due_date.as_deref().map(|d| parse_time("due_date", d)).transpose()?,
+            estimated_date: request.estimated_date.as_deref().map(|d| parse_time("estimated_date", d)).transpose()?,
+            implementation_details: None,
+            success_criteria: None,
+            test_strategy: None,
+            visibility: None,
+            custom_properties: None,
+        };
+        domain_request.validate().map_err(status_from)?;
+
+        let updated_task = self.domain_service().update_task(&request.id, domain_request, None).await
+            .map_err(domain_status)?;
+
+        let event = TaskUpdated {
+            task_id: updated_task.id.clone(),
+            previous_status: original_task.status,
+            current_status: updated_task.status,
+            updated_fields: vec![],
+            updated_at: updated_task.updated_at,
+        };
+        if let Err(e) = self.state.event_service.publish(TaskUpdated::TOPIC, event).await {
+            tracing::warn!("Failed to publish task.updated event: {}", e);
+        }
+
+        Ok(Response::new(proto::Task::from(&updated_task)))
+    }
+
+    async fn list_tasks(&self, request: Request<proto::ListTasksRequest>) -> Result<Response<proto::ListTasksResponse>, Status> {
+        let actor = actor_from(&request);
+        let request = request.into_inner();
+        let filter = TaskFilter {
+            status: parse_enums("status", &request.status)?,
+            context: parse_enums("context", &request.context)?,
+            project_id: request.project_id,
+            assigned_user_id: request.assigned_user_id,
+            limit: Some(request.limit.map_or(DEFAULT_LIST_LIMIT, |limit| limit as usize)),
+            offset: Some(request.offset.unwrap_or(0) as usize),
+            ..TaskFilter::default()
+        };
+
+        let mut tasks = Vec::new();
+        for task in self.domain_service().list_tasks(filter).await.map_err(domain_status)? {
+            if self.state.authorization_policy.can_read_task(&actor, &task).await {
+                tasks.push(proto::Task::from(&task));
+            }
+        }
+
+        Ok(Response::new(proto::ListTasksResponse { tasks }))
+    }
+
+    async fn stream_task_updates(
+        &self,
+        request: Request<proto::StreamTaskUpdatesRequest>,
+    ) -> Result<Response<Self::StreamTaskUpdatesStream>, Status> {
+        let actor = actor_from(&request);
+        let task_ids: HashSet<String> = request.into_inner().task_ids.into_iter().collect();
+        let receiver = self.updates.subscribe();
+        let state = self.state.clone();
+
+        let stream = futures::stream::unfold(receiver, move |mut receiver| {
+            let (state, actor, task_ids) = (state.clone(), actor.clone(), task_ids.clone());
+            async move {
+                loop {
+                    let change = match receiver.recv().await {
+                        Ok(change) => change,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!("gRPC update stream fell behind, skipped {} task changes", skipped);
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    };
+                    if !task_ids.is_empty() && !task_ids.contains(&change.task_id) {
+                        continue;
+                    }
+
+                    let task = match state.domain_service.get_task_by_id(&change.task_id).await {
+                        Ok(Some(task)) => task,
+                        // Deleted since the change
+                        Ok(None) => continue,
+                        Err(e) => return Some((Err(domain_status(e)), receiver)),
+                    };
+                    if !state.authorization_policy.can_read_task(&actor, &task).await {
+                        continue;
+                    }
+                    let update = proto::TaskUpdate {
+                        event_type: change.event_type.to_string(),
+                        task: Some(proto::Task::from(&task)),
+                    };
+                    return Some((Ok(update), receiver));
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+impl From<&Task> for proto::Task {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            description: task.description.clone(),
+            context: enum_name(&task.context),
+            status: enum_name(&task.status),
+            priority: enum_name(&task.priority),
+            complexity: enum_name(&task.complexity),
+            due_date: task.due_date.map(|d| d.to_rfc3339()),
+            estimated_date: task.estimated_date.map(|d| d.to_rfc3339()),
+            created_at: task.created_at.to_rfc3339(),
+            updated_at: task.updated_at.to_rfc3339(),
+            started_at: task.started_at.map(|d| d.to_rfc3339()),
+            completed_at: task.completed_at.map(|d| d.to_rfc3339()),
+            tags: task.tags.clone(),
+            version: task.version,
+        }
+    }
+}
+
+/// Caller named by the `x-user-id` metadata, as the HTTP header
+fn actor_from<T>(request: &Request<T>) -> Actor {
+    request.metadata().get(USER_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map_or_else(Actor::anonymous, Actor::user)
+}
+
+/// The snake_case name an enum has in the HTTP API
+fn enum_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn parse_enum<T: DeserializeOwned>(field: &str, value: &str) -> Result<T, Status> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| Status::invalid_argument(format!("Invalid {} '{}'", field, value)))
+}
+
+/// Filter values, `None` when the request lists none
+fn parse_enums<T: DeserializeOwned>(field: &str, values: &[String]) -> Result<Option<Vec<T>>, Status> {
+    if values.is_empty() {
+        return Ok(None);
+    }
+    values.iter().map(|value| parse_enum(field, value)).collect::<Result<_, _>>().map(Some)
+}
+
+fn parse_time(field: &str, value: &str) -> Result<DateTime<Utc>, Status> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| Status::invalid_argument(format!("Invalid {} '{}', expected an RFC 3339 time", field, value)))
+}
+
+/// Domain errors carry no kind to map, so they fail like in the HTTP API
+fn domain_status(err: TylError) -> Status {
+    Status::internal(err.to_string())
+}
+
+fn status_from(err: TaskServiceError) -> Status {
+    match err {
+        TaskServiceError::ValidationFailed { .. } | TaskServiceError::InvalidInput { .. } => {
+            Status::invalid_argument(err.to_string())
+        }
+        TaskServiceError::TaskNotFound { .. } | TaskServiceError::ProjectNotFound { .. } | TaskServiceError::UserNotFound { .. } => {
+            Status::not_found(err.to_string())
+        }
+        TaskServiceError::InvalidStatusTransition { .. }
+        | TaskServiceError::CircularDependency { .. }
+        | TaskServiceError::DependencyViolation { .. } => Status::failed_precondition(err.to_string()),
+        TaskServiceError::VersionConflict { .. } | TaskServiceError::Concurrency { .. } => Status::aborted(err.to_string()),
+        TaskServiceError::NotAuthorized { .. } | TaskServiceError::Authorization { .. } => {
+            Status::permission_denied(err.to_string())
+        }
+        TaskServiceError::Authentication { .. } => Status::unauthenticated(err.to_string()),
+        TaskServiceError::RateLimitExceeded { .. } => Status::resource_exhausted(err.to_string()),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{MockTaskService, TaskContext};
+    use crate::{EventService, TaskServiceConfig};
+
+    async fn test_state() -> AppState {
+        AppState {
+            config: Arc::new(TaskServiceConfig::default()),
+            domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
+            event_service: Arc::new(EventService::new().await.unwrap()),
+            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grpc_get_and_create_task() {
+        let service = GrpcTaskService::new(test_state().await).await.unwrap();
+
+        let task = service.get_task(Request::new(proto::GetTaskRequest { id: "test-id".to_string() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(task.id, "test-id");
+        assert_eq!(task.status, "backlog");
+
+        let missing = service.get_task(Request::new(proto::GetTaskRequest { id: "missing".to_string() })).await;
+        assert_eq!(missing.unwrap_err().code(), tonic::Code::NotFound);
+
+        let invalid = service.create_task(Request::new(proto::CreateTaskRequest {
+            name: "Task".to_string(),
+            context: "nowhere".to_string(),
+            ..Default::default()
+        })).await;
+        assert_eq!(invalid.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+        let created = service.create_task(Request::new(proto::CreateTaskRequest {
+            name: "gRPC task".to_string(),
+            context: enum_name(&TaskContext::Work),
+            priority: Some("high".to_string()),
+            ..Default::default()
+        })).await.unwrap().into_inner();
+        assert_eq!(created.name, "gRPC task");
+        assert_eq!(created.priority, "high");
+    }
+}
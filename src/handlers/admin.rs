@@ -0,0 +1,275 @@
+//! Administrative HTTP handlers
+//!
+//! Maintenance endpoints guarded by the `X-Admin-Key` header. They are disabled
+//! unless an admin key is configured.
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    archival::{ArchivalReport, ArchivalStatus},
+    domain::{ArchiveResult, SchedulingReport},
+    events::WebhookDeliveryLog,
+    handlers::ApiError,
+    monitoring::{evaluate_thresholds, ThresholdStatus},
+    AppState,
+};
+
+const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ArchiveQueryParams {
+    pub before: DateTime<Utc>,
+    pub batch_size: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveriesQueryParams {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyGraphRebuildResponse {
+    pub dependency_count: usize,
+}
+
+/// Check the admin key header against the configured key
+fn require_admin_key(state: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let expected = state.config.api.admin_api_key.as_deref()
+        .ok_or_else(|| ApiError::new("FORBIDDEN", "Admin endpoints are disabled"))?;
+
+    match headers.get(ADMIN_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+        // Constant time, so response timing doesn't reveal how much of the key matched
+        Some(key) if bool::from(key.as_bytes().ct_eq(expected.as_bytes())) => Ok(()),
+        Some(_) => Err(ApiError::new("FORBIDDEN", "Invalid admin key")),
+        None => Err(ApiError::new("UNAUTHORIZED", "Missing X-Admin-Key header")),
+    }
+}
+
+/// Archive tasks completed before a cutoff date
+///
+/// POST /api/v1/admin/tasks/archive?before=...&batch_size=100
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/tasks/archive",
+    tag = "admin",
+    params(
+        ArchiveQueryParams,
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Outcome of the archival", body = ArchiveResult),
+        ApiError
+    )
+)]
+pub async fn archive_tasks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ArchiveQueryParams>,
+) -> Result<Json<ArchiveResult>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let batch_size = params.batch_size.unwrap_or(100);
+    let result = state.domain_service
+        .archive_completed_tasks_before(params.before, batch_size).await
+        .map_err(ApiError::from)?;
+
+    tracing::info!(
+        archived = result.archived_count,
+        failed = result.failed_ids.len(),
+        "Archived tasks completed before {}", params.before
+    );
+
+    Ok(Json(result))
+}
+
+/// Current alert metrics compared with the configured thresholds
+///
+/// GET /api/v1/admin/alert-status
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/alert-status",
+    tag = "admin",
+    params(
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Alert metrics and their thresholds", body = Vec<ThresholdStatus>),
+        ApiError
+    )
+)]
+pub async fn alert_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ThresholdStatus>>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let metrics = state.domain_service.get_alert_metrics().await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(evaluate_thresholds(&state.config.monitoring.alert_thresholds, &metrics)))
+}
+
+/// Latest webhook delivery attempts, newest first
+///
+/// GET /api/v1/admin/webhooks/deliveries?limit=100
+///
+/// Empty when no webhook is configured.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/webhooks/deliveries",
+    tag = "admin",
+    params(
+        WebhookDeliveriesQueryParams,
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Delivery attempts, newest first", body = Vec<WebhookDeliveryLog>),
+        ApiError
+    )
+)]
+pub async fn webhook_deliveries(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<WebhookDeliveriesQueryParams>,
+) -> Result<Json<Vec<WebhookDeliveryLog>>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let limit = params.limit.unwrap_or(100);
+    let deliveries = state.webhook_delivery.as_ref()
+        .map(|delivery| delivery.recent_deliveries(limit))
+        .unwrap_or_default();
+
+    Ok(Json(deliveries))
+}
+
+/// Reload the in-memory dependency graph used for cycle checks from the database
+///
+/// POST /api/v1/admin/dependency-graph/rebuild
+///
+/// Needed after dependencies were added outside this instance, e.g. by
+/// another replica, which the cached graph does not see until then.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/dependency-graph/rebuild",
+    tag = "admin",
+    params(
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Dependencies loaded", body = DependencyGraphRebuildResponse),
+        ApiError
+    )
+)]
+pub async fn rebuild_dependency_graph(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<DependencyGraphRebuildResponse>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let dependency_count = state.domain_service.rebuild_dependency_graph().await
+        .map_err(ApiError::from)?;
+
+    tracing::info!(dependency_count, "Rebuilt dependency graph");
+
+    Ok(Json(DependencyGraphRebuildResponse { dependency_count }))
+}
+
+/// Run an archival cycle now, moving old completed tasks to the archive graph
+///
+/// POST /api/v1/admin/archival/run
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/archival/run",
+    tag = "admin",
+    params(
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Outcome of the cycle", body = ArchivalReport),
+        ApiError
+    )
+)]
+pub async fn run_archival(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ArchivalReport>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let archival = state.archival_service.as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Task archival is disabled"))?;
+    let report = archival.run_archival_cycle().await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(report))
+}
+
+/// Archival settings and the outcome of the latest cycle
+///
+/// GET /api/v1/admin/archival/status
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/archival/status",
+    tag = "admin",
+    params(
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Archival settings and latest outcome", body = ArchivalStatus),
+        ApiError
+    )
+)]
+pub async fn archival_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ArchivalStatus>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let status = match &state.archival_service {
+        Some(archival) => archival.status(),
+        None => ArchivalStatus {
+            enabled: false,
+            archive_after_days: state.config.archival.archive_after_days,
+            archive_graph_name: state.config.archival.archive_graph_name.clone(),
+            last_run: None,
+        },
+    };
+
+    Ok(Json(status))
+}
+
+/// Outcome of the latest pass promoting backlog tasks whose dependencies are done
+///
+/// GET /api/v1/admin/scheduler/last-report
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/scheduler/last-report",
+    tag = "admin",
+    params(
+        ("x-admin-key" = String, Header, description = "Admin API key")
+    ),
+    responses(
+        (status = 200, description = "Outcome of the latest scheduling pass", body = SchedulingReport),
+        ApiError
+    )
+)]
+pub async fn scheduler_last_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SchedulingReport>, ApiError> {
+    require_admin_key(&state, &headers)?;
+
+    let report = state.task_scheduler.last_report()
+        .ok_or_else(|| ApiError::new("NOT_FOUND", "No scheduling pass has finished yet"))?;
+
+    Ok(Json(report))
+}
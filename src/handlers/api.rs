@@ -4,25 +4,124 @@
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{Json, IntoResponse},
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::openapi::{ContentBuilder, Ref, RefOr, ResponseBuilder, ResponsesBuilder};
+use utoipa::{IntoResponses, ToSchema};
 use tracing::{info, error};
 
 use crate::{
-    AppState, 
-    domain::{CreateTaskRequest, TaskDetailResponse, UpdateTaskRequest, Task},
+    AppState, TaskServiceError,
+    domain::{
+        CreateTaskRequest, TaskDetailResponse, UpdateTaskRequest, Task,
+        RuleViolation, RuleWarning, ValidationResult,
+    },
+    middleware::correlation::current_correlation_id,
     utils::generate_correlation_id,
+    validation::{describe_field_errors, FieldError},
 };
 
+/// Problem type of responses listing the fields that failed validation
+pub const VALIDATION_PROBLEM_TYPE: &str = "urn:tyl-task-service:problem:validation-error";
+
+/// Correlation ID of the request being handled, or a new one outside a request
+fn request_correlation_id() -> String {
+    current_correlation_id().unwrap_or_else(generate_correlation_id)
+}
+
 /// API error response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiError {
     pub error: String,
     pub message: String,
     pub correlation_id: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Business rule violations that caused the request to be rejected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<ApiViolation>,
+    /// Fields that failed validation, rendered as a problem details response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+/// RFC 7807 problem details for a request whose fields failed validation
+///
+/// Sent with status 422 and `Content-Type: application/problem+json`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub errors: Vec<FieldError>,
+    pub correlation_id: String,
+}
+
+impl ValidationErrorResponse {
+    pub fn new(errors: Vec<FieldError>) -> Self {
+        Self {
+            problem_type: VALIDATION_PROBLEM_TYPE.to_string(),
+            title: "Validation failed".to_string(),
+            status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+            detail: describe_field_errors(&errors),
+            errors,
+            correlation_id: request_correlation_id(),
+        }
+    }
+}
+
+impl IntoResponse for ValidationErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(self),
+        ).into_response()
+    }
+}
+
+/// Business rule violation as exposed to API clients
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiViolation {
+    pub rule_name: String,
+    pub field: Option<String>,
+    pub message: String,
+    pub suggested_fix: Option<String>,
+}
+
+/// Business rule warning as exposed to API clients
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiWarning {
+    pub rule_name: String,
+    pub message: String,
+    pub recommendation: Option<String>,
+}
+
+impl From<&RuleViolation> for ApiViolation {
+    fn from(violation: &RuleViolation) -> Self {
+        Self {
+            rule_name: violation.rule_name.clone(),
+            field: violation.field.clone(),
+            message: violation.message.clone(),
+            suggested_fix: violation.suggested_fix.clone(),
+        }
+    }
+}
+
+impl From<&RuleWarning> for ApiWarning {
+    fn from(warning: &RuleWarning) -> Self {
+        Self {
+            rule_name: warning.rule_name.clone(),
+            message: warning.message.clone(),
+            recommendation: warning.recommendation.clone(),
+        }
+    }
 }
 
 /// API success response wrapper
@@ -37,7 +136,7 @@ impl<T> ApiResponse<T> {
     pub fn new(data: T) -> Self {
         Self {
             data,
-            correlation_id: generate_correlation_id(),
+            correlation_id: request_correlation_id(),
             timestamp: chrono::Utc::now(),
         }
     }
@@ -48,8 +147,31 @@ impl ApiError {
         Self {
             error: error.into(),
             message: message.into(),
-            correlation_id: generate_correlation_id(),
+            correlation_id: request_correlation_id(),
             timestamp: chrono::Utc::now(),
+            violations: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+    
+    /// Build a 422 error from a failed business rule evaluation
+    pub fn from_validation(result: &ValidationResult) -> Self {
+        let message = result.violations.iter()
+            .map(|v| v.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        
+        Self {
+            violations: result.violations.iter().map(ApiViolation::from).collect(),
+            ..Self::new("UNPROCESSABLE_ENTITY", message)
+        }
+    }
+    
+    /// Build a 422 problem response listing every field that failed validation
+    pub fn from_field_errors(errors: Vec<FieldError>) -> Self {
+        Self {
+            errors,
+            ..Self::new("VALIDATION_FAILED", "Validation failed")
         }
     }
     
@@ -60,6 +182,10 @@ impl ApiError {
         )
     }
     
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new("BAD_REQUEST", message)
+    }
+    
     pub fn internal_error(message: impl Into<String>) -> Self {
         Self::new("INTERNAL_ERROR", message)
     }
@@ -79,15 +205,39 @@ impl From<tyl_errors::TylError> for ApiError {
     }
 }
 
+impl From<TaskServiceError> for ApiError {
+    fn from(err: TaskServiceError) -> Self {
+        match err {
+            TaskServiceError::VersionConflict { .. } => Self::new("PRECONDITION_FAILED", err.to_string()),
+            TaskServiceError::NotAuthorized { .. } => Self::new("FORBIDDEN", err.to_string()),
+            TaskServiceError::RateLimitExceeded { .. } => Self::new("TOO_MANY_REQUESTS", err.to_string()),
+            TaskServiceError::TaskNotFound { ref id } => Self::not_found("Task", id),
+            TaskServiceError::ValidationFailed { errors } => Self::from_field_errors(errors),
+            TaskServiceError::InvalidInput { field, message } => {
+                Self::from_field_errors(vec![FieldError::new(field, "invalid", message)])
+            },
+            other => Self::from(tyl_errors::TylError::from(other)),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
+        if self.error == "VALIDATION_FAILED" {
+            let mut problem = ValidationErrorResponse::new(self.errors);
+            problem.correlation_id = self.correlation_id;
+            return problem.into_response();
+        }
+        
         let status_code = match self.error.as_str() {
             "NOT_FOUND" => StatusCode::NOT_FOUND,
             "BAD_REQUEST" => StatusCode::BAD_REQUEST,
             "UNAUTHORIZED" => StatusCode::UNAUTHORIZED,
             "FORBIDDEN" => StatusCode::FORBIDDEN,
             "CONFLICT" => StatusCode::CONFLICT,
+            "PRECONDITION_FAILED" => StatusCode::PRECONDITION_FAILED,
             "UNPROCESSABLE_ENTITY" => StatusCode::UNPROCESSABLE_ENTITY,
+            "TOO_MANY_REQUESTS" => StatusCode::TOO_MANY_REQUESTS,
             "SERVICE_UNAVAILABLE" => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
@@ -96,6 +246,35 @@ impl IntoResponse for ApiError {
     }
 }
 
+/// Documents the error responses of handlers returning [`ApiError`]
+impl IntoResponses for ApiError {
+    fn responses() -> BTreeMap<String, RefOr<utoipa::openapi::Response>> {
+        let error = |description: &str| {
+            ResponseBuilder::new()
+                .description(description)
+                .content("application/json", ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ApiError")))
+                    .build())
+                .build()
+        };
+        
+        ResponsesBuilder::new()
+            .response("422", ResponseBuilder::new()
+                .description("Fields failed validation, or business rules were violated")
+                .content("application/problem+json", ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ValidationErrorResponse")))
+                    .build())
+                .content("application/json", ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ApiError")))
+                    .build())
+                .build())
+            .response("4XX", error("Request rejected"))
+            .response("5XX", error("Request failed"))
+            .build()
+            .responses
+    }
+}
+
 /// Main business operation endpoint
 /// 
 /// POST /api/v1/process
@@ -103,7 +282,7 @@ pub async fn process_request(
     State(state): State<AppState>,
     Json(request): Json<CreateTaskRequest>,
 ) -> Result<Json<ApiResponse<TaskDetailResponse>>, (StatusCode, Json<ApiError>)> {
-    let correlation_id = generate_correlation_id();
+    let correlation_id = request_correlation_id();
     
     info!(
         correlation_id = %correlation_id,
@@ -143,7 +322,7 @@ pub async fn get_entity(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<ApiResponse<Task>>, (StatusCode, Json<ApiError>)> {
-    let correlation_id = generate_correlation_id();
+    let correlation_id = request_correlation_id();
     
     info!(
         correlation_id = %correlation_id,
@@ -196,7 +375,7 @@ pub async fn create_entity(
     State(state): State<AppState>,
     Json(request): Json<CreateTaskRequest>,
 ) -> Result<Json<ApiResponse<Task>>, (StatusCode, Json<ApiError>)> {
-    let correlation_id = generate_correlation_id();
+    let correlation_id = request_correlation_id();
     
     info!(
         correlation_id = %correlation_id,
@@ -237,7 +416,7 @@ pub async fn update_entity(
     Path(id): Path<String>,
     Json(request): Json<UpdateTaskRequest>,
 ) -> Result<Json<ApiResponse<Task>>, (StatusCode, Json<ApiError>)> {
-    let correlation_id = generate_correlation_id();
+    let correlation_id = request_correlation_id();
     
     info!(
         correlation_id = %correlation_id,
@@ -245,7 +424,7 @@ pub async fn update_entity(
         "Updating entity"
     );
 
-    match state.domain_service.update_task(&id, request).await {
+    match state.domain_service.update_task(&id, request, None).await {
         Ok(entity) => {
             info!(
                 correlation_id = %correlation_id,
@@ -278,7 +457,7 @@ pub async fn delete_entity(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, (StatusCode, Json<ApiError>)> {
-    let correlation_id = generate_correlation_id();
+    let correlation_id = request_correlation_id();
     
     info!(
         correlation_id = %correlation_id,
@@ -322,9 +501,20 @@ mod tests {
         AppState {
             config: Arc::new(TaskServiceConfig::default()),
             domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
             event_service: Arc::new(EventService::new().await.unwrap()),
             logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
-            tracer: Arc::new(tyl_tracing::SimpleTracer::new(tyl_tracing::TraceConfig::new("test-service"))),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
         }
     }
 
@@ -349,6 +539,7 @@ mod tests {
             custom_properties: std::collections::HashMap::new(),
             assigned_user_id: None,
             project_id: None,
+            created_by: None,
         };
         
         let result = process_request(State(state), Json(request)).await;
@@ -399,6 +590,7 @@ mod tests {
             custom_properties: std::collections::HashMap::new(),
             assigned_user_id: None,
             project_id: None,
+            created_by: None,
         };
         
         let result = create_entity(State(state), Json(request)).await;
@@ -0,0 +1,186 @@
+//! Context-aware task handlers
+//!
+//! Work, personal, learning, maintenance and research tasks follow slightly
+//! different rules. A [`ContextualTaskHandler`] layers context-specific
+//! validation and response fields on top of the generic handlers in
+//! [`crate::handlers::tasks`], which keep serving `/api/v1/tasks` unchanged.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::Json,
+};
+use serde_json::Value;
+
+use crate::{
+    auth::Actor,
+    domain::TaskContext,
+    handlers::{
+        jsonapi::JsonApi,
+        tasks::{self, CreateTaskApiRequest, GetTaskParams, TaskListResponse, TaskQueryParams, TaskResponse},
+        ApiError,
+    },
+    AppState,
+};
+
+/// Context-specific behaviour layered on top of the generic task handlers
+pub trait ContextualTaskHandler: Send + Sync + 'static {
+    /// The task context served by this handler
+    fn context() -> TaskContext;
+
+    /// Path segment the handler is mounted under, e.g. `work` for `/api/v1/work/tasks`
+    fn route_segment() -> &'static str {
+        context_route_segment(&Self::context())
+    }
+
+    /// Validation applied before a task is created in this context
+    fn validate_create(_request: &CreateTaskApiRequest) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    /// Add context-specific fields to a task response
+    fn extend_response(response: TaskResponse) -> TaskResponse {
+        response
+    }
+}
+
+/// Extension trait for attaching context-specific fields to a [`TaskResponse`]
+pub trait TaskResponseExt {
+    fn with_extension(self, key: &str, value: impl Into<Value>) -> Self;
+}
+
+impl TaskResponseExt for TaskResponse {
+    fn with_extension(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.extensions.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+/// Path segment used to mount the routes of a given context
+pub fn context_route_segment(context: &TaskContext) -> &'static str {
+    match context {
+        TaskContext::Work => "work",
+        TaskContext::Personal => "personal",
+        TaskContext::Learning => "learning",
+        TaskContext::Maintenance => "maintenance",
+        TaskContext::Research => "research",
+    }
+}
+
+/// Create a task in the handler's context
+///
+/// The context in the request body is ignored; the route decides it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/{context}/tasks",
+    operation_id = "create_task_in_context",
+    tag = "contexts",
+    params(
+        ("context" = TaskContext, Path, description = "Task context"),
+        Actor
+    ),
+    request_body = CreateTaskApiRequest,
+    responses(
+        (status = 200, description = "The created task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn create_task<H: ContextualTaskHandler>(
+    state: State<AppState>,
+    actor: Actor,
+    Json(mut request): Json<CreateTaskApiRequest>,
+) -> Result<Json<TaskResponse>, ApiError> {
+    request.context = H::context();
+    H::validate_create(&request)?;
+
+    let Json(response) = tasks::create_task(state, actor, Json(request)).await?;
+    Ok(Json(H::extend_response(response)))
+}
+
+/// Get a task by ID, only if it belongs to the handler's context
+#[utoipa::path(
+    get,
+    path = "/api/v1/{context}/tasks/{id}",
+    operation_id = "get_task_in_context",
+    tag = "contexts",
+    params(
+        ("context" = TaskContext, Path, description = "Task context"),
+        ("id" = String, Path, description = "Task ID"),
+        GetTaskParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "The task", body = TaskResponse, headers(("ETag" = String, description = "Current version of the task"))),
+        ApiError
+    )
+)]
+pub async fn get_task<H: ContextualTaskHandler>(
+    state: State<AppState>,
+    Path(task_id): Path<String>,
+    params: Query<GetTaskParams>,
+    actor: Actor,
+    headers: HeaderMap,
+) -> Result<([(header::HeaderName, String); 1], JsonApi<TaskResponse>), ApiError> {
+    let (etag, response) = tasks::get_task(state, Path(task_id.clone()), params, actor, headers).await?;
+    let response = response.into_inner();
+    if response.context != H::context() {
+        return Err(ApiError::not_found("Task", task_id));
+    }
+
+    Ok((etag, JsonApi::resource(H::extend_response(response))))
+}
+
+/// List tasks restricted to the handler's context
+#[utoipa::path(
+    get,
+    path = "/api/v1/{context}/tasks",
+    operation_id = "list_tasks_in_context",
+    tag = "contexts",
+    params(
+        ("context" = TaskContext, Path, description = "Task context"),
+        TaskQueryParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "One page of matching tasks", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn list_tasks<H: ContextualTaskHandler>(
+    state: State<AppState>,
+    Query(mut params): Query<TaskQueryParams>,
+    actor: Actor,
+) -> Result<JsonApi<TaskListResponse>, ApiError> {
+    params.context = Some(context_route_segment(&H::context()).to_string());
+
+    let mut list = tasks::list_tasks(state, Query(params), actor).await?.into_inner();
+    list.tasks = list.tasks.into_iter()
+        .filter(|task| task.context == H::context())
+        .map(H::extend_response)
+        .collect();
+
+    Ok(JsonApi::collection(list, |list| &list.tasks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::{LearningTaskHandler, WorkTaskHandler};
+
+    #[test]
+    fn test_route_segments_match_context_serialization() {
+        for context in [
+            TaskContext::Work,
+            TaskContext::Personal,
+            TaskContext::Learning,
+            TaskContext::Maintenance,
+            TaskContext::Research,
+        ] {
+            let serialized = serde_json::to_value(context).unwrap();
+            assert_eq!(serialized, context_route_segment(&context));
+        }
+
+        assert_eq!(WorkTaskHandler::route_segment(), "work");
+        assert_eq!(LearningTaskHandler::route_segment(), "learning");
+    }
+}
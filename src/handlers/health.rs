@@ -4,16 +4,17 @@
 
 use axum::{
     extract::State,
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::{AppState, LogLevel, LogRecord};
+use crate::{adapters::CircuitState, logging::ContextualLogger, telemetry, AppState, LogLevel};
 use tokio::time::{timeout, Duration};
 
 /// Health check response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
     pub service: String,
@@ -23,7 +24,7 @@ pub struct HealthResponse {
 }
 
 /// Detailed health check response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthDetailResponse {
     pub status: String,
     pub service: String,
@@ -34,7 +35,7 @@ pub struct HealthDetailResponse {
 }
 
 /// Dependency health status
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DependencyHealth {
     pub database: DependencyStatus,
     pub event_system: DependencyStatus,
@@ -42,7 +43,7 @@ pub struct DependencyHealth {
 }
 
 /// Individual dependency status with details
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DependencyStatus {
     pub status: HealthStatus,
     pub name: String,
@@ -50,8 +51,18 @@ pub struct DependencyStatus {
     pub response_time_ms: Option<u64>,
 }
 
+/// Database circuit breaker status
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DatabaseHealthResponse {
+    /// `healthy` while closed without failures, `degraded` while failures
+    /// add up or a trial call is pending, `unhealthy` while open
+    pub status: String,
+    pub circuit_state: CircuitState,
+    pub consecutive_failures: u32,
+}
+
 /// Health status enumeration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub enum HealthStatus {
     #[serde(rename = "healthy")]
     Healthy,
@@ -64,6 +75,14 @@ pub enum HealthStatus {
 /// Basic health check endpoint
 /// 
 /// Returns a simple health status for basic monitoring.
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service is running", body = HealthResponse)
+    )
+)]
 pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "healthy".to_string(),
@@ -78,9 +97,21 @@ pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse>
 /// 
 /// Checks if the service is ready to accept traffic.
 /// This should return 200 when the service can handle requests.
-pub async fn readiness_check(State(state): State<AppState>) -> Result<Json<HealthResponse>, StatusCode> {
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service can handle requests", body = HealthResponse),
+        (status = 503, description = "A critical dependency is unavailable")
+    )
+)]
+pub async fn readiness_check(
+    State(state): State<AppState>,
+    logger: ContextualLogger,
+) -> Result<Json<HealthResponse>, StatusCode> {
     // Check if service is ready (databases connected, etc.)
-    let is_ready = check_service_readiness(&state).await;
+    let is_ready = check_service_readiness(&state, &logger).await;
     
     if is_ready {
         Ok(Json(HealthResponse {
@@ -99,6 +130,14 @@ pub async fn readiness_check(State(state): State<AppState>) -> Result<Json<Healt
 /// 
 /// Checks if the service is alive and should not be restarted.
 /// This should return 200 unless the service is in an unrecoverable state.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service is alive", body = HealthResponse)
+    )
+)]
 pub async fn liveness_check(State(state): State<AppState>) -> Json<HealthResponse> {
     // Basic liveness check - service is running if this handler is called
     Json(HealthResponse {
@@ -110,11 +149,81 @@ pub async fn liveness_check(State(state): State<AppState>) -> Json<HealthRespons
     })
 }
 
+/// Prometheus metrics endpoint
+/// 
+/// Returns the recorded metrics in the Prometheus text format, or 404 when
+/// `monitoring.metrics_enabled` is off.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+        (status = 404, description = "Metrics are disabled"),
+        (status = 503, description = "Metrics recorder is not installed")
+    )
+)]
+pub async fn prometheus_metrics(State(state): State<AppState>) -> Response {
+    if !state.config.monitoring.metrics_enabled {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    
+    match telemetry::prometheus_handle() {
+        Some(handle) => (
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            handle.render(),
+        ).into_response(),
+        None => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    }
+}
+
+/// Database circuit breaker endpoint
+///
+/// GET /health/db
+///
+/// Reports what the breaker has seen of recent queries without querying
+/// FalkorDB itself, so it stays cheap to poll while the database is down.
+#[utoipa::path(
+    get,
+    path = "/health/db",
+    tag = "health",
+    responses(
+        (status = 200, description = "Circuit breaker state", body = DatabaseHealthResponse)
+    )
+)]
+pub async fn database_health(State(state): State<AppState>) -> Json<DatabaseHealthResponse> {
+    let breaker = &state.db_circuit_breaker;
+    let circuit_state = breaker.state();
+    let consecutive_failures = breaker.consecutive_failures();
+    let status = match circuit_state {
+        CircuitState::Closed if consecutive_failures == 0 => "healthy",
+        CircuitState::Closed | CircuitState::HalfOpen => "degraded",
+        CircuitState::Open => "unhealthy",
+    };
+
+    Json(DatabaseHealthResponse {
+        status: status.to_string(),
+        circuit_state,
+        consecutive_failures,
+    })
+}
+
 /// Detailed health check endpoint
 /// 
 /// Returns comprehensive health information including dependency status.
-pub async fn health_detail(State(state): State<AppState>) -> Json<HealthDetailResponse> {
-    let dependency_health = check_dependencies(&state).await;
+#[utoipa::path(
+    get,
+    path = "/health/detail",
+    tag = "health",
+    responses(
+        (status = 200, description = "Service and dependency health", body = HealthDetailResponse)
+    )
+)]
+pub async fn health_detail(
+    State(state): State<AppState>,
+    logger: ContextualLogger,
+) -> Json<HealthDetailResponse> {
+    let dependency_health = check_dependencies(&state, &logger).await;
     
     Json(HealthDetailResponse {
         status: determine_overall_status(&dependency_health),
@@ -127,31 +236,37 @@ pub async fn health_detail(State(state): State<AppState>) -> Json<HealthDetailRe
 }
 
 /// Check if the service is ready to accept traffic
-async fn check_service_readiness(state: &AppState) -> bool {
+async fn check_service_readiness(state: &AppState, logger: &ContextualLogger) -> bool {
     // Log readiness check start
-    state.logger.log(&LogRecord::new(LogLevel::Debug, "Starting service readiness check"));
+    logger.log(LogLevel::Debug, "Starting service readiness check");
+    
+    // The circuit breaker has already seen the database fail; skip the probes
+    if !state.db_health.load(std::sync::atomic::Ordering::Relaxed) {
+        logger.log(LogLevel::Warn, "Service not ready: database circuit breaker is open");
+        return false;
+    }
     
     // Check all critical dependencies
-    let dependencies = check_dependencies(state).await;
+    let dependencies = check_dependencies(state, logger).await;
     
     // Service is ready if database and event system are healthy
     let is_ready = matches!(dependencies.database.status, HealthStatus::Healthy) &&
                    matches!(dependencies.event_system.status, HealthStatus::Healthy) &&
                    matches!(dependencies.domain_service.status, HealthStatus::Healthy);
     
-    state.logger.log(&LogRecord::new(LogLevel::Info, 
-        &format!("Service readiness check result: {}", if is_ready { "ready" } else { "not ready" })));
+    logger.log(LogLevel::Info, 
+        &format!("Service readiness check result: {}", if is_ready { "ready" } else { "not ready" }));
     
     is_ready
 }
 
 /// Check the health of all dependencies
-async fn check_dependencies(state: &AppState) -> DependencyHealth {
+async fn check_dependencies(state: &AppState, logger: &ContextualLogger) -> DependencyHealth {
     // Run all dependency checks concurrently for better performance
     let (database_result, event_system_result, domain_service_result) = tokio::join!(
-        check_database_health(state),
-        check_event_system_health(state),
-        check_domain_service_health(state)
+        check_database_health(state, logger),
+        check_event_system_health(state, logger),
+        check_domain_service_health(logger)
     );
     
     DependencyHealth {
@@ -162,7 +277,7 @@ async fn check_dependencies(state: &AppState) -> DependencyHealth {
 }
 
 /// Check database health
-async fn check_database_health(state: &AppState) -> DependencyStatus {
+async fn check_database_health(state: &AppState, logger: &ContextualLogger) -> DependencyStatus {
     let start_time = std::time::Instant::now();
     let db_name = format!("FalkorDB ({})", state.config.database.graph_name);
     
@@ -183,7 +298,7 @@ async fn check_database_health(state: &AppState) -> DependencyStatus {
     
     match health_check_result {
         Ok(Ok(_)) => {
-            state.logger.log(&LogRecord::new(LogLevel::Debug, "Database health check: SUCCESS"));
+            logger.log(LogLevel::Debug, "Database health check: SUCCESS");
             DependencyStatus {
                 status: HealthStatus::Healthy,
                 name: db_name,
@@ -193,7 +308,7 @@ async fn check_database_health(state: &AppState) -> DependencyStatus {
         },
         Ok(Err(e)) => {
             let error_msg = format!("Database query failed: {}", e);
-            state.logger.log(&LogRecord::new(LogLevel::Error, &format!("Database health check: {}", error_msg)));
+            logger.log(LogLevel::Error, &format!("Database health check: {}", error_msg));
             DependencyStatus {
                 status: HealthStatus::Unhealthy,
                 name: db_name,
@@ -203,7 +318,7 @@ async fn check_database_health(state: &AppState) -> DependencyStatus {
         },
         Err(_) => {
             let error_msg = "Database health check timeout (>5s)".to_string();
-            state.logger.log(&LogRecord::new(LogLevel::Error, &format!("Database health check: {}", error_msg)));
+            logger.log(LogLevel::Error, &format!("Database health check: {}", error_msg));
             DependencyStatus {
                 status: HealthStatus::Unhealthy,
                 name: db_name,
@@ -215,7 +330,7 @@ async fn check_database_health(state: &AppState) -> DependencyStatus {
 }
 
 /// Check event system health
-async fn check_event_system_health(state: &AppState) -> DependencyStatus {
+async fn check_event_system_health(state: &AppState, logger: &ContextualLogger) -> DependencyStatus {
     let start_time = std::time::Instant::now();
     
     // Simple check - if event service is configured and available
@@ -223,7 +338,7 @@ async fn check_event_system_health(state: &AppState) -> DependencyStatus {
     let response_time = start_time.elapsed().as_millis() as u64;
     
     if is_healthy {
-        state.logger.log(&LogRecord::new(LogLevel::Debug, "Event system health check: SUCCESS"));
+        logger.log(LogLevel::Debug, "Event system health check: SUCCESS");
         DependencyStatus {
             status: HealthStatus::Healthy,
             name: "Event System".to_string(),
@@ -231,7 +346,7 @@ async fn check_event_system_health(state: &AppState) -> DependencyStatus {
             response_time_ms: Some(response_time),
         }
     } else {
-        state.logger.log(&LogRecord::new(LogLevel::Warn, "Event system health check: DISABLED"));
+        logger.log(LogLevel::Warn, "Event system health check: DISABLED");
         DependencyStatus {
             status: HealthStatus::Unknown,
             name: "Event System".to_string(),
@@ -242,14 +357,14 @@ async fn check_event_system_health(state: &AppState) -> DependencyStatus {
 }
 
 /// Check domain service health  
-async fn check_domain_service_health(state: &AppState) -> DependencyStatus {
+async fn check_domain_service_health(logger: &ContextualLogger) -> DependencyStatus {
     let start_time = std::time::Instant::now();
     
     // The domain service is healthy if it's initialized and available
     // Since we have it in our state, it's available
     let response_time = start_time.elapsed().as_millis() as u64;
     
-    state.logger.log(&LogRecord::new(LogLevel::Debug, "Domain service health check: SUCCESS"));
+    logger.log(LogLevel::Debug, "Domain service health check: SUCCESS");
     DependencyStatus {
         status: HealthStatus::Healthy,
         name: "Task Domain Service".to_string(), 
@@ -294,9 +409,20 @@ mod tests {
         AppState {
             config: Arc::new(TaskServiceConfig::default()),
             domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
             event_service: Arc::new(EventService::new().await.unwrap()),
             logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
-            tracer: Arc::new(tyl_tracing::SimpleTracer::new(tyl_tracing::TraceConfig::new("test-service"))),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
         }
     }
 
@@ -312,13 +438,44 @@ mod tests {
     #[tokio::test]
     async fn test_readiness_check() {
         let state = create_test_state().await;
-        let result = readiness_check(State(state)).await;
+        let result = readiness_check(State(state.clone()), ContextualLogger::new(state.logger.clone())).await;
         
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.status, "ready");
     }
 
+    #[tokio::test]
+    async fn test_readiness_fails_while_circuit_is_open() {
+        let state = create_test_state().await;
+        state.db_health.store(false, std::sync::atomic::Ordering::Relaxed);
+        
+        let result = readiness_check(State(state.clone()), ContextualLogger::new(state.logger.clone())).await;
+        
+        assert_eq!(result.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_database_health_follows_circuit_breaker() {
+        let state = create_test_state().await;
+
+        let response = database_health(State(state.clone())).await;
+        assert_eq!(response.status, "healthy");
+        assert_eq!(response.circuit_state, CircuitState::Closed);
+
+        state.db_circuit_breaker.record_failure();
+        let response = database_health(State(state.clone())).await;
+        assert_eq!(response.status, "degraded");
+        assert_eq!(response.consecutive_failures, 1);
+
+        for _ in 1..state.db_circuit_breaker.config().failure_threshold {
+            state.db_circuit_breaker.record_failure();
+        }
+        let response = database_health(State(state)).await;
+        assert_eq!(response.status, "unhealthy");
+        assert_eq!(response.circuit_state, CircuitState::Open);
+    }
+
     #[tokio::test]
     async fn test_liveness_check() {
         let state = create_test_state().await;
@@ -330,7 +487,7 @@ mod tests {
     #[tokio::test]
     async fn test_health_detail() {
         let state = create_test_state().await;
-        let response = health_detail(State(state)).await;
+        let response = health_detail(State(state.clone()), ContextualLogger::new(state.logger.clone())).await;
         
         assert!(!response.status.is_empty());
         assert!(!response.service.is_empty());
@@ -349,7 +506,7 @@ mod tests {
     #[tokio::test]
     async fn test_dependency_health_checks() {
         let state = create_test_state().await;
-        let dependencies = check_dependencies(&state).await;
+        let dependencies = check_dependencies(&state, &ContextualLogger::new(state.logger.clone())).await;
         
         // Database should be healthy (using MockTaskService)
         assert!(matches!(dependencies.database.status, HealthStatus::Healthy));
@@ -365,7 +522,7 @@ mod tests {
     #[tokio::test]
     async fn test_service_readiness() {
         let state = create_test_state().await;
-        let is_ready = check_service_readiness(&state).await;
+        let is_ready = check_service_readiness(&state, &ContextualLogger::new(state.logger.clone())).await;
         
         // Service should be ready with mock components
         assert!(is_ready);
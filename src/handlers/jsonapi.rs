@@ -0,0 +1,219 @@
+//! Opt-in JSON:API response format
+//!
+//! Clients sending `Accept: application/vnd.api+json` get responses wrapped in
+//! a JSON:API document (`{"data": {"type", "id", "attributes", "relationships"}}`).
+//! Handlers that support the format return [`JsonApi`], which serializes the
+//! usual flat body and stores the JSON:API document alongside it; the
+//! [`JsonApiLayer`] swaps the two when the client asked for JSON:API. Every
+//! other client keeps receiving the flat format.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+pub const JSON_API_MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// Reference to another resource in a relationship
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonApiResourceIdentifier {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonApiRelationship {
+    pub data: Vec<JsonApiResourceIdentifier>,
+}
+
+/// A response DTO that can be rendered as a JSON:API resource object
+pub trait ToJsonApi: Serialize {
+    fn resource_type(&self) -> &str;
+
+    fn resource_id(&self) -> &str;
+
+    fn relationships(&self) -> HashMap<String, JsonApiRelationship> {
+        HashMap::new()
+    }
+
+    /// Resource object with every serialized field except `id` as an attribute
+    fn to_json_api(&self) -> Value {
+        let mut attributes = serde_json::to_value(self).unwrap_or_else(|_| json!({}));
+        if let Some(fields) = attributes.as_object_mut() {
+            fields.remove("id");
+        }
+
+        let mut resource = json!({
+            "type": self.resource_type(),
+            "id": self.resource_id(),
+            "attributes": attributes,
+        });
+        let relationships = self.relationships();
+        if !relationships.is_empty() {
+            resource["relationships"] = json!(relationships);
+        }
+        resource
+    }
+}
+
+/// Whether the request asked for the JSON:API format
+pub fn wants_json_api(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|accept| accept.contains(JSON_API_MEDIA_TYPE))
+}
+
+/// JSON:API document attached to a response for [`JsonApiLayer`]
+#[derive(Debug, Clone)]
+struct JsonApiDocument(Value);
+
+/// JSON response that can also be served as a JSON:API document
+pub struct JsonApi<T> {
+    body: T,
+    document: Value,
+}
+
+impl<T: ToJsonApi> JsonApi<T> {
+    pub fn resource(body: T) -> Self {
+        let document = json!({ "data": body.to_json_api() });
+        Self { body, document }
+    }
+}
+
+impl<T: Serialize> JsonApi<T> {
+    /// Response whose JSON:API `data` is the list picked out of `body`
+    pub fn collection<R, F>(body: T, resources: F) -> Self
+    where
+        R: ToJsonApi,
+        F: FnOnce(&T) -> &[R],
+    {
+        let data: Vec<Value> = resources(&body).iter().map(ToJsonApi::to_json_api).collect();
+        let document = json!({ "data": data });
+        Self { body, document }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.body
+    }
+}
+
+impl<T: Serialize> IntoResponse for JsonApi<T> {
+    fn into_response(self) -> Response {
+        let mut response = Json(self.body).into_response();
+        response.extensions_mut().insert(JsonApiDocument(self.document));
+        response
+    }
+}
+
+/// Serve the JSON:API document instead of the flat body when requested
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonApiLayer;
+
+impl<S> Layer<S> for JsonApiLayer {
+    type Service = JsonApiService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JsonApiService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonApiService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for JsonApiService<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let json_api = wants_json_api(request.headers());
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+            if !json_api {
+                return Ok(response);
+            }
+            Ok(into_json_api_response(response))
+        })
+    }
+}
+
+/// Replace the body with the attached document, if the handler provided one
+fn into_json_api_response(mut response: Response) -> Response {
+    let Some(JsonApiDocument(document)) = response.extensions_mut().remove::<JsonApiDocument>() else {
+        return response;
+    };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(JSON_API_MEDIA_TYPE));
+    let body = serde_json::to_vec(&document).unwrap_or_default();
+    Response::from_parts(parts, body.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Widget {
+        id: String,
+        name: String,
+    }
+
+    impl ToJsonApi for Widget {
+        fn resource_type(&self) -> &str {
+            "widget"
+        }
+
+        fn resource_id(&self) -> &str {
+            &self.id
+        }
+    }
+
+    #[test]
+    fn test_resource_object_moves_fields_into_attributes() {
+        let widget = Widget { id: "w-1".to_string(), name: "Gear".to_string() };
+
+        let resource = widget.to_json_api();
+
+        assert_eq!(resource["type"], "widget");
+        assert_eq!(resource["id"], "w-1");
+        assert_eq!(resource["attributes"], json!({ "name": "Gear" }));
+        assert!(resource.get("relationships").is_none());
+    }
+
+    #[test]
+    fn test_wants_json_api() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_json_api(&headers));
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+        assert!(!wants_json_api(&headers));
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/vnd.api+json, application/json"));
+        assert!(wants_json_api(&headers));
+    }
+}
@@ -0,0 +1,40 @@
+//! Learning task handlers
+//!
+//! A learning task is only useful when it states what "learned" means, so at
+//! least one success criterion is required.
+
+use crate::{
+    domain::TaskContext,
+    handlers::{
+        contextual::{ContextualTaskHandler, TaskResponseExt},
+        tasks::{CreateTaskApiRequest, TaskResponse},
+        ApiError,
+    },
+};
+
+/// Handler for `/api/v1/learning/tasks`
+pub struct LearningTaskHandler;
+
+impl ContextualTaskHandler for LearningTaskHandler {
+    fn context() -> TaskContext {
+        TaskContext::Learning
+    }
+
+    fn validate_create(request: &CreateTaskApiRequest) -> Result<(), ApiError> {
+        let has_criteria = request
+            .success_criteria
+            .as_ref()
+            .is_some_and(|criteria| !criteria.is_empty());
+        if !has_criteria {
+            return Err(ApiError::bad_request(
+                "Learning tasks require at least one success criterion",
+            ));
+        }
+        Ok(())
+    }
+
+    fn extend_response(response: TaskResponse) -> TaskResponse {
+        let count = response.success_criteria.len();
+        response.with_extension("success_criteria_count", count)
+    }
+}
@@ -0,0 +1,25 @@
+//! Maintenance task handlers
+//!
+//! Maintenance work is often periodic; responses flag recurring tasks.
+
+use crate::{
+    domain::TaskContext,
+    handlers::{
+        contextual::{ContextualTaskHandler, TaskResponseExt},
+        tasks::TaskResponse,
+    },
+};
+
+/// Handler for `/api/v1/maintenance/tasks`
+pub struct MaintenanceTaskHandler;
+
+impl ContextualTaskHandler for MaintenanceTaskHandler {
+    fn context() -> TaskContext {
+        TaskContext::Maintenance
+    }
+
+    fn extend_response(response: TaskResponse) -> TaskResponse {
+        let is_recurring = response.recurrence.is_some();
+        response.with_extension("is_recurring", is_recurring)
+    }
+}
@@ -2,12 +2,34 @@
 //!
 //! This module contains all HTTP request handlers organized by functionality.
 //! Task-specific handlers provide REST API endpoints for task management.
+//! Context-specific handlers (`work_tasks`, `personal_tasks`, ...) build on
+//! the generic task handlers through [`contextual::ContextualTaskHandler`].
 
 pub mod health;
 pub mod api;
 pub mod tasks;
+pub mod projects;
+pub mod admin;
+pub mod jsonapi;
+pub mod schemas;
+pub mod contextual;
+pub mod work_tasks;
+pub mod personal_tasks;
+pub mod learning_tasks;
+pub mod maintenance_tasks;
+pub mod research_tasks;
 
 // Re-export commonly used handlers
 pub use health::*;
 pub use api::*;
-pub use tasks::*;
\ No newline at end of file
+pub use tasks::*;
+pub use projects::*;
+pub use admin::*;
+pub use schemas::*;
+pub use contextual::{ContextualTaskHandler, TaskResponseExt};
+pub use jsonapi::{JsonApi, JsonApiLayer, ToJsonApi};
+pub use work_tasks::*;
+pub use personal_tasks::*;
+pub use learning_tasks::*;
+pub use maintenance_tasks::*;
+pub use research_tasks::*;
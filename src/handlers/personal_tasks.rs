@@ -0,0 +1,28 @@
+//! Personal task handlers
+//!
+//! Personal tasks never leave their owner: public visibility is refused.
+
+use crate::{
+    domain::{TaskContext, TaskVisibility},
+    handlers::{
+        contextual::ContextualTaskHandler,
+        tasks::CreateTaskApiRequest,
+        ApiError,
+    },
+};
+
+/// Handler for `/api/v1/personal/tasks`
+pub struct PersonalTaskHandler;
+
+impl ContextualTaskHandler for PersonalTaskHandler {
+    fn context() -> TaskContext {
+        TaskContext::Personal
+    }
+
+    fn validate_create(request: &CreateTaskApiRequest) -> Result<(), ApiError> {
+        if matches!(request.visibility, Some(TaskVisibility::Public)) {
+            return Err(ApiError::bad_request("Personal tasks cannot be public"));
+        }
+        Ok(())
+    }
+}
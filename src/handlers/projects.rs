@@ -0,0 +1,246 @@
+//! Project lifecycle HTTP handlers
+//!
+//! Thin layer over [`ProjectService`](crate::domain::ProjectService). Project
+//! analytics, such as throughput or snapshots, are served by the task handlers.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    domain::{validate_create_project_request, CreateProjectRequest, Project, ProjectSummary, UpdateProjectRequest},
+    handlers::ApiError,
+    AppState,
+};
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListProjectsParams {
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Request body of adding a task to a project
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddProjectTaskRequest {
+    pub task_id: String,
+}
+
+/// List projects, archived ones only when asked for
+///
+/// GET /api/v1/projects
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects",
+    tag = "projects",
+    params(ListProjectsParams),
+    responses(
+        (status = 200, description = "Projects", body = Vec<Project>),
+        ApiError
+    )
+)]
+pub async fn list_projects(
+    State(state): State<AppState>,
+    Query(params): Query<ListProjectsParams>,
+) -> Result<Json<Vec<Project>>, ApiError> {
+    let projects = state.project_service.list_projects(params.include_archived).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(projects))
+}
+
+/// POST /api/v1/projects
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects",
+    tag = "projects",
+    request_body = CreateProjectRequest,
+    responses(
+        (status = 201, description = "The created project", body = Project),
+        ApiError
+    )
+)]
+pub async fn create_project(
+    State(state): State<AppState>,
+    Json(request): Json<CreateProjectRequest>,
+) -> Result<(StatusCode, Json<Project>), ApiError> {
+    validate_create_project_request(&request).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    if state.project_service.get_project(&request.id).await.map_err(ApiError::from)?.is_some() {
+        return Err(ApiError::new("CONFLICT", format!("Project '{}' already exists", request.id)));
+    }
+
+    let project = state.project_service.create_project(request).await
+        .map_err(ApiError::from)?;
+
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+/// GET /api/v1/projects/:id
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "The project", body = Project),
+        ApiError
+    )
+)]
+pub async fn get_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Project>, ApiError> {
+    let project = find_project(&state, &project_id).await?;
+
+    Ok(Json(project))
+}
+
+/// Change the name, description or dates of a project
+///
+/// PATCH /api/v1/projects/:id
+#[utoipa::path(
+    patch,
+    path = "/api/v1/projects/{id}",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = UpdateProjectRequest,
+    responses(
+        (status = 200, description = "The updated project", body = Project),
+        ApiError
+    )
+)]
+pub async fn update_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<UpdateProjectRequest>,
+) -> Result<Json<Project>, ApiError> {
+    if request.name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::bad_request("Project name cannot be empty"));
+    }
+    find_project(&state, &project_id).await?;
+
+    let project = state.project_service.update_project(&project_id, request).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(project))
+}
+
+/// POST /api/v1/projects/:id/archive
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{id}/archive",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "The archived project", body = Project),
+        ApiError
+    )
+)]
+pub async fn archive_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Project>, ApiError> {
+    find_project(&state, &project_id).await?;
+
+    let project = state.project_service.archive_project(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(project))
+}
+
+/// Delete a project without tasks; archive it instead to keep its tasks
+///
+/// DELETE /api/v1/projects/:id
+#[utoipa::path(
+    delete,
+    path = "/api/v1/projects/{id}",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 204, description = "Project deleted"),
+        ApiError
+    )
+)]
+pub async fn delete_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    find_project(&state, &project_id).await?;
+    let task_count = state.domain_service.get_project_tasks(&project_id).await
+        .map_err(ApiError::from)?
+        .len();
+    if task_count > 0 {
+        return Err(ApiError::new(
+            "CONFLICT",
+            format!("Project '{}' still has {} tasks", project_id, task_count),
+        ));
+    }
+
+    state.project_service.delete_project(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/projects/:id/tasks
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{id}/tasks",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = AddProjectTaskRequest,
+    responses(
+        (status = 204, description = "Task added to the project"),
+        ApiError
+    )
+)]
+pub async fn add_task_to_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<AddProjectTaskRequest>,
+) -> Result<StatusCode, ApiError> {
+    find_project(&state, &project_id).await?;
+    if state.domain_service.get_task_by_id(&request.task_id).await.map_err(ApiError::from)?.is_none() {
+        return Err(ApiError::not_found("Task", &request.task_id));
+    }
+
+    state.project_service.add_task_to_project(&request.task_id, &project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Task counts, effort and completion of a project
+///
+/// GET /api/v1/projects/:id/summary
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/summary",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Summary of the project", body = ProjectSummary),
+        ApiError
+    )
+)]
+pub async fn get_project_summary(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<ProjectSummary>, ApiError> {
+    find_project(&state, &project_id).await?;
+
+    let summary = state.project_service.get_project_summary(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(summary))
+}
+
+async fn find_project(state: &AppState, project_id: &str) -> Result<Project, ApiError> {
+    state.project_service.get_project(project_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Project", project_id))
+}
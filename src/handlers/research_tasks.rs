@@ -0,0 +1,34 @@
+//! Research task handlers
+//!
+//! Research tasks must describe the question being investigated.
+
+use crate::{
+    domain::TaskContext,
+    handlers::{
+        contextual::ContextualTaskHandler,
+        tasks::CreateTaskApiRequest,
+        ApiError,
+    },
+};
+
+/// Handler for `/api/v1/research/tasks`
+pub struct ResearchTaskHandler;
+
+impl ContextualTaskHandler for ResearchTaskHandler {
+    fn context() -> TaskContext {
+        TaskContext::Research
+    }
+
+    fn validate_create(request: &CreateTaskApiRequest) -> Result<(), ApiError> {
+        let has_description = request
+            .description
+            .as_deref()
+            .is_some_and(|d| !d.trim().is_empty());
+        if !has_description {
+            return Err(ApiError::bad_request(
+                "Research tasks require a description of the question being investigated",
+            ));
+        }
+        Ok(())
+    }
+}
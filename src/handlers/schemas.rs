@@ -0,0 +1,62 @@
+//! Schema discovery HTTP handlers
+//!
+//! Lets clients build task forms from the custom property schemas configured
+//! for each task context, either as field lists or as JSON Schema documents.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+
+use crate::{
+    domain::{ContextSchema, TaskContext},
+    handlers::{contextual::context_route_segment, ApiError},
+    AppState,
+};
+
+/// Get the custom property schema of a task context
+#[utoipa::path(
+    get,
+    path = "/api/v1/schemas/context/{context}",
+    tag = "schemas",
+    params(
+        ("context" = TaskContext, Path, description = "Task context")
+    ),
+    responses(
+        (status = 200, description = "Schema of the context", body = ContextSchema),
+        ApiError
+    )
+)]
+pub async fn get_context_schema(
+    State(state): State<AppState>,
+    Path(context): Path<TaskContext>,
+) -> Result<Json<ContextSchema>, ApiError> {
+    state.config.context_schemas.iter()
+        .find(|schema| schema.context == context)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Context schema", context_route_segment(&context)))
+}
+
+/// Get the JSON Schema registered for the custom properties of a task context
+#[utoipa::path(
+    get,
+    path = "/api/v1/schemas/custom-properties/{context}",
+    tag = "schemas",
+    params(
+        ("context" = TaskContext, Path, description = "Task context")
+    ),
+    responses(
+        (status = 200, description = "JSON Schema of the custom properties", body = serde_json::Value),
+        ApiError
+    )
+)]
+pub async fn get_custom_property_schema(
+    State(state): State<AppState>,
+    Path(context): Path<TaskContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state.config.custom_property_schemas.iter()
+        .find(|schema| schema.context == context)
+        .map(|schema| Json(schema.schema.clone()))
+        .ok_or_else(|| ApiError::not_found("Custom property schema", context_route_segment(&context)))
+}
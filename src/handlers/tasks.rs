@@ -4,25 +4,56 @@
 //! integrating with the graph-based task service and event system.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{Json, IntoResponse},
+    http::{header, HeaderMap, StatusCode},
+    response::{Json, IntoResponse, Response},
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+use std::collections::{HashMap, HashSet};
 use tyl_errors::TylError;
 use uuid::Uuid;
 
 use crate::{
     domain::{
-        TaskService, CreateTaskRequest, UpdateTaskRequest, TaskFilter, CreateProjectRequest,
+        TaskService, CreateTaskRequest, UpdateTaskRequest, TaskFilter, TaskCursor,
         Task, Project, TaskDependency, TaskStatus, TaskPriority, TaskContext, TaskComplexity,
-        TaskSource, TaskVisibility, DependencyType, TaskAnalytics,
+        TaskSource, TaskVisibility, DependencyType, TaskAnalytics, evaluate_status_transition,
+        CloneProjectOptions, CloneProjectResult, ProjectWorkflowConfig, WipViolation,
+        validate_custom_properties_against_schema, BottleneckProgress, ResolutionProgress, ThroughputMetrics,
+        OwnershipTransferResult, DependencyAgeReport, parse_natural_language_task, NaturalLanguageParseResult,
+        BulkDeleteResult, MAX_BULK_DELETE_SIZE, BulkCreateResult, BulkItemError, MAX_BULK_CREATE_SIZE, VelocityForecast, INSUFFICIENT_VELOCITY_DATA,
+        PRIORITY_CHURN_THRESHOLD, TaskCentrality, OrderedTask, CrossProjectDependency, DescriptionSuggestion,
+        FocusScore, ExternalSystem, ExternalTicketLink, SyncStatus, EstimationAccuracy, RecurringTaskCompliance,
+        RECURRING_COMPLIANCE_ALERT_THRESHOLD, ProjectSnapshot, SnapshotDiff, CommentSentiment, Tag, TagUsage, StandupReport,
+        ReviewQueueItem, ExecutionPlan, TaskSearchResult, RecurrenceService, recurrence::validate_recurrence,
+        CustomPropertiesPatch, validate_custom_properties_patch, BatchStatusResult, CloneTaskOverrides, TaskComment,
+        MergeOptions, TaskAttachment, TaskSearchQuery, SortSpec, PageRequest, PagedResult, validate_search_query, MAX_SEARCH_PAGE_SIZE,
+        DEFAULT_SUBGRAPH_DEPTH, MAX_SUBGRAPH_DEPTH, MAX_SUBGRAPH_NODES, DueSoonReport, MAX_DUE_SOON_LOOKAHEAD_HOURS,
+        ReassignmentReport, BurndownChart, validate_burndown_range, DependencyDiff,
     },
-    events::{EventService, TaskCreated, TaskUpdated, TaskStatusChanged, TaskAssigned},
-    handlers::ApiError,
-    AppState, TaskServiceError, LogLevel, LogRecord,
+    validation::{validate_comment_body, validate_context_custom_properties, validate_search_text, Validate},
+    events::{
+        DomainEvent, EventService, TaskCreated, TaskUpdated, TaskStatusChanged, TaskAssigned,
+        TasksBulkStatusTransitioned, WipLimitViolated, WipLimitRestored, BottleneckResolved,
+        ProjectOwnershipTransferred, FocusScoreImproved, RecurringTaskComplianceAlert, DiscussionHealthAlert,
+        TaskAutoLabeled, TaskReviewRequested, TaskTagged, TaskRecurrenceScheduled, BatchStatusChanged,
+        TaskCloned, TaskCommented, TasksMerged, TaskSplit, StoredEvent, TaskEventStore, BulkTasksReassigned,
+    },
+    handlers::{
+        jsonapi::{wants_json_api, JsonApi, JsonApiRelationship, JsonApiResourceIdentifier, ToJsonApi},
+        ApiError, ApiViolation, ApiWarning,
+    },
+    attachments::{validate_upload, AttachmentService, PresignedUpload},
+    auth::{self, Actor},
+    export::{ExportFormat, TaskExportService},
+    middleware::correlation::in_current_correlation,
+    logging::ContextualLogger,
+    tracing::SpanGuard,
+    AppState, TaskServiceError, LogLevel,
 };
 use tokio::time::{sleep, Duration};
 
@@ -30,7 +61,7 @@ use tokio::time::{sleep, Duration};
 // Request/Response DTOs
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTaskApiRequest {
     pub name: String,
     pub description: Option<String>,
@@ -50,7 +81,7 @@ pub struct CreateTaskApiRequest {
     pub project_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateTaskApiRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -65,27 +96,33 @@ pub struct UpdateTaskApiRequest {
     pub custom_properties: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SuccessCriterionDto {
     pub criterion: String,
     pub measurable: bool,
     pub verification_method: String,
+    #[serde(default)]
+    pub is_completed: bool,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TaskRecurrenceDto {
     pub pattern: String,
     pub interval: u32,
     pub end_date: Option<DateTime<Utc>>,
+    /// Cron expression, only used by the `custom` pattern
+    pub cron: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TaskStatusTransitionRequest {
     pub new_status: TaskStatus,
     pub comment: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AddDependencyRequest {
     pub to_task_id: String,
     pub dependency_type: DependencyType,
@@ -93,13 +130,105 @@ pub struct AddDependencyRequest {
     pub delay_days: Option<u32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AssignTaskRequest {
     pub user_id: String,
     pub role: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExecutionOrderRequest {
+    pub task_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkCreateTasksRequest {
+    pub tasks: Vec<CreateTaskApiRequest>,
+    /// Create nothing when any task is rejected
+    #[serde(default)]
+    pub fail_fast: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDeleteRequest {
+    pub task_ids: Vec<String>,
+    /// Also remove dependencies on tasks that are not deleted
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ParseTaskRequest {
+    pub input: String,
+}
+
+/// Largest number of matches returned by the description similarity search
+const MAX_SIMILAR_TASKS: usize = 100;
+
+fn default_similar_tasks_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimilarByDescriptionRequest {
+    pub description: String,
+    #[serde(default = "default_similar_tasks_limit")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SuggestDescriptionRequest {
+    pub name: String,
+    pub context: TaskContext,
+}
+
+/// Template for the task's context and details taken from similar completed tasks
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DescriptionSuggestionResponse {
+    pub template: String,
+    pub similar_tasks: Vec<DescriptionSuggestion>,
+}
+
+/// Largest batch accepted by the bulk status transition endpoint
+const MAX_BULK_TRANSITION_SIZE: usize = 200;
+
+/// Pause between the event batches of a bulk create, leaving room in the event channel
+const BULK_EVENT_BATCH_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkStatusTransitionRequest {
+    pub task_ids: Vec<String>,
+    pub new_status: TaskStatus,
+    /// Keep going after a task fails to transition instead of stopping the batch
+    #[serde(default)]
+    pub ignore_validation_errors: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchStatusRequest {
+    pub task_ids: Vec<String>,
+    pub new_status: TaskStatus,
+    /// Change the status whatever the transition rules say, e.g. to cancel a dropped project
+    #[serde(default)]
+    pub skip_validation: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CommentRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkTransitionResult {
+    pub transitioned: Vec<String>,
+    /// Task IDs paired with the reason they could not be transitioned
+    #[schema(value_type = Vec<Vec<String>>)]
+    pub failed: Vec<(String, String)>,
+    pub events_published: u32,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskQueryParams {
     pub status: Option<String>, // Comma-separated statuses
     pub priority: Option<String>, // Comma-separated priorities
@@ -110,17 +239,37 @@ pub struct TaskQueryParams {
     pub due_after: Option<DateTime<Utc>>,
     pub created_after: Option<DateTime<Utc>>,
     pub is_overdue: Option<bool>,
+    /// Parent tasks with (true) or without (false) an open subtask past its due date
+    pub has_overdue_subtasks: Option<bool>,
+    pub priority_changed_more_than: Option<u32>,
+    /// Subtasks in another context than their parent (true) or not (false)
+    pub cross_context: Option<bool>,
+    pub tags: Option<String>, // Comma-separated tags, any of which must match
+    /// Match all of `tags` instead of any
+    pub require_all_tags: Option<bool>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// `next_cursor` of the previous page; replaces `offset`
+    pub cursor: Option<String>,
+    /// Count every matching task into `total_count` (an extra unpaginated query)
+    pub include_count: Option<bool>,
+    /// `effective_priority` orders by [`Task::effective_priority`], highest first
+    pub sort_by: Option<String>,
+    /// Count blocked tasks into the effective priority (one lookup per task)
+    pub blocked_counts: Option<bool>,
+    /// Case-insensitive text in the name, description or implementation details
+    pub q: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskResponse {
     pub id: String,
     pub uuid: String,
     pub name: String,
     pub description: Option<String>,
     pub context: TaskContext,
+    /// Context of the parent task, when it differs from `context`
+    pub parent_context: Option<TaskContext>,
     pub status: TaskStatus,
     pub priority: TaskPriority,
     pub complexity: TaskComplexity,
@@ -136,13 +285,90 @@ pub struct TaskResponse {
     pub source: TaskSource,
     pub visibility: TaskVisibility,
     pub recurrence: Option<TaskRecurrenceDto>,
+    /// Due date of the next instance, only set once a recurring task is done
+    pub next_recurrence_date: Option<DateTime<Utc>>,
     pub attachments: Vec<TaskAttachmentDto>,
     pub custom_properties: HashMap<String, serde_json::Value>,
     pub is_overdue: bool,
     pub is_actionable: bool,
+    pub priority_change_count: u32,
+    pub last_priority_change_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    /// Only set when sorting by effective priority
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_priority_score: Option<f64>,
+    /// Only set when filtering by `has_overdue_subtasks`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overdue_subtask_count: Option<u32>,
+    /// Only loaded when fetching a single task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_links: Option<Vec<ExternalTicketLinkDto>>,
+    /// Only set when fetching a single task with `include_health=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_score: Option<f64>,
+    /// Context-specific fields added by contextual handlers, flattened into the response
+    #[serde(flatten)]
+    pub extensions: HashMap<String, serde_json::Value>,
+    /// Only loaded for JSON:API responses, where it becomes the task's relationships
+    #[serde(skip)]
+    pub dependencies: Vec<TaskDependencyResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExternalTicketLinkDto {
+    pub system: ExternalSystem,
+    pub external_id: String,
+    pub external_url: String,
+    pub sync_status: SyncStatus,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ExternalTicketLink> for ExternalTicketLinkDto {
+    fn from(link: &ExternalTicketLink) -> Self {
+        Self {
+            system: link.system.clone(),
+            external_id: link.external_id.clone(),
+            external_url: link.external_url.clone(),
+            sync_status: link.sync_status,
+            last_synced_at: link.last_synced_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskHistoryParams {
+    /// Leave out events after this sequence number
+    pub up_to_sequence: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetTaskParams {
+    /// Add the task's composite health score
+    pub include_health: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExternalLinkRequest {
+    pub system: ExternalSystem,
+    pub external_id: String,
+    pub external_url: String,
+    #[serde(default)]
+    pub sync_status: SyncStatus,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+/// Task returned from a status transition, with any non-blocking rule warnings
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskTransitionResponse {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ApiWarning>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskAttachmentDto {
     pub name: String,
     pub url: String,
@@ -151,14 +377,41 @@ pub struct TaskAttachmentDto {
     pub uploaded_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskListResponse {
     pub tasks: Vec<TaskResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub total_count: Option<usize>,
     pub has_more: bool,
+    /// Pass back as `cursor` to get the next page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExecutionOrderResponse {
+    /// Tasks ordered so that dependencies come before their dependents
+    pub tasks: Vec<TaskResponse>,
+    /// Task ids grouped by topological level; tasks in a wave can run in parallel
+    pub execution_waves: Vec<Vec<String>>,
+}
+
+/// Execution plan with the time it saves by running waves in parallel
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExecutionPlanResponse {
+    #[serde(flatten)]
+    pub plan: ExecutionPlan,
+    pub total_sequential_days: f64,
+    pub total_parallel_days: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimilarTaskResponse {
+    pub task: TaskResponse,
+    /// Cosine similarity of the TF-IDF vectors of both descriptions, between 0 and 1
+    pub similarity: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskDependencyResponse {
     pub id: String,
     pub from_task_id: String,
@@ -167,9 +420,12 @@ pub struct TaskDependencyResponse {
     pub is_hard_dependency: bool,
     pub delay_days: u32,
     pub created_at: DateTime<Utc>,
+    /// Only set on removed dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskAnalyticsResponse {
     pub task_id: String,
     pub completion_percentage: f64,
@@ -182,6 +438,7 @@ pub struct TaskAnalyticsResponse {
     pub time_to_completion_days: Option<i32>,
     pub dependency_chain_length: u32,
     pub priority_score: f64,
+    pub cycle_time_days: Option<f64>,
 }
 
 // ============================================================================
@@ -196,6 +453,7 @@ impl From<&Task> for TaskResponse {
             name: task.name.clone(),
             description: task.description.clone(),
             context: task.context.clone(),
+            parent_context: task.parent_context,
             status: task.status.clone(),
             priority: task.priority.clone(),
             complexity: task.complexity.clone(),
@@ -205,6 +463,8 @@ impl From<&Task> for TaskResponse {
                     criterion: sc.criterion.clone(),
                     measurable: sc.measurable,
                     verification_method: sc.verification_method.clone(),
+                    is_completed: sc.is_completed,
+                    completed_at: sc.completed_at,
                 })
                 .collect(),
             test_strategy: task.test_strategy.clone(),
@@ -220,7 +480,13 @@ impl From<&Task> for TaskResponse {
                 pattern: r.pattern.clone(),
                 interval: r.interval,
                 end_date: r.end_date,
+                cron: r.cron.clone(),
             }),
+            next_recurrence_date: if task.status == TaskStatus::Done {
+                task.next_recurrence_date()
+            } else {
+                None
+            },
             attachments: task.attachments.iter()
                 .map(|a| TaskAttachmentDto {
                     name: a.name.clone(),
@@ -233,6 +499,15 @@ impl From<&Task> for TaskResponse {
             custom_properties: task.custom_properties.clone(),
             is_overdue: task.is_overdue(),
             is_actionable: task.is_actionable(),
+            priority_change_count: task.priority_change_count,
+            last_priority_change_at: task.last_priority_change_at,
+            tags: task.tags.clone(),
+            effective_priority_score: None,
+            overdue_subtask_count: None,
+            external_links: None,
+            health_score: None,
+            extensions: HashMap::new(),
+            dependencies: Vec::new(),
         }
     }
 }
@@ -247,10 +522,44 @@ impl From<&TaskDependency> for TaskDependencyResponse {
             is_hard_dependency: dep.is_hard_dependency,
             delay_days: dep.delay_days,
             created_at: dep.created_at,
+            deleted_at: dep.deleted_at,
         }
     }
 }
 
+impl ToJsonApi for TaskResponse {
+    fn resource_type(&self) -> &str {
+        "task"
+    }
+
+    fn resource_id(&self) -> &str {
+        &self.id
+    }
+
+    /// `dependencies` are the tasks this one depends on, `dependents` the tasks depending on it
+    fn relationships(&self) -> HashMap<String, JsonApiRelationship> {
+        let task_ref = |id: &str| JsonApiResourceIdentifier {
+            resource_type: "task".to_string(),
+            id: id.to_string(),
+        };
+
+        let dependencies: Vec<_> = self.dependencies.iter()
+            .filter(|dep| dep.from_task_id == self.id)
+            .map(|dep| task_ref(&dep.to_task_id))
+            .collect();
+        let dependents: Vec<_> = self.dependencies.iter()
+            .filter(|dep| dep.to_task_id == self.id)
+            .map(|dep| task_ref(&dep.from_task_id))
+            .collect();
+
+        [("dependencies", dependencies), ("dependents", dependents)]
+            .into_iter()
+            .filter(|(_, data)| !data.is_empty())
+            .map(|(name, data)| (name.to_string(), JsonApiRelationship { data }))
+            .collect()
+    }
+}
+
 impl From<&TaskAnalytics> for TaskAnalyticsResponse {
     fn from(analytics: &TaskAnalytics) -> Self {
         Self {
@@ -265,6 +574,7 @@ impl From<&TaskAnalytics> for TaskAnalyticsResponse {
             time_to_completion_days: analytics.time_to_completion_days,
             dependency_chain_length: analytics.dependency_chain_length,
             priority_score: analytics.priority_score,
+            cycle_time_days: analytics.cycle_time_days,
         }
     }
 }
@@ -341,33 +651,128 @@ fn create_task_filter(params: TaskQueryParams) -> TaskFilter {
         due_date_to: None,
         created_after: params.created_after,
         created_before: None,
-        search_text: None,
-        tags: None,
+        search_text: params.q.map(|q| q.trim().to_string()),
+        tags: params.tags.and_then(|tags_str| {
+            let tags: Vec<String> = tags_str.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+            if tags.is_empty() { None } else { Some(tags) }
+        }),
+        require_all_tags: params.require_all_tags.unwrap_or(false),
         complexity: None,
         has_dependencies: None,
         is_overdue: params.is_overdue,
+        has_overdue_subtasks: params.has_overdue_subtasks,
+        priority_changed_more_than: params.priority_changed_more_than,
+        cross_context: params.cross_context,
         limit: params.limit.or(Some(100)),
         offset: params.offset.or(Some(0)),
+        cursor: params.cursor,
     }
 }
 
+/// Start the span of a handler, ended when the guard is dropped
+fn start_span(state: &AppState, name: &str) -> Result<SpanGuard, ApiError> {
+    SpanGuard::start(&state.tracer, name)
+        .map_err(|e| ApiError::internal_server_error(format!("Tracing error: {}", e)))
+}
+
+fn add_span_attribute(span: &SpanGuard, key: &str, value: serde_json::Value) -> Result<(), ApiError> {
+    span.add_attribute(key, value)
+        .map_err(|e| ApiError::internal_server_error(format!("Tracing error: {}", e)))
+}
+
 // ============================================================================
 // HTTP Handlers
 // ============================================================================
 
 /// Create a new task
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks",
+    tag = "tasks",
+    params(Actor),
+    request_body = CreateTaskApiRequest,
+    responses(
+        (status = 200, description = "The created task", body = TaskResponse),
+        ApiError
+    )
+)]
 pub async fn create_task(
     State(state): State<AppState>,
+    actor: Actor,
+    logger: ContextualLogger,
     Json(request): Json<CreateTaskApiRequest>,
 ) -> Result<Json<TaskResponse>, ApiError> {
-    // Start tracing span for this request
-    let span_id = state.tracer.start_span("create_task", None)
-        .map_err(|e| ApiError::internal_server_error(format!("Tracing error: {}", e)))?;
+    let span = start_span(&state, "create_task")?;
 
     // Log request received
-    state.logger.log(&LogRecord::new(LogLevel::Info, 
-        &format!("Creating new task: {}", request.name)));
+    logger.log(LogLevel::Info, &format!("Creating new task: {}", request.name));
+
+    // Check custom properties against the schema configured for the context
+    let custom_properties = request.custom_properties.unwrap_or_default();
+    if let Err(e) = validate_context_custom_properties(&state.config.context_schemas, request.context, &custom_properties) {
+        return Err(ApiError::bad_request(e.to_string()));
+    }
+    check_custom_property_schema(&state, request.context, &custom_properties)?;
+
+    let assigned_user_id = request.assigned_user_id.clone();
+    let project_id = request.project_id.clone();
+    let mut domain_request = create_task_request(request, custom_properties);
+    domain_request.created_by = actor.user_id;
+    if let Some(Err(e)) = domain_request.recurrence.as_ref().map(validate_recurrence) {
+        return Err(ApiError::bad_request(e.to_string()));
+    }
+    if let Err(e) = domain_request.validate() {
+        return Err(ApiError::from(e));
+    }
+
+    add_span_attribute(&span, "task_id", serde_json::json!(domain_request.id.clone()))?;
+
+    // Create the task
+    let task = match state.domain_service.create_task(domain_request).await {
+        Ok(task) => task,
+        Err(e) => {
+            logger.log(LogLevel::Error, &format!("Failed to create task: {}", e));
+            return Err(ApiError::from(e));
+        }
+    };
+    let logger = logger.with_task_id(&task.id);
+
+    // Publish task created event
+    let event = TaskCreated {
+        task_id: task.id.clone(),
+        name: task.name.clone(),
+        context: task.context.clone(),
+        priority: task.priority.clone(),
+        assigned_user_id,
+        project_id,
+        created_at: task.created_at,
+    };
+    
+    // Publish task created event with retry logic
+    if let Err(e) = publish_event_with_retry(&state.event_service, TaskCreated::TOPIC, event, 3).await {
+        logger.log(LogLevel::Error, &format!("Failed to publish task.created event after retries: {}", e));
+        // We don't fail the request if event publishing fails, but we log it as an error
+    }
+
+    // Label the task in the background, creation does not wait for it
+    let (domain_service, event_service, task_id) = (state.domain_service.clone(), state.event_service.clone(), task.id.clone());
+    tokio::spawn(in_current_correlation(async move {
+        if let Err(e) = auto_label_and_publish(domain_service.as_ref(), &event_service, &task_id).await {
+            tracing::warn!("Failed to auto-label task {}: {}", task_id, e);
+        }
+    }));
+
+    // Log successful task creation
+    logger.log(LogLevel::Info, &format!("Task created successfully: {}", task.id));
+
+    Ok(Json(TaskResponse::from(&task)))
+}
 
+/// Domain request for an API create request, with a generated task id
+pub(crate) fn create_task_request(request: CreateTaskApiRequest, custom_properties: HashMap<String, serde_json::Value>) -> CreateTaskRequest {
     // Generate task ID (in a real implementation, this would be more sophisticated)
     let task_id = if let Some(ref project_id) = request.project_id {
         format!("{}-T{}", project_id, Uuid::new_v4().simple().to_string()[..8].to_uppercase())
@@ -375,12 +780,7 @@ pub async fn create_task(
         format!("TASK-{}", Uuid::new_v4().simple().to_string()[..8].to_uppercase())
     };
 
-    // Add task ID to trace span
-    state.tracer.add_span_attribute(&span_id, "task_id", serde_json::json!(task_id.clone()))
-        .map_err(|e| ApiError::internal_server_error(format!("Tracing error: {}", e)))?;
-
-    // Convert API request to domain request
-    let domain_request = CreateTaskRequest {
+    CreateTaskRequest {
         id: task_id,
         name: request.name,
         description: request.description,
@@ -395,6 +795,8 @@ pub async fn create_task(
                 criterion: sc.criterion,
                 measurable: sc.measurable,
                 verification_method: sc.verification_method,
+                is_completed: sc.is_completed,
+                completed_at: sc.completed_at,
             })
             .collect(),
         test_strategy: request.test_strategy,
@@ -404,74 +806,342 @@ pub async fn create_task(
             pattern: r.pattern,
             interval: r.interval,
             end_date: r.end_date,
+            cron: r.cron,
         }),
-        custom_properties: request.custom_properties.unwrap_or_default(),
-        assigned_user_id: request.assigned_user_id.clone(),
-        project_id: request.project_id.clone(),
-    };
+        custom_properties,
+        assigned_user_id: request.assigned_user_id,
+        project_id: request.project_id,
+        created_by: None,
+    }
+}
 
-    // Create the task
-    let task = match state.domain_service.create_task(domain_request).await {
-        Ok(task) => task,
-        Err(e) => {
-            state.logger.log(&LogRecord::new(LogLevel::Error, 
-                &format!("Failed to create task: {}", e)));
-            let _ = state.tracer.end_span(span_id);
-            return Err(ApiError::from(e));
+/// Create many tasks in one request, e.g. to import or seed a project
+///
+/// Tasks are validated like single creations and rejected ones are reported
+/// by their position in `tasks`. The accepted tasks are stored together;
+/// with `fail_fast` nothing is stored once any task is rejected. Publishes
+/// `TaskCreated` for every created task in the background, in paced batches
+/// so a large import does not flood the event channel.
+///
+/// POST /api/v1/tasks/bulk
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/bulk",
+    tag = "tasks",
+    params(Actor),
+    request_body = BulkCreateTasksRequest,
+    responses(
+        (status = 200, description = "Created tasks and the ones rejected", body = BulkCreateResult),
+        ApiError
+    )
+)]
+pub async fn bulk_create_tasks(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(request): Json<BulkCreateTasksRequest>,
+) -> Result<Json<BulkCreateResult>, ApiError> {
+    let span = start_span(&state, "bulk_create_tasks")?;
+    add_span_attribute(&span, "task_count", serde_json::json!(request.tasks.len()))?;
+
+    if request.tasks.len() > MAX_BULK_CREATE_SIZE {
+        let message = format!(
+            "At most {} tasks can be created at once, got {}",
+            MAX_BULK_CREATE_SIZE,
+            request.tasks.len()
+        );
+        return Err(ApiError {
+            violations: vec![ApiViolation {
+                rule_name: "max_bulk_create_size".to_string(),
+                field: Some("tasks".to_string()),
+                message: message.clone(),
+                suggested_fix: Some(format!("Split the tasks into batches of at most {}", MAX_BULK_CREATE_SIZE)),
+            }],
+            ..ApiError::new("UNPROCESSABLE_ENTITY", message)
+        });
+    }
+
+    // Positions in the request of the tasks handed to the domain service
+    let mut positions = Vec::new();
+    let mut requests = Vec::new();
+    let mut rejected = Vec::new();
+    for (index, task) in request.tasks.into_iter().enumerate() {
+        let custom_properties = task.custom_properties.clone().unwrap_or_default();
+        let check = validate_context_custom_properties(&state.config.context_schemas, task.context, &custom_properties)
+            .map_err(|e| e.to_string())
+            .and_then(|_| check_custom_property_schema(&state, task.context, &custom_properties).map_err(|e| e.message));
+        match check {
+            Ok(()) => {
+                let mut request = create_task_request(task, custom_properties);
+                request.created_by = actor.user_id.clone();
+                positions.push(index);
+                requests.push(request);
+            }
+            Err(reason) => rejected.push(BulkItemError { index, reason }),
         }
-    };
+    }
 
-    // Publish task created event
-    let event = TaskCreated {
-        task_id: task.id.clone(),
-        name: task.name.clone(),
-        context: task.context.clone(),
-        priority: task.priority.clone(),
-        assigned_user_id: request.assigned_user_id,
-        project_id: request.project_id,
-        created_at: task.created_at,
+    let links: HashMap<String, (Option<String>, Option<String>)> = requests.iter()
+        .map(|r| (r.id.clone(), (r.assigned_user_id.clone(), r.project_id.clone())))
+        .collect();
+    let mut result = if request.fail_fast && !rejected.is_empty() {
+        BulkCreateResult::default()
+    } else {
+        state.domain_service.bulk_create_tasks(requests, request.fail_fast).await
+            .map_err(ApiError::from)?
     };
-    
-    // Publish task created event with retry logic
-    if let Err(e) = publish_event_with_retry(&state.event_service, "task.created", event, 3).await {
-        state.logger.log(&LogRecord::new(LogLevel::Error, 
-            &format!("Failed to publish task.created event after retries: {}", e)));
-        // We don't fail the request if event publishing fails, but we log it as an error
+    for failure in &mut result.failed {
+        failure.index = positions[failure.index];
     }
+    result.failed.extend(rejected);
+    result.failed.sort_by_key(|failure| failure.index);
+    result.partial_success = !result.created.is_empty() && !result.failed.is_empty();
 
-    // Log successful task creation
-    state.logger.log(&LogRecord::new(LogLevel::Info, 
-        &format!("Task created successfully: {}", task.id)));
+    let events: Vec<(String, serde_json::Value)> = result.created.iter()
+        .filter_map(|task| {
+            let (assigned_user_id, project_id) = links.get(&task.id).cloned().unwrap_or_default();
+            let event = TaskCreated {
+                task_id: task.id.clone(),
+                name: task.name.clone(),
+                context: task.context,
+                priority: task.priority,
+                assigned_user_id,
+                project_id,
+                created_at: task.created_at,
+            };
+            serde_json::to_value(event).ok().map(|payload| (TaskCreated::TOPIC.to_string(), payload))
+        })
+        .collect();
+    let (event_service, batch_size) = (state.event_service.clone(), state.config.events.batch_publish_max_size.max(1));
+    state.event_service.spawn_background_publish(async move {
+        for (index, batch) in events.chunks(batch_size).enumerate() {
+            if index > 0 {
+                sleep(BULK_EVENT_BATCH_DELAY).await;
+            }
+            match event_service.publish_batch(batch.to_vec()).await {
+                Ok(published) if !published.failed.is_empty() => {
+                    tracing::error!("Failed to publish {} task.created events of a bulk create", published.failed.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to publish task.created events of a bulk create: {}", e),
+            }
+        }
+    });
 
-    // End tracing span
-    let _ = state.tracer.end_span(span_id);
+    tracing::info!(
+        created = result.created.len(),
+        failed = result.failed.len(),
+        "Bulk task creation finished"
+    );
 
-    Ok(Json(TaskResponse::from(&task)))
+    Ok(Json(result))
+}
+
+/// Reject custom properties violating the JSON Schema registered for the context
+///
+/// Undeclared properties are accepted but logged.
+fn check_custom_property_schema(
+    state: &AppState,
+    context: TaskContext,
+    properties: &HashMap<String, serde_json::Value>,
+) -> Result<(), ApiError> {
+    let validation = validate_custom_properties_against_schema(
+        &state.config.custom_property_schemas,
+        context,
+        properties,
+    );
+    if !validation.is_valid {
+        return Err(ApiError::from_validation(&validation));
+    }
+
+    for warning in &validation.warnings {
+        tracing::warn!(rule = %warning.rule_name, "{}", warning.message);
+    }
+    Ok(())
 }
 
 /// Get a task by ID
+///
+/// Dependencies are only loaded when the client asked for JSON:API, where they
+/// are rendered as relationships.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        GetTaskParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "The task", body = TaskResponse, headers(("ETag" = String, description = "Current version of the task"))),
+        ApiError
+    )
+)]
 pub async fn get_task(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
-) -> Result<Json<TaskResponse>, ApiError> {
-    match state.domain_service.get_task_by_id(&task_id).await
+    Query(params): Query<GetTaskParams>,
+    actor: Actor,
+    headers: HeaderMap,
+) -> Result<([(header::HeaderName, String); 1], JsonApi<TaskResponse>), ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+    let etag = [(header::ETAG, task.etag())];
+
+    let mut response = TaskResponse::from(&task);
+    response.external_links = Some(state.domain_service.get_external_links(&task_id).await
         .map_err(ApiError::from)?
-    {
-        Some(task) => Ok(Json(TaskResponse::from(&task))),
-        None => Err(ApiError::not_found("Task", task_id)),
+        .iter()
+        .map(ExternalTicketLinkDto::from)
+        .collect());
+    if params.include_health.unwrap_or(false) {
+        let health = state.domain_service.get_task_health_score(&task_id).await
+            .map_err(ApiError::from)?;
+        response.health_score = Some(health.score);
+    }
+    if wants_json_api(&headers) {
+        response.dependencies = state.domain_service.get_task_dependencies(&task_id).await
+            .map_err(ApiError::from)?
+            .iter()
+            .map(TaskDependencyResponse::from)
+            .collect();
+    }
+
+    Ok((etag, JsonApi::resource(response)))
+}
+
+/// The tasks of `tasks` the actor may read, in their order
+async fn readable_tasks(state: &AppState, actor: &Actor, tasks: Vec<Task>) -> Vec<Task> {
+    let mut readable = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        if state.authorization_policy.can_read_task(actor, &task).await {
+            readable.push(task);
+        }
     }
+    readable
+}
+
+/// Check the actor may change `task_id`, or delete it when `delete` is set
+///
+/// Unknown tasks pass, the domain service reports them like any other failure.
+async fn check_bulk_access(state: &AppState, actor: &Actor, task_id: &str, delete: bool) -> Result<(), String> {
+    let Some(task) = state.domain_service.get_task_by_id(task_id).await.map_err(|e| e.to_string())? else {
+        return Ok(());
+    };
+    let allowed = if delete {
+        auth::require(state.authorization_policy.can_delete_task(actor, &task).await, "delete", &task)
+    } else {
+        auth::require(state.authorization_policy.can_write_task(actor, &task).await, "update", &task)
+    };
+    allowed.map_err(|e| e.to_string())
+}
+
+/// Split `task_ids` into those passing [`check_bulk_access`] and the failures of the others
+async fn partition_by_access(
+    state: &AppState,
+    actor: &Actor,
+    task_ids: Vec<String>,
+    delete: bool,
+) -> (Vec<String>, Vec<(String, String)>) {
+    let (mut allowed, mut denied) = (Vec::new(), Vec::new());
+    for task_id in task_ids {
+        match check_bulk_access(state, actor, &task_id, delete).await {
+            Ok(()) => allowed.push(task_id),
+            Err(reason) => denied.push((task_id, reason)),
+        }
+    }
+    (allowed, denied)
+}
+
+/// Reject the write with a version conflict unless `If-Match` names the task's current version
+///
+/// Returns the version the write must still find when it lands. Writes without
+/// `If-Match`, or with `If-Match: *`, are not checked.
+fn check_if_match(headers: &HeaderMap, task: &Task) -> Result<Option<u64>, ApiError> {
+    let Some(if_match) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let if_match = if_match.to_str()
+        .map_err(|_| ApiError::bad_request("If-Match must be an ETag like \"v3\""))?;
+    if if_match.trim() == "*" {
+        return Ok(None);
+    }
+
+    let mut expected = Vec::new();
+    for tag in if_match.split(',') {
+        let version = tag.trim()
+            .trim_start_matches("W/")
+            .trim_matches('"')
+            .strip_prefix('v')
+            .and_then(|version| version.parse::<u64>().ok())
+            .ok_or_else(|| ApiError::bad_request(format!("Invalid ETag in If-Match: {}", tag.trim())))?;
+        expected.push(version);
+    }
+    if expected.contains(&task.version) {
+        return Ok(Some(task.version));
+    }
+
+    Err(ApiError::from(TaskServiceError::VersionConflict {
+        task_id: task.id.clone(),
+        expected: expected[0],
+        actual: task.version,
+    }))
+}
+
+/// Report a failed conditional write as a version conflict when the task
+/// moved past `expected_version` after `check_if_match` passed
+async fn conditional_write_error(state: &AppState, task_id: &str, expected_version: Option<u64>, err: TylError) -> ApiError {
+    if let Some(expected) = expected_version {
+        if let Ok(Some(task)) = state.domain_service.get_task_by_id(task_id).await {
+            if task.version != expected {
+                return ApiError::from(TaskServiceError::VersionConflict { task_id: task.id, expected, actual: task.version });
+            }
+        }
+    }
+    ApiError::from(err)
 }
 
 /// Update an existing task
+///
+/// With an `If-Match` header the update is rejected with 412 unless it names
+/// the task's current ETag.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tasks/{id}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor,
+        ("If-Match" = Option<String>, Header, description = "Current ETag of the task, rejected with 412 when stale")
+    ),
+    request_body = UpdateTaskApiRequest,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        ApiError
+    )
+)]
 pub async fn update_task(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
+    actor: Actor,
+    headers: HeaderMap,
     Json(request): Json<UpdateTaskApiRequest>,
 ) -> Result<Json<TaskResponse>, ApiError> {
+    let span = start_span(&state, "update_task")?;
+    add_span_attribute(&span, "task_id", serde_json::json!(task_id))?;
+
     // Get original task for comparison
     let original_task = state.domain_service.get_task_by_id(&task_id).await
         .map_err(ApiError::from)?
         .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &original_task).await, "update", &original_task)?;
+    let expected_version = check_if_match(&headers, &original_task)?;
+
+    if let Some(ref custom_properties) = request.custom_properties {
+        check_custom_property_schema(&state, original_task.context, custom_properties)?;
+    }
 
     // Convert API request to domain request
     let domain_request = UpdateTaskRequest {
@@ -487,16 +1157,21 @@ pub async fn update_task(
                 criterion: sc.criterion,
                 measurable: sc.measurable,
                 verification_method: sc.verification_method,
+                is_completed: sc.is_completed,
+                completed_at: sc.completed_at,
             }).collect()
         ),
         test_strategy: request.test_strategy,
         visibility: request.visibility,
         custom_properties: request.custom_properties,
     };
+    domain_request.validate().map_err(ApiError::from)?;
 
     // Update the task
-    let updated_task = state.domain_service.update_task(&task_id, domain_request).await
-        .map_err(ApiError::from)?;
+    let updated_task = match state.domain_service.update_task(&task_id, domain_request, expected_version).await {
+        Ok(task) => task,
+        Err(e) => return Err(conditional_write_error(&state, &task_id, expected_version, e).await),
+    };
 
     // Publish task updated event
     let event = TaskUpdated {
@@ -507,7 +1182,71 @@ pub async fn update_task(
         updated_at: updated_task.updated_at,
     };
     
-    if let Err(e) = state.event_service.publish("task.updated", event).await {
+    if let Err(e) = state.event_service.publish(TaskUpdated::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.updated event: {}", e);
+    }
+
+    Ok(Json(TaskResponse::from(&updated_task)))
+}
+
+/// Set and remove individual custom properties
+///
+/// The body is a JSON Merge Patch (`application/merge-patch+json`) of the
+/// custom properties: keys with a value are set, keys set to `null` are
+/// removed and keys left out are kept. Values are limited to 4KB each.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tasks/{id}/custom-properties",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor,
+        ("If-Match" = Option<String>, Header, description = "Current ETag of the task, rejected with 412 when stale")
+    ),
+    request_body = HashMap<String, Option<serde_json::Value>>,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn patch_custom_properties(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    headers: HeaderMap,
+    Json(patch): Json<CustomPropertiesPatch>,
+) -> Result<Json<TaskResponse>, ApiError> {
+    let span = start_span(&state, "patch_custom_properties")?;
+    add_span_attribute(&span, "task_id", serde_json::json!(task_id))?;
+
+    let original_task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &original_task).await, "update", &original_task)?;
+    let expected_version = check_if_match(&headers, &original_task)?;
+    validate_custom_properties_patch(&patch).map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let mut patched = original_task.clone();
+    patched.apply_custom_properties_patch(&patch);
+    check_custom_property_schema(&state, original_task.context, &patched.custom_properties)?;
+
+    let updated_task = match state.domain_service.patch_custom_properties(&task_id, patch.clone(), expected_version).await {
+        Ok(task) => task,
+        Err(e) => return Err(conditional_write_error(&state, &task_id, expected_version, e).await),
+    };
+
+    let mut updated_fields: Vec<String> = patch.keys()
+        .map(|key| format!("custom_properties.{}", key))
+        .collect();
+    updated_fields.sort();
+    let event = TaskUpdated {
+        task_id: updated_task.id.clone(),
+        previous_status: original_task.status,
+        current_status: updated_task.status.clone(),
+        updated_fields,
+        updated_at: updated_task.updated_at,
+    };
+    if let Err(e) = state.event_service.publish(TaskUpdated::TOPIC, event).await {
         tracing::warn!("Failed to publish task.updated event: {}", e);
     }
 
@@ -515,10 +1254,32 @@ pub async fn update_task(
 }
 
 /// Delete a task
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tasks/{id}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    responses(
+        (status = 204, description = "Task deleted"),
+        ApiError
+    )
+)]
 pub async fn delete_task(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
+    actor: Actor,
 ) -> Result<StatusCode, ApiError> {
+    let span = start_span(&state, "delete_task")?;
+    add_span_attribute(&span, "task_id", serde_json::json!(task_id))?;
+
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_delete_task(&actor, &task).await, "delete", &task)?;
+
     state.domain_service.delete_task(&task_id).await
         .map_err(ApiError::from)?;
 
@@ -526,64 +1287,423 @@ pub async fn delete_task(
 }
 
 /// List tasks with filtering
+///
+/// Tasks the caller may not read are left out of the page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks",
+    tag = "tasks",
+    params(
+        TaskQueryParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "One page of matching tasks", body = TaskListResponse),
+        ApiError
+    )
+)]
 pub async fn list_tasks(
     State(state): State<AppState>,
     Query(params): Query<TaskQueryParams>,
-) -> Result<Json<TaskListResponse>, ApiError> {
+    actor: Actor,
+) -> Result<JsonApi<TaskListResponse>, ApiError> {
+    let sort_by_effective_priority = params.sort_by.as_deref() == Some("effective_priority");
+    let with_blocked_counts = params.blocked_counts.unwrap_or(false);
+    let has_overdue_subtasks = params.has_overdue_subtasks;
+    let include_count = params.include_count.unwrap_or(false);
+    if let Some(ref cursor) = params.cursor {
+        TaskCursor::decode(cursor).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    }
+    if let Some(ref q) = params.q {
+        validate_search_text(q).map_err(|e| ApiError::from_field_errors(vec![e]))?;
+    }
+
     let filter = create_task_filter(params);
-    let tasks = state.domain_service.list_tasks(filter).await
+    // Counted after the policy, so the count doesn't reveal hidden tasks
+    let total_count = if include_count {
+        let unpaginated = TaskFilter { limit: None, offset: None, cursor: None, ..filter.clone() };
+        let all = state.domain_service.list_tasks(unpaginated).await
+            .map_err(ApiError::from)?;
+        Some(readable_tasks(&state, &actor, all).await.len())
+    } else {
+        None
+    };
+
+    // One task past the page tells whether another page follows
+    let page_size = filter.limit.unwrap_or(usize::MAX);
+    let filter = TaskFilter { limit: filter.limit.map(|limit| limit.saturating_add(1)), ..filter };
+    let mut tasks = state.domain_service.list_tasks(filter).await
         .map_err(ApiError::from)?;
+    let has_more = tasks.len() > page_size;
+    tasks.truncate(page_size);
+    let next_cursor = if has_more {
+        tasks.last().map(|task| TaskCursor::encode(task).into_string())
+    } else {
+        None
+    };
+    let mut tasks = readable_tasks(&state, &actor, tasks).await;
 
-    let task_responses: Vec<TaskResponse> = tasks.iter()
-        .map(TaskResponse::from)
-        .collect();
+    // Counted from the subtasks themselves, which also drops any task the
+    // repository returned that does not match the filter
+    let mut overdue_subtask_counts = HashMap::new();
+    if let Some(wanted) = has_overdue_subtasks {
+        for task in &tasks {
+            let count = state.domain_service.get_subtasks(&task.id).await
+                .map_err(ApiError::from)?
+                .iter()
+                .filter(|subtask| subtask.is_overdue())
+                .count() as u32;
+            overdue_subtask_counts.insert(task.id.clone(), count);
+        }
+        tasks.retain(|task| (overdue_subtask_counts[&task.id] > 0) == wanted);
+    }
+
+    let mut task_responses: Vec<TaskResponse> = if sort_by_effective_priority {
+        let mut scored = Vec::with_capacity(tasks.len());
+        for task in &tasks {
+            let blocked_count = if with_blocked_counts {
+                state.domain_service.get_blocked_tasks(&task.id).await
+                    .map_err(ApiError::from)?
+                    .len() as u32
+            } else {
+                0
+            };
+            let mut response = TaskResponse::from(task);
+            response.effective_priority_score = Some(task.effective_priority(blocked_count));
+            scored.push(response);
+        }
+        scored.sort_by(|a, b| b.effective_priority_score.partial_cmp(&a.effective_priority_score)
+            .unwrap_or(std::cmp::Ordering::Equal));
+        scored
+    } else {
+        tasks.iter()
+            .map(TaskResponse::from)
+            .collect()
+    };
+    for response in &mut task_responses {
+        response.overdue_subtask_count = overdue_subtask_counts.get(&response.id).copied();
+    }
 
     let response = TaskListResponse {
-        has_more: false, // In a real implementation, check if there are more results
-        total_count: Some(task_responses.len()),
+        has_more,
+        total_count,
+        next_cursor,
         tasks: task_responses,
     };
 
-    Ok(Json(response))
+    Ok(JsonApi::collection(response, |list| &list.tasks))
 }
 
-/// Transition task status
-pub async fn transition_task_status(
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchTasksParams {
+    pub q: String,
+    pub context: Option<TaskContext>,
+    pub limit: Option<usize>,
+}
+
+/// Full-text search over task names and descriptions, most relevant first
+///
+/// GET /api/v1/tasks/search?q=&context=&limit=
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/search",
+    tag = "tasks",
+    params(
+        SearchTasksParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Matching tasks, most relevant first", body = Vec<TaskSearchResult>),
+        ApiError
+    )
+)]
+pub async fn search_tasks(
     State(state): State<AppState>,
-    Path(task_id): Path<String>,
-    Json(request): Json<TaskStatusTransitionRequest>,
-) -> Result<Json<TaskResponse>, ApiError> {
-    let original_status = state.domain_service.get_task_by_id(&task_id).await
-        .map_err(ApiError::from)?
-        .ok_or_else(|| ApiError::not_found("Task", &task_id))?
-        .status;
+    Query(params): Query<SearchTasksParams>,
+    actor: Actor,
+) -> Result<Json<Vec<TaskSearchResult>>, ApiError> {
+    if params.q.trim().is_empty() {
+        return Err(ApiError::bad_request("q cannot be empty"));
+    }
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
 
-    let updated_task = state.domain_service.transition_task_status(&task_id, request.new_status.clone()).await
+    let results = state.domain_service.search_tasks(&params.q, params.context, limit).await
         .map_err(ApiError::from)?;
+    let mut readable = Vec::with_capacity(results.len());
+    for result in results {
+        if state.authorization_policy.can_read_task(&actor, &result.task).await {
+            readable.push(result);
+        }
+    }
 
-    // Publish status change event
-    let event = TaskStatusChanged {
-        task_id: updated_task.id.clone(),
-        previous_status: original_status,
+    Ok(Json(readable))
+}
+
+/// Request body of the structured task search
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdvancedSearchRequest {
+    pub query: TaskSearchQuery,
+    #[serde(default)]
+    pub sort: Vec<SortSpec>,
+    #[serde(default)]
+    pub page: PageRequest,
+}
+
+/// Tasks matching a structured query, see [`TaskSearchQuery`]
+///
+/// POST /api/v1/tasks/search
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/search",
+    tag = "tasks",
+    params(Actor),
+    request_body = AdvancedSearchRequest,
+    responses(
+        (status = 200, description = "One page of matching tasks", body = PagedResult<TaskResponse>),
+        ApiError
+    )
+)]
+pub async fn search_tasks_advanced(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(request): Json<AdvancedSearchRequest>,
+) -> Result<Json<PagedResult<TaskResponse>>, ApiError> {
+    validate_search_query(&request.query, &request.sort, &request.page)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    // Every match goes through the policy before paging, so hidden tasks
+    // shift neither the pages nor the total
+    let mut readable = Vec::new();
+    let mut scan = PageRequest { page: 1, page_size: MAX_SEARCH_PAGE_SIZE };
+    loop {
+        let result = state.domain_service
+            .search_tasks_advanced(request.query.clone(), request.sort.clone(), scan).await
+            .map_err(ApiError::from)?;
+        let fetched = result.items.len();
+        readable.extend(readable_tasks(&state, &actor, result.items).await);
+        if fetched < scan.page_size || scan.page * scan.page_size >= result.total {
+            break;
+        }
+        scan.page += 1;
+    }
+
+    let PageRequest { page, page_size } = request.page;
+    Ok(Json(PagedResult {
+        items: readable.iter().skip((page - 1) * page_size).take(page_size).map(TaskResponse::from).collect(),
+        total: readable.len(),
+        page,
+        page_size,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportTasksParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Tasks as a CSV or newline-delimited JSON (the default) attachment
+///
+/// Takes the same filters as listing tasks, but is not paged unless `limit`
+/// is given. Tasks are read from the database while the body is sent, so the
+/// export never holds all tasks in memory. An error mid-stream aborts the
+/// response.
+///
+/// GET /api/v1/tasks/export?format=csv|ndjson
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/export",
+    tag = "tasks",
+    params(
+        ExportTasksParams,
+        TaskQueryParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Matching tasks as an attachment", content((String = "application/x-ndjson"), (String = "text/csv"))),
+        ApiError
+    )
+)]
+pub async fn export_tasks(
+    State(state): State<AppState>,
+    Query(export): Query<ExportTasksParams>,
+    Query(params): Query<TaskQueryParams>,
+    actor: Actor,
+) -> Result<Response, ApiError> {
+    if let Some(ref cursor) = params.cursor {
+        TaskCursor::decode(cursor).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    }
+    if let Some(ref q) = params.q {
+        validate_search_text(q).map_err(|e| ApiError::from_field_errors(vec![e]))?;
+    }
+    let limit = params.limit;
+    let filter = TaskFilter { limit, ..create_task_filter(params) };
+
+    let exporter = TaskExportService::new(state.domain_service.clone(), state.authorization_policy.clone(), actor);
+    let chunks = exporter.stream(export.format, filter).map(|chunk| {
+        chunk.map_err(|e| {
+            tracing::error!("Task export failed: {}", e);
+            std::io::Error::other(e.to_string())
+        })
+    });
+
+    let filename = format!(
+        "tasks_{}.{}",
+        Utc::now().format("%Y%m%dT%H%M%SZ"),
+        export.format.file_extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, export.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response())
+}
+
+/// Transition task status
+///
+/// Business rules are evaluated first: violations reject the request with a
+/// 422 listing each of them, warnings are returned alongside the updated task.
+/// With an `If-Match` header the transition is rejected with 412 unless it
+/// names the task's current ETag.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/status",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor,
+        ("If-Match" = Option<String>, Header, description = "Current ETag of the task, rejected with 412 when stale")
+    ),
+    request_body = TaskStatusTransitionRequest,
+    responses(
+        (status = 200, description = "The transitioned task with any rule warnings", body = TaskTransitionResponse),
+        ApiError
+    )
+)]
+pub async fn transition_task_status(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    headers: HeaderMap,
+    Json(request): Json<TaskStatusTransitionRequest>,
+) -> Result<Json<TaskTransitionResponse>, ApiError> {
+    let span = start_span(&state, "transition_task_status")?;
+    add_span_attribute(&span, "task_id", serde_json::json!(task_id))?;
+    add_span_attribute(&span, "new_status", serde_json::json!(request.new_status))?;
+
+    let original_task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &original_task).await, "update", &original_task)?;
+    let expected_version = check_if_match(&headers, &original_task)?;
+    let original_status = original_task.status;
+
+    let dependencies = state.domain_service.get_task_dependencies(&task_id).await
+        .map_err(ApiError::from)?;
+    let mut resolved_dependencies = Vec::new();
+    for dependency in dependencies.into_iter().filter(|d| d.from_task_id == task_id) {
+        if let Some(target) = state.domain_service.get_task_by_id(&dependency.to_task_id).await
+            .map_err(ApiError::from)?
+        {
+            resolved_dependencies.push((dependency, target));
+        }
+    }
+
+    let validation = evaluate_status_transition(&original_task, &request.new_status, &resolved_dependencies);
+    if !validation.is_valid {
+        return Err(ApiError::from_validation(&validation));
+    }
+
+    let project_id = state.domain_service.get_task_project_id(&task_id).await
+        .map_err(ApiError::from)?;
+    let violations_before = current_wip_violations(&state, project_id.as_deref()).await;
+
+    let updated_task = match state.domain_service.transition_task_status(&task_id, request.new_status.clone(), expected_version).await {
+        Ok(task) => task,
+        Err(e) => return Err(conditional_write_error(&state, &task_id, expected_version, e).await),
+    };
+
+    if let Some(project_id) = project_id {
+        let violations_after = current_wip_violations(&state, Some(&project_id)).await;
+        publish_wip_limit_changes(&state.event_service, &project_id, &violations_before, &violations_after).await;
+    }
+
+    // Publish status change event
+    let event = TaskStatusChanged {
+        task_id: updated_task.id.clone(),
+        previous_status: original_status,
         new_status: request.new_status,
         changed_by: None, // In a real implementation, get from auth context
         comment: request.comment,
         changed_at: updated_task.updated_at,
     };
     
-    if let Err(e) = publish_event_with_retry(&state.event_service, "task.status_changed", event, 3).await {
+    if let Err(e) = publish_event_with_retry(&state.event_service, TaskStatusChanged::TOPIC, event, 3).await {
         tracing::error!("Failed to publish task.status_changed event after retries: {}", e);
     }
 
-    Ok(Json(TaskResponse::from(&updated_task)))
+    // The domain service created the next instance of a completed recurring task
+    if updated_task.status == TaskStatus::Done {
+        if let Some(next_task_id) = RecurrenceService::next_occurrence_id(&updated_task) {
+            match state.domain_service.get_task_by_id(&next_task_id).await {
+                Ok(Some(next)) => {
+                    let event = TaskRecurrenceScheduled {
+                        completed_task_id: updated_task.id.clone(),
+                        next_task_id,
+                        next_due_date: next.due_date,
+                        scheduled_at: next.created_at,
+                    };
+                    if let Err(e) = publish_event_with_retry(&state.event_service, TaskRecurrenceScheduled::TOPIC, event, 3).await {
+                        tracing::error!("Failed to publish task.recurrence.scheduled event after retries: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load next occurrence {}: {}", next_task_id, e),
+            }
+        }
+    }
+
+    Ok(Json(TaskTransitionResponse {
+        task: TaskResponse::from(&updated_task),
+        warnings: validation.warnings.iter().map(ApiWarning::from).collect(),
+    }))
 }
 
 /// Add task dependency
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/dependencies",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = AddDependencyRequest,
+    responses(
+        (status = 200, description = "The created dependency", body = TaskDependencyResponse),
+        ApiError
+    )
+)]
 pub async fn add_task_dependency(
     State(state): State<AppState>,
     Path(from_task_id): Path<String>,
+    actor: Actor,
     Json(request): Json<AddDependencyRequest>,
 ) -> Result<Json<TaskDependencyResponse>, ApiError> {
+    let from_task = state.domain_service.get_task_by_id(&from_task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &from_task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &from_task).await, "update", &from_task)?;
+    let to_task = state.domain_service.get_task_by_id(&request.to_task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &request.to_task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &to_task).await, "depend on", &to_task)?;
+
     let dependency = state.domain_service.add_task_dependency(
         &from_task_id,
         &request.to_task_id,
@@ -593,173 +1713,2824 @@ pub async fn add_task_dependency(
     Ok(Json(TaskDependencyResponse::from(&dependency)))
 }
 
-/// Get task dependencies
-pub async fn get_task_dependencies(
+/// Link a task to a ticket in JIRA, GitHub, Linear or another tracker
+///
+/// POST /api/v1/tasks/:id/external-links
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/external-links",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = ExternalLinkRequest,
+    responses(
+        (status = 200, description = "The created link", body = ExternalTicketLinkDto),
+        ApiError
+    )
+)]
+pub async fn link_external_ticket(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
-) -> Result<Json<Vec<TaskDependencyResponse>>, ApiError> {
-    let dependencies = state.domain_service.get_task_dependencies(&task_id).await
+    actor: Actor,
+    Json(request): Json<ExternalLinkRequest>,
+) -> Result<Json<ExternalTicketLinkDto>, ApiError> {
+    if request.external_id.trim().is_empty() || request.external_url.trim().is_empty() {
+        return Err(ApiError::bad_request("external_id and external_url cannot be empty"));
+    }
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "update", &task)?;
+
+    let link = ExternalTicketLink {
+        sync_status: request.sync_status,
+        last_synced_at: request.last_synced_at,
+        ..ExternalTicketLink::new(task_id.clone(), request.system, request.external_id, request.external_url)
+    };
+    let link = state.domain_service.link_external_ticket(&task_id, link).await
         .map_err(ApiError::from)?;
 
-    let responses: Vec<TaskDependencyResponse> = dependencies.iter()
-        .map(TaskDependencyResponse::from)
-        .collect();
+    Ok(Json(ExternalTicketLinkDto::from(&link)))
+}
 
-    Ok(Json(responses))
+/// Add the tags the auto-labelling rules give a task
+///
+/// Publishes `TaskAutoLabeled` when any tag was added. New tasks are labeled
+/// in the background on creation; this endpoint reruns the rules, e.g. after
+/// an update.
+///
+/// POST /api/v1/tasks/:id/auto-label
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/auto-label",
+    tag = "tasks",
+    params(("id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Tags added to the task", body = Vec<Tag>),
+        ApiError
+    )
+)]
+pub async fn auto_label_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+) -> Result<Json<Vec<Tag>>, ApiError> {
+    if state.domain_service.get_task_by_id(&task_id).await.map_err(ApiError::from)?.is_none() {
+        return Err(ApiError::not_found("Task", &task_id));
+    }
+
+    let tags = auto_label_and_publish(state.domain_service.as_ref(), &state.event_service, &task_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(tags))
 }
 
-/// Assign task to user
-pub async fn assign_task(
+async fn auto_label_and_publish(
+    domain_service: &(dyn TaskService + Send + Sync),
+    event_service: &EventService,
+    task_id: &str,
+) -> Result<Vec<Tag>, TylError> {
+    let tags = domain_service.auto_label_task(task_id).await?;
+    if !tags.is_empty() {
+        let event = TaskAutoLabeled {
+            task_id: task_id.to_string(),
+            tags_added: tags.iter().map(|tag| tag.name.clone()).collect(),
+            labeled_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(event_service, TaskAutoLabeled::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish task.auto_labeled event after retries: {}", e);
+        }
+    }
+    Ok(tags)
+}
+
+/// Rule-based sentiment of the comments on a task
+///
+/// Publishes `DiscussionHealthAlert` when the overall score is below the alert threshold.
+///
+/// GET /api/v1/tasks/:id/comment-sentiment
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/comment-sentiment",
+    tag = "tasks",
+    params(("id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Sentiment of the task's comments", body = CommentSentiment),
+        ApiError
+    )
+)]
+pub async fn get_comment_sentiment(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
-    Json(request): Json<AssignTaskRequest>,
-) -> Result<StatusCode, ApiError> {
-    let role = request.role.as_deref().unwrap_or("owner");
-    
-    state.domain_service.assign_task(&task_id, &request.user_id, role).await
+) -> Result<Json<CommentSentiment>, ApiError> {
+    if state.domain_service.get_task_by_id(&task_id).await.map_err(ApiError::from)?.is_none() {
+        return Err(ApiError::not_found("Task", &task_id));
+    }
+
+    let sentiment = state.domain_service.get_comment_sentiment(&task_id).await
         .map_err(ApiError::from)?;
 
-    // Publish task assigned event
-    let event = TaskAssigned {
-        task_id: task_id.clone(),
-        user_id: request.user_id,
-        role: role.to_string(),
-        assigned_by: None, // In a real implementation, get from auth context
-        assigned_at: Utc::now(),
-    };
-    
-    if let Err(e) = state.event_service.publish("task.assigned", event).await {
-        tracing::warn!("Failed to publish task.assigned event: {}", e);
+    if sentiment.needs_alert() {
+        let event = DiscussionHealthAlert {
+            task_id: task_id.clone(),
+            sentiment_score: sentiment.overall_score,
+            comment_count: sentiment.comment_count,
+            trend: sentiment.trend,
+            detected_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(&state.event_service, DiscussionHealthAlert::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish task.discussion_health_alert event after retries: {}", e);
+        }
     }
 
-    Ok(StatusCode::OK)
+    Ok(Json(sentiment))
 }
 
-/// Get assigned tasks for a user
-pub async fn get_assigned_tasks(
+/// Comments on a task, oldest first
+///
+/// GET /api/v1/tasks/:id/comments
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/comments",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Comments on the task", body = Vec<TaskComment>),
+        ApiError
+    )
+)]
+pub async fn list_task_comments(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
-) -> Result<Json<TaskListResponse>, ApiError> {
-    let tasks = state.domain_service.get_assigned_tasks(&user_id).await
+    Path(task_id): Path<String>,
+    actor: Actor,
+) -> Result<Json<Vec<TaskComment>>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+
+    let comments = state.domain_service.list_comments(&task_id).await
         .map_err(ApiError::from)?;
 
-    let task_responses: Vec<TaskResponse> = tasks.iter()
-        .map(TaskResponse::from)
-        .collect();
+    Ok(Json(comments))
+}
 
-    let response = TaskListResponse {
-        has_more: false,
-        total_count: Some(task_responses.len()),
-        tasks: task_responses,
-    };
+/// Recorded changes of a task, oldest first
+///
+/// Only tasks that still exist can be looked up.
+///
+/// GET /api/v1/tasks/:id/history
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/history",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        TaskHistoryParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Events recorded for the task", body = Vec<StoredEvent>),
+        ApiError
+    )
+)]
+pub async fn get_task_history(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    Query(params): Query<TaskHistoryParams>,
+    actor: Actor,
+) -> Result<Json<Vec<StoredEvent>>, ApiError> {
+    let event_store = state.event_store.clone()
+        .ok_or_else(|| ApiError::service_unavailable("Task event store is disabled"))?;
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
 
-    Ok(Json(response))
+    let events = event_store.load_events(&task_id, params.up_to_sequence).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(events))
 }
 
-/// Get actionable tasks for a user
-pub async fn get_actionable_tasks(
+/// Comment on a task as the calling user
+///
+/// POST /api/v1/tasks/:id/comments
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/comments",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = CommentRequest,
+    responses(
+        (status = 201, description = "The created comment", body = TaskComment),
+        ApiError
+    )
+)]
+pub async fn add_task_comment(
     State(state): State<AppState>,
-    Path(user_id): Path<String>,
-) -> Result<Json<TaskListResponse>, ApiError> {
-    let tasks = state.domain_service.get_actionable_tasks(&user_id).await
-        .map_err(ApiError::from)?;
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<CommentRequest>,
+) -> Result<(StatusCode, Json<TaskComment>), ApiError> {
+    let author_id = actor.user_id.clone()
+        .ok_or_else(|| ApiError::new("UNAUTHORIZED", "Comments need an X-User-Id header"))?;
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "comment on", &task)?;
+    validate_comment_body(&request.body).map_err(|e| ApiError::bad_request(e.to_string()))?;
 
-    let task_responses: Vec<TaskResponse> = tasks.iter()
-        .map(TaskResponse::from)
-        .collect();
+    let comment = state.domain_service.add_comment(&task_id, &author_id, &request.body).await
+        .map_err(ApiError::from)?;
 
-    let response = TaskListResponse {
-        has_more: false,
-        total_count: Some(task_responses.len()),
-        tasks: task_responses,
+    let event = TaskCommented {
+        task_id,
+        comment_id: comment.id.clone(),
+        author_id,
+        body: comment.body.clone(),
+        commented_at: comment.created_at,
     };
+    if let Err(e) = state.event_service.publish(TaskCommented::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.commented event: {}", e);
+    }
 
-    Ok(Json(response))
+    Ok((StatusCode::CREATED, Json(comment)))
 }
 
-/// Get overdue tasks
-pub async fn get_overdue_tasks(
+/// Change the body of a comment; only its author may
+///
+/// PATCH /api/v1/tasks/:id/comments/:comment_id
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tasks/{id}/comments/{comment_id}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("comment_id" = String, Path, description = "Comment ID"),
+        Actor
+    ),
+    request_body = CommentRequest,
+    responses(
+        (status = 200, description = "The edited comment", body = TaskComment),
+        ApiError
+    )
+)]
+pub async fn edit_task_comment(
     State(state): State<AppState>,
-) -> Result<Json<TaskListResponse>, ApiError> {
-    let tasks = state.domain_service.get_overdue_tasks().await
+    Path((task_id, comment_id)): Path<(String, String)>,
+    actor: Actor,
+    Json(request): Json<CommentRequest>,
+) -> Result<Json<TaskComment>, ApiError> {
+    let comment = find_task_comment(&state, &task_id, &comment_id).await?;
+    if actor.user_id.as_deref() != Some(comment.author_id.as_str()) {
+        return Err(ApiError::new("FORBIDDEN", "Only the author can edit a comment"));
+    }
+    validate_comment_body(&request.body).map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let comment = state.domain_service.edit_comment(&comment_id, &request.body).await
         .map_err(ApiError::from)?;
 
-    let task_responses: Vec<TaskResponse> = tasks.iter()
-        .map(TaskResponse::from)
-        .collect();
+    Ok(Json(comment))
+}
 
-    let response = TaskListResponse {
-        has_more: false,
-        total_count: Some(task_responses.len()),
-        tasks: task_responses,
-    };
+/// Delete a comment; allowed to its author and to whoever may change the task
+///
+/// DELETE /api/v1/tasks/:id/comments/:comment_id
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tasks/{id}/comments/{comment_id}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("comment_id" = String, Path, description = "Comment ID"),
+        Actor
+    ),
+    responses(
+        (status = 204, description = "Comment deleted"),
+        ApiError
+    )
+)]
+pub async fn delete_task_comment(
+    State(state): State<AppState>,
+    Path((task_id, comment_id)): Path<(String, String)>,
+    actor: Actor,
+) -> Result<StatusCode, ApiError> {
+    let comment = find_task_comment(&state, &task_id, &comment_id).await?;
+    if actor.user_id.as_deref() != Some(comment.author_id.as_str()) {
+        let task = state.domain_service.get_task_by_id(&task_id).await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+        auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "delete comments on", &task)?;
+    }
 
-    Ok(Json(response))
+    state.domain_service.delete_comment(&comment_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// Get task analytics
-pub async fn get_task_analytics(
+/// The comment, provided it is on the task
+async fn find_task_comment(state: &AppState, task_id: &str, comment_id: &str) -> Result<TaskComment, ApiError> {
+    state.domain_service.get_comment(comment_id).await
+        .map_err(ApiError::from)?
+        .filter(|comment| comment.task_id == task_id)
+        .ok_or_else(|| ApiError::not_found("Comment", comment_id))
+}
+
+/// Tickets in other trackers linked to a task
+///
+/// GET /api/v1/tasks/:id/external-links
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/external-links",
+    tag = "tasks",
+    params(("id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Links of the task", body = Vec<ExternalTicketLinkDto>),
+        ApiError
+    )
+)]
+pub async fn get_external_links(
     State(state): State<AppState>,
     Path(task_id): Path<String>,
-) -> Result<Json<TaskAnalyticsResponse>, ApiError> {
-    let analytics = state.domain_service.get_task_analytics(&task_id).await
+) -> Result<Json<Vec<ExternalTicketLinkDto>>, ApiError> {
+    if state.domain_service.get_task_by_id(&task_id).await.map_err(ApiError::from)?.is_none() {
+        return Err(ApiError::not_found("Task", &task_id));
+    }
+
+    let links = state.domain_service.get_external_links(&task_id).await
         .map_err(ApiError::from)?;
 
-    Ok(Json(TaskAnalyticsResponse::from(&analytics)))
+    Ok(Json(links.iter().map(ExternalTicketLinkDto::from).collect()))
 }
 
-/// Add subtask
-pub async fn add_subtask(
+/// Get task dependencies
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/dependencies",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Dependencies of the task", body = Vec<TaskDependencyResponse>),
+        ApiError
+    )
+)]
+pub async fn get_task_dependencies(
     State(state): State<AppState>,
-    Path((parent_id, child_id)): Path<(String, String)>,
-) -> Result<StatusCode, ApiError> {
-    state.domain_service.add_subtask(&parent_id, &child_id).await
+    Path(task_id): Path<String>,
+    actor: Actor,
+) -> Result<Json<Vec<TaskDependencyResponse>>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+
+    let dependencies = state.domain_service.get_task_dependencies(&task_id).await
         .map_err(ApiError::from)?;
 
-    Ok(StatusCode::OK)
+    let responses: Vec<TaskDependencyResponse> = dependencies.iter()
+        .map(TaskDependencyResponse::from)
+        .collect();
+
+    Ok(Json(responses))
 }
 
-/// Get subtasks
-pub async fn get_subtasks(
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DependencyDiffParams {
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyDiffResponse {
+    pub added: Vec<TaskDependencyResponse>,
+    pub removed: Vec<TaskDependencyResponse>,
+    pub unchanged: Vec<TaskDependencyResponse>,
+}
+
+impl From<&DependencyDiff> for DependencyDiffResponse {
+    fn from(diff: &DependencyDiff) -> Self {
+        Self {
+            added: diff.added.iter().map(TaskDependencyResponse::from).collect(),
+            removed: diff.removed.iter().map(TaskDependencyResponse::from).collect(),
+            unchanged: diff.unchanged.iter().map(TaskDependencyResponse::from).collect(),
+        }
+    }
+}
+
+/// Dependencies of a task added, removed and kept since a point in time
+///
+/// GET /api/v1/tasks/:id/dependencies/diff?since=2024-01-01T00:00:00Z
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/dependencies/diff",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        DependencyDiffParams
+    ),
+    responses(
+        (status = 200, description = "Changes to the dependencies of the task", body = DependencyDiffResponse),
+        ApiError
+    )
+)]
+pub async fn get_dependency_diff(
     State(state): State<AppState>,
-    Path(parent_id): Path<String>,
-) -> Result<Json<TaskListResponse>, ApiError> {
-    let tasks = state.domain_service.get_subtasks(&parent_id).await
+    Path(task_id): Path<String>,
+    Query(params): Query<DependencyDiffParams>,
+) -> Result<Json<DependencyDiffResponse>, ApiError> {
+    state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+
+    let diff = state.domain_service.get_dependency_diff(&task_id, params.since).await
         .map_err(ApiError::from)?;
 
-    let task_responses: Vec<TaskResponse> = tasks.iter()
-        .map(TaskResponse::from)
-        .collect();
+    Ok(Json(DependencyDiffResponse::from(&diff)))
+}
 
-    let response = TaskListResponse {
-        has_more: false,
-        total_count: Some(task_responses.len()),
-        tasks: task_responses,
-    };
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskGraphParams {
+    pub depth: Option<u32>,
+}
 
-    Ok(Json(response))
+/// Body of the 400 answered when a subgraph exceeds [`MAX_SUBGRAPH_NODES`]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GraphTooLargeResponse {
+    pub error: String,
+    pub message: String,
+    pub graph_too_large: bool,
+    pub node_count: usize,
 }
 
-/// Get circular dependency analysis
-pub async fn get_circular_dependencies(
+/// Get the dependency subgraph around a task for visualization
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/graph",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        TaskGraphParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Tasks and dependencies around the task", body = crate::domain::queries::TaskSubgraph),
+        (status = 400, description = "Subgraph has too many tasks for the requested depth", body = GraphTooLargeResponse),
+        ApiError
+    )
+)]
+pub async fn get_task_graph(
     State(state): State<AppState>,
-) -> Result<Json<CircularDependenciesResponse>, ApiError> {
-    let cycles = state.domain_service.get_detailed_circular_dependencies().await
+    Path(task_id): Path<String>,
+    Query(params): Query<TaskGraphParams>,
+    actor: Actor,
+) -> Result<Response, ApiError> {
+    let depth = params.depth.unwrap_or(DEFAULT_SUBGRAPH_DEPTH);
+    if !(1..=MAX_SUBGRAPH_DEPTH).contains(&depth) {
+        return Err(ApiError::bad_request(format!("depth must be between 1 and {}", MAX_SUBGRAPH_DEPTH)));
+    }
+
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+
+    let subgraph = state.domain_service.get_task_subgraph(&task_id, depth).await
         .map_err(ApiError::from)?;
+    if subgraph.nodes.len() > MAX_SUBGRAPH_NODES {
+        let node_count = subgraph.nodes.len();
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            Json(GraphTooLargeResponse {
+                error: "GRAPH_TOO_LARGE".to_string(),
+                message: format!(
+                    "Subgraph has {} tasks, more than the {} allowed; request a smaller depth",
+                    node_count, MAX_SUBGRAPH_NODES
+                ),
+                graph_too_large: true,
+                node_count,
+            }),
+        )
+            .into_response());
+    }
 
-    let response = CircularDependenciesResponse {
-        total_cycles: cycles.len(),
-        has_critical_cycles: cycles.iter().any(|c| matches!(c.severity, crate::domain::queries::CycleSeverity::Critical)),
-        cycles,
-    };
+    Ok(Json(subgraph).into_response())
+}
 
-    Ok(Json(response))
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DependencyNavigationParams {
+    #[serde(default)]
+    pub include_done: bool,
 }
 
-/// Response for circular dependency analysis
-#[derive(Debug, Serialize)]
-pub struct CircularDependenciesResponse {
-    pub total_cycles: usize,
-    pub cycles: Vec<crate::domain::queries::DependencyCycle>,
-    pub has_critical_cycles: bool,
-}
\ No newline at end of file
+/// Get the tasks a task depends on
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/predecessors",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        DependencyNavigationParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Tasks the task depends on", body = Vec<TaskResponse>),
+        ApiError
+    )
+)]
+pub async fn get_task_predecessors(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    Query(params): Query<DependencyNavigationParams>,
+    actor: Actor,
+) -> Result<Json<Vec<TaskResponse>>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+
+    let tasks = state.domain_service.get_predecessors(&task_id).await
+        .map_err(ApiError::from)?;
+    let tasks = readable_tasks(&state, &actor, tasks).await;
+
+    Ok(Json(navigation_response(tasks, params.include_done)))
+}
+
+/// Get the tasks depending on a task
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/successors",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        DependencyNavigationParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Tasks depending on the task", body = Vec<TaskResponse>),
+        ApiError
+    )
+)]
+pub async fn get_task_successors(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    Query(params): Query<DependencyNavigationParams>,
+    actor: Actor,
+) -> Result<Json<Vec<TaskResponse>>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &task).await, "read", &task)?;
+
+    let tasks = state.domain_service.get_successors(&task_id).await
+        .map_err(ApiError::from)?;
+    let tasks = readable_tasks(&state, &actor, tasks).await;
+
+    Ok(Json(navigation_response(tasks, params.include_done)))
+}
+
+fn navigation_response(tasks: Vec<Task>, include_done: bool) -> Vec<TaskResponse> {
+    tasks.iter()
+        .filter(|task| include_done || task.status != TaskStatus::Done)
+        .map(TaskResponse::from)
+        .collect()
+}
+
+/// Order tasks for execution
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/execution-order",
+    tag = "tasks",
+    request_body = ExecutionOrderRequest,
+    responses(
+        (status = 200, description = "Tasks in execution order", body = ExecutionOrderResponse),
+        ApiError
+    )
+)]
+pub async fn get_execution_order(
+    State(state): State<AppState>,
+    Json(request): Json<ExecutionOrderRequest>,
+) -> Result<Json<ExecutionOrderResponse>, ApiError> {
+    // The order is the waves flattened, computed once so both always agree
+    let plan = state.domain_service.get_execution_waves(request.task_ids).await
+        .map_err(ApiError::from)?;
+    let tasks = plan.waves.iter()
+        .flat_map(|wave| &wave.tasks)
+        .map(TaskResponse::from)
+        .collect();
+    let execution_waves = plan.waves.iter()
+        .map(|wave| wave.tasks.iter().map(|task| task.id.clone()).collect())
+        .collect();
+
+    Ok(Json(ExecutionOrderResponse {
+        tasks,
+        execution_waves,
+    }))
+}
+
+/// Turn free text into a task request for the client to review
+///
+/// POST /api/v1/tasks/parse
+///
+/// The task is not created.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/parse",
+    tag = "tasks",
+    request_body = ParseTaskRequest,
+    responses(
+        (status = 200, description = "Task request read from the text", body = NaturalLanguageParseResult),
+        ApiError
+    )
+)]
+pub async fn parse_task(
+    Json(request): Json<ParseTaskRequest>,
+) -> Result<Json<NaturalLanguageParseResult>, ApiError> {
+    if request.input.trim().is_empty() {
+        return Err(ApiError::bad_request("input cannot be empty"));
+    }
+
+    let result = parse_natural_language_task(&request.input)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    Ok(Json(result))
+}
+
+/// Find tasks whose description reads like the given text
+///
+/// POST /api/v1/tasks/similar-by-description
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/similar-by-description",
+    tag = "tasks",
+    request_body = SimilarByDescriptionRequest,
+    responses(
+        (status = 200, description = "Similar tasks, most similar first", body = Vec<SimilarTaskResponse>),
+        ApiError
+    )
+)]
+pub async fn find_tasks_similar_to_description(
+    State(state): State<AppState>,
+    Json(request): Json<SimilarByDescriptionRequest>,
+) -> Result<Json<Vec<SimilarTaskResponse>>, ApiError> {
+    if request.description.trim().is_empty() {
+        return Err(ApiError::bad_request("description cannot be empty"));
+    }
+    if request.limit == 0 || request.limit > MAX_SIMILAR_TASKS {
+        return Err(ApiError::bad_request(format!(
+            "limit must be between 1 and {}", MAX_SIMILAR_TASKS
+        )));
+    }
+
+    let similar = state.domain_service
+        .find_tasks_similar_to_description(&request.description, request.limit).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(similar.iter()
+        .map(|(task, similarity)| SimilarTaskResponse {
+            task: TaskResponse::from(task),
+            similarity: *similarity,
+        })
+        .collect()))
+}
+
+/// Suggest a description for a task that is about to be created
+///
+/// POST /api/v1/tasks/suggest-description
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/suggest-description",
+    tag = "tasks",
+    request_body = SuggestDescriptionRequest,
+    responses(
+        (status = 200, description = "Suggested description", body = DescriptionSuggestionResponse),
+        ApiError
+    )
+)]
+pub async fn suggest_task_description(
+    State(state): State<AppState>,
+    Json(request): Json<SuggestDescriptionRequest>,
+) -> Result<Json<DescriptionSuggestionResponse>, ApiError> {
+    if request.name.trim().is_empty() {
+        return Err(ApiError::bad_request("name cannot be empty"));
+    }
+
+    let similar_tasks = state.domain_service
+        .suggest_task_description(&request.name, request.context).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(DescriptionSuggestionResponse {
+        template: request.context.description_template().to_string(),
+        similar_tasks,
+    }))
+}
+
+/// Transition many tasks to the same status
+///
+/// Without `ignore_validation_errors` the batch stops at the first failure;
+/// tasks transitioned before it keep their new status. A single
+/// `TasksBulkStatusTransitioned` event covers every changed task.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/bulk-status-transition",
+    tag = "tasks",
+    params(Actor),
+    request_body = BulkStatusTransitionRequest,
+    responses(
+        (status = 200, description = "Transitioned and failed tasks", body = BulkTransitionResult),
+        ApiError
+    )
+)]
+pub async fn bulk_transition_task_status(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(request): Json<BulkStatusTransitionRequest>,
+) -> Result<Json<BulkTransitionResult>, ApiError> {
+    if request.task_ids.len() > MAX_BULK_TRANSITION_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "At most {} tasks can be transitioned at once, got {}",
+            MAX_BULK_TRANSITION_SIZE,
+            request.task_ids.len()
+        )));
+    }
+
+    let mut result = BulkTransitionResult {
+        transitioned: Vec::new(),
+        failed: Vec::new(),
+        events_published: 0,
+    };
+    for task_id in request.task_ids {
+        let transitioned = match check_bulk_access(&state, &actor, &task_id, false).await {
+            Ok(()) => state.domain_service.transition_task_status(&task_id, request.new_status, None).await
+                .map_err(|e| e.to_string()),
+            Err(reason) => Err(reason),
+        };
+        match transitioned {
+            Ok(_) => result.transitioned.push(task_id),
+            Err(reason) => {
+                result.failed.push((task_id, reason));
+                if !request.ignore_validation_errors {
+                    break;
+                }
+            }
+        }
+    }
+
+    if !result.transitioned.is_empty() {
+        let event = TasksBulkStatusTransitioned {
+            task_ids: result.transitioned.clone(),
+            new_status: request.new_status,
+            changed_by: None, // In a real implementation, get from auth context
+            changed_at: Utc::now(),
+        };
+        match publish_event_with_retry(&state.event_service, TasksBulkStatusTransitioned::TOPIC, event, 3).await {
+            Ok(()) => result.events_published = 1,
+            Err(e) => tracing::error!("Failed to publish task.bulk_status_transitioned event after retries: {}", e),
+        }
+    }
+
+    Ok(Json(result))
+}
+
+/// Move many tasks to the same status in a single write
+///
+/// POST /api/v1/tasks/bulk-status
+///
+/// Unlike the bulk transition, tasks failing validation never stop the
+/// batch and `skip_validation` bypasses the transition rules altogether.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/bulk-status",
+    tag = "tasks",
+    params(Actor),
+    request_body = BatchStatusRequest,
+    responses(
+        (status = 200, description = "Outcome of the batch", body = BatchStatusResult),
+        ApiError
+    )
+)]
+pub async fn batch_update_task_status(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(request): Json<BatchStatusRequest>,
+) -> Result<Json<BatchStatusResult>, ApiError> {
+    if request.task_ids.len() > MAX_BULK_TRANSITION_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "At most {} tasks can be updated at once, got {}",
+            MAX_BULK_TRANSITION_SIZE,
+            request.task_ids.len()
+        )));
+    }
+
+    let (task_ids, denied) = partition_by_access(&state, &actor, request.task_ids, false).await;
+    let mut result = state.domain_service
+        .batch_update_status(task_ids, request.new_status, request.skip_validation).await
+        .map_err(ApiError::from)?;
+    result.failed.extend(denied);
+
+    // Published in the background, the response does not wait for room in the event channel
+    if !result.succeeded.is_empty() {
+        let event = BatchStatusChanged {
+            task_ids: result.succeeded.clone(),
+            new_status: request.new_status,
+            changed_at: Utc::now(),
+        };
+        let event_service = state.event_service.clone();
+        state.event_service.spawn_background_publish(async move {
+            if let Err(e) = publish_event_with_retry(&event_service, BatchStatusChanged::TOPIC, event, 3).await {
+                tracing::error!("Failed to publish task.batch_status_changed event after retries: {}", e);
+            }
+        });
+    }
+
+    Ok(Json(result))
+}
+
+/// Delete many tasks at once
+///
+/// POST /api/v1/tasks/bulk-delete
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/bulk-delete",
+    tag = "tasks",
+    params(Actor),
+    request_body = BulkDeleteRequest,
+    responses(
+        (status = 200, description = "Deleted and skipped tasks", body = BulkDeleteResult),
+        ApiError
+    )
+)]
+pub async fn bulk_delete_tasks(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(request): Json<BulkDeleteRequest>,
+) -> Result<Json<BulkDeleteResult>, ApiError> {
+    let span = start_span(&state, "bulk_delete_tasks")?;
+    add_span_attribute(&span, "task_count", serde_json::json!(request.task_ids.len()))?;
+
+    if request.task_ids.len() > MAX_BULK_DELETE_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "At most {} tasks can be deleted at once, got {}",
+            MAX_BULK_DELETE_SIZE,
+            request.task_ids.len()
+        )));
+    }
+
+    let (task_ids, denied) = partition_by_access(&state, &actor, request.task_ids, true).await;
+    let mut result = state.domain_service.bulk_delete_tasks(task_ids, request.force).await
+        .map_err(ApiError::from)?;
+    result.failed.extend(denied);
+
+    tracing::info!(
+        deleted = result.deleted.len(),
+        failed = result.failed.len(),
+        orphaned_dependencies_removed = result.orphaned_dependencies_removed,
+        "Bulk task deletion finished"
+    );
+
+    Ok(Json(result))
+}
+
+/// Assign task to user
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/assign",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = AssignTaskRequest,
+    responses(
+        (status = 200, description = "Task assigned"),
+        ApiError
+    )
+)]
+pub async fn assign_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<AssignTaskRequest>,
+) -> Result<StatusCode, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_assign_task(&actor, &task).await, "assign", &task)?;
+    let role = request.role.as_deref().unwrap_or("owner");
+    
+    state.domain_service.assign_task(&task_id, &request.user_id, role).await
+        .map_err(ApiError::from)?;
+
+    // Publish task assigned event
+    let event = TaskAssigned {
+        task_id: task_id.clone(),
+        user_id: request.user_id,
+        role: role.to_string(),
+        assigned_by: actor.user_id,
+        assigned_at: Utc::now(),
+    };
+    
+    if let Err(e) = state.event_service.publish(TaskAssigned::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.assigned event: {}", e);
+    }
+
+    Ok(StatusCode::OK)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RequestReviewRequest {
+    pub reviewer_id: String,
+}
+
+/// Ask a user to review a task
+///
+/// Publishes `TaskReviewRequested`.
+///
+/// POST /api/v1/tasks/:id/review-requests
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/review-requests",
+    tag = "tasks",
+    params(("id" = String, Path, description = "Task ID")),
+    request_body = RequestReviewRequest,
+    responses(
+        (status = 200, description = "Review requested"),
+        ApiError
+    )
+)]
+pub async fn request_review(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    Json(request): Json<RequestReviewRequest>,
+) -> Result<StatusCode, ApiError> {
+    if request.reviewer_id.trim().is_empty() {
+        return Err(ApiError::bad_request("reviewer_id cannot be empty"));
+    }
+    if state.domain_service.get_task_by_id(&task_id).await.map_err(ApiError::from)?.is_none() {
+        return Err(ApiError::not_found("Task", &task_id));
+    }
+
+    state.domain_service.request_review(&task_id, &request.reviewer_id).await
+        .map_err(ApiError::from)?;
+
+    let event = TaskReviewRequested {
+        task_id,
+        reviewer_id: request.reviewer_id,
+        requested_at: Utc::now(),
+    };
+    if let Err(e) = publish_event_with_retry(&state.event_service, TaskReviewRequested::TOPIC, event, 3).await {
+        tracing::error!("Failed to publish task.review_requested event after retries: {}", e);
+    }
+
+    Ok(StatusCode::OK)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagTaskRequest {
+    pub tags: Vec<String>,
+}
+
+/// Add tags to a task
+///
+/// Publishes `TaskTagged` for every tag that was added.
+///
+/// POST /api/v1/tasks/:id/tags
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/tags",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = TagTaskRequest,
+    responses(
+        (status = 200, description = "The tagged task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn tag_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<TagTaskRequest>,
+) -> Result<Json<TaskResponse>, ApiError> {
+    if request.tags.is_empty() || request.tags.iter().any(|tag| tag.trim().is_empty()) {
+        return Err(ApiError::bad_request("tags must contain at least one non-empty tag name"));
+    }
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "tag", &task)?;
+
+    let added = state.domain_service.tag_task(&task_id, &request.tags).await
+        .map_err(ApiError::from)?;
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+
+    for tag in added {
+        let event = TaskTagged {
+            task_id: task_id.clone(),
+            tag_id: tag.id,
+            tag_name: tag.name,
+            tag_category: tag.category,
+            tagged_by: None,
+            tagged_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(&state.event_service, TaskTagged::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish task.tagged event after retries: {}", e);
+        }
+    }
+
+    Ok(Json(TaskResponse::from(&task)))
+}
+
+/// Remove a tag from a task
+///
+/// DELETE /api/v1/tasks/:id/tags/:tag
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tasks/{id}/tags/{tag}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("tag" = String, Path, description = "Tag name"),
+        Actor
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        ApiError
+    )
+)]
+pub async fn untag_task(
+    State(state): State<AppState>,
+    Path((task_id, tag)): Path<(String, String)>,
+    actor: Actor,
+) -> Result<StatusCode, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "untag", &task)?;
+
+    state.domain_service.untag_task(&task_id, &tag).await
+        .map_err(ApiError::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Tags with the number of tasks carrying each, most used first
+///
+/// GET /api/v1/tasks/tags
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/tags",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Tags, most used first", body = Vec<TagUsage>),
+        ApiError
+    )
+)]
+pub async fn get_tag_usage(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TagUsage>>, ApiError> {
+    let usage = state.domain_service.get_tag_usage().await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(usage))
+}
+
+/// Tasks carrying a tag
+///
+/// GET /api/v1/tasks/tags/:tag
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/tags/{tag}",
+    tag = "tasks",
+    params(
+        ("tag" = String, Path, description = "Tag name"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Tasks carrying the tag", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn get_tasks_by_tag(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+    actor: Actor,
+) -> Result<Json<TaskListResponse>, ApiError> {
+    let filter = TaskFilter {
+        tags: Some(vec![tag]),
+        ..Default::default()
+    };
+    let tasks = state.domain_service.list_tasks(filter).await
+        .map_err(ApiError::from)?;
+    let tasks = readable_tasks(&state, &actor, tasks).await;
+
+    let task_responses: Vec<TaskResponse> = tasks.iter()
+        .map(TaskResponse::from)
+        .collect();
+
+    let response = TaskListResponse {
+        has_more: false,
+        total_count: Some(task_responses.len()),
+        next_cursor: None,
+        tasks: task_responses,
+    };
+
+    Ok(Json(response))
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReviewQueueParams {
+    pub reviewer_id: Option<String>,
+    pub project_id: Option<String>,
+}
+
+/// Tasks waiting in review, longest waiting first
+///
+/// GET /api/v1/tasks/review-queue?reviewer_id=&project_id=
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/review-queue",
+    tag = "tasks",
+    params(ReviewQueueParams),
+    responses(
+        (status = 200, description = "Tasks in review, longest waiting first", body = Vec<ReviewQueueItem>),
+        ApiError
+    )
+)]
+pub async fn get_review_queue(
+    State(state): State<AppState>,
+    Query(params): Query<ReviewQueueParams>,
+) -> Result<Json<Vec<ReviewQueueItem>>, ApiError> {
+    let queue = state.domain_service
+        .get_tasks_ready_for_review(params.reviewer_id.as_deref(), params.project_id.as_deref()).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(queue))
+}
+
+/// Get assigned tasks for a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/tasks",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Tasks assigned to the user", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn get_assigned_tasks(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Result<Json<TaskListResponse>, ApiError> {
+    let tasks = state.domain_service.get_assigned_tasks(&user_id).await
+        .map_err(ApiError::from)?;
+
+    let task_responses: Vec<TaskResponse> = tasks.iter()
+        .map(TaskResponse::from)
+        .collect();
+
+    let response = TaskListResponse {
+        has_more: false,
+        total_count: Some(task_responses.len()),
+        next_cursor: None,
+        tasks: task_responses,
+    };
+
+    Ok(Json(response))
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ReassignTasksRequest {
+    /// User taking over the tasks; they are unassigned when absent
+    pub to_user_id: Option<String>,
+    /// Only move tasks in one of these statuses, e.g. to leave done tasks alone
+    pub status_filter: Option<Vec<TaskStatus>>,
+}
+
+/// Move every task of a user to another user, or unassign them
+///
+/// POST /api/v1/users/:user_id/reassign-tasks
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/reassign-tasks",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User whose tasks are moved")),
+    request_body = ReassignTasksRequest,
+    responses(
+        (status = 200, description = "Tasks moved and tasks left out by the status filter", body = ReassignmentReport),
+        ApiError
+    )
+)]
+pub async fn reassign_user_tasks(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Json(request): Json<ReassignTasksRequest>,
+) -> Result<Json<ReassignmentReport>, ApiError> {
+    let to_user_id = request.to_user_id.as_deref().map(str::trim);
+    if to_user_id.is_some_and(str::is_empty) {
+        return Err(ApiError::bad_request("to_user_id cannot be empty"));
+    }
+
+    let report = state.domain_service
+        .reassign_all_tasks(&user_id, to_user_id, request.status_filter).await
+        .map_err(ApiError::from)?;
+
+    let event = BulkTasksReassigned {
+        from_user_id: user_id,
+        to_user_id: to_user_id.map(str::to_string),
+        task_count: report.reassigned.len(),
+        reassigned_at: Utc::now(),
+    };
+    if let Err(e) = publish_event_with_retry(&state.event_service, BulkTasksReassigned::TOPIC, event, 3).await {
+        tracing::error!("Failed to publish task.bulk_reassigned event after retries: {}", e);
+    }
+
+    Ok(Json(report))
+}
+
+/// Get actionable tasks for a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/tasks/actionable",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Tasks the user can start now", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn get_actionable_tasks(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Result<Json<TaskListResponse>, ApiError> {
+    let tasks = state.domain_service.get_actionable_tasks(&user_id).await
+        .map_err(ApiError::from)?;
+
+    let task_responses: Vec<TaskResponse> = tasks.iter()
+        .map(TaskResponse::from)
+        .collect();
+
+    let response = TaskListResponse {
+        has_more: false,
+        total_count: Some(task_responses.len()),
+        next_cursor: None,
+        tasks: task_responses,
+    };
+
+    Ok(Json(response))
+}
+
+/// Actionable task with its place in the user's work order
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderedTaskResponse {
+    pub task: TaskResponse,
+    pub rank: u32,
+    pub score: f64,
+    pub reasoning: Vec<String>,
+}
+
+impl From<OrderedTask> for OrderedTaskResponse {
+    fn from(ordered: OrderedTask) -> Self {
+        Self {
+            task: TaskResponse::from(&ordered.task),
+            rank: ordered.rank,
+            score: ordered.score,
+            reasoning: ordered.reasoning,
+        }
+    }
+}
+
+/// Get a user's actionable tasks ranked by urgency, priority, impact and complexity
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/task-order",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Actionable tasks in work order", body = Vec<OrderedTaskResponse>),
+        ApiError
+    )
+)]
+pub async fn get_task_order(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Result<Json<Vec<OrderedTaskResponse>>, ApiError> {
+    let ordered = state.domain_service.get_optimal_task_ordering(&user_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ordered.into_iter().map(OrderedTaskResponse::from).collect()))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StandupQueryParams {
+    /// Day of the standup, today when omitted
+    pub date: Option<NaiveDate>,
+}
+
+/// A user's standup: completed yesterday, in progress and planned today, and blockers
+///
+/// GET /api/v1/users/:user_id/standup?date=2025-06-15
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/standup",
+    tag = "users",
+    params(
+        ("user_id" = String, Path, description = "User ID"),
+        StandupQueryParams
+    ),
+    responses(
+        (status = 200, description = "The standup report", body = StandupReport),
+        ApiError
+    )
+)]
+pub async fn get_daily_standup_report(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Query(params): Query<StandupQueryParams>,
+) -> Result<Json<StandupReport>, ApiError> {
+    let date = params.date.unwrap_or_else(|| Utc::now().date_naive());
+    let report = state.domain_service.get_daily_standup_report(&user_id, date).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(report))
+}
+
+/// Get overdue tasks
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/overdue",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Overdue tasks", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn get_overdue_tasks(
+    State(state): State<AppState>,
+) -> Result<Json<TaskListResponse>, ApiError> {
+    let tasks = state.domain_service.get_overdue_tasks().await
+        .map_err(ApiError::from)?;
+
+    let task_responses: Vec<TaskResponse> = tasks.iter()
+        .map(TaskResponse::from)
+        .collect();
+
+    let response = TaskListResponse {
+        has_more: false,
+        total_count: Some(task_responses.len()),
+        next_cursor: None,
+        tasks: task_responses,
+    };
+
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DueSoonQueryParams {
+    /// Lookahead window in hours, 24 by default
+    pub hours: Option<i64>,
+    /// Only `assignee` is supported
+    pub group_by: Option<String>,
+}
+
+/// Open tasks due within the lookahead window
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DueSoonResponse {
+    pub tasks: Vec<TaskResponse>,
+    /// Tasks per assigned user id, only with `group_by=assignee`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub grouped: HashMap<String, Vec<TaskResponse>>,
+    pub earliest_due: Option<DateTime<Utc>>,
+}
+
+impl From<DueSoonReport> for DueSoonResponse {
+    fn from(report: DueSoonReport) -> Self {
+        Self {
+            tasks: report.tasks.iter().map(TaskResponse::from).collect(),
+            grouped: report.grouped.iter()
+                .map(|(user_id, tasks)| (user_id.clone(), tasks.iter().map(TaskResponse::from).collect()))
+                .collect(),
+            earliest_due: report.earliest_due,
+        }
+    }
+}
+
+/// Open tasks due in the next hours, earliest first
+///
+/// GET /api/v1/tasks/due-soon?hours=24&group_by=assignee
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/due-soon",
+    tag = "tasks",
+    params(
+        DueSoonQueryParams,
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Open tasks due within the window", body = DueSoonResponse),
+        ApiError
+    )
+)]
+pub async fn get_tasks_due_soon(
+    State(state): State<AppState>,
+    Query(params): Query<DueSoonQueryParams>,
+    actor: Actor,
+) -> Result<Json<DueSoonResponse>, ApiError> {
+    let hours = params.hours.unwrap_or(24);
+    if !(1..=MAX_DUE_SOON_LOOKAHEAD_HOURS).contains(&hours) {
+        return Err(ApiError::bad_request(format!(
+            "hours must be between 1 and {}",
+            MAX_DUE_SOON_LOOKAHEAD_HOURS
+        )));
+    }
+    let group_by_assignee = match params.group_by.as_deref() {
+        None => false,
+        Some("assignee") => true,
+        Some(other) => {
+            return Err(ApiError::bad_request(format!("Unsupported group_by '{}', expected 'assignee'", other)));
+        }
+    };
+
+    let mut report = state.domain_service.get_tasks_due_soon(chrono::Duration::hours(hours), group_by_assignee).await
+        .map_err(ApiError::from)?;
+    report.tasks = readable_tasks(&state, &actor, report.tasks).await;
+    let readable_ids: HashSet<&str> = report.tasks.iter().map(|task| task.id.as_str()).collect();
+    for tasks in report.grouped.values_mut() {
+        tasks.retain(|task| readable_ids.contains(task.id.as_str()));
+    }
+    report.grouped.retain(|_, tasks| !tasks.is_empty());
+    report.earliest_due = report.tasks.first().and_then(|task| task.due_date);
+
+    Ok(Json(DueSoonResponse::from(report)))
+}
+
+/// Get task analytics
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{id}/analytics",
+    tag = "tasks",
+    params(("id" = String, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Analytics of the task", body = TaskAnalyticsResponse),
+        ApiError
+    )
+)]
+pub async fn get_task_analytics(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskAnalyticsResponse>, ApiError> {
+    let analytics = state.domain_service.get_task_analytics(&task_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TaskAnalyticsResponse::from(&analytics)))
+}
+
+/// Add subtask
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{parent_id}/subtasks/{child_id}",
+    tag = "tasks",
+    params(
+        ("parent_id" = String, Path, description = "Parent task ID"),
+        ("child_id" = String, Path, description = "Subtask ID"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Subtask added"),
+        ApiError
+    )
+)]
+pub async fn add_subtask(
+    State(state): State<AppState>,
+    Path((parent_id, child_id)): Path<(String, String)>,
+    actor: Actor,
+) -> Result<StatusCode, ApiError> {
+    for task_id in [&parent_id, &child_id] {
+        let task = state.domain_service.get_task_by_id(task_id).await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::not_found("Task", task_id))?;
+        auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "update", &task)?;
+    }
+
+    state.domain_service.add_subtask(&parent_id, &child_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(StatusCode::OK)
+}
+
+/// Get subtasks
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{parent_id}/subtasks",
+    tag = "tasks",
+    params(
+        ("parent_id" = String, Path, description = "Parent task ID"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "Subtasks of the task", body = TaskListResponse),
+        ApiError
+    )
+)]
+pub async fn get_subtasks(
+    State(state): State<AppState>,
+    Path(parent_id): Path<String>,
+    actor: Actor,
+) -> Result<Json<TaskListResponse>, ApiError> {
+    let parent = state.domain_service.get_task_by_id(&parent_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &parent_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &parent).await, "read", &parent)?;
+
+    let tasks = state.domain_service.get_subtasks(&parent_id).await
+        .map_err(ApiError::from)?;
+    let tasks = readable_tasks(&state, &actor, tasks).await;
+
+    let task_responses: Vec<TaskResponse> = tasks.iter()
+        .map(TaskResponse::from)
+        .collect();
+
+    let response = TaskListResponse {
+        has_more: false,
+        total_count: Some(task_responses.len()),
+        next_cursor: None,
+        tasks: task_responses,
+    };
+
+    Ok(Json(response))
+}
+
+/// Get circular dependency analysis
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/circular-dependencies",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Dependency cycles found", body = CircularDependenciesResponse),
+        ApiError
+    )
+)]
+pub async fn get_circular_dependencies(
+    State(state): State<AppState>,
+) -> Result<Json<CircularDependenciesResponse>, ApiError> {
+    let cycles = state.domain_service.get_detailed_circular_dependencies().await
+        .map_err(ApiError::from)?;
+
+    let response = CircularDependenciesResponse {
+        total_cycles: cycles.len(),
+        has_critical_cycles: cycles.iter().any(|c| matches!(c.severity, crate::domain::queries::CycleSeverity::Critical)),
+        cycles,
+    };
+
+    Ok(Json(response))
+}
+
+/// Query parameters for dependency chain analysis
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DependencyChainQueryParams {
+    pub top: Option<usize>,
+}
+
+/// Get the longest dependency chains of a project
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/dependency-chains",
+    tag = "projects",
+    params(
+        ("id" = String, Path, description = "Project ID"),
+        DependencyChainQueryParams
+    ),
+    responses(
+        (status = 200, description = "Longest dependency chains, longest first", body = Vec<crate::domain::queries::DependencyPath>),
+        ApiError
+    )
+)]
+pub async fn get_project_dependency_chains(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(params): Query<DependencyChainQueryParams>,
+) -> Result<Json<Vec<crate::domain::queries::DependencyPath>>, ApiError> {
+    let top_n = params.top.unwrap_or(5).min(50);
+    let chains = state.domain_service.get_dependency_chains_for_project(&project_id, top_n).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(chains))
+}
+
+/// Clone a project as the starting point for a similar one
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{id}/clone",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = CloneProjectOptions,
+    responses(
+        (status = 201, description = "The cloned project and its tasks", body = CloneProjectResult),
+        ApiError
+    )
+)]
+pub async fn clone_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(options): Json<CloneProjectOptions>,
+) -> Result<(StatusCode, Json<CloneProjectResult>), ApiError> {
+    let result = state.domain_service.clone_project(&project_id, options).await
+        .map_err(ApiError::from)?;
+
+    tracing::info!(
+        source_project_id = %project_id,
+        new_project_id = %result.new_project_id,
+        cloned_tasks = result.cloned_task_count,
+        "Project cloned"
+    );
+
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+/// Clone a task, optionally with its subtasks, dependencies and assignments
+///
+/// POST /api/v1/tasks/{id}/clone
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/clone",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = CloneTaskOverrides,
+    responses(
+        (status = 201, description = "The cloned task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn clone_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(overrides): Json<CloneTaskOverrides>,
+) -> Result<(StatusCode, Json<TaskResponse>), ApiError> {
+    let source = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_read_task(&actor, &source).await, "clone", &source)?;
+    if let Some(ref id) = overrides.id {
+        if state.domain_service.get_task_by_id(id).await.map_err(ApiError::from)?.is_some() {
+            return Err(ApiError::new("CONFLICT", format!("Task '{}' already exists", id)));
+        }
+    }
+
+    let task = state.domain_service.clone_task(&task_id, overrides).await
+        .map_err(ApiError::from)?;
+
+    let event = TaskCloned {
+        source_task_id: task_id,
+        cloned_task_id: task.id.clone(),
+        cloned_at: Utc::now(),
+    };
+    if let Err(e) = state.event_service.publish(TaskCloned::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.cloned event: {}", e);
+    }
+
+    Ok((StatusCode::CREATED, Json(TaskResponse::from(&task))))
+}
+
+/// Request body of a task merge
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeTasksRequest {
+    pub target_id: String,
+    #[serde(flatten)]
+    pub options: MergeOptions,
+}
+
+/// Merge a duplicate task into another one, returning the task merged into
+///
+/// POST /api/v1/tasks/{id}/merge
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/merge",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = MergeTasksRequest,
+    responses(
+        (status = 200, description = "The task merged into", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn merge_tasks(
+    State(state): State<AppState>,
+    Path(source_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<MergeTasksRequest>,
+) -> Result<Json<TaskResponse>, ApiError> {
+    if source_id == request.target_id {
+        return Err(ApiError::bad_request("A task cannot be merged into itself"));
+    }
+    for task_id in [&source_id, &request.target_id] {
+        let task = state.domain_service.get_task_by_id(task_id).await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::not_found("Task", task_id))?;
+        auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "merge", &task)?;
+    }
+
+    let task = state.domain_service.merge_tasks(&source_id, &request.target_id, request.options).await
+        .map_err(ApiError::from)?;
+
+    let event = TasksMerged {
+        source_id,
+        target_id: task.id.clone(),
+        merged_at: Utc::now(),
+    };
+    if let Err(e) = state.event_service.publish(TasksMerged::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.merged event: {}", e);
+    }
+
+    Ok(Json(TaskResponse::from(&task)))
+}
+
+/// Request body of an epic split
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SplitTaskApiRequest {
+    pub subtasks: Vec<CreateTaskApiRequest>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SplitTaskResponse {
+    pub parent: TaskResponse,
+    pub subtasks: Vec<TaskResponse>,
+}
+
+/// Split an epic into new subtasks, starting the epic
+///
+/// POST /api/v1/tasks/{id}/split
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/split",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = SplitTaskApiRequest,
+    responses(
+        (status = 201, description = "The started epic and its new subtasks", body = SplitTaskResponse),
+        ApiError
+    )
+)]
+pub async fn split_task(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<SplitTaskApiRequest>,
+) -> Result<(StatusCode, Json<SplitTaskResponse>), ApiError> {
+    let parent = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &parent).await, "split", &parent)?;
+    if parent.complexity != TaskComplexity::Epic {
+        return Err(ApiError::bad_request(format!("Only epics can be split, {} is {:?}", task_id, parent.complexity)));
+    }
+    if request.subtasks.is_empty() {
+        return Err(ApiError::bad_request("At least one subtask is required"));
+    }
+
+    let mut subtask_requests = Vec::with_capacity(request.subtasks.len());
+    for subtask in request.subtasks {
+        let custom_properties = subtask.custom_properties.clone().unwrap_or_default();
+        validate_context_custom_properties(&state.config.context_schemas, subtask.context, &custom_properties)
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        check_custom_property_schema(&state, subtask.context, &custom_properties)?;
+        let mut domain_request = create_task_request(subtask, custom_properties);
+        domain_request.created_by = actor.user_id.clone();
+        if let Some(Err(e)) = domain_request.recurrence.as_ref().map(validate_recurrence) {
+            return Err(ApiError::bad_request(e.to_string()));
+        }
+        domain_request.validate().map_err(ApiError::from)?;
+        subtask_requests.push(domain_request);
+    }
+    let links: HashMap<String, (Option<String>, Option<String>)> = subtask_requests.iter()
+        .map(|r| (r.id.clone(), (r.assigned_user_id.clone(), r.project_id.clone())))
+        .collect();
+
+    let result = state.domain_service.split_task(&task_id, subtask_requests).await
+        .map_err(ApiError::from)?;
+
+    for subtask in &result.subtasks {
+        let (assigned_user_id, project_id) = links.get(&subtask.id).cloned().unwrap_or_default();
+        let event = TaskCreated {
+            task_id: subtask.id.clone(),
+            name: subtask.name.clone(),
+            context: subtask.context,
+            priority: subtask.priority,
+            assigned_user_id,
+            project_id,
+            created_at: subtask.created_at,
+        };
+        if let Err(e) = state.event_service.publish(TaskCreated::TOPIC, event).await {
+            tracing::warn!("Failed to publish task.created event: {}", e);
+        }
+    }
+    if result.parent.status != parent.status {
+        let event = TaskStatusChanged {
+            task_id: task_id.clone(),
+            previous_status: parent.status,
+            new_status: result.parent.status,
+            changed_by: actor.user_id,
+            comment: Some("Split into subtasks".to_string()),
+            changed_at: result.parent.updated_at,
+        };
+        if let Err(e) = state.event_service.publish(TaskStatusChanged::TOPIC, event).await {
+            tracing::warn!("Failed to publish task.status_changed event: {}", e);
+        }
+    }
+    let event = TaskSplit {
+        parent_id: task_id,
+        subtask_ids: result.subtasks.iter().map(|subtask| subtask.id.clone()).collect(),
+        split_at: Utc::now(),
+    };
+    if let Err(e) = state.event_service.publish(TaskSplit::TOPIC, event).await {
+        tracing::warn!("Failed to publish task.split event: {}", e);
+    }
+
+    Ok((StatusCode::CREATED, Json(SplitTaskResponse {
+        parent: TaskResponse::from(&result.parent),
+        subtasks: result.subtasks.iter().map(TaskResponse::from).collect(),
+    })))
+}
+
+/// Mark a single success criterion of a task as done
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tasks/{id}/criteria/{index}/complete",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("index" = usize, Path, description = "Position of the success criterion"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn complete_success_criterion(
+    State(state): State<AppState>,
+    Path((task_id, index)): Path<(String, usize)>,
+    actor: Actor,
+) -> Result<Json<TaskResponse>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "update", &task)?;
+    if index >= task.success_criteria.len() {
+        return Err(ApiError::not_found("Success criterion", format!("{}/{}", task_id, index)));
+    }
+
+    let task = state.domain_service.complete_success_criterion(&task_id, index).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TaskResponse::from(&task)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSuccessCriterionRequest {
+    pub completed: bool,
+}
+
+/// Mark a success criterion of a task as done or open again
+///
+/// PATCH /api/v1/tasks/:id/criteria/:index
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tasks/{id}/criteria/{index}",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("index" = usize, Path, description = "Position of the success criterion"),
+        Actor
+    ),
+    request_body = UpdateSuccessCriterionRequest,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn update_success_criterion(
+    State(state): State<AppState>,
+    Path((task_id, index)): Path<(String, usize)>,
+    actor: Actor,
+    Json(request): Json<UpdateSuccessCriterionRequest>,
+) -> Result<Json<TaskResponse>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "update", &task)?;
+    if index >= task.success_criteria.len() {
+        return Err(ApiError::not_found("Success criterion", format!("{}/{}", task_id, index)));
+    }
+
+    let task = state.domain_service.update_success_criterion(&task_id, index, request.completed).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TaskResponse::from(&task)))
+}
+
+/// Open a completed success criterion of a task again
+///
+/// PATCH /api/v1/tasks/:id/criteria/:index/uncomplete
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tasks/{id}/criteria/{index}/uncomplete",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("index" = usize, Path, description = "Position of the success criterion"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse),
+        ApiError
+    )
+)]
+pub async fn uncomplete_success_criterion(
+    State(state): State<AppState>,
+    Path((task_id, index)): Path<(String, usize)>,
+    actor: Actor,
+) -> Result<Json<TaskResponse>, ApiError> {
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "update", &task)?;
+    if index >= task.success_criteria.len() {
+        return Err(ApiError::not_found("Success criterion", format!("{}/{}", task_id, index)));
+    }
+
+    let task = state.domain_service.update_success_criterion(&task_id, index, false).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TaskResponse::from(&task)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PresignAttachmentRequest {
+    pub filename: String,
+    pub content_type: String,
+}
+
+/// Attachment service of the state, 503 when no storage is configured
+fn attachment_service(state: &AppState) -> Result<&AttachmentService, ApiError> {
+    state.attachment_service.as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Attachment storage is not configured"))
+}
+
+/// Get a URL to upload a new attachment of the task to
+///
+/// POST /api/v1/tasks/:id/attachments/presign
+///
+/// The file is uploaded straight to storage with a `PUT` to `upload_url`,
+/// sending `upload_headers`, and then confirmed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/attachments/presign",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        Actor
+    ),
+    request_body = PresignAttachmentRequest,
+    responses(
+        (status = 201, description = "Where and how to upload the file", body = PresignedUpload),
+        ApiError
+    )
+)]
+pub async fn presign_task_attachment(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    actor: Actor,
+    Json(request): Json<PresignAttachmentRequest>,
+) -> Result<(StatusCode, Json<PresignedUpload>), ApiError> {
+    let attachments = attachment_service(&state)?;
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "attach files to", &task)?;
+    validate_upload(&request.filename, &request.content_type).map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let upload = attachments.generate_upload_url(&task_id, &request.filename, &request.content_type).await
+        .map_err(ApiError::from)?;
+
+    Ok((StatusCode::CREATED, Json(upload)))
+}
+
+/// Record an uploaded file as an attachment of the task
+///
+/// POST /api/v1/tasks/:id/attachments/:attachment_id/confirm
+///
+/// Answers 409 while the file has not arrived in storage.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/{id}/attachments/{attachment_id}/confirm",
+    tag = "tasks",
+    params(
+        ("id" = String, Path, description = "Task ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID"),
+        Actor
+    ),
+    responses(
+        (status = 200, description = "The recorded attachment", body = TaskAttachment),
+        ApiError
+    )
+)]
+pub async fn confirm_task_attachment(
+    State(state): State<AppState>,
+    Path((task_id, attachment_id)): Path<(String, String)>,
+    actor: Actor,
+) -> Result<Json<TaskAttachment>, ApiError> {
+    let attachments = attachment_service(&state)?;
+    let task = state.domain_service.get_task_by_id(&task_id).await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Task", &task_id))?;
+    auth::require(state.authorization_policy.can_write_task(&actor, &task).await, "attach files to", &task)?;
+    if !attachments.is_pending(&task_id, &attachment_id) {
+        return Err(ApiError::not_found("Pending attachment", &attachment_id));
+    }
+    if !attachments.is_uploaded(&task_id, &attachment_id).await.map_err(ApiError::from)? {
+        return Err(ApiError::new(
+            "CONFLICT",
+            format!("The file of attachment '{}' has not been uploaded yet", attachment_id),
+        ));
+    }
+
+    let attachment = attachments.confirm_attachment(&task_id, &attachment_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(attachment))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SnapshotProjectRequest {
+    pub label: String,
+}
+
+/// Capture the project's current metrics as a baseline
+///
+/// POST /api/v1/projects/:id/snapshots
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{id}/snapshots",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = SnapshotProjectRequest,
+    responses(
+        (status = 201, description = "The captured snapshot", body = ProjectSnapshot),
+        ApiError
+    )
+)]
+pub async fn snapshot_project_state(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<SnapshotProjectRequest>,
+) -> Result<(StatusCode, Json<ProjectSnapshot>), ApiError> {
+    if request.label.trim().is_empty() {
+        return Err(ApiError::bad_request("label cannot be empty"));
+    }
+
+    let snapshot = state.domain_service.snapshot_project_state(&project_id, request.label).await
+        .map_err(ApiError::from)?;
+
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+/// How the project's metrics changed since a snapshot
+///
+/// GET /api/v1/projects/:id/snapshots/:snapshot_id/diff
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/snapshots/{snapshot_id}/diff",
+    tag = "projects",
+    params(
+        ("id" = String, Path, description = "Project ID"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID")
+    ),
+    responses(
+        (status = 200, description = "Metric changes since the snapshot", body = SnapshotDiff),
+        ApiError
+    )
+)]
+pub async fn get_snapshot_diff(
+    State(state): State<AppState>,
+    Path((project_id, snapshot_id)): Path<(String, String)>,
+) -> Result<Json<SnapshotDiff>, ApiError> {
+    let diff = state.domain_service.get_snapshot_diff(&snapshot_id).await
+        .map_err(ApiError::from)?;
+    if diff.project_id != project_id {
+        return Err(ApiError::not_found("Snapshot", &snapshot_id));
+    }
+
+    Ok(Json(diff))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferOwnershipRequest {
+    pub new_owner_id: String,
+    pub reason: String,
+}
+
+/// Make another user the project lead
+///
+/// PUT /api/v1/projects/:id/ownership
+#[utoipa::path(
+    put,
+    path = "/api/v1/projects/{id}/ownership",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = TransferOwnershipRequest,
+    responses(
+        (status = 200, description = "Outcome of the transfer", body = OwnershipTransferResult),
+        ApiError
+    )
+)]
+pub async fn transfer_project_ownership(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<TransferOwnershipRequest>,
+) -> Result<Json<OwnershipTransferResult>, ApiError> {
+    if request.new_owner_id.trim().is_empty() {
+        return Err(ApiError::bad_request("new_owner_id cannot be empty"));
+    }
+    if request.reason.trim().is_empty() {
+        return Err(ApiError::bad_request("reason cannot be empty"));
+    }
+
+    let result = state.project_service
+        .transfer_project_ownership(&project_id, &request.new_owner_id, request.reason.clone()).await
+        .map_err(ApiError::from)?;
+
+    let event = ProjectOwnershipTransferred {
+        project_id: project_id.clone(),
+        previous_owner_id: result.previous_owner_id.clone(),
+        new_owner_id: request.new_owner_id,
+        reason: request.reason,
+        auto_assigned_task_ids: result.auto_assigned_task_ids.clone(),
+        transferred_at: Utc::now(),
+    };
+    if let Err(e) = publish_event_with_retry(&state.event_service, ProjectOwnershipTransferred::TOPIC, event, 3).await {
+        tracing::error!("Failed to publish project.ownership_transferred event after retries: {}", e);
+    }
+
+    Ok(Json(result))
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ResolveBottleneckActionRequest {
+    pub resolved_by: Option<String>,
+}
+
+/// Mark a suggested action of a bottleneck as carried out
+///
+/// Publishes `BottleneckResolved` when this was the bottleneck's last
+/// outstanding action. The request body is optional.
+#[utoipa::path(
+    post,
+    path = "/api/v1/analytics/bottlenecks/{id}/actions/{index}/resolve",
+    tag = "analytics",
+    params(
+        ("id" = String, Path, description = "Bottleneck ID"),
+        ("index" = u32, Path, description = "Position of the action")
+    ),
+    request_body = Option<ResolveBottleneckActionRequest>,
+    responses(
+        (status = 200, description = "Progress on the bottleneck", body = BottleneckProgress),
+        ApiError
+    )
+)]
+pub async fn resolve_bottleneck_action(
+    State(state): State<AppState>,
+    Path((bottleneck_id, action_index)): Path<(String, u32)>,
+    request: Option<Json<ResolveBottleneckActionRequest>>,
+) -> Result<Json<BottleneckProgress>, ApiError> {
+    let Json(request) = request.unwrap_or_default();
+    let progress = state.domain_service
+        .resolve_bottleneck_action(&bottleneck_id, action_index, request.resolved_by.clone()).await
+        .map_err(ApiError::from)?;
+
+    if progress.is_resolved() {
+        let event = BottleneckResolved {
+            bottleneck_id: bottleneck_id.clone(),
+            resolved_actions: progress.resolved_actions,
+            resolved_by: request.resolved_by,
+            resolved_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(&state.event_service, BottleneckResolved::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish analytics.bottleneck_resolved event after retries: {}", e);
+        }
+    }
+
+    Ok(Json(progress))
+}
+
+/// Dependencies on cancelled tasks or on work that has stalled for months
+///
+/// GET /api/v1/analytics/stale-dependencies
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/stale-dependencies",
+    tag = "analytics",
+    responses(
+        (status = 200, description = "Stale dependencies", body = DependencyAgeReport),
+        ApiError
+    )
+)]
+pub async fn get_stale_dependencies(
+    State(state): State<AppState>,
+) -> Result<Json<DependencyAgeReport>, ApiError> {
+    let report = state.domain_service.get_dependency_age_report().await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(report))
+}
+
+/// Dependencies crossing project boundaries, for portfolio planning
+///
+/// GET /api/v1/analytics/cross-project-dependencies
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/cross-project-dependencies",
+    tag = "analytics",
+    responses(
+        (status = 200, description = "Dependencies crossing projects", body = Vec<CrossProjectDependency>),
+        ApiError
+    )
+)]
+pub async fn get_cross_project_dependencies(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CrossProjectDependency>>, ApiError> {
+    let dependencies = state.domain_service.get_cross_project_dependencies().await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(dependencies))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PriorityChurnQueryParams {
+    /// Defaults to [`PRIORITY_CHURN_THRESHOLD`]
+    pub priority_changed_more_than: Option<u32>,
+}
+
+/// Tasks whose priority keeps changing, most changed first
+///
+/// GET /api/v1/analytics/priority-churn?priority_changed_more_than=2
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/priority-churn",
+    tag = "analytics",
+    params(PriorityChurnQueryParams),
+    responses(
+        (status = 200, description = "Tasks with changing priority, most changed first", body = Vec<TaskResponse>),
+        ApiError
+    )
+)]
+pub async fn get_priority_churn(
+    State(state): State<AppState>,
+    Query(params): Query<PriorityChurnQueryParams>,
+) -> Result<Json<Vec<TaskResponse>>, ApiError> {
+    let threshold = params.priority_changed_more_than.unwrap_or(PRIORITY_CHURN_THRESHOLD);
+    let filter = TaskFilter {
+        priority_changed_more_than: Some(threshold),
+        ..Default::default()
+    };
+    let mut tasks = state.domain_service.list_tasks(filter).await
+        .map_err(ApiError::from)?;
+
+    tasks.retain(|task| task.priority_change_count > threshold);
+    tasks.sort_by(|a, b| b.priority_change_count.cmp(&a.priority_change_count));
+
+    Ok(Json(tasks.iter().map(TaskResponse::from).collect()))
+}
+
+/// Share of the suggested bottleneck actions a project has carried out
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/bottleneck-resolution",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Resolution progress of the project", body = ResolutionProgress),
+        ApiError
+    )
+)]
+pub async fn get_bottleneck_resolution_progress(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<ResolutionProgress>, ApiError> {
+    let progress = state.domain_service.get_bottleneck_resolution_progress(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(progress))
+}
+
+/// Get a project's workflow settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/workflow-config",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Workflow settings of the project", body = ProjectWorkflowConfig),
+        ApiError
+    )
+)]
+pub async fn get_project_workflow_config(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<ProjectWorkflowConfig>, ApiError> {
+    let config = state.domain_service.get_project_workflow_config(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(config))
+}
+
+/// Request to replace a project's workflow settings
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateWorkflowConfigRequest {
+    #[serde(default)]
+    pub wip_limits: HashMap<TaskStatus, u32>,
+}
+
+/// Replace a project's workflow settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/projects/{id}/workflow-config",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    request_body = UpdateWorkflowConfigRequest,
+    responses(
+        (status = 200, description = "The new workflow settings", body = ProjectWorkflowConfig),
+        ApiError
+    )
+)]
+pub async fn update_project_workflow_config(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<UpdateWorkflowConfigRequest>,
+) -> Result<Json<ProjectWorkflowConfig>, ApiError> {
+    let config = ProjectWorkflowConfig { project_id, wip_limits: request.wip_limits };
+    let config = state.domain_service.update_project_workflow_config(config).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(config))
+}
+
+/// Get the statuses of a project holding more tasks than their WIP limit
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/wip-violations",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Statuses over their WIP limit", body = Vec<WipViolation>),
+        ApiError
+    )
+)]
+pub async fn get_project_wip_violations(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Vec<WipViolation>>, ApiError> {
+    let violations = state.domain_service.get_wip_limit_violations(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(violations))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ThroughputQueryParams {
+    pub days: Option<u32>,
+}
+
+/// Tasks a project completed per day, with control limits for spotting instability
+///
+/// GET /api/v1/projects/:id/throughput?days=30
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/throughput",
+    tag = "projects",
+    params(
+        ("id" = String, Path, description = "Project ID"),
+        ThroughputQueryParams
+    ),
+    responses(
+        (status = 200, description = "Throughput of the project", body = ThroughputMetrics),
+        ApiError
+    )
+)]
+pub async fn get_project_throughput(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(params): Query<ThroughputQueryParams>,
+) -> Result<Json<ThroughputMetrics>, ApiError> {
+    let period_days = params.days.unwrap_or(30);
+    if period_days == 0 {
+        return Err(ApiError::bad_request("days must be greater than 0"));
+    }
+
+    let metrics = state.domain_service.get_throughput_metrics(&project_id, period_days).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(metrics))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BurndownQueryParams {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Remaining tasks of a project per day, with the ideal line to zero by the end date
+///
+/// GET /api/v1/projects/:id/burndown?start=...&end=...
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/burndown",
+    tag = "projects",
+    params(
+        ("id" = String, Path, description = "Project ID"),
+        BurndownQueryParams
+    ),
+    responses(
+        (status = 200, description = "Burndown of the project", body = BurndownChart),
+        ApiError
+    )
+)]
+pub async fn get_project_burndown(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(params): Query<BurndownQueryParams>,
+) -> Result<Json<BurndownChart>, ApiError> {
+    validate_burndown_range(params.start, params.end)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let chart = state.domain_service.get_project_burndown(&project_id, params.start, params.end).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(chart))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EstimationAccuracyQueryParams {
+    pub user_id: Option<String>,
+    pub context: Option<TaskContext>,
+    pub days: Option<u32>,
+}
+
+/// How close estimated dates came to actual completion, overall and per complexity
+///
+/// GET /api/v1/analytics/estimation-accuracy?user_id=&context=work&days=90
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/estimation-accuracy",
+    tag = "analytics",
+    params(EstimationAccuracyQueryParams),
+    responses(
+        (status = 200, description = "Estimation accuracy", body = EstimationAccuracy),
+        ApiError
+    )
+)]
+pub async fn get_estimation_accuracy(
+    State(state): State<AppState>,
+    Query(params): Query<EstimationAccuracyQueryParams>,
+) -> Result<Json<EstimationAccuracy>, ApiError> {
+    let period_days = params.days.unwrap_or(90);
+    if period_days == 0 {
+        return Err(ApiError::bad_request("days must be greater than 0"));
+    }
+
+    let accuracy = state.domain_service
+        .calculate_estimation_accuracy(params.user_id.as_deref(), params.context, period_days).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(accuracy))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecurringComplianceQueryParams {
+    pub user_id: Option<String>,
+    pub days: Option<u32>,
+}
+
+/// How many recurring task instances were completed on time, late or missed
+///
+/// Publishes `RecurringTaskComplianceAlert` when the compliance rate is below
+/// the alert threshold.
+///
+/// GET /api/v1/analytics/recurring-compliance?user_id=&days=90
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics/recurring-compliance",
+    tag = "analytics",
+    params(RecurringComplianceQueryParams),
+    responses(
+        (status = 200, description = "Compliance of recurring tasks", body = RecurringTaskCompliance),
+        ApiError
+    )
+)]
+pub async fn get_recurring_task_compliance(
+    State(state): State<AppState>,
+    Query(params): Query<RecurringComplianceQueryParams>,
+) -> Result<Json<RecurringTaskCompliance>, ApiError> {
+    let period_days = params.days.unwrap_or(90);
+    if period_days == 0 {
+        return Err(ApiError::bad_request("days must be greater than 0"));
+    }
+
+    let compliance = state.domain_service
+        .get_recurring_task_compliance(params.user_id.as_deref(), period_days).await
+        .map_err(ApiError::from)?;
+
+    if compliance.needs_alert() {
+        let event = RecurringTaskComplianceAlert {
+            user_id: params.user_id,
+            period_days,
+            compliance_rate: compliance.compliance_rate,
+            threshold: RECURRING_COMPLIANCE_ALERT_THRESHOLD,
+            missed: compliance.missed,
+            measured_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(&state.event_service, RecurringTaskComplianceAlert::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish analytics.recurring_task_compliance_alert event after retries: {}", e);
+        }
+    }
+
+    Ok(Json(compliance))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FocusScoreQueryParams {
+    pub days: Option<u32>,
+}
+
+/// How much a user switched contexts between completed tasks
+///
+/// Publishes `FocusScoreImproved` when the score of the last week beats the
+/// week before by more than 20%.
+///
+/// GET /api/v1/users/:user_id/focus-score?days=30
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/focus-score",
+    tag = "users",
+    params(
+        ("user_id" = String, Path, description = "User ID"),
+        FocusScoreQueryParams
+    ),
+    responses(
+        (status = 200, description = "Focus score of the user", body = FocusScore),
+        ApiError
+    )
+)]
+pub async fn get_user_focus_score(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Query(params): Query<FocusScoreQueryParams>,
+) -> Result<Json<FocusScore>, ApiError> {
+    let period_days = params.days.unwrap_or(30);
+    if period_days == 0 {
+        return Err(ApiError::bad_request("days must be greater than 0"));
+    }
+
+    let score = state.domain_service.get_user_focus_score(&user_id, period_days).await
+        .map_err(ApiError::from)?;
+
+    if score.improved_week_over_week() {
+        let event = FocusScoreImproved {
+            user_id: user_id.clone(),
+            focus_score: score.focus_score,
+            week_over_week_change: score.week_over_week_change.unwrap_or_default(),
+            measured_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(&state.event_service, FocusScoreImproved::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish analytics.focus_score_improved event after retries: {}", e);
+        }
+    }
+
+    Ok(Json(score))
+}
+
+/// Forecast of when a project's open story points are done, from its last sprints
+///
+/// GET /api/v1/projects/:id/velocity-forecast
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/velocity-forecast",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "The velocity forecast", body = VelocityForecast),
+        ApiError
+    )
+)]
+pub async fn get_project_velocity_forecast(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<VelocityForecast>, ApiError> {
+    let forecast = state.domain_service.get_project_velocity_forecast(&project_id).await
+        .map_err(|e| {
+            let message = e.to_string();
+            if message.contains(INSUFFICIENT_VELOCITY_DATA) {
+                ApiError::new("UNPROCESSABLE_ENTITY", message)
+            } else {
+                ApiError::from(e)
+            }
+        })?;
+
+    Ok(Json(forecast))
+}
+
+/// Tasks ranked by how central they are to the project's dependency graph;
+/// the top 10% by betweenness are flagged as high risk
+///
+/// GET /api/v1/projects/:id/centrality
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/centrality",
+    tag = "projects",
+    params(("id" = String, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Tasks by centrality, most central first", body = Vec<TaskCentrality>),
+        ApiError
+    )
+)]
+pub async fn get_project_centrality(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Vec<TaskCentrality>>, ApiError> {
+    let centralities = state.domain_service.get_task_network_centrality(&project_id).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(centralities))
+}
+
+/// The project's tasks grouped into waves that can each run in parallel
+///
+/// Accepts the same filters as the task list.
+///
+/// GET /api/v1/projects/:id/execution-plan
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}/execution-plan",
+    tag = "projects",
+    params(
+        ("id" = String, Path, description = "Project ID"),
+        TaskQueryParams
+    ),
+    responses(
+        (status = 200, description = "The execution plan", body = ExecutionPlanResponse),
+        ApiError
+    )
+)]
+pub async fn get_project_execution_plan(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(params): Query<TaskQueryParams>,
+) -> Result<Json<ExecutionPlanResponse>, ApiError> {
+    if let Some(ref q) = params.q {
+        validate_search_text(q).map_err(|e| ApiError::from_field_errors(vec![e]))?;
+    }
+    let plan = state.domain_service
+        .get_dependency_resolution_order(&project_id, create_task_filter(params)).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ExecutionPlanResponse {
+        total_sequential_days: plan.total_sequential_days(),
+        total_parallel_days: plan.total_parallel_days(),
+        plan,
+    }))
+}
+
+/// WIP violations of a project; monitoring problems must not fail the request
+async fn current_wip_violations(state: &AppState, project_id: Option<&str>) -> Vec<WipViolation> {
+    let Some(project_id) = project_id else {
+        return Vec::new();
+    };
+
+    state.domain_service.get_wip_limit_violations(project_id).await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to check WIP limits of project {}: {}", project_id, e);
+        Vec::new()
+    })
+}
+
+/// Publish `WipLimitViolated` for statuses that went over their limit and
+/// `WipLimitRestored` for statuses that are back within it
+async fn publish_wip_limit_changes(
+    event_service: &EventService,
+    project_id: &str,
+    before: &[WipViolation],
+    after: &[WipViolation],
+) {
+    for violation in after.iter().filter(|v| !before.iter().any(|b| b.status == v.status)) {
+        let event = WipLimitViolated {
+            project_id: project_id.to_string(),
+            status: violation.status,
+            current_count: violation.current_count,
+            limit: violation.limit,
+            affected_tasks: violation.affected_tasks.clone(),
+            detected_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(event_service, WipLimitViolated::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish {} event after retries: {}", WipLimitViolated::TOPIC, e);
+        }
+    }
+
+    for restored in before.iter().filter(|b| !after.iter().any(|v| v.status == b.status)) {
+        let event = WipLimitRestored {
+            project_id: project_id.to_string(),
+            status: restored.status,
+            restored_at: Utc::now(),
+        };
+        if let Err(e) = publish_event_with_retry(event_service, WipLimitRestored::TOPIC, event, 3).await {
+            tracing::error!("Failed to publish {} event after retries: {}", WipLimitRestored::TOPIC, e);
+        }
+    }
+}
+
+/// Response for circular dependency analysis
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CircularDependenciesResponse {
+    pub total_cycles: usize,
+    pub cycles: Vec<crate::domain::queries::DependencyCycle>,
+    pub has_critical_cycles: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskRecurrence;
+
+    fn recurring_task() -> Task {
+        let mut task = Task::new("TASK-001".to_string(), "Water plants".to_string(), TaskContext::Personal);
+        task.recurrence = Some(TaskRecurrence {
+            pattern: "daily".to_string(),
+            interval: 3,
+            end_date: None,
+            cron: None,
+        });
+        task
+    }
+
+    #[test]
+    fn test_next_recurrence_date_for_done_recurring_task() {
+        let mut task = recurring_task();
+        task.status = TaskStatus::Done;
+        task.completed_at = Some(Utc::now());
+
+        let response = TaskResponse::from(&task);
+
+        assert_eq!(
+            response.next_recurrence_date,
+            Some(task.completed_at.unwrap() + chrono::Duration::days(3))
+        );
+    }
+
+    #[test]
+    fn test_next_recurrence_date_absent_until_done() {
+        let mut task = recurring_task();
+        task.due_date = Some(Utc::now());
+        assert!(TaskResponse::from(&task).next_recurrence_date.is_none());
+
+        let mut task = Task::new("TASK-002".to_string(), "One-off".to_string(), TaskContext::Work);
+        task.status = TaskStatus::Done;
+        task.completed_at = Some(Utc::now());
+        assert!(TaskResponse::from(&task).next_recurrence_date.is_none());
+    }
+}
@@ -0,0 +1,44 @@
+//! Work task handlers
+//!
+//! Work tasks are expected to be scheduled: urgent work without a due date is
+//! rejected, and responses carry the number of days left until the deadline.
+
+use chrono::Utc;
+
+use crate::{
+    domain::{TaskContext, TaskPriority},
+    handlers::{
+        contextual::{ContextualTaskHandler, TaskResponseExt},
+        tasks::{CreateTaskApiRequest, TaskResponse},
+        ApiError,
+    },
+};
+
+/// Handler for `/api/v1/work/tasks`
+pub struct WorkTaskHandler;
+
+impl ContextualTaskHandler for WorkTaskHandler {
+    fn context() -> TaskContext {
+        TaskContext::Work
+    }
+
+    fn validate_create(request: &CreateTaskApiRequest) -> Result<(), ApiError> {
+        let urgent = matches!(
+            request.priority,
+            Some(TaskPriority::Critical) | Some(TaskPriority::High)
+        );
+        if urgent && request.due_date.is_none() {
+            return Err(ApiError::bad_request(
+                "Critical and high priority work tasks require a due date",
+            ));
+        }
+        Ok(())
+    }
+
+    fn extend_response(response: TaskResponse) -> TaskResponse {
+        let days_until_due = response
+            .due_date
+            .map(|due| (due - Utc::now()).num_days());
+        response.with_extension("days_until_due", days_until_due)
+    }
+}
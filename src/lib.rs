@@ -5,6 +5,7 @@
 //! ## Features
 //!
 //! - RESTful HTTP API with Axum
+//! - gRPC API with Tonic, served next to the HTTP API
 //! - Hexagonal architecture with ports and adapters
 //! - Event-driven architecture with TYL PubSub
 //! - Async-first design with Tokio
@@ -50,21 +51,34 @@ pub use tyl_logging::{Logger, LogRecord, LogLevel, ConsoleLogger, JsonLogger};
 pub use tyl_tracing::{TracingManager, SimpleTracer, TraceConfig};
 
 // Standard library imports
-use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 // External crates
 use axum::Router;
 use tokio::signal;
-use tower::ServiceBuilder;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 // Internal modules
+pub mod archival;
+pub mod attachments;
+pub mod auth;
 pub mod config;
 pub mod domain;
 pub mod handlers;
 pub mod adapters;
 pub mod routes;
 pub mod events;
+pub mod export;
+pub mod grpc;
+pub mod logging;
+pub mod maintenance;
+pub mod middleware;
+pub mod monitoring;
+pub mod notifications;
+pub mod plugins;
+pub mod telemetry;
+pub mod tracing;
 pub mod validation;
 
 // Re-exports for convenience
@@ -73,6 +87,18 @@ pub use domain::{TaskService, Task, CreateTaskRequest, TaskDetailResponse, TaskD
 pub use events::{EventService, DomainEventHandler};
 pub use adapters::GraphTaskRepository;
 
+use adapters::{CircuitBreaker, CircuitBreakerConfig};
+use archival::ArchivalService;
+use attachments::AttachmentService;
+use domain::{MockProjectService, ProjectService, TaskRepository, TaskScheduler, TfIdfIndex, UserServicePort};
+use auth::{AuthorizationPolicy, PermissivePolicy, VisibilityPolicy};
+use plugins::Plugin;
+use crate::tracing::{NoOpTracingService, OtelTracingService, TracingService};
+use events::{
+    handlers::{DomainEventHandlerAdapter, TaskAutoUnblockerHandler}, DomainEvent, SubscriptionId, TaskEventStore,
+    WebhookDeliveryService,
+};
+
 /// Result type for task service operations
 pub type TaskServiceResult<T> = Result<T, TaskServiceError>;
 
@@ -82,6 +108,9 @@ pub enum TaskServiceError {
     #[error("Configuration error: {message}")]
     Configuration { message: String },
     
+    #[error(transparent)]
+    ConfigValidation(#[from] config::ConfigValidationError),
+    
     #[error("Database error: {message}")]
     Database { message: String },
     
@@ -100,6 +129,9 @@ pub enum TaskServiceError {
     #[error("Invalid input: {field}: {message}")]
     InvalidInput { field: String, message: String },
     
+    #[error("Validation failed: {}", validation::describe_field_errors(.errors))]
+    ValidationFailed { errors: Vec<validation::FieldError> },
+    
     #[error("Task not found: {id}")]
     TaskNotFound { id: String },
     
@@ -129,18 +161,32 @@ pub enum TaskServiceError {
     
     #[error("Concurrency error: {message}")]
     Concurrency { message: String },
+    
+    #[error("Version conflict on task {task_id}: expected version {expected}, found {actual}")]
+    VersionConflict { task_id: String, expected: u64, actual: u64 },
+    
+    #[error("Not authorized to {action} {resource}")]
+    NotAuthorized { action: String, resource: String },
+
+    #[error("Rate limit of {limit} requests exceeded for {key}")]
+    RateLimitExceeded { key: String, limit: u32 },
 }
 
 impl From<TaskServiceError> for TylError {
     fn from(err: TaskServiceError) -> Self {
         match err {
             TaskServiceError::Configuration { message } => TylError::configuration(message),
+            TaskServiceError::ConfigValidation(err) => TylError::configuration(err.to_string()),
             TaskServiceError::Database { message } => TylError::database(message),
             TaskServiceError::GraphDatabase { message } => TylError::database(format!("Graph DB: {}", message)),
             TaskServiceError::Api { message } => TylError::network(message),
             TaskServiceError::Domain { message } => TylError::validation("domain", message),
             TaskServiceError::ExternalService { message } => TylError::network(message),
             TaskServiceError::InvalidInput { field, message } => TylError::validation(&field, message),
+            TaskServiceError::ValidationFailed { errors } => {
+                let field = errors.first().map(|e| e.field.as_str()).unwrap_or("request");
+                TylError::validation(field, validation::describe_field_errors(&errors))
+            },
             TaskServiceError::TaskNotFound { id } => TylError::not_found("task", id),
             TaskServiceError::ProjectNotFound { id } => TylError::not_found("project", id),
             TaskServiceError::UserNotFound { id } => TylError::not_found("user", id),
@@ -159,6 +205,16 @@ impl From<TaskServiceError> for TylError {
                 TylError::internal(format!("Event {} publishing failed: {}", event_type, message))
             },
             TaskServiceError::Concurrency { message } => TylError::internal(format!("Concurrency error: {}", message)),
+            TaskServiceError::VersionConflict { task_id, expected, actual } => TylError::validation(
+                "version",
+                format!("Task {} is at version {}, not {}", task_id, actual, expected),
+            ),
+            TaskServiceError::NotAuthorized { action, resource } => {
+                TylError::internal(format!("Not authorized to {} {}", action, resource))
+            },
+            TaskServiceError::RateLimitExceeded { key, limit } => {
+                TylError::network(format!("Rate limit of {} requests exceeded for {}", limit, key))
+            },
         }
     }
 }
@@ -168,71 +224,389 @@ impl From<TaskServiceError> for TylError {
 pub struct AppState {
     pub config: Arc<TaskServiceConfig>,
     pub domain_service: Arc<dyn TaskService + Send + Sync>,
+    pub project_service: Arc<dyn ProjectService + Send + Sync>,
     pub event_service: Arc<EventService>,
     pub logger: Arc<dyn Logger + Send + Sync>,
-    pub tracer: Arc<dyn TracingManager + Send + Sync>,
+    /// Spans of handler work, e.g. `create_task`; sampled following `monitoring.trace_sampling_rate`
+    pub tracer: Arc<dyn TracingService + Send + Sync>,
+    /// Cleared by the database circuit breaker while FalkorDB is unreachable
+    pub db_health: Arc<AtomicBool>,
+    /// Breaker guarding every FalkorDB call, reported by `GET /health/db`
+    pub db_circuit_breaker: Arc<CircuitBreaker>,
+    /// TF-IDF index of task descriptions, kept up to date by the domain service
+    pub description_index: Arc<RwLock<TfIdfIndex>>,
+    /// Plugins wrapping the router, in the order they are applied
+    pub plugins: Vec<Arc<dyn Plugin + Send + Sync>>,
+    /// Checked by the task handlers before reading or changing a task
+    pub authorization_policy: Arc<dyn AuthorizationPolicy + Send + Sync>,
+    /// Delivers domain events to the configured webhook, if any
+    pub webhook_delivery: Option<Arc<WebhookDeliveryService>>,
+    /// Presigns attachment uploads to the configured storage, if any
+    pub attachment_service: Option<Arc<AttachmentService>>,
+    /// Moves old completed tasks to the archive graph, when archival is enabled
+    pub archival_service: Option<Arc<ArchivalService>>,
+    /// History of every task change, when the event store is enabled
+    pub event_store: Option<Arc<dyn TaskEventStore>>,
+    /// Promotes backlog tasks whose dependencies are done, keeping its latest report
+    pub task_scheduler: Arc<TaskScheduler>,
+}
+
+impl AppState {
+    /// Run an in-process handler whenever the domain event `E` is published
+    pub async fn register_domain_handler<E, H>(&self, handler: H) -> TaskServiceResult<SubscriptionId>
+    where
+        E: DomainEvent,
+        H: DomainEventHandler<E> + 'static,
+    {
+        self.event_service
+            .subscribe_domain_event(Arc::new(DomainEventHandlerAdapter::new(handler)))
+            .await
+    }
+}
+
+/// Builder for [`AppState`]
+///
+/// Only the domain service is required. Everything else falls back to the
+/// default configuration, the in-memory [`MockProjectService`], an event
+/// service on the in-memory adapter, a
+/// console logger, a [`NoOpTracingService`], a healthy
+/// database flag with a closed circuit breaker, an empty description index, no plugins, the
+/// [`PermissivePolicy`], no webhook, no attachment storage and no archival, so tests can build a
+/// state without connecting to anything.
+#[derive(Default)]
+pub struct AppStateBuilder {
+    config: Option<TaskServiceConfig>,
+    domain_service: Option<Arc<dyn TaskService + Send + Sync>>,
+    project_service: Option<Arc<dyn ProjectService + Send + Sync>>,
+    event_service: Option<Arc<EventService>>,
+    logger: Option<Arc<dyn Logger + Send + Sync>>,
+    tracer: Option<Arc<dyn TracingService + Send + Sync>>,
+    db_health: Option<Arc<AtomicBool>>,
+    db_circuit_breaker: Option<Arc<CircuitBreaker>>,
+    description_index: Option<Arc<RwLock<TfIdfIndex>>>,
+    plugins: Vec<Arc<dyn Plugin + Send + Sync>>,
+    authorization_policy: Option<Arc<dyn AuthorizationPolicy + Send + Sync>>,
+    webhook_delivery: Option<Arc<WebhookDeliveryService>>,
+    attachment_service: Option<Arc<AttachmentService>>,
+    archival_service: Option<Arc<ArchivalService>>,
+    event_store: Option<Arc<dyn TaskEventStore>>,
+    task_scheduler: Option<Arc<TaskScheduler>>,
+}
+
+impl AppStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(mut self, config: TaskServiceConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_domain_service<S: TaskService + Send + Sync + 'static>(self, domain_service: S) -> Self {
+        self.with_shared_domain_service(Arc::new(domain_service))
+    }
+
+    /// Use a domain service that is already shared with other components
+    pub fn with_shared_domain_service(mut self, domain_service: Arc<dyn TaskService + Send + Sync>) -> Self {
+        self.domain_service = Some(domain_service);
+        self
+    }
+
+    pub fn with_project_service(mut self, project_service: Arc<dyn ProjectService + Send + Sync>) -> Self {
+        self.project_service = Some(project_service);
+        self
+    }
+
+    pub fn with_event_service(mut self, event_service: Arc<EventService>) -> Self {
+        self.event_service = Some(event_service);
+        self
+    }
+
+    pub fn with_logger(mut self, logger: Arc<dyn Logger + Send + Sync>) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    pub fn with_tracer(mut self, tracer: Arc<dyn TracingService + Send + Sync>) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Share the flag the database circuit breaker clears while FalkorDB is unreachable
+    pub fn with_db_health(mut self, db_health: Arc<AtomicBool>) -> Self {
+        self.db_health = Some(db_health);
+        self
+    }
+
+    /// Share the breaker of the database adapter, see [`adapters::RetryingFalkorDBAdapter::circuit_breaker`]
+    pub fn with_db_circuit_breaker(mut self, db_circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.db_circuit_breaker = Some(db_circuit_breaker);
+        self
+    }
+
+    /// Share the description index the domain service keeps up to date
+    pub fn with_description_index(mut self, description_index: Arc<RwLock<TfIdfIndex>>) -> Self {
+        self.description_index = Some(description_index);
+        self
+    }
+
+    pub fn with_plugins(mut self, plugins: Vec<Arc<dyn Plugin + Send + Sync>>) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    pub fn with_authorization_policy(mut self, policy: Arc<dyn AuthorizationPolicy + Send + Sync>) -> Self {
+        self.authorization_policy = Some(policy);
+        self
+    }
+
+    pub fn with_webhook_delivery(mut self, webhook_delivery: Arc<WebhookDeliveryService>) -> Self {
+        self.webhook_delivery = Some(webhook_delivery);
+        self
+    }
+
+    pub fn with_attachment_service(mut self, attachment_service: Arc<AttachmentService>) -> Self {
+        self.attachment_service = Some(attachment_service);
+        self
+    }
+
+    pub fn with_archival_service(mut self, archival_service: Arc<ArchivalService>) -> Self {
+        self.archival_service = Some(archival_service);
+        self
+    }
+
+    pub fn with_event_store(mut self, event_store: Arc<dyn TaskEventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    pub fn with_task_scheduler(mut self, task_scheduler: Arc<TaskScheduler>) -> Self {
+        self.task_scheduler = Some(task_scheduler);
+        self
+    }
+
+    /// Build the state, failing with a configuration error when no domain service was set
+    pub async fn build(self) -> TaskServiceResult<AppState> {
+        let domain_service = self.domain_service.ok_or_else(|| TaskServiceError::Configuration {
+            message: "AppState requires a domain service".to_string(),
+        })?;
+        let config = self.config.unwrap_or_default();
+        let event_service = match self.event_service {
+            Some(event_service) => event_service,
+            None => Arc::new(EventService::new().await?
+                .with_batch_publish_max_size(config.events.batch_publish_max_size)
+                .with_channel_depth(config.events.event_channel_depth)
+                .with_publish_timeout(Duration::from_millis(config.events.publish_timeout_ms))),
+        };
+        let tracer = self.tracer.unwrap_or_else(|| Arc::new(NoOpTracingService));
+        let db_health = self.db_health.unwrap_or_else(|| Arc::new(AtomicBool::new(true)));
+        let db_circuit_breaker = self.db_circuit_breaker.unwrap_or_else(|| {
+            Arc::new(CircuitBreaker::new(CircuitBreakerConfig::from(&config.database), db_health.clone()))
+        });
+        let task_scheduler = self.task_scheduler
+            .unwrap_or_else(|| Arc::new(TaskScheduler::new(event_service.clone())));
+
+        Ok(AppState {
+            config: Arc::new(config),
+            domain_service,
+            project_service: self.project_service.unwrap_or_else(|| Arc::new(MockProjectService::new())),
+            event_service,
+            logger: self.logger.unwrap_or_else(|| Arc::new(ConsoleLogger::new())),
+            tracer,
+            db_health,
+            db_circuit_breaker,
+            description_index: self.description_index.unwrap_or_else(|| Arc::new(RwLock::new(TfIdfIndex::new()))),
+            plugins: self.plugins,
+            authorization_policy: self.authorization_policy.unwrap_or_else(|| Arc::new(PermissivePolicy)),
+            webhook_delivery: self.webhook_delivery,
+            attachment_service: self.attachment_service,
+            archival_service: self.archival_service,
+            event_store: self.event_store,
+            task_scheduler,
+        })
+    }
 }
 
 /// Create the main application with all routes and middleware
+///
+/// Middleware comes from the built-in plugins followed by the ones registered
+/// in `config.plugins`.
 pub async fn create_app(config: TaskServiceConfig) -> TaskServiceResult<Router> {
+    let state = create_app_state(config).await?;
+    Ok(create_router(state))
+}
+
+/// Initialize every component into the state shared by the HTTP and gRPC servers
+///
+/// Also registers the in-process event handlers and starts the background jobs.
+pub async fn create_app_state(mut config: TaskServiceConfig) -> TaskServiceResult<AppState> {
     // Initialize TYL logging based on configuration
     let logger: Arc<dyn Logger + Send + Sync> = match config.monitoring.log_format.as_str() {
         "json" => Arc::new(JsonLogger::new()),
         _ => Arc::new(ConsoleLogger::new()),
     };
     
-    // Initialize TYL tracing 
-    let trace_config = TraceConfig::new(&config.service_name)
-        .with_sampling_rate(config.monitoring.trace_sampling_rate)
-        .with_max_spans(config.monitoring.max_spans);
-    let tracer = Arc::new(SimpleTracer::new(trace_config));
+    // Export handler spans when tracing is on and an exporter is configured
+    let tracer: Arc<dyn TracingService + Send + Sync> = if config.monitoring.tracing_enabled && config.tracing.exports() {
+        let tracer = OtelTracingService::new(&config.tracing, config.monitoring.trace_sampling_rate, config.monitoring.max_spans)
+            .map_err(|e| TaskServiceError::Configuration { message: e.to_string() })?;
+        Arc::new(tracer)
+    } else {
+        Arc::new(NoOpTracingService)
+    };
     
     // Log service initialization
     logger.log(&LogRecord::new(LogLevel::Info, &format!(
         "Initializing {} v{}", config.service_name, config.version
     )));
     
+    // Record metrics from startup on, not only once /metrics is first scraped
+    if config.monitoring.metrics_enabled {
+        telemetry::prometheus_handle();
+    }
+    
     // Initialize event service
     let event_service = Arc::new(EventService::new().await.map_err(|e| {
         let error_msg = format!("Failed to initialize event service: {}", e);
         logger.log(&LogRecord::new(LogLevel::Error, &error_msg));
         TaskServiceError::Configuration { message: error_msg }
-    })?);
+    })?
+        .with_batch_publish_max_size(config.events.batch_publish_max_size)
+        .with_channel_depth(config.events.event_channel_depth)
+        .with_publish_timeout(Duration::from_millis(config.events.publish_timeout_ms)));
     
     // Initialize domain service with dependencies
     logger.log(&LogRecord::new(LogLevel::Debug, "Initializing domain service and database connection"));
-    let domain_service = create_domain_service(&config).await?;
+    let db_health = Arc::new(AtomicBool::new(true));
+    let db_circuit_breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig::from(&config.database), db_health.clone()));
+    let description_index = Arc::new(RwLock::new(TfIdfIndex::new()));
+    let repository = create_graph_repository(&config, db_circuit_breaker.clone()).await?;
+    let archival_service = config.archival.enabled
+        .then(|| Arc::new(ArchivalService::new(config.archival.clone(), Arc::new(repository.clone()))));
+    let scheduler_repository: Arc<dyn TaskRepository + Send + Sync> = Arc::new(repository.clone());
+    let event_store = create_event_store(&config, db_circuit_breaker.clone()).await?;
+    let user_service = create_user_service(&config)?;
+    let mut graph_project_service = adapters::GraphProjectService::new(repository.clone());
+    if let Some(user_service) = user_service.clone() {
+        graph_project_service = graph_project_service.with_user_service(user_service);
+    }
+    let project_service: Arc<dyn ProjectService + Send + Sync> = Arc::new(graph_project_service);
+    let domain_service = create_domain_service(&config, repository, description_index.clone(), event_store.clone(), user_service);
     logger.log(&LogRecord::new(LogLevel::Info, "Domain service initialized successfully"));
     
+    // Index the descriptions of tasks that already exist
+    match domain_service.list_tasks(domain::TaskFilter::default()).await {
+        Ok(tasks) => {
+            let mut index = description_index.write().unwrap();
+            for task in &tasks {
+                if let Some(description) = &task.description {
+                    index.add_document(&task.id, description);
+                }
+            }
+        }
+        Err(e) => logger.log(&LogRecord::new(LogLevel::Warn, &format!(
+            "Description index starts empty, failed to load existing tasks: {}", e
+        ))),
+    }
+    
     logger.log(&LogRecord::new(LogLevel::Info, "All components initialized successfully"));
     
-    // Create shared application state
-    let state = AppState {
-        config: Arc::new(config),
-        domain_service,
-        event_service,
-        logger,
-        tracer,
+    let mut app_plugins = plugins::builtin_plugins();
+    app_plugins.extend(std::mem::take(&mut config.plugins).into_vec());
+    
+    let authorization_policy: Arc<dyn AuthorizationPolicy + Send + Sync> = if config.api.enforce_task_visibility {
+        Arc::new(VisibilityPolicy::new(domain_service.clone()))
+    } else {
+        Arc::new(PermissivePolicy)
     };
+    
+    // Deliver domain events to the webhook, when one is configured
+    let webhook_delivery = match config.webhook.clone() {
+        Some(webhook) => {
+            let delivery = Arc::new(WebhookDeliveryService::new(webhook, event_service.clone()));
+            delivery.clone().start().await?;
+            Some(delivery)
+        }
+        None => None,
+    };
+    
+    // Presign attachment uploads, when storage is configured
+    let attachment_service = config.attachments.clone()
+        .map(|storage| Arc::new(AttachmentService::new(storage, domain_service.clone())));
+    
+    // Create shared application state
+    let mut builder = AppStateBuilder::new()
+        .with_config(config)
+        .with_shared_domain_service(domain_service)
+        .with_project_service(project_service)
+        .with_event_service(event_service)
+        .with_logger(logger)
+        .with_tracer(tracer)
+        .with_db_health(db_health)
+        .with_db_circuit_breaker(db_circuit_breaker)
+        .with_description_index(description_index)
+        .with_plugins(app_plugins)
+        .with_authorization_policy(authorization_policy);
+    if let Some(webhook_delivery) = webhook_delivery {
+        builder = builder.with_webhook_delivery(webhook_delivery);
+    }
+    if let Some(attachment_service) = attachment_service {
+        builder = builder.with_attachment_service(attachment_service);
+    }
+    if let Some(archival_service) = archival_service.clone() {
+        builder = builder.with_archival_service(archival_service);
+    }
+    if let Some(event_store) = event_store {
+        builder = builder.with_event_store(event_store);
+    }
+    let state = builder.build().await?;
+    
+    // In-process reactions to domain events
+    state.register_domain_handler(TaskAutoUnblockerHandler::new(state.domain_service.clone())).await?;
+
+    // Periodic alert threshold checks
+    monitoring::ThresholdMonitorJob::new(
+        state.domain_service.clone(),
+        state.event_service.clone(),
+        state.config.monitoring.alert_thresholds.clone(),
+    ).spawn();
+
+    // Hourly digest of tasks coming due, for notification services
+    notifications::DueSoonDigestJob::new(state.domain_service.clone(), state.event_service.clone()).spawn();
+
+    // Promotion of backlog tasks whose dependencies are done, every minute
+    state.task_scheduler.clone().spawn(state.domain_service.clone(), scheduler_repository);
+
+    // Monthly removal of dependencies on cancelled tasks
+    maintenance::CleanupStaleDependencies::new(state.domain_service.clone()).spawn();
+
+    // Daily archival of old completed tasks
+    if let Some(archival_service) = archival_service {
+        archival_service.spawn();
+    }
 
-    // Build the application with routes and middleware
-    let app = Router::new()
+    Ok(state)
+}
+
+/// Build the HTTP routes over `state`, wrapped in the plugins' middleware
+pub fn create_router(state: AppState) -> Router {
+    let router = Router::new()
         .merge(routes::health_routes())
         .merge(routes::api_routes())
-        .layer(
-            ServiceBuilder::new()
-                .layer(TraceLayer::new_for_http())
-                .layer(CorsLayer::permissive()) // Configure based on your needs
-        )
-        .with_state(state);
-
-    Ok(app)
+        .merge(routes::openapi_routes(&state.config))
+        .with_state(state.clone());
+    state.plugins.iter().fold(router, |router, plugin| {
+        state.logger.log(&LogRecord::new(LogLevel::Debug, &format!("Applying plugin {}", plugin.name())));
+        plugin.apply(router, &state)
+    })
 }
 
-/// Create domain service with all its dependencies
-async fn create_domain_service(
+/// Connect to FalkorDB and create the graph repository shared by the domain and project services
+async fn create_graph_repository(
     config: &TaskServiceConfig,
-) -> TaskServiceResult<Arc<dyn TaskService + Send + Sync>> {
+    db_circuit_breaker: Arc<CircuitBreaker>,
+) -> TaskServiceResult<adapters::GraphTaskRepository> {
     // Create FalkorDB adapter using tyl-config RedisConfig
     let db_adapter = tyl_falkordb_adapter::FalkorDBAdapter::new(
         config.database.redis.clone(),
@@ -242,21 +616,142 @@ async fn create_domain_service(
                         config.database.graph_name, e),
     })?;
 
-    // Create graph repository
-    let repository = adapters::GraphTaskRepository::new(
+    // Create graph repository, retrying queries while FalkorDB is unreachable
+    let db_adapter = adapters::RetryingFalkorDBAdapter::with_circuit_breaker(
+        db_adapter,
+        adapters::ConnectionRetryPolicy::from(&config.database),
+        db_circuit_breaker.clone(),
+    );
+    let mut repository = adapters::GraphTaskRepository::with_adapter(
         db_adapter,
         config.database.graph_name.clone(),
     );
+    
+    // Archived tasks go to their own graph, behind the same circuit breaker
+    if config.archival.enabled {
+        let archive_adapter = tyl_falkordb_adapter::FalkorDBAdapter::new(
+            config.database.redis.clone(),
+            config.archival.archive_graph_name.clone(),
+        ).await.map_err(|e| TaskServiceError::Database {
+            message: format!("Failed to create FalkorDB adapter for archive graph '{}': {}", 
+                            config.archival.archive_graph_name, e),
+        })?;
+        repository = repository.with_archive_graph(
+            config.archival.archive_graph_name.clone(),
+            adapters::RetryingFalkorDBAdapter::with_circuit_breaker(
+                archive_adapter,
+                adapters::ConnectionRetryPolicy::from(&config.database),
+                db_circuit_breaker,
+            ),
+        );
+    }
+    repository.ensure_schema().await.map_err(|e| TaskServiceError::Database {
+        message: format!("Failed to ensure schema for graph '{}': {}", 
+                        config.database.graph_name, e),
+    })?;
+    
+    Ok(repository)
+}
+
+/// Create the event store on its own graph, behind the task graph's circuit breaker
+async fn create_event_store(
+    config: &TaskServiceConfig,
+    db_circuit_breaker: Arc<CircuitBreaker>,
+) -> TaskServiceResult<Option<Arc<dyn TaskEventStore>>> {
+    if !config.event_store.enabled {
+        return Ok(None);
+    }
 
-    // Create domain service with real repository
-    let service = domain::TaskDomainService::new(repository);
+    let adapter = tyl_falkordb_adapter::FalkorDBAdapter::new(
+        config.database.redis.clone(),
+        config.event_store.graph_name.clone(),
+    ).await.map_err(|e| TaskServiceError::Database {
+        message: format!("Failed to create FalkorDB adapter for event graph '{}': {}", 
+                        config.event_store.graph_name, e),
+    })?;
+    let store = events::GraphTaskEventStore::new(
+        adapters::RetryingFalkorDBAdapter::with_circuit_breaker(
+            adapter,
+            adapters::ConnectionRetryPolicy::from(&config.database),
+            db_circuit_breaker,
+        ),
+        config.event_store.graph_name.clone(),
+    );
+    store.ensure_schema().await.map_err(|e| TaskServiceError::Database {
+        message: format!("Failed to ensure schema for event graph '{}': {}", 
+                        config.event_store.graph_name, e),
+    })?;
+    
+    Ok(Some(Arc::new(store)))
+}
+
+/// Look assignees up in the user service, when one is configured
+fn create_user_service(config: &TaskServiceConfig) -> TaskServiceResult<Option<Arc<dyn UserServicePort + Send + Sync>>> {
+    let Some(url) = config.external.user_service_url.as_deref() else {
+        return Ok(None);
+    };
+    let adapter = adapters::HttpUserServiceAdapter::new(url, config.external.clone())?;
+    Ok(Some(Arc::new(adapter)))
+}
+
+/// Create domain service with all its dependencies
+fn create_domain_service(
+    config: &TaskServiceConfig,
+    repository: adapters::GraphTaskRepository,
+    description_index: Arc<RwLock<TfIdfIndex>>,
+    event_store: Option<Arc<dyn TaskEventStore>>,
+    user_service: Option<Arc<dyn UserServicePort + Send + Sync>>,
+) -> Arc<dyn TaskService + Send + Sync> {
+    // Create domain service with real repository, optionally behind the task cache
+    let stale_review_after = chrono::Duration::hours(config.monitoring.alert_thresholds.stale_review_hours as i64);
+    if config.cache.enabled {
+        let repository = adapters::CachingTaskRepository::new(repository, config.cache.clone());
+        let mut service = domain::TaskDomainService::new(repository)
+            .with_graph_detection(config.database.use_graph_detection)
+            .with_description_index(description_index)
+            .with_stale_review_after(stale_review_after);
+        if let Some(event_store) = event_store {
+            service = service.with_event_store(event_store);
+        }
+        if let Some(user_service) = user_service {
+            service = service.with_user_service(user_service);
+        }
+        return Arc::new(service);
+    }
+
+    let mut service = domain::TaskDomainService::new(repository)
+        .with_graph_detection(config.database.use_graph_detection)
+        .with_description_index(description_index)
+        .with_stale_review_after(stale_review_after);
+    if let Some(event_store) = event_store {
+        service = service.with_event_store(event_store);
+    }
+    if let Some(user_service) = user_service {
+        service = service.with_user_service(user_service);
+    }
     
-    Ok(Arc::new(service))
+    Arc::new(service)
 }
 
 /// Start the microservice with graceful shutdown
 pub async fn run_microservice(config: TaskServiceConfig) -> TaskServiceResult<()> {
-    let app = create_app(config.clone()).await?;
+    config.validate()?;
+    let state = create_app_state(config.clone()).await?;
+    
+    // The gRPC API shares the state, and so the domain service, with the HTTP API
+    let grpc_server = match config.grpc.clone() {
+        Some(grpc_config) => {
+            let port = grpc_config.port;
+            let handle = grpc::spawn_server(state.clone(), grpc_config, shutdown_signal()).await?;
+            state.logger.log(&LogRecord::new(LogLevel::Info, &format!(
+                "gRPC server started on {}:{}", config.api.host, port
+            )));
+            Some(handle)
+        }
+        None => None,
+    };
+    let (event_service, logger) = (state.event_service.clone(), state.logger.clone());
+    let app = create_router(state);
     
     let listener = tokio::net::TcpListener::bind(&format!("{}:{}", config.api.host, config.api.port))
         .await
@@ -266,13 +761,29 @@ pub async fn run_microservice(config: TaskServiceConfig) -> TaskServiceResult<()
 
     println!("🚀 Microservice started on {}:{}", config.api.host, config.api.port);
     
-    axum::serve(listener, app)
+    // Connect info gives the rate limiter the peer address of each request
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await
         .map_err(|e| TaskServiceError::Api {
             message: format!("Server error: {}", e),
         })?;
 
+    if let Some(grpc_server) = grpc_server {
+        grpc_server.await.map_err(|e| TaskServiceError::Api {
+            message: format!("gRPC server task failed: {}", e),
+        })??;
+    }
+
+    // Events of recent bulk operations may still be on their way out
+    let shutdown_timeout = Duration::from_secs(config.api.shutdown_timeout_seconds);
+    let cancelled = event_service.wait_for_background_publishes(shutdown_timeout).await;
+    if cancelled > 0 {
+        logger.log(&LogRecord::new(LogLevel::Warn, &format!(
+            "Shutdown cancelled {} background event publishes, their events are lost", cancelled
+        )));
+    }
+
     Ok(())
 }
 
@@ -329,6 +840,27 @@ mod tests {
         assert!(app.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_app_state_builder_defaults() {
+        let state = AppStateBuilder::new()
+            .with_domain_service(domain::MockTaskService::new())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(state.config.service_name, TaskServiceConfig::default().service_name);
+        assert!(state.db_health.load(std::sync::atomic::Ordering::Relaxed));
+        assert!(state.plugins.is_empty());
+        assert!(state.domain_service.get_task_by_id("test-id").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_app_state_builder_requires_domain_service() {
+        let result = AppStateBuilder::new().with_config(TaskServiceConfig::default()).build().await;
+
+        assert!(matches!(result, Err(TaskServiceError::Configuration { .. })));
+    }
+
     #[test]
     fn test_error_conversion() {
         let service_error = TaskServiceError::Domain {
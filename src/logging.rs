@@ -0,0 +1,128 @@
+//! Log records carrying the context of the request being handled
+//!
+//! A [`LogRecord`] only holds a level and a message, so the context travels in
+//! the message: [`LogRecordExt::with_context`] appends it as sorted
+//! `key=value` pairs that stay greppable whatever the configured logger.
+//! Handlers take a [`ContextualLogger`], which adds the request ID,
+//! correlation ID, user and, on `tasks/:id` routes, the task ID to every
+//! record it writes.
+
+use async_trait::async_trait;
+use axum::extract::{FromRequestParts, MatchedPath, RawPathParams};
+use axum::http::request::Parts;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::middleware::request_context::RequestContext;
+use crate::{AppState, LogLevel, LogRecord, Logger};
+
+/// Constructors of [`LogRecord`] beyond those of `tyl_logging`
+pub trait LogRecordExt {
+    /// Record of `message` followed by `context`, e.g. `Task created [request_id=... task_id=...]`
+    fn with_context(level: LogLevel, message: &str, context: &HashMap<String, String>) -> LogRecord;
+}
+
+impl LogRecordExt for LogRecord {
+    fn with_context(level: LogLevel, message: &str, context: &HashMap<String, String>) -> LogRecord {
+        LogRecord::new(level, &message_with_context(message, context))
+    }
+}
+
+fn message_with_context(message: &str, context: &HashMap<String, String>) -> String {
+    if context.is_empty() {
+        return message.to_string();
+    }
+    let mut fields: Vec<_> = context.iter().collect();
+    fields.sort();
+    let fields: Vec<String> = fields.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    format!("{} [{}]", message, fields.join(" "))
+}
+
+/// Logger adding a fixed context to every record, see [`LogRecordExt::with_context`]
+#[derive(Clone)]
+pub struct ContextualLogger {
+    inner: Arc<dyn Logger + Send + Sync>,
+    context: HashMap<String, String>,
+}
+
+impl ContextualLogger {
+    pub fn new(inner: Arc<dyn Logger + Send + Sync>) -> Self {
+        Self { inner, context: HashMap::new() }
+    }
+
+    pub fn with_request_context(self, request: &RequestContext) -> Self {
+        let logger = self
+            .with_field("request_id", &request.request_id)
+            .with_field("correlation_id", &request.correlation_id);
+        match &request.user_id {
+            Some(user_id) => logger.with_field("user_id", user_id),
+            None => logger,
+        }
+    }
+
+    pub fn with_task_id(self, task_id: &str) -> Self {
+        self.with_field("task_id", task_id)
+    }
+
+    pub fn with_field(mut self, key: &str, value: &str) -> Self {
+        self.context.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn context(&self) -> &HashMap<String, String> {
+        &self.context
+    }
+
+    pub fn log(&self, level: LogLevel, message: &str) {
+        self.inner.log(&LogRecord::with_context(level, message, &self.context));
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for ContextualLogger {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let request = RequestContext::from_request_parts(parts, state).await?;
+        let logger = ContextualLogger::new(state.logger.clone()).with_request_context(&request);
+
+        let on_task_route = parts.extensions.get::<MatchedPath>()
+            .is_some_and(|path| path.as_str().contains("tasks/:id"));
+        if !on_task_route {
+            return Ok(logger);
+        }
+        let task_id = RawPathParams::from_request_parts(parts, state).await.ok()
+            .and_then(|params| params.iter().find(|(name, _)| *name == "id").map(|(_, id)| id.to_string()));
+        Ok(match task_id {
+            Some(task_id) => logger.with_task_id(&task_id),
+            None => logger,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contextual_logger_collects_request_and_task_context() {
+        let request = RequestContext {
+            request_id: "req-1".to_string(),
+            correlation_id: "corr-1".to_string(),
+            user_id: None,
+        };
+        let logger = ContextualLogger::new(Arc::new(crate::ConsoleLogger::new()))
+            .with_request_context(&request)
+            .with_task_id("task-1");
+
+        assert_eq!(logger.context().len(), 3);
+        assert_eq!(logger.context()["task_id"], "task-1");
+        assert!(!logger.context().contains_key("user_id"));
+        assert_eq!(
+            message_with_context("Task created", logger.context()),
+            "Task created [correlation_id=corr-1 request_id=req-1 task_id=task-1]"
+        );
+        assert_eq!(message_with_context("Task created", &HashMap::new()), "Task created");
+    }
+}
@@ -0,0 +1,98 @@
+//! Scheduled maintenance of the task graph
+//!
+//! [`CleanupStaleDependencies`] runs once a month. It removes dependencies
+//! whose report entry suggests removal, i.e. one end was cancelled, and only
+//! logs the ones that need a human to confirm them.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant};
+
+use crate::domain::{StaleDependencyAction, TaskService};
+use crate::{TaskServiceError, TaskServiceResult};
+
+/// Time between two cleanups
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Outcome of one stale dependency cleanup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanupResult {
+    pub removed_dependency_ids: Vec<String>,
+    /// Stale dependencies left in place because they need review
+    pub flagged_dependency_ids: Vec<String>,
+}
+
+/// Background job removing dependencies on cancelled tasks
+pub struct CleanupStaleDependencies {
+    service: Arc<dyn TaskService + Send + Sync>,
+}
+
+impl CleanupStaleDependencies {
+    pub fn new(service: Arc<dyn TaskService + Send + Sync>) -> Self {
+        Self { service }
+    }
+
+    /// Clean up once
+    ///
+    /// A dependency that fails to be removed is logged and skipped so the
+    /// rest of the report is still processed.
+    pub async fn run_once(&self) -> TaskServiceResult<CleanupResult> {
+        let report = self.service.get_dependency_age_report().await
+            .map_err(|e| TaskServiceError::Database { message: e.to_string() })?;
+
+        let mut result = CleanupResult::default();
+        for stale in report.stale_dependencies {
+            match stale.suggested_action {
+                StaleDependencyAction::Remove => {
+                    if let Err(e) = self.service.remove_task_dependency(&stale.dependency_id).await {
+                        tracing::warn!(dependency_id = %stale.dependency_id, error = %e, "Failed to remove stale dependency");
+                        continue;
+                    }
+                    tracing::info!(dependency_id = %stale.dependency_id, reason = %stale.reason, "Removed stale dependency");
+                    result.removed_dependency_ids.push(stale.dependency_id);
+                }
+                StaleDependencyAction::Update => {
+                    tracing::info!(dependency_id = %stale.dependency_id, reason = %stale.reason, "Stale dependency needs review");
+                    result.flagged_dependency_ids.push(stale.dependency_id);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Run the cleanup every month, starting one month from now, until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval_at(Instant::now() + CLEANUP_INTERVAL, CLEANUP_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::warn!(error = %e, "Stale dependency cleanup failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DependencyType, MockTaskService, Task, TaskContext, TaskStatus};
+
+    #[tokio::test]
+    async fn test_run_once_removes_dependencies_on_cancelled_tasks() {
+        let service = Arc::new(MockTaskService::new());
+        let mut cancelled = Task::new("cancelled".to_string(), "Cancelled".to_string(), TaskContext::Work);
+        cancelled.status = TaskStatus::Cancelled;
+        service.insert_task(cancelled);
+        let dependency = service.add_task_dependency("test-id", "cancelled", DependencyType::Blocks).await.unwrap();
+
+        let result = CleanupStaleDependencies::new(service.clone()).run_once().await.unwrap();
+
+        assert_eq!(result.removed_dependency_ids, vec![dependency.id]);
+        assert!(result.flagged_dependency_ids.is_empty());
+        assert!(service.get_dependency_age_report().await.unwrap().stale_dependencies.is_empty());
+    }
+}
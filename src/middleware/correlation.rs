@@ -0,0 +1,169 @@
+//! Request correlation IDs
+//!
+//! [`CorrelationIdLayer`] resolves the ID of every request, taken from the
+//! `X-Correlation-Id` header or generated when the client sent none, and
+//! echoes it in the response header. While the request is handled the ID is
+//! kept in a task-local, so code without access to the request, such as
+//! [`EventService::publish`](crate::EventService::publish), can read it
+//! through [`current_correlation_id`]. Handlers get it with the
+//! [`CorrelationId`] extractor.
+
+use async_trait::async_trait;
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::Response;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::Instrument;
+
+use crate::utils::generate_correlation_id;
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+/// Longest client-supplied ID accepted; longer ones are replaced by a generated ID
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_CORRELATION_ID: String;
+}
+
+/// Correlation ID of the request being handled, if any
+pub fn current_correlation_id() -> Option<String> {
+    CURRENT_CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` under `correlation_id`, e.g. to continue the correlation of a consumed event
+pub async fn with_correlation_id<F: Future>(correlation_id: String, future: F) -> F::Output {
+    CURRENT_CORRELATION_ID.scope(correlation_id, future).await
+}
+
+/// Run `future` under the current correlation ID, e.g. before handing it to `tokio::spawn`
+pub fn in_current_correlation<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let correlation_id = current_correlation_id();
+    async move {
+        match correlation_id {
+            Some(correlation_id) => with_correlation_id(correlation_id, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Correlation ID of the request, as resolved by [`CorrelationIdLayer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(pub String);
+
+impl CorrelationId {
+    /// ID from the request headers, or a new one when it is missing or unusable
+    pub fn from_headers(headers: &axum::http::HeaderMap) -> Self {
+        let id = headers
+            .get(CORRELATION_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_CORRELATION_ID_LEN)
+            .map(str::to_string)
+            .unwrap_or_else(generate_correlation_id);
+        Self(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CorrelationId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // Without the layer, e.g. in handler tests, fall back to the headers
+        Ok(parts.extensions.get::<CorrelationId>().cloned()
+            .unwrap_or_else(|| CorrelationId::from_headers(&parts.headers)))
+    }
+}
+
+/// Resolve the correlation ID of every request and return it in `X-Correlation-Id`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CorrelationIdLayer;
+
+impl<S> Layer<S> for CorrelationIdLayer {
+    type Service = CorrelationIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorrelationIdService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CorrelationIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for CorrelationIdService<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        let correlation_id = CorrelationId::from_headers(request.headers());
+        request.extensions_mut().insert(correlation_id.clone());
+
+        let span = tracing::info_span!("request", correlation_id = %correlation_id.0);
+        let future = self.inner.call(request);
+        let CorrelationId(id) = correlation_id;
+        Box::pin(CURRENT_CORRELATION_ID.scope(id.clone(), async move {
+            let mut response = future.await?;
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                response.headers_mut().insert(HeaderName::from_static(CORRELATION_ID_HEADER), value);
+            }
+            Ok(response)
+        }.instrument(span)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use axum_test::TestServer;
+
+    fn server() -> TestServer {
+        let router = Router::new()
+            .route("/id", get(|CorrelationId(id): CorrelationId| async move {
+                format!("{}|{}", id, current_correlation_id().unwrap_or_default())
+            }))
+            .layer(CorrelationIdLayer);
+        TestServer::new(router).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_layer_propagates_client_correlation_id() {
+        let response = server().get("/id")
+            .add_header(CORRELATION_ID_HEADER.parse().unwrap(), "abc-123".parse().unwrap())
+            .await;
+
+        response.assert_text("abc-123|abc-123");
+        assert_eq!(response.header(CORRELATION_ID_HEADER), "abc-123");
+    }
+
+    #[tokio::test]
+    async fn test_layer_generates_missing_correlation_id() {
+        let response = server().get("/id").await;
+
+        let id = response.header(CORRELATION_ID_HEADER).to_str().unwrap().to_string();
+        assert!(!id.is_empty());
+        response.assert_text(format!("{}|{}", id, id));
+        assert!(current_correlation_id().is_none());
+    }
+}
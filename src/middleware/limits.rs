@@ -0,0 +1,83 @@
+//! Request body size limits
+//!
+//! [`limit_request_body`] caps the body of every request at
+//! `api.request_size_limit.max_body_bytes`, except on the routes listed in
+//! [`route_max_body_bytes`], which accept more or less than the default.
+//! Requests over the limit are refused with a `413 Payload Too Large` and a
+//! JSON body like `{"error": "request_too_large", "max_bytes": 1048576}`,
+//! whether the client declared a `Content-Length` or streamed the body.
+
+use axum::body::Body;
+use axum::extract::{MatchedPath, Request};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use http_body_util::Limited;
+use serde_json::json;
+use tower::{ServiceBuilder, ServiceExt};
+use tower_http::limit::{RequestBodyLimitLayer, ResponseBody};
+
+/// Body limit of routes without an override, unless configured otherwise
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+/// `POST /api/v1/tasks/bulk` takes up to a whole import at once
+pub const BULK_CREATE_MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// Presigning only takes a file name and content type; the file itself goes to storage
+pub const ATTACHMENT_PRESIGN_MAX_BODY_BYTES: usize = 1024;
+/// Adding or editing a comment
+pub const COMMENT_MAX_BODY_BYTES: usize = 50 * 1024;
+
+/// Limit of the route with pattern `path`, when it overrides the configured default
+pub fn route_max_body_bytes(path: &str) -> Option<usize> {
+    match path {
+        "/api/v1/tasks/bulk" => Some(BULK_CREATE_MAX_BODY_BYTES),
+        "/api/v1/tasks/:id/attachments/presign" => Some(ATTACHMENT_PRESIGN_MAX_BODY_BYTES),
+        "/api/v1/tasks/:id/comments" | "/api/v1/tasks/:id/comments/:comment_id" => Some(COMMENT_MAX_BODY_BYTES),
+        _ => None,
+    }
+}
+
+/// Refuse request bodies over the limit of the matched route, `default_max_bytes` for most
+pub async fn limit_request_body(default_max_bytes: usize, request: Request, next: Next) -> Response {
+    let max_bytes = request.extensions().get::<MatchedPath>()
+        .and_then(|path| route_max_body_bytes(path.as_str()))
+        .unwrap_or(default_max_bytes);
+
+    let service = ServiceBuilder::new()
+        .map_response(|response: Response<ResponseBody<Body>>| response.map(Body::new))
+        .layer(RequestBodyLimitLayer::new(max_bytes))
+        .map_request(|request: Request<Limited<Body>>| request.map(Body::new))
+        .service(next);
+    let response = match service.oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+
+    // Covers both the declared length being refused up front and the body
+    // extractor running over the limit while reading a streamed body
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return request_too_large(max_bytes);
+    }
+    response
+}
+
+fn request_too_large(max_bytes: usize) -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({ "error": "request_too_large", "max_bytes": max_bytes })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_overrides() {
+        assert_eq!(route_max_body_bytes("/api/v1/tasks/bulk"), Some(BULK_CREATE_MAX_BODY_BYTES));
+        assert_eq!(route_max_body_bytes("/api/v1/tasks/:id/attachments/presign"), Some(ATTACHMENT_PRESIGN_MAX_BODY_BYTES));
+        assert_eq!(route_max_body_bytes("/api/v1/tasks/:id/comments/:comment_id"), Some(COMMENT_MAX_BODY_BYTES));
+        assert_eq!(route_max_body_bytes("/api/v1/tasks"), None);
+    }
+}
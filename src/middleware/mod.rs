@@ -0,0 +1,8 @@
+//! Tower middleware applied to the whole router
+//!
+//! The layers here are wired in by the built-in [`plugins`](crate::plugins).
+
+pub mod correlation;
+pub mod limits;
+pub mod rate_limit;
+pub mod request_context;
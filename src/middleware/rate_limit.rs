@@ -0,0 +1,347 @@
+//! Sliding-window rate limiting
+//!
+//! [`RateLimitLayer`] keeps the time of every accepted request per key, as
+//! chosen by [`RateLimitKeyStrategy`], and rejects a request with a 429 and
+//! `Retry-After` when the key already used its `requests_per_minute` in the
+//! last 60 seconds or its `burst_size` in the last second. Unlike a fixed
+//! window, a client cannot double its rate by straddling a window boundary.
+
+use axum::extract::{ConnectInfo, Request};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+use crate::auth::USER_ID_HEADER;
+use crate::config::{RateLimitConfig, RateLimitKeyStrategy};
+use crate::handlers::api::ApiError;
+use crate::TaskServiceError;
+
+const MINUTE: Duration = Duration::from_secs(60);
+const BURST_WINDOW: Duration = Duration::from_secs(1);
+/// Checks between two sweeps of keys that went idle, e.g. clients that left
+const PURGE_EVERY: u64 = 4096;
+
+/// Times of the requests accepted in the last minute, per key
+#[derive(Debug)]
+pub struct SlidingWindowRateLimiter {
+    config: RateLimitConfig,
+    windows: DashMap<String, VecDeque<Instant>>,
+    checks: AtomicU64,
+}
+
+/// Why a request was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    /// The limit that was hit, per minute or per second
+    pub limit: u32,
+    /// When the oldest request counting against that limit leaves the window
+    pub retry_after: Duration,
+}
+
+impl SlidingWindowRateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, windows: DashMap::new(), checks: AtomicU64::new(0) }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Count a request for `key` unless it would exceed a limit
+    pub fn check(&self, key: &str) -> Result<(), RateLimited> {
+        if self.checks.fetch_add(1, Ordering::Relaxed) % PURGE_EVERY == PURGE_EVERY - 1 {
+            self.purge_idle();
+        }
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> Result<(), RateLimited> {
+        let mut window = self.windows.entry(key.to_string()).or_default();
+        while window.front().is_some_and(|&at| now.duration_since(at) >= MINUTE) {
+            window.pop_front();
+        }
+
+        if window.len() >= self.config.requests_per_minute as usize {
+            let oldest = window[window.len() - self.config.requests_per_minute as usize];
+            return Err(RateLimited {
+                limit: self.config.requests_per_minute,
+                retry_after: MINUTE - now.duration_since(oldest),
+            });
+        }
+
+        let burst = window.iter().rev().take_while(|&&at| now.duration_since(at) < BURST_WINDOW).count();
+        if burst >= self.config.burst_size as usize {
+            let oldest = window[window.len() - self.config.burst_size as usize];
+            return Err(RateLimited {
+                limit: self.config.burst_size,
+                retry_after: BURST_WINDOW - now.duration_since(oldest),
+            });
+        }
+
+        window.push_back(now);
+        Ok(())
+    }
+
+    /// Forget keys without a request in the last minute
+    pub fn purge_idle(&self) {
+        let now = Instant::now();
+        self.windows
+            .retain(|_, window| window.back().is_some_and(|&at| now.duration_since(at) < MINUTE));
+    }
+
+    /// Key the request is limited under
+    ///
+    /// Client headers can be rotated at will, so they only count when the
+    /// peer is one of the configured trusted proxies.
+    fn key(&self, request: &Request) -> String {
+        match self.config.key_strategy {
+            RateLimitKeyStrategy::IpAddress => self.client_ip(request),
+            RateLimitKeyStrategy::UserId => self
+                .trusted_peer(request)
+                .and_then(|_| request.headers().get(USER_ID_HEADER))
+                .and_then(|v| v.to_str().ok())
+                .map(|user| format!("user:{}", user))
+                .unwrap_or_else(|| self.client_ip(request)),
+            RateLimitKeyStrategy::GlobalShared => "global".to_string(),
+        }
+    }
+
+    /// Client address: the peer, or the one reported by the trusted proxies in front of it
+    fn client_ip(&self, request: &Request) -> String {
+        let Some(peer) = peer_ip(request) else {
+            return "unknown".to_string();
+        };
+        if !self.config.trusted_proxies.contains(&peer) {
+            return peer.to_string();
+        }
+        // Each proxy appends the address it received the request from, so the
+        // last address not belonging to a trusted proxy is the client
+        let forwarded = forwarded_for(request.headers());
+        forwarded
+            .iter()
+            .rev()
+            .find(|ip| !self.config.trusted_proxies.contains(ip))
+            .or(forwarded.first())
+            .unwrap_or(&peer)
+            .to_string()
+    }
+
+    fn trusted_peer(&self, request: &Request) -> Option<IpAddr> {
+        peer_ip(request).filter(|peer| self.config.trusted_proxies.contains(peer))
+    }
+}
+
+/// Address of the connection, tracked when the server is run with connect info
+fn peer_ip(request: &Request) -> Option<IpAddr> {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// Addresses of `X-Forwarded-For`, client first, skipping those that do not parse
+fn forwarded_for(headers: &HeaderMap) -> Vec<IpAddr> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect()
+}
+
+/// Reject requests over the configured limits with a 429
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<SlidingWindowRateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { limiter: Arc::new(SlidingWindowRateLimiter::new(config)) }
+    }
+
+    /// Limiter shared by every service this layer creates
+    pub fn limiter(&self) -> Arc<SlidingWindowRateLimiter> {
+        self.limiter.clone()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService { inner, limiter: self.limiter.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<SlidingWindowRateLimiter>,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let key = self.limiter.key(&request);
+        match self.limiter.check(&key) {
+            Ok(()) => {
+                let future = self.inner.call(request);
+                Box::pin(future)
+            }
+            Err(limited) => {
+                tracing::debug!(key = %key, limit = limited.limit, "Rate limit exceeded");
+                let response = too_many_requests(key, limited);
+                Box::pin(async move { Ok(response) })
+            }
+        }
+    }
+}
+
+fn too_many_requests(key: String, limited: RateLimited) -> Response {
+    let mut response = ApiError::from(TaskServiceError::RateLimitExceeded { key, limit: limited.limit }).into_response();
+    // Whole seconds, rounded up so a client retrying on time is not rejected again
+    let seconds = limited.retry_after.as_millis().div_ceil(1000).max(1);
+    response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(seconds as u64));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Router;
+    use axum_test::TestServer;
+
+    fn limiter(requests_per_minute: u32, burst_size: u32) -> SlidingWindowRateLimiter {
+        SlidingWindowRateLimiter::new(RateLimitConfig {
+            requests_per_minute,
+            burst_size,
+            key_strategy: RateLimitKeyStrategy::GlobalShared,
+            trusted_proxies: Vec::new(),
+        })
+    }
+
+    fn keyed_by(key_strategy: RateLimitKeyStrategy, trusted_proxies: &[&str]) -> SlidingWindowRateLimiter {
+        SlidingWindowRateLimiter::new(RateLimitConfig {
+            key_strategy,
+            trusted_proxies: trusted_proxies.iter().map(|ip| ip.parse().unwrap()).collect(),
+            ..RateLimitConfig::default()
+        })
+    }
+
+    fn request_from(peer: &str, headers: &[(&str, &str)]) -> Request {
+        let mut request = axum::http::Request::builder().uri("/ping");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let mut request = request.body(axum::body::Body::empty()).unwrap();
+        let peer: SocketAddr = format!("{}:4000", peer).parse().unwrap();
+        request.extensions_mut().insert(ConnectInfo(peer));
+        request
+    }
+
+    #[test]
+    fn test_window_slides_instead_of_resetting() {
+        let limiter = limiter(2, 10);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("k", start).is_ok());
+        assert!(limiter.check_at("k", start + Duration::from_secs(30)).is_ok());
+        let limited = limiter.check_at("k", start + Duration::from_secs(45)).unwrap_err();
+        assert_eq!(limited, RateLimited { limit: 2, retry_after: Duration::from_secs(15) });
+
+        // Only the first request has left the window
+        assert!(limiter.check_at("k", start + Duration::from_secs(60)).is_ok());
+        assert!(limiter.check_at("k", start + Duration::from_secs(61)).is_err());
+        assert!(limiter.check_at("other", start + Duration::from_secs(61)).is_ok());
+    }
+
+    #[test]
+    fn test_burst_size_limits_each_second() {
+        let limiter = limiter(100, 2);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("k", start).is_ok());
+        assert!(limiter.check_at("k", start + Duration::from_millis(100)).is_ok());
+        let limited = limiter.check_at("k", start + Duration::from_millis(200)).unwrap_err();
+        assert_eq!(limited, RateLimited { limit: 2, retry_after: Duration::from_millis(800) });
+
+        assert!(limiter.check_at("k", start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_forwarded_headers_count_only_from_trusted_proxies() {
+        let forwarded = [("x-forwarded-for", "203.0.113.7, 10.0.0.2")];
+        let limiter = keyed_by(RateLimitKeyStrategy::IpAddress, &["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(limiter.key(&request_from("10.0.0.1", &forwarded)), "203.0.113.7");
+        assert_eq!(limiter.key(&request_from("198.51.100.4", &forwarded)), "198.51.100.4");
+        assert_eq!(limiter.key(&request_from("10.0.0.1", &[])), "10.0.0.1");
+
+        // A client prepending its own entry does not get a fresh key
+        let spoofed = [("x-forwarded-for", "192.0.2.1, 203.0.113.7")];
+        assert_eq!(limiter.key(&request_from("10.0.0.2", &spoofed)), "203.0.113.7");
+
+        let limiter = keyed_by(RateLimitKeyStrategy::UserId, &["10.0.0.1"]);
+        let alice = [("x-user-id", "alice")];
+        assert_eq!(limiter.key(&request_from("10.0.0.1", &alice)), "user:alice");
+        assert_eq!(limiter.key(&request_from("198.51.100.4", &alice)), "198.51.100.4");
+    }
+
+    #[tokio::test]
+    async fn test_layer_limits_each_forwarded_address() {
+        let config = RateLimitConfig {
+            requests_per_minute: 1,
+            burst_size: 1,
+            key_strategy: RateLimitKeyStrategy::IpAddress,
+            trusted_proxies: vec!["127.0.0.1".parse().unwrap()],
+        };
+        // As set by serving with connect info, here for a proxy on localhost
+        let peer: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let router = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(RateLimitLayer::new(config))
+            .layer(axum::Extension(ConnectInfo(peer)));
+        let server = TestServer::new(router).unwrap();
+
+        server
+            .get("/ping")
+            .add_header("x-forwarded-for".parse().unwrap(), "10.0.0.1".parse().unwrap())
+            .await
+            .assert_status_ok();
+        let response = server
+            .get("/ping")
+            .add_header("x-forwarded-for".parse().unwrap(), "10.0.0.9, 10.0.0.1".parse().unwrap())
+            .await;
+        response.assert_status(StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.header(RETRY_AFTER), "60");
+        server
+            .get("/ping")
+            .add_header("x-forwarded-for".parse().unwrap(), "10.0.0.2".parse().unwrap())
+            .await
+            .assert_status_ok();
+    }
+}
@@ -0,0 +1,114 @@
+//! Per-request logging context
+//!
+//! The [`request_context`] middleware resolves a [`RequestContext`] for every
+//! request: its own ID, taken from `X-Request-Id` or generated, the
+//! correlation ID resolved by [`CorrelationIdLayer`](super::correlation::CorrelationIdLayer)
+//! and the calling user. The request ID is echoed in the response header.
+//! Handlers get the context with the [`RequestContext`] extractor, usually
+//! through [`ContextualLogger`](crate::logging::ContextualLogger).
+
+use async_trait::async_trait;
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::convert::Infallible;
+use uuid::Uuid;
+
+use crate::auth::USER_ID_HEADER;
+use crate::middleware::correlation::CorrelationId;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Longest client-supplied ID accepted; longer ones are replaced by a generated ID
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Who made the request being handled and how to find its logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub correlation_id: String,
+    pub user_id: Option<String>,
+}
+
+impl RequestContext {
+    /// Context of a request with `headers`, reusing the correlation ID when already resolved
+    pub fn from_headers(headers: &HeaderMap, correlation_id: Option<&CorrelationId>) -> Self {
+        let request_id = header_value(headers, REQUEST_ID_HEADER)
+            .filter(|id| id.len() <= MAX_REQUEST_ID_LEN)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let correlation_id = correlation_id.cloned()
+            .unwrap_or_else(|| CorrelationId::from_headers(headers));
+
+        Self {
+            request_id,
+            correlation_id: correlation_id.0,
+            user_id: header_value(headers, USER_ID_HEADER),
+        }
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers.get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Attach a [`RequestContext`] to the request and return its ID in `X-Request-Id`
+pub async fn request_context(mut request: Request, next: Next) -> Response {
+    let context = RequestContext::from_headers(request.headers(), request.extensions().get::<CorrelationId>());
+    let request_id = context.request_id.clone();
+    request.extensions_mut().insert(context);
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    response
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // Without the middleware, e.g. in handler tests, fall back to the headers
+        Ok(parts.extensions.get::<RequestContext>().cloned()
+            .unwrap_or_else(|| RequestContext::from_headers(&parts.headers, parts.extensions.get::<CorrelationId>())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::correlation::{CorrelationIdLayer, CORRELATION_ID_HEADER};
+    use axum::routing::get;
+    use axum::Router;
+    use axum_test::TestServer;
+
+    #[tokio::test]
+    async fn test_middleware_attaches_request_context() {
+        let router = Router::new()
+            .route("/context", get(|context: RequestContext| async move {
+                format!("{}|{}|{}", context.request_id, context.correlation_id, context.user_id.unwrap_or_default())
+            }))
+            .layer(axum::middleware::from_fn(request_context))
+            .layer(CorrelationIdLayer);
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/context")
+            .add_header(REQUEST_ID_HEADER.parse().unwrap(), "req-1".parse().unwrap())
+            .add_header(CORRELATION_ID_HEADER.parse().unwrap(), "corr-1".parse().unwrap())
+            .add_header(USER_ID_HEADER.parse().unwrap(), "user-1".parse().unwrap())
+            .await;
+        response.assert_text("req-1|corr-1|user-1");
+        assert_eq!(response.header(REQUEST_ID_HEADER), "req-1");
+
+        let response = server.get("/context").await;
+        let request_id = response.header(REQUEST_ID_HEADER).to_str().unwrap().to_string();
+        let correlation_id = response.header(CORRELATION_ID_HEADER).to_str().unwrap().to_string();
+        response.assert_text(format!("{}|{}|", request_id, correlation_id));
+    }
+}
@@ -0,0 +1,205 @@
+//! Alert threshold monitoring
+//!
+//! [`ThresholdMonitorJob`] periodically compares service-wide metrics with the
+//! configured [`AlertThresholds`] and publishes a [`ThresholdBreached`] event
+//! for every limit that is exceeded. The same evaluation backs the admin
+//! alert status endpoint.
+
+use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::config::AlertThresholds;
+use crate::domain::{AlertMetrics, TaskService};
+use crate::events::{AlertSeverity, DomainEvent, EventService, ThresholdBreached};
+use crate::{TaskServiceError, TaskServiceResult};
+
+/// Values this far past their limit are critical rather than a warning
+const CRITICAL_FACTOR: f64 = 1.5;
+
+/// Current value of one monitored metric next to its limit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ThresholdStatus {
+    pub threshold_name: String,
+    pub current_value: f64,
+    pub threshold_value: f64,
+    /// Set only when the threshold is breached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<AlertSeverity>,
+}
+
+impl ThresholdStatus {
+    /// Status of a metric that must stay at or below `limit`
+    fn at_most(name: &str, current: f64, limit: f64) -> Self {
+        let severity = match current {
+            c if c > limit * CRITICAL_FACTOR => Some(AlertSeverity::Critical),
+            c if c > limit => Some(AlertSeverity::Warning),
+            _ => None,
+        };
+        Self::new(name, current, limit, severity)
+    }
+
+    /// Status of a metric that must stay at or above `limit`
+    fn at_least(name: &str, current: f64, limit: f64) -> Self {
+        let severity = match current {
+            c if c < limit / CRITICAL_FACTOR => Some(AlertSeverity::Critical),
+            c if c < limit => Some(AlertSeverity::Warning),
+            _ => None,
+        };
+        Self::new(name, current, limit, severity)
+    }
+
+    fn new(name: &str, current_value: f64, threshold_value: f64, severity: Option<AlertSeverity>) -> Self {
+        Self {
+            threshold_name: name.to_string(),
+            current_value,
+            threshold_value,
+            severity,
+        }
+    }
+
+    pub fn is_breached(&self) -> bool {
+        self.severity.is_some()
+    }
+}
+
+/// Compare metrics with their thresholds
+///
+/// The project health threshold is skipped when there are no projects with
+/// open tasks.
+pub fn evaluate_thresholds(thresholds: &AlertThresholds, metrics: &AlertMetrics) -> Vec<ThresholdStatus> {
+    let mut statuses = vec![
+        ThresholdStatus::at_most(
+            "max_overdue_tasks",
+            metrics.overdue_tasks as f64,
+            thresholds.max_overdue_tasks as f64,
+        ),
+        ThresholdStatus::at_most(
+            "max_single_user_task_count",
+            metrics.max_single_user_task_count as f64,
+            thresholds.max_single_user_task_count as f64,
+        ),
+        ThresholdStatus::at_most(
+            "max_dependency_chain_length",
+            metrics.max_dependency_chain_length as f64,
+            thresholds.max_dependency_chain_length as f64,
+        ),
+    ];
+
+    if let Some(score) = metrics.lowest_project_health_score {
+        statuses.push(ThresholdStatus::at_least(
+            "min_project_health_score",
+            score,
+            thresholds.min_project_health_score,
+        ));
+    }
+
+    statuses
+}
+
+/// Background job publishing `ThresholdBreached` events
+pub struct ThresholdMonitorJob {
+    service: Arc<dyn TaskService + Send + Sync>,
+    event_service: Arc<EventService>,
+    thresholds: AlertThresholds,
+}
+
+impl ThresholdMonitorJob {
+    pub fn new(
+        service: Arc<dyn TaskService + Send + Sync>,
+        event_service: Arc<EventService>,
+        thresholds: AlertThresholds,
+    ) -> Self {
+        Self { service, event_service, thresholds }
+    }
+
+    /// Check the thresholds once, publishing an event for each breach
+    pub async fn run_once(&self) -> TaskServiceResult<Vec<ThresholdStatus>> {
+        let metrics = self.service.get_alert_metrics().await
+            .map_err(|e| TaskServiceError::Database { message: e.to_string() })?;
+        let statuses = evaluate_thresholds(&self.thresholds, &metrics);
+
+        for status in statuses.iter().filter(|s| s.is_breached()) {
+            let event = ThresholdBreached {
+                threshold_name: status.threshold_name.clone(),
+                current_value: status.current_value,
+                threshold_value: status.threshold_value,
+                severity: status.severity.unwrap_or(AlertSeverity::Warning),
+                detected_at: Utc::now(),
+            };
+            self.event_service.publish(ThresholdBreached::TOPIC, event).await?;
+        }
+
+        Ok(statuses)
+    }
+
+    /// Run the checks every `check_interval_seconds` until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.thresholds.check_interval_seconds.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::warn!(error = %e, "Alert threshold check failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::MockTaskService;
+
+    fn metrics(overdue: u32, health: Option<f64>) -> AlertMetrics {
+        AlertMetrics {
+            overdue_tasks: overdue,
+            lowest_project_health_score: health,
+            max_single_user_task_count: 3,
+            max_dependency_chain_length: 2,
+        }
+    }
+
+    #[test]
+    fn test_threshold_severity() {
+        let thresholds = AlertThresholds { max_overdue_tasks: 10, ..Default::default() };
+
+        let find = |statuses: Vec<ThresholdStatus>, name: &str| {
+            statuses.into_iter().find(|s| s.threshold_name == name).unwrap()
+        };
+
+        assert!(!find(evaluate_thresholds(&thresholds, &metrics(10, None)), "max_overdue_tasks").is_breached());
+        assert_eq!(
+            find(evaluate_thresholds(&thresholds, &metrics(12, None)), "max_overdue_tasks").severity,
+            Some(AlertSeverity::Warning)
+        );
+        assert_eq!(
+            find(evaluate_thresholds(&thresholds, &metrics(16, None)), "max_overdue_tasks").severity,
+            Some(AlertSeverity::Critical)
+        );
+
+        let health = find(evaluate_thresholds(&thresholds, &metrics(0, Some(30.0))), "min_project_health_score");
+        assert_eq!(health.severity, Some(AlertSeverity::Critical));
+        assert!(evaluate_thresholds(&thresholds, &metrics(0, None)).iter()
+            .all(|s| s.threshold_name != "min_project_health_score"));
+    }
+
+    #[tokio::test]
+    async fn test_run_once_reports_every_threshold() {
+        let job = ThresholdMonitorJob::new(
+            Arc::new(MockTaskService::new()),
+            Arc::new(EventService::new().await.unwrap()),
+            AlertThresholds::default(),
+        );
+
+        let statuses = job.run_once().await.unwrap();
+
+        assert_eq!(statuses.len(), 3);
+        assert!(statuses.iter().all(|s| !s.is_breached()));
+    }
+}
@@ -0,0 +1,90 @@
+//! Due date notifications
+//!
+//! [`DueSoonDigestJob`] runs every hour and publishes a [`DueSoonDigest`]
+//! listing the open tasks due within the next day, grouped by assignee, for
+//! downstream notification services to fan out.
+
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::domain::TaskService;
+use crate::events::{DomainEvent, DueSoonDigest, EventService};
+use crate::{TaskServiceError, TaskServiceResult};
+
+/// Time between two digests
+const DIGEST_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How far ahead each digest looks for due tasks
+const DIGEST_LOOKAHEAD_HOURS: i64 = 24;
+
+/// Background job publishing `DueSoonDigest` events
+pub struct DueSoonDigestJob {
+    service: Arc<dyn TaskService + Send + Sync>,
+    event_service: Arc<EventService>,
+}
+
+impl DueSoonDigestJob {
+    pub fn new(service: Arc<dyn TaskService + Send + Sync>, event_service: Arc<EventService>) -> Self {
+        Self { service, event_service }
+    }
+
+    /// Publish one digest, or nothing when no open task is due soon
+    pub async fn run_once(&self) -> TaskServiceResult<Option<DueSoonDigest>> {
+        let lookahead = chrono::Duration::hours(DIGEST_LOOKAHEAD_HOURS);
+        let report = self.service.get_tasks_due_soon(lookahead, true).await
+            .map_err(|e| TaskServiceError::Database { message: e.to_string() })?;
+        if report.tasks.is_empty() {
+            return Ok(None);
+        }
+
+        let digest = DueSoonDigest {
+            task_ids: report.tasks.iter().map(|t| t.id.clone()).collect(),
+            task_ids_by_assignee: report.grouped.iter()
+                .map(|(user_id, tasks)| (user_id.clone(), tasks.iter().map(|t| t.id.clone()).collect()))
+                .collect(),
+            earliest_due: report.earliest_due,
+            lookahead_hours: DIGEST_LOOKAHEAD_HOURS,
+            generated_at: Utc::now(),
+        };
+        self.event_service.publish(DueSoonDigest::TOPIC, digest.clone()).await?;
+
+        Ok(Some(digest))
+    }
+
+    /// Publish a digest every hour until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DIGEST_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::warn!(error = %e, "Due soon digest failed");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{MockTaskService, Task, TaskContext};
+
+    #[tokio::test]
+    async fn test_run_once_publishes_digest_of_tasks_due_soon() {
+        let service = Arc::new(MockTaskService::new());
+        let job = DueSoonDigestJob::new(service.clone(), Arc::new(EventService::new().await.unwrap()));
+        assert!(job.run_once().await.unwrap().is_none());
+
+        let mut task = Task::new("due-soon".to_string(), "Due soon".to_string(), TaskContext::Work);
+        task.due_date = Some(Utc::now() + chrono::Duration::hours(3));
+        service.insert_task(task.clone());
+
+        let digest = job.run_once().await.unwrap().unwrap();
+        assert_eq!(digest.task_ids, vec!["due-soon"]);
+        assert_eq!(digest.earliest_due, task.due_date);
+        assert_eq!(digest.lookahead_hours, DIGEST_LOOKAHEAD_HOURS);
+    }
+}
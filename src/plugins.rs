@@ -0,0 +1,395 @@
+//! Router plugins
+//!
+//! A [`Plugin`] wraps the fully built router, typically by adding a tower
+//! layer. [`create_app`](crate::create_app) applies the built-in plugins
+//! followed by any registered in [`TaskServiceConfig::plugins`], so a
+//! deployment can add company SSO, feature toggles or its own rate limits
+//! without forking the crate. Each plugin wraps the ones applied before it.
+//!
+//! [`TaskServiceConfig::plugins`]: crate::TaskServiceConfig::plugins
+
+use axum::extract::{DefaultBodyLimit, MatchedPath, Request};
+use axum::http::{header, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use axum::Router;
+use serde::Serialize;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::trace::TraceLayer;
+
+use crate::config::CorsConfig;
+use crate::middleware::correlation::CorrelationIdLayer;
+use crate::middleware::limits::limit_request_body;
+use crate::middleware::request_context::request_context;
+use crate::middleware::rate_limit::RateLimitLayer;
+use crate::{telemetry, AppState};
+
+/// Extension wrapping the application router
+pub trait Plugin {
+    fn name(&self) -> &str;
+
+    /// Wrap `router`, which already has its state and all routes
+    fn apply(&self, router: Router, state: &AppState) -> Router;
+}
+
+/// Plugins registered by the embedding application, applied in registration order
+#[derive(Clone, Default)]
+pub struct PluginBag(Vec<Arc<dyn Plugin + Send + Sync>>);
+
+impl PluginBag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, plugin: impl Plugin + Send + Sync + 'static) {
+        self.0.push(Arc::new(plugin));
+    }
+
+    pub fn with(mut self, plugin: impl Plugin + Send + Sync + 'static) -> Self {
+        self.register(plugin);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<Arc<dyn Plugin + Send + Sync>> {
+        self.0
+    }
+}
+
+impl From<Vec<Arc<dyn Plugin + Send + Sync>>> for PluginBag {
+    fn from(plugins: Vec<Arc<dyn Plugin + Send + Sync>>) -> Self {
+        Self(plugins)
+    }
+}
+
+impl fmt::Debug for PluginBag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|plugin| plugin.name())).finish()
+    }
+}
+
+/// Plugins every app gets, innermost first, so HTTP tracing sees every request
+/// and every response, rejections included, carries its correlation ID
+pub fn builtin_plugins() -> Vec<Arc<dyn Plugin + Send + Sync>> {
+    vec![
+        Arc::new(RequestSizeLimitPlugin),
+        Arc::new(CorsPlugin),
+        Arc::new(RateLimitPlugin),
+        Arc::new(MetricsPlugin::new()),
+        Arc::new(TracingPlugin),
+        Arc::new(RequestContextPlugin),
+        Arc::new(CorrelationIdPlugin),
+    ]
+}
+
+/// CORS headers following `api.cors`, see [`CorsConfig`]
+///
+/// With `"*"` among the allowed origins every origin is allowed. Otherwise
+/// requests carrying an `Origin` that is not listed, preflights included, are
+/// refused with a 403.
+pub struct CorsPlugin;
+
+impl Plugin for CorsPlugin {
+    fn name(&self) -> &str {
+        "cors"
+    }
+
+    fn apply(&self, router: Router, state: &AppState) -> Router {
+        let config = &state.config.api.cors;
+        if config.allows_any_origin() {
+            return router.layer(CorsLayer::permissive());
+        }
+
+        let origins: Arc<Vec<HeaderValue>> = Arc::new(config.allowed_origins.iter()
+            .filter_map(|origin| HeaderValue::from_str(origin.trim_end_matches('/')).ok())
+            .collect());
+        let layer = cors_layer(config, origins.as_ref().clone());
+        router.layer(layer).layer(middleware::from_fn(move |request: Request, next: Next| {
+            let origins = origins.clone();
+            async move {
+                match request.headers().get(header::ORIGIN) {
+                    Some(origin) if !origins.contains(origin) => StatusCode::FORBIDDEN.into_response(),
+                    _ => next.run(request).await,
+                }
+            }
+        }))
+    }
+}
+
+/// CORS layer for explicit origins; entries that do not parse are left out,
+/// [`TaskServiceConfig::validate`](crate::TaskServiceConfig::validate) reports them
+fn cors_layer(config: &CorsConfig, origins: Vec<HeaderValue>) -> CorsLayer {
+    let methods: Vec<Method> = config.allowed_methods.iter()
+        .filter_map(|method| Method::from_bytes(method.as_bytes()).ok())
+        .collect();
+    let headers: Vec<HeaderName> = config.allowed_headers.iter()
+        .filter_map(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+        .collect();
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(config.allow_credentials)
+        .max_age(Duration::from_secs(config.max_age_seconds))
+}
+
+/// Request body limits from `api.request_size_limit`, see [`limit_request_body`]
+///
+/// Replaces axum's default extractor limit, so routes like bulk create can
+/// accept more than it allows.
+pub struct RequestSizeLimitPlugin;
+
+impl Plugin for RequestSizeLimitPlugin {
+    fn name(&self) -> &str {
+        "request_size_limit"
+    }
+
+    fn apply(&self, router: Router, state: &AppState) -> Router {
+        let max_body_bytes = state.config.api.request_size_limit.max_body_bytes;
+        router
+            .layer(middleware::from_fn(move |request: Request, next: Next| limit_request_body(max_body_bytes, request, next)))
+            .layer(DefaultBodyLimit::disable())
+    }
+}
+
+/// HTTP request spans
+pub struct TracingPlugin;
+
+impl Plugin for TracingPlugin {
+    fn name(&self) -> &str {
+        "tracing"
+    }
+
+    fn apply(&self, router: Router, _state: &AppState) -> Router {
+        router.layer(TraceLayer::new_for_http())
+    }
+}
+
+/// `X-Request-Id` on every response and a [`RequestContext`](crate::middleware::request_context::RequestContext)
+/// for handlers, see [`request_context`]
+pub struct RequestContextPlugin;
+
+impl Plugin for RequestContextPlugin {
+    fn name(&self) -> &str {
+        "request-context"
+    }
+
+    fn apply(&self, router: Router, _state: &AppState) -> Router {
+        router.layer(middleware::from_fn(request_context))
+    }
+}
+
+/// `X-Correlation-Id` on every request and response, see [`CorrelationIdLayer`]
+pub struct CorrelationIdPlugin;
+
+impl Plugin for CorrelationIdPlugin {
+    fn name(&self) -> &str {
+        "correlation_id"
+    }
+
+    fn apply(&self, router: Router, _state: &AppState) -> Router {
+        router.layer(CorrelationIdLayer)
+    }
+}
+
+/// Sliding-window limits from `api.rate_limit`, see [`RateLimitLayer`]
+///
+/// Only applied when `api.rate_limit_enabled` is set; requests over the limit
+/// get a 429 with `Retry-After`. Sits inside CORS, so rejections still carry
+/// CORS headers, and outside tracing, so rejected requests never open a span.
+pub struct RateLimitPlugin;
+
+impl Plugin for RateLimitPlugin {
+    fn name(&self) -> &str {
+        "rate_limit"
+    }
+
+    fn apply(&self, router: Router, state: &AppState) -> Router {
+        if !state.config.api.rate_limit_enabled {
+            return router;
+        }
+
+        router.layer(RateLimitLayer::new(state.config.api.rate_limit.clone()))
+    }
+}
+
+/// Request counts and latency, only applied when `monitoring.metrics_enabled` is set
+///
+/// Besides its own totals, every request is recorded in the
+/// `http_request_duration_seconds` histogram served by `GET /metrics`.
+#[derive(Default)]
+pub struct MetricsPlugin {
+    metrics: Arc<RequestMetrics>,
+}
+
+impl MetricsPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters updated by this plugin, e.g. to export them from another plugin
+    pub fn metrics(&self) -> Arc<RequestMetrics> {
+        self.metrics.clone()
+    }
+}
+
+impl Plugin for MetricsPlugin {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn apply(&self, router: Router, state: &AppState) -> Router {
+        if !state.config.monitoring.metrics_enabled {
+            return router;
+        }
+
+        let metrics = self.metrics.clone();
+        router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            let metrics = metrics.clone();
+            async move {
+                let method = request.method().to_string();
+                // The route pattern keeps the number of label values bounded
+                let path = request.extensions().get::<MatchedPath>()
+                    .map_or("unmatched", |path| path.as_str())
+                    .to_string();
+                let started = Instant::now();
+                let response = next.run(request).await;
+                let elapsed = started.elapsed();
+                metrics.record(response.status(), elapsed);
+                telemetry::record_http_request(&method, &path, response.status().as_u16(), elapsed);
+                response
+            }
+        }))
+    }
+}
+
+/// Totals since startup
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    requests: AtomicU64,
+    server_errors: AtomicU64,
+    total_latency_ms: AtomicU64,
+}
+
+/// Point-in-time copy of [`RequestMetrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RequestMetricsSnapshot {
+    pub requests: u64,
+    pub server_errors: u64,
+    pub average_latency_ms: f64,
+}
+
+impl RequestMetrics {
+    fn record(&self, status: StatusCode, latency: Duration) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if status.is_server_error() {
+            self.server_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_latency_ms.fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> RequestMetricsSnapshot {
+        let requests = self.requests.load(Ordering::Relaxed);
+        let total_latency_ms = self.total_latency_ms.load(Ordering::Relaxed);
+        RequestMetricsSnapshot {
+            requests,
+            server_errors: self.server_errors.load(Ordering::Relaxed),
+            average_latency_ms: if requests == 0 { 0.0 } else { total_latency_ms as f64 / requests as f64 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::MockTaskService;
+    use crate::{EventService, TaskServiceConfig};
+    use axum::routing::get;
+    use axum_test::TestServer;
+
+    async fn test_state(config: TaskServiceConfig) -> AppState {
+        AppState {
+            config: Arc::new(config),
+            domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
+            event_service: Arc::new(EventService::new().await.unwrap()),
+            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugins_wrap_router() {
+        let mut config = TaskServiceConfig::default();
+        config.api.rate_limit_enabled = true;
+        config.api.rate_limit.requests_per_minute = 1;
+        let state = test_state(config).await;
+
+        let metrics = MetricsPlugin::new();
+        let counters = metrics.metrics();
+        let router = Router::new().route("/ping", get(|| async { "pong" }));
+        let router = metrics.apply(RateLimitPlugin.apply(router, &state), &state);
+        let server = TestServer::new(router).unwrap();
+
+        server.get("/ping").await.assert_status_ok();
+        server.get("/ping").await.assert_status(StatusCode::TOO_MANY_REQUESTS);
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.server_errors, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cors_plugin_allows_only_configured_origins() {
+        let mut config = TaskServiceConfig::default();
+        config.api.cors.allowed_origins = vec!["https://app.example.com".to_string()];
+        let state = test_state(config).await;
+        let router = Router::new().route("/ping", get(|| async { "pong" }));
+        let server = TestServer::new(CorsPlugin.apply(router, &state)).unwrap();
+
+        let response = server.get("/ping")
+            .add_header(header::ORIGIN, HeaderValue::from_static("https://app.example.com"))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN), "https://app.example.com");
+
+        server.get("/ping")
+            .add_header(header::ORIGIN, HeaderValue::from_static("https://evil.example.com"))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        server.get("/ping").await.assert_status_ok();
+
+        let permissive = test_state(TaskServiceConfig::default()).await;
+        let router = Router::new().route("/ping", get(|| async { "pong" }));
+        let server = TestServer::new(CorsPlugin.apply(router, &permissive)).unwrap();
+        let response = server.get("/ping")
+            .add_header(header::ORIGIN, HeaderValue::from_static("https://evil.example.com"))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.header(header::ACCESS_CONTROL_ALLOW_ORIGIN), "*");
+    }
+
+    #[test]
+    fn test_plugin_bag_debug_lists_names() {
+        let bag = PluginBag::new().with(CorsPlugin).with(TracingPlugin);
+        assert_eq!(format!("{:?}", bag), r#"["cors", "tracing"]"#);
+    }
+}
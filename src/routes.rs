@@ -4,21 +4,54 @@
 
 use axum::{
     http::StatusCode,
-    routing::{delete, get, post, put},
+    response::Json,
+    routing::{delete, get, patch, post, put},
     Router,
 };
+use utoipa::{openapi::ContactBuilder, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     handlers::{
-        health::{health_check, readiness_check, liveness_check, health_detail},
+        self,
+        api::{ApiError, ValidationErrorResponse},
+        admin::{
+            alert_status, archival_status, archive_tasks, rebuild_dependency_graph, run_archival, scheduler_last_report,
+            webhook_deliveries,
+        },
+        projects::{
+            list_projects, create_project, get_project, update_project, archive_project, delete_project,
+            add_task_to_project, get_project_summary,
+        },
+        schemas::{get_context_schema, get_custom_property_schema},
+        contextual::{self, ContextualTaskHandler},
+        jsonapi::JsonApiLayer,
+        LearningTaskHandler, MaintenanceTaskHandler, PersonalTaskHandler,
+        ResearchTaskHandler, WorkTaskHandler,
+        health::{health_check, readiness_check, liveness_check, health_detail, database_health, prometheus_metrics},
         tasks::{
-            create_task, get_task, update_task, delete_task, list_tasks,
-            transition_task_status, add_task_dependency, get_task_dependencies,
-            assign_task, get_assigned_tasks, get_actionable_tasks, get_overdue_tasks,
-            get_task_analytics, add_subtask, get_subtasks, get_circular_dependencies
+            create_task, get_task, update_task, delete_task, list_tasks, patch_custom_properties, clone_task, merge_tasks, split_task,
+            transition_task_status, add_task_dependency, get_task_dependencies, get_dependency_diff,
+            assign_task, get_assigned_tasks, reassign_user_tasks, get_actionable_tasks, get_overdue_tasks, get_tasks_due_soon,
+            get_task_analytics, add_subtask, get_subtasks, get_circular_dependencies,
+            get_execution_order, get_project_dependency_chains, bulk_transition_task_status, batch_update_task_status,
+            clone_project, get_project_workflow_config, update_project_workflow_config,
+            get_project_wip_violations, complete_success_criterion, update_success_criterion, uncomplete_success_criterion,
+            get_task_predecessors, get_task_successors, resolve_bottleneck_action, get_bottleneck_resolution_progress, get_task_history,
+            get_project_throughput, get_project_burndown, find_tasks_similar_to_description, transfer_project_ownership,
+            get_stale_dependencies, parse_task, bulk_delete_tasks, get_project_velocity_forecast,
+            get_priority_churn, export_tasks, get_project_centrality, get_task_order,
+            get_cross_project_dependencies, suggest_task_description, get_user_focus_score,
+            link_external_ticket, get_external_links, get_estimation_accuracy, get_recurring_task_compliance,
+            snapshot_project_state, get_snapshot_diff, get_comment_sentiment, auto_label_task,
+            list_task_comments, add_task_comment, edit_task_comment, delete_task_comment,
+            get_daily_standup_report, request_review, get_review_queue, get_project_execution_plan,
+            bulk_create_tasks, tag_task, untag_task, get_tag_usage, get_tasks_by_tag, search_tasks,
+            search_tasks_advanced, presign_task_attachment, confirm_task_attachment,
+            get_task_graph,
         },
     },
-    AppState,
+    AppState, TaskServiceConfig,
 };
 
 /// Create health check routes
@@ -30,6 +63,8 @@ pub fn health_routes() -> Router<AppState> {
         .route("/health/ready", get(readiness_check))
         .route("/health/live", get(liveness_check))
         .route("/health/detail", get(health_detail))
+        .route("/health/db", get(database_health))
+        .route("/metrics", get(prometheus_metrics))
 }
 
 /// Create API routes for task management
@@ -40,16 +75,48 @@ pub fn api_routes() -> Router<AppState> {
         // Core task CRUD operations
         .route("/api/v1/tasks", post(create_task))
         .route("/api/v1/tasks", get(list_tasks))
+        .route("/api/v1/tasks/export", get(export_tasks))
+        .route("/api/v1/tasks/bulk", post(bulk_create_tasks))
         .route("/api/v1/tasks/:id", get(get_task))
         .route("/api/v1/tasks/:id", put(update_task))
         .route("/api/v1/tasks/:id", delete(delete_task))
+        .route("/api/v1/tasks/:id/custom-properties", patch(patch_custom_properties))
+        .route("/api/v1/tasks/:id/clone", post(clone_task))
+        .route("/api/v1/tasks/:id/merge", post(merge_tasks))
+        .route("/api/v1/tasks/:id/split", post(split_task))
         
         // Task status management
         .route("/api/v1/tasks/:id/status", post(transition_task_status))
+        .route("/api/v1/tasks/bulk-status-transition", post(bulk_transition_task_status))
+        .route("/api/v1/tasks/bulk-status", post(batch_update_task_status))
+        .route("/api/v1/tasks/bulk-delete", post(bulk_delete_tasks))
         
         // Task dependencies
         .route("/api/v1/tasks/:id/dependencies", post(add_task_dependency))
         .route("/api/v1/tasks/:id/dependencies", get(get_task_dependencies))
+        .route("/api/v1/tasks/:id/dependencies/diff", get(get_dependency_diff))
+        .route("/api/v1/tasks/:id/graph", get(get_task_graph))
+        .route("/api/v1/tasks/:id/external-links", post(link_external_ticket).get(get_external_links))
+        .route("/api/v1/tasks/:id/comment-sentiment", get(get_comment_sentiment))
+        .route("/api/v1/tasks/:id/comments", get(list_task_comments).post(add_task_comment))
+        .route("/api/v1/tasks/:id/history", get(get_task_history))
+        .route("/api/v1/tasks/:id/comments/:comment_id", patch(edit_task_comment).delete(delete_task_comment))
+        .route("/api/v1/tasks/:id/auto-label", post(auto_label_task))
+        .route("/api/v1/tasks/:id/tags", post(tag_task))
+        .route("/api/v1/tasks/:id/tags/:tag", delete(untag_task))
+        .route("/api/v1/tasks/:id/attachments/presign", post(presign_task_attachment))
+        .route("/api/v1/tasks/:id/attachments/:attachment_id/confirm", post(confirm_task_attachment))
+        .route("/api/v1/tasks/:id/predecessors", get(get_task_predecessors))
+        .route("/api/v1/tasks/:id/successors", get(get_task_successors))
+        .route("/api/v1/tasks/execution-order", post(get_execution_order))
+        .route("/api/v1/tasks/similar-by-description", post(find_tasks_similar_to_description))
+        .route("/api/v1/tasks/suggest-description", post(suggest_task_description))
+        .route("/api/v1/tasks/parse", post(parse_task))
+        
+        // Success criteria
+        .route("/api/v1/tasks/:id/criteria/:index", patch(update_success_criterion))
+        .route("/api/v1/tasks/:id/criteria/:index/complete", patch(complete_success_criterion))
+        .route("/api/v1/tasks/:id/criteria/:index/uncomplete", patch(uncomplete_success_criterion))
         
         // Task hierarchy (subtasks)
         .route("/api/v1/tasks/:parent_id/subtasks/:child_id", post(add_subtask))
@@ -57,13 +124,246 @@ pub fn api_routes() -> Router<AppState> {
         
         // Task assignment
         .route("/api/v1/tasks/:id/assign", post(assign_task))
+        .route("/api/v1/tasks/:id/review-requests", post(request_review))
         
         // Task queries and analytics
         .route("/api/v1/tasks/:id/analytics", get(get_task_analytics))
         .route("/api/v1/users/:user_id/tasks", get(get_assigned_tasks))
+        .route("/api/v1/users/:user_id/reassign-tasks", post(reassign_user_tasks))
         .route("/api/v1/users/:user_id/tasks/actionable", get(get_actionable_tasks))
+        .route("/api/v1/users/:user_id/task-order", get(get_task_order))
+        .route("/api/v1/users/:user_id/focus-score", get(get_user_focus_score))
+        .route("/api/v1/users/:user_id/standup", get(get_daily_standup_report))
         .route("/api/v1/tasks/overdue", get(get_overdue_tasks))
+        .route("/api/v1/tasks/due-soon", get(get_tasks_due_soon))
+        .route("/api/v1/tasks/review-queue", get(get_review_queue))
+        .route("/api/v1/tasks/tags", get(get_tag_usage))
+        .route("/api/v1/tasks/search", get(search_tasks).post(search_tasks_advanced))
+        .route("/api/v1/tasks/tags/:tag", get(get_tasks_by_tag))
         .route("/api/v1/tasks/circular-dependencies", get(get_circular_dependencies))
+        
+        // Projects
+        .route("/api/v1/projects", get(list_projects).post(create_project))
+        .route("/api/v1/projects/:id", get(get_project).patch(update_project).delete(delete_project))
+        .route("/api/v1/projects/:id/archive", post(archive_project))
+        .route("/api/v1/projects/:id/tasks", post(add_task_to_project))
+        .route("/api/v1/projects/:id/summary", get(get_project_summary))
+        .route("/api/v1/projects/:id/dependency-chains", get(get_project_dependency_chains))
+        .route("/api/v1/projects/:id/clone", post(clone_project))
+        .route("/api/v1/projects/:id/ownership", put(transfer_project_ownership))
+        .route("/api/v1/projects/:id/workflow-config", get(get_project_workflow_config).put(update_project_workflow_config))
+        .route("/api/v1/projects/:id/wip-violations", get(get_project_wip_violations))
+        .route("/api/v1/projects/:id/bottleneck-resolution", get(get_bottleneck_resolution_progress))
+        .route("/api/v1/projects/:id/throughput", get(get_project_throughput))
+        .route("/api/v1/projects/:id/burndown", get(get_project_burndown))
+        .route("/api/v1/projects/:id/velocity-forecast", get(get_project_velocity_forecast))
+        .route("/api/v1/projects/:id/centrality", get(get_project_centrality))
+        .route("/api/v1/projects/:id/execution-plan", get(get_project_execution_plan))
+        .route("/api/v1/projects/:id/snapshots", post(snapshot_project_state))
+        .route("/api/v1/projects/:id/snapshots/:snapshot_id/diff", get(get_snapshot_diff))
+        .route("/api/v1/analytics/bottlenecks/:id/actions/:index/resolve", post(resolve_bottleneck_action))
+        .route("/api/v1/analytics/stale-dependencies", get(get_stale_dependencies))
+        .route("/api/v1/analytics/cross-project-dependencies", get(get_cross_project_dependencies))
+        .route("/api/v1/analytics/estimation-accuracy", get(get_estimation_accuracy))
+        .route("/api/v1/analytics/recurring-compliance", get(get_recurring_task_compliance))
+        .route("/api/v1/analytics/priority-churn", get(get_priority_churn))
+        
+        // Schemas for client-side form building
+        .route("/api/v1/schemas/context/:context", get(get_context_schema))
+        .route("/api/v1/schemas/custom-properties/:context", get(get_custom_property_schema))
+        
+        // Administration
+        .route("/api/v1/admin/tasks/archive", post(archive_tasks))
+        .route("/api/v1/admin/alert-status", get(alert_status))
+        .route("/api/v1/admin/webhooks/deliveries", get(webhook_deliveries))
+        .route("/api/v1/admin/dependency-graph/rebuild", post(rebuild_dependency_graph))
+        .route("/api/v1/admin/archival/run", post(run_archival))
+        .route("/api/v1/admin/archival/status", get(archival_status))
+        .route("/api/v1/admin/scheduler/last-report", get(scheduler_last_report))
+        
+        // Context-specific task endpoints
+        .merge(contextual_task_routes::<WorkTaskHandler>())
+        .merge(contextual_task_routes::<PersonalTaskHandler>())
+        .merge(contextual_task_routes::<LearningTaskHandler>())
+        .merge(contextual_task_routes::<MaintenanceTaskHandler>())
+        .merge(contextual_task_routes::<ResearchTaskHandler>())
+        // `Accept: application/vnd.api+json` opts into JSON:API documents
+        .layer(JsonApiLayer)
+}
+
+/// Create routes for a single task context
+/// 
+/// Mounted at `/api/v1/{context}/tasks`, e.g. `/api/v1/work/tasks`.
+pub fn contextual_task_routes<H: ContextualTaskHandler>() -> Router<AppState> {
+    let base = format!("/api/v1/{}/tasks", H::route_segment());
+
+    Router::new()
+        .route(&base, post(contextual::create_task::<H>).get(contextual::list_tasks::<H>))
+        .route(&format!("{}/:id", base), get(contextual::get_task::<H>))
+}
+
+/// OpenAPI description of the HTTP API
+///
+/// `info.version` and `info.contact` come from the configuration, see [`openapi_spec`].
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "TYL Task Service",
+        description = "Task management microservice built with the TYL framework",
+        license(name = "AGPL-3.0", identifier = "AGPL-3.0")
+    ),
+    paths(
+        handlers::health::health_check,
+        handlers::health::readiness_check,
+        handlers::health::liveness_check,
+        handlers::health::health_detail,
+        handlers::health::database_health,
+        handlers::health::prometheus_metrics,
+        handlers::tasks::create_task,
+        handlers::tasks::list_tasks,
+        handlers::tasks::export_tasks,
+        handlers::tasks::bulk_create_tasks,
+        handlers::tasks::get_task,
+        handlers::tasks::update_task,
+        handlers::tasks::delete_task,
+        handlers::tasks::patch_custom_properties,
+        handlers::tasks::clone_task,
+        handlers::tasks::merge_tasks,
+        handlers::tasks::split_task,
+        handlers::tasks::transition_task_status,
+        handlers::tasks::bulk_transition_task_status,
+        handlers::tasks::batch_update_task_status,
+        handlers::tasks::bulk_delete_tasks,
+        handlers::tasks::add_task_dependency,
+        handlers::tasks::get_task_dependencies,
+        handlers::tasks::get_dependency_diff,
+        handlers::tasks::get_task_graph,
+        handlers::tasks::link_external_ticket,
+        handlers::tasks::get_external_links,
+        handlers::tasks::get_comment_sentiment,
+        handlers::tasks::list_task_comments,
+        handlers::tasks::add_task_comment,
+        handlers::tasks::get_task_history,
+        handlers::tasks::edit_task_comment,
+        handlers::tasks::delete_task_comment,
+        handlers::tasks::auto_label_task,
+        handlers::tasks::tag_task,
+        handlers::tasks::untag_task,
+        handlers::tasks::presign_task_attachment,
+        handlers::tasks::confirm_task_attachment,
+        handlers::tasks::get_task_predecessors,
+        handlers::tasks::get_task_successors,
+        handlers::tasks::get_execution_order,
+        handlers::tasks::find_tasks_similar_to_description,
+        handlers::tasks::suggest_task_description,
+        handlers::tasks::parse_task,
+        handlers::tasks::update_success_criterion,
+        handlers::tasks::complete_success_criterion,
+        handlers::tasks::uncomplete_success_criterion,
+        handlers::tasks::add_subtask,
+        handlers::tasks::get_subtasks,
+        handlers::tasks::assign_task,
+        handlers::tasks::request_review,
+        handlers::tasks::get_task_analytics,
+        handlers::tasks::get_assigned_tasks,
+        handlers::tasks::reassign_user_tasks,
+        handlers::tasks::get_actionable_tasks,
+        handlers::tasks::get_task_order,
+        handlers::tasks::get_user_focus_score,
+        handlers::tasks::get_daily_standup_report,
+        handlers::tasks::get_overdue_tasks,
+        handlers::tasks::get_tasks_due_soon,
+        handlers::tasks::get_review_queue,
+        handlers::tasks::get_tag_usage,
+        handlers::tasks::search_tasks,
+        handlers::tasks::search_tasks_advanced,
+        handlers::tasks::get_tasks_by_tag,
+        handlers::tasks::get_circular_dependencies,
+        handlers::projects::list_projects,
+        handlers::projects::create_project,
+        handlers::projects::get_project,
+        handlers::projects::update_project,
+        handlers::projects::delete_project,
+        handlers::projects::archive_project,
+        handlers::projects::add_task_to_project,
+        handlers::projects::get_project_summary,
+        handlers::tasks::get_project_dependency_chains,
+        handlers::tasks::clone_project,
+        handlers::tasks::transfer_project_ownership,
+        handlers::tasks::get_project_workflow_config,
+        handlers::tasks::update_project_workflow_config,
+        handlers::tasks::get_project_wip_violations,
+        handlers::tasks::get_bottleneck_resolution_progress,
+        handlers::tasks::get_project_throughput,
+        handlers::tasks::get_project_burndown,
+        handlers::tasks::get_project_velocity_forecast,
+        handlers::tasks::get_project_centrality,
+        handlers::tasks::get_project_execution_plan,
+        handlers::tasks::snapshot_project_state,
+        handlers::tasks::get_snapshot_diff,
+        handlers::tasks::resolve_bottleneck_action,
+        handlers::tasks::get_stale_dependencies,
+        handlers::tasks::get_cross_project_dependencies,
+        handlers::tasks::get_estimation_accuracy,
+        handlers::tasks::get_recurring_task_compliance,
+        handlers::tasks::get_priority_churn,
+        handlers::schemas::get_context_schema,
+        handlers::schemas::get_custom_property_schema,
+        handlers::admin::archive_tasks,
+        handlers::admin::alert_status,
+        handlers::admin::webhook_deliveries,
+        handlers::admin::rebuild_dependency_graph,
+        handlers::admin::run_archival,
+        handlers::admin::archival_status,
+        handlers::admin::scheduler_last_report,
+        handlers::contextual::create_task,
+        handlers::contextual::list_tasks,
+        handlers::contextual::get_task
+    ),
+    components(schemas(ApiError, ValidationErrorResponse)),
+    tags(
+        (name = "tasks", description = "Task management"),
+        (name = "contexts", description = "Tasks of a single context"),
+        (name = "projects", description = "Projects and their analytics"),
+        (name = "users", description = "Tasks of a user"),
+        (name = "analytics", description = "Analytics across projects"),
+        (name = "schemas", description = "Context and custom property schemas"),
+        (name = "admin", description = "Administration, requires the admin API key"),
+        (name = "health", description = "Health probes and metrics")
+    )
+)]
+pub struct TaskServiceApi;
+
+/// OpenAPI document of the API as deployed with `config`
+pub fn openapi_spec(config: &TaskServiceConfig) -> utoipa::openapi::OpenApi {
+    let mut spec = TaskServiceApi::openapi();
+    spec.info.version = config.version.clone();
+    if !config.contact.is_empty() {
+        spec.info.contact = Some(
+            ContactBuilder::new()
+                .name(config.contact.name.clone())
+                .email(config.contact.email.clone())
+                .url(config.contact.url.clone())
+                .build(),
+        );
+    }
+    spec
+}
+
+/// Create the OpenAPI routes
+///
+/// The document is always served at `/openapi.json`; Swagger UI is added at
+/// `/docs` when `api.docs_enabled` is set.
+pub fn openapi_routes(config: &TaskServiceConfig) -> Router<AppState> {
+    let spec = openapi_spec(config);
+    if config.api.docs_enabled {
+        return Router::new().merge(SwaggerUi::new("/docs").url("/openapi.json", spec));
+    }
+
+    Router::new().route("/openapi.json", get(move || {
+        let spec = spec.clone();
+        async move { Json(spec) }
+    }))
 }
 
 /// Create the complete router with all routes
@@ -79,17 +379,15 @@ pub fn create_router() -> Router<AppState> {
 mod tests {
     use super::*;
     use axum_test::TestServer;
-    use crate::{TaskServiceConfig, domain::MockTaskService, events::EventService};
+    use crate::{AppStateBuilder, TaskServiceConfig, domain::MockTaskService, events::EventService};
     use std::sync::Arc;
 
     async fn create_test_app() -> Router {
-        let state = AppState {
-            config: Arc::new(TaskServiceConfig::default()),
-            domain_service: Arc::new(MockTaskService::new()),
-            event_service: Arc::new(EventService::new().await.unwrap()),
-            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
-            tracer: Arc::new(tyl_tracing::SimpleTracer::new(tyl_tracing::TraceConfig::new("test-service"))),
-        };
+        let state = AppStateBuilder::new()
+            .with_domain_service(MockTaskService::new())
+            .build()
+            .await
+            .unwrap();
 
         create_router().with_state(state)
     }
@@ -117,6 +415,30 @@ mod tests {
         // Test detailed health check
         let response = server.get("/health/detail").await;
         response.assert_status_ok();
+
+        // Test database circuit breaker status
+        let response = server.get("/health/db").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["circuit_state"], "closed");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        // Recording starts once the recorder is installed
+        crate::telemetry::prometheus_handle().unwrap();
+
+        server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Measured task", "context": "work" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get("/metrics").await;
+        response.assert_status_ok();
+        assert!(response.text().contains(r#"event_published_total{topic="task.created"}"#));
     }
 
     #[tokio::test]
@@ -171,4 +493,2093 @@ mod tests {
         let response = server.delete(&format!("/api/v1/tasks/{}", task_id)).await;
         response.assert_status(StatusCode::NO_CONTENT);
     }
+
+    #[tokio::test]
+    async fn test_status_transition_reports_violations() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // The pre-populated mock task is in the backlog and cannot jump to done
+        let response = server
+            .post("/api/v1/tasks/test-id/status")
+            .json(&serde_json::json!({ "new_status": "done" }))
+            .await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["violations"][0]["ruleName"], "status_transition");
+        assert!(json["violations"][0]["suggestedFix"].is_string());
+
+        let response = server
+            .post("/api/v1/tasks/test-id/status")
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["status"], "ready");
+        assert!(json.get("warnings").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_completing_recurring_task_schedules_next_instance() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Weekly backup check",
+                "context": "maintenance",
+                "due_date": "2030-01-07T09:00:00Z",
+                "recurrence": { "pattern": "custom", "interval": 1, "cron": "not a schedule" }
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Weekly backup check",
+                "context": "maintenance",
+                "due_date": "2030-01-07T09:00:00Z",
+                "recurrence": { "pattern": "weekly", "interval": 1 }
+            }))
+            .await;
+        response.assert_status_ok();
+        let task_id = response.json::<serde_json::Value>()["id"].as_str().unwrap().to_string();
+
+        for status in ["ready", "in_progress", "done"] {
+            server
+                .post(&format!("/api/v1/tasks/{}/status", task_id))
+                .json(&serde_json::json!({ "new_status": status }))
+                .await
+                .assert_status_ok();
+        }
+
+        let response = server.get(&format!("/api/v1/tasks/{}-R20300114", task_id)).await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["status"], "backlog");
+        assert_eq!(json["name"], "Weekly backup check");
+    }
+
+    #[tokio::test]
+    async fn test_execution_order_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let mut ids = Vec::new();
+        for name in ["Design", "Build", "Docs"] {
+            let response = server
+                .post("/api/v1/tasks")
+                .json(&serde_json::json!({ "name": name, "context": "work" }))
+                .await;
+            let json: serde_json::Value = response.json();
+            ids.push(json["id"].as_str().unwrap().to_string());
+        }
+        let (design, build, docs) = (&ids[0], &ids[1], &ids[2]);
+
+        // Build depends on Design
+        server
+            .post(&format!("/api/v1/tasks/{}/dependencies", build))
+            .json(&serde_json::json!({ "to_task_id": design, "dependency_type": "requires" }))
+            .await
+            .assert_status_ok();
+
+        let response = server
+            .post("/api/v1/tasks/execution-order")
+            .json(&serde_json::json!({ "task_ids": [build, docs, design] }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+
+        let waves = json["execution_waves"].as_array().unwrap();
+        assert_eq!(waves.len(), 2);
+        assert!(waves[0].as_array().unwrap().contains(&serde_json::json!(design)));
+        assert!(waves[0].as_array().unwrap().contains(&serde_json::json!(docs)));
+        assert_eq!(waves[1], serde_json::json!([build]));
+
+        let ordered: Vec<&str> = json["tasks"].as_array().unwrap()
+            .iter()
+            .map(|t| t["id"].as_str().unwrap())
+            .collect();
+        let position = |id: &str| ordered.iter().position(|o| *o == id).unwrap();
+        assert!(position(design) < position(build));
+    }
+
+    #[tokio::test]
+    async fn test_predecessor_and_successor_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let mut ids = Vec::new();
+        for name in ["Design", "Build"] {
+            let response = server
+                .post("/api/v1/tasks")
+                .json(&serde_json::json!({ "name": name, "context": "work" }))
+                .await;
+            let json: serde_json::Value = response.json();
+            ids.push(json["id"].as_str().unwrap().to_string());
+        }
+        let (design, build) = (&ids[0], &ids[1]);
+        server
+            .post(&format!("/api/v1/tasks/{}/dependencies", build))
+            .json(&serde_json::json!({ "to_task_id": design, "dependency_type": "requires" }))
+            .await
+            .assert_status_ok();
+
+        let json: serde_json::Value = server.get(&format!("/api/v1/tasks/{}/predecessors", build)).await.json();
+        assert_eq!(json[0]["id"], design.as_str());
+
+        let json: serde_json::Value = server.get(&format!("/api/v1/tasks/{}/successors?include_done=true", design)).await.json();
+        assert_eq!(json[0]["id"], build.as_str());
+        assert_eq!(json[0]["name"], "Build");
+    }
+
+    #[tokio::test]
+    async fn test_admin_scheduler_last_report() {
+        let repository = crate::adapters::InMemoryTaskRepository::new();
+        let service = crate::domain::TaskDomainService::new(repository.clone());
+        let mut config = TaskServiceConfig::default();
+        config.api.admin_api_key = Some("secret".to_string());
+        let state = AppStateBuilder::new()
+            .with_config(config)
+            .with_domain_service(MockTaskService::new())
+            .build()
+            .await
+            .unwrap();
+        let scheduler = state.task_scheduler.clone();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let path = "/api/v1/admin/scheduler/last-report";
+
+        server.get(path).await.assert_status(StatusCode::UNAUTHORIZED);
+        server.get(path)
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        scheduler.run_scheduling_pass(&service, &repository).await.unwrap();
+        let response = server.get(path)
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["promoted"], serde_json::json!([]));
+        assert_eq!(json["errors"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_admin_archive_requires_key() {
+        let mut config = TaskServiceConfig::default();
+        config.api.admin_api_key = Some("secret".to_string());
+        let state = AppState {
+            config: Arc::new(config),
+            domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
+            event_service: Arc::new(EventService::new().await.unwrap()),
+            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
+        };
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+        let path = "/api/v1/admin/tasks/archive?before=2030-01-01T00:00:00Z&batch_size=10";
+
+        server.post(path).await.assert_status(StatusCode::UNAUTHORIZED);
+        server
+            .post(path)
+            .add_header("x-admin-key".parse().unwrap(), "wrong".parse().unwrap())
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        let response = server
+            .post(path)
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["archived_count"], 0);
+
+        server.get("/api/v1/admin/alert-status").await.assert_status(StatusCode::UNAUTHORIZED);
+        let response = server
+            .get("/api/v1/admin/alert-status")
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["threshold_name"], "max_overdue_tasks");
+        assert!(json[0].get("severity").is_none());
+
+        server.get("/api/v1/admin/webhooks/deliveries").await.assert_status(StatusCode::UNAUTHORIZED);
+        let response = server
+            .get("/api/v1/admin/webhooks/deliveries?limit=10")
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json, serde_json::json!([]));
+
+        server.post("/api/v1/admin/dependency-graph/rebuild").await.assert_status(StatusCode::UNAUTHORIZED);
+        let response = server
+            .post("/api/v1/admin/dependency-graph/rebuild")
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["dependency_count"], 0);
+
+        // Archival is disabled by default
+        server
+            .post("/api/v1/admin/archival/run")
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        let response = server
+            .get("/api/v1/admin/archival/status")
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["enabled"], false);
+        assert_eq!(json["archive_after_days"], 90);
+
+        // Without a configured key the endpoint is disabled
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        server
+            .post(path)
+            .add_header("x-admin-key".parse().unwrap(), "secret".parse().unwrap())
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_json_api_format() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let created: serde_json::Value = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Prerequisite", "context": "work" }))
+            .await
+            .json();
+        let prerequisite = created["id"].as_str().unwrap().to_string();
+        server
+            .post("/api/v1/tasks/test-id/dependencies")
+            .json(&serde_json::json!({ "to_task_id": prerequisite, "dependency_type": "requires" }))
+            .await
+            .assert_status_ok();
+
+        // The flat format stays the default
+        let json: serde_json::Value = server.get("/api/v1/tasks/test-id").await.json();
+        assert_eq!(json["id"], "test-id");
+        assert!(json.get("data").is_none());
+
+        let response = server
+            .get("/api/v1/tasks/test-id")
+            .add_header("accept".parse().unwrap(), "application/vnd.api+json".parse().unwrap())
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), "application/vnd.api+json");
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["data"]["type"], "task");
+        assert_eq!(json["data"]["id"], "test-id");
+        assert_eq!(json["data"]["attributes"]["status"], "backlog");
+        assert!(json["data"]["attributes"].get("id").is_none());
+        assert_eq!(json["data"]["relationships"]["dependencies"]["data"][0]["id"], prerequisite.as_str());
+
+        let json: serde_json::Value = server
+            .get("/api/v1/tasks")
+            .add_header("accept".parse().unwrap(), "application/vnd.api+json".parse().unwrap())
+            .await
+            .json();
+        assert!(json["data"].as_array().unwrap().iter().any(|task| task["id"] == "test-id"));
+    }
+
+    #[tokio::test]
+    async fn test_contextual_task_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // Context comes from the route, not the body
+        let create_response = server
+            .post("/api/v1/work/tasks")
+            .json(&serde_json::json!({
+                "name": "Quarterly report",
+                "context": "personal",
+                "priority": "low"
+            }))
+            .await;
+        create_response.assert_status_ok();
+        let create_json: serde_json::Value = create_response.json();
+        assert_eq!(create_json["context"], "work");
+        assert!(create_json.get("days_until_due").is_some());
+        let task_id = create_json["id"].as_str().unwrap().to_string();
+
+        let response = server.get(&format!("/api/v1/work/tasks/{}", task_id)).await;
+        response.assert_status_ok();
+
+        // Task exists, but not in this context
+        let response = server.get(&format!("/api/v1/personal/tasks/{}", task_id)).await;
+        response.assert_status_not_found();
+
+        // Context-specific validation
+        let response = server
+            .post("/api/v1/work/tasks")
+            .json(&serde_json::json!({
+                "name": "Urgent fix",
+                "context": "work",
+                "priority": "critical"
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        // Generic endpoint still accepts any context
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Urgent fix",
+                "context": "work",
+                "priority": "critical"
+            }))
+            .await;
+        response.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_get_task_with_health_score() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/test-id?include_health=true").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        // Fresh, not started and unassigned: only the deadline, dependency and staleness factors count
+        assert!((json["health_score"].as_f64().unwrap() - 0.55).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_auto_label_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // "Test Task" earns the testing tag once
+        let response = server.post("/api/v1/tasks/test-id/auto-label").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["name"], "testing");
+        assert_eq!(json[0]["category"], "auto");
+
+        let response = server.post("/api/v1/tasks/test-id/auto-label").await;
+        assert!(response.json::<serde_json::Value>().as_array().unwrap().is_empty());
+
+        server.post("/api/v1/tasks/missing/auto-label").await.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/search?q=test&context=work").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["id"], "test-id");
+        assert!(json[0]["relevance_score"].as_f64().unwrap() > 0.0);
+
+        let response = server.get("/api/v1/tasks/search?q=test&context=personal").await;
+        assert!(response.json::<serde_json::Value>().as_array().unwrap().is_empty());
+
+        server.get("/api/v1/tasks/search?q=%20").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_search_tasks_advanced_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.post("/api/v1/tasks/search")
+            .json(&serde_json::json!({
+                "query": {"or": [
+                    {"field": {"name": "id", "op": "eq", "value": "test-id"}},
+                    {"field": {"name": "name", "op": "contains", "value": "no such task"}}
+                ]},
+                "sort": [{"field": "name", "direction": "desc"}],
+                "page": {"page": 1, "page_size": 10}
+            }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["items"][0]["id"], "test-id");
+
+        let unknown_field = serde_json::json!({"query": {"field": {"name": "password", "op": "eq", "value": "x"}}});
+        server.post("/api/v1/tasks/search").json(&unknown_field).await.assert_status(StatusCode::BAD_REQUEST);
+
+        let conditions: Vec<_> = (0..11)
+            .map(|i| serde_json::json!({"field": {"name": "id", "op": "eq", "value": format!("T{}", i)}}))
+            .collect();
+        let too_large = serde_json::json!({"query": {"and": conditions}});
+        server.post("/api/v1/tasks/search").json(&too_large).await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_attachment_routes() {
+        let server = TestServer::new(create_test_app().await).unwrap();
+        let presign = serde_json::json!({"filename": "notes.txt", "content_type": "text/plain"});
+        server.post("/api/v1/tasks/test-id/attachments/presign").json(&presign).await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        let base_path = std::env::temp_dir().join(format!("tyl-attachment-routes-{}", uuid::Uuid::new_v4()));
+        let domain_service: Arc<dyn crate::domain::TaskService + Send + Sync> = Arc::new(MockTaskService::new());
+        let storage = crate::config::AttachmentStorageConfig {
+            provider: crate::config::StorageProvider::Local { base_path: base_path.clone() },
+            bucket: "tasks".to_string(),
+            prefix: String::new(),
+            presigned_url_expiry_seconds: 60,
+        };
+        let state = AppStateBuilder::new()
+            .with_shared_domain_service(domain_service.clone())
+            .with_attachment_service(Arc::new(crate::attachments::AttachmentService::new(storage, domain_service)))
+            .build()
+            .await
+            .unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        server.post("/api/v1/tasks/missing/attachments/presign").json(&presign).await
+            .assert_status(StatusCode::NOT_FOUND);
+        let bad_type = serde_json::json!({"filename": "notes.txt", "content_type": ""});
+        server.post("/api/v1/tasks/test-id/attachments/presign").json(&bad_type).await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = server.post("/api/v1/tasks/test-id/attachments/presign").json(&presign).await;
+        response.assert_status(StatusCode::CREATED);
+        let upload: serde_json::Value = response.json();
+        assert_eq!(upload["upload_headers"]["Content-Type"], "text/plain");
+        let confirm_path = format!(
+            "/api/v1/tasks/test-id/attachments/{}/confirm", upload["attachment_id"].as_str().unwrap()
+        );
+
+        server.post(&confirm_path).await.assert_status(StatusCode::CONFLICT);
+        let path = reqwest::Url::parse(upload["upload_url"].as_str().unwrap()).unwrap().to_file_path().unwrap();
+        std::fs::write(path, b"hello").unwrap();
+        let response = server.post(&confirm_path).await;
+        response.assert_status_ok();
+        let attachment: serde_json::Value = response.json();
+        assert_eq!(attachment["name"], "notes.txt");
+        assert_eq!(attachment["size"], 5);
+        server.post(&confirm_path).await.assert_status(StatusCode::NOT_FOUND);
+
+        let _ = std::fs::remove_dir_all(base_path);
+    }
+
+    #[tokio::test]
+    async fn test_tag_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks/test-id/tags")
+            .json(&serde_json::json!({ "tags": ["backend", " Backend ", "urgent"] }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tags"], serde_json::json!(["backend", "urgent"]));
+
+        let response = server.get("/api/v1/tasks/tags").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json, serde_json::json!([
+            { "tag": "backend", "task_count": 1 },
+            { "tag": "urgent", "task_count": 1 },
+        ]));
+
+        let response = server.get("/api/v1/tasks/tags/urgent").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tasks"][0]["id"], "test-id");
+
+        let response = server.get("/api/v1/tasks?tags=urgent,frontend&require_all_tags=true").await;
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"].as_array().unwrap().is_empty());
+
+        server.delete("/api/v1/tasks/test-id/tags/urgent").await.assert_status(StatusCode::NO_CONTENT);
+        let response = server.get("/api/v1/tasks/tags/urgent").await;
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"].as_array().unwrap().is_empty());
+
+        server
+            .post("/api/v1/tasks/test-id/tags")
+            .json(&serde_json::json!({ "tags": [" "] }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server
+            .post("/api/v1/tasks/missing/tags")
+            .json(&serde_json::json!({ "tags": ["backend"] }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_task_comment_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .post("/api/v1/tasks/test-id/comments")
+            .json(&serde_json::json!({ "body": "Anonymous" }))
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+        let response = server
+            .post("/api/v1/tasks/test-id/comments")
+            .add_header("x-user-id".parse().unwrap(), "user-1".parse().unwrap())
+            .json(&serde_json::json!({ "body": "Looks good" }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let comment: serde_json::Value = response.json();
+        let comment_path = format!("/api/v1/tasks/test-id/comments/{}", comment["id"].as_str().unwrap());
+        assert_eq!(comment["author_id"], "user-1");
+        assert_eq!(comment["edited"], false);
+
+        let response = server.get("/api/v1/tasks/test-id/comments").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+
+        server
+            .patch(&comment_path)
+            .add_header("x-user-id".parse().unwrap(), "user-2".parse().unwrap())
+            .json(&serde_json::json!({ "body": "Hijacked" }))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        server
+            .patch(&comment_path)
+            .add_header("x-user-id".parse().unwrap(), "user-1".parse().unwrap())
+            .json(&serde_json::json!({ "body": "x".repeat(10_001) }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        let response = server
+            .patch(&comment_path)
+            .add_header("x-user-id".parse().unwrap(), "user-1".parse().unwrap())
+            .json(&serde_json::json!({ "body": "Looks good to me" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["body"], "Looks good to me");
+        assert_eq!(json["edited"], true);
+
+        server
+            .delete(&comment_path)
+            .add_header("x-user-id".parse().unwrap(), "user-1".parse().unwrap())
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        server.delete(&comment_path).await.assert_status(StatusCode::NOT_FOUND);
+        server.get("/api/v1/tasks/missing/comments").await.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_comment_sentiment_route() {
+        let service = MockTaskService::new();
+        service.add_comment("test-id", "user-1", "Build is broken").await.unwrap();
+        service.add_comment("test-id", "user-2", "Still blocked").await.unwrap();
+        let state = AppStateBuilder::new().with_domain_service(service).build().await.unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response = server.get("/api/v1/tasks/test-id/comment-sentiment").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["comment_count"], 2);
+        assert_eq!(json["negative_comments"], 2);
+        assert_eq!(json["overall_score"], -1.0);
+
+        server.get("/api/v1/tasks/missing/comment-sentiment").await.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_external_link_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        for url in ["https://jira.example.com/browse/OPS-1", "https://jira.example.com/browse/OPS-1?focused"] {
+            server
+                .post("/api/v1/tasks/test-id/external-links")
+                .json(&serde_json::json!({ "system": "jira", "external_id": "OPS-1", "external_url": url }))
+                .await
+                .assert_status_ok();
+        }
+        let response = server
+            .post("/api/v1/tasks/test-id/external-links")
+            .json(&serde_json::json!({
+                "system": { "custom": "youtrack" },
+                "external_id": "YT-7",
+                "external_url": "https://youtrack.example.com/issue/YT-7",
+                "sync_status": "sync_pending"
+            }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["sync_status"], "sync_pending");
+
+        // Linking the same ticket again updates it
+        let response = server.get("/api/v1/tasks/test-id/external-links").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[0]["external_url"], "https://jira.example.com/browse/OPS-1?focused");
+        assert_eq!(json[0]["sync_status"], "linked");
+
+        let response = server.get("/api/v1/tasks/test-id").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["external_links"][1]["system"]["custom"], "youtrack");
+        assert!(json.get("health_score").is_none());
+
+        server
+            .post("/api/v1/tasks/missing/external-links")
+            .json(&serde_json::json!({ "system": "github", "external_id": "1", "external_url": "https://github.com/o/r/issues/1" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        server
+            .post("/api/v1/tasks/test-id/external-links")
+            .json(&serde_json::json!({ "system": "linear", "external_id": "", "external_url": "" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_project_dependency_chains_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/PROJ1/dependency-chains?top=3").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json.is_array());
+    }
+
+    #[tokio::test]
+    async fn test_clone_project_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/projects/PROJ1/clone")
+            .json(&serde_json::json!({
+                "new_project_id": "PROJ2",
+                "new_project_name": "Project two",
+                "copy_tasks": true,
+                "copy_assignments": false,
+                "reset_statuses": true,
+                "date_offset_days": 30
+            }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["new_project_id"], "PROJ2");
+    }
+
+    #[tokio::test]
+    async fn test_patch_custom_properties() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .patch("/api/v1/tasks/test-id/custom-properties")
+            .json(&serde_json::json!({ "sprint": 12, "team": "core" }))
+            .content_type("application/merge-patch+json")
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["custom_properties"]["sprint"], 12);
+        assert_eq!(json["custom_properties"]["team"], "core");
+
+        // null removes a key, keys left out are kept
+        let response = server
+            .patch("/api/v1/tasks/test-id/custom-properties")
+            .json(&serde_json::json!({ "sprint": null }))
+            .content_type("application/merge-patch+json")
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["custom_properties"].get("sprint").is_none());
+        assert_eq!(json["custom_properties"]["team"], "core");
+
+        server
+            .patch("/api/v1/tasks/test-id/custom-properties")
+            .json(&serde_json::json!({ "notes": "x".repeat(5000) }))
+            .content_type("application/merge-patch+json")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server
+            .patch("/api/v1/tasks/missing/custom-properties")
+            .json(&serde_json::json!({ "sprint": 1 }))
+            .content_type("application/merge-patch+json")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_clone_task_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks/test-id/clone")
+            .json(&serde_json::json!({ "id": "test-id-copy", "name": "Copied task" }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["id"], "test-id-copy");
+        assert_eq!(json["name"], "Copied task");
+        server.get("/api/v1/tasks/test-id-copy").await.assert_status_ok();
+
+        server
+            .post("/api/v1/tasks/test-id/clone")
+            .json(&serde_json::json!({ "id": "test-id-copy" }))
+            .await
+            .assert_status(StatusCode::CONFLICT);
+        server
+            .post("/api/v1/tasks/missing/clone")
+            .json(&serde_json::json!({}))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_project_lifecycle_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/projects")
+            .json(&serde_json::json!({ "id": "P-1", "code": "P1", "name": "Launch" }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        server
+            .post("/api/v1/projects")
+            .json(&serde_json::json!({ "id": "P-1", "code": "P1", "name": "Launch" }))
+            .await
+            .assert_status(StatusCode::CONFLICT);
+        server
+            .post("/api/v1/projects")
+            .json(&serde_json::json!({ "id": "P-2", "code": "P2", "name": " " }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = server
+            .patch("/api/v1/projects/P-1")
+            .json(&serde_json::json!({ "description": "Public launch" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["name"], "Launch");
+        assert_eq!(json["description"], "Public launch");
+
+        server
+            .post("/api/v1/projects/P-1/tasks")
+            .json(&serde_json::json!({ "task_id": "missing" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        let response = server.get("/api/v1/projects/P-1/summary").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["total_tasks"], 0);
+
+        let json: serde_json::Value = server.post("/api/v1/projects/P-1/archive").await.json();
+        assert_eq!(json["status"], "archived");
+        let json: serde_json::Value = server.get("/api/v1/projects").await.json();
+        assert!(json.as_array().unwrap().is_empty());
+        let json: serde_json::Value = server.get("/api/v1/projects?include_archived=true").await.json();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+
+        server.delete("/api/v1/projects/P-1").await.assert_status(StatusCode::NO_CONTENT);
+        server.get("/api/v1/projects/P-1").await.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_merge_tasks_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        server
+            .post("/api/v1/tasks/test-id/clone")
+            .json(&serde_json::json!({ "id": "duplicate" }))
+            .await
+            .assert_status(StatusCode::CREATED);
+
+        let response = server
+            .post("/api/v1/tasks/duplicate/merge")
+            .json(&serde_json::json!({ "target_id": "test-id" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["id"], "test-id");
+        let json: serde_json::Value = server.get("/api/v1/tasks/duplicate").await.json();
+        assert_eq!(json["status"], "cancelled");
+        let json: serde_json::Value = server.get("/api/v1/tasks/test-id/comments").await.json();
+        assert_eq!(json[0]["author_id"], "system");
+
+        server
+            .post("/api/v1/tasks/test-id/merge")
+            .json(&serde_json::json!({ "target_id": "test-id" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server
+            .post("/api/v1/tasks/test-id/merge")
+            .json(&serde_json::json!({ "target_id": "missing" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_split_task_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        let epic: serde_json::Value = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Epic", "context": "work", "complexity": "epic" }))
+            .await
+            .json();
+        let epic_id = epic["id"].as_str().unwrap();
+
+        let response = server
+            .post(&format!("/api/v1/tasks/{}/split", epic_id))
+            .json(&serde_json::json!({ "subtasks": [
+                { "name": "First part", "context": "work" },
+                { "name": "Second part", "context": "work" }
+            ] }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["parent"]["status"], "in_progress");
+        assert_eq!(json["subtasks"].as_array().unwrap().len(), 2);
+
+        server
+            .post("/api/v1/tasks/test-id/split")
+            .json(&serde_json::json!({ "subtasks": [{ "name": "Part", "context": "work" }] }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server
+            .post(&format!("/api/v1/tasks/{}/split", epic_id))
+            .json(&serde_json::json!({ "subtasks": [] }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_if_match_rejects_stale_writes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/test-id").await;
+        assert_eq!(response.header("etag"), "\"v0\"");
+
+        server
+            .put("/api/v1/tasks/test-id")
+            .add_header("if-match".parse().unwrap(), "\"v0\"".parse().unwrap())
+            .json(&serde_json::json!({ "name": "Renamed" }))
+            .await
+            .assert_status_ok();
+        assert_eq!(server.get("/api/v1/tasks/test-id").await.header("etag"), "\"v1\"");
+
+        // A second writer still holding v0 loses
+        let response = server
+            .put("/api/v1/tasks/test-id")
+            .add_header("if-match".parse().unwrap(), "\"v0\"".parse().unwrap())
+            .json(&serde_json::json!({ "name": "Overwritten" }))
+            .await;
+        response.assert_status(StatusCode::PRECONDITION_FAILED);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["error"], "PRECONDITION_FAILED");
+
+        server
+            .post("/api/v1/tasks/test-id/status")
+            .add_header("if-match".parse().unwrap(), "W/\"v0\"".parse().unwrap())
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+        server
+            .post("/api/v1/tasks/test-id/status")
+            .add_header("if-match".parse().unwrap(), "\"v1\"".parse().unwrap())
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await
+            .assert_status_ok();
+
+        server
+            .put("/api/v1/tasks/test-id")
+            .add_header("if-match".parse().unwrap(), "latest".parse().unwrap())
+            .json(&serde_json::json!({ "name": "Renamed" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_visibility_policy_restricts_private_tasks() {
+        let service: Arc<dyn crate::domain::TaskService + Send + Sync> = Arc::new(MockTaskService::new());
+        let state = AppStateBuilder::new()
+            .with_shared_domain_service(service.clone())
+            .with_authorization_policy(Arc::new(crate::auth::VisibilityPolicy::new(service)))
+            .build()
+            .await
+            .unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        // The seeded private task has no creator or assignee
+        let response = server.get("/api/v1/tasks/test-id").await;
+        response.assert_status(StatusCode::FORBIDDEN);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["error"], "FORBIDDEN");
+
+        let response = server
+            .post("/api/v1/tasks")
+            .add_header("x-user-id".parse().unwrap(), "alice".parse().unwrap())
+            .json(&serde_json::json!({ "name": "Private plans", "context": "work", "visibility": "private" }))
+            .await;
+        response.assert_status_ok();
+        let task_id = response.json::<serde_json::Value>()["id"].as_str().unwrap().to_string();
+        let path = format!("/api/v1/tasks/{}", task_id);
+
+        server
+            .get(&path)
+            .add_header("x-user-id".parse().unwrap(), "alice".parse().unwrap())
+            .await
+            .assert_status_ok();
+        let json: serde_json::Value = server
+            .get("/api/v1/tasks")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .await
+            .json();
+        assert!(json["tasks"].as_array().unwrap().is_empty());
+        server
+            .delete(&path)
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        server
+            .delete(&path)
+            .add_header("x-user-id".parse().unwrap(), "alice".parse().unwrap())
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_visibility_policy_covers_bulk_and_lookup_routes() {
+        let service: Arc<dyn crate::domain::TaskService + Send + Sync> = Arc::new(MockTaskService::new());
+        let state = AppStateBuilder::new()
+            .with_shared_domain_service(service.clone())
+            .with_authorization_policy(Arc::new(crate::auth::VisibilityPolicy::new(service)))
+            .build()
+            .await
+            .unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        // The seeded private task is hidden from bob, counts included
+        let json: serde_json::Value = server
+            .get("/api/v1/tasks?include_count=true")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .await
+            .json();
+        assert_eq!(json["total_count"], 0);
+
+        let json: serde_json::Value = server
+            .post("/api/v1/tasks/search")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .json(&serde_json::json!({ "query": {"field": {"name": "id", "op": "eq", "value": "test-id"}} }))
+            .await
+            .json();
+        assert!(json["items"].as_array().unwrap().is_empty());
+        assert_eq!(json["total"], 0);
+
+        let response = server
+            .post("/api/v1/tasks/bulk-delete")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .json(&serde_json::json!({ "task_ids": ["test-id"] }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["deleted"].as_array().unwrap().is_empty());
+        assert_eq!(json["failed"][0][0], "test-id");
+
+        server
+            .post("/api/v1/tasks/test-id/tags")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .json(&serde_json::json!({ "tags": ["urgent"] }))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        server
+            .get("/api/v1/tasks/test-id/subtasks")
+            .add_header("x-user-id".parse().unwrap(), "bob".parse().unwrap())
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_priority_churn_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/analytics/priority-churn").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json.as_array().unwrap().is_empty());
+
+        for priority in ["high", "low", "high"] {
+            server
+                .put("/api/v1/tasks/test-id")
+                .json(&serde_json::json!({ "priority": priority }))
+                .await
+                .assert_status_ok();
+        }
+
+        let response = server.get("/api/v1/analytics/priority-churn").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["id"], "test-id");
+        assert_eq!(json[0]["priority_change_count"], 3);
+        assert!(json[0]["last_priority_change_at"].is_string());
+
+        let response = server.get("/api/v1/analytics/priority-churn?priority_changed_more_than=3").await;
+        let json: serde_json::Value = response.json();
+        assert!(json.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_focus_score_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/users/user-1/focus-score?days=14").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["user_id"], "user-1");
+        assert_eq!(json["period_days"], 14);
+        assert_eq!(json["completed_tasks"], 0);
+        assert_eq!(json["focus_score"], 1.0);
+        assert_eq!(json["dominant_context"], "work");
+
+        server.get("/api/v1/users/user-1/focus-score?days=0").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_review_queue_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        for status in ["ready", "in_progress", "review"] {
+            server
+                .post("/api/v1/tasks/test-id/status")
+                .json(&serde_json::json!({ "new_status": status }))
+                .await
+                .assert_status_ok();
+        }
+        server
+            .post("/api/v1/tasks/test-id/review-requests")
+            .json(&serde_json::json!({ "reviewer_id": "user-2" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get("/api/v1/tasks/review-queue?reviewer_id=user-2").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["task"]["id"], "test-id");
+        assert_eq!(json[0]["is_stale_review"], false);
+
+        let response = server.get("/api/v1/tasks/review-queue?reviewer_id=user-3").await;
+        assert!(response.json::<serde_json::Value>().as_array().unwrap().is_empty());
+
+        server
+            .post("/api/v1/tasks/missing/review-requests")
+            .json(&serde_json::json!({ "reviewer_id": "user-2" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_project_execution_plan_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/P-1/execution-plan").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["waves"][0]["wave_number"], 1);
+        assert_eq!(json["waves"][0]["tasks"][0]["id"], "test-id");
+        assert_eq!(json["waves"][0]["can_parallelize"], false);
+        assert_eq!(json["total_sequential_days"], 0.5);
+        assert_eq!(json["total_parallel_days"], 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_daily_standup_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .post("/api/v1/tasks/test-id/status")
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get("/api/v1/users/user-1/standup?date=2025-06-15").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["date"], "2025-06-15");
+        assert_eq!(json["planned_today"][0]["id"], "test-id");
+        assert!(json["blockers"].as_array().unwrap().is_empty());
+        assert!(json["formatted_text"].as_str().unwrap().contains("- Planned: Test Task (test-id)"));
+
+        server.get("/api/v1/users/user-1/standup?date=not-a-date").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_task_order_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .post("/api/v1/tasks/test-id/status")
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get("/api/v1/users/user-1/task-order").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["task"]["id"], "test-id");
+        assert_eq!(json[0]["rank"], 1);
+        assert!(!json[0]["reasoning"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_project_centrality_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/PROJ1/centrality").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["task_id"], "test-id");
+        assert_eq!(json[0]["betweenness_centrality"], 0.0);
+        assert_eq!(json[0]["is_high_risk"], false);
+    }
+
+    #[tokio::test]
+    async fn test_velocity_forecast_requires_two_sprints() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // The only task is open, so no sprint has completed work
+        let response = server.get("/api/v1/projects/PROJ1/velocity-forecast").await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        let json: serde_json::Value = response.json();
+        assert!(json["message"].as_str().unwrap().contains("Insufficient data"));
+    }
+
+    #[tokio::test]
+    async fn test_project_snapshot_routes() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .post("/api/v1/projects/PROJ1/clone")
+            .json(&serde_json::json!({
+                "new_project_id": "PROJ2",
+                "new_project_name": "Project two",
+                "copy_tasks": false,
+                "copy_assignments": false,
+                "reset_statuses": true
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+
+        let response = server
+            .post("/api/v1/projects/PROJ2/snapshots")
+            .json(&serde_json::json!({ "label": "Sprint 1 start" }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["label"], "Sprint 1 start");
+        assert_eq!(json["metrics"]["total_tasks"], 0.0);
+        let snapshot_id = json["id"].as_str().unwrap().to_string();
+
+        let response = server.get(&format!("/api/v1/projects/PROJ2/snapshots/{}/diff", snapshot_id)).await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["snapshot_id"], snapshot_id.as_str());
+        assert!(json["deltas"].as_array().unwrap().iter().all(|d| d["delta"] == 0.0));
+
+        server
+            .get(&format!("/api/v1/projects/PROJ1/snapshots/{}/diff", snapshot_id))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        server
+            .post("/api/v1/projects/PROJ2/snapshots")
+            .json(&serde_json::json!({ "label": " " }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_project_ownership_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .post("/api/v1/projects")
+            .json(&serde_json::json!({ "id": "PROJ2", "code": "P2", "name": "Project two" }))
+            .await
+            .assert_status(StatusCode::CREATED);
+
+        let response = server
+            .put("/api/v1/projects/PROJ2/ownership")
+            .json(&serde_json::json!({ "new_owner_id": "user-2", "reason": "Team change" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["project"]["project_lead_user_id"], "user-2");
+        assert!(json["previous_owner_id"].is_null());
+
+        server
+            .put("/api/v1/projects/PROJ2/ownership")
+            .json(&serde_json::json!({ "new_owner_id": "user-3", "reason": "" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server
+            .put("/api/v1/projects/UNKNOWN/ownership")
+            .json(&serde_json::json!({ "new_owner_id": "user-2", "reason": "Team change" }))
+            .await
+            .assert_status_not_ok();
+    }
+
+    #[tokio::test]
+    async fn test_wip_violations_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/PROJ1/wip-violations").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<serde_json::Value>(), serde_json::json!([]));
+
+        server
+            .put("/api/v1/projects/PROJ1/workflow-config")
+            .json(&serde_json::json!({ "wip_limits": { "backlog": 1 } }))
+            .await
+            .assert_status_ok();
+        server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Second", "context": "work", "priority": "wish" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get("/api/v1/projects/PROJ1/wip-violations").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json[0]["status"], "backlog");
+        assert_eq!(json[0]["overflow"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_bottleneck_resolution_routes() {
+        use crate::domain::{Bottleneck, BottleneckType, TaskService};
+
+        let service = MockTaskService::new();
+        service.track_bottleneck("P-1", &Bottleneck {
+            bottleneck_id: "B-1".to_string(),
+            bottleneck_type: BottleneckType::SinglePersonDependency,
+            affected_tasks: vec!["test-id".to_string()],
+            severity: 0.8,
+            estimated_delay: chrono::Duration::days(2),
+            suggested_actions: vec!["Redistribute tasks".to_string(), "Pair on reviews".to_string()],
+        }).await.unwrap();
+        let state = AppState {
+            config: Arc::new(TaskServiceConfig::default()),
+            domain_service: Arc::new(service),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
+            event_service: Arc::new(EventService::new().await.unwrap()),
+            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
+        };
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let json: serde_json::Value = server
+            .post("/api/v1/analytics/bottlenecks/B-1/actions/0/resolve")
+            .json(&serde_json::json!({ "resolved_by": "alice" }))
+            .await
+            .json();
+        assert_eq!(json["completion_percentage"], 50.0);
+
+        // Already resolved and unknown actions are rejected
+        server.post("/api/v1/analytics/bottlenecks/B-1/actions/0/resolve").await.assert_status_not_ok();
+        server.post("/api/v1/analytics/bottlenecks/B-1/actions/9/resolve").await.assert_status_not_ok();
+
+        server.post("/api/v1/analytics/bottlenecks/B-1/actions/1/resolve").await.assert_status_ok();
+        let json: serde_json::Value = server.get("/api/v1/projects/P-1/bottleneck-resolution").await.json();
+        assert_eq!(json["bottlenecks"][0]["resolved_actions"], 2);
+        assert_eq!(json["overall_percentage"], 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_project_throughput_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/P-1/throughput?days=14").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["daily_throughput"].as_array().unwrap().len(), 14);
+        assert_eq!(json["rolling_7d_avg"], 0.0);
+
+        server.get("/api/v1/projects/P-1/throughput?days=0").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_project_burndown_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/projects/P-1/burndown?start=2024-01-01T00:00:00Z&end=2024-01-10T00:00:00Z").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["data_points"].as_array().unwrap().len(), 10);
+        assert_eq!(json["ideal_line"].as_array().unwrap().len(), 10);
+        assert_eq!(json["ideal_line"][9]["remaining_tasks"], 0);
+
+        server.get("/api/v1/projects/P-1/burndown?start=2024-01-01T00:00:00Z&end=2025-06-01T00:00:00Z").await
+            .assert_status(StatusCode::BAD_REQUEST);
+        server.get("/api/v1/projects/P-1/burndown?start=2024-01-10T00:00:00Z&end=2024-01-01T00:00:00Z").await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_similar_by_description_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let mut ids = Vec::new();
+        for description in ["Fix login redirect after password reset", "Generate monthly invoices"] {
+            let response = server
+                .post("/api/v1/tasks")
+                .json(&serde_json::json!({ "name": "Task", "description": description, "context": "work" }))
+                .await;
+            let json: serde_json::Value = response.json();
+            ids.push(json["id"].as_str().unwrap().to_string());
+        }
+
+        let response = server
+            .post("/api/v1/tasks/similar-by-description")
+            .json(&serde_json::json!({ "description": "login redirect broken" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        let matches = json.as_array().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["task"]["id"], ids[0]);
+        assert!(matches[0]["similarity"].as_f64().unwrap() > 0.0);
+
+        server
+            .post("/api/v1/tasks/similar-by-description")
+            .json(&serde_json::json!({ "description": "login", "limit": 0 }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_description_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // Tasks still in the backlog are not suggested from
+        server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Task",
+                "description": "Fix login redirect",
+                "implementation_details": "Clear the session cookie",
+                "context": "work"
+            }))
+            .await;
+
+        let response = server
+            .post("/api/v1/tasks/suggest-description")
+            .json(&serde_json::json!({ "name": "login redirect", "context": "work" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["template"].as_str().unwrap().starts_with("Problem:"));
+        assert!(json["similar_tasks"].as_array().unwrap().is_empty());
+
+        server
+            .post("/api/v1/tasks/suggest-description")
+            .json(&serde_json::json!({ "name": " ", "context": "work" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_stale_dependencies_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/analytics/stale-dependencies").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["stale_dependencies"].as_array().unwrap().is_empty());
+        assert!(json["generated_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_estimation_accuracy_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/analytics/estimation-accuracy?context=work&days=30").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["total_estimated_tasks"], 0);
+        assert!(json["by_complexity"].as_object().unwrap().is_empty());
+
+        server.get("/api/v1/analytics/estimation-accuracy?days=0").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_recurring_compliance_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/analytics/recurring-compliance?user_id=user-1").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["total_instances"], 0);
+        assert!(json["by_task"].as_array().unwrap().is_empty());
+
+        server.get("/api/v1/analytics/recurring-compliance?days=0").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_cross_project_dependencies_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/analytics/cross-project-dependencies").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parse_task_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks/parse")
+            .json(&serde_json::json!({ "input": "Fix login bug tomorrow urgent" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["parsed_request"]["name"], "Fix login bug");
+        assert_eq!(json["parsed_request"]["priority"], "critical");
+        assert!(json["parsed_request"]["due_date"].is_string());
+
+        server
+            .post("/api/v1/tasks/parse")
+            .json(&serde_json::json!({ "input": "tomorrow" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        // Parsing never creates the task
+        let response = server.get("/api/v1/tasks").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tasks"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        let tasks = serde_json::json!([
+            { "name": "Design", "context": "work" },
+            { "name": "  ", "context": "work" },
+            { "name": "Build", "context": "work" },
+        ]);
+
+        let response = server
+            .post("/api/v1/tasks/bulk")
+            .json(&serde_json::json!({ "tasks": tasks, "fail_fast": true }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["created"].as_array().unwrap().is_empty());
+        assert_eq!(json["failed"][0]["index"], 1);
+        assert_eq!(json["partial_success"], false);
+
+        let response = server.post("/api/v1/tasks/bulk").json(&serde_json::json!({ "tasks": tasks })).await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["created"].as_array().unwrap().len(), 2);
+        assert_eq!(json["failed"][0]["index"], 1);
+        assert_eq!(json["partial_success"], true);
+
+        let too_many = vec![serde_json::json!({ "name": "Task", "context": "work" }); 1001];
+        let response = server.post("/api/v1/tasks/bulk").json(&serde_json::json!({ "tasks": too_many })).await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["violations"][0]["ruleName"], "max_bulk_create_size");
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_cursor_pagination() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+        let tasks = serde_json::json!([
+            { "name": "Design", "context": "work" },
+            { "name": "Build", "context": "work" },
+        ]);
+        server.post("/api/v1/tasks/bulk").json(&serde_json::json!({ "tasks": tasks })).await.assert_status_ok();
+
+        let response = server.get("/api/v1/tasks?limit=2").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tasks"].as_array().unwrap().len(), 2);
+        assert_eq!(json["has_more"], true);
+        assert!(json.get("total_count").is_none());
+        let cursor = json["next_cursor"].as_str().unwrap().to_string();
+
+        let response = server.get(&format!("/api/v1/tasks?limit=2&cursor={}", cursor)).await;
+        let last_page: serde_json::Value = response.json();
+        assert_eq!(last_page["tasks"].as_array().unwrap().len(), 1);
+        assert_eq!(last_page["has_more"], false);
+        assert!(last_page["next_cursor"].is_null());
+        let first_ids: Vec<&serde_json::Value> = json["tasks"].as_array().unwrap().iter().map(|t| &t["id"]).collect();
+        assert!(!first_ids.contains(&&last_page["tasks"][0]["id"]));
+
+        let response = server.get("/api/v1/tasks?limit=1&include_count=true").await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tasks"].as_array().unwrap().len(), 1);
+        assert_eq!(json["total_count"], 3);
+
+        server
+            .get("/api/v1/tasks?cursor=not-a-cursor")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let mut ids = Vec::new();
+        for name in ["Design", "Build"] {
+            let response = server
+                .post("/api/v1/tasks")
+                .json(&serde_json::json!({ "name": name, "context": "work" }))
+                .await;
+            let json: serde_json::Value = response.json();
+            ids.push(json["id"].as_str().unwrap().to_string());
+        }
+        server
+            .post(&format!("/api/v1/tasks/{}/dependencies", ids[1]))
+            .json(&serde_json::json!({ "to_task_id": "test-id", "dependency_type": "blocks" }))
+            .await
+            .assert_status_ok();
+
+        let response = server
+            .post("/api/v1/tasks/bulk-delete")
+            .json(&serde_json::json!({ "task_ids": [ids[0], ids[1], "missing"] }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["deleted"], serde_json::json!([ids[0]]));
+        assert_eq!(json["failed"].as_array().unwrap().len(), 2);
+
+        let response = server
+            .post("/api/v1/tasks/bulk-delete")
+            .json(&serde_json::json!({ "task_ids": [ids[1]], "force": true }))
+            .await;
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["deleted"], serde_json::json!([ids[1]]));
+        assert_eq!(json["orphaned_dependencies_removed"], 1);
+
+        let too_many: Vec<String> = (0..501).map(|i| format!("T{}", i)).collect();
+        server
+            .post("/api/v1/tasks/bulk-delete")
+            .json(&serde_json::json!({ "task_ids": too_many }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_dependency_diff_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .get("/api/v1/tasks/test-id/dependencies/diff?since=2024-01-01T00:00:00Z")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        server
+            .get("/api/v1/tasks/test-id/dependencies/diff?since=yesterday")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_complete_missing_success_criterion_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .patch("/api/v1/tasks/test-id/criteria/0/complete")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        server
+            .patch("/api/v1/tasks/test-id/criteria/0/uncomplete")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        server
+            .patch("/api/v1/tasks/test-id/criteria/0")
+            .json(&serde_json::json!({"completed": true}))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_sorted_by_effective_priority() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks?sort_by=effective_priority&blocked_counts=true").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"][0]["effective_priority_score"].is_number());
+
+        let response = server.get("/api/v1/tasks").await;
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"][0].get("effective_priority_score").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_tasks_as_ndjson() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/export").await;
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), "application/x-ndjson");
+
+        let lines: Vec<serde_json::Value> = response.text().lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["id"], "test-id");
+    }
+
+    #[tokio::test]
+    async fn test_export_tasks_as_csv() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/export?format=csv&status=backlog").await;
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), "text/csv");
+        let disposition = response.header("content-disposition");
+        let disposition = disposition.to_str().unwrap();
+        assert!(disposition.starts_with("attachment; filename=\"tasks_"));
+        assert!(disposition.ends_with(".csv\""));
+
+        let text = response.text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("id,name,"));
+        assert!(lines[1].starts_with("test-id,"));
+
+        let response = server.get("/api/v1/tasks/export?format=csv&status=done").await;
+        assert_eq!(response.text().lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_with_overdue_subtasks() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // "test-id" has no subtasks, so it is not a parent with overdue ones
+        let response = server.get("/api/v1/tasks?has_overdue_subtasks=true").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"].as_array().unwrap().is_empty());
+
+        let response = server.get("/api/v1/tasks?has_overdue_subtasks=false").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["tasks"][0]["id"], "test-id");
+        assert_eq!(json["tasks"][0]["overdue_subtask_count"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_status_transition_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks/bulk-status-transition")
+            .json(&serde_json::json!({
+                "task_ids": ["missing", "test-id"],
+                "new_status": "ready",
+                "ignore_validation_errors": true
+            }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["transitioned"], serde_json::json!(["test-id"]));
+        assert_eq!(json["failed"][0][0], "missing");
+        assert_eq!(json["events_published"], 1);
+
+        // Without ignoring errors the batch stops at the first failure
+        let response = server
+            .post("/api/v1/tasks/bulk-status-transition")
+            .json(&serde_json::json!({
+                "task_ids": ["missing", "test-id"],
+                "new_status": "in_progress"
+            }))
+            .await;
+        let json: serde_json::Value = response.json();
+        assert!(json["transitioned"].as_array().unwrap().is_empty());
+        assert_eq!(json["events_published"], 0);
+
+        let task_ids: Vec<String> = (0..201).map(|i| format!("T{}", i)).collect();
+        let response = server
+            .post("/api/v1/tasks/bulk-status-transition")
+            .json(&serde_json::json!({ "task_ids": task_ids, "new_status": "ready" }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_batch_status_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        // Backlog -> Done breaks the transition rules
+        let response = server
+            .post("/api/v1/tasks/bulk-status")
+            .json(&serde_json::json!({ "task_ids": ["missing", "test-id"], "new_status": "done" }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["succeeded"].as_array().unwrap().is_empty());
+        assert_eq!(json["failed"].as_array().unwrap().len(), 2);
+
+        let response = server
+            .post("/api/v1/tasks/bulk-status")
+            .json(&serde_json::json!({ "task_ids": ["test-id"], "new_status": "done", "skip_validation": true }))
+            .await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["succeeded"], serde_json::json!(["test-id"]));
+        let json: serde_json::Value = server.get("/api/v1/tasks/test-id").await.json();
+        assert_eq!(json["status"], "done");
+    }
+
+    #[tokio::test]
+    async fn test_custom_property_json_schema() {
+        let mut config = TaskServiceConfig::default();
+        config.custom_property_schemas = vec![crate::domain::CustomPropertySchema {
+            context: crate::domain::TaskContext::Work,
+            schema: serde_json::json!({
+                "type": "object",
+                "required": ["ticket_number"],
+                "properties": { "ticket_number": { "type": "string" } },
+            }),
+        }];
+        let state = AppState {
+            config: Arc::new(config),
+            domain_service: Arc::new(MockTaskService::new()),
+            project_service: Arc::new(crate::domain::MockProjectService::new()),
+            event_service: Arc::new(EventService::new().await.unwrap()),
+            logger: Arc::new(tyl_logging::loggers::console::ConsoleLogger::new()),
+            tracer: Arc::new(crate::tracing::NoOpTracingService),
+            db_health: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+            db_circuit_breaker: Arc::new(crate::adapters::CircuitBreaker::new(Default::default(), Arc::new(std::sync::atomic::AtomicBool::new(true)))),
+            description_index: Arc::new(std::sync::RwLock::new(crate::domain::TfIdfIndex::new())),
+            plugins: Vec::new(),
+            authorization_policy: Arc::new(crate::auth::PermissivePolicy),
+            webhook_delivery: None,
+            attachment_service: None,
+            archival_service: None,
+            event_store: None,
+            task_scheduler: Arc::new(crate::domain::TaskScheduler::new(Arc::new(EventService::new().await.unwrap()))),
+        };
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let json: serde_json::Value = server.get("/api/v1/schemas/custom-properties/work").await.json();
+        assert_eq!(json["required"][0], "ticket_number");
+        server.get("/api/v1/schemas/custom-properties/personal").await.assert_status(StatusCode::NOT_FOUND);
+
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Fix login", "context": "work" }))
+            .await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["violations"][0]["ruleName"], "custom_property_schema");
+
+        server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Fix login",
+                "context": "work",
+                "custom_properties": { "ticket_number": "OPS-1", "team": "core" }
+            }))
+            .await
+            .assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_context_schema_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/schemas/context/work").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["context"], "work");
+        assert_eq!(json["optional_custom_fields"][0]["name"], "ticket_number");
+
+        let response = server.get("/api/v1/schemas/context/unknown").await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        // Task creation honours the schema types
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": "Fix login",
+                "context": "work",
+                "custom_properties": { "ticket_number": 42 }
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_task_history_route() {
+        let server = TestServer::new(create_test_app().await).unwrap();
+        server.get("/api/v1/tasks/test-id/history").await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        let event_store = Arc::new(crate::events::InMemoryTaskEventStore::new());
+        let domain_service = crate::domain::TaskDomainService::new(crate::adapters::InMemoryTaskRepository::new())
+            .with_event_store(event_store.clone());
+        let state = AppStateBuilder::new()
+            .with_domain_service(domain_service)
+            .with_event_store(event_store)
+            .build()
+            .await
+            .unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({ "name": "Write docs", "context": "personal" }))
+            .await;
+        response.assert_status_ok();
+        let task_id = response.json::<serde_json::Value>()["id"].as_str().unwrap().to_string();
+        server
+            .post(&format!("/api/v1/tasks/{}/status", task_id))
+            .json(&serde_json::json!({ "new_status": "ready" }))
+            .await
+            .assert_status_ok();
+
+        let response = server.get(&format!("/api/v1/tasks/{}/history", task_id)).await;
+        response.assert_status_ok();
+        let events: serde_json::Value = response.json();
+        assert_eq!(events[0]["event_type"], "task.created");
+        assert_eq!(events[1]["event_type"], "task.status_changed");
+        assert_eq!(events[1]["sequence"], 2);
+
+        let response = server.get(&format!("/api/v1/tasks/{}/history?up_to_sequence=1", task_id)).await;
+        assert_eq!(response.json::<serde_json::Value>().as_array().unwrap().len(), 1);
+        server.get("/api/v1/tasks/missing/history").await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_search_text_route() {
+        let state = AppStateBuilder::new()
+            .with_domain_service(crate::domain::TaskDomainService::new(crate::adapters::InMemoryTaskRepository::new()))
+            .build()
+            .await
+            .unwrap();
+        let server = TestServer::new(create_router().with_state(state)).unwrap();
+
+        for task in [
+            serde_json::json!({ "name": "Fix Login form", "context": "work" }),
+            serde_json::json!({ "name": "Audit", "context": "work", "description": "Check the LOGIN rate limits" }),
+            serde_json::json!({ "name": "Write docs", "context": "work" }),
+        ] {
+            server.post("/api/v1/tasks").json(&task).await.assert_status_ok();
+        }
+
+        let response = server.get("/api/v1/tasks?q=login").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        let mut names: Vec<&str> = json["tasks"].as_array().unwrap().iter()
+            .map(|task| task["name"].as_str().unwrap())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["Audit", "Fix Login form"]);
+
+        let response = server.get("/api/v1/tasks?q=l").await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["errors"][0]["field"], "q");
+        assert_eq!(json["errors"][0]["code"], "too_short");
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_are_problem_details() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/api/v1/tasks")
+            .json(&serde_json::json!({
+                "name": " ",
+                "context": "work",
+                "description": "a".repeat(5001)
+            }))
+            .await;
+        response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.header("content-type"), "application/problem+json");
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["status"], 422);
+        assert_eq!(json["type"], crate::handlers::api::VALIDATION_PROBLEM_TYPE);
+        let errors = json["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["field"], "name");
+        assert_eq!(errors[0]["code"], "required");
+        assert_eq!(errors[1]["field"], "description");
+        assert_eq!(errors[1]["params"]["max"], 5000);
+    }
+
+    #[tokio::test]
+    async fn test_task_graph_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/test-id/graph").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["nodes"][0]["id"], "test-id");
+        assert!(json["edges"].as_array().unwrap().is_empty());
+
+        server.get("/api/v1/tasks/test-id/graph?depth=0").await.assert_status(StatusCode::BAD_REQUEST);
+        server.get("/api/v1/tasks/test-id/graph?depth=11").await.assert_status(StatusCode::BAD_REQUEST);
+        server.get("/api/v1/tasks/missing/graph").await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_tasks_due_soon_route() {
+        let app = create_test_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/tasks/due-soon?hours=48&group_by=assignee").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert!(json["tasks"].as_array().unwrap().is_empty());
+        assert!(json["earliest_due"].is_null());
+
+        server.get("/api/v1/tasks/due-soon?hours=0").await.assert_status(StatusCode::BAD_REQUEST);
+        server.get("/api/v1/tasks/due-soon?group_by=project").await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    async fn create_openapi_app(config: TaskServiceConfig) -> Router {
+        let state = AppStateBuilder::new()
+            .with_config(config.clone())
+            .with_domain_service(MockTaskService::new())
+            .build()
+            .await
+            .unwrap();
+
+        openapi_routes(&config).with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_openapi_route() {
+        let mut config = TaskServiceConfig::default();
+        config.version = "2.3.0".to_string();
+        config.contact.email = Some("tasks@example.com".to_string());
+        let server = TestServer::new(create_openapi_app(config).await).unwrap();
+
+        let response = server.get("/openapi.json").await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["openapi"], "3.1.0");
+        assert_eq!(json["info"]["version"], "2.3.0");
+        assert_eq!(json["info"]["contact"]["email"], "tasks@example.com");
+        assert!(json["paths"]["/api/v1/tasks/{id}"]["get"].is_object());
+        assert!(json["paths"]["/api/v1/{context}/tasks"]["post"].is_object());
+
+        // Swagger UI is off by default
+        server.get("/docs/").await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_docs_route() {
+        let mut config = TaskServiceConfig::default();
+        config.api.docs_enabled = true;
+        let server = TestServer::new(create_openapi_app(config).await).unwrap();
+
+        let response = server.get("/docs/").await;
+        response.assert_status_ok();
+        assert!(response.text().contains("swagger"));
+        server.get("/openapi.json").await.assert_status_ok();
+    }
+
+    #[test]
+    fn test_openapi_spec_references_resolve() {
+        fn collect_references<'a>(value: &'a serde_json::Value, found: &mut Vec<&'a str>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(reference)) = map.get("$ref") {
+                        found.push(reference);
+                    }
+                    map.values().for_each(|value| collect_references(value, found));
+                }
+                serde_json::Value::Array(values) => values.iter().for_each(|value| collect_references(value, found)),
+                _ => {}
+            }
+        }
+
+        let spec = serde_json::to_value(openapi_spec(&TaskServiceConfig::default())).unwrap();
+        assert!(spec["info"].get("contact").is_none());
+
+        let mut references = Vec::new();
+        collect_references(&spec, &mut references);
+        assert!(!references.is_empty());
+        for reference in references {
+            let name = reference.strip_prefix("#/components/schemas/").unwrap();
+            assert!(spec["components"]["schemas"][name].is_object(), "unresolved reference {}", reference);
+        }
+
+        // Operation ids are unique, also across the generic and contextual task handlers
+        let mut operation_ids: Vec<&str> = spec["paths"].as_object().unwrap().values()
+            .flat_map(|item| item.as_object().unwrap().values())
+            .filter_map(|operation| operation["operationId"].as_str())
+            .collect();
+        let operation_count = operation_ids.len();
+        operation_ids.sort_unstable();
+        operation_ids.dedup();
+        assert_eq!(operation_ids.len(), operation_count);
+    }
 }
\ No newline at end of file
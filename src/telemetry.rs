@@ -0,0 +1,129 @@
+//! Prometheus metrics
+//!
+//! Metrics are recorded through the `metrics` facade with the helpers below.
+//! [`prometheus_handle`] installs the Prometheus recorder on first use; until
+//! then recording is a no-op. `GET /metrics` renders what was recorded.
+
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use serde::Serialize;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::adapters::CircuitState;
+use crate::domain::{TaskContext, TaskPriority, TaskStatus};
+
+/// Buckets of the `_seconds` histograms, from a cached lookup to a slow export
+const DURATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Cypher clauses that change the graph
+const WRITE_CLAUSES: &[&str] = &["CREATE", "MERGE", "SET", "DELETE", "REMOVE"];
+
+static PROMETHEUS: OnceLock<Option<PrometheusHandle>> = OnceLock::new();
+
+/// Handle rendering the recorded metrics, installing the recorder on first use
+///
+/// `None` when another global recorder was installed first, e.g. by an
+/// application embedding the service.
+pub fn prometheus_handle() -> Option<PrometheusHandle> {
+    PROMETHEUS
+        .get_or_init(|| {
+            let builder = PrometheusBuilder::new()
+                .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), DURATION_BUCKETS)
+                .ok()?;
+            match builder.install_recorder() {
+                Ok(handle) => Some(handle),
+                Err(e) => {
+                    tracing::warn!("Prometheus metrics are unavailable: {}", e);
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+pub fn record_task_created(context: &TaskContext, priority: &TaskPriority) {
+    counter!("task_created_total", "context" => label(context), "priority" => label(priority)).increment(1);
+}
+
+pub fn record_status_transition(from: &TaskStatus, to: &TaskStatus) {
+    counter!("task_status_transitions_total", "from_status" => label(from), "to_status" => label(to)).increment(1);
+}
+
+pub fn record_dependency_added() {
+    counter!("task_dependency_added_total").increment(1);
+}
+
+/// Record a served request; `path` is the route pattern, not the concrete URI
+pub fn record_http_request(method: &str, path: &str, status: u16, duration: Duration) {
+    histogram!(
+        "http_request_duration_seconds",
+        "method" => method.to_string(),
+        "path" => path.to_string(),
+        "status" => status.to_string(),
+    )
+    .record(duration.as_secs_f64());
+}
+
+/// Await a graph query, recording how long it took
+pub async fn time_graph_query<T>(query_type: &'static str, query: impl Future<Output = T>) -> T {
+    let started = Instant::now();
+    let result = query.await;
+    histogram!("graph_query_duration_seconds", "query_type" => query_type).record(started.elapsed().as_secs_f64());
+    result
+}
+
+/// `write` for Cypher that changes the graph, `read` for everything else
+pub fn cypher_query_type(query: &str) -> &'static str {
+    if query.split_whitespace().any(|word| WRITE_CLAUSES.contains(&word)) {
+        "write"
+    } else {
+        "read"
+    }
+}
+
+/// 0 while the database circuit breaker is closed, 1 while half-open, 2 while open
+pub fn record_db_circuit_state(state: CircuitState) {
+    gauge!("db_circuit_breaker_state").set(state.gauge_value());
+}
+
+pub fn record_event_published(topic: &str) {
+    counter!("event_published_total", "topic" => topic.to_string()).increment(1);
+}
+
+pub fn record_event_publish_failure() {
+    counter!("event_publish_failures_total").increment(1);
+}
+
+/// Events being published right now, out of the `event_channel_capacity` allowed at once
+pub fn record_event_channel_depth(depth: usize, capacity: usize) {
+    gauge!("event_channel_current_depth").set(depth as f64);
+    gauge!("event_channel_capacity").set(capacity as f64);
+}
+
+/// Label value of an enum, as it is spelled in the API
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(label)) => label,
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cypher_query_type() {
+        assert_eq!(cypher_query_type("MATCH (t:Task {id: 'T1'}) RETURN t"), "read");
+        assert_eq!(cypher_query_type("MATCH (t:Task {id: 'T1'}) DETACH DELETE t"), "write");
+        assert_eq!(cypher_query_type("UNWIND ['T1'] AS id MATCH (t:Task {id: id}) SET t.status = 'done'"), "write");
+    }
+
+    #[test]
+    fn test_labels_use_api_spelling() {
+        assert_eq!(label(&TaskStatus::InProgress), "in_progress");
+        assert_eq!(label(&TaskContext::Work), "work");
+    }
+}
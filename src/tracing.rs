@@ -0,0 +1,213 @@
+//! Spans of handler work
+//!
+//! Handlers start, annotate and end spans through [`TracingService`].
+//! [`OtelTracingService`] exports them over OTLP to `tracing.exporter_url`;
+//! [`NoOpTracingService`] drops them, for tests and when exporting is off.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use opentelemetry::trace::{Span as _, TraceContextExt, Tracer as _, TracerProvider as _};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{BatchConfigBuilder, Sampler, Span, Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use tyl_errors::{TylError, TylResult};
+
+use crate::config::TracingConfig;
+
+/// Start, annotate and end spans identified by the id `start_span` returns
+pub trait TracingService {
+    /// Start a span, as a child of the open span `parent` when given
+    fn start_span(&self, name: &str, parent: Option<&str>) -> TylResult<String>;
+    fn add_span_attribute(&self, span_id: &str, key: &str, value: serde_json::Value) -> TylResult<()>;
+    fn end_span(&self, span_id: &str) -> TylResult<()>;
+}
+
+/// Spans exported over OTLP by a batching exporter
+pub struct OtelTracingService {
+    provider: TracerProvider,
+    tracer: Tracer,
+    /// Spans started and not yet ended, by span id
+    open_spans: Mutex<HashMap<String, Span>>,
+}
+
+impl OtelTracingService {
+    /// Export to `config.exporter_url`, keeping `sampling_rate` of the traces
+    /// and queueing at most `max_queued_spans` ended spans for export
+    ///
+    /// Must be called within a Tokio runtime, which runs the exporter.
+    pub fn new(config: &TracingConfig, sampling_rate: f64, max_queued_spans: usize) -> TylResult<Self> {
+        let exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(config.exporter_url.clone());
+        let trace_config = opentelemetry_sdk::trace::Config::default()
+            .with_sampler(Sampler::TraceIdRatioBased(sampling_rate))
+            .with_resource(Resource::new(vec![KeyValue::new("service.name", config.service_name.clone())]));
+        let provider = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(exporter)
+            .with_trace_config(trace_config)
+            .with_batch_config(BatchConfigBuilder::default().with_max_queue_size(max_queued_spans).build())
+            .install_batch(opentelemetry_sdk::runtime::Tokio)
+            .map_err(|e| TylError::configuration(format!("Cannot export traces to '{}': {}", config.exporter_url, e)))?;
+        let tracer = provider.tracer(config.service_name.clone());
+
+        Ok(Self { provider, tracer, open_spans: Mutex::new(HashMap::new()) })
+    }
+}
+
+impl TracingService for OtelTracingService {
+    fn start_span(&self, name: &str, parent: Option<&str>) -> TylResult<String> {
+        let mut open_spans = self.open_spans.lock().unwrap();
+        let parent_context = match parent {
+            Some(parent_id) => {
+                let parent = open_spans.get(parent_id).ok_or_else(|| TylError::not_found("span", parent_id))?;
+                Context::new().with_remote_span_context(parent.span_context().clone())
+            }
+            None => Context::new(),
+        };
+        let span = self.tracer.start_with_context(name.to_string(), &parent_context);
+        let span_id = span.span_context().span_id().to_string();
+        open_spans.insert(span_id.clone(), span);
+        Ok(span_id)
+    }
+
+    fn add_span_attribute(&self, span_id: &str, key: &str, value: serde_json::Value) -> TylResult<()> {
+        let mut open_spans = self.open_spans.lock().unwrap();
+        let span = open_spans.get_mut(span_id).ok_or_else(|| TylError::not_found("span", span_id))?;
+        span.set_attribute(KeyValue::new(key.to_string(), attribute_value(value)));
+        Ok(())
+    }
+
+    fn end_span(&self, span_id: &str) -> TylResult<()> {
+        let mut span = self.open_spans.lock().unwrap().remove(span_id)
+            .ok_or_else(|| TylError::not_found("span", span_id))?;
+        span.end();
+        Ok(())
+    }
+}
+
+impl Drop for OtelTracingService {
+    fn drop(&mut self) {
+        // Hand the spans still queued to the exporter before it stops
+        for result in self.provider.force_flush() {
+            if let Err(e) = result {
+                tracing::warn!("Failed to flush spans: {}", e);
+            }
+        }
+    }
+}
+
+/// OpenTelemetry attribute holding a JSON value; arrays and objects are kept as JSON text
+fn attribute_value(value: serde_json::Value) -> opentelemetry::Value {
+    match value {
+        serde_json::Value::Bool(value) => value.into(),
+        serde_json::Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(value), _) => value.into(),
+            (None, Some(value)) => value.into(),
+            (None, None) => number.to_string().into(),
+        },
+        serde_json::Value::String(value) => value.into(),
+        other => other.to_string().into(),
+    }
+}
+
+/// Tracing service that records nothing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpTracingService;
+
+impl TracingService for NoOpTracingService {
+    fn start_span(&self, _name: &str, _parent: Option<&str>) -> TylResult<String> {
+        Ok(uuid::Uuid::new_v4().to_string())
+    }
+
+    fn add_span_attribute(&self, _span_id: &str, _key: &str, _value: serde_json::Value) -> TylResult<()> {
+        Ok(())
+    }
+
+    fn end_span(&self, _span_id: &str) -> TylResult<()> {
+        Ok(())
+    }
+}
+
+/// Span that ends when dropped, so every return from a handler ends it
+pub struct SpanGuard {
+    tracer: Arc<dyn TracingService + Send + Sync>,
+    span_id: String,
+}
+
+impl SpanGuard {
+    pub fn start(tracer: &Arc<dyn TracingService + Send + Sync>, name: &str) -> TylResult<Self> {
+        let span_id = tracer.start_span(name, None)?;
+        Ok(Self { tracer: tracer.clone(), span_id })
+    }
+
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+
+    pub fn add_attribute(&self, key: &str, value: serde_json::Value) -> TylResult<()> {
+        self.tracer.add_span_attribute(&self.span_id, key, value)
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.tracer.end_span(&self.span_id) {
+            tracing::warn!("Failed to end span {}: {}", self.span_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the spans started and ended
+    #[derive(Default)]
+    struct RecordingTracer {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl TracingService for RecordingTracer {
+        fn start_span(&self, name: &str, _parent: Option<&str>) -> TylResult<String> {
+            self.events.lock().unwrap().push(format!("start {}", name));
+            Ok(name.to_string())
+        }
+
+        fn add_span_attribute(&self, span_id: &str, key: &str, value: serde_json::Value) -> TylResult<()> {
+            self.events.lock().unwrap().push(format!("{} {}={}", span_id, key, value));
+            Ok(())
+        }
+
+        fn end_span(&self, span_id: &str) -> TylResult<()> {
+            self.events.lock().unwrap().push(format!("end {}", span_id));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_span_guard_ends_span_when_dropped() {
+        let recorder = Arc::new(RecordingTracer::default());
+        let tracer: Arc<dyn TracingService + Send + Sync> = recorder.clone();
+        {
+            let span = SpanGuard::start(&tracer, "update_task").unwrap();
+            span.add_attribute("task_id", serde_json::json!("T1")).unwrap();
+        }
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec!["start update_task", "update_task task_id=\"T1\"", "end update_task"]
+        );
+    }
+
+    #[test]
+    fn test_attribute_values_keep_json_scalars() {
+        assert_eq!(attribute_value(serde_json::json!(3)), opentelemetry::Value::I64(3));
+        assert_eq!(attribute_value(serde_json::json!(0.5)), opentelemetry::Value::F64(0.5));
+        assert_eq!(attribute_value(serde_json::json!(true)), opentelemetry::Value::Bool(true));
+        assert_eq!(attribute_value(serde_json::json!("T1")), opentelemetry::Value::from("T1"));
+        assert_eq!(attribute_value(serde_json::json!(["a"])), opentelemetry::Value::from("[\"a\"]"));
+    }
+}
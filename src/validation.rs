@@ -4,8 +4,13 @@
 //! to ensure data integrity and proper error handling.
 
 use crate::{TaskServiceError, TaskServiceResult};
-use crate::domain::{CreateTaskRequest, UpdateTaskRequest, TaskStatus, TaskPriority, TaskComplexity};
+use crate::domain::{
+    CreateTaskRequest, UpdateTaskRequest, TaskStatus, TaskPriority, TaskComplexity, TaskContext,
+    ContextSchema, validate_success_criteria_format, recurrence::validate_recurrence,
+};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::collections::HashMap;
 
 /// Maximum allowed length for task names
@@ -23,145 +28,224 @@ const MAX_SUCCESS_CRITERIA: usize = 20;
 /// Maximum allowed number of custom properties
 const MAX_CUSTOM_PROPERTIES: usize = 50;
 
+/// Maximum allowed length of a comment body, in characters
+pub const MAX_COMMENT_BODY_LENGTH: usize = 10_000;
+
+/// Minimum length of the `q` text filter, shorter text would scan the whole graph
+pub const MIN_SEARCH_TEXT_LENGTH: usize = 2;
+
+/// A single field that failed validation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    /// Path of the offending field, e.g. `success_criteria[0].criterion`
+    pub field: String,
+    /// Machine readable reason such as `required` or `too_long`
+    pub code: String,
+    pub message: String,
+    /// Values the field was checked against, such as the maximum length
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+            params: HashMap::new(),
+        }
+    }
+    
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl From<FieldError> for TaskServiceError {
+    fn from(error: FieldError) -> Self {
+        TaskServiceError::InvalidInput {
+            field: error.field,
+            message: error.message,
+        }
+    }
+}
+
+/// Join the messages of failed fields into one line
+pub fn describe_field_errors(errors: &[FieldError]) -> String {
+    errors.iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Turn the collected field errors into the result of a validation
+fn into_result(errors: Vec<FieldError>) -> TaskServiceResult<()> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(TaskServiceError::ValidationFailed { errors })
+    }
+}
+
 /// Trait for request validation
+///
+/// Every field is checked and all failures are reported together in
+/// [`TaskServiceError::ValidationFailed`].
 pub trait Validate {
     fn validate(&self) -> TaskServiceResult<()>;
 }
 
 impl Validate for CreateTaskRequest {
     fn validate(&self) -> TaskServiceResult<()> {
-        // Validate task ID
-        validate_task_id(&self.id)?;
+        let mut errors = Vec::new();
         
-        // Validate task name
-        validate_task_name(&self.name)?;
+        errors.extend(validate_task_id(&self.id).err());
+        errors.extend(validate_task_name(&self.name).err());
         
-        // Validate description
         if let Some(ref description) = self.description {
-            validate_description(description)?;
+            errors.extend(validate_description(description).err());
         }
         
-        // Validate dates
-        validate_dates(self.due_date, self.estimated_date)?;
+        errors.extend(validate_dates(self.due_date, self.estimated_date));
         
-        // Validate success criteria
-        validate_success_criteria(&self.success_criteria)?;
+        // Poorly worded criteria only produce warnings
+        errors.extend(validate_success_criteria(&self.success_criteria));
+        for warning in validate_success_criteria_format(&self.success_criteria) {
+            tracing::warn!(task_id = %self.id, rule = %warning.rule_name, "{}", warning.message);
+        }
         
-        // Validate implementation details
         if let Some(ref details) = self.implementation_details {
-            validate_implementation_details(details)?;
+            errors.extend(validate_implementation_details(details).err());
         }
         
-        // Validate custom properties
-        validate_custom_properties(&self.custom_properties)?;
+        if let Some(ref recurrence) = self.recurrence {
+            if let Err(e) = validate_recurrence(recurrence) {
+                errors.push(FieldError::new("recurrence", "invalid_recurrence", e.to_string()));
+            }
+        }
+        
+        errors.extend(validate_custom_properties(&self.custom_properties));
+        errors.extend(context_custom_property_errors(&ContextSchema::defaults(), self.context, &self.custom_properties));
         
-        // Validate user and project IDs
         if let Some(ref user_id) = self.assigned_user_id {
-            validate_user_id(user_id)?;
+            errors.extend(validate_user_id(user_id).err());
         }
         
         if let Some(ref project_id) = self.project_id {
-            validate_project_id(project_id)?;
+            errors.extend(validate_project_id(project_id).err());
         }
         
-        Ok(())
+        into_result(errors)
     }
 }
 
 impl Validate for UpdateTaskRequest {
     fn validate(&self) -> TaskServiceResult<()> {
-        // Validate name if provided
+        let mut errors = Vec::new();
+        
         if let Some(ref name) = self.name {
-            validate_task_name(name)?;
+            errors.extend(validate_task_name(name).err());
         }
         
-        // Validate description if provided
         if let Some(ref description) = self.description {
-            validate_description(description)?;
+            errors.extend(validate_description(description).err());
         }
         
-        // Validate dates if provided
-        validate_dates(self.due_date, self.estimated_date)?;
+        errors.extend(validate_dates(self.due_date, self.estimated_date));
         
-        // Validate success criteria if provided
         if let Some(ref criteria) = self.success_criteria {
-            validate_success_criteria(criteria)?;
+            errors.extend(validate_success_criteria(criteria));
         }
         
-        // Validate implementation details if provided
         if let Some(ref details) = self.implementation_details {
-            validate_implementation_details(details)?;
+            errors.extend(validate_implementation_details(details).err());
         }
         
-        // Validate custom properties if provided
         if let Some(ref properties) = self.custom_properties {
-            validate_custom_properties(properties)?;
+            errors.extend(validate_custom_properties(properties));
         }
         
-        Ok(())
+        into_result(errors)
     }
 }
 
 /// Validate task ID format and length
-fn validate_task_id(id: &str) -> TaskServiceResult<()> {
+fn validate_task_id(id: &str) -> Result<(), FieldError> {
     if id.is_empty() {
-        return Err(TaskServiceError::InvalidInput {
-            field: "id".to_string(),
-            message: "Task ID cannot be empty".to_string(),
-        });
+        return Err(FieldError::new("id", "required", "Task ID cannot be empty"));
     }
     
     if id.len() > MAX_TASK_ID_LENGTH {
-        return Err(TaskServiceError::InvalidInput {
-            field: "id".to_string(),
-            message: format!("Task ID cannot exceed {} characters", MAX_TASK_ID_LENGTH),
-        });
+        return Err(FieldError::new("id", "too_long", format!("Task ID cannot exceed {} characters", MAX_TASK_ID_LENGTH))
+            .with_param("max", MAX_TASK_ID_LENGTH));
     }
     
     // Check for valid characters (alphanumeric, hyphens, underscores)
     if !id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-        return Err(TaskServiceError::InvalidInput {
-            field: "id".to_string(),
-            message: "Task ID can only contain alphanumeric characters, hyphens, and underscores".to_string(),
-        });
+        return Err(FieldError::new(
+            "id",
+            "invalid_characters",
+            "Task ID can only contain alphanumeric characters, hyphens, and underscores",
+        ));
     }
     
     Ok(())
 }
 
 /// Validate task name
-fn validate_task_name(name: &str) -> TaskServiceResult<()> {
+fn validate_task_name(name: &str) -> Result<(), FieldError> {
     if name.trim().is_empty() {
-        return Err(TaskServiceError::InvalidInput {
-            field: "name".to_string(),
-            message: "Task name cannot be empty".to_string(),
-        });
+        return Err(FieldError::new("name", "required", "Task name cannot be empty"));
     }
     
     if name.len() > MAX_TASK_NAME_LENGTH {
-        return Err(TaskServiceError::InvalidInput {
-            field: "name".to_string(),
-            message: format!("Task name cannot exceed {} characters", MAX_TASK_NAME_LENGTH),
-        });
+        return Err(FieldError::new("name", "too_long", format!("Task name cannot exceed {} characters", MAX_TASK_NAME_LENGTH))
+            .with_param("max", MAX_TASK_NAME_LENGTH));
     }
     
     // Check for potentially dangerous characters
     if name.contains('\0') || name.contains('\x1f') {
-        return Err(TaskServiceError::InvalidInput {
-            field: "name".to_string(),
-            message: "Task name contains invalid characters".to_string(),
-        });
+        return Err(FieldError::new("name", "invalid_characters", "Task name contains invalid characters"));
     }
     
     Ok(())
 }
 
 /// Validate task description
-fn validate_description(description: &str) -> TaskServiceResult<()> {
+fn validate_description(description: &str) -> Result<(), FieldError> {
     if description.len() > MAX_DESCRIPTION_LENGTH {
+        return Err(FieldError::new("description", "too_long", format!("Description cannot exceed {} characters", MAX_DESCRIPTION_LENGTH))
+            .with_param("max", MAX_DESCRIPTION_LENGTH));
+    }
+    
+    Ok(())
+}
+
+/// Validate the `q` text filter of a task listing
+pub fn validate_search_text(text: &str) -> Result<(), FieldError> {
+    if text.trim().chars().count() < MIN_SEARCH_TEXT_LENGTH {
+        return Err(FieldError::new("q", "too_short", format!("Search text must be at least {} characters", MIN_SEARCH_TEXT_LENGTH))
+            .with_param("min", MIN_SEARCH_TEXT_LENGTH));
+    }
+    
+    Ok(())
+}
+
+/// Validate a comment body
+pub fn validate_comment_body(body: &str) -> TaskServiceResult<()> {
+    if body.trim().is_empty() {
+        return Err(TaskServiceError::InvalidInput {
+            field: "body".to_string(),
+            message: "Comment body cannot be empty".to_string(),
+        });
+    }
+    
+    if body.chars().count() > MAX_COMMENT_BODY_LENGTH {
         return Err(TaskServiceError::InvalidInput {
-            field: "description".to_string(),
-            message: format!("Description cannot exceed {} characters", MAX_DESCRIPTION_LENGTH),
+            field: "body".to_string(),
+            message: format!("Comment body cannot exceed {} characters", MAX_COMMENT_BODY_LENGTH),
         });
     }
     
@@ -169,13 +253,16 @@ fn validate_description(description: &str) -> TaskServiceResult<()> {
 }
 
 /// Validate dates are logical
-fn validate_dates(due_date: Option<DateTime<Utc>>, estimated_date: Option<DateTime<Utc>>) -> TaskServiceResult<()> {
+fn validate_dates(due_date: Option<DateTime<Utc>>, estimated_date: Option<DateTime<Utc>>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    
     if let (Some(due), Some(estimated)) = (due_date, estimated_date) {
         if estimated > due {
-            return Err(TaskServiceError::InvalidInput {
-                field: "estimated_date".to_string(),
-                message: "Estimated completion date cannot be after due date".to_string(),
-            });
+            errors.push(FieldError::new(
+                "estimated_date",
+                "after_due_date",
+                "Estimated completion date cannot be after due date",
+            ).with_param("due_date", due.to_rfc3339()));
         }
     }
     
@@ -184,163 +271,181 @@ fn validate_dates(due_date: Option<DateTime<Utc>>, estimated_date: Option<DateTi
     let max_future = now + chrono::Duration::days(365 * 10); // 10 years
     let min_past = now - chrono::Duration::days(365 * 5); // 5 years
     
-    if let Some(due) = due_date {
-        if due > max_future {
-            return Err(TaskServiceError::InvalidInput {
-                field: "due_date".to_string(),
-                message: "Due date cannot be more than 10 years in the future".to_string(),
-            });
-        }
-        if due < min_past {
-            return Err(TaskServiceError::InvalidInput {
-                field: "due_date".to_string(),
-                message: "Due date cannot be more than 5 years in the past".to_string(),
-            });
+    for (field, label, date) in [("due_date", "Due date", due_date), ("estimated_date", "Estimated date", estimated_date)] {
+        let Some(date) = date else { continue };
+        if date > max_future {
+            errors.push(FieldError::new(field, "too_far_in_future", format!("{} cannot be more than 10 years in the future", label))
+                .with_param("max_years_ahead", 10));
+        } else if date < min_past {
+            errors.push(FieldError::new(field, "too_far_in_past", format!("{} cannot be more than 5 years in the past", label))
+                .with_param("max_years_ago", 5));
         }
     }
     
-    if let Some(estimated) = estimated_date {
-        if estimated > max_future {
-            return Err(TaskServiceError::InvalidInput {
-                field: "estimated_date".to_string(),
-                message: "Estimated date cannot be more than 10 years in the future".to_string(),
-            });
-        }
-        if estimated < min_past {
-            return Err(TaskServiceError::InvalidInput {
-                field: "estimated_date".to_string(),
-                message: "Estimated date cannot be more than 5 years in the past".to_string(),
-            });
-        }
-    }
-    
-    Ok(())
+    errors
 }
 
 /// Validate success criteria
-fn validate_success_criteria(criteria: &[crate::domain::SuccessCriterion]) -> TaskServiceResult<()> {
+fn validate_success_criteria(criteria: &[crate::domain::SuccessCriterion]) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    
     if criteria.len() > MAX_SUCCESS_CRITERIA {
-        return Err(TaskServiceError::InvalidInput {
-            field: "success_criteria".to_string(),
-            message: format!("Cannot have more than {} success criteria", MAX_SUCCESS_CRITERIA),
-        });
+        errors.push(FieldError::new(
+            "success_criteria",
+            "too_many",
+            format!("Cannot have more than {} success criteria", MAX_SUCCESS_CRITERIA),
+        ).with_param("max", MAX_SUCCESS_CRITERIA));
     }
     
     for (i, criterion) in criteria.iter().enumerate() {
+        let field = format!("success_criteria[{}].criterion", i);
         if criterion.criterion.trim().is_empty() {
-            return Err(TaskServiceError::InvalidInput {
-                field: format!("success_criteria[{}].criterion", i),
-                message: "Success criterion cannot be empty".to_string(),
-            });
-        }
-        
-        if criterion.criterion.len() > 500 {
-            return Err(TaskServiceError::InvalidInput {
-                field: format!("success_criteria[{}].criterion", i),
-                message: "Success criterion cannot exceed 500 characters".to_string(),
-            });
+            errors.push(FieldError::new(field, "required", "Success criterion cannot be empty"));
+        } else if criterion.criterion.len() > 500 {
+            errors.push(FieldError::new(field, "too_long", "Success criterion cannot exceed 500 characters")
+                .with_param("max", 500));
         }
         
         if criterion.verification_method.trim().is_empty() {
-            return Err(TaskServiceError::InvalidInput {
-                field: format!("success_criteria[{}].verification_method", i),
-                message: "Verification method cannot be empty".to_string(),
-            });
+            errors.push(FieldError::new(
+                format!("success_criteria[{}].verification_method", i),
+                "required",
+                "Verification method cannot be empty",
+            ));
         }
     }
     
-    Ok(())
+    errors
 }
 
 /// Validate implementation details
-fn validate_implementation_details(details: &str) -> TaskServiceResult<()> {
+fn validate_implementation_details(details: &str) -> Result<(), FieldError> {
     if details.len() > MAX_DESCRIPTION_LENGTH {
-        return Err(TaskServiceError::InvalidInput {
-            field: "implementation_details".to_string(),
-            message: format!("Implementation details cannot exceed {} characters", MAX_DESCRIPTION_LENGTH),
-        });
+        return Err(FieldError::new(
+            "implementation_details",
+            "too_long",
+            format!("Implementation details cannot exceed {} characters", MAX_DESCRIPTION_LENGTH),
+        ).with_param("max", MAX_DESCRIPTION_LENGTH));
     }
     
     Ok(())
 }
 
+/// Validate custom properties against the schema of the task's context
+///
+/// Required fields must be present and every declared field must have the
+/// declared type. Properties the schema does not mention are left alone, as
+/// are tasks whose context has no schema. Only the first problem is returned.
+pub fn validate_context_custom_properties(
+    schemas: &[ContextSchema],
+    context: TaskContext,
+    properties: &HashMap<String, serde_json::Value>,
+) -> TaskServiceResult<()> {
+    match context_custom_property_errors(schemas, context, properties).into_iter().next() {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
+    }
+}
+
+/// Every way the custom properties break the schema of the task's context
+fn context_custom_property_errors(
+    schemas: &[ContextSchema],
+    context: TaskContext,
+    properties: &HashMap<String, serde_json::Value>,
+) -> Vec<FieldError> {
+    let Some(schema) = schemas.iter().find(|schema| schema.context == context) else {
+        return Vec::new();
+    };
+    let mut errors = Vec::new();
+    
+    for field in &schema.required_custom_fields {
+        if !properties.contains_key(&field.name) {
+            errors.push(FieldError::new(
+                format!("custom_properties.{}", field.name),
+                "required",
+                format!("Custom property '{}' is required for {:?} tasks", field.name, schema.context),
+            ));
+        }
+    }
+    
+    for (key, value) in properties {
+        if let Some(field) = schema.field(key) {
+            if !field.field_type.accepts(value) {
+                errors.push(FieldError::new(
+                    format!("custom_properties.{}", key),
+                    "invalid_type",
+                    format!("Custom property '{}' must be of type {:?}", key, field.field_type),
+                ).with_param("expected_type", format!("{:?}", field.field_type)));
+            }
+        }
+    }
+    
+    errors
+}
+
 /// Validate custom properties
-fn validate_custom_properties(properties: &HashMap<String, serde_json::Value>) -> TaskServiceResult<()> {
+fn validate_custom_properties(properties: &HashMap<String, serde_json::Value>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    
     if properties.len() > MAX_CUSTOM_PROPERTIES {
-        return Err(TaskServiceError::InvalidInput {
-            field: "custom_properties".to_string(),
-            message: format!("Cannot have more than {} custom properties", MAX_CUSTOM_PROPERTIES),
-        });
+        errors.push(FieldError::new(
+            "custom_properties",
+            "too_many",
+            format!("Cannot have more than {} custom properties", MAX_CUSTOM_PROPERTIES),
+        ).with_param("max", MAX_CUSTOM_PROPERTIES));
     }
     
     for (key, value) in properties {
         // Validate key
         if key.trim().is_empty() {
-            return Err(TaskServiceError::InvalidInput {
-                field: "custom_properties".to_string(),
-                message: "Custom property keys cannot be empty".to_string(),
-            });
+            errors.push(FieldError::new("custom_properties", "empty_key", "Custom property keys cannot be empty"));
+            continue;
         }
         
         if key.len() > 100 {
-            return Err(TaskServiceError::InvalidInput {
-                field: "custom_properties".to_string(),
-                message: "Custom property keys cannot exceed 100 characters".to_string(),
-            });
+            errors.push(FieldError::new("custom_properties", "key_too_long", "Custom property keys cannot exceed 100 characters")
+                .with_param("max", 100));
+            continue;
         }
         
+        let field = format!("custom_properties.{}", key);
         // Validate value size (prevent excessively large JSON values)
-        let value_str = serde_json::to_string(value).map_err(|_| {
-            TaskServiceError::InvalidInput {
-                field: "custom_properties".to_string(),
-                message: "Invalid JSON value in custom properties".to_string(),
+        match serde_json::to_string(value) {
+            Ok(value_str) if value_str.len() > 10000 => {
+                errors.push(FieldError::new(field, "too_large", "Custom property values cannot exceed 10KB when serialized")
+                    .with_param("max_bytes", 10000));
             }
-        })?;
-        
-        if value_str.len() > 10000 {
-            return Err(TaskServiceError::InvalidInput {
-                field: "custom_properties".to_string(),
-                message: "Custom property values cannot exceed 10KB when serialized".to_string(),
-            });
+            Ok(_) => {}
+            Err(_) => errors.push(FieldError::new(field, "invalid_json", "Invalid JSON value in custom properties")),
         }
     }
     
-    Ok(())
+    errors
 }
 
 /// Validate user ID format
-fn validate_user_id(user_id: &str) -> TaskServiceResult<()> {
+fn validate_user_id(user_id: &str) -> Result<(), FieldError> {
     if user_id.trim().is_empty() {
-        return Err(TaskServiceError::InvalidInput {
-            field: "assigned_user_id".to_string(),
-            message: "User ID cannot be empty".to_string(),
-        });
+        return Err(FieldError::new("assigned_user_id", "required", "User ID cannot be empty"));
     }
     
     if user_id.len() > 100 {
-        return Err(TaskServiceError::InvalidInput {
-            field: "assigned_user_id".to_string(),
-            message: "User ID cannot exceed 100 characters".to_string(),
-        });
+        return Err(FieldError::new("assigned_user_id", "too_long", "User ID cannot exceed 100 characters")
+            .with_param("max", 100));
     }
     
     Ok(())
 }
 
 /// Validate project ID format
-fn validate_project_id(project_id: &str) -> TaskServiceResult<()> {
+fn validate_project_id(project_id: &str) -> Result<(), FieldError> {
     if project_id.trim().is_empty() {
-        return Err(TaskServiceError::InvalidInput {
-            field: "project_id".to_string(),
-            message: "Project ID cannot be empty".to_string(),
-        });
+        return Err(FieldError::new("project_id", "required", "Project ID cannot be empty"));
     }
     
     if project_id.len() > 100 {
-        return Err(TaskServiceError::InvalidInput {
-            field: "project_id".to_string(),
-            message: "Project ID cannot exceed 100 characters".to_string(),
-        });
+        return Err(FieldError::new("project_id", "too_long", "Project ID cannot exceed 100 characters")
+            .with_param("max", 100));
     }
     
     Ok(())
@@ -505,7 +610,7 @@ pub fn validate_assignment_request(task_id: &str, user_id: &str, role: &str) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{TaskContext, TaskSource, TaskVisibility};
+    use crate::domain::{FieldSchema, FieldType, TaskSource, TaskVisibility};
     
     #[test]
     fn test_valid_create_task_request() {
@@ -527,6 +632,7 @@ mod tests {
             custom_properties: HashMap::new(),
             assigned_user_id: None,
             project_id: None,
+            created_by: None,
         };
         
         assert!(request.validate().is_ok());
@@ -552,6 +658,7 @@ mod tests {
             custom_properties: HashMap::new(),
             assigned_user_id: None,
             project_id: None,
+            created_by: None,
         };
         
         assert!(request.validate().is_err());
@@ -561,6 +668,31 @@ mod tests {
         assert!(request.validate().is_err());
     }
     
+    #[test]
+    fn test_validation_collects_every_field_error() {
+        let request = UpdateTaskRequest {
+            name: Some("".to_string()),
+            description: Some("a".repeat(MAX_DESCRIPTION_LENGTH + 1)),
+            priority: None,
+            complexity: None,
+            due_date: Some(Utc::now() + chrono::Duration::days(5)),
+            estimated_date: Some(Utc::now() + chrono::Duration::days(10)),
+            implementation_details: None,
+            success_criteria: None,
+            test_strategy: None,
+            visibility: None,
+            custom_properties: None,
+        };
+        
+        let Err(TaskServiceError::ValidationFailed { errors }) = request.validate() else {
+            panic!("expected a validation failure");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["name", "description", "estimated_date"]);
+        assert_eq!(errors[1].code, "too_long");
+        assert_eq!(errors[1].params["max"], serde_json::json!(MAX_DESCRIPTION_LENGTH));
+    }
+    
     #[test]
     fn test_invalid_dates() {
         let request = CreateTaskRequest {
@@ -581,6 +713,7 @@ mod tests {
             custom_properties: HashMap::new(),
             assigned_user_id: None,
             project_id: None,
+            created_by: None,
         };
         
         assert!(request.validate().is_err());
@@ -704,6 +837,52 @@ mod tests {
         assert!(validate_bulk_status_transition(&invalid_transitions).is_err());
     }
     
+    #[test]
+    fn test_context_custom_properties_follow_schema() {
+        let schemas = vec![ContextSchema {
+            context: TaskContext::Work,
+            required_custom_fields: vec![
+                FieldSchema::new("ticket_number", FieldType::String, "Tracker reference"),
+            ],
+            optional_custom_fields: vec![
+                FieldSchema::new("severity", FieldType::Enum(vec!["low".to_string(), "high".to_string()]), "Impact"),
+            ],
+        }];
+        let mut properties = HashMap::new();
+        
+        let result = validate_context_custom_properties(&schemas, TaskContext::Work, &properties);
+        assert!(matches!(result, Err(TaskServiceError::InvalidInput { ref field, .. }) if field == "custom_properties.ticket_number"));
+        
+        properties.insert("ticket_number".to_string(), serde_json::json!("OPS-42"));
+        properties.insert("severity".to_string(), serde_json::json!("medium"));
+        assert!(validate_context_custom_properties(&schemas, TaskContext::Work, &properties).is_err());
+        
+        properties.insert("severity".to_string(), serde_json::json!("high"));
+        properties.insert("unrelated".to_string(), serde_json::json!(3));
+        assert!(validate_context_custom_properties(&schemas, TaskContext::Work, &properties).is_ok());
+        
+        // Contexts without a schema accept anything
+        assert!(validate_context_custom_properties(&schemas, TaskContext::Personal, &HashMap::new()).is_ok());
+    }
+    
+    #[test]
+    fn test_comment_body_limits() {
+        assert!(validate_comment_body("Looks good").is_ok());
+        assert!(validate_comment_body("  ").is_err());
+        // The limit counts characters, not bytes
+        assert!(validate_comment_body(&"é".repeat(MAX_COMMENT_BODY_LENGTH)).is_ok());
+        assert!(validate_comment_body(&"a".repeat(MAX_COMMENT_BODY_LENGTH + 1)).is_err());
+    }
+    
+    #[test]
+    fn test_search_text_minimum_length() {
+        assert!(validate_search_text("ui").is_ok());
+        let error = validate_search_text(" a ").unwrap_err();
+        assert_eq!(error.field, "q");
+        assert_eq!(error.code, "too_short");
+        assert_eq!(error.params["min"], MIN_SEARCH_TEXT_LENGTH);
+    }
+    
     #[test]
     fn test_suggested_next_statuses() {
         use crate::domain::TaskStatus;
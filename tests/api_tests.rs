@@ -302,4 +302,34 @@ async fn test_rate_limiting() {
         let response = server.get("/health").await;
         response.assert_status_ok();
     }
-}
\ No newline at end of file
+}
+/// Test request body size limits, including the per-route overrides
+#[tokio::test]
+async fn test_request_body_limits() {
+    use tyl_task_service::middleware::limits::{
+        ATTACHMENT_PRESIGN_MAX_BODY_BYTES, COMMENT_MAX_BODY_BYTES, DEFAULT_MAX_BODY_BYTES,
+    };
+
+    let server = create_test_server().await;
+    let padded = |bytes: usize| json!({ "name": "Too large", "description": "x".repeat(bytes) });
+
+    let response = server.post("/api/v1/tasks").json(&padded(DEFAULT_MAX_BODY_BYTES)).await;
+    response.assert_status(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.json::<serde_json::Value>(), json!({ "error": "request_too_large", "max_bytes": DEFAULT_MAX_BODY_BYTES }));
+
+    let response = server.post("/api/v1/tasks/some-task/attachments/presign")
+        .json(&padded(ATTACHMENT_PRESIGN_MAX_BODY_BYTES))
+        .await;
+    response.assert_status(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.json::<serde_json::Value>()["max_bytes"], ATTACHMENT_PRESIGN_MAX_BODY_BYTES);
+
+    let response = server.post("/api/v1/tasks/some-task/comments").json(&padded(COMMENT_MAX_BODY_BYTES)).await;
+    response.assert_status(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.json::<serde_json::Value>()["max_bytes"], COMMENT_MAX_BODY_BYTES);
+
+    // Bulk create accepts more than the default
+    let response = server.post("/api/v1/tasks/bulk")
+        .json(&json!({ "tasks": [], "padding": "x".repeat(DEFAULT_MAX_BODY_BYTES) }))
+        .await;
+    assert_ne!(response.status_code(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -112,6 +112,8 @@ async fn test_task_crud_operations() -> TylResult<()> {
                 criterion: "All tests pass".to_string(),
                 measurable: true,
                 verification_method: "Automated test suite".to_string(),
+                is_completed: false,
+                completed_at: None,
             }
         ],
         test_strategy: Some("Unit and integration tests".to_string()),
@@ -125,6 +127,7 @@ async fn test_task_crud_operations() -> TylResult<()> {
         },
         assigned_user_id: None,
         project_id: None,
+        created_by: None,
     };
     
     // Create the task
@@ -157,7 +160,7 @@ async fn test_task_crud_operations() -> TylResult<()> {
         custom_properties: None,
     };
     
-    let updated_task = service.update_task(&created_task.id, update_request).await?;
+    let updated_task = service.update_task(&created_task.id, update_request, None).await?;
     println!("✓ Updated task: {}", updated_task.id);
     assert_eq!(updated_task.name, "Updated Integration Test Task");
     assert_eq!(updated_task.priority, TaskPriority::Critical);
@@ -209,6 +212,7 @@ async fn test_task_status_transitions() -> TylResult<()> {
         custom_properties: HashMap::new(),
         assigned_user_id: None,
         project_id: None,
+        created_by: None,
     };
     
     let task = service.create_task(create_request).await?;
@@ -216,24 +220,24 @@ async fn test_task_status_transitions() -> TylResult<()> {
     println!("✓ Created task in Backlog status");
     
     // Valid transition: Backlog -> Ready
-    let ready_task = service.transition_task_status(&task.id, TaskStatus::Ready).await?;
+    let ready_task = service.transition_task_status(&task.id, TaskStatus::Ready, None).await?;
     assert_eq!(ready_task.status, TaskStatus::Ready);
     println!("✓ Transitioned to Ready status");
     
     // Valid transition: Ready -> InProgress
-    let in_progress_task = service.transition_task_status(&task.id, TaskStatus::InProgress).await?;
+    let in_progress_task = service.transition_task_status(&task.id, TaskStatus::InProgress, None).await?;
     assert_eq!(in_progress_task.status, TaskStatus::InProgress);
     assert!(in_progress_task.started_at.is_some());
     println!("✓ Transitioned to InProgress status with started_at timestamp");
     
     // Valid transition: InProgress -> Done
-    let done_task = service.transition_task_status(&task.id, TaskStatus::Done).await?;
+    let done_task = service.transition_task_status(&task.id, TaskStatus::Done, None).await?;
     assert_eq!(done_task.status, TaskStatus::Done);
     assert!(done_task.completed_at.is_some());
     println!("✓ Transitioned to Done status with completed_at timestamp");
     
     // Test invalid transition (should fail)
-    let invalid_transition = service.transition_task_status(&task.id, TaskStatus::Backlog).await;
+    let invalid_transition = service.transition_task_status(&task.id, TaskStatus::Backlog, None).await;
     assert!(invalid_transition.is_err());
     println!("✓ Invalid transition correctly rejected");
     
@@ -266,6 +270,7 @@ async fn test_task_dependencies() -> TylResult<()> {
         custom_properties: HashMap::new(),
         assigned_user_id: None,
         project_id: None,
+        created_by: None,
     };
     
     let task2_request = CreateTaskRequest {
@@ -286,6 +291,7 @@ async fn test_task_dependencies() -> TylResult<()> {
         custom_properties: HashMap::new(),
         assigned_user_id: None,
         project_id: None,
+        created_by: None,
     };
     
     let task1 = service.create_task(task1_request).await?;